@@ -0,0 +1,217 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use clap::{Args, ValueEnum};
+use stellar_database::{CfgOptions, Config, State};
+use stellar_diagnostics::{DiagnosticsEmitter, SeverityOverride};
+use stellar_driver::{CompileError, Pipeline, Session};
+use stellar_filesystem::file_utils::make_unique_file;
+use stellar_interner::IdentifierId;
+
+use crate::log::log_info;
+
+/// An intermediate representation, that can be emitted by `stellar check --emit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Emit {
+    /// Typed function bodies.
+    Thir,
+}
+
+/// Options of `stellar check`, that configure the compiler.
+#[derive(Debug, Clone, Args)]
+pub struct ConfigArgs {
+    #[arg(
+        long = "cfg",
+        value_name = "NAME",
+        help = "Enables a flag for `#[cfg(...)]` attributes"
+    )]
+    cfg: Vec<String>,
+    #[arg(
+        long,
+        help = "Replaces invalid UTF-8 in source files with `U+FFFD` instead of failing"
+    )]
+    lenient_utf8: bool,
+    #[arg(
+        long,
+        value_name = "BYTES",
+        default_value_t = Config::DEFAULT_MAX_FILE_SIZE,
+        help = "Maximum size of a source file"
+    )]
+    max_file_size: usize,
+    #[arg(
+        long,
+        value_name = "COUNT",
+        default_value_t = Config::DEFAULT_MAX_TOKEN_COUNT,
+        help = "Maximum number of tokens in a source file"
+    )]
+    max_token_count: usize,
+    #[arg(
+        long,
+        value_name = "BYTES",
+        default_value_t = Config::DEFAULT_MAX_IDENTIFIER_LENGTH,
+        help = "Maximum length of an identifier"
+    )]
+    max_identifier_length: usize,
+    #[arg(
+        long,
+        help = "Stops checking further files and phases after the first error"
+    )]
+    fail_fast: bool,
+    #[arg(
+        long,
+        value_name = "CODE",
+        help = "Reports warnings with a given code, e.g. `W004`, as errors"
+    )]
+    deny: Vec<String>,
+    #[arg(
+        long,
+        value_name = "CODE",
+        help = "Doesn't report warnings with a given code, e.g. `W004`"
+    )]
+    allow: Vec<String>,
+}
+
+impl ConfigArgs {
+    /// Returns the compiler configuration.
+    fn config(&self) -> Config {
+        let cfg_options = self
+            .cfg
+            .iter()
+            .map(IdentifierId::from)
+            .collect::<CfgOptions>();
+
+        let config = Config::new()
+            .with_cfg_options(cfg_options)
+            .with_lenient_utf8(self.lenient_utf8)
+            .with_max_file_size(self.max_file_size)
+            .with_max_token_count(self.max_token_count)
+            .with_max_identifier_length(self.max_identifier_length)
+            .with_fail_fast(self.fail_fast);
+
+        // `--deny` wins, if a code is both allowed and denied.
+        let config = self.allow.iter().fold(config, |config, code| {
+            config.with_severity_override(code.as_str(), SeverityOverride::Allow)
+        });
+
+        self.deny.iter().fold(config, |config, code| {
+            config.with_severity_override(code.as_str(), SeverityOverride::Deny)
+        })
+    }
+}
+
+pub fn command(
+    directory: &str,
+    dependencies: &[(String, PathBuf)],
+    emit: Option<Emit>,
+    config: &ConfigArgs,
+    lib: bool,
+    diagnostics_emitter: DiagnosticsEmitter,
+) -> Result<(), CompileError> {
+    let state = State::new().with_config(config.config());
+    let mut checked = Pipeline::new(state)
+        .with_diagnostics_emitter(diagnostics_emitter)
+        .library(lib)
+        .parse_package(Path::new(directory), dependencies)?
+        .lower()
+        .collect_definitions()
+        .check();
+
+    checked.finish()?;
+
+    log_info("Checked", format!("`{directory}`"));
+
+    if emit == Some(Emit::Thir) {
+        emit_thir(checked.session());
+    }
+
+    Ok(())
+}
+
+/// Checks all members of a workspace, reporting diagnostics of every member
+/// separately, and prints whether every member passed.
+pub fn workspace_command(
+    directory: &str,
+    emit: Option<Emit>,
+    config: &ConfigArgs,
+    lib: bool,
+    diagnostics_emitter: DiagnosticsEmitter,
+) -> Result<(), CompileError> {
+    let state = State::new().with_config(config.config());
+    let mut checked = Pipeline::new(state)
+        .with_diagnostics_emitter(diagnostics_emitter)
+        .library(lib)
+        .parse_workspace(Path::new(directory))?
+        .lower()
+        .collect_definitions()
+        .check();
+
+    // `finish_member()` borrows the pipeline mutably, so members are
+    // accessed by index.
+    let reports = (0..checked.members().len())
+        .map(|index| {
+            let package = checked.members()[index].package;
+            checked.finish_member(package)
+        })
+        .collect::<Vec<_>>();
+
+    for report in &reports {
+        if report.is_ok() {
+            log_info("Passed", format!("`{}`", report.name));
+        } else {
+            log_info(
+                "Failed",
+                format!(
+                    "`{}` ({} error{})",
+                    report.name,
+                    report.error_count,
+                    if report.error_count == 1 { "" } else { "s" }
+                ),
+            );
+        }
+    }
+
+    let failed = reports.iter().filter(|report| !report.is_ok()).count();
+    log_info(
+        "Checked",
+        format!(
+            "workspace `{directory}`: {} passed, {failed} failed",
+            reports.len() - failed
+        ),
+    );
+
+    checked.finish()?;
+
+    if emit == Some(Emit::Thir) {
+        emit_thir(checked.session());
+    }
+
+    Ok(())
+}
+
+/// Serializes typed bodies of all functions into `thir.json`.
+fn emit_thir(session: &Session) {
+    let mut bodies = session.thir().values().collect::<Vec<_>>();
+    bodies.sort_by_key(|body| body.function);
+
+    let thir_string = serde_json::to_string(&bodies).unwrap();
+
+    let (filename, file) = make_unique_file("thir", "json");
+    file.expect("Cannot create `thir (n).json` file")
+        .write_all(thir_string.as_bytes())
+        .unwrap_or_else(|_| panic!("Cannot write to file {filename}"));
+
+    log_info("Emitted", format!("THIR in `{filename}`"));
+}
+
+/// Parses a dependency passed as `--dep name=path`.
+pub fn parse_dependency(dependency: &str) -> Result<(String, PathBuf), String> {
+    let Some((name, path)) = dependency.split_once('=') else {
+        return Err(format!(
+            "expected a dependency in the form `name=path`, got `{dependency}`"
+        ));
+    };
+
+    Ok((name.to_owned(), PathBuf::from(path)))
+}
@@ -0,0 +1,54 @@
+use std::{fs, path::Path};
+
+use stellar_diagnostics::DiagnosticsEmitter;
+use stellar_doc::{generate_documentation, Options};
+use stellar_driver::{CompileError, Session};
+
+use crate::log::log_info;
+
+pub fn command(
+    directory: &str,
+    output: &str,
+    document_private_items: bool,
+    diagnostics_emitter: DiagnosticsEmitter,
+) -> Result<(), CompileError> {
+    let mut session = Session::new().with_diagnostics_emitter(diagnostics_emitter);
+
+    let package = session.parse_package(Path::new(directory))?;
+    session.finish_parsing()?;
+
+    session.lower();
+    session.collect_definitions();
+    session.resolve_imports();
+    session.finish_checking()?;
+
+    let pages = generate_documentation(
+        session.state(),
+        session.hir(),
+        package,
+        Options {
+            document_private_items,
+        },
+    );
+
+    fs::create_dir_all(output).map_err(|source| CompileError::Io {
+        path: output.into(),
+        source,
+    })?;
+
+    for page in &pages {
+        let filepath = Path::new(output).join(&page.filename);
+
+        fs::write(&filepath, &page.content).map_err(|source| CompileError::Io {
+            path: filepath,
+            source,
+        })?;
+    }
+
+    log_info(
+        "Documented",
+        format!("{} modules in `{output}`", pages.len()),
+    );
+
+    Ok(())
+}
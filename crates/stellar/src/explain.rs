@@ -0,0 +1,29 @@
+use std::process::exit;
+
+use stellar_driver::explain::explain;
+
+use crate::log::log_error;
+
+pub fn command(code: &str) {
+    let Some(info) = explain(code) else {
+        log_error(format!("no diagnostic has the code `{code}`"));
+        exit(1);
+    };
+
+    let severity = format!("{:?}", info.severity).to_lowercase();
+
+    match info.phase() {
+        Some(phase) => println!("{}: {} ({severity}, {phase})", info.code, info.name),
+        None => println!("{}: {} ({severity})", info.code, info.name),
+    }
+    println!();
+
+    // Link definitions of doc comments, e.g. "[`Config`]: stellar_database::Config",
+    // only make sense in rustdoc.
+    for line in info
+        .explanation_lines()
+        .filter(|line| !(line.starts_with('[') && line.contains("]: ")))
+    {
+        println!("{line}");
+    }
+}
@@ -1,6 +1,45 @@
 use std::io::Write;
 
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// Environment variable with a tracing filter, that overrides `--verbose`,
+/// e.g. `STELLAR_LOG=stellar_typechecker=trace`.
+const LOG_FILTER_VARIABLE: &str = "STELLAR_LOG";
+
+/// Installs a subscriber, that prints compiler stages with their timings into
+/// stderr, when they finish.
+///
+/// Status messages of [`log_info()`] are printed into stdout regardless of the
+/// verbosity, so they are not duplicated as tracing events.
+pub fn init_tracing(verbosity: u8) {
+    tracing_subscriber::fmt()
+        .compact()
+        .with_writer(std::io::stderr)
+        .with_env_filter(tracing_filter(
+            verbosity,
+            std::env::var(LOG_FILTER_VARIABLE).ok(),
+        ))
+        .with_span_events(FmtSpan::CLOSE)
+        .without_time()
+        .with_ansi(false)
+        .with_target(false)
+        .init();
+}
+
+/// Returns the tracing filter: `STELLAR_LOG`, if it is set, or the level
+/// corresponding to the number of `-v` flags.
+fn tracing_filter(verbosity: u8, filter: Option<String>) -> String {
+    filter.unwrap_or_else(|| {
+        match verbosity {
+            0 => "off",
+            1 => "info",
+            2 => "debug",
+            _ => "trace",
+        }
+        .to_owned()
+    })
+}
 
 #[allow(dead_code)]
 pub fn log_info(prefix: impl AsRef<str>, message: impl AsRef<str>) {
@@ -16,7 +55,7 @@ pub fn log_error(message: impl AsRef<str>) {
         .unwrap();
     write!(&mut stdout, "error: ").unwrap();
     stdout.set_color(ColorSpec::new().set_fg(None)).unwrap();
-    write!(&mut stdout, "{}", message.as_ref()).unwrap();
+    writeln!(&mut stdout, "{}", message.as_ref()).unwrap();
 }
 
 #[allow(dead_code)]
@@ -38,3 +77,27 @@ fn log_with_prefix(prefix: impl AsRef<str>, message: impl AsRef<str>) {
     writeln!(&mut stdout, "{}", message.as_ref()).unwrap();
     stdout.set_color(ColorSpec::new().set_fg(None)).unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::EnvFilter;
+
+    use super::tracing_filter;
+
+    #[test]
+    fn verbosity_levels() {
+        assert_eq!(tracing_filter(0, None), "off");
+        assert_eq!(tracing_filter(1, None), "info");
+        assert_eq!(tracing_filter(2, None), "debug");
+        assert_eq!(tracing_filter(3, None), "trace");
+        assert_eq!(tracing_filter(7, None), "trace");
+    }
+
+    #[test]
+    fn environment_filter_overrides_verbosity() {
+        let filter = tracing_filter(1, Some("stellar_typechecker=trace".to_owned()));
+
+        assert_eq!(filter, "stellar_typechecker=trace");
+        assert!(EnvFilter::try_new(filter).is_ok());
+    }
+}
@@ -3,52 +3,69 @@
 use std::io::Write;
 use std::time::Instant;
 
-use stellar_ast_lowering::LowerToHir;
 use stellar_database::State;
 use stellar_diagnostics::DiagnosticsEmitter;
+use stellar_driver::{CompileError, Phase, Pipeline};
 use stellar_filesystem::file_utils::make_unique_file;
-use stellar_interner::{PathId, DUMMY_IDENTIFIER_ID};
-use stellar_parser::read_and_parse_module;
+use stellar_interner::{IdentifierInterner, PathId};
 
-use crate::log::{log_error, log_info};
+use crate::log::log_info;
 
-pub fn command(filepath: &str) {
-    let mut diagnostics_emitter = DiagnosticsEmitter::new();
-    let mut state = State::new();
-    let filepath = PathId::from(filepath);
+pub fn command(
+    filepath: &str,
+    optimize_hir: bool,
+    stats: bool,
+    diagnostics_emitter: DiagnosticsEmitter,
+) -> Result<(), CompileError> {
+    let lowered = Pipeline::new(State::new())
+        .with_diagnostics_emitter(diagnostics_emitter)
+        .on_phase(|event| {
+            let prefix = match event.phase {
+                Phase::Parse => "Parsed",
+                Phase::Lower => "Lowered",
+                Phase::FoldConstants => "Optimized",
+                _ => return,
+            };
 
-    let mut now = Instant::now();
+            log_info(prefix, format!("in {}s", event.duration.as_secs_f64()));
+        })
+        .parse(&[PathId::from(filepath)])?
+        .lower();
 
-    match read_and_parse_module(&mut state, DUMMY_IDENTIFIER_ID, filepath) {
-        Err(..) => {
-            log_error(format!("cannot read the file {filepath}"));
-        }
-        Ok(ast) => {
-            log_info("Parsed", format!("in {}s", now.elapsed().as_secs_f64()));
+    let mut lowered = if optimize_hir {
+        lowered.fold_constants()
+    } else {
+        lowered
+    };
 
-            now = Instant::now();
+    let result = lowered.finish();
 
-            let hir = LowerToHir::run_all(&mut state, vec![ast]);
-            let hir = hir.first_key_value().unwrap().1;
+    if result.is_ok() {
+        let now = Instant::now();
 
-            log_info("Lowered", format!("in {}s", now.elapsed().as_secs_f64()));
+        let hir = lowered.hir().values().next().unwrap();
+        let hir_string = serde_json::to_string(hir).unwrap();
 
-            diagnostics_emitter.emit_global_diagnostics(state.diagnostics());
+        log_info("Serialized", format!("in {}s", now.elapsed().as_secs_f64()));
 
-            if state.diagnostics().is_ok() {
-                now = Instant::now();
+        let (filename, file) = make_unique_file("hir", "json");
+        file.expect("Cannot create `hir (n).json` file")
+            .write_all(hir_string.as_bytes())
+            .unwrap_or_else(|_| panic!("Cannot write to file {filename}"));
 
-                let hir_string = serde_json::to_string(hir).unwrap();
+        log_info("Emitted", format!("HIR in `{filename}`"));
+    }
 
-                log_info("Serialized", format!("in {}s", now.elapsed().as_secs_f64()));
+    if stats {
+        println!("{}", lowered.state().db().stats());
 
-                let (filename, file) = make_unique_file("hir", "json");
-                file.expect("Cannot create `hir (n).json` file")
-                    .write_all(hir_string.as_bytes())
-                    .unwrap_or_else(|_| panic!("Cannot write to file {filename}"));
+        let interner = IdentifierInterner::global();
+        println!(
+            "interned identifiers: {} ({} B)",
+            interner.len(),
+            interner.bytes_used()
+        );
+    }
 
-                log_info("Emitted", format!("HIR in `{filename}`"));
-            }
-        }
-    };
+    result
 }
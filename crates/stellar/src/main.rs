@@ -57,17 +57,30 @@
     clippy::significant_drop_tightening
 )]
 
+use std::{path::PathBuf, process::exit};
+
 use clap::{Parser, Subcommand};
 
+use crate::{
+    log::{init_tracing, log_error},
+    message_format::MessageFormat,
+};
+
+mod check;
+mod doc;
+mod explain;
 #[cfg(feature = "debug")]
 // mod collect_definitions;
 // mod collect_signatures;
 mod lex;
 mod log;
-// mod lower;
+mod lower;
+mod message_format;
+mod metrics;
 mod parse;
 mod parse_manifest;
 // mod resolve_imports;
+mod tokens;
 mod version;
 
 #[derive(Parser)]
@@ -76,6 +89,28 @@ mod version;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    #[arg(
+        short,
+        long,
+        global = true,
+        action = clap::ArgAction::Count,
+        help = "Prints compiler stages with their timings, repeat for more details (-vv, -vvv)"
+    )]
+    verbose: u8,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = MessageFormat::Human,
+        help = "Format of reported diagnostics"
+    )]
+    message_format: MessageFormat,
+    #[arg(
+        long,
+        global = true,
+        help = "Includes source text of labels into diagnostics with `--message-format json`"
+    )]
+    include_snippets: bool,
 }
 
 #[derive(Subcommand)]
@@ -102,6 +137,11 @@ enum Commands {
         show_locations: bool,
     },
     #[cfg(feature = "debug")]
+    #[command(
+        about = "Debug mode: print tokens of a given source file with their trivia, one per line"
+    )]
+    Tokens { filepath: String },
+    #[cfg(feature = "debug")]
     #[command(about = "Debug mode: parse a given source file and serialize its AST")]
     Ast { filepath: String },
     #[cfg(feature = "debug")]
@@ -109,13 +149,85 @@ enum Commands {
     Parse { filepath: String },
     #[cfg(feature = "debug")]
     #[command(about = "Debug mode: parse a given source file, lower its AST and serialize HIR")]
-    Hir { filepath: String },
+    Hir {
+        filepath: String,
+        #[arg(long)]
+        optimize_hir: bool,
+        #[arg(long, help = "Print statistics of the compiler database")]
+        stats: bool,
+    },
     #[cfg(feature = "debug")]
     #[command(about = "Debug mode: parse a given source file, lower its AST and serialize HIR")]
-    LowerAst { filepath: String },
+    LowerAst {
+        filepath: String,
+        #[arg(long)]
+        optimize_hir: bool,
+        #[arg(long, help = "Print statistics of the compiler database")]
+        stats: bool,
+    },
     #[cfg(feature = "debug")]
     #[command(about = "Debug mode: parses a given manifest file")]
     ParseManifest { filepath: String },
+    #[command(about = "Checks a package and its dependencies for errors")]
+    Check {
+        #[arg(default_value = ".")]
+        directory: String,
+        #[arg(
+            long = "dep",
+            value_name = "NAME=PATH",
+            value_parser = check::parse_dependency,
+            help = "Adds a local package as a dependency"
+        )]
+        dependencies: Vec<(String, PathBuf)>,
+        #[arg(
+            long,
+            value_enum,
+            help = "Serializes an intermediate representation to JSON"
+        )]
+        emit: Option<check::Emit>,
+        #[command(flatten)]
+        config: check::ConfigArgs,
+        #[arg(long, help = "Checks the package as a library, i.e. without `main`")]
+        lib: bool,
+        #[arg(
+            long,
+            conflicts_with = "dependencies",
+            help = "Checks all members of the workspace in the directory"
+        )]
+        workspace: bool,
+    },
+    #[command(about = "Generates documentation of a package")]
+    Doc {
+        #[arg(default_value = ".")]
+        directory: String,
+        #[arg(
+            short,
+            long,
+            default_value = "doc",
+            help = "Directory to write the documentation into"
+        )]
+        output: String,
+        #[arg(long, help = "Document private module items and methods")]
+        document_private_items: bool,
+    },
+    #[command(about = "Prints sizes and complexity of functions of a package")]
+    Metrics {
+        #[arg(default_value = ".")]
+        directory: String,
+        #[arg(long, help = "Prints metrics as JSON")]
+        json: bool,
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = metrics::SortBy::Statements,
+            help = "Metric to sort functions by, the largest values first"
+        )]
+        sort_by: metrics::SortBy,
+        #[command(flatten)]
+        thresholds: metrics::ThresholdArgs,
+    },
+    #[command(about = "Explains a diagnostic with a given code, e.g. `E001`")]
+    Explain { code: String },
     #[command(about = "Creates a new package")]
     New { package_name: String },
     #[command(about = "Prints current version of the compiler")]
@@ -138,26 +250,74 @@ enum Commands {
 }
 
 fn main() {
-    #[cfg(feature = "debug")]
-    tracing_subscriber::fmt()
-        .with_writer(std::io::stderr)
-        .with_env_filter(std::env::var("STELLAR_LOG").unwrap_or_else(|_| "off".to_owned()))
-        .without_time()
-        .with_ansi(false)
-        .with_target(false)
-        .with_level(false)
-        .init();
+    let cli = Cli::parse();
+
+    init_tracing(cli.verbose);
 
-    match Cli::parse().command {
+    let diagnostics_emitter = cli.message_format.diagnostics_emitter(cli.include_snippets);
+
+    let result = match cli.command {
         // #[cfg(feature = "debug")]
         // Commands::CollectDefinitions => collect_definitions::command(),
         // #[cfg(feature = "debug")]
         // Commands::CollectSignatures => collect_signatures::command(),
         // #[cfg(feature = "debug")]
         // Commands::ResolveImports => resolve_imports::command(),
-        Commands::CompilerVersion => version::compiler_version_command(),
-        Commands::StdVersion => version::std_version_command(),
-        Commands::PackageManagerVersion => version::package_manager_version_command(),
+        Commands::CompilerVersion => {
+            version::compiler_version_command();
+            Ok(())
+        }
+        Commands::StdVersion => {
+            version::std_version_command();
+            Ok(())
+        }
+        Commands::PackageManagerVersion => {
+            version::package_manager_version_command();
+            Ok(())
+        }
+        Commands::Explain { code } => {
+            explain::command(&code);
+            Ok(())
+        }
+        Commands::Check {
+            directory,
+            dependencies,
+            emit,
+            config,
+            lib,
+            workspace: false,
+        } => check::command(
+            &directory,
+            &dependencies,
+            emit,
+            &config,
+            lib,
+            diagnostics_emitter,
+        ),
+        Commands::Check {
+            directory,
+            emit,
+            config,
+            lib,
+            workspace: true,
+            ..
+        } => check::workspace_command(&directory, emit, &config, lib, diagnostics_emitter),
+        Commands::Doc {
+            directory,
+            output,
+            document_private_items,
+        } => doc::command(
+            &directory,
+            &output,
+            document_private_items,
+            diagnostics_emitter,
+        ),
+        Commands::Metrics {
+            directory,
+            json,
+            sort_by,
+            thresholds,
+        } => metrics::command(&directory, json, sort_by, &thresholds, diagnostics_emitter),
         #[cfg(feature = "debug")]
         Commands::Lex {
             filepath,
@@ -172,19 +332,31 @@ fn main() {
             show_locations,
         } => lex::command(&filepath, show_locations),
         #[cfg(feature = "debug")]
+        Commands::Tokens { filepath } => tokens::command(&filepath),
+        #[cfg(feature = "debug")]
         Commands::Ast { filepath } | Commands::Parse { filepath } => {
-            parse::command(&filepath);
+            parse::command(&filepath, diagnostics_emitter)
         }
-        // #[cfg(feature = "debug")]
-        // Commands::Hir { filepath } | Commands::LowerAst { filepath } => {
-        //     lower::command(&filepath);
-        // }
         #[cfg(feature = "debug")]
-        Commands::ParseManifest { filepath } => {
-            parse_manifest::command(&filepath);
+        Commands::Hir {
+            filepath,
+            optimize_hir,
+            stats,
         }
+        | Commands::LowerAst {
+            filepath,
+            optimize_hir,
+            stats,
+        } => lower::command(&filepath, optimize_hir, stats, diagnostics_emitter),
+        #[cfg(feature = "debug")]
+        Commands::ParseManifest { filepath } => parse_manifest::command(&filepath),
         _ => {
             todo!()
         }
+    };
+
+    if let Err(err) = result {
+        log_error(err.to_string());
+        exit(err.exit_code());
     }
 }
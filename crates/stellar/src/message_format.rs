@@ -0,0 +1,53 @@
+use clap::ValueEnum;
+use stellar_diagnostics::{
+    term::{
+        termcolor::{ColorChoice, StandardStream},
+        Config, DisplayStyle,
+    },
+    DiagnosticsEmitter,
+};
+
+/// The format of diagnostics, that can be chosen with `--message-format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum MessageFormat {
+    /// Diagnostics with source code previews.
+    Human,
+
+    /// One line per diagnostic with its location, severity and message.
+    Short,
+
+    /// GitHub Actions workflow commands, which are shown as annotations
+    /// of pull requests.
+    Github,
+
+    /// One JSON object per diagnostic, printed to stdout.
+    Json,
+}
+
+impl MessageFormat {
+    /// Returns an emitter, that reports diagnostics in the format. Source
+    /// text of labels is only included into JSON diagnostics.
+    pub fn diagnostics_emitter(self, include_snippets: bool) -> DiagnosticsEmitter {
+        match self {
+            Self::Human => DiagnosticsEmitter::new(),
+            Self::Short => DiagnosticsEmitter::new().with_diagnostics_config(Config {
+                display_style: DisplayStyle::Short,
+                ..Config::default()
+            }),
+            // Workflow commands are only recognized in stdout.
+            Self::Github => DiagnosticsEmitter::new()
+                .with_diagnostics_writer(StandardStream::stdout(ColorChoice::Never))
+                .with_diagnostics_config(Config {
+                    display_style: DisplayStyle::GitHub,
+                    ..Config::default()
+                }),
+            Self::Json => DiagnosticsEmitter::new()
+                .with_diagnostics_writer(StandardStream::stdout(ColorChoice::Never))
+                .with_diagnostics_config(Config {
+                    display_style: DisplayStyle::Json,
+                    include_snippets,
+                    ..Config::default()
+                }),
+        }
+    }
+}
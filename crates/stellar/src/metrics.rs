@@ -0,0 +1,191 @@
+use std::{cmp::Reverse, path::Path};
+
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use stellar_diagnostics::DiagnosticsEmitter;
+use stellar_driver::{
+    metrics::{FunctionReport, MetricThreshold},
+    CompileError, Session,
+};
+use stellar_hir::metrics::{FunctionMetrics, Metric};
+
+use crate::log::log_info;
+
+/// A metric, that functions are sorted by in `stellar metrics`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SortBy {
+    /// The number of statements.
+    Statements,
+
+    /// The number of expressions.
+    Expressions,
+
+    /// The maximum nesting depth of blocks.
+    Depth,
+
+    /// The number of branches of `if` and `match` expressions.
+    Branches,
+
+    /// The number of parameters.
+    Parameters,
+}
+
+impl From<SortBy> for Metric {
+    fn from(sort_by: SortBy) -> Self {
+        match sort_by {
+            SortBy::Statements => Self::Statements,
+            SortBy::Expressions => Self::Expressions,
+            SortBy::Depth => Self::Depth,
+            SortBy::Branches => Self::Branches,
+            SortBy::Parameters => Self::Parameters,
+        }
+    }
+}
+
+/// Options of `stellar metrics`, that turn functions exceeding a metric into
+/// warnings.
+#[derive(Debug, Clone, Args)]
+pub struct ThresholdArgs {
+    #[arg(
+        long = "max-statements",
+        value_name = "N",
+        help = "Maximum number of statements"
+    )]
+    statements: Option<usize>,
+    #[arg(
+        long = "max-expressions",
+        value_name = "N",
+        help = "Maximum number of expressions"
+    )]
+    expressions: Option<usize>,
+    #[arg(
+        long = "max-depth",
+        value_name = "N",
+        help = "Maximum nesting depth of blocks"
+    )]
+    depth: Option<usize>,
+    #[arg(
+        long = "max-branches",
+        value_name = "N",
+        help = "Maximum number of branches"
+    )]
+    branches: Option<usize>,
+    #[arg(
+        long = "max-parameters",
+        value_name = "N",
+        help = "Maximum number of parameters"
+    )]
+    parameters: Option<usize>,
+}
+
+impl ThresholdArgs {
+    /// Returns thresholds, that are passed.
+    fn thresholds(&self) -> Vec<MetricThreshold> {
+        [
+            (Metric::Statements, self.statements),
+            (Metric::Expressions, self.expressions),
+            (Metric::Depth, self.depth),
+            (Metric::Branches, self.branches),
+            (Metric::Parameters, self.parameters),
+        ]
+        .into_iter()
+        .filter_map(|(metric, max)| Some(MetricThreshold { metric, max: max? }))
+        .collect()
+    }
+}
+
+/// A function in the output of `stellar metrics --json`.
+#[derive(Serialize)]
+struct JsonFunctionReport<'a> {
+    function: &'a str,
+    #[serde(flatten)]
+    metrics: &'a FunctionMetrics,
+}
+
+pub fn command(
+    directory: &str,
+    json: bool,
+    sort_by: SortBy,
+    thresholds: &ThresholdArgs,
+    diagnostics_emitter: DiagnosticsEmitter,
+) -> Result<(), CompileError> {
+    let mut session = Session::new().with_diagnostics_emitter(diagnostics_emitter);
+
+    let package = session.parse_package(Path::new(directory))?;
+    session.finish_parsing()?;
+
+    session.lower();
+    session.collect_definitions();
+    session.finish_checking()?;
+
+    // Functions with equal values stay sorted by their paths.
+    let metric = Metric::from(sort_by);
+    let mut reports = session.function_metrics(package);
+    reports.sort_by_key(|report| Reverse(report.metrics.get(metric)));
+
+    let violations = session.check_metric_thresholds(&reports, &thresholds.thresholds());
+
+    if json {
+        let reports = reports
+            .iter()
+            .map(|report| JsonFunctionReport {
+                function: &report.path,
+                metrics: &report.metrics,
+            })
+            .collect::<Vec<_>>();
+
+        println!("{}", serde_json::to_string(&reports).unwrap());
+    } else {
+        print_table(&reports);
+    }
+
+    session.finish_checking()?;
+
+    log_info(
+        "Measured",
+        format!(
+            "{} function{} in `{directory}`, {violations} threshold violation{}",
+            reports.len(),
+            if reports.len() == 1 { "" } else { "s" },
+            if violations == 1 { "" } else { "s" }
+        ),
+    );
+
+    Ok(())
+}
+
+/// Prints metrics of functions as a table with a column per metric.
+fn print_table(reports: &[FunctionReport]) {
+    const HEADERS: [&str; 5] = [
+        "statements",
+        "expressions",
+        "depth",
+        "branches",
+        "parameters",
+    ];
+
+    let name_width = reports
+        .iter()
+        .map(|report| report.path.len())
+        .chain(["function".len()])
+        .max()
+        .unwrap_or_default();
+
+    print!("{:name_width$}", "function");
+    for header in HEADERS {
+        print!("  {header}");
+    }
+    println!();
+
+    for report in reports {
+        print!("{:name_width$}", report.path);
+        for (header, metric) in HEADERS.iter().zip(Metric::ALL) {
+            print!(
+                "  {:>width$}",
+                report.metrics.get(*metric),
+                width = header.len()
+            );
+        }
+        println!();
+    }
+}
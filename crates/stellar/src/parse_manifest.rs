@@ -1,25 +1,21 @@
 #![cfg(feature = "debug")]
 
-use std::fs;
+use std::{fs, path::PathBuf};
 
+use stellar_driver::CompileError;
 use stellar_manifest::parse_manifest;
 
-use crate::log::log_error;
+pub fn command(filepath: &str) -> Result<(), CompileError> {
+    let source = fs::read_to_string(filepath).map_err(|source| CompileError::Io {
+        path: PathBuf::from(filepath),
+        source,
+    })?;
+    let manifest = parse_manifest(source).map_err(|message| CompileError::InvalidManifest {
+        path: PathBuf::from(filepath),
+        message,
+    })?;
 
-pub fn command(filepath: &str) {
-    match fs::read_to_string(filepath) {
-        Err(..) => {
-            log_error(format!("cannot read the file {filepath}"));
-        }
-        Ok(source) => match parse_manifest(source) {
-            Err(err) => {
-                log_error(format!(
-                    "cannot parse the manifest file due to the error: {err}"
-                ));
-            }
-            Ok(manifest) => {
-                println!("{manifest:?}");
-            }
-        },
-    }
+    println!("{manifest:?}");
+
+    Ok(())
 }
@@ -39,7 +39,7 @@ pub fn command() {
 
             log_info("Analyzed", format!("in {}s", now.elapsed().as_secs_f64()));
 
-            diagnostics_emitter.emit_global_diagnostics(state.diagnostics());
+            diagnostics_emitter.emit_global_diagnostics(&state.diagnostics().snapshot());
         }
     };
 }
@@ -0,0 +1,38 @@
+#![cfg(feature = "debug")]
+use std::{fs, path::PathBuf};
+
+use stellar_driver::CompileError;
+use stellar_interner::PathId;
+use stellar_parser::{tokenize, TriviaKind};
+
+pub fn command(filepath: &str) -> Result<(), CompileError> {
+    let source = fs::read_to_string(filepath).map_err(|source| CompileError::Io {
+        path: PathBuf::from(filepath),
+        source,
+    })?;
+
+    for token in tokenize(PathId::from(filepath), &source) {
+        let comments = token
+            .leading_trivia
+            .iter()
+            .filter(|trivia| trivia.kind == TriviaKind::Comment)
+            .count();
+        let newlines = token
+            .leading_trivia
+            .iter()
+            .filter(|trivia| trivia.kind == TriviaKind::Newline)
+            .count();
+
+        println!(
+            "{: <12} {: <25} {:?} (comments: {comments}, newlines: {newlines})",
+            format!(
+                "{}..{}",
+                token.token.location.start, token.token.location.end
+            ),
+            token.token.raw.to_string(),
+            &source.as_str()[token.token.location],
+        );
+    }
+
+    Ok(())
+}
@@ -40,41 +40,43 @@
 //!                 "parameters": [],
 //!                 "where_predicates": []
 //!             },
-//!             "body": [
-//!                 {
-//!                     "kind": "expression_statement",
-//!                     "expression": {
-//!                         "kind": "call_expression",
-//!                         "location": {
-//!                             "filepath": "test.sr",
-//!                             "start": 15,
-//!                             "end": 37
-//!                         },
-//!                         "callee": {
-//!                             "kind": "identifier_expression",
+//!             "body": {
+//!                 "statements": [
+//!                     {
+//!                         "kind": "expression_statement",
+//!                         "expression": {
+//!                             "kind": "call_expression",
 //!                             "location": {
 //!                                 "filepath": "test.sr",
 //!                                 "start": 15,
-//!                                 "end": 22
+//!                                 "end": 37
 //!                             },
-//!                             "id": "println"
-//!                         },
-//!                         "arguments": [
-//!                             {
-//!                                 "kind": "literal_expression",
-//!                                 "literal_kind": "string",
-//!                                 "value": "hello world",
+//!                             "callee": {
+//!                                 "kind": "identifier_expression",
 //!                                 "location": {
 //!                                     "filepath": "test.sr",
-//!                                     "start": 23,
-//!                                     "end": 36
+//!                                     "start": 15,
+//!                                     "end": 22
+//!                                 },
+//!                                 "id": "println"
+//!                             },
+//!                             "arguments": [
+//!                                 {
+//!                                     "kind": "literal_expression",
+//!                                     "literal_kind": "string",
+//!                                     "value": "hello world",
+//!                                     "location": {
+//!                                         "filepath": "test.sr",
+//!                                         "start": 23,
+//!                                         "end": 36
+//!                                     }
 //!                                 }
-//!                             }
-//!                         ]
-//!                     },
-//!                     "has_semicolon": true
-//!                 }
-//!             ]
+//!                             ]
+//!                         },
+//!                         "has_semicolon": true
+//!                     }
+//!                 ]
+//!             }
 //!         }
 //!     ]
 //! }
@@ -159,7 +161,7 @@ use serde::Deserializer;
 use serde::Serializer;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use stellar_filesystem::location::Location;
+use stellar_filesystem::location::{HasLocation, Location};
 use stellar_interner::IdentifierId;
 use stellar_interner::PathId;
 use token::{Punctuator, RawToken};
@@ -185,13 +187,29 @@ pub enum Literal {
     #[cfg_attr(feature = "serde", serde(rename = "string"))]
     String { value: String, location: Location },
 
-    /// Integer literal, e.g. `123`,
+    /// Integer literal, e.g. `123`, `255u8`.
     #[cfg_attr(feature = "serde", serde(rename = "integer"))]
-    Integer { value: u64, location: Location },
+    Integer {
+        value: u64,
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        suffix: Option<NumericLiteralSuffix>,
+        /// The literal as it is written in the source text, including the
+        /// suffix, e.g. `0xFF`, `1_000u32`.
+        text: IdentifierId,
+        location: Location,
+    },
 
-    /// Float literal, e.g. `3.14`.
+    /// Float literal, e.g. `3.14`, `2.5f32`.
     #[cfg_attr(feature = "serde", serde(rename = "float"))]
-    Float { value: f64, location: Location },
+    Float {
+        value: f64,
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        suffix: Option<NumericLiteralSuffix>,
+        /// The literal as it is written in the source text, including the
+        /// suffix, e.g. `1e3`, `2.5f32`.
+        text: IdentifierId,
+        location: Location,
+    },
 }
 
 impl Literal {
@@ -208,6 +226,120 @@ impl Literal {
     }
 }
 
+impl Display for Literal {
+    /// Formats the literal as it can be written in the source text. Numeric
+    /// literals keep their original spelling, e.g. `0xFF` is not formatted
+    /// as `255`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Boolean { value, .. } => write!(f, "{value}"),
+            Self::Character { value, .. } => {
+                f.write_str("'")?;
+                write_escaped(f, *value)?;
+                f.write_str("'")
+            }
+            Self::String { value, .. } => {
+                f.write_str("\"")?;
+
+                for c in value.chars() {
+                    write_escaped(f, c)?;
+                }
+
+                f.write_str("\"")
+            }
+            Self::Integer { text, .. } | Self::Float { text, .. } => write!(f, "{text}"),
+        }
+    }
+}
+
+/// Writes a character of a string or a character literal, escaping it if
+/// it can't be written as is.
+fn write_escaped(f: &mut std::fmt::Formatter<'_>, c: char) -> std::fmt::Result {
+    match c {
+        '\n' => f.write_str("\\n"),
+        '\r' => f.write_str("\\r"),
+        '\t' => f.write_str("\\t"),
+        '\\' => f.write_str("\\\\"),
+        '"' => f.write_str("\\\""),
+        '\'' => f.write_str("\\'"),
+        c if c.is_control() && u32::from(c) <= 0xFFFF => write!(f, "\\u{{{:04x}}}", u32::from(c)),
+        c if c.is_control() => write!(f, "\\U{{{:08x}}}", u32::from(c)),
+        c => write!(f, "{c}"),
+    }
+}
+
+/// A type suffix of a numeric literal, e.g. `u8` in `255u8`, which sets
+/// the type of the literal.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Display)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum NumericLiteralSuffix {
+    #[display(fmt = "i8")]
+    I8,
+    #[display(fmt = "i16")]
+    I16,
+    #[display(fmt = "i32")]
+    I32,
+    #[display(fmt = "i64")]
+    I64,
+    #[display(fmt = "u8")]
+    U8,
+    #[display(fmt = "u16")]
+    U16,
+    #[display(fmt = "u32")]
+    U32,
+    #[display(fmt = "u64")]
+    U64,
+    #[display(fmt = "f32")]
+    F32,
+    #[display(fmt = "f64")]
+    F64,
+}
+
+impl NumericLiteralSuffix {
+    /// Returns a suffix with a given name, e.g. `u8`, or `None` if there
+    /// is no such suffix.
+    #[must_use]
+    pub fn from_str_or_none(s: &str) -> Option<Self> {
+        Some(match s {
+            "i8" => Self::I8,
+            "i16" => Self::I16,
+            "i32" => Self::I32,
+            "i64" => Self::I64,
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "u32" => Self::U32,
+            "u64" => Self::U64,
+            "f32" => Self::F32,
+            "f64" => Self::F64,
+            _ => return None,
+        })
+    }
+
+    /// Returns `true` if the suffix is `f32` or `f64`.
+    #[inline]
+    #[must_use]
+    pub const fn is_float(self) -> bool {
+        matches!(self, Self::F32 | Self::F64)
+    }
+
+    /// Splits the source text of a numeric literal into its value and its
+    /// suffix, e.g. `0xffu8` into `0xff` and `u8`.
+    ///
+    /// **Note**: suffixes are validated by the lexer, so an unknown suffix
+    /// is silently dropped here.
+    #[must_use]
+    pub fn split_off(literal: &str) -> (&str, Option<Self>) {
+        // `f` is a digit in hexadecimal literals.
+        let is_hexadecimal = literal.starts_with("0x") || literal.starts_with("0X");
+
+        match literal.find(|c: char| c == 'i' || c == 'u' || (c == 'f' && !is_hexadecimal)) {
+            Some(idx) => (&literal[..idx], Self::from_str_or_none(&literal[idx..])),
+            None => (literal, None),
+        }
+    }
+}
+
 /// An identifier with a specified location, e.g. `foo`, `std`.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -277,14 +409,96 @@ pub struct TypeConstructor {
     pub arguments: Vec<Type>,
 }
 
+/// An attribute of a module item, e.g. `#[cfg(test)]`, `#[deprecated]`.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Attribute {
+    /// Location of the entire attribute, including `#[` and `]`.
+    pub location: Location,
+    pub name: IdentifierAST,
+
+    /// Arguments in parentheses, e.g. `test` in `#[cfg(test)]`. `None` if
+    /// the attribute doesn't have parentheses.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub arguments: Option<Vec<AttributeArgument>>,
+}
+
+/// An argument of an attribute, e.g. `not(test)` in `#[cfg(not(test))]`.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+pub enum AttributeArgument {
+    /// An identifier, e.g. `test`.
+    #[cfg_attr(feature = "serde", serde(rename = "identifier"))]
+    Identifier(IdentifierAST),
+
+    /// A string literal, e.g. `"use Bar instead"`.
+    #[cfg_attr(feature = "serde", serde(rename = "string"))]
+    String { location: Location, value: String },
+
+    /// An identifier with arguments, e.g. `any(a, b)`.
+    #[cfg_attr(feature = "serde", serde(rename = "call"))]
+    Call {
+        location: Location,
+        name: IdentifierAST,
+        arguments: Vec<Self>,
+    },
+}
+
+impl AttributeArgument {
+    /// Returns the location of the argument.
+    #[inline]
+    #[must_use]
+    pub const fn location(&self) -> Location {
+        match self {
+            Self::Identifier(IdentifierAST { location, .. })
+            | Self::String { location, .. }
+            | Self::Call { location, .. } => *location,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "kind"))]
 pub enum NegativeNumericLiteral {
     #[cfg_attr(feature = "serde", serde(rename = "float"))]
-    Float { location: Location, value: f64 },
+    Float {
+        location: Location,
+        value: f64,
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        suffix: Option<NumericLiteralSuffix>,
+        /// The literal as it is written in the source text without `-`.
+        text: IdentifierId,
+    },
     #[cfg_attr(feature = "serde", serde(rename = "integer"))]
-    Integer { location: Location, value: u64 },
+    Integer {
+        location: Location,
+        value: u64,
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        suffix: Option<NumericLiteralSuffix>,
+        /// The literal as it is written in the source text without `-`.
+        text: IdentifierId,
+    },
+}
+
+impl NegativeNumericLiteral {
+    /// Returns the location of the literal.
+    #[inline]
+    #[must_use]
+    pub const fn location(&self) -> Location {
+        match self {
+            Self::Float { location, .. } | Self::Integer { location, .. } => *location,
+        }
+    }
+}
+
+impl Display for NegativeNumericLiteral {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Float { text, .. } | Self::Integer { text, .. } => write!(f, "-{text}"),
+        }
+    }
 }
 
 /// A pattern, e.g. `Some(x)`, `None`, `a @ [3, ..]`, `[1, .., 3]`, `(1, \"hello\")`, `3.2`.
@@ -300,12 +514,15 @@ pub enum Pattern {
     #[cfg_attr(feature = "serde", serde(rename = "negative_numeric_literal"))]
     NegativeNumericLiteral(NegativeNumericLiteral),
 
-    /// An identifier pattern, e.g. `f`, `list @ [3, ..]`.
+    /// An identifier pattern, e.g. `f`, `mut x`, `list @ [3, ..]`.
     #[cfg_attr(feature = "serde", serde(rename = "identifier_pattern"))]
     Identifier {
         location: Location,
         identifier: IdentifierAST,
 
+        /// Whether the binding is declared with `mut`.
+        mutable: bool,
+
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pattern: Option<Box<Self>>,
     },
@@ -433,6 +650,17 @@ pub enum StructFieldPattern {
     Rest { location: Location },
 }
 
+impl StructFieldPattern {
+    /// Returns the location of the struct field pattern.
+    #[inline]
+    #[must_use]
+    pub const fn location(&self) -> Location {
+        match self {
+            Self::NotRest { location, .. } | Self::Rest { location } => *location,
+        }
+    }
+}
+
 /// A type, e.g. `int32`, `(char): bool`, `(char, char)`.
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -519,6 +747,12 @@ pub struct TypeAlias {
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub docstring: Option<String>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attributes: Vec<Attribute>,
 }
 
 /// A where clause predicate, e.g. `T: ToString`.
@@ -555,7 +789,10 @@ pub enum Expression {
     #[cfg_attr(feature = "serde", serde(rename = "loop_expression"))]
     Loop {
         location: Location,
-        statements_block: Vec<Statement>,
+        /// Label of the loop, e.g. `outer` in `@outer: loop { ... }`.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        label: Option<IdentifierAST>,
+        statements_block: StatementsBlock,
     },
 
     /// Binary expression, e.g. `1 + 2`.
@@ -571,7 +808,7 @@ pub enum Expression {
     #[cfg_attr(feature = "serde", serde(rename = "statements_block_expression"))]
     StatementsBlock {
         location: Location,
-        block: Vec<Statement>,
+        block: StatementsBlock,
     },
 
     /// Literal expression, e.g. `true`, `\"hello\"`, `1.2`.
@@ -597,8 +834,8 @@ pub enum Expression {
     #[cfg_attr(feature = "serde", serde(rename = "if_expression"))]
     If {
         location: Location,
-        if_blocks: Vec<(Self, Vec<Statement>)>,
-        r#else: Option<Vec<Statement>>,
+        if_blocks: Vec<(Self, StatementsBlock)>,
+        r#else: Option<StatementsBlock>,
     },
 
     /// Field access expression, e.g. `x.y`.
@@ -609,6 +846,22 @@ pub enum Expression {
         right: IdentifierAST,
     },
 
+    /// Tuple index expression, e.g. `x.0`.
+    #[cfg_attr(feature = "serde", serde(rename = "tuple_index_expression"))]
+    TupleIndex {
+        location: Location,
+        left: Box<Self>,
+        index: u32,
+    },
+
+    /// Index expression, e.g. `xs[0]`.
+    #[cfg_attr(feature = "serde", serde(rename = "index_expression"))]
+    Index {
+        location: Location,
+        left: Box<Self>,
+        index: Box<Self>,
+    },
+
     /// Prefix expression, e.g. `!false`, `++a`.
     #[cfg_attr(feature = "serde", serde(rename = "prefix_expression"))]
     Prefix {
@@ -629,16 +882,19 @@ pub enum Expression {
     #[cfg_attr(feature = "serde", serde(rename = "while_expression"))]
     While {
         location: Location,
+        /// Label of the loop, e.g. `outer` in `@outer: while x != 0 { ... }`.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        label: Option<IdentifierAST>,
         condition: Box<Self>,
-        statements_block: Vec<Statement>,
+        statements_block: StatementsBlock,
     },
 
-    /// Call expression, e.g. `s.to_string()`.
+    /// Call expression, e.g. `s.to_string()` or `draw(x: 10, y: 20)`.
     #[cfg_attr(feature = "serde", serde(rename = "call_expression"))]
     Call {
         location: Location,
         callee: Box<Self>,
-        arguments: Vec<Self>,
+        arguments: Vec<CallArgument>,
     },
 
     /// Type arguments expression, e.g. `sizeof[uint32]`.
@@ -662,6 +918,9 @@ pub enum Expression {
         location: Location,
         left: Box<Self>,
         fields: Vec<StructFieldExpression>,
+        /// Base of the struct update syntax, e.g. `other` in `Point { x: 1, ..other }`.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        base: Option<Box<Self>>,
     },
 
     /// Match expression (`match fs.read_file(...) { ... }`).
@@ -718,6 +977,8 @@ impl Expression {
             | Self::Parenthesized { location, .. }
             | Self::If { location, .. }
             | Self::FieldAccess { location, .. }
+            | Self::TupleIndex { location, .. }
+            | Self::Index { location, .. }
             | Self::Prefix { location, .. }
             | Self::Postfix { location, .. }
             | Self::While { location, .. }
@@ -786,31 +1047,51 @@ macro_rules! operator_type {
             }
         }
 
+        impl $raw_operator_type_name {
+            /// All operators of the kind.
+            pub const ALL: &'static [Self] = &[$(Self::$name),*];
+
+            /// Returns the operator, that a token stands for, or `None`, if
+            /// the token is not an operator of the kind.
+            #[inline]
+            #[must_use]
+            pub const fn from_token(token: RawToken) -> Option<Self> {
+                match token {
+                    $(RawToken::Punctuator(Punctuator::$name) => Some(Self::$name),)*
+                    _ => None,
+                }
+            }
+
+            /// Returns the token of the operator.
+            #[inline]
+            #[must_use]
+            pub const fn to_token(self) -> RawToken {
+                RawToken::Punctuator(match self {
+                    $(Self::$name => Punctuator::$name,)*
+                })
+            }
+        }
+
         impl RawToken {
             $(#[$($raw_operator_type_doc)*])*
             #[inline]
             #[must_use]
             pub const fn $token_check_fn_name(self) -> bool {
-                matches!(self, RawToken::Punctuator($(| Punctuator::$name)*))
+                $raw_operator_type_name::from_token(self).is_some()
             }
         }
 
         impl From<Punctuator> for $raw_operator_type_name {
             fn from(punctuator: Punctuator) -> Self {
-                match punctuator {
-                    $(Punctuator::$name => Self::$name,)*
-                    _ => unreachable!(),
-                }
+                RawToken::Punctuator(punctuator).into()
             }
         }
 
         impl From<RawToken> for $raw_operator_type_name {
             fn from(token: RawToken) -> Self {
-                if let RawToken::Punctuator(punctuator) = token {
-                    punctuator.into()
-                } else {
-                    unreachable!()
-                }
+                Self::from_token(token).unwrap_or_else(|| {
+                    panic!(concat!("token {:?} is not a ", stringify!($raw_operator_type_name)), token)
+                })
             }
         }
 
@@ -824,7 +1105,7 @@ macro_rules! operator_type {
 
         impl From<$raw_operator_type_name> for RawToken {
             fn from(value: $raw_operator_type_name) -> Self {
-                RawToken::Punctuator(value.into()).into()
+                value.to_token()
             }
         }
 
@@ -980,14 +1261,81 @@ operator_type! {
     "--" => DoubleMinus
 }
 
-/// A match expression item - `pattern` `=>` `expression`.
+impl RawBinaryOperator {
+    /// Returns `true` if the operator is an assignment operator, e.g. `=`, `+=`.
+    #[inline]
+    #[must_use]
+    pub const fn is_assignment(self) -> bool {
+        matches!(self, Self::Eq) || self.compound_assignment_operator().is_some()
+    }
+
+    /// Returns the operator, that a compound assignment operator applies,
+    /// e.g. `+` for `+=`.
+    #[inline]
+    #[must_use]
+    pub const fn compound_assignment_operator(self) -> Option<Self> {
+        match self {
+            Self::PlusEq => Some(Self::Plus),
+            Self::MinusEq => Some(Self::Minus),
+            Self::AsteriskEq => Some(Self::Asterisk),
+            Self::SlashEq => Some(Self::Slash),
+            Self::PercentEq => Some(Self::Percent),
+            Self::OrEq => Some(Self::Or),
+            Self::AmpersandEq => Some(Self::Ampersand),
+            _ => None,
+        }
+    }
+}
+
+/// A match expression item - `pattern` (`if` `guard`)? `->` `expression`,
+/// e.g. `Some(x) if x > 0 -> x`.
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MatchExpressionItem {
     pub left: Pattern,
+
+    /// A condition, that must be true for the item to be chosen.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub guard: Option<Expression>,
+
     pub right: Expression,
 }
 
+/// An argument of a call expression (optionally `identifier` `:` or `..`, and
+/// `expression`), e.g. `10` and `y: 20` in `draw(10, y: 20)`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CallArgument {
+    /// Name of the parameter, that the argument is passed to.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub label: Option<IdentifierAST>,
+
+    /// Location of `..`, if elements of the tuple are passed as separate
+    /// arguments, e.g. `..args` in `f(..args)`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub spread: Option<Location>,
+
+    pub value: Expression,
+}
+
+impl CallArgument {
+    /// Returns the location of the argument, including its label or `..`.
+    #[inline]
+    #[must_use]
+    pub fn location(&self) -> Location {
+        let start = self
+            .label
+            .map(|label| label.location)
+            .or(self.spread)
+            .unwrap_or_else(|| self.value.location());
+
+        Location {
+            end: self.value.location().end,
+            ..start
+        }
+    }
+}
+
 /// A field item in a struct expression (`identifier` and optionally `:` `expression`),
 /// e.g. `name: "John"` and `age` in `Person { name: "John", age }`.
 #[derive(Debug, PartialEq, Clone)]
@@ -1010,20 +1358,38 @@ impl Expression {
             self,
             Self::If { .. }
                 | Self::While { .. }
+                | Self::Loop { .. }
                 | Self::Match { .. }
                 | Self::StatementsBlock { .. }
         )
     }
 }
 
+/// A block of statements, e.g. `{ let b = 1; b }`.
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StatementsBlock {
+    pub statements: Vec<Statement>,
+
+    /// The last expression of the block, that is not followed by `;`, e.g. `b`
+    /// in `{ let b = 1; b }`. The block evaluates to it or to `()` if there is
+    /// no such expression.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub tail_expression: Option<Box<Expression>>,
+}
+
 /// A statement, e.g. `defer file.close()`, `return Some("hello");`, `break;`.
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "kind"))]
+#[allow(clippy::large_enum_variant)] // boxing would allocate for every `let` statement
 pub enum Statement {
     /// Defer statement - `defer <expr>;`, e.g. `defer file.close()`.
     #[cfg_attr(feature = "serde", serde(rename = "defer_statement"))]
-    Defer { call: Expression },
+    Defer {
+        location: Location,
+        call: Expression,
+    },
 
     /// Expression statement, e.g. `call();`.
     #[cfg_attr(feature = "serde", serde(rename = "expression_statement"))]
@@ -1032,30 +1398,71 @@ pub enum Statement {
         has_semicolon: bool,
     },
 
-    /// Break statement - `break;`.
+    /// Break statement - `break;`, `break @outer;` or `break 42;`.
     #[cfg_attr(feature = "serde", serde(rename = "break_statement"))]
-    Break { location: Location },
+    Break {
+        location: Location,
+
+        /// Label of the loop to break out of, e.g. `outer` in `break @outer;`.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        label: Option<IdentifierAST>,
+
+        /// Value of the loop expression, e.g. `42` in `break 42;`.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        value: Option<Expression>,
+    },
 
-    /// Continue statement - `continue`;
+    /// Continue statement - `continue;` or `continue @outer;`.
     #[cfg_attr(feature = "serde", serde(rename = "continue_statement"))]
-    Continue { location: Location },
+    Continue {
+        location: Location,
+
+        /// Label of the loop to continue, e.g. `outer` in `continue @outer;`.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        label: Option<IdentifierAST>,
+    },
 
     /// Return statement - `return <expr>;`, e.g. `return 42;`.
     #[cfg_attr(feature = "serde", serde(rename = "return_statement"))]
-    Return { expression: Expression },
+    Return {
+        location: Location,
+        expression: Expression,
+    },
 
-    /// Let statement - `let <pattern> = <expr>;`, e.g. `let x = 1`.
+    /// Let statement - `let <pattern> = <expr>;`, e.g. `let x = 1`, or
+    /// `let <pattern> = <expr> else { ... };`, e.g. `let Some(x) = y else { return; };`.
     #[cfg_attr(feature = "serde", serde(rename = "let_statement"))]
     Let {
+        location: Location,
         pattern: Pattern,
         value: Expression,
 
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         #[cfg_attr(feature = "serde", serde(rename = "type"))]
         ty: Option<Type>,
+
+        /// Block, that is executed when the pattern doesn't match. Must diverge.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        r#else: Option<StatementsBlock>,
     },
 }
 
+impl Statement {
+    /// Returns the location of the statement.
+    #[inline]
+    #[must_use]
+    pub const fn location(&self) -> Location {
+        match self {
+            Self::Defer { location, .. }
+            | Self::Break { location, .. }
+            | Self::Continue { location, .. }
+            | Self::Return { location, .. }
+            | Self::Let { location, .. } => *location,
+            Self::Expression { expression, .. } => expression.location(),
+        }
+    }
+}
+
 /// An interface module item.
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1071,6 +1478,12 @@ pub struct Interface {
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub docstring: Option<String>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attributes: Vec<Attribute>,
 }
 
 /// An enum module item.
@@ -1089,6 +1502,12 @@ pub struct Enum {
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub docstring: Option<String>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attributes: Vec<Attribute>,
 }
 
 /// A struct module item.
@@ -1107,6 +1526,12 @@ pub struct Struct {
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub docstring: Option<String>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attributes: Vec<Attribute>,
 }
 
 /// A tuple-like struct module item.
@@ -1125,6 +1550,30 @@ pub struct TupleLikeStruct {
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub docstring: Option<String>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attributes: Vec<Attribute>,
+}
+
+/// An inline module item, e.g. `module foo { ... }`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InlineModule {
+    pub visibility: Visibility,
+    pub name: IdentifierAST,
+    pub items: Vec<ModuleItem>,
+
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub docstring: Option<String>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attributes: Vec<Attribute>,
 }
 
 /// A module item.
@@ -1146,6 +1595,12 @@ pub enum ModuleItem {
         /// Location of the entire import item.
         location: Location,
         path: ImportPath,
+
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        attributes: Vec<Attribute>,
     },
 
     /// An interface module item.
@@ -1163,6 +1618,10 @@ pub enum ModuleItem {
     /// A type alias module item.
     #[cfg_attr(feature = "serde", serde(rename = "type_alias_module_item"))]
     TypeAlias(TypeAlias),
+
+    /// An inline module item.
+    #[cfg_attr(feature = "serde", serde(rename = "inline_module_item"))]
+    Module(InlineModule),
 }
 
 impl ModuleItem {
@@ -1199,6 +1658,10 @@ impl ModuleItem {
             | Self::TypeAlias(TypeAlias {
                 name: IdentifierAST { location, .. },
                 ..
+            })
+            | Self::Module(InlineModule {
+                name: IdentifierAST { location, .. },
+                ..
             }) => *location,
         }
     }
@@ -1235,6 +1698,10 @@ impl ModuleItem {
             | Self::TypeAlias(TypeAlias {
                 name: IdentifierAST { id, .. },
                 ..
+            })
+            | Self::Module(InlineModule {
+                name: IdentifierAST { id, .. },
+                ..
             }) => Some(*id),
             Self::Import { .. } => None,
         }
@@ -1263,6 +1730,7 @@ impl ModuleItem {
             Self::Struct { .. } => ModuleItemKind::Struct,
             Self::TupleLikeStruct { .. } => ModuleItemKind::TupleLikeStruct,
             Self::TypeAlias(..) => ModuleItemKind::TypeAlias,
+            Self::Module(..) => ModuleItemKind::Module,
         }
     }
 
@@ -1276,6 +1744,7 @@ impl ModuleItem {
             | Self::TupleLikeStruct(TupleLikeStruct { visibility, .. })
             | Self::Interface(Interface { visibility, .. })
             | Self::TypeAlias(TypeAlias { visibility, .. })
+            | Self::Module(InlineModule { visibility, .. })
             | Self::Function(Function {
                 signature: FunctionSignature { visibility, .. },
                 ..
@@ -1284,6 +1753,25 @@ impl ModuleItem {
         }
     }
 
+    /// Returns attributes of the item.
+    #[inline]
+    #[must_use]
+    pub fn attributes(&self) -> &[Attribute] {
+        match self {
+            Self::Enum(Enum { attributes, .. })
+            | Self::Struct(Struct { attributes, .. })
+            | Self::TupleLikeStruct(TupleLikeStruct { attributes, .. })
+            | Self::Interface(Interface { attributes, .. })
+            | Self::TypeAlias(TypeAlias { attributes, .. })
+            | Self::Module(InlineModule { attributes, .. })
+            | Self::Import { attributes, .. }
+            | Self::Function(Function {
+                signature: FunctionSignature { attributes, .. },
+                ..
+            }) => attributes,
+        }
+    }
+
     /// Returns the visibility of the item.
     ///
     /// # Panics
@@ -1319,6 +1807,9 @@ pub enum ModuleItemKind {
 
     #[display(fmt = "type alias")]
     TypeAlias,
+
+    #[display(fmt = "module")]
+    Module,
 }
 
 impl From<ModuleItemKind> for String {
@@ -1329,15 +1820,19 @@ impl From<ModuleItemKind> for String {
 }
 
 /// An enum item, e.g. `None`, `Ok(T)`, `A { b: T }`.
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "kind"))]
 pub enum EnumItem {
-    /// Just an identifier, e.g. `None` in `enum Option[T] { Some(T), None }`.
+    /// Just an identifier, e.g. `None` in `enum Option[T] { Some(T), None }`,
+    /// optionally with an explicit discriminant, e.g. `A = 1`.
     #[cfg_attr(feature = "serde", serde(rename = "identifier_item"))]
     Just {
         name: IdentifierAST,
 
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        discriminant: Option<Expression>,
+
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         docstring: Option<String>,
     },
@@ -1392,7 +1887,7 @@ pub struct Function {
     pub signature: FunctionSignature,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub body: Option<Vec<Statement>>,
+    pub body: Option<StatementsBlock>,
 }
 
 /// A function signature - information about function except a block.
@@ -1411,6 +1906,14 @@ pub struct FunctionSignature {
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub docstring: Option<String>,
+
+    /// Attributes of the function. Only module items can have attributes,
+    /// so it is always empty for methods.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attributes: Vec<Attribute>,
 }
 
 /// A function parameter, e.g. `self`, `self: Self`, `a: uint32`.
@@ -1438,7 +1941,8 @@ pub struct SelfFunctionParameter {
     pub ty: Option<Type>,
 }
 
-/// A function parameter that is not `self`, e.g. `a: uint32`.
+/// A function parameter that is not `self`, e.g. `a: uint32` or
+/// `punctuation: String = "!"`.
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NotSelfFunctionParameter {
@@ -1446,6 +1950,10 @@ pub struct NotSelfFunctionParameter {
 
     #[cfg_attr(feature = "serde", serde(rename = "type"))]
     pub ty: Type,
+
+    /// The value used when the argument is omitted in a call.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub default_value: Option<Expression>,
 }
 
 /// A Stellar module.
@@ -1492,3 +2000,168 @@ where
     let s = String::deserialize(deserializer)?;
     T::from_str(&s).map_err(serde::de::Error::custom)
 }
+
+/// Implements [`HasLocation`] for nodes, that already have an inherent `location()` method.
+macro_rules! delegate_has_location {
+    ($($ty:ty),*) => {
+        $(
+            impl HasLocation for $ty {
+                #[inline]
+                fn location(&self) -> Location {
+                    <$ty>::location(self)
+                }
+            }
+        )*
+    };
+}
+
+delegate_has_location!(
+    Literal,
+    NegativeNumericLiteral,
+    Pattern,
+    StructFieldPattern,
+    Type,
+    Expression,
+    Statement,
+    ModuleItem
+);
+
+impl HasLocation for IdentifierAST {
+    #[inline]
+    fn location(&self) -> Location {
+        self.location
+    }
+}
+
+impl HasLocation for Path {
+    #[inline]
+    fn location(&self) -> Location {
+        self.location
+    }
+}
+
+impl HasLocation for ImportPath {
+    #[inline]
+    fn location(&self) -> Location {
+        self.as_.map_or(self.path.location, |as_| Location {
+            end: as_.location.end,
+            ..self.path.location
+        })
+    }
+}
+
+impl HasLocation for TypeConstructor {
+    #[inline]
+    fn location(&self) -> Location {
+        self.location
+    }
+}
+
+impl HasLocation for BinaryOperator {
+    #[inline]
+    fn location(&self) -> Location {
+        self.location
+    }
+}
+
+impl HasLocation for PrefixOperator {
+    #[inline]
+    fn location(&self) -> Location {
+        self.location
+    }
+}
+
+impl HasLocation for PostfixOperator {
+    #[inline]
+    fn location(&self) -> Location {
+        self.location
+    }
+}
+
+impl HasLocation for GenericParameter {
+    #[inline]
+    fn location(&self) -> Location {
+        self.name.location
+    }
+}
+
+impl HasLocation for WherePredicate {
+    #[inline]
+    fn location(&self) -> Location {
+        self.ty.location()
+    }
+}
+
+impl HasLocation for LambdaFunctionParameter {
+    #[inline]
+    fn location(&self) -> Location {
+        self.name.location
+    }
+}
+
+impl HasLocation for MatchExpressionItem {
+    #[inline]
+    fn location(&self) -> Location {
+        Location {
+            end: self.right.location().end,
+            ..self.left.location()
+        }
+    }
+}
+
+impl HasLocation for StructFieldExpression {
+    #[inline]
+    fn location(&self) -> Location {
+        self.value
+            .as_ref()
+            .map_or(self.name.location, |value| Location {
+                end: value.location().end,
+                ..self.name.location
+            })
+    }
+}
+
+impl HasLocation for EnumItem {
+    #[inline]
+    fn location(&self) -> Location {
+        match self {
+            Self::Just { name, .. } | Self::TupleLike { name, .. } | Self::Struct { name, .. } => {
+                name.location
+            }
+        }
+    }
+}
+
+impl HasLocation for TupleField {
+    #[inline]
+    fn location(&self) -> Location {
+        self.ty.location()
+    }
+}
+
+impl HasLocation for StructField {
+    #[inline]
+    fn location(&self) -> Location {
+        self.name.location
+    }
+}
+
+impl HasLocation for Function {
+    #[inline]
+    fn location(&self) -> Location {
+        self.signature.name.location
+    }
+}
+
+impl HasLocation for FunctionParameter {
+    #[inline]
+    fn location(&self) -> Location {
+        match self {
+            Self::NotSelfParameter(parameter) => Location {
+                end: parameter.ty.location().end,
+                ..parameter.pattern.location()
+            },
+            Self::SelfParameter(parameter) => parameter.self_location,
+        }
+    }
+}
@@ -1,4 +1,6 @@
-//! Defines a [`Precedence`] enum for different operator precedences.
+//! Defines a [`Precedence`] enum for different operator precedences and
+//! [`precedence()`] - the table of precedences and associativities of binary
+//! operators, that is used by the parser.
 //!
 //! # Operator Precedence
 //!
@@ -8,6 +10,8 @@
 //!
 //! See [`Precedence`] for more details.
 
+use crate::RawBinaryOperator;
+
 /// Defines an enum representing different operator precedences.
 ///
 /// In Stellar programming language, operators have different levels of precedence.
@@ -23,7 +27,7 @@ pub enum Precedence {
     Lowest,
 
     /// Precedence corresponding to assignment operators: `=`, `+=`, `-=`,
-    /// `*=`, `/=`, `^=`, `|=`.
+    /// `*=`, `/=`, `%=`, `|=`, `&=`.
     Assign,
 
     /// Precedence corresponding to binary expressions with `||` operator.
@@ -38,6 +42,9 @@ pub enum Precedence {
     /// Precedence corresponding to binary expressions with `^` operator.
     Xor,
 
+    /// Precedence corresponding to binary expressions with `&` operator.
+    And,
+
     /// Precedence corresponding to binary expressions with comparison operators:
     /// `<`, `<=`, `>`, `>=`, `==` and `!=`.
     Comparison,
@@ -91,3 +98,66 @@ pub enum Precedence {
     /// ```
     GenericArgument,
 }
+
+/// Defines how binary operators with the same precedence are grouped.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Associativity {
+    /// `a - b - c` is `(a - b) - c`.
+    Left,
+
+    /// `a op b op c` is `a op (b op c)`.
+    Right,
+}
+
+/// Returns the precedence and the associativity of a binary operator.
+///
+/// Operators from the highest precedence to the lowest one:
+///
+/// - `%`,
+/// - `**`,
+/// - `*`, `/`,
+/// - `+`, `-`,
+/// - `<<`, `>>`,
+/// - `==`, `!=`, `<`, `<=`, `>`, `>=`,
+/// - `&`,
+/// - `|`,
+/// - `&&`,
+/// - `||`,
+/// - `=`, `+=`, `-=`, `*=`, `/=`, `%=`, `|=`, `&=`.
+///
+/// All binary operators are currently left associative, e.g. `a = b = c` is
+/// parsed as `(a = b) = c`.
+#[must_use]
+pub const fn precedence(operator: RawBinaryOperator) -> (Precedence, Associativity) {
+    match operator {
+        RawBinaryOperator::Percent => (Precedence::Mod, Associativity::Left),
+        RawBinaryOperator::DoubleAsterisk => (Precedence::Power, Associativity::Left),
+        RawBinaryOperator::Asterisk | RawBinaryOperator::Slash => {
+            (Precedence::Product, Associativity::Left)
+        }
+        RawBinaryOperator::Plus | RawBinaryOperator::Minus => {
+            (Precedence::Sum, Associativity::Left)
+        }
+        RawBinaryOperator::LeftShift | RawBinaryOperator::RightShift => {
+            (Precedence::Shift, Associativity::Left)
+        }
+        RawBinaryOperator::DoubleEq
+        | RawBinaryOperator::BangEq
+        | RawBinaryOperator::Less
+        | RawBinaryOperator::LessEq
+        | RawBinaryOperator::Greater
+        | RawBinaryOperator::GreaterEq => (Precedence::Comparison, Associativity::Left),
+        RawBinaryOperator::Ampersand => (Precedence::And, Associativity::Left),
+        RawBinaryOperator::Or => (Precedence::Or, Associativity::Left),
+        RawBinaryOperator::DoubleAmpersand => (Precedence::DoubleAmpersand, Associativity::Left),
+        RawBinaryOperator::DoubleOr => (Precedence::DoubleOr, Associativity::Left),
+        RawBinaryOperator::Eq
+        | RawBinaryOperator::PlusEq
+        | RawBinaryOperator::MinusEq
+        | RawBinaryOperator::AsteriskEq
+        | RawBinaryOperator::SlashEq
+        | RawBinaryOperator::PercentEq
+        | RawBinaryOperator::OrEq
+        | RawBinaryOperator::AmpersandEq => (Precedence::Assign, Associativity::Left),
+    }
+}
@@ -3,8 +3,9 @@
 use derive_more::Display;
 use paste::paste;
 use stellar_filesystem::location::Location;
+use stellar_interner::{builtin_identifiers, IdentifierId};
 
-use crate::precedence::Precedence;
+use crate::precedence::{precedence, Precedence};
 
 /// Represents error that scanning process can fail with.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Display)]
@@ -35,10 +36,14 @@ pub enum RawLexError {
     ExponentRequiresDecimalMantissa,
     #[display(fmt = "number contains no digits")]
     NumberContainsNoDigits,
+    #[display(fmt = "identifier is too long")]
+    IdentifierTooLong,
     #[display(fmt = "invalid byte escape sequence")]
     InvalidByteEscapeSequence,
     #[display(fmt = "invalid digit")]
     InvalidDigit,
+    #[display(fmt = "invalid suffix of number literal")]
+    InvalidNumberSuffix,
     #[display(fmt = "invalid radix point")]
     InvalidRadixPoint,
     #[display(fmt = "invalid Unicode escape sequence")]
@@ -119,6 +124,22 @@ macro_rules! define_keywords {
                 }
             }
 
+            impl Keyword {
+                /// Returns a keyword with a given identifier ID, or `None` if
+                /// the identifier is not a keyword. See
+                /// [`IdentifierId::is_keyword()`] for more details.
+                #[inline]
+                #[must_use]
+                pub const fn from_identifier(id: IdentifierId) -> Option<Self> {
+                    match id {
+                        $(
+                            builtin_identifiers::[<$keyword:upper>] => Some(Self::[<$keyword:camel>]),
+                        )*
+                        _ => None,
+                    }
+                }
+            }
+
             /// Convert a string into a keyword.
             pub fn resolve_keyword(string: impl AsRef<str>) -> Option<Keyword> {
                 match string.as_ref() {
@@ -172,7 +193,7 @@ macro_rules! define_punctuators {
 define_keywords! {
     as, defer, else, enum, for, fun, if, pub, return,
     struct, type, let, where, while, match, import, break,
-    continue, dyn, loop, interface, implements
+    continue, dyn, loop, interface, implements, mut, module
 }
 
 define_punctuators! {
@@ -434,6 +455,9 @@ macro_rules! map_precedences {
             fn from(value: Punctuator) -> Self {
                 match value {
                     $($(| Punctuator::$punctuator)* => Precedence::$precedence,)*
+                    _ if RawToken::Punctuator(value).is_binary_operator() => {
+                        precedence(value.into()).0
+                    }
                     _ => Precedence::Lowest
                 }
             }
@@ -441,18 +465,11 @@ macro_rules! map_precedences {
     };
 }
 
+// Precedences of binary operators are defined in [`precedence()`].
 map_precedences! {
-    DoubleOr => DoubleOr,
-    DoubleAmpersand => DoubleAmpersand,
-    Or => Or,
     Caret => Xor,
-    Eq, PlusEq, MinusEq, AsteriskEq, SlashEq, OrEq, CaretEq, PercentEq => Assign,
-    DoubleEq, BangEq, Less, LessEq, Greater, GreaterEq => Comparison,
-    LeftShift, RightShift => Shift,
+    CaretEq => Assign,
     OpenBracket => GenericArgument,
-    Plus, Minus => Sum,
-    Asterisk, Slash => Product,
-    Percent => Mod,
     OpenParent => Call,
     Dot => Field,
     Tilde, DoublePlus, DoubleMinus, Bang, QuestionMark => Unastellar,
@@ -18,11 +18,11 @@
 use stellar_filesystem::location::Location;
 
 use crate::{
-    BinaryOperator, Enum, Expression, Function, GenericParameter, IdentifierAST, ImportPath,
-    Interface, LambdaFunctionParameter, Literal, MatchExpressionItem, Module, ModuleItem,
-    NegativeNumericLiteral, Path, Pattern, PostfixOperator, PrefixOperator, Statement, Struct,
-    StructField, StructFieldExpression, StructFieldPattern, TupleField, TupleLikeStruct, Type,
-    TypeAlias, TypeConstructor, WherePredicate,
+    BinaryOperator, CallArgument, Enum, Expression, Function, GenericParameter, IdentifierAST,
+    ImportPath, InlineModule, Interface, LambdaFunctionParameter, Literal, MatchExpressionItem,
+    Module, ModuleItem, NegativeNumericLiteral, Path, Pattern, PostfixOperator, PrefixOperator,
+    Statement, StatementsBlock, Struct, StructField, StructFieldExpression, StructFieldPattern,
+    TupleField, TupleLikeStruct, Type, TypeAlias, TypeConstructor, WherePredicate,
 };
 
 /// Allows to traverse AST.
@@ -43,10 +43,18 @@ pub trait Visitor {
             ModuleItem::Enum(enum_) => self.visit_enum(enum_),
             ModuleItem::Interface(interface) => self.visit_interface(interface),
             ModuleItem::Function(function) => self.visit_function(function),
-            ModuleItem::Import { location, path } => self.visit_import(*location, path),
+            ModuleItem::Import { location, path, .. } => self.visit_import(*location, path),
             ModuleItem::Struct(struct_) => self.visit_struct(struct_),
             ModuleItem::TupleLikeStruct(tl_struct) => self.visit_tuple_like_struct(tl_struct),
             ModuleItem::TypeAlias(alias) => self.visit_type_alias(alias),
+            ModuleItem::Module(module) => self.visit_inline_module(module),
+        }
+    }
+
+    /// Visits an inline module.
+    fn visit_inline_module(&mut self, module: &InlineModule) {
+        for item in &module.items {
+            self.visit_module_item(item);
         }
     }
 
@@ -191,34 +199,59 @@ pub trait Visitor {
     }
 
     /// Visits a statements block.
-    fn visit_statements_block(&mut self, statements: &[Statement]) {
-        for statement in statements {
+    fn visit_statements_block(&mut self, block: &StatementsBlock) {
+        for statement in &block.statements {
             self.visit_statement(statement);
         }
+
+        if let Some(tail_expression) = &block.tail_expression {
+            self.visit_expression(tail_expression);
+        }
     }
 
     /// Visits a statement.
     fn visit_statement(&mut self, statement: &Statement) {
         match statement {
-            Statement::Break { location } => self.visit_break_statement(*location),
-            Statement::Continue { location } => self.visit_continue_statement(*location),
-            Statement::Defer { call } => self.visit_defer_expression(call),
+            Statement::Break {
+                location,
+                label,
+                value,
+            } => self.visit_break_statement(*location, *label, value.as_ref()),
+            Statement::Continue { location, label } => {
+                self.visit_continue_statement(*location, *label);
+            }
+            Statement::Defer { call, .. } => self.visit_defer_expression(call),
             Statement::Expression {
                 expression,
                 has_semicolon,
             } => self.visit_expression_statement(expression, *has_semicolon),
-            Statement::Let { pattern, value, ty } => {
-                self.visit_let_statement(pattern, value, ty.as_ref());
+            Statement::Let {
+                pattern,
+                value,
+                ty,
+                r#else,
+                ..
+            } => {
+                self.visit_let_statement(pattern, value, ty.as_ref(), r#else.as_ref());
             }
-            Statement::Return { expression } => self.visit_return_statement(expression),
+            Statement::Return { expression, .. } => self.visit_return_statement(expression),
         }
     }
 
     /// Visits a break statement.
-    fn visit_break_statement(&mut self, location: Location) {}
+    fn visit_break_statement(
+        &mut self,
+        location: Location,
+        label: Option<IdentifierAST>,
+        value: Option<&Expression>,
+    ) {
+        if let Some(value) = value {
+            self.visit_expression(value);
+        }
+    }
 
     /// Visits a continue statement.
-    fn visit_continue_statement(&mut self, location: Location) {}
+    fn visit_continue_statement(&mut self, location: Location, label: Option<IdentifierAST>) {}
 
     /// Visits a defer expression.
     fn visit_defer_expression(&mut self, call: &Expression) {}
@@ -229,13 +262,23 @@ pub trait Visitor {
     }
 
     /// Visits a let statement.
-    fn visit_let_statement(&mut self, pattern: &Pattern, value: &Expression, ty: Option<&Type>) {
+    fn visit_let_statement(
+        &mut self,
+        pattern: &Pattern,
+        value: &Expression,
+        ty: Option<&Type>,
+        r#else: Option<&StatementsBlock>,
+    ) {
         self.visit_pattern(pattern);
         self.visit_expression(value);
 
         if let Some(ty) = ty {
             self.visit_type(ty);
         }
+
+        if let Some(r#else) = r#else {
+            self.visit_statements_block(r#else);
+        }
     }
 
     /// Visits a return statement.
@@ -251,6 +294,7 @@ pub trait Visitor {
                 location,
                 identifier,
                 pattern,
+                ..
             } => self.visit_identifier_pattern(*location, *identifier, pattern),
             Pattern::List {
                 location,
@@ -482,6 +526,20 @@ pub trait Visitor {
             } => {
                 self.visit_field_access_expression(*location, left, *right);
             }
+            Expression::TupleIndex {
+                location,
+                left,
+                index,
+            } => {
+                self.visit_tuple_index_expression(*location, left, *index);
+            }
+            Expression::Index {
+                location,
+                left,
+                index,
+            } => {
+                self.visit_index_expression(*location, left, index);
+            }
             Expression::Identifier(identifier) => self.visit_identifier_expression(*identifier),
             Expression::List { location, elements } => {
                 self.visit_list_expression(*location, elements);
@@ -492,7 +550,7 @@ pub trait Visitor {
                 if_blocks,
                 r#else,
             } => {
-                self.visit_if_expression(*location, if_blocks, r#else.as_deref());
+                self.visit_if_expression(*location, if_blocks, r#else.as_ref());
             }
             Expression::Lambda {
                 location,
@@ -504,9 +562,10 @@ pub trait Visitor {
             }
             Expression::Loop {
                 location,
+                label,
                 statements_block,
             } => {
-                self.visit_loop_expression(*location, statements_block);
+                self.visit_loop_expression(*location, *label, statements_block);
             }
             Expression::Match {
                 location,
@@ -539,18 +598,20 @@ pub trait Visitor {
                 location,
                 left,
                 fields,
+                base,
             } => {
-                self.visit_struct_expression(*location, left, fields);
+                self.visit_struct_expression(*location, left, fields, base.as_deref());
             }
             Expression::Tuple { location, elements } => {
                 self.visit_tuple_expression(*location, elements);
             }
             Expression::While {
                 location,
+                label,
                 condition,
                 statements_block,
             } => {
-                self.visit_while_expression(*location, condition, statements_block);
+                self.visit_while_expression(*location, *label, condition, statements_block);
             }
             Expression::TypeArguments {
                 location,
@@ -586,12 +647,12 @@ pub trait Visitor {
         &mut self,
         location: Location,
         callee: &Expression,
-        arguments: &[Expression],
+        arguments: &[CallArgument],
     ) {
         self.visit_expression(callee);
 
         for argument in arguments {
-            self.visit_expression(argument);
+            self.visit_expression(&argument.value);
         }
     }
 
@@ -605,6 +666,22 @@ pub trait Visitor {
         self.visit_expression(left);
     }
 
+    /// Visits a tuple index expression.
+    fn visit_tuple_index_expression(&mut self, location: Location, left: &Expression, index: u32) {
+        self.visit_expression(left);
+    }
+
+    /// Visits an index expression.
+    fn visit_index_expression(
+        &mut self,
+        location: Location,
+        left: &Expression,
+        index: &Expression,
+    ) {
+        self.visit_expression(left);
+        self.visit_expression(index);
+    }
+
     /// Visits an identifier expression.
     fn visit_identifier_expression(&mut self, identifier: IdentifierAST) {}
 
@@ -622,8 +699,8 @@ pub trait Visitor {
     fn visit_if_expression(
         &mut self,
         location: Location,
-        if_blocks: &[(Expression, Vec<Statement>)],
-        r#else: Option<&[Statement]>,
+        if_blocks: &[(Expression, StatementsBlock)],
+        r#else: Option<&StatementsBlock>,
     ) {
         for (condition, block) in if_blocks {
             self.visit_expression(condition);
@@ -665,7 +742,12 @@ pub trait Visitor {
     }
 
     /// Visits a loop expression.
-    fn visit_loop_expression(&mut self, location: Location, statements_block: &[Statement]) {
+    fn visit_loop_expression(
+        &mut self,
+        location: Location,
+        label: Option<IdentifierAST>,
+        statements_block: &StatementsBlock,
+    ) {
         self.visit_statements_block(statements_block);
     }
 
@@ -686,6 +768,11 @@ pub trait Visitor {
     /// Visits a match expression item.
     fn visit_match_expression_item(&mut self, item: &MatchExpressionItem) {
         self.visit_pattern(&item.left);
+
+        if let Some(guard) = &item.guard {
+            self.visit_expression(guard);
+        }
+
         self.visit_expression(&item.right);
     }
 
@@ -715,7 +802,7 @@ pub trait Visitor {
     }
 
     /// Visits a statements block expression.
-    fn visit_statements_block_expression(&mut self, location: Location, block: &[Statement]) {
+    fn visit_statements_block_expression(&mut self, location: Location, block: &StatementsBlock) {
         self.visit_statements_block(block);
     }
 
@@ -725,9 +812,14 @@ pub trait Visitor {
         location: Location,
         left: &Expression,
         fields: &[StructFieldExpression],
+        base: Option<&Expression>,
     ) {
         self.visit_expression(left);
         self.visit_struct_field_expressions(fields);
+
+        if let Some(base) = base {
+            self.visit_expression(base);
+        }
     }
 
     /// Visits struct field expressions.
@@ -755,8 +847,9 @@ pub trait Visitor {
     fn visit_while_expression(
         &mut self,
         location: Location,
+        label: Option<IdentifierAST>,
         condition: &Expression,
-        statements_block: &[Statement],
+        statements_block: &StatementsBlock,
     ) {
         self.visit_expression(condition);
 
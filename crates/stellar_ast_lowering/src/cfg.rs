@@ -0,0 +1,141 @@
+//! Evaluates `#[cfg(...)]` attributes of module items:
+//!
+//! ```stellar
+//! #[cfg(test)]
+//! fun only_in_tests() {}
+//!
+//! #[cfg(not(any(windows, macos)))]
+//! fun only_on_other_platforms() {}
+//! ```
+//!
+//! A predicate is either a flag, that is satisfied when it is enabled in
+//! [`Config::cfg_options()`], or one of the combinators: `not(...)`,
+//! `any(...)` and `all(...)`. Items with malformed predicates are removed
+//! as if their predicates were not satisfied.
+//!
+//! [`Config::cfg_options()`]: stellar_database::Config::cfg_options
+
+use stellar_ast::{Attribute, AttributeArgument};
+use stellar_filesystem::location::Location;
+
+use crate::{diagnostics::MalformedCfgPredicate, LowerToHir};
+
+impl LowerToHir<'_> {
+    /// Returns `true` if predicates of all `#[cfg(...)]` attributes in a
+    /// given list are satisfied.
+    pub(crate) fn is_cfg_enabled(&mut self, attributes: &[Attribute]) -> bool {
+        let mut enabled = true;
+
+        for attribute in attributes {
+            if attribute.name.id.as_str() != "cfg" {
+                continue;
+            }
+
+            // Every attribute is evaluated, so that all malformed predicates
+            // are reported.
+            enabled &= self.evaluate_cfg_attribute(attribute);
+        }
+
+        enabled
+    }
+
+    fn evaluate_cfg_attribute(&mut self, attribute: &Attribute) -> bool {
+        match attribute.arguments.as_deref() {
+            Some([predicate]) => self
+                .evaluate_cfg_predicate(attribute.location, predicate)
+                .unwrap_or(false),
+            _ => {
+                self.add_malformed_cfg_predicate_diagnostic(
+                    attribute.location,
+                    attribute.location,
+                    "expected exactly one predicate, e.g. `#[cfg(test)]`",
+                );
+
+                false
+            }
+        }
+    }
+
+    /// Evaluates a predicate. Returns `None` if the predicate is malformed.
+    fn evaluate_cfg_predicate(
+        &mut self,
+        attribute_location: Location,
+        predicate: &AttributeArgument,
+    ) -> Option<bool> {
+        match predicate {
+            AttributeArgument::Identifier(flag) => {
+                Some(self.state.config().cfg_options().is_enabled(flag.id))
+            }
+            AttributeArgument::String { location, .. } => {
+                self.add_malformed_cfg_predicate_diagnostic(
+                    attribute_location,
+                    *location,
+                    "expected a flag, `not(...)`, `any(...)` or `all(...)`",
+                );
+
+                None
+            }
+            AttributeArgument::Call {
+                location,
+                name,
+                arguments,
+            } => {
+                let values = arguments
+                    .iter()
+                    .map(|argument| self.evaluate_cfg_predicate(attribute_location, argument))
+                    .collect::<Vec<_>>();
+
+                match name.id.as_str() {
+                    "not" => {
+                        if let [value] = values[..] {
+                            value.map(|value| !value)
+                        } else {
+                            self.add_malformed_cfg_predicate_diagnostic(
+                                attribute_location,
+                                *location,
+                                "`not` expects exactly one predicate",
+                            );
+
+                            None
+                        }
+                    }
+                    "any" => values
+                        .into_iter()
+                        .collect::<Option<Vec<_>>>()
+                        .map(|values| values.into_iter().any(|value| value)),
+                    "all" => values
+                        .into_iter()
+                        .collect::<Option<Vec<_>>>()
+                        .map(|values| values.into_iter().all(|value| value)),
+                    _ => {
+                        self.add_malformed_cfg_predicate_diagnostic(
+                            attribute_location,
+                            name.location,
+                            format!(
+                                "unknown predicate `{}`, expected `not`, `any` or `all`",
+                                name.id
+                            ),
+                        );
+
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_malformed_cfg_predicate_diagnostic(
+        &mut self,
+        attribute_location: Location,
+        predicate_location: Location,
+        reason: impl Into<String>,
+    ) {
+        self.state
+            .diagnostics_mut()
+            .add_diagnostic(MalformedCfgPredicate::new(
+                attribute_location,
+                predicate_location,
+                reason.into(),
+            ));
+    }
+}
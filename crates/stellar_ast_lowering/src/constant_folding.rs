@@ -0,0 +1,442 @@
+//! Constant folding pass over HIR.
+//!
+//! The pass:
+//! * evaluates arithmetic, comparison and boolean operators applied to literals.
+//! * short-circuits `&&` and `||` when the left operand is a boolean literal.
+//! * concatenates string literals joined with `+`.
+//! * collapses `if` expressions with trivially true or false conditions into
+//!   the taken branch and reports the branches that are never taken.
+//!
+//! Numeric literals with different suffixes, e.g. `1u8 + 2i32`, are not folded
+//! and an unsuffixed literal takes the suffix of the other operand. Folded
+//! integers, that don't fit into the type of the suffix (`int32` for
+//! unsuffixed literals), e.g. `255u8 + 1u8`, are reported as overflows.
+
+use std::mem;
+
+use stellar_ast::{NumericLiteralSuffix, PrefixOperator, RawBinaryOperator, RawPrefixOperator};
+use stellar_database::{ModuleId, State};
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::{
+    visit::{walk_expression_mut, MutVisitor},
+    Expression, Literal, Module, StatementsBlock,
+};
+use stellar_interner::IdentifierId;
+use stellar_parser::diagnostics::{FloatOverflow, IntegerOverflow};
+
+use crate::diagnostics::UnusedBranch;
+
+/// Folds constant expressions in HIR.
+pub struct ConstantFolding<'s> {
+    state: &'s mut State,
+}
+
+impl<'s> ConstantFolding<'s> {
+    /// Folds constant expressions in all the given modules.
+    pub fn run_all(state: &'s mut State, modules: &mut FxHashMap<ModuleId, Module>) {
+        for module in modules.values_mut() {
+            ConstantFolding::run(state, module);
+        }
+    }
+
+    /// Folds constant expressions in a given module.
+    pub fn run(state: &'s mut State, module: &mut Module) {
+        ConstantFolding { state }.visit_module_mut(module);
+    }
+
+    fn fold_expression(&mut self, expression: Expression) -> Expression {
+        match expression {
+            Expression::Binary {
+                location,
+                left,
+                operator,
+                right,
+            } => self.fold_binary_expression(location, *left, operator, *right),
+            Expression::Prefix {
+                location,
+                inner,
+                operator,
+            } => match (operator.raw, *inner) {
+                (RawPrefixOperator::Bang, Expression::Literal(Literal::Boolean { value, .. })) => {
+                    Expression::Literal(Literal::Boolean {
+                        value: !value,
+                        location,
+                    })
+                }
+                (_, inner) => Expression::Prefix {
+                    location,
+                    inner: Box::new(inner),
+                    operator,
+                },
+            },
+            Expression::If {
+                location,
+                if_blocks,
+                r#else,
+            } => self.fold_if_expression(location, if_blocks, r#else),
+            _ => expression,
+        }
+    }
+
+    fn fold_binary_expression(
+        &mut self,
+        location: Location,
+        left: Expression,
+        operator: stellar_ast::BinaryOperator,
+        right: Expression,
+    ) -> Expression {
+        if let (Some((left_value, left_suffix)), Some((right_value, right_suffix))) =
+            (integer_operand(&left), integer_operand(&right))
+        {
+            if suffixes_match(left_suffix, right_suffix) {
+                match fold_integer_operation(
+                    left_value,
+                    operator.raw,
+                    right_value,
+                    left_suffix.or(right_suffix),
+                    location,
+                ) {
+                    Some(Ok(folded)) => return folded,
+                    Some(Err(())) => {
+                        self.state
+                            .diagnostics_mut()
+                            .add_diagnostic(IntegerOverflow::new(location, None));
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        let folded = match (&left, operator.raw, &right) {
+            // `false && x` and `true || x` never evaluate `x`,
+            // `true && x` and `false || x` are just `x`.
+            (
+                Expression::Literal(Literal::Boolean { value, .. }),
+                RawBinaryOperator::DoubleAmpersand | RawBinaryOperator::DoubleOr,
+                _,
+            ) => {
+                if *value == (operator.raw == RawBinaryOperator::DoubleOr) {
+                    Some(Literal::Boolean {
+                        value: *value,
+                        location,
+                    })
+                } else {
+                    return right;
+                }
+            }
+            (
+                Expression::Literal(Literal::Boolean { value: left, .. }),
+                operator,
+                Expression::Literal(Literal::Boolean { value: right, .. }),
+            ) => fold_boolean_operation(*left, operator, *right)
+                .map(|value| Literal::Boolean { value, location }),
+            (
+                Expression::Literal(Literal::Float {
+                    value: left,
+                    suffix: left_suffix,
+                    ..
+                }),
+                operator,
+                Expression::Literal(Literal::Float {
+                    value: right,
+                    suffix: right_suffix,
+                    ..
+                }),
+            ) if suffixes_match(*left_suffix, *right_suffix) => match fold_float_operation(
+                *left,
+                operator,
+                *right,
+                left_suffix.or(*right_suffix),
+                location,
+            ) {
+                Some(Ok(literal)) => Some(literal),
+                Some(Err(())) => {
+                    self.state
+                        .diagnostics_mut()
+                        .add_diagnostic(FloatOverflow::new(location, None));
+
+                    None
+                }
+                None => None,
+            },
+            (
+                Expression::Literal(Literal::String { value: left, .. }),
+                operator,
+                Expression::Literal(Literal::String { value: right, .. }),
+            ) => match operator {
+                RawBinaryOperator::Plus => Some(Literal::String {
+                    value: format!("{left}{right}"),
+                    location,
+                }),
+                RawBinaryOperator::DoubleEq => Some(Literal::Boolean {
+                    value: left == right,
+                    location,
+                }),
+                RawBinaryOperator::BangEq => Some(Literal::Boolean {
+                    value: left != right,
+                    location,
+                }),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        match folded {
+            Some(literal) => Expression::Literal(literal),
+            None => Expression::Binary {
+                location,
+                left: Box::new(left),
+                operator,
+                right: Box::new(right),
+            },
+        }
+    }
+
+    fn fold_if_expression(
+        &mut self,
+        location: Location,
+        if_blocks: Vec<(Expression, StatementsBlock)>,
+        mut r#else: Option<StatementsBlock>,
+    ) -> Expression {
+        let mut remaining_if_blocks = vec![];
+        let mut if_blocks = if_blocks.into_iter();
+
+        while let Some((condition, block)) = if_blocks.next() {
+            match condition {
+                Expression::Literal(Literal::Boolean {
+                    value: false,
+                    location: condition_location,
+                }) => {
+                    self.state
+                        .diagnostics_mut()
+                        .add_diagnostic(UnusedBranch::new(condition_location, false));
+                }
+                Expression::Literal(Literal::Boolean {
+                    value: true,
+                    location: condition_location,
+                }) => {
+                    if if_blocks.len() != 0 || r#else.is_some() {
+                        self.state
+                            .diagnostics_mut()
+                            .add_diagnostic(UnusedBranch::new(condition_location, true));
+                    }
+
+                    r#else = Some(block);
+                    break;
+                }
+                condition => remaining_if_blocks.push((condition, block)),
+            }
+        }
+
+        if remaining_if_blocks.is_empty() {
+            Expression::StatementsBlock {
+                location,
+                block: r#else.unwrap_or_default(),
+            }
+        } else {
+            Expression::If {
+                location,
+                if_blocks: remaining_if_blocks,
+                r#else,
+            }
+        }
+    }
+}
+
+impl MutVisitor for ConstantFolding<'_> {
+    fn visit_expression_mut(&mut self, expression: &mut Expression) {
+        walk_expression_mut(self, expression);
+
+        let location = expression.location();
+        let expression_to_fold = mem::replace(expression, Expression::Underscore { location });
+
+        *expression = self.fold_expression(expression_to_fold);
+    }
+}
+
+/// Result of folding a numeric operation: `Err(())` means that the operation overflows.
+type NumericFoldingResult = Result<Literal, ()>;
+
+/// Returns the spelling of a folded numeric literal, e.g. `3u8`.
+fn numeric_literal_text(value: String, suffix: Option<NumericLiteralSuffix>) -> IdentifierId {
+    match suffix {
+        Some(suffix) => IdentifierId::from(format!("{value}{suffix}")),
+        None => IdentifierId::from(value),
+    }
+}
+
+fn fold_boolean_operation(left: bool, operator: RawBinaryOperator, right: bool) -> Option<bool> {
+    match operator {
+        RawBinaryOperator::Ampersand => Some(left & right),
+        RawBinaryOperator::Or => Some(left | right),
+        RawBinaryOperator::DoubleEq => Some(left == right),
+        RawBinaryOperator::BangEq => Some(left != right),
+        _ => None,
+    }
+}
+
+/// Returns `true` if literals with given suffixes can be folded together.
+fn suffixes_match(left: Option<NumericLiteralSuffix>, right: Option<NumericLiteralSuffix>) -> bool {
+    left.is_none() || right.is_none() || left == right
+}
+
+/// Returns the value and the suffix of an integer literal, that is possibly
+/// negated, e.g. `-1`.
+fn integer_operand(expression: &Expression) -> Option<(i128, Option<NumericLiteralSuffix>)> {
+    match expression {
+        Expression::Literal(Literal::Integer { value, suffix, .. }) => {
+            Some((i128::from(*value), *suffix))
+        }
+        Expression::Prefix {
+            inner, operator, ..
+        } if operator.raw == RawPrefixOperator::Minus => match inner.as_ref() {
+            Expression::Literal(Literal::Integer { value, suffix, .. }) => {
+                Some((-i128::from(*value), *suffix))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the minimum and the maximum values of an integer literal with a
+/// given suffix. Literals without suffixes are `int32` by default.
+fn integer_range(suffix: Option<NumericLiteralSuffix>) -> (i128, i128) {
+    match suffix {
+        Some(NumericLiteralSuffix::I8) => (i8::MIN.into(), i8::MAX.into()),
+        Some(NumericLiteralSuffix::I16) => (i16::MIN.into(), i16::MAX.into()),
+        None | Some(NumericLiteralSuffix::I32) => (i32::MIN.into(), i32::MAX.into()),
+        Some(NumericLiteralSuffix::I64) => (i64::MIN.into(), i64::MAX.into()),
+        Some(NumericLiteralSuffix::U8) => (0, u8::MAX.into()),
+        Some(NumericLiteralSuffix::U16) => (0, u16::MAX.into()),
+        Some(NumericLiteralSuffix::U32) => (0, u32::MAX.into()),
+        Some(NumericLiteralSuffix::U64) => (0, u64::MAX.into()),
+        Some(NumericLiteralSuffix::F32 | NumericLiteralSuffix::F64) => {
+            unreachable!("literals with float suffixes are floats")
+        }
+    }
+}
+
+/// Returns an integer literal with a given value, negated if the value is
+/// negative, e.g. `-1`, or `None` if the value doesn't fit into the type of
+/// the literal.
+fn integer_expression(
+    value: i128,
+    suffix: Option<NumericLiteralSuffix>,
+    location: Location,
+) -> Option<Expression> {
+    let (min, max) = integer_range(suffix);
+
+    if !(min..=max).contains(&value) {
+        return None;
+    }
+
+    let magnitude = u64::try_from(value.unsigned_abs()).ok()?;
+    let literal = Expression::Literal(Literal::Integer {
+        value: magnitude,
+        suffix,
+        text: numeric_literal_text(magnitude.to_string(), suffix),
+        location,
+    });
+
+    Some(if value < 0 {
+        Expression::Prefix {
+            location,
+            inner: Box::new(literal),
+            operator: PrefixOperator {
+                raw: RawPrefixOperator::Minus,
+                location,
+            },
+        }
+    } else {
+        literal
+    })
+}
+
+/// Folds an operation on integers. Arithmetic is done in `i128`, so that
+/// e.g. `1 - 2` is folded into `-1`.
+fn fold_integer_operation(
+    left: i128,
+    operator: RawBinaryOperator,
+    right: i128,
+    suffix: Option<NumericLiteralSuffix>,
+    location: Location,
+) -> Option<Result<Expression, ()>> {
+    let integer = |value: Option<i128>| {
+        Some(
+            value
+                .and_then(|value| integer_expression(value, suffix, location))
+                .ok_or(()),
+        )
+    };
+    let boolean = |value| {
+        Some(Ok(Expression::Literal(Literal::Boolean {
+            value,
+            location,
+        })))
+    };
+
+    match operator {
+        RawBinaryOperator::Plus => integer(left.checked_add(right)),
+        RawBinaryOperator::Minus => integer(left.checked_sub(right)),
+        RawBinaryOperator::Asterisk => integer(left.checked_mul(right)),
+        // Negative exponents are left for the later stages to report.
+        RawBinaryOperator::DoubleAsterisk if right >= 0 => integer(
+            u32::try_from(right)
+                .ok()
+                .and_then(|right| left.checked_pow(right)),
+        ),
+        // Division by zero is left for the later stages to report.
+        RawBinaryOperator::Slash if right != 0 => integer(Some(left / right)),
+        RawBinaryOperator::Percent if right != 0 => integer(Some(left % right)),
+        RawBinaryOperator::Ampersand => integer(Some(left & right)),
+        RawBinaryOperator::Or => integer(Some(left | right)),
+        RawBinaryOperator::DoubleEq => boolean(left == right),
+        RawBinaryOperator::BangEq => boolean(left != right),
+        RawBinaryOperator::Less => boolean(left < right),
+        RawBinaryOperator::LessEq => boolean(left <= right),
+        RawBinaryOperator::Greater => boolean(left > right),
+        RawBinaryOperator::GreaterEq => boolean(left >= right),
+        _ => None,
+    }
+}
+
+#[allow(clippy::float_cmp)]
+fn fold_float_operation(
+    left: f64,
+    operator: RawBinaryOperator,
+    right: f64,
+    suffix: Option<NumericLiteralSuffix>,
+    location: Location,
+) -> Option<NumericFoldingResult> {
+    let float = |value: f64| {
+        Some(if value.is_finite() {
+            Ok(Literal::Float {
+                value,
+                suffix,
+                // `{:?}` keeps `.0`, e.g. in `3.0`, so that the literal is
+                // not spelled as an integer.
+                text: numeric_literal_text(format!("{value:?}"), suffix),
+                location,
+            })
+        } else {
+            Err(())
+        })
+    };
+    let boolean = |value| Some(Ok(Literal::Boolean { value, location }));
+
+    match operator {
+        RawBinaryOperator::Plus => float(left + right),
+        RawBinaryOperator::Minus => float(left - right),
+        RawBinaryOperator::Asterisk => float(left * right),
+        RawBinaryOperator::Slash if right != 0.0 => float(left / right),
+        RawBinaryOperator::DoubleEq => boolean(left == right),
+        RawBinaryOperator::BangEq => boolean(left != right),
+        RawBinaryOperator::Less => boolean(left < right),
+        RawBinaryOperator::LessEq => boolean(left <= right),
+        RawBinaryOperator::Greater => boolean(left > right),
+        RawBinaryOperator::GreaterEq => boolean(left >= right),
+        _ => None,
+    }
+}
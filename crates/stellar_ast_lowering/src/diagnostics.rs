@@ -1,7 +1,10 @@
+use stellar_ast::RawBinaryOperator;
 use stellar_diagnostics::define_diagnostics;
 use stellar_filesystem::location::Location;
 
 define_diagnostics! {
+    /// Diagnostic, that occurs when a pattern is wrapped in parentheses, that
+    /// don't change its meaning, e.g. `(a)` in `let (a) = 1;`.
     diagnostic(warning) UnnecessaryGroupedPattern(
         self,
         location: Location
@@ -14,6 +17,8 @@ define_diagnostics! {
         }
     }
 
+    /// Diagnostic, that occurs when an expression is wrapped in parentheses,
+    /// that don't change its meaning, e.g. `(1)` in `let a = (1);`.
     diagnostic(warning) UnnecessaryParenthesizedExpression(
         self,
         location: Location
@@ -26,6 +31,8 @@ define_diagnostics! {
         }
     }
 
+    /// Diagnostic, that occurs when a type is wrapped in parentheses, that
+    /// don't change its meaning, e.g. `(int32)` in `let a: (int32) = 1;`.
     diagnostic(warning) UnnecessaryParenthesizedType(
         self,
         location: Location
@@ -37,4 +44,93 @@ define_diagnostics! {
             primary { self.location.end_byte_location() => "help: remove these parentheses" }
         }
     }
+
+    /// Diagnostic, that occurs when a condition of an `if` branch is known at
+    /// compile time, so the branch is either always or never taken.
+    diagnostic(warning) UnusedBranch(
+        self,
+        condition_location: Location,
+        condition_value: bool
+    ) {
+        code { "W003" }
+        message { "unused branch" }
+        labels {
+            primary { self.condition_location => format!("condition is always `{}`", self.condition_value) }
+        }
+        notes {
+            if self.condition_value {
+                "note: all branches after this one are never taken"
+            } else {
+                "note: this branch is never taken"
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when operators, which precedence is easy to
+    /// confuse, are mixed without parentheses, e.g. `a && b || c` or
+    /// `a << b + c`.
+    diagnostic(warning) AmbiguousOperatorPrecedence(
+        self,
+        inner_location: Location,
+        inner_operator: RawBinaryOperator,
+        outer_operator: RawBinaryOperator
+    ) {
+        code { "W009" }
+        message {
+            format!(
+                "`{}` and `{}` are mixed without parentheses",
+                self.inner_operator, self.outer_operator
+            )
+        }
+        labels {
+            primary {
+                self.inner_location => format!("`{}` is evaluated first", self.inner_operator)
+            }
+        }
+        suggestions {
+            Location {
+                end: self.inner_location.start,
+                ..self.inner_location
+            } => "(", "add parentheses to clarify the precedence";
+            Location {
+                start: self.inner_location.end,
+                ..self.inner_location
+            } => ")", "add parentheses to clarify the precedence"
+        }
+    }
+
+    /// Diagnostic, that occurs when a predicate of a `#[cfg(...)]` attribute
+    /// is malformed, e.g. `#[cfg(not(a, b))]`.
+    diagnostic(error) MalformedCfgPredicate(
+        self,
+        attribute_location: Location,
+        predicate_location: Location,
+        reason: String
+    ) {
+        code { "E015" }
+        message { "malformed `cfg` predicate" }
+        labels {
+            primary { self.attribute_location }
+            secondary { self.predicate_location => self.reason.clone() }
+        }
+    }
+
+    /// Diagnostic, that occurs when a parameter with a default value is
+    /// followed by a parameter without one, e.g. `a` in
+    /// `fun f(a: int32 = 1, b: int32) {}`.
+    diagnostic(error) NonTrailingDefaultParameter(
+        self,
+        default_value_location: Location,
+        required_parameter_location: Location
+    ) {
+        code { "E016" }
+        message { "parameters with default values must come after all other parameters" }
+        labels {
+            primary { self.default_value_location => "default value is specified here" }
+            secondary { self.required_parameter_location => "this parameter has no default value" }
+        }
+        notes {
+            "help: move the parameter to the end of the parameter list or remove its default value"
+        }
+    }
 }
@@ -8,8 +8,17 @@
 //! * removes grouped patterns.
 //! * converts `loop {}` into `while true {}`.
 //! * converts `interface A[T]: B[T] + C` into `interface A[T] where Self: B[T] + C`.
+//! * moves items of inline modules, e.g. `module foo { ... }`, into separate
+//!   modules.
+//! * removes module items, which `#[cfg(...)]` attributes are not satisfied
+//!   by flags in [`Config::cfg_options()`].
+//!
+//! Optionally, constant expressions in the lowered HIR can be folded
+//! with [`ConstantFolding`].
 //!
 //! See the [`stellar_hir`] crate for more details.
+//!
+//! [`Config::cfg_options()`]: stellar_database::Config::cfg_options
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png",
     html_favicon_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png"
@@ -18,9 +27,12 @@
 #[cfg(feature = "debug")]
 use std::time::Instant;
 
-use diagnostics::{UnnecessaryGroupedPattern, UnnecessaryParenthesizedExpression};
-use stellar_ast::IdentifierAST;
-use stellar_database::{ModuleId, State};
+use diagnostics::{
+    AmbiguousOperatorPrecedence, NonTrailingDefaultParameter, UnnecessaryGroupedPattern,
+    UnnecessaryParenthesizedExpression,
+};
+use stellar_ast::{BinaryOperator, IdentifierAST, RawBinaryOperator};
+use stellar_database::{ModuleData, ModuleId, State};
 use stellar_filesystem::location::Location;
 use stellar_fx_hash::FxHashMap;
 use stellar_interner::builtin_identifiers::BIG_SELF;
@@ -28,10 +40,21 @@ use stellar_parser::ParseResult;
 #[cfg(feature = "debug")]
 use tracing::trace;
 
+mod cfg;
+mod constant_folding;
 mod diagnostics;
 
+pub use constant_folding::ConstantFolding;
+pub use diagnostics::DIAGNOSTICS;
+
 pub struct LowerToHir<'s> {
     state: &'s mut State,
+
+    /// The module, that is currently being lowered.
+    module: ModuleId,
+
+    /// Inline modules, that are lowered so far.
+    inline_modules: Vec<(ModuleId, stellar_hir::Module)>,
 }
 
 /// A lowered module.
@@ -86,29 +109,39 @@ impl From<LoweredModule> for stellar_hir::Module {
 }
 
 impl<'s> LowerToHir<'s> {
+    /// Lowers parsed modules into HIR. Inline modules are allocated in the
+    /// database and returned as separate modules, but they are not added to
+    /// submodules of their parents until definitions are collected.
     pub fn run_all(
         state: &'s mut State,
         modules: Vec<ParseResult>,
     ) -> FxHashMap<ModuleId, stellar_hir::Module> {
-        modules
-            .into_iter()
-            .map(|module| {
-                #[cfg(feature = "debug")]
-                let now = Instant::now();
-
-                let (module, ast) = (module.module(), module.into_ast());
-                let hir = LowerToHir { state }.run(ast);
-
-                #[cfg(feature = "debug")]
-                trace!(
-                    "lower_ast(module = '{}') <{} us>",
-                    module.filepath(state.db()),
-                    now.elapsed().as_micros()
-                );
-
-                (module, hir)
-            })
-            .collect()
+        let mut lowered = FxHashMap::default();
+
+        for module in modules {
+            #[cfg(feature = "debug")]
+            let now = Instant::now();
+
+            let (module, ast) = (module.module(), module.into_ast());
+            let mut lower = LowerToHir {
+                state,
+                module,
+                inline_modules: vec![],
+            };
+            let hir = lower.run(ast);
+
+            lowered.extend(lower.inline_modules);
+            lowered.insert(module, hir);
+
+            #[cfg(feature = "debug")]
+            trace!(
+                "lower_ast(module = '{}') <{} us>",
+                module.filepath(state.db()),
+                now.elapsed().as_micros()
+            );
+        }
+
+        lowered
     }
 
     fn run(&mut self, ast: stellar_ast::Module) -> stellar_hir::Module {
@@ -119,12 +152,45 @@ impl<'s> LowerToHir<'s> {
         };
 
         for item in ast.items {
-            lowered.items.push(self.lower_module_item(item));
+            if self.is_cfg_enabled(item.attributes()) {
+                lowered.items.push(self.lower_module_item(item));
+            }
         }
 
         lowered
     }
 
+    /// Allocates a child module for an inline module and lowers items of
+    /// the inline module into it.
+    fn lower_inline_module(&mut self, ast: stellar_ast::InlineModule) -> stellar_hir::InlineModule {
+        let parent = self.module;
+        let db = self.state.db_mut();
+
+        let filepath = parent.filepath(db);
+        let path = parent.path(db).clone() + ast.name;
+        let module = ModuleData::alloc(db, parent.package(), ast.name, path, filepath);
+
+        module.set_parent(db, parent);
+        module.set_visibility(db, ast.visibility);
+
+        self.module = module;
+        let hir = self.run(stellar_ast::Module {
+            filepath,
+            items: ast.items,
+            docstring: ast.docstring.clone(),
+        });
+        self.module = parent;
+
+        self.inline_modules.push((module, hir));
+
+        stellar_hir::InlineModule {
+            visibility: ast.visibility,
+            name: ast.name,
+            docstring: ast.docstring,
+            attributes: ast.attributes,
+        }
+    }
+
     /// Converts a given module item AST into HIR.
     fn lower_module_item(&mut self, ast: stellar_ast::ModuleItem) -> stellar_hir::ModuleItem {
         match ast {
@@ -137,6 +203,7 @@ impl<'s> LowerToHir<'s> {
                 methods,
                 implements,
                 docstring,
+                attributes,
             }) => stellar_hir::ModuleItem::Enum(stellar_hir::Enum {
                 visibility,
                 name,
@@ -157,6 +224,7 @@ impl<'s> LowerToHir<'s> {
                         .collect()
                 }),
                 docstring,
+                attributes,
             }),
             stellar_ast::ModuleItem::Struct(stellar_ast::Struct {
                 visibility,
@@ -167,6 +235,7 @@ impl<'s> LowerToHir<'s> {
                 methods,
                 implements,
                 docstring,
+                attributes,
             }) => stellar_hir::ModuleItem::Struct(stellar_hir::Struct {
                 visibility,
                 name,
@@ -187,16 +256,20 @@ impl<'s> LowerToHir<'s> {
                         .collect()
                 }),
                 docstring,
+                attributes,
             }),
             stellar_ast::ModuleItem::Function(function) => {
                 stellar_hir::ModuleItem::Function(self.lower_function(function))
             }
-            stellar_ast::ModuleItem::Import { location, path } => {
+            stellar_ast::ModuleItem::Import { location, path, .. } => {
                 stellar_hir::ModuleItem::Import { location, path }
             }
             stellar_ast::ModuleItem::TypeAlias(alias) => {
                 stellar_hir::ModuleItem::TypeAlias(self.lower_type_alias(alias))
             }
+            stellar_ast::ModuleItem::Module(module) => {
+                stellar_hir::ModuleItem::Module(self.lower_inline_module(module))
+            }
             stellar_ast::ModuleItem::TupleLikeStruct(stellar_ast::TupleLikeStruct {
                 visibility,
                 name,
@@ -206,6 +279,7 @@ impl<'s> LowerToHir<'s> {
                 methods,
                 implements,
                 docstring,
+                attributes,
             }) => stellar_hir::ModuleItem::TupleLikeStruct(stellar_hir::TupleLikeStruct {
                 visibility,
                 name,
@@ -226,6 +300,7 @@ impl<'s> LowerToHir<'s> {
                         .collect()
                 }),
                 docstring,
+                attributes,
             }),
             stellar_ast::ModuleItem::Interface(stellar_ast::Interface {
                 visibility,
@@ -235,6 +310,7 @@ impl<'s> LowerToHir<'s> {
                 methods,
                 inherits,
                 docstring,
+                attributes,
             }) => stellar_hir::ModuleItem::Interface(stellar_hir::Interface {
                 visibility,
                 name,
@@ -272,6 +348,7 @@ impl<'s> LowerToHir<'s> {
                     .map(|method| self.lower_function(method))
                     .collect(),
                 docstring,
+                attributes,
             }),
         }
     }
@@ -285,9 +362,15 @@ impl<'s> LowerToHir<'s> {
 
     fn lower_enum_item(&mut self, ast: stellar_ast::EnumItem) -> stellar_hir::EnumItem {
         match ast {
-            stellar_ast::EnumItem::Just { name, docstring } => {
-                stellar_hir::EnumItem::Just { name, docstring }
-            }
+            stellar_ast::EnumItem::Just {
+                name,
+                discriminant,
+                docstring,
+            } => stellar_hir::EnumItem::Just {
+                name,
+                discriminant: discriminant.map(|expression| self.lower_expression(expression)),
+                docstring,
+            },
             stellar_ast::EnumItem::Struct {
                 name,
                 fields,
@@ -316,33 +399,58 @@ impl<'s> LowerToHir<'s> {
     }
     fn lower_statements_block(
         &mut self,
-        ast: Vec<stellar_ast::Statement>,
-    ) -> Vec<stellar_hir::Statement> {
-        ast.into_iter()
-            .map(|statement| self.lower_statement(statement))
-            .collect()
+        ast: stellar_ast::StatementsBlock,
+    ) -> stellar_hir::StatementsBlock {
+        stellar_hir::StatementsBlock {
+            statements: ast
+                .statements
+                .into_iter()
+                .map(|statement| self.lower_statement(statement))
+                .collect(),
+            tail_expression: ast
+                .tail_expression
+                .map(|expression| Box::new(self.lower_expression(*expression))),
+        }
     }
 
     fn lower_statement(&mut self, ast: stellar_ast::Statement) -> stellar_hir::Statement {
         match ast {
-            stellar_ast::Statement::Break { location } => {
-                stellar_hir::Statement::Break { location }
-            }
-            stellar_ast::Statement::Continue { location } => {
-                stellar_hir::Statement::Continue { location }
+            stellar_ast::Statement::Break {
+                location,
+                label,
+                value,
+            } => stellar_hir::Statement::Break {
+                location,
+                label,
+                value: value.map(|value| self.lower_expression(value)),
+            },
+            stellar_ast::Statement::Continue { location, label } => {
+                stellar_hir::Statement::Continue { location, label }
             }
-            stellar_ast::Statement::Defer { call } => {
+            stellar_ast::Statement::Defer { location, call } => {
                 let call = self.lower_expression(call);
 
-                stellar_hir::Statement::Defer { call }
+                stellar_hir::Statement::Defer { location, call }
             }
-            stellar_ast::Statement::Return { expression } => stellar_hir::Statement::Return {
+            stellar_ast::Statement::Return {
+                location,
+                expression,
+            } => stellar_hir::Statement::Return {
+                location,
                 expression: self.lower_expression(expression),
             },
-            stellar_ast::Statement::Let { pattern, value, ty } => stellar_hir::Statement::Let {
+            stellar_ast::Statement::Let {
+                location,
+                pattern,
+                value,
+                ty,
+                r#else,
+            } => stellar_hir::Statement::Let {
+                location,
                 pattern: self.lower_pattern(pattern),
                 value: self.lower_expression(value),
                 ty: ty.map(|ty| self.lower_type(ty)),
+                r#else: r#else.map(|block| self.lower_statements_block(block)),
             },
             stellar_ast::Statement::Expression {
                 expression,
@@ -374,10 +482,12 @@ impl<'s> LowerToHir<'s> {
             stellar_ast::Pattern::Identifier {
                 location,
                 identifier,
+                mutable,
                 pattern,
             } => stellar_hir::Pattern::Identifier {
                 location,
                 identifier,
+                mutable,
                 pattern: pattern.map(|pattern| Box::new(self.lower_pattern(*pattern))),
             },
             stellar_ast::Pattern::List {
@@ -456,6 +566,66 @@ impl<'s> LowerToHir<'s> {
         }
     }
 
+    /// Lowers an assignment. Compound assignments are desugared, e.g.
+    /// `x += 1` is lowered into `x = x + 1`.
+    fn lower_assignment(
+        &mut self,
+        location: Location,
+        left: stellar_ast::Expression,
+        right: stellar_ast::Expression,
+        operator: BinaryOperator,
+    ) -> stellar_hir::Expression {
+        let left = self.lower_expression(left);
+        let right = self.lower_expression(right);
+
+        let right = match operator.raw.compound_assignment_operator() {
+            Some(raw) => stellar_hir::Expression::Binary {
+                location,
+                left: Box::new(left.clone()),
+                right: Box::new(right),
+                operator: BinaryOperator {
+                    location: operator.location,
+                    raw,
+                },
+            },
+            None => right,
+        };
+
+        stellar_hir::Expression::Assign {
+            location,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    /// Warns if an operand of a binary expression is a binary expression
+    /// without parentheses, which operator is easy to confuse with the outer
+    /// one, e.g. `a && b` in `a && b || c`.
+    fn check_operand_precedence(
+        &mut self,
+        outer_operator: RawBinaryOperator,
+        operand: &stellar_ast::Expression,
+    ) {
+        let stellar_ast::Expression::Binary {
+            location,
+            operator: inner_operator,
+            ..
+        } = operand
+        else {
+            return;
+        };
+
+        if is_ambiguous_precedence(outer_operator, inner_operator.raw) {
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(AmbiguousOperatorPrecedence::new(
+                    *location,
+                    inner_operator.raw,
+                    outer_operator,
+                ));
+        }
+    }
+
     fn lower_expression(&mut self, ast: stellar_ast::Expression) -> stellar_hir::Expression {
         match ast {
             stellar_ast::Expression::Literal(literal) => stellar_hir::Expression::Literal(literal),
@@ -467,13 +637,20 @@ impl<'s> LowerToHir<'s> {
             }
             stellar_ast::Expression::Loop {
                 location,
+                label,
                 statements_block,
             } => stellar_hir::Expression::While {
                 location,
+                kind: stellar_hir::LoopKind::Loop,
+                label,
                 condition: Box::new(stellar_hir::Expression::Literal(
                     stellar_ast::Literal::Boolean {
                         value: true,
-                        location,
+                        // location of the `loop` keyword
+                        location: Location {
+                            end: location.start + "loop".len(),
+                            ..location
+                        },
                     },
                 )),
                 statements_block: self.lower_statements_block(statements_block),
@@ -500,6 +677,7 @@ impl<'s> LowerToHir<'s> {
                     .collect(),
                 return_type: return_type.map(|ty| self.lower_type(ty)),
                 value: Box::new(self.lower_expression(*value)),
+                captures: vec![],
             },
             stellar_ast::Expression::Match {
                 location,
@@ -525,6 +703,7 @@ impl<'s> LowerToHir<'s> {
                 location,
                 left,
                 fields,
+                base,
             } => stellar_hir::Expression::Struct {
                 location,
                 left: Box::new(self.lower_expression(*left)),
@@ -532,9 +711,11 @@ impl<'s> LowerToHir<'s> {
                     .into_iter()
                     .map(|field| self.lower_struct_field_expression(field))
                     .collect(),
+                base: base.map(|base| Box::new(self.lower_expression(*base))),
             },
             stellar_ast::Expression::While {
                 location,
+                label,
                 condition,
                 statements_block: body,
             } => {
@@ -546,6 +727,8 @@ impl<'s> LowerToHir<'s> {
 
                 stellar_hir::Expression::While {
                     location,
+                    kind: stellar_hir::LoopKind::While,
+                    label,
                     condition: Box::new(self.lower_expression(*condition)),
                     statements_block: self.lower_statements_block(body),
                 }
@@ -591,12 +774,25 @@ impl<'s> LowerToHir<'s> {
                 left,
                 right,
                 operator,
-            } => stellar_hir::Expression::Binary {
+            } if operator.raw.is_assignment() => {
+                self.lower_assignment(location, *left, *right, operator)
+            }
+            stellar_ast::Expression::Binary {
                 location,
-                left: Box::new(self.lower_expression(*left)),
-                right: Box::new(self.lower_expression(*right)),
+                left,
+                right,
                 operator,
-            },
+            } => {
+                self.check_operand_precedence(operator.raw, &left);
+                self.check_operand_precedence(operator.raw, &right);
+
+                stellar_hir::Expression::Binary {
+                    location,
+                    left: Box::new(self.lower_expression(*left)),
+                    right: Box::new(self.lower_expression(*right)),
+                    operator,
+                }
+            }
             stellar_ast::Expression::Call {
                 location,
                 callee,
@@ -606,7 +802,11 @@ impl<'s> LowerToHir<'s> {
                 callee: Box::new(self.lower_expression(*callee)),
                 arguments: arguments
                     .into_iter()
-                    .map(|argument| self.lower_expression(argument))
+                    .map(|argument| stellar_hir::CallArgument {
+                        label: argument.label,
+                        spread: argument.spread,
+                        value: self.lower_expression(argument.value),
+                    })
                     .collect(),
             },
             stellar_ast::Expression::As {
@@ -634,6 +834,24 @@ impl<'s> LowerToHir<'s> {
                 left: Box::new(self.lower_expression(*left)),
                 right,
             },
+            stellar_ast::Expression::TupleIndex {
+                location,
+                left,
+                index,
+            } => stellar_hir::Expression::TupleIndex {
+                location,
+                left: Box::new(self.lower_expression(*left)),
+                index,
+            },
+            stellar_ast::Expression::Index {
+                location,
+                left,
+                index,
+            } => stellar_hir::Expression::Index {
+                location,
+                left: Box::new(self.lower_expression(*left)),
+                index: Box::new(self.lower_expression(*index)),
+            },
             stellar_ast::Expression::TypeArguments {
                 location,
                 left,
@@ -662,8 +880,15 @@ impl<'s> LowerToHir<'s> {
                 .add_diagnostic(UnnecessaryParenthesizedExpression::new(location));
         }
 
+        if let Some(stellar_ast::Expression::Parenthesized { location, .. }) = ast.guard {
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(UnnecessaryParenthesizedExpression::new(location));
+        }
+
         stellar_hir::MatchExpressionItem {
             left: self.lower_pattern(ast.left),
+            guard: ast.guard.map(|guard| self.lower_expression(guard)),
             right: self.lower_expression(ast.right),
         }
     }
@@ -690,8 +915,8 @@ impl<'s> LowerToHir<'s> {
 
     fn lower_if_blocks(
         &mut self,
-        if_blocks: Vec<(stellar_ast::Expression, Vec<stellar_ast::Statement>)>,
-    ) -> Vec<(stellar_hir::Expression, Vec<stellar_hir::Statement>)> {
+        if_blocks: Vec<(stellar_ast::Expression, stellar_ast::StatementsBlock)>,
+    ) -> Vec<(stellar_hir::Expression, stellar_hir::StatementsBlock)> {
         if_blocks
             .into_iter()
             .map(|if_block| self.lower_if_block(if_block))
@@ -700,8 +925,8 @@ impl<'s> LowerToHir<'s> {
 
     fn lower_if_block(
         &mut self,
-        if_block: (stellar_ast::Expression, Vec<stellar_ast::Statement>),
-    ) -> (stellar_hir::Expression, Vec<stellar_hir::Statement>) {
+        if_block: (stellar_ast::Expression, stellar_ast::StatementsBlock),
+    ) -> (stellar_hir::Expression, stellar_hir::StatementsBlock) {
         if let stellar_ast::Expression::Parenthesized { location, .. } = if_block.0 {
             self.state
                 .diagnostics_mut()
@@ -724,6 +949,8 @@ impl<'s> LowerToHir<'s> {
         &mut self,
         ast: stellar_ast::FunctionSignature,
     ) -> stellar_hir::FunctionSignature {
+        self.check_default_parameters(&ast.parameters);
+
         stellar_hir::FunctionSignature {
             visibility: ast.visibility,
             name: ast.name,
@@ -736,6 +963,36 @@ impl<'s> LowerToHir<'s> {
             return_type: ast.return_type.map(|ty| self.lower_type(ty)),
             where_predicates: self.lower_where_predicates(ast.where_predicates),
             docstring: ast.docstring,
+            attributes: ast.attributes,
+        }
+    }
+
+    /// Reports the first parameter with a default value, that is followed
+    /// by a parameter without one, e.g. `a` in `fun f(a: int32 = 1, b: int32) {}`.
+    fn check_default_parameters(&mut self, parameters: &[stellar_ast::FunctionParameter]) {
+        let mut default_value_location = None;
+
+        for parameter in parameters {
+            let stellar_ast::FunctionParameter::NotSelfParameter(parameter) = parameter else {
+                continue;
+            };
+
+            match (&parameter.default_value, default_value_location) {
+                (Some(default_value), None) => {
+                    default_value_location = Some(default_value.location());
+                }
+                (None, Some(default_value_location)) => {
+                    self.state
+                        .diagnostics_mut()
+                        .add_diagnostic(NonTrailingDefaultParameter::new(
+                            default_value_location,
+                            parameter.pattern.location(),
+                        ));
+
+                    return;
+                }
+                _ => {}
+            }
         }
     }
 
@@ -764,6 +1021,9 @@ impl<'s> LowerToHir<'s> {
         stellar_hir::NotSelfFunctionParameter {
             pattern: self.lower_pattern(ast.pattern),
             ty: self.lower_type(ast.ty),
+            default_value: ast
+                .default_value
+                .map(|default_value| self.lower_expression(default_value)),
         }
     }
 
@@ -784,6 +1044,7 @@ impl<'s> LowerToHir<'s> {
             generic_parameters: self.lower_generic_parameters(ast.generic_parameters),
             value: self.lower_type(ast.value),
             docstring: ast.docstring,
+            attributes: ast.attributes,
         }
     }
 
@@ -919,3 +1180,24 @@ impl<'s> LowerToHir<'s> {
         }
     }
 }
+
+/// Returns `true` if an unparenthesized binary expression with the inner
+/// operator, used as an operand of the outer operator, is likely to be
+/// misread, e.g. `&&` and `||` in `a && b || c` or `<<` and `+` in
+/// `a << b + c`.
+const fn is_ambiguous_precedence(outer: RawBinaryOperator, inner: RawBinaryOperator) -> bool {
+    match outer {
+        RawBinaryOperator::DoubleOr => matches!(inner, RawBinaryOperator::DoubleAmpersand),
+        RawBinaryOperator::DoubleAmpersand => matches!(inner, RawBinaryOperator::DoubleOr),
+        RawBinaryOperator::LeftShift | RawBinaryOperator::RightShift => matches!(
+            inner,
+            RawBinaryOperator::Plus
+                | RawBinaryOperator::Minus
+                | RawBinaryOperator::Asterisk
+                | RawBinaryOperator::Slash
+                | RawBinaryOperator::Percent
+                | RawBinaryOperator::DoubleAsterisk
+        ),
+        _ => false,
+    }
+}
@@ -0,0 +1,110 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{CfgOptions, Config, PackageData, State};
+use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_hir::Module;
+use stellar_interner::{IdentifierId, PathId, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+
+fn lower(source_code: &str, flags: &[&str]) -> (State, Module) {
+    let config = Config::new()
+        .with_cfg_options(flags.iter().map(IdentifierId::from).collect::<CfgOptions>());
+    let mut state = State::new().with_config(config);
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("a").into(),
+        PathId::from("cfg.sr"),
+        source_code,
+    );
+    let module = parse_result.module();
+
+    let mut hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+    let hir = hir.remove(&module).unwrap();
+
+    (state, hir)
+}
+
+fn item_names(module: &Module) -> Vec<&'static str> {
+    module
+        .items
+        .iter()
+        .map(|item| item.name_or_panic().as_str())
+        .collect()
+}
+
+#[test]
+fn item_is_kept_when_flag_is_enabled() {
+    let source_code = "#[cfg(test)] fun foo() {} fun bar() {}";
+
+    let (state, module) = lower(source_code, &["test"]);
+    assert!(state.diagnostics().is_ok());
+    assert_eq!(item_names(&module), ["foo", "bar"]);
+
+    let (state, module) = lower(source_code, &[]);
+    assert!(state.diagnostics().is_ok());
+    assert_eq!(item_names(&module), ["bar"]);
+}
+
+#[test]
+fn combinators() {
+    let source_code = "#[cfg(not(any(a, b)))] struct Neither {}
+#[cfg(all(a, b))] struct Both {}";
+
+    let (_, module) = lower(source_code, &[]);
+    assert_eq!(item_names(&module), ["Neither"]);
+
+    let (_, module) = lower(source_code, &["b"]);
+    assert!(item_names(&module).is_empty());
+
+    let (_, module) = lower(source_code, &["a", "b"]);
+    assert_eq!(item_names(&module), ["Both"]);
+}
+
+#[test]
+fn items_of_inline_modules() {
+    let source_code = "module m { #[cfg(a)] fun foo() {} #[cfg(not(a))] fun foo() {} }";
+
+    let mut state = State::new();
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("a").into(),
+        PathId::from("cfg.sr"),
+        source_code,
+    );
+
+    let root = parse_result.module();
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+    let (_, inline_module) = hir.iter().find(|(module, _)| **module != root).unwrap();
+
+    assert_eq!(item_names(inline_module), ["foo"]);
+}
+
+#[test]
+fn malformed_predicate() {
+    let source_code = "fun foo() {}\n#[cfg(not(a, b))] fun bar() {}";
+
+    let (state, module) = lower(source_code, &[]);
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(item_names(&module), ["foo"]);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code.as_deref(), Some("E015"));
+    assert_eq!(diagnostics[0].message, "malformed `cfg` predicate");
+    assert_eq!(
+        diagnostics[0].labels[0].location,
+        Location {
+            filepath: PathId::from("cfg.sr"),
+            start: ByteOffset(13),
+            end: ByteOffset(30),
+        }
+    );
+    assert_eq!(
+        diagnostics[0].labels[1].message,
+        "`not` expects exactly one predicate"
+    );
+}
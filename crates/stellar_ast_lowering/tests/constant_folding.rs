@@ -0,0 +1,238 @@
+use serde_json::Value;
+use stellar_ast_lowering::{ConstantFolding, LowerToHir};
+use stellar_database::{PackageData, State};
+use stellar_diagnostics::diagnostic::Severity;
+use stellar_hir::Module;
+use stellar_interner::{PathId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+
+fn lower(state: &mut State, source_code: &str) -> Module {
+    let filepath = PathId::from("test.sr");
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        filepath,
+        source_code,
+    );
+    let module = parse_result.module();
+
+    let mut hir = LowerToHir::run_all(state, vec![parse_result]);
+
+    assert!(state.diagnostics().is_ok());
+
+    hir.remove(&module).unwrap()
+}
+
+/// Serializes HIR into JSON without locations, so that HIR of
+/// different source files can be compared.
+fn to_json(module: &Module) -> Value {
+    fn strip_locations(value: &mut Value) {
+        match value {
+            Value::Object(object) => {
+                object.remove("location");
+                object.values_mut().for_each(strip_locations);
+            }
+            Value::Array(array) => array.iter_mut().for_each(strip_locations),
+            _ => {}
+        }
+    }
+
+    let mut value = serde_json::to_value(module).unwrap();
+    strip_locations(&mut value);
+    value
+}
+
+/// Folds constants in the given source code and checks that the HIR
+/// matches the HIR of the expected source code.
+fn assert_folds_into(source_code: &str, expected: &str) -> State {
+    let mut state = State::new();
+    let mut module = lower(&mut state, source_code);
+    let before = to_json(&module);
+
+    ConstantFolding::run(&mut state, &mut module);
+
+    let after = to_json(&module);
+
+    assert_ne!(before, after);
+    assert_eq!(after, to_json(&lower(&mut State::new(), expected)));
+
+    state
+}
+
+fn warnings(state: &State) -> Vec<&str> {
+    state
+        .diagnostics()
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Warning)
+        .map(|diagnostic| diagnostic.code.as_deref().unwrap())
+        .collect()
+}
+
+#[test]
+fn arithmetic() {
+    let state = assert_folds_into(
+        "fun main() { let a = 1 + 2 * 3 - 4 / 2; let b = 1.5 * 2.0; let c = 2 * 600 > 1000; }",
+        "fun main() { let a = 5; let b = 3.0; let c = true; }",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn negative_result() {
+    let state = assert_folds_into(
+        "fun main() { let a = 1 - 2; let b = 1 - 2 - 3; let c = -2 * 3 < 0; }",
+        "fun main() { let a = -1; let b = -4; let c = true; }",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn suffixed_literals() {
+    assert_folds_into(
+        "fun main() { let a = 1u8 + 2; let b = 1.5f32 * 2.0f32; let c = 1u8 + 2i32; }",
+        "fun main() { let a = 3u8; let b = 3.0f32; let c = 1u8 + 2i32; }",
+    );
+}
+
+#[test]
+fn string_concatenation() {
+    assert_folds_into(
+        r#"fun main() { let a = "hello, " + "world"; }"#,
+        r#"fun main() { let a = "hello, world"; }"#,
+    );
+}
+
+#[test]
+fn short_circuit_booleans() {
+    assert_folds_into(
+        "fun main() { let a = false && foo(); let b = true || foo(); let c = true && foo(); let d = !(false || foo()); }",
+        "fun main() { let a = false; let b = true; let c = foo(); let d = !foo(); }",
+    );
+}
+
+#[test]
+fn integer_overflow() {
+    let mut state = State::new();
+    let mut module = lower(
+        &mut state,
+        "fun main() { let a = 18446744073709551615 + 1; }",
+    );
+    let before = to_json(&module);
+
+    ConstantFolding::run(&mut state, &mut module);
+
+    assert_eq!(before, to_json(&module));
+    assert_eq!(state.diagnostics().len(), 1);
+    assert_eq!(state.diagnostics().all()[0].code.as_deref(), Some("E002"));
+}
+
+/// Checks that folding a given expression is reported as an overflow and
+/// the expression is left as is.
+fn assert_overflows(expression: &str) {
+    let mut state = State::new();
+    let mut module = lower(
+        &mut state,
+        &format!("fun main() {{ let a = {expression}; }}"),
+    );
+    let before = to_json(&module);
+
+    ConstantFolding::run(&mut state, &mut module);
+
+    assert_eq!(before, to_json(&module), "{expression}");
+    assert_eq!(
+        state
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| diagnostic.code.as_deref().unwrap())
+            .collect::<Vec<_>>(),
+        ["E002"],
+        "{expression}"
+    );
+}
+
+#[test]
+fn suffixed_integer_overflow() {
+    assert_overflows("255u8 + 1u8");
+    assert_overflows("255u8 + 1");
+    assert_overflows("0u8 - 1u8");
+    assert_overflows("-128i8 - 1i8");
+
+    let state = assert_folds_into(
+        "fun main() { let a = 254u8 + 1u8; let b = -127i8 - 1i8; }",
+        "fun main() { let a = 255u8; let b = -128i8; }",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn unsuffixed_integer_overflow() {
+    assert_overflows("2147483647 + 1");
+    assert_overflows("-2147483647 - 2");
+
+    let state = assert_folds_into(
+        "fun main() { let a = 2147483646 + 1; let b = -2147483647 - 1; }",
+        "fun main() { let a = 2147483647; let b = -2147483648; }",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn dead_branch() {
+    let state = assert_folds_into(
+        "fun main() { if false { a(); } else if 1 == 1 { b(); } else { c(); } }",
+        "fun main() { { b(); } }",
+    );
+
+    assert_eq!(warnings(&state), ["W003", "W003"]);
+}
+
+#[test]
+fn partially_dead_branch() {
+    let state = assert_folds_into(
+        "fun main() { if x { a(); } else if false { b(); } else { c(); } }",
+        "fun main() { if x { a(); } else { c(); } }",
+    );
+
+    assert_eq!(warnings(&state), ["W003"]);
+}
+
+#[test]
+fn numeric_literal_spelling_in_json() {
+    let mut state = State::new();
+    let mut module = lower(
+        &mut state,
+        "fun main() { let a = 0xFF; let b = 0x10 * 2u8; let c = 1.5 * 2.0; }",
+    );
+
+    ConstantFolding::run(&mut state, &mut module);
+
+    fn collect_texts(value: &Value, texts: &mut Vec<String>) {
+        match value {
+            Value::Object(object) => {
+                if let Some(Value::String(text)) = object.get("text") {
+                    texts.push(text.clone());
+                }
+
+                object
+                    .values()
+                    .for_each(|value| collect_texts(value, texts));
+            }
+            Value::Array(array) => array.iter().for_each(|value| collect_texts(value, texts)),
+            _ => {}
+        }
+    }
+
+    let json = serde_json::to_value(&module).unwrap();
+    let mut texts = vec![];
+    collect_texts(&json, &mut texts);
+
+    assert_eq!(texts, ["0xFF", "32u8", "3.0"]);
+    assert_eq!(serde_json::from_value::<Module>(json).unwrap(), module);
+}
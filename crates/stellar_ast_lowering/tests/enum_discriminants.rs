@@ -0,0 +1,47 @@
+use serde_json::Value;
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_interner::{PathId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+
+/// Lowers a given source code and serializes items of its only enum.
+fn enum_items_json(source_code: &str) -> Vec<Value> {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        PathId::from("test.sr"),
+        source_code,
+    );
+    let module = parse_result.module();
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result])
+        .remove(&module)
+        .unwrap();
+
+    assert!(state.diagnostics().is_ok());
+
+    let Value::Array(items) = serde_json::to_value(&hir).unwrap()["items"][0]["items"].take()
+    else {
+        panic!("expected enum items");
+    };
+
+    items
+}
+
+#[test]
+fn discriminants_are_serialized() {
+    let items = enum_items_json("enum Flag { A = 1, B, C = 1 << 2 }");
+
+    assert_eq!(items[0]["discriminant"]["kind"], "literal_expression");
+    assert_eq!(items[0]["discriminant"]["value"], 1);
+
+    // Items without discriminants are serialized as before.
+    assert_eq!(items[1].get("discriminant"), None);
+
+    assert_eq!(items[2]["discriminant"]["kind"], "binary_expression");
+    assert_eq!(items[2]["discriminant"]["operator"]["raw"], "<<");
+}
@@ -0,0 +1,165 @@
+use stellar_ast::RawBinaryOperator;
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State, Symbol};
+use stellar_filesystem::location::HasLocation;
+use stellar_hir::{Expression, Function, Module, ModuleItem, Statement, StatementsBlock, Type};
+use stellar_interner::{IdentifierId, PathId, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+
+fn lower(source_code: &str) -> Module {
+    let mut state = State::new();
+    let filepath = PathId::from("test.sr");
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("a").into(),
+        filepath,
+        source_code,
+    );
+    let module = parse_result.module();
+
+    let mut hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    assert!(state.diagnostics().is_ok());
+
+    hir.remove(&module).unwrap()
+}
+
+fn text<'s>(source_code: &'s str, node: &impl HasLocation) -> &'s str {
+    &source_code[node.location()]
+}
+
+fn function_body(module: &Module) -> &StatementsBlock {
+    let ModuleItem::Function(Function {
+        body: Some(body), ..
+    }) = &module.items[0]
+    else {
+        panic!("expected function with body");
+    };
+
+    body
+}
+
+#[test]
+fn statement_locations() {
+    let source_code = "fun main() { let x: int32 = 1; defer close(); return x; }";
+    let module = lower(source_code);
+    let body = function_body(&module);
+
+    assert_eq!(text(source_code, &body.statements[0]), "let x: int32 = 1;");
+    assert_eq!(text(source_code, &body.statements[1]), "defer close();");
+    assert_eq!(text(source_code, &body.statements[2]), "return x;");
+
+    let Statement::Let { pattern, ty, .. } = &body.statements[0] else {
+        panic!("expected let statement");
+    };
+
+    assert_eq!(text(source_code, pattern), "x");
+    assert_eq!(text(source_code, ty.as_ref().unwrap()), "int32");
+}
+
+#[test]
+fn type_arguments_locations() {
+    let source_code = "fun main() { let x = new[List[int32], uint8](); }";
+    let module = lower(source_code);
+    let body = function_body(&module);
+
+    let Statement::Let { value, .. } = &body.statements[0] else {
+        panic!("expected let statement");
+    };
+    let Expression::Call { callee, .. } = value else {
+        panic!("expected call expression");
+    };
+    let Expression::TypeArguments { type_arguments, .. } = callee.as_ref() else {
+        panic!("expected type arguments expression");
+    };
+
+    assert_eq!(
+        text(source_code, callee.as_ref()),
+        "new[List[int32], uint8]"
+    );
+    assert_eq!(text(source_code, &type_arguments[0]), "List[int32]");
+    assert_eq!(text(source_code, &type_arguments[1]), "uint8");
+
+    let Type::Constructor(constructor) = &type_arguments[0] else {
+        panic!("expected type constructor");
+    };
+
+    assert_eq!(text(source_code, &constructor.arguments[0]), "int32");
+}
+
+#[test]
+fn lowered_loop_location() {
+    let source_code = "fun main() { loop { break; } }";
+    let module = lower(source_code);
+    let body = function_body(&module);
+
+    let Some(expression) = body.tail_expression.as_deref() else {
+        panic!("expected tail expression");
+    };
+    let Expression::While {
+        condition,
+        statements_block,
+        ..
+    } = expression
+    else {
+        panic!("expected while expression");
+    };
+
+    assert_eq!(text(source_code, expression), "loop { break; }");
+    assert_eq!(text(source_code, condition.as_ref()), "loop");
+    assert_eq!(text(source_code, &statements_block.statements[0]), "break");
+}
+
+#[test]
+fn module_name_location() {
+    let mut state = State::new();
+    let filepath = PathId::from("test.sr");
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("a").into(),
+        filepath,
+        "",
+    );
+
+    let name = Symbol::Module(parse_result.module()).name(state.db());
+
+    assert_eq!(name.id, IdentifierId::from("a"));
+    assert_eq!(name.location.filepath, filepath);
+}
+
+#[test]
+fn compound_assignment_locations() {
+    let source_code = "fun main() { p.x += 1; }";
+    let module = lower(source_code);
+    let body = function_body(&module);
+
+    let Statement::Expression {
+        expression: assignment @ Expression::Assign { left, right, .. },
+        ..
+    } = &body.statements[0]
+    else {
+        panic!("expected assignment");
+    };
+    let Expression::Binary {
+        left: read,
+        right: value,
+        operator,
+        ..
+    } = right.as_ref()
+    else {
+        panic!("expected binary expression");
+    };
+
+    assert_eq!(text(source_code, assignment), "p.x += 1");
+    assert_eq!(text(source_code, left.as_ref()), "p.x");
+    assert_eq!(text(source_code, read.as_ref()), "p.x");
+    assert_eq!(text(source_code, value.as_ref()), "1");
+    assert_eq!(operator.raw, RawBinaryOperator::Plus);
+    assert_eq!(&source_code[operator.location], "+=");
+}
@@ -0,0 +1,75 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_diagnostics::diagnostic::Suggestion;
+use stellar_interner::{PathId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+
+fn lower(source_code: &str) -> State {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        PathId::from("test.sr"),
+        source_code,
+    );
+
+    LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    state
+}
+
+/// Applies suggestions of the only diagnostic to the source code.
+fn apply_suggestions(source_code: &str, suggestions: &[Suggestion]) -> String {
+    let mut result = source_code.to_owned();
+
+    for suggestion in suggestions.iter().rev() {
+        result.replace_range(
+            suggestion.location.start.0..suggestion.location.end.0,
+            &suggestion.replacement,
+        );
+    }
+
+    result
+}
+
+#[test]
+fn mixed_logical_operators() {
+    let source_code = "fun main() { let x = a && b || c; }";
+    let state = lower(source_code);
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code.as_deref(), Some("W009"));
+    assert_eq!(
+        diagnostics[0].message,
+        "`&&` and `||` are mixed without parentheses"
+    );
+    assert_eq!(
+        apply_suggestions(source_code, &diagnostics[0].suggestions),
+        "fun main() { let x = (a && b) || c; }"
+    );
+}
+
+#[test]
+fn shift_of_arithmetic_expression() {
+    let source_code = "fun main() { let x = a << b + c; }";
+    let state = lower(source_code);
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code.as_deref(), Some("W009"));
+    assert_eq!(
+        apply_suggestions(source_code, &diagnostics[0].suggestions),
+        "fun main() { let x = a << (b + c); }"
+    );
+}
+
+#[test]
+fn parenthesized_operands_are_not_reported() {
+    let state = lower("fun main() { let x = (a && b) || c; let y = a << (b + c); }");
+
+    assert!(state.diagnostics().is_empty());
+}
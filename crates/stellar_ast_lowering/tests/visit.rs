@@ -0,0 +1,124 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_hir::{
+    visit::{walk_expression, walk_module_item, walk_pattern, walk_statement, walk_type},
+    visit::{MutVisitor, Visitor},
+    Expression, IdentifierAST, Module, ModuleItem, Pattern, Statement, Type,
+};
+use stellar_interner::{IdentifierId, PathId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+
+fn lower(source_code: &str) -> Module {
+    let mut state = State::new();
+    let filepath = PathId::from("test.sr");
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        filepath,
+        source_code,
+    );
+    let module = parse_result.module();
+
+    let mut hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    assert!(state.diagnostics().is_ok());
+
+    hir.remove(&module).unwrap()
+}
+
+#[derive(Default)]
+struct NodeCounter {
+    items: usize,
+    statements: usize,
+    expressions: usize,
+    patterns: usize,
+    types: usize,
+    identifiers: usize,
+}
+
+impl Visitor for NodeCounter {
+    fn visit_module_item(&mut self, item: &ModuleItem) {
+        self.items += 1;
+        walk_module_item(self, item);
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        self.statements += 1;
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        self.expressions += 1;
+        walk_expression(self, expression);
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        self.patterns += 1;
+        walk_pattern(self, pattern);
+    }
+
+    fn visit_type(&mut self, ty: &Type) {
+        self.types += 1;
+        walk_type(self, ty);
+    }
+
+    fn visit_identifier(&mut self, _: IdentifierAST) {
+        self.identifiers += 1;
+    }
+}
+
+#[test]
+fn count_nodes() {
+    let module = lower(
+        "struct Point { x: int32, y: int32 }
+
+fun sum(a: int32, b: int32): int32 {
+    let c = a + b;
+    return c;
+}",
+    );
+
+    let mut counter = NodeCounter::default();
+    counter.visit_module(&module);
+
+    assert_eq!(counter.items, 2);
+    assert_eq!(counter.statements, 2);
+    assert_eq!(counter.expressions, 4);
+    assert_eq!(counter.patterns, 3);
+    assert_eq!(counter.types, 5);
+    // `Point`, `x`, `int32`, `y`, `int32`, `sum`, `a`, `int32`, `b`, `int32`,
+    // `int32`, `c`, `a`, `b`, `c`.
+    assert_eq!(counter.identifiers, 15);
+}
+
+struct Renamer {
+    from: IdentifierId,
+    to: IdentifierId,
+}
+
+impl MutVisitor for Renamer {
+    fn visit_identifier_mut(&mut self, identifier: &mut IdentifierAST) {
+        if identifier.id == self.from {
+            identifier.id = self.to;
+        }
+    }
+}
+
+#[test]
+fn rename_identifier() {
+    let mut module = lower("fun foo(a: int32): int32 { let b = a; return b; }");
+
+    Renamer {
+        from: IdentifierId::from("b"),
+        to: IdentifierId::from("c"),
+    }
+    .visit_module_mut(&mut module);
+
+    assert_eq!(
+        module,
+        lower("fun foo(a: int32): int32 { let c = a; return c; }")
+    );
+}
@@ -0,0 +1,245 @@
+//! Defines [`SymbolDebug`] and [`IdDebug`] - adapters, that render symbols
+//! and IDs together with names of entities they refer to, e.g.
+//! ``struct `Point` (id 17, module src/geo.sr)``, so that logs and test
+//! failures are readable.
+//!
+//! IDs of entities, that are not present in the database, are rendered as
+//! `<unknown>` instead of panicking.
+
+use std::fmt;
+
+use stellar_interner::IdentifierId;
+
+use crate::{
+    Database, EnumId, EnumItemId, FieldId, FunctionId, GenericParameterId, GenericParameterScopeId,
+    InterfaceId, ModuleId, PredicateId, SignatureId, StructId, Symbol, TupleLikeStructId,
+    TypeAliasId,
+};
+
+/// Renders a [`Symbol`] together with its name and the module, that defines
+/// it, see [`Symbol::debug()`].
+#[derive(Clone, Copy)]
+pub struct SymbolDebug<'db> {
+    symbol: Symbol,
+    db: &'db Database,
+}
+
+impl<'db> SymbolDebug<'db> {
+    #[inline]
+    #[must_use]
+    pub(crate) const fn new(symbol: Symbol, db: &'db Database) -> Self {
+        Self { symbol, db }
+    }
+}
+
+impl fmt::Display for SymbolDebug<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let db = self.db;
+
+        match self.symbol {
+            Symbol::Module(id) => id.debug(db).fmt(f),
+            Symbol::Enum(id) => id.debug(db).fmt(f),
+            Symbol::Struct(id) => id.debug(db).fmt(f),
+            Symbol::Function(id) => id.debug(db).fmt(f),
+            Symbol::Interface(id) => id.debug(db).fmt(f),
+            Symbol::TupleLikeStruct(id) => id.debug(db).fmt(f),
+            Symbol::TypeAlias(id) => id.debug(db).fmt(f),
+            Symbol::EnumItem(id) => id.debug(db).fmt(f),
+            Symbol::BuiltinSymbol(id) => write!(f, "builtin type `{}`", IdentifierId::from(id)),
+        }
+    }
+}
+
+impl fmt::Debug for SymbolDebug<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Symbol {
+    /// Returns an adapter, that renders the symbol together with its name
+    /// and the module, that defines it, e.g. in logs.
+    #[inline]
+    #[must_use]
+    pub const fn debug(self, db: &Database) -> SymbolDebug<'_> {
+        SymbolDebug::new(self, db)
+    }
+}
+
+/// Renders an ID, e.g. [`StructId`], together with the name of the entity,
+/// that it refers to, see e.g. [`StructId::debug()`].
+#[derive(Clone, Copy)]
+pub struct IdDebug<'db, T> {
+    id: T,
+    db: &'db Database,
+}
+
+impl<'db, T> IdDebug<'db, T> {
+    #[inline]
+    #[must_use]
+    pub(crate) const fn new(id: T, db: &'db Database) -> Self {
+        Self { id, db }
+    }
+}
+
+impl<T> fmt::Debug for IdDebug<'_, T>
+where
+    Self: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Writes a description of a named entity, e.g. ``struct `Point` (id 17, module src/geo.sr)``,
+/// or ``struct <unknown> (id 17)`` if the entity is not present in the database.
+fn write_named(
+    f: &mut fmt::Formatter<'_>,
+    kind: &str,
+    idx: usize,
+    name_and_module: Option<(IdentifierId, ModuleId)>,
+    db: &Database,
+) -> fmt::Result {
+    match name_and_module {
+        Some((name, module)) => match module.get_data_or_none(db) {
+            Some(module) => write!(f, "{kind} `{name}` (id {idx}, module {})", module.filepath),
+            None => write!(f, "{kind} `{name}` (id {idx})"),
+        },
+        None => write!(f, "{kind} <unknown> (id {idx})"),
+    }
+}
+
+/// Returns the name of an item and the module, that defines it, given the
+/// signature of the item.
+fn signature_name_and_module(
+    signature: Option<SignatureId>,
+    db: &Database,
+) -> Option<(IdentifierId, ModuleId)> {
+    let signature = signature?.get_data_or_none(db)?;
+
+    Some((signature.name.id, signature.module))
+}
+
+/// Implements [`fmt::Display`] for [`IdDebug`] of IDs of module items, that
+/// have signatures.
+macro_rules! signature_id_debug {
+    ($($id:ident => $kind:literal),*) => {
+        $(
+            impl fmt::Display for IdDebug<'_, $id> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    let signature = self.id.get_data_or_none(self.db).map(|data| data.signature);
+
+                    write_named(
+                        f,
+                        $kind,
+                        self.id.idx(),
+                        signature_name_and_module(signature, self.db),
+                        self.db,
+                    )
+                }
+            }
+        )*
+    };
+}
+
+signature_id_debug! {
+    EnumId => "enum",
+    StructId => "struct",
+    TupleLikeStructId => "tuple-like struct",
+    FunctionId => "function",
+    InterfaceId => "interface",
+    TypeAliasId => "type alias"
+}
+
+impl fmt::Display for IdDebug<'_, SignatureId> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_named(
+            f,
+            "signature of",
+            self.id.idx(),
+            signature_name_and_module(Some(self.id), self.db),
+            self.db,
+        )
+    }
+}
+
+impl fmt::Display for IdDebug<'_, EnumItemId> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let item = self.id.get_data_or_none(self.db);
+
+        write_named(
+            f,
+            "enum item",
+            self.id.idx(),
+            item.map(|item| (item.name.id, item.module)),
+            self.db,
+        )
+    }
+}
+
+impl fmt::Display for IdDebug<'_, ModuleId> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let idx = self.id.idx();
+
+        match self.id.get_data_or_none(self.db) {
+            Some(module) => write!(
+                f,
+                "module `{}` (id {idx}, {})",
+                module.path, module.filepath
+            ),
+            None => write!(f, "module <unknown> (id {idx})"),
+        }
+    }
+}
+
+impl fmt::Display for IdDebug<'_, FieldId> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let idx = self.id.idx();
+
+        match self.id.get_data_or_none(self.db) {
+            Some(field) => write!(f, "field `{}` (id {idx})", field.name.id),
+            None => write!(f, "field <unknown> (id {idx})"),
+        }
+    }
+}
+
+impl fmt::Display for IdDebug<'_, GenericParameterId> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let idx = self.id.idx();
+
+        if !self.id.is_valid(self.db) {
+            return write!(f, "generic parameter <unknown> (id {idx})");
+        }
+
+        match self.id.name(self.db) {
+            Some(name) => write!(f, "generic parameter `{name}` (id {idx})"),
+            None => write!(f, "generic parameter (id {idx})"),
+        }
+    }
+}
+
+impl fmt::Display for IdDebug<'_, GenericParameterScopeId> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let idx = self.id.idx();
+
+        if self.id.is_valid(self.db) {
+            write!(f, "generic parameter scope (id {idx})")
+        } else {
+            write!(f, "generic parameter scope <unknown> (id {idx})")
+        }
+    }
+}
+
+impl fmt::Display for IdDebug<'_, PredicateId> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let idx = self.id.idx();
+
+        if self.id.is_valid(self.db) {
+            write!(f, "predicate (id {idx})")
+        } else {
+            write!(f, "predicate <unknown> (id {idx})")
+        }
+    }
+}
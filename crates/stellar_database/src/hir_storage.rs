@@ -0,0 +1,85 @@
+//! Defines [`HirStorage`] - HIR of module items by their symbols, which is
+//! used by passes, that need to look into definitions, e.g. checking bodies
+//! of functions.
+
+use std::sync::Arc;
+
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::{metrics::FunctionMetrics, ModuleItem, StatementsBlock};
+
+use crate::{FunctionId, Symbol};
+
+/// HIR of module items by their symbols.
+///
+/// Items are stored behind [`Arc`], so that passes can hold onto them
+/// without copying the whole item.
+#[derive(Default, Debug, Clone)]
+pub struct HirStorage {
+    items: FxHashMap<Symbol, Arc<ModuleItem>>,
+}
+
+impl HirStorage {
+    /// Creates a new empty storage.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores HIR of a module item defined by a given symbol.
+    #[inline]
+    pub fn insert(&mut self, symbol: Symbol, item: Arc<ModuleItem>) {
+        self.items.insert(symbol, item);
+    }
+
+    /// Returns HIR of a module item defined by a given symbol.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, symbol: Symbol) -> Option<&Arc<ModuleItem>> {
+        self.items.get(&symbol)
+    }
+
+    /// Returns HIR of a function.
+    #[inline]
+    #[must_use]
+    pub fn get_function(&self, function: FunctionId) -> Option<&stellar_hir::Function> {
+        match self.get(Symbol::Function(function))?.as_ref() {
+            ModuleItem::Function(function) => Some(function),
+            _ => None,
+        }
+    }
+
+    /// Returns a function body, or `None` if the function doesn't have one.
+    #[inline]
+    #[must_use]
+    pub fn get_function_body(&self, function: FunctionId) -> Option<&StatementsBlock> {
+        self.get_function(function)?.body.as_ref()
+    }
+
+    /// Returns sizes and complexity of a function, see [`FunctionMetrics`].
+    #[inline]
+    #[must_use]
+    pub fn metrics(&self, function: FunctionId) -> Option<FunctionMetrics> {
+        self.get_function(function).map(FunctionMetrics::of)
+    }
+
+    /// Returns an iterator over all stored module items and their symbols.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &Arc<ModuleItem>)> {
+        self.items.iter().map(|(symbol, item)| (*symbol, item))
+    }
+
+    /// Returns the number of stored module items.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if no module items are stored.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
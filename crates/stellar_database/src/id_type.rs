@@ -16,7 +16,7 @@ macro_rules! id_types {
         $(
             paste! {
                 #[doc = "A unique ID that maps to [`" [<$what:camel Data>] "`]."]
-                #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+                #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
                 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
                 pub struct [<$what:camel Id>](
                     // the package that data is associated with
@@ -28,9 +28,10 @@ macro_rules! id_types {
 
                 impl [<$what:camel Id>] {
                     /// Constructs a new index type.
+                    #[allow(dead_code)]
                     #[inline]
                     #[must_use]
-                    pub fn new(package: PackageId, id: usize) -> Self {
+                    pub(crate) fn new(package: PackageId, id: usize) -> Self {
                         Self(package, id)
                     }
 
@@ -44,7 +45,7 @@ macro_rules! id_types {
                     /// Returns the underlying ID of the index type within the package.
                     #[inline]
                     #[must_use]
-                    pub fn idx(&self) -> usize {
+                    pub(crate) fn idx(&self) -> usize {
                         self.1
                     }
                 }
@@ -52,12 +53,29 @@ macro_rules! id_types {
                 impl [<$what:camel Id>] {
                     #[allow(dead_code)]
                     #[doc = "Returns an immutable reference to [`" [<$what:camel Data>] "`] by its ID ([`" [<$what:camel Id>] "`])."]
+                    #[track_caller]
                     fn get_data(self, db: &Database) -> &[<$what:camel Data>] {
                         &db.package(self.package()).[<$what _>][self.idx() - 1]
                     }
 
+                    #[doc = "Returns an immutable reference to [`" [<$what:camel Data>] "`] by its ID ([`" [<$what:camel Id>] "`]), or `None` if it is not present in the database storage."]
+                    #[track_caller]
+                    fn get_data_or_none(self, db: &Database) -> Option<&[<$what:camel Data>]> {
+                        db.package_or_none(self.package())?
+                            .[<$what _>]
+                            .get(self.idx().checked_sub(1)?)
+                    }
+
+                    #[doc = "Returns an adapter, that renders the ID together with the name of [`" [<$what:camel Data>] "`], e.g. in logs."]
+                    #[inline]
+                    #[must_use]
+                    pub fn debug(self, db: &Database) -> IdDebug<'_, Self> {
+                        IdDebug::new(self, db)
+                    }
+
                     #[allow(dead_code)]
                     #[doc = "Returns a mutable reference to [`" [<$what:camel Data>] "`] by its ID ([`" [<$what:camel Id>] "`])."]
+                    #[track_caller]
                     fn get_data_mut(self, db: &mut Database) -> &mut [<$what:camel Data>] {
                         &mut db.package_mut(self.package()).[<$what _>][self.idx() - 1]
                     }
@@ -65,12 +83,9 @@ macro_rules! id_types {
                     #[doc = "Returns whether a [`" [<$what:camel Data>] "`] with a given ID ([`" [<$what:camel Id>] "`]) is present in the database storage."]
                     #[inline]
                     #[must_use]
+                    #[track_caller]
                     pub fn is_valid(self, db: &Database) -> bool {
-                        if let Some(package) = db.package_or_none(self.package()) {
-                            self.idx() - 1 < package.[<$what _>].len()
-                        } else {
-                            false
-                        }
+                        self.get_data_or_none(db).is_some()
                     }
                 }
 
@@ -83,6 +98,7 @@ macro_rules! id_types {
                     /// _This function is automatically generated using a macro!_
                     #[inline]
                     #[must_use]
+                    #[track_caller]
                     pub fn [<add_ $what>](&mut self, package: PackageId, data: [<$what:camel Data>]) -> [<$what:camel Id>] {
                         self.package_mut(package).[<$what _>].push(data);
 
@@ -3,27 +3,47 @@
     html_favicon_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png"
 )]
 
-use std::{iter, ops::Add};
+#[cfg(debug_assertions)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{fmt::Display, iter, ops::Add};
 
 use filetime::FileTime;
 use paste::paste;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use stellar_ast::{IdentifierAST, ModuleItemKind, Visibility};
-use stellar_diagnostics::Diagnostics;
-use stellar_filesystem::location::{Location, DUMMY_LOCATION};
-use stellar_fx_hash::FxHashMap;
-use stellar_interner::{IdentifierId, PathId};
-
+use stellar_diagnostics::{Diagnostics, SeverityOverride};
+use stellar_filesystem::{
+    location::{Location, DUMMY_LOCATION},
+    source_map::SourceMap,
+};
+use stellar_fx_hash::{FxHashMap, FxHashSet};
+use stellar_interner::{IdentifierId, PathId, DUMMY_PATH_ID};
+
+pub mod debug;
+pub mod hir_storage;
 #[macro_use]
 mod id_type;
+pub mod references;
+pub mod resolution;
+pub mod search;
+pub mod snapshot;
+pub mod stats;
 pub mod symbol;
 pub mod ty;
 
-pub use symbol::Symbol;
+pub use debug::{IdDebug, SymbolDebug};
+pub use hir_storage::HirStorage;
+pub use references::References;
+pub use resolution::{DefinitionId, ResolutionError, ResolutionErrorKind};
+pub use snapshot::DatabaseSnapshot;
+use snapshot::JournalEntry;
+pub use stats::{DatabaseStats, StorageStats};
+pub use symbol::{Symbol, SymbolKind};
 use ty::{Type, TypeConstructor};
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Path {
     segments: Vec<IdentifierId>,
 }
@@ -48,6 +68,20 @@ impl Path {
     }
 }
 
+impl Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.segments
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(".")
+        )
+    }
+}
+
 impl From<IdentifierId> for Path {
     fn from(id: IdentifierId) -> Self {
         Self::new(vec![id])
@@ -97,6 +131,9 @@ impl Add<IdentifierAST> for Path {
 pub struct EnumData {
     pub signature: SignatureId,
     pub items: FxHashMap<IdentifierId, EnumItemId>,
+
+    /// Names of the items in the order they are defined in.
+    pub item_order: Vec<IdentifierId>,
     pub methods: FxHashMap<IdentifierId, FunctionId>,
 }
 
@@ -115,6 +152,7 @@ impl EnumData {
         Self {
             signature,
             items: FxHashMap::default(),
+            item_order: Vec::new(),
             methods: FxHashMap::default(),
         }
     }
@@ -135,6 +173,28 @@ impl EnumId {
         &self.get_data(db).items
     }
 
+    /// Returns items of the enum in the order they are defined in.
+    #[inline]
+    pub fn items_ordered(self, db: &Database) -> impl Iterator<Item = EnumItemId> + '_ {
+        let data = self.get_data(db);
+
+        data.item_order.iter().map(|name| data.items[name])
+    }
+
+    /// Returns the number of items in the enum.
+    #[inline]
+    #[must_use]
+    pub fn items_len(self, db: &Database) -> usize {
+        self.get_data(db).items.len()
+    }
+
+    /// Returns the visibility of the enum.
+    #[inline]
+    #[must_use]
+    pub fn visibility(self, db: &Database) -> Visibility {
+        self.signature(db).visibility(db)
+    }
+
     /// Returns `true` if an item with a given name is contained in the enum definition.
     #[inline]
     #[must_use]
@@ -149,9 +209,78 @@ impl EnumId {
     }
 
     /// Adds an item to the enum definition.
+    ///
+    /// If an item with the same name is already defined, it is replaced, but
+    /// keeps its position in [`EnumId::items_ordered()`]. If a method with the
+    /// same name is already defined, it is removed and returned, because
+    /// items take precedence over methods (see [`EnumId::add_method()`]).
+    #[inline]
+    pub fn add_item(
+        self,
+        db: &mut Database,
+        name: IdentifierId,
+        item: EnumItemId,
+    ) -> Option<FunctionId> {
+        let data = self.get_data_mut(db);
+        let previous = data.items.insert(name, item);
+
+        if previous.is_none() {
+            data.item_order.push(name);
+        }
+
+        let removed_method = data.methods.remove(&name);
+
+        db.record(JournalEntry::EnumItem {
+            enum_: self,
+            name,
+            previous,
+            removed_method,
+        });
+
+        removed_method
+    }
+
+    /// Returns methods of the enum.
     #[inline]
-    pub fn add_item(self, db: &mut Database, name: IdentifierId, item: EnumItemId) {
-        self.get_data_mut(db).items.insert(name, item);
+    #[must_use]
+    pub fn methods(self, db: &Database) -> &FxHashMap<IdentifierId, FunctionId> {
+        &self.get_data(db).methods
+    }
+
+    /// Returns a method with a given name.
+    #[inline]
+    pub fn method(self, db: &Database, name: IdentifierId) -> Option<FunctionId> {
+        self.get_data(db).methods.get(&name).copied()
+    }
+
+    /// Adds a method to the enum definition.
+    ///
+    /// # Errors
+    /// If an item with the same name is defined, the method is not added,
+    /// so that `Enum.name` always refers to the item, and the item is
+    /// returned.
+    #[inline]
+    pub fn add_method(
+        self,
+        db: &mut Database,
+        name: IdentifierId,
+        method: FunctionId,
+    ) -> Result<(), EnumItemId> {
+        let data = self.get_data_mut(db);
+
+        if let Some(&item) = data.items.get(&name) {
+            return Err(item);
+        }
+
+        let previous = data.methods.insert(name, method);
+
+        db.record(JournalEntry::EnumMethod {
+            enum_: self,
+            name,
+            previous,
+        });
+
+        Ok(())
     }
 }
 
@@ -335,10 +464,17 @@ pub struct GenericParameterScopeData {
 
 impl GenericParameterScopeData {
     /// Creates a new generic parameter scope data object in the database and returns its ID.
+    ///
+    /// See [`GenericParameterScopeId::child()`] to create a child scope of
+    /// an existing one.
     #[inline]
     #[must_use]
-    pub fn alloc(db: &mut Database, package: PackageId) -> GenericParameterScopeId {
-        db.add_generic_parameter_scope(package, Self::new(None))
+    pub fn alloc(
+        db: &mut Database,
+        package: PackageId,
+        parent_scope: Option<GenericParameterScopeId>,
+    ) -> GenericParameterScopeId {
+        db.add_generic_parameter_scope(package, Self::new(parent_scope))
     }
 
     /// Creates a new empty generic parameter scope.
@@ -353,6 +489,13 @@ impl GenericParameterScopeData {
 }
 
 impl GenericParameterScopeId {
+    /// Creates a new empty scope, whose parent is the scope, in the same package.
+    #[inline]
+    #[must_use]
+    pub fn child(self, db: &mut Database) -> Self {
+        GenericParameterScopeData::alloc(db, self.package(), Some(self))
+    }
+
     /// Returns the parent scope.
     pub fn parent_scope(self, db: &Database) -> Option<GenericParameterScopeId> {
         self.get_data(db).parent_scope
@@ -371,9 +514,16 @@ impl GenericParameterScopeId {
         parameter_name: IdentifierId,
         parameter: GenericParameterId,
     ) {
-        self.get_data_mut(db)
+        let previous = self
+            .get_data_mut(db)
             .parameters
             .insert(parameter_name, parameter);
+
+        db.record(JournalEntry::GenericParameter {
+            scope: self,
+            name: parameter_name,
+            previous,
+        });
     }
 
     /// Resolves a data about generic parameter in the scope.
@@ -389,25 +539,102 @@ impl GenericParameterScopeId {
         db: &Database,
         parameter_name: IdentifierId,
     ) -> Option<GenericParameterId> {
-        if let Some(parameter_id) = self.parameters(db).get(&parameter_name) {
-            Some(*parameter_id)
-        } else if let Some(parent_scope_id) = &self.parent_scope(db) {
-            parent_scope_id.resolve(db, parameter_name)
-        } else {
-            None
+        // Scopes are walked iteratively, because parent chains can be arbitrarily long.
+        let mut scope = Some(*self);
+
+        while let Some(id) = scope {
+            if let Some(parameter_id) = id.parameters(db).get(&parameter_name) {
+                return Some(*parameter_id);
+            }
+
+            scope = id.parent_scope(db);
         }
+
+        None
     }
 
     /// Checks if the generic parameter exists in the scope.
     #[inline]
     #[must_use]
     pub fn contains(&self, db: &Database, parameter_name: IdentifierId) -> bool {
-        self.parameters(db).contains_key(&parameter_name)
-            || if let Some(parent_scope_id) = &self.parent_scope(db) {
-                parent_scope_id.contains(db, parameter_name)
-            } else {
-                false
-            }
+        self.resolve(db, parameter_name).is_some()
+    }
+
+    /// Returns the number of generic parameters available in the scope,
+    /// including ones from parent scopes. Shadowed parameters are not counted.
+    #[inline]
+    #[must_use]
+    pub fn len(self, db: &Database) -> usize {
+        self.iter_all(db).count()
+    }
+
+    /// Returns `true` if there are no generic parameters available in
+    /// the scope and in its parent scopes.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(self, db: &Database) -> bool {
+        self.iter_all(db).next().is_none()
+    }
+
+    /// Returns generic parameters available in the scope, including ones from
+    /// parent scopes, from the innermost scope to the outermost one. Parameters
+    /// of the same scope are ordered as they are defined.
+    ///
+    /// Parameters, that are shadowed by parameters of inner scopes, are skipped:
+    ///
+    /// ```stellar
+    /// interface Foo[T, U] {
+    ///     fun bar[T](); // `T` (of `bar`), `U`
+    /// }
+    /// ```
+    pub fn iter_all(
+        self,
+        db: &Database,
+    ) -> impl Iterator<Item = (IdentifierId, GenericParameterId)> {
+        self.visible_parameters_by_scope(db).into_iter().flatten()
+    }
+
+    /// Renders names of generic parameters available in the scope for
+    /// debugging, from the outermost scope to the innermost one, e.g. `[T, M]`.
+    #[must_use]
+    pub fn display(self, db: &Database) -> String {
+        let names = self
+            .visible_parameters_by_scope(db)
+            .into_iter()
+            .rev()
+            .flatten()
+            .map(|(name, _)| name.to_string())
+            .collect::<Vec<_>>();
+
+        format!("[{}]", names.join(", "))
+    }
+
+    /// Returns generic parameters, that are not shadowed, grouped by scopes
+    /// from the innermost scope to the outermost one.
+    fn visible_parameters_by_scope(
+        self,
+        db: &Database,
+    ) -> Vec<Vec<(IdentifierId, GenericParameterId)>> {
+        let mut names = FxHashSet::default();
+        let mut scopes = vec![];
+        let mut scope = Some(self);
+
+        while let Some(id) = scope {
+            let mut parameters = id
+                .parameters(db)
+                .iter()
+                .filter(|(name, _)| !names.contains(*name))
+                .map(|(name, parameter)| (*name, *parameter))
+                .collect::<Vec<_>>();
+            parameters.sort_by_key(|(_, parameter)| parameter.location(db).start);
+
+            names.extend(parameters.iter().map(|(name, _)| *name));
+            scopes.push(parameters);
+
+            scope = id.parent_scope(db);
+        }
+
+        scopes
     }
 }
 
@@ -456,6 +683,33 @@ impl GenericParameterData {
     }
 }
 
+impl GenericParameterId {
+    /// Returns the location of the name of the generic parameter.
+    #[inline]
+    #[must_use]
+    pub fn location(self, db: &Database) -> Location {
+        self.get_data(db).location
+    }
+
+    /// Returns the default value of the generic parameter.
+    #[inline]
+    #[must_use]
+    pub fn default_value(self, db: &Database) -> Option<&Type> {
+        self.get_data(db).default_value.as_ref()
+    }
+
+    /// Returns the name of the generic parameter, or `None` if the parameter
+    /// is not added to any generic parameter scope.
+    #[must_use]
+    pub fn name(self, db: &Database) -> Option<IdentifierId> {
+        db.package(self.package())
+            .generic_parameter_scope_
+            .iter()
+            .flat_map(|scope| &scope.parameters)
+            .find_map(|(name, parameter)| (*parameter == self).then_some(*name))
+    }
+}
+
 /// A data that Stellar compiler has about an enum item.
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -511,6 +765,32 @@ impl EnumItemId {
     }
 }
 
+/// Information from attributes of a module item, e.g. `#[deprecated]`.
+///
+/// Module items, except modules, store it in their signatures, see
+/// [`SignatureId::attributes()`] and [`ModuleId::attributes()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ItemAttributes {
+    /// Set if the item has a `#[deprecated]` attribute.
+    pub deprecation: Option<Deprecation>,
+
+    /// `true` if the item has an `#[allow(dead_code)]` attribute, so it is
+    /// not reported, when it is never used.
+    pub allow_dead_code: bool,
+}
+
+/// A `#[deprecated]` or `#[deprecated("note")]` attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Deprecation {
+    /// Location of the attribute.
+    pub location: Location,
+
+    /// An explanation, e.g. `use Bar instead`.
+    pub note: Option<String>,
+}
+
 /// A data that Stellar compiler has about a particular type signature.
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -523,6 +803,7 @@ pub struct SignatureData {
     pub predicates: Vec<PredicateId>,
     pub implements: Vec<TypeConstructor>,
     pub is_analyzed: bool,
+    pub attributes: ItemAttributes,
 }
 
 impl SignatureData {
@@ -536,7 +817,7 @@ impl SignatureData {
         node_idx: usize,
         module: ModuleId,
     ) -> SignatureId {
-        let generic_parameter_scope = GenericParameterScopeData::alloc(db, module.package());
+        let generic_parameter_scope = GenericParameterScopeData::alloc(db, module.package(), None);
 
         db.add_signature(
             module.package(),
@@ -563,6 +844,7 @@ impl SignatureData {
             predicates: Vec::new(),
             implements: Vec::new(),
             is_analyzed: false,
+            attributes: ItemAttributes::default(),
         }
     }
 }
@@ -623,6 +905,19 @@ impl SignatureId {
         self.get_data_mut(db).implements.push(interface);
     }
 
+    /// Returns information from attributes of the item.
+    #[inline]
+    #[must_use]
+    pub fn attributes(self, db: &Database) -> &ItemAttributes {
+        &self.get_data(db).attributes
+    }
+
+    /// Sets information from attributes of the item.
+    #[inline]
+    pub fn set_attributes(self, db: &mut Database, attributes: ItemAttributes) {
+        self.get_data_mut(db).attributes = attributes;
+    }
+
     #[inline]
     #[must_use]
     pub fn generic_parameter_scope(self, db: &Database) -> GenericParameterScopeId {
@@ -644,6 +939,11 @@ impl SignatureId {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FunctionData {
     pub signature: SignatureId,
+
+    /// Number of trailing parameters, that have default values and can be
+    /// omitted in calls, e.g. `1` for
+    /// `fun greet(name: String, punctuation: String = "!")`.
+    pub default_parameters: usize,
 }
 
 impl FunctionData {
@@ -658,7 +958,10 @@ impl FunctionData {
     #[inline]
     #[must_use]
     pub fn new(signature: SignatureId) -> Self {
-        Self { signature }
+        Self {
+            signature,
+            default_parameters: 0,
+        }
     }
 }
 
@@ -669,6 +972,18 @@ impl FunctionId {
     pub fn signature(self, db: &Database) -> SignatureId {
         self.get_data(db).signature
     }
+
+    /// Returns the number of trailing parameters, that have default values.
+    #[inline]
+    #[must_use]
+    pub fn default_parameters(self, db: &Database) -> usize {
+        self.get_data(db).default_parameters
+    }
+
+    #[inline]
+    pub fn set_default_parameters(self, db: &mut Database, default_parameters: usize) {
+        self.get_data_mut(db).default_parameters = default_parameters;
+    }
 }
 
 /// A data that Stellar compiler has about an interface.
@@ -713,6 +1028,15 @@ impl InterfaceId {
 pub struct TypeAliasData {
     pub signature: SignatureId,
     pub ty: Type,
+
+    /// Scope of generic parameters of the alias, e.g. `T` in
+    /// `type Pair[T] = (T, T);`. `None` if the alias isn't generic.
+    pub generic_parameter_scope: Option<GenericParameterScopeId>,
+
+    /// Path of the aliased type constructor, e.g. `std.option.Option` in
+    /// `type Opt[T] = std.option.Option[T];`. Empty if the aliased type is
+    /// not a type constructor, e.g. a tuple type.
+    pub aliased_path: Vec<IdentifierAST>,
 }
 
 impl TypeAliasData {
@@ -730,6 +1054,8 @@ impl TypeAliasData {
         Self {
             signature,
             ty: Type::Unknown,
+            generic_parameter_scope: None,
+            aliased_path: Vec::new(),
         }
     }
 }
@@ -752,37 +1078,103 @@ impl TypeAliasId {
     pub fn set_type(self, db: &mut Database, ty: Type) {
         self.get_data_mut(db).ty = ty;
     }
+
+    /// Returns the scope of generic parameters of the type alias, if it is
+    /// generic.
+    #[inline]
+    #[must_use]
+    pub fn generic_parameter_scope(self, db: &Database) -> Option<GenericParameterScopeId> {
+        self.get_data(db).generic_parameter_scope
+    }
+
+    #[inline]
+    pub fn set_generic_parameter_scope(
+        self,
+        db: &mut Database,
+        generic_parameter_scope: GenericParameterScopeId,
+    ) {
+        self.get_data_mut(db).generic_parameter_scope = Some(generic_parameter_scope);
+    }
+
+    /// Returns the path of the aliased type constructor, see
+    /// [`TypeAliasData::aliased_path`].
+    #[inline]
+    #[must_use]
+    pub fn aliased_path(self, db: &Database) -> &[IdentifierAST] {
+        &self.get_data(db).aliased_path
+    }
+
+    #[inline]
+    pub fn set_aliased_path(self, db: &mut Database, path: Vec<IdentifierAST>) {
+        self.get_data_mut(db).aliased_path = path;
+    }
 }
 
 /// A data that Stellar compiler has about a module.
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ModuleData {
+    /// Name of the module with the location, that it refers to.
+    pub name: IdentifierAST,
     pub path: Path,
     pub filepath: PathId,
     pub module_item_symbols: FxHashMap<IdentifierId, Symbol>,
     pub submodules: FxHashMap<IdentifierId, ModuleId>,
     pub resolved_imports: FxHashMap<IdentifierId, Symbol>,
+
+    /// Modules, items of which are used in the module.
+    pub dependencies: FxHashSet<ModuleId>,
+
+    /// Modules, that use items of the module.
+    pub dependents: FxHashSet<ModuleId>,
+
+    /// Whether the module's source file cannot be read. Such modules are
+    /// empty placeholders, names in which cannot be resolved.
+    pub load_failed: bool,
+
+    /// The module, that contains the module as a submodule, if any.
+    pub parent: Option<ModuleId>,
+
+    /// Visibility of the module. Modules defined in separate files are
+    /// public, inline modules, e.g. `module foo { ... }`, can be private.
+    pub visibility: Visibility,
+
+    /// Information from attributes of an inline module. Modules defined in
+    /// separate files don't have attributes.
+    pub attributes: ItemAttributes,
 }
 
 impl ModuleData {
     /// Creates a new module data object in the database and returns its ID.
     #[inline]
     #[must_use]
-    pub fn alloc(db: &mut Database, package: PackageId, path: Path, filepath: PathId) -> ModuleId {
-        db.add_module(package, Self::new(path, filepath))
+    pub fn alloc(
+        db: &mut Database,
+        package: PackageId,
+        name: IdentifierAST,
+        path: Path,
+        filepath: PathId,
+    ) -> ModuleId {
+        db.add_module(package, Self::new(name, path, filepath))
     }
 
     /// Creates a new module data object.
     #[inline]
     #[must_use]
-    pub fn new(path: Path, filepath: PathId) -> Self {
+    pub fn new(name: IdentifierAST, path: Path, filepath: PathId) -> Self {
         Self {
+            name,
             path,
             filepath,
             submodules: FxHashMap::default(),
             resolved_imports: FxHashMap::default(),
             module_item_symbols: FxHashMap::default(),
+            dependencies: FxHashSet::default(),
+            dependents: FxHashSet::default(),
+            load_failed: false,
+            parent: None,
+            visibility: Visibility::Public(DUMMY_LOCATION),
+            attributes: ItemAttributes::default(),
         }
     }
 }
@@ -795,10 +1187,11 @@ impl ModuleId {
         self.get_data(db).filepath
     }
 
+    /// Returns module's name.
     #[inline]
     #[must_use]
-    pub fn name(self, db: &Database) -> IdentifierId {
-        self.get_data(db).path.last()
+    pub fn name(self, db: &Database) -> IdentifierAST {
+        self.get_data(db).name
     }
 
     #[inline]
@@ -816,9 +1209,16 @@ impl ModuleId {
 
     /// Adds a module item symbol to the module.
     pub fn add_module_item(self, db: &mut Database, name: IdentifierId, symbol: Symbol) {
-        self.get_data_mut(db)
+        let previous = self
+            .get_data_mut(db)
             .module_item_symbols
             .insert(name, symbol);
+
+        db.record(JournalEntry::ModuleItem {
+            module: self,
+            name,
+            previous,
+        });
     }
 
     /// Returns an immutable reference to submodules.
@@ -847,13 +1247,60 @@ impl ModuleId {
         self.module_item_symbols(db).get(&item_name).copied()
     }
 
-    /// Resolves a symbol in the module.
+    /// Resolves a symbol defined in the module: a module item or a submodule.
+    ///
+    /// Imports are not included, use [`ModuleId::symbol_or_none()`] to resolve them too.
     #[inline]
-    pub fn symbol_or_none(self, db: &Database, name: IdentifierId) -> Option<Symbol> {
+    pub fn local_symbol_or_none(self, db: &Database, name: IdentifierId) -> Option<Symbol> {
         self.module_item_symbol_or_none(db, name)
             .or(self.submodule(db, name).map(Symbol::Module))
     }
 
+    /// Resolves a symbol in the module.
+    ///
+    /// Local definitions (module items and submodules) shadow imports with the
+    /// same name.
+    #[inline]
+    pub fn symbol_or_none(self, db: &Database, name: IdentifierId) -> Option<Symbol> {
+        self.local_symbol_or_none(db, name)
+            .or_else(|| self.resolved_imports(db).get(&name).copied())
+    }
+
+    /// Returns names, that are visible from outside of the module, together
+    /// with their symbols: public module items and public submodules, sorted
+    /// by names.
+    ///
+    /// Imports are never included, because they cannot be public (`pub import`
+    /// is an error), so modules don't re-export names.
+    #[must_use]
+    pub fn public_symbols(self, db: &Database) -> Vec<(IdentifierId, Symbol)> {
+        let module_items = self.module_item_symbols(db);
+
+        let mut symbols = module_items
+            .iter()
+            .map(|(name, symbol)| (*name, *symbol))
+            .chain(
+                self.submodules(db)
+                    .iter()
+                    .filter(|(name, _)| !module_items.contains_key(name))
+                    .map(|(name, submodule)| (*name, Symbol::Module(*submodule))),
+            )
+            .filter(|(_, symbol)| symbol.visibility(db) != Visibility::Private)
+            .collect::<Vec<_>>();
+
+        symbols.sort_unstable_by_key(|(name, _)| name.as_str());
+        symbols
+    }
+
+    /// Returns `true` if a name is visible from outside of the module, see
+    /// [`ModuleId::public_symbols()`].
+    #[inline]
+    #[must_use]
+    pub fn is_public_symbol(self, db: &Database, name: IdentifierId) -> bool {
+        self.local_symbol_or_none(db, name)
+            .is_some_and(|symbol| symbol.visibility(db) != Visibility::Private)
+    }
+
     /// Resolves a symbol in the module.
     ///
     /// # Panics
@@ -887,12 +1334,66 @@ impl ModuleId {
         self.submodules(db).get(&name).copied()
     }
 
-    /// Adds a submodule to the module.
+    /// Adds a submodule to the module and sets the module as its parent.
     #[inline]
     pub fn add_submodule(self, db: &mut Database, module: ModuleId) {
-        let name = module.name(db);
+        let name = module.name(db).id;
 
-        self.submodules_mut(db).insert(name, module);
+        let previous = self.submodules_mut(db).insert(name, module);
+
+        db.record(JournalEntry::Submodule {
+            module: self,
+            name,
+            previous,
+        });
+        module.set_parent(db, self);
+    }
+
+    /// Returns the module, that contains the module as a submodule, if any.
+    #[inline]
+    #[must_use]
+    pub fn parent(self, db: &Database) -> Option<ModuleId> {
+        self.get_data(db).parent
+    }
+
+    /// Sets the module, that contains the module as a submodule.
+    ///
+    /// **Note**: the module is not added to submodules of the parent, see
+    /// [`ModuleId::add_submodule()`].
+    #[inline]
+    pub fn set_parent(self, db: &mut Database, parent: ModuleId) {
+        let previous = self.get_data_mut(db).parent.replace(parent);
+
+        db.record(JournalEntry::Parent {
+            module: self,
+            previous,
+        });
+    }
+
+    /// Returns the visibility of the module.
+    #[inline]
+    #[must_use]
+    pub fn visibility(self, db: &Database) -> Visibility {
+        self.get_data(db).visibility
+    }
+
+    /// Sets the visibility of the module.
+    #[inline]
+    pub fn set_visibility(self, db: &mut Database, visibility: Visibility) {
+        self.get_data_mut(db).visibility = visibility;
+    }
+
+    /// Returns information from attributes of the module.
+    #[inline]
+    #[must_use]
+    pub fn attributes(self, db: &Database) -> &ItemAttributes {
+        &self.get_data(db).attributes
+    }
+
+    /// Sets information from attributes of the module.
+    #[inline]
+    pub fn set_attributes(self, db: &mut Database, attributes: ItemAttributes) {
+        self.get_data_mut(db).attributes = attributes;
     }
 
     /// Checks if a submodule with a given name is contained in the module.
@@ -921,70 +1422,238 @@ impl ModuleId {
     /// Adds a resolved import to the module.
     #[inline]
     pub fn add_resolved_import(self, db: &mut Database, name: IdentifierId, symbol: Symbol) {
-        self.get_data_mut(db).resolved_imports.insert(name, symbol);
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct PackageId(pub usize);
-
-pub const DUMMY_PACKAGE_ID: PackageId = PackageId(0);
+        let previous = self.get_data_mut(db).resolved_imports.insert(name, symbol);
 
-impl PackageId {
-    #[inline]
-    #[must_use]
-    pub fn name(self, db: &Database) -> IdentifierId {
-        db.packages[self.0 - 1].name
+        db.record(JournalEntry::ResolvedImport {
+            module: self,
+            name,
+            previous,
+        });
     }
 
+    /// Returns modules, items of which are used in the module.
     #[inline]
     #[must_use]
-    pub fn parent(self, db: &Database) -> Option<PackageId> {
-        db.packages[self.0 - 1].parent
+    pub fn dependencies(self, db: &Database) -> &FxHashSet<ModuleId> {
+        &self.get_data(db).dependencies
     }
 
+    /// Returns modules, that directly use items of the module. See
+    /// [`Database::dependents_of()`] for all modules, that depend on it.
     #[inline]
     #[must_use]
-    pub fn parent_or_none(self, db: &Database) -> Option<PackageId> {
-        db.packages
-            .get(self.0 - 1)
-            .and_then(|package| package.parent)
+    pub fn dependents(self, db: &Database) -> &FxHashSet<ModuleId> {
+        &self.get_data(db).dependents
     }
 
-    #[inline]
-    #[must_use]
-    pub fn dependencies(self, db: &Database) -> &FxHashMap<IdentifierId, PackageId> {
-        &db.packages[self.0 - 1].dependencies
+    /// Records, that the module uses items of a given module, e.g. imports
+    /// them or refers to them in types.
+    ///
+    /// Modules referring to themselves and builtin symbols (which don't
+    /// belong to any module) are not recorded.
+    pub fn add_dependency(self, db: &mut Database, module: ModuleId) {
+        if module == self || module == DUMMY_MODULE_ID {
+            return;
+        }
+
+        if self.get_data_mut(db).dependencies.insert(module) {
+            module.get_data_mut(db).dependents.insert(self);
+
+            db.record(JournalEntry::DependencyAdded {
+                module: self,
+                dependency: module,
+            });
+        }
     }
 
-    #[inline]
-    #[must_use]
-    pub fn dependencies_or_none(
-        self,
-        db: &Database,
-    ) -> Option<&FxHashMap<IdentifierId, PackageId>> {
-        db.packages
-            .get(self.0 - 1)
-            .map(|package| &package.dependencies)
+    /// Removes all recorded dependencies of the module.
+    pub fn clear_dependencies(self, db: &mut Database) {
+        for dependency in std::mem::take(&mut self.get_data_mut(db).dependencies) {
+            dependency.get_data_mut(db).dependents.remove(&self);
+
+            db.record(JournalEntry::DependencyRemoved {
+                module: self,
+                dependency,
+            });
+        }
     }
 
+    /// Returns `true` if the module's source file cannot be read, so the
+    /// module is an empty placeholder.
     #[inline]
     #[must_use]
-    pub fn root_module(self, db: &Database) -> ModuleId {
-        db.packages[self.0 - 1].root_module
+    pub fn load_failed(self, db: &Database) -> bool {
+        self.get_data(db).load_failed
     }
 
+    /// Marks the module as a placeholder for a module, source file of which
+    /// cannot be read.
     #[inline]
-    #[must_use]
-    pub fn root_module_or_none(self, db: &Database) -> Option<ModuleId> {
-        db.packages
-            .get(self.0 - 1)
-            .map(|package| package.root_module)
+    pub fn mark_load_failed(self, db: &mut Database) {
+        self.get_data_mut(db).load_failed = true;
     }
+}
 
-    pub fn set_root_module(self, db: &mut Database, module: ModuleId) {
-        db.packages[self.0 - 1].root_module = module;
+/// ID of a package in the [`Database`].
+///
+/// In debug builds, the ID also stores the generation of the database, that
+/// allocated it (see [`Database::generation()`]). Using the ID, or any ID of
+/// an entity in the package, with another database panics instead of
+/// silently returning data of an unrelated package.
+#[derive(Clone, Copy, Default)]
+pub struct PackageId {
+    idx: usize,
+
+    /// Generation of the database, that allocated the package, or `0`, if
+    /// the ID is not bound to a particular database, e.g. for
+    /// [`DUMMY_PACKAGE_ID`] and deserialized IDs.
+    #[cfg(debug_assertions)]
+    generation: usize,
+}
+
+pub const DUMMY_PACKAGE_ID: PackageId = PackageId::new(0);
+
+// IDs are compared and hashed by their indices only, so that IDs, that are
+// not bound to a database, e.g. deserialized ones, are equal to the ones
+// allocated by it.
+impl PartialEq for PackageId {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.idx == other.idx
+    }
+}
+
+impl Eq for PackageId {}
+
+impl PartialOrd for PackageId {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PackageId {
+    #[inline]
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.idx.cmp(&other.idx)
+    }
+}
+
+impl std::hash::Hash for PackageId {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.idx.hash(state);
+    }
+}
+
+impl std::fmt::Debug for PackageId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("PackageId").field(&self.idx).finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for PackageId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_newtype_struct("PackageId", &self.idx)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for PackageId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "PackageId")]
+        struct RawPackageId(usize);
+
+        RawPackageId::deserialize(deserializer).map(|RawPackageId(idx)| Self::new(idx))
+    }
+}
+
+impl PackageId {
+    /// Creates an ID, that is not bound to a particular database.
+    #[inline]
+    #[must_use]
+    pub(crate) const fn new(idx: usize) -> Self {
+        Self {
+            idx,
+            #[cfg(debug_assertions)]
+            generation: 0,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn name(self, db: &Database) -> IdentifierId {
+        db.package(self).name
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn parent(self, db: &Database) -> Option<PackageId> {
+        db.package(self).parent
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn parent_or_none(self, db: &Database) -> Option<PackageId> {
+        db.package_or_none(self).and_then(|package| package.parent)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn dependencies(self, db: &Database) -> &FxHashMap<IdentifierId, PackageId> {
+        &db.package(self).dependencies
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn dependencies_or_none(
+        self,
+        db: &Database,
+    ) -> Option<&FxHashMap<IdentifierId, PackageId>> {
+        db.package_or_none(self)
+            .map(|package| &package.dependencies)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn root_module(self, db: &Database) -> ModuleId {
+        db.package(self).root_module
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn root_module_or_none(self, db: &Database) -> Option<ModuleId> {
+        db.package_or_none(self).map(|package| package.root_module)
+    }
+
+    pub fn set_root_module(self, db: &mut Database, module: ModuleId) {
+        let previous = std::mem::replace(&mut db.package_mut(self).root_module, module);
+
+        db.record(JournalEntry::RootModule {
+            package: self,
+            previous,
+        });
+    }
+
+    /// Adds a dependency of the package, that is available in the package
+    /// under a given name.
+    #[inline]
+    pub fn add_dependency(self, db: &mut Database, name: IdentifierId, dependency: PackageId) {
+        let previous = db.package_mut(self).dependencies.insert(name, dependency);
+
+        db.record(JournalEntry::PackageDependency {
+            package: self,
+            name,
+            previous,
+        });
     }
 }
 
@@ -1027,6 +1696,7 @@ pub struct PackageData {
 
     /// The time of the last modification of the package folder.
     #[allow(dead_code)]
+    #[cfg_attr(feature = "serde", serde(skip))]
     last_modification_time: Option<FileTime>,
 
     // Information about all package-related compiler entities.
@@ -1047,6 +1717,10 @@ pub struct PackageData {
 
 /// Returns the last modification time of a folder with a given path.
 fn last_modification_time_of(path: PathId) -> Option<FileTime> {
+    if path == DUMMY_PATH_ID {
+        return None;
+    }
+
     path.as_path()
         .metadata()
         .ok()
@@ -1079,7 +1753,11 @@ impl PackageData {
             signature_: Vec::new(),
         });
 
-        PackageId(db.packages.len())
+        PackageId {
+            idx: db.packages.len(),
+            #[cfg(debug_assertions)]
+            generation: db.generation,
+        }
     }
 
     #[inline]
@@ -1113,10 +1791,37 @@ impl From<Vec<u8>> for PackageData {
     }
 }
 
+/// Generation of the next database to be created, see [`Database::generation()`].
+#[cfg(debug_assertions)]
+static NEXT_GENERATION: AtomicUsize = AtomicUsize::new(1);
+
 /// Storage for Stellar compiler entities.
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct Database {
     packages: Vec<PackageData>,
+
+    /// Unique number of the database, that is stored in IDs of its packages
+    /// in debug builds, see [`PackageId`].
+    #[cfg(debug_assertions)]
+    generation: usize,
+
+    /// Changes made while there are snapshots, see [`Database::snapshot()`].
+    journal: Vec<JournalEntry>,
+
+    /// Number of snapshots, that are not released yet.
+    open_snapshots: usize,
+}
+
+impl Default for Database {
+    fn default() -> Self {
+        Self {
+            packages: Vec::new(),
+            #[cfg(debug_assertions)]
+            generation: NEXT_GENERATION.fetch_add(1, Ordering::Relaxed),
+            journal: Vec::new(),
+            open_snapshots: 0,
+        }
+    }
 }
 
 impl Database {
@@ -1127,52 +1832,395 @@ impl Database {
         Self::default()
     }
 
+    /// Returns the unique number of the database, that is stored in IDs of
+    /// its packages. Always `0` in release builds.
+    #[inline]
+    #[must_use]
+    pub const fn generation(&self) -> usize {
+        #[cfg(debug_assertions)]
+        return self.generation;
+
+        #[cfg(not(debug_assertions))]
+        0
+    }
+
+    /// Checks, that a package ID was allocated by the database.
+    ///
+    /// # Panics
+    /// In debug builds, if the ID was allocated by another database.
+    #[inline]
+    #[track_caller]
+    fn check_package_id(&self, id: PackageId) {
+        #[cfg(debug_assertions)]
+        assert!(
+            id.generation == 0 || id.generation == self.generation,
+            "{id:?} belongs to another database (generation {}), but is used with database of generation {}",
+            id.generation,
+            self.generation
+        );
+
+        #[cfg(not(debug_assertions))]
+        let _ = (self, id);
+    }
+
     /// Returns an immutable reference to package data by its ID.
     #[inline]
     #[must_use]
+    #[track_caller]
     pub fn package(&self, id: PackageId) -> &PackageData {
-        &self.packages[id.0 - 1]
+        self.check_package_id(id);
+        &self.packages[id.idx - 1]
     }
 
     /// Returns an immutable reference to package data by its ID.
     #[inline]
     #[must_use]
+    #[track_caller]
     pub fn package_or_none(&self, id: PackageId) -> Option<&PackageData> {
-        self.packages.get(id.0 - 1)
+        self.check_package_id(id);
+        self.packages.get(id.idx.checked_sub(1)?)
     }
 
     /// Returns a mutable reference to package data by its ID.
     #[inline]
     #[must_use]
+    #[track_caller]
     pub fn package_mut(&mut self, id: PackageId) -> &mut PackageData {
-        &mut self.packages[id.0 - 1]
+        self.check_package_id(id);
+        &mut self.packages[id.idx - 1]
+    }
+
+    /// Returns all modules, that directly or transitively use items of
+    /// a given module, not including the module itself.
+    #[must_use]
+    pub fn dependents_of(&self, module: ModuleId) -> Vec<ModuleId> {
+        let mut visited = FxHashSet::default();
+        visited.insert(module);
+
+        let mut dependents = vec![];
+        let mut stack = vec![module];
+
+        while let Some(module) = stack.pop() {
+            for &dependent in module.dependents(self) {
+                if visited.insert(dependent) {
+                    dependents.push(dependent);
+                    stack.push(dependent);
+                }
+            }
+        }
+
+        dependents
     }
 
     /// Returns a mutable reference to package data by its ID.
     #[inline]
     #[must_use]
+    #[track_caller]
     pub fn package_mut_or_none(&mut self, id: PackageId) -> Option<&mut PackageData> {
-        self.packages.get_mut(id.0 - 1)
+        self.check_package_id(id);
+        self.packages.get_mut(id.idx.checked_sub(1)?)
     }
 }
 
-/// Contains database and diagnostics.
+/// Contains database, HIR of module items, references to symbols, sources
+/// of files and diagnostics.
 #[derive(Default)]
 pub struct State {
     db: Database,
+    hir: HirStorage,
+    references: References,
+    source_map: SourceMap,
     diagnostics: Diagnostics,
     config: Config,
 }
 
-#[derive(Default)]
-pub struct Config {}
+/// Compiler configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// Maximum nesting depth of types, e.g. `List[List[int32]]` has the depth of 3.
+    max_type_nesting: usize,
+
+    /// Maximum depth of a module in the package's module tree,
+    /// e.g. `a.b.c` has the depth of 3.
+    max_module_depth: usize,
+
+    /// Flags, that `#[cfg(...)]` attributes are evaluated against.
+    cfg_options: CfgOptions,
+
+    /// Whether source files, that are not valid UTF-8, are decoded lossily
+    /// instead of failing to load.
+    lenient_utf8: bool,
+
+    /// Maximum size of a source file in bytes.
+    max_file_size: usize,
+
+    /// Maximum number of tokens in a source file, not counting comments.
+    max_token_count: usize,
+
+    /// Maximum length of an identifier in bytes.
+    max_identifier_length: usize,
+
+    /// Whether private types in signatures of public items are reported as
+    /// errors instead of warnings.
+    deny_private_in_public: bool,
+
+    /// Whether compilation stops before the next file or phase once an error
+    /// is reported.
+    fail_fast: bool,
+
+    /// Severity overrides of diagnostics by their codes, e.g. set with
+    /// `--deny W004`.
+    severity_overrides: FxHashMap<String, SeverityOverride>,
+}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_type_nesting: Self::DEFAULT_MAX_TYPE_NESTING,
+            max_module_depth: Self::DEFAULT_MAX_MODULE_DEPTH,
+            cfg_options: CfgOptions::default(),
+            lenient_utf8: false,
+            max_file_size: Self::DEFAULT_MAX_FILE_SIZE,
+            max_token_count: Self::DEFAULT_MAX_TOKEN_COUNT,
+            max_identifier_length: Self::DEFAULT_MAX_IDENTIFIER_LENGTH,
+            deny_private_in_public: false,
+            fail_fast: false,
+            severity_overrides: FxHashMap::default(),
+        }
+    }
+}
+
+/// A set of enabled conditional compilation flags, e.g. `test` in
+/// `stellar check --cfg test`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CfgOptions {
+    flags: FxHashSet<IdentifierId>,
+}
+
+impl CfgOptions {
+    /// Creates a new empty set of flags.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables a given flag.
+    #[inline]
+    pub fn enable(&mut self, flag: IdentifierId) {
+        self.flags.insert(flag);
+    }
+
+    /// Returns `true` if a given flag is enabled.
+    #[inline]
+    #[must_use]
+    pub fn is_enabled(&self, flag: IdentifierId) -> bool {
+        self.flags.contains(&flag)
+    }
+}
+
+impl FromIterator<IdentifierId> for CfgOptions {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = IdentifierId>>(iter: T) -> Self {
+        Self {
+            flags: iter.into_iter().collect(),
+        }
+    }
+}
 
 impl Config {
+    /// Default value of [`Config::max_type_nesting()`].
+    pub const DEFAULT_MAX_TYPE_NESTING: usize = 128;
+
+    /// Default value of [`Config::max_module_depth()`].
+    pub const DEFAULT_MAX_MODULE_DEPTH: usize = 128;
+
+    /// Default value of [`Config::max_file_size()`], 16 MiB.
+    pub const DEFAULT_MAX_FILE_SIZE: usize = 16 * 1024 * 1024;
+
+    /// Default value of [`Config::max_token_count()`].
+    pub const DEFAULT_MAX_TOKEN_COUNT: usize = 4 * 1024 * 1024;
+
+    /// Default value of [`Config::max_identifier_length()`].
+    pub const DEFAULT_MAX_IDENTIFIER_LENGTH: usize = 1024;
+
     #[inline]
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Sets the maximum nesting depth of types.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_type_nesting(mut self, max_type_nesting: usize) -> Self {
+        self.max_type_nesting = max_type_nesting;
+        self
+    }
+
+    /// Sets the maximum depth of a module in the package's module tree.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_module_depth(mut self, max_module_depth: usize) -> Self {
+        self.max_module_depth = max_module_depth;
+        self
+    }
+
+    /// Sets flags, that `#[cfg(...)]` attributes are evaluated against.
+    #[inline]
+    #[must_use]
+    pub fn with_cfg_options(mut self, cfg_options: CfgOptions) -> Self {
+        self.cfg_options = cfg_options;
+        self
+    }
+
+    /// Sets whether source files, that are not valid UTF-8, are decoded
+    /// lossily, with invalid byte sequences replaced by `U+FFFD`.
+    #[inline]
+    #[must_use]
+    pub const fn with_lenient_utf8(mut self, lenient_utf8: bool) -> Self {
+        self.lenient_utf8 = lenient_utf8;
+        self
+    }
+
+    /// Sets the maximum size of a source file in bytes.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_file_size(mut self, max_file_size: usize) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Sets the maximum number of tokens in a source file.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_token_count(mut self, max_token_count: usize) -> Self {
+        self.max_token_count = max_token_count;
+        self
+    }
+
+    /// Sets the maximum length of an identifier in bytes.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_identifier_length(mut self, max_identifier_length: usize) -> Self {
+        self.max_identifier_length = max_identifier_length;
+        self
+    }
+
+    /// Sets whether private types in signatures of public items are reported
+    /// as errors instead of warnings.
+    #[inline]
+    #[must_use]
+    pub const fn with_deny_private_in_public(mut self, deny_private_in_public: bool) -> Self {
+        self.deny_private_in_public = deny_private_in_public;
+        self
+    }
+
+    /// Sets whether compilation stops before the next file or phase once an
+    /// error is reported.
+    #[inline]
+    #[must_use]
+    pub const fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
+    /// Overrides the severity of diagnostics with a given code. A later
+    /// override of the same code replaces an earlier one.
+    #[inline]
+    #[must_use]
+    pub fn with_severity_override(
+        mut self,
+        code: impl Into<String>,
+        severity: SeverityOverride,
+    ) -> Self {
+        self.severity_overrides.insert(code.into(), severity);
+        self
+    }
+
+    /// Returns the maximum nesting depth of types.
+    #[inline]
+    #[must_use]
+    pub const fn max_type_nesting(&self) -> usize {
+        self.max_type_nesting
+    }
+
+    /// Returns the maximum depth of a module in the package's module tree.
+    #[inline]
+    #[must_use]
+    pub const fn max_module_depth(&self) -> usize {
+        self.max_module_depth
+    }
+
+    /// Returns flags, that `#[cfg(...)]` attributes are evaluated against.
+    #[inline]
+    #[must_use]
+    pub const fn cfg_options(&self) -> &CfgOptions {
+        &self.cfg_options
+    }
+
+    /// Returns `true` if source files, that are not valid UTF-8, are decoded
+    /// lossily.
+    #[inline]
+    #[must_use]
+    pub const fn lenient_utf8(&self) -> bool {
+        self.lenient_utf8
+    }
+
+    /// Returns the maximum size of a source file in bytes. Larger files are
+    /// reported and are not parsed.
+    #[inline]
+    #[must_use]
+    pub const fn max_file_size(&self) -> usize {
+        self.max_file_size
+    }
+
+    /// Returns the maximum number of tokens in a source file, not counting
+    /// comments. Files with more tokens are reported and are not parsed.
+    #[inline]
+    #[must_use]
+    pub const fn max_token_count(&self) -> usize {
+        self.max_token_count
+    }
+
+    /// Returns the maximum length of an identifier in bytes. Longer
+    /// identifiers are reported and are not interned.
+    #[inline]
+    #[must_use]
+    pub const fn max_identifier_length(&self) -> usize {
+        self.max_identifier_length
+    }
+
+    /// Returns `true` if private types in signatures of public items are
+    /// reported as errors instead of warnings.
+    #[inline]
+    #[must_use]
+    pub const fn deny_private_in_public(&self) -> bool {
+        self.deny_private_in_public
+    }
+
+    /// Returns `true` if compilation stops before the next file or phase
+    /// once an error is reported.
+    #[inline]
+    #[must_use]
+    pub const fn fail_fast(&self) -> bool {
+        self.fail_fast
+    }
+
+    /// Returns the severity override of diagnostics with a given code.
+    #[inline]
+    #[must_use]
+    pub fn severity_override(&self, code: &str) -> Option<SeverityOverride> {
+        self.severity_overrides.get(code).copied()
+    }
+
+    /// Returns severity overrides of diagnostics by their codes.
+    #[inline]
+    #[must_use]
+    pub const fn severity_overrides(&self) -> &FxHashMap<String, SeverityOverride> {
+        &self.severity_overrides
+    }
 }
 
 impl State {
@@ -1184,9 +2232,18 @@ impl State {
     }
 
     /// Builds a new state with given configuration.
+    ///
+    /// Severity overrides of the config are applied to diagnostics, that are
+    /// added after it, see [`Diagnostics::override_severity()`].
     #[inline]
     #[must_use]
     pub fn with_config(mut self, config: Config) -> Self {
+        self.source_map.set_lenient_utf8(config.lenient_utf8());
+
+        for (code, severity) in config.severity_overrides() {
+            self.diagnostics.override_severity(code.as_str(), *severity);
+        }
+
         self.config = config;
         self
     }
@@ -1219,6 +2276,62 @@ impl State {
         self.db
     }
 
+    /// Returns an immutable reference to HIR of module items.
+    #[inline]
+    #[must_use]
+    pub const fn hir(&self) -> &HirStorage {
+        &self.hir
+    }
+
+    /// Returns a mutable reference to HIR of module items.
+    #[inline]
+    #[must_use]
+    pub fn hir_mut(&mut self) -> &mut HirStorage {
+        &mut self.hir
+    }
+
+    /// Returns an immutable reference to locations of references to symbols.
+    #[inline]
+    #[must_use]
+    pub const fn references(&self) -> &References {
+        &self.references
+    }
+
+    /// Returns a mutable reference to locations of references to symbols.
+    #[inline]
+    #[must_use]
+    pub fn references_mut(&mut self) -> &mut References {
+        &mut self.references
+    }
+
+    /// Records, that a given symbol is referred to at a given location.
+    /// See [`References::record_reference()`] for details.
+    #[inline]
+    pub fn record_reference(&mut self, symbol: Symbol, location: Location) {
+        self.references.record_reference(symbol, location);
+    }
+
+    /// Returns locations of all references to a given symbol.
+    #[inline]
+    #[must_use]
+    pub fn references_of(&self, symbol: Symbol) -> &[Location] {
+        self.references.references_of(symbol)
+    }
+
+    /// Returns an immutable reference to sources of files.
+    #[inline]
+    #[must_use]
+    pub const fn source_map(&self) -> &SourceMap {
+        &self.source_map
+    }
+
+    /// Returns a mutable reference to sources of files.
+    #[inline]
+    #[must_use]
+    pub fn source_map_mut(&mut self) -> &mut SourceMap {
+        &mut self.source_map
+    }
+
     /// Returns an immutable reference to diagnostics.
     #[inline]
     #[must_use]
@@ -1233,6 +2346,39 @@ impl State {
         &mut self.diagnostics
     }
 
+    /// Invalidates results of checking a module, e.g. after its file has
+    /// changed, and returns the module together with all modules, that depend
+    /// on it (see [`Database::dependents_of()`]).
+    ///
+    /// Module item symbols, diagnostics and references in the file of the
+    /// module are cleared, and the file is evicted from the source map.
+    /// Resolved imports and references of its dependents are cleared too,
+    /// so that they are resolved again.
+    pub fn invalidate_module(&mut self, module: ModuleId) -> Vec<ModuleId> {
+        let filepath = module.filepath(&self.db);
+
+        self.source_map.forget(filepath);
+
+        module
+            .get_data_mut(&mut self.db)
+            .module_item_symbols
+            .clear();
+        self.diagnostics.clear_file(filepath);
+
+        let mut invalidated = vec![module];
+        invalidated.extend(self.db.dependents_of(module));
+
+        for &module in &invalidated {
+            module.get_data_mut(&mut self.db).resolved_imports.clear();
+            module.clear_dependencies(&mut self.db);
+
+            self.references
+                .remove_references_in_file(module.filepath(&self.db));
+        }
+
+        invalidated
+    }
+
     /// Gives an ownership over diagnostics object inside the state.
     #[inline]
     #[must_use]
@@ -0,0 +1,64 @@
+//! Defines [`References`] - locations, where symbols are referred to by name,
+//! which is used by tooling, e.g. to rename a definition together with all
+//! of its usages.
+
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::PathId;
+
+use crate::Symbol;
+
+/// Locations of references to symbols, recorded during name resolution.
+///
+/// Definitions themselves are not references and are never recorded.
+#[derive(Default, Debug, Clone)]
+pub struct References {
+    references: FxHashMap<Symbol, Vec<Location>>,
+}
+
+impl References {
+    /// Creates a new empty table.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records, that a given symbol is referred to at a given location.
+    ///
+    /// Recording the same location twice has no effect, so passes, that
+    /// resolve the same path more than once, don't produce duplicates.
+    pub fn record_reference(&mut self, symbol: Symbol, location: Location) {
+        let locations = self.references.entry(symbol).or_default();
+
+        if !locations.contains(&location) {
+            locations.push(location);
+        }
+    }
+
+    /// Returns locations of all references to a given symbol in the order,
+    /// in which they were recorded.
+    #[inline]
+    #[must_use]
+    pub fn references_of(&self, symbol: Symbol) -> &[Location] {
+        self.references.get(&symbol).map_or(&[], Vec::as_slice)
+    }
+
+    /// Removes all references located in a given file.
+    pub fn remove_references_in_file(&mut self, filepath: PathId) {
+        self.references.retain(|_, locations| {
+            locations.retain(|location| location.filepath != filepath);
+
+            !locations.is_empty()
+        });
+    }
+
+    /// Returns an iterator over all symbols, that are referred to, and
+    /// locations of their references.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &[Location])> {
+        self.references
+            .iter()
+            .map(|(symbol, locations)| (*symbol, locations.as_slice()))
+    }
+}
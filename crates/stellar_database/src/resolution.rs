@@ -0,0 +1,274 @@
+//! Defines [`Database::resolve_path()`] - resolution of paths like `a.b.C`
+//! into symbols, that reports failures as [`ResolutionError`] instead of
+//! panicking, and [`DefinitionId`] - a stable name of a definition, that
+//! can be mapped to its symbol and back.
+
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_interner::IdentifierId;
+
+use crate::{Database, ModuleId, Symbol, TypeAliasId};
+
+/// Maximum number of type aliases, that can be expanded while resolving
+/// a path. Prevents infinite recursion on cyclic aliases, e.g.
+/// `type A = B; type B = A;`.
+const MAX_TYPE_ALIAS_DEPTH: usize = 64;
+
+/// A reason why a path cannot be resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResolutionErrorKind {
+    /// The first segment of the path is not defined in the module, and is not
+    /// a name of the current package or of one of its dependencies.
+    NotInScope,
+
+    /// The module or the enum doesn't contain the segment.
+    NotFoundInNamespace,
+
+    /// The symbol is neither a module nor an enum, so it cannot contain
+    /// other names, e.g. `Foo` in `Foo.bar`, where `Foo` is a struct.
+    NotANamespace,
+
+    /// The segment refers to a private module item of another package.
+    PrivateItem,
+
+    /// The segment is searched in a module, source file of which cannot be
+    /// read, so it is unknown whether the module contains it.
+    ModuleFailedToLoad,
+}
+
+/// An error, that occurs when a path cannot be resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ResolutionError {
+    /// The segment, that cannot be resolved.
+    pub failed_segment: IdentifierAST,
+
+    /// The symbol, that the segment is searched in.
+    pub searched_in: Symbol,
+
+    /// The segment before the failed one, that refers to
+    /// [`ResolutionError::searched_in`]. `None` if the failed segment is
+    /// the first one.
+    pub previous_segment: Option<IdentifierAST>,
+
+    /// The type alias, that the previous segment refers to, if
+    /// [`ResolutionError::searched_in`] was obtained by expanding it, e.g.
+    /// `Opt` in `Opt.Nome` with `type Opt = Option;`.
+    pub alias: Option<TypeAliasId>,
+    pub reason: ResolutionErrorKind,
+}
+
+/// Identifies a module item or a submodule by the module, it is defined in,
+/// and its name.
+///
+/// Names of definitions are unique within their modules, so the ID refers to
+/// at most one symbol, see [`Database::symbol_by_definition_id()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DefinitionId {
+    pub module: ModuleId,
+    pub name: IdentifierId,
+}
+
+impl Database {
+    /// Returns the ID of the definition of a symbol.
+    ///
+    /// Returns `None` for root modules of packages, enum items and builtin
+    /// symbols, as they are not defined in modules directly.
+    #[must_use]
+    pub fn definition_id_of(&self, symbol: Symbol) -> Option<DefinitionId> {
+        match symbol {
+            Symbol::Module(module) => Some(DefinitionId {
+                module: module.parent(self)?,
+                name: module.name(self).id,
+            }),
+            Symbol::Enum(_)
+            | Symbol::Struct(_)
+            | Symbol::TupleLikeStruct(_)
+            | Symbol::Function(_)
+            | Symbol::Interface(_)
+            | Symbol::TypeAlias(_) => {
+                let signature = symbol.signature(self);
+
+                Some(DefinitionId {
+                    module: signature.module(self),
+                    name: signature.name(self).id,
+                })
+            }
+            Symbol::EnumItem(_) | Symbol::BuiltinSymbol(_) => None,
+        }
+    }
+
+    /// Returns the symbol of a module item or a submodule by its definition
+    /// ID, if the module defines it. Imports are not taken into account.
+    #[inline]
+    #[must_use]
+    pub fn symbol_by_definition_id(&self, id: DefinitionId) -> Option<Symbol> {
+        id.module.local_symbol_or_none(self, id.name)
+    }
+
+    /// Resolves a path, e.g. `std.io.println` or `Color.Red`, in a given module.
+    ///
+    /// The first segment is looked up in the module items, submodules, imports
+    /// and then in the names of the current package and its dependencies. Other
+    /// segments are looked up in submodules, items and resolved imports of
+    /// modules and in items of enums. Type aliases in the middle of a path are
+    /// expanded, so `Opt.Some` resolves to `Option.Some` with
+    /// `type Opt = Option;`.
+    ///
+    /// Private module items of other packages are not accessible. Imports are
+    /// private, so they are only accessible within their package.
+    ///
+    /// An empty path resolves to the module itself.
+    ///
+    /// # Errors
+    /// Returns [`ResolutionError`] describing the first segment, that cannot be
+    /// resolved.
+    pub fn resolve_path(
+        &self,
+        start: ModuleId,
+        path: &[IdentifierAST],
+    ) -> Result<Symbol, Box<ResolutionError>> {
+        self.resolve_path_with_depth(start, path, 0)
+    }
+
+    /// Expands a type alias into the symbol, that it refers to, e.g. enum
+    /// `Option` for `type Opt = Option;`. Aliases of aliases are expanded
+    /// until a symbol, that is not an alias, is reached.
+    ///
+    /// Returns the chain of expanded aliases starting with the given one and
+    /// the resulting symbol. The symbol is `None` if the aliased type is not
+    /// a type constructor, its path cannot be resolved or the aliases are
+    /// cyclic.
+    #[must_use]
+    pub fn expand_type_alias(&self, alias: TypeAliasId) -> (Vec<TypeAliasId>, Option<Symbol>) {
+        self.expand_type_alias_with_depth(alias, 0)
+    }
+
+    fn expand_type_alias_with_depth(
+        &self,
+        alias: TypeAliasId,
+        depth: usize,
+    ) -> (Vec<TypeAliasId>, Option<Symbol>) {
+        let mut chain = vec![];
+        let mut alias = alias;
+
+        loop {
+            if chain.contains(&alias) || depth + chain.len() >= MAX_TYPE_ALIAS_DEPTH {
+                return (chain, None);
+            }
+
+            chain.push(alias);
+
+            let path = alias.aliased_path(self);
+
+            if path.is_empty() {
+                return (chain, None);
+            }
+
+            let module = alias.signature(self).module(self);
+
+            match self.resolve_path_with_depth(module, path, depth + chain.len()) {
+                Ok(Symbol::TypeAlias(next)) => alias = next,
+                Ok(symbol) => return (chain, Some(symbol)),
+                Err(_) => return (chain, None),
+            }
+        }
+    }
+
+    fn resolve_path_with_depth(
+        &self,
+        start: ModuleId,
+        path: &[IdentifierAST],
+        depth: usize,
+    ) -> Result<Symbol, Box<ResolutionError>> {
+        let Some((first, rest)) = path.split_first() else {
+            return Ok(Symbol::Module(start));
+        };
+
+        let mut symbol = self
+            .resolve_first_path_segment(start, *first)
+            .ok_or_else(|| {
+                Box::new(ResolutionError {
+                    failed_segment: *first,
+                    searched_in: Symbol::Module(start),
+                    previous_segment: None,
+                    alias: None,
+                    reason: ResolutionErrorKind::NotInScope,
+                })
+            })?;
+        let mut previous_segment = *first;
+
+        for segment in rest {
+            let alias = match symbol {
+                Symbol::TypeAlias(alias) => Some(alias),
+                _ => None,
+            };
+
+            if let Some(alias) = alias {
+                if let (_, Some(expanded)) = self.expand_type_alias_with_depth(alias, depth) {
+                    symbol = expanded;
+                }
+            }
+
+            let error = |reason| {
+                Box::new(ResolutionError {
+                    failed_segment: *segment,
+                    searched_in: symbol,
+                    previous_segment: Some(previous_segment),
+                    alias,
+                    reason,
+                })
+            };
+
+            let (member, imported) = match symbol {
+                Symbol::Module(module) => match module.local_symbol_or_none(self, segment.id) {
+                    Some(member) => (Some(member), false),
+                    None => (
+                        module.resolved_imports(self).get(&segment.id).copied(),
+                        true,
+                    ),
+                },
+                Symbol::Enum(enum_) => (enum_.item(self, segment.id).map(Symbol::EnumItem), false),
+                _ => return Err(error(ResolutionErrorKind::NotANamespace)),
+            };
+
+            let member = member.ok_or_else(|| {
+                error(match symbol {
+                    Symbol::Module(module) if module.load_failed(self) => {
+                        ResolutionErrorKind::ModuleFailedToLoad
+                    }
+                    _ => ResolutionErrorKind::NotFoundInNamespace,
+                })
+            })?;
+
+            // Private items and imports are only accessible within their package.
+            if symbol.module(self).package() != start.package()
+                && (imported || member.visibility(self) == Visibility::Private)
+            {
+                return Err(error(ResolutionErrorKind::PrivateItem));
+            }
+
+            symbol = member;
+            previous_segment = *segment;
+        }
+
+        Ok(symbol)
+    }
+
+    fn resolve_first_path_segment(
+        &self,
+        module: ModuleId,
+        segment: IdentifierAST,
+    ) -> Option<Symbol> {
+        module.symbol_or_none(self, segment.id).or_else(|| {
+            let package = module.package();
+
+            if segment.id == package.name(self) {
+                Some(Symbol::Module(package.root_module(self)))
+            } else {
+                package
+                    .dependencies(self)
+                    .get(&segment.id)
+                    .map(|dependency| Symbol::Module(dependency.root_module(self)))
+            }
+        })
+    }
+}
@@ -0,0 +1,120 @@
+//! Defines [`Database::find_symbols_by_name()`] and
+//! [`Database::find_symbols_matching()`] - lookup of definitions by name
+//! across a whole package, e.g. for workspace symbol search, when the module
+//! path of the definition is not known.
+
+use stellar_interner::IdentifierId;
+
+use crate::{Database, EnumId, ModuleId, PackageId, Symbol, DUMMY_MODULE_ID};
+
+impl Database {
+    /// Returns module items and enum items with a given name, that are
+    /// defined in a package.
+    ///
+    /// Symbols are ordered by paths of modules, that define them. Enum items
+    /// come after module items of the same module, ordered by enum names.
+    #[must_use]
+    pub fn find_symbols_by_name(&self, package: PackageId, name: IdentifierId) -> Vec<Symbol> {
+        let mut symbols = Vec::new();
+
+        for module in self.modules_of_package(package) {
+            symbols.extend(module.module_item_symbol_or_none(self, name));
+            symbols.extend(
+                self.enums_of_module(module)
+                    .into_iter()
+                    .filter_map(|enum_| enum_.item(self, name))
+                    .map(Symbol::EnumItem),
+            );
+        }
+
+        symbols
+    }
+
+    /// Returns module items and enum items defined in a package, names of
+    /// which satisfy a given predicate, e.g. start with a query typed by
+    /// the user.
+    ///
+    /// Symbols are ordered by paths of modules, that define them, and then
+    /// by names. Enum items come after module items of the same module.
+    #[must_use]
+    pub fn find_symbols_matching(
+        &self,
+        package: PackageId,
+        predicate: impl Fn(&str) -> bool,
+    ) -> Vec<Symbol> {
+        let mut symbols = Vec::new();
+
+        for module in self.modules_of_package(package) {
+            symbols.extend(sorted_by_name(
+                module
+                    .module_item_symbols(self)
+                    .iter()
+                    .filter(|(name, _)| predicate(name.as_str()))
+                    .map(|(name, symbol)| (*name, *symbol)),
+            ));
+
+            for enum_ in self.enums_of_module(module) {
+                symbols.extend(sorted_by_name(
+                    enum_
+                        .items(self)
+                        .iter()
+                        .filter(|(name, _)| predicate(name.as_str()))
+                        .map(|(name, item)| (*name, Symbol::EnumItem(*item))),
+                ));
+            }
+        }
+
+        symbols
+    }
+
+    /// Returns enums defined in a module, ordered by their names.
+    fn enums_of_module(&self, module: ModuleId) -> Vec<EnumId> {
+        sorted_by_name(
+            module
+                .module_item_symbols(self)
+                .iter()
+                .filter_map(|(name, symbol)| match symbol {
+                    Symbol::Enum(enum_) => Some((*name, *enum_)),
+                    _ => None,
+                }),
+        )
+    }
+
+    /// Returns modules of a package, including inline ones, ordered by
+    /// their paths.
+    fn modules_of_package(&self, package: PackageId) -> Vec<ModuleId> {
+        let Some(root) = package
+            .root_module_or_none(self)
+            .filter(|root| *root != DUMMY_MODULE_ID)
+        else {
+            return Vec::new();
+        };
+
+        let mut modules = Vec::new();
+        let mut stack = vec![root];
+
+        while let Some(module) = stack.pop() {
+            modules.push(module);
+            stack.extend(module.submodules(self).values().copied());
+        }
+
+        modules.sort_by_cached_key(|module| {
+            module
+                .path(self)
+                .segments()
+                .iter()
+                .map(|segment| segment.as_str())
+                .collect::<Vec<_>>()
+        });
+
+        modules
+    }
+}
+
+/// Sorts named values by their names, so that results of lookups don't
+/// depend on the order of hash map entries.
+fn sorted_by_name<T>(values: impl Iterator<Item = (IdentifierId, T)>) -> Vec<T> {
+    let mut values = values.collect::<Vec<_>>();
+    values.sort_unstable_by_key(|(name, _)| name.as_str());
+    values.into_iter().map(|(_, value)| value).collect()
+}
@@ -0,0 +1,302 @@
+//! Defines [`Database::snapshot()`] and [`Database::rollback()`] - a way to
+//! run a speculative analysis, e.g. to try unifying a type with several
+//! candidate methods or to analyze a broken buffer in the language server,
+//! and to undo everything it added to the database, if it fails.
+//!
+//! A snapshot captures lengths of entity storages, so that entities
+//! allocated after it can be removed by truncating them. Insertions into
+//! maps of already existing entities, e.g. [`ModuleId::add_module_item()`],
+//! are recorded into a journal, while at least one snapshot is taken, and
+//! are undone in reverse order.
+//!
+//! **Note**: updates of plain fields of existing entities, e.g. attributes
+//! or types of type aliases, and changes made directly through
+//! [`ModuleId::submodules_mut()`] are not recorded.
+
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::IdentifierId;
+
+use crate::{
+    Database, EnumId, EnumItemId, FunctionId, GenericParameterId, GenericParameterScopeId,
+    ModuleId, PackageData, PackageId, Symbol,
+};
+
+/// Generates [`StorageLengths`], that stores lengths of given entity storages
+/// of a package.
+macro_rules! storage_lengths {
+    { $($storage:ident),* } => {
+        /// Lengths of entity storages of a package at the time a snapshot is
+        /// taken.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        struct StorageLengths {
+            $($storage: usize,)*
+        }
+
+        impl StorageLengths {
+            fn of(package: &PackageData) -> Self {
+                Self {
+                    $($storage: package.$storage.len(),)*
+                }
+            }
+
+            /// Removes entities allocated after the lengths were captured.
+            fn truncate(self, package: &mut PackageData) {
+                $(package.$storage.truncate(self.$storage);)*
+            }
+        }
+    };
+}
+
+storage_lengths! {
+    module_,
+    enum_,
+    enum_item_,
+    predicate_,
+    struct_,
+    tuple_like_struct_,
+    field_,
+    function_,
+    interface_,
+    type_alias_,
+    generic_parameter_scope_,
+    generic_parameter_,
+    signature_
+}
+
+/// A state of the database, that it can be rolled back to, see
+/// [`Database::snapshot()`].
+#[derive(Debug, PartialEq, Eq)]
+#[must_use = "a snapshot must be either rolled back or committed"]
+pub struct DatabaseSnapshot {
+    /// Lengths of entity storages of every package, that existed at the
+    /// time the snapshot was taken.
+    packages: Vec<StorageLengths>,
+    journal_length: usize,
+
+    /// Number of snapshots taken before this one and not released yet, used
+    /// to check, that snapshots are released in the reverse order.
+    depth: usize,
+}
+
+/// A change of a map of an existing entity, that can be undone.
+#[derive(Debug)]
+pub(crate) enum JournalEntry {
+    ModuleItem {
+        module: ModuleId,
+        name: IdentifierId,
+        previous: Option<Symbol>,
+    },
+    Submodule {
+        module: ModuleId,
+        name: IdentifierId,
+        previous: Option<ModuleId>,
+    },
+    Parent {
+        module: ModuleId,
+        previous: Option<ModuleId>,
+    },
+    ResolvedImport {
+        module: ModuleId,
+        name: IdentifierId,
+        previous: Option<Symbol>,
+    },
+    DependencyAdded {
+        module: ModuleId,
+        dependency: ModuleId,
+    },
+    DependencyRemoved {
+        module: ModuleId,
+        dependency: ModuleId,
+    },
+    EnumItem {
+        enum_: EnumId,
+        name: IdentifierId,
+        previous: Option<EnumItemId>,
+        removed_method: Option<FunctionId>,
+    },
+    EnumMethod {
+        enum_: EnumId,
+        name: IdentifierId,
+        previous: Option<FunctionId>,
+    },
+    GenericParameter {
+        scope: GenericParameterScopeId,
+        name: IdentifierId,
+        previous: Option<GenericParameterId>,
+    },
+    PackageDependency {
+        package: PackageId,
+        name: IdentifierId,
+        previous: Option<PackageId>,
+    },
+    RootModule {
+        package: PackageId,
+        previous: ModuleId,
+    },
+}
+
+impl Database {
+    /// Captures the current state of the database, so that all entities
+    /// allocated and map insertions made after it can be undone with
+    /// [`Database::rollback()`].
+    ///
+    /// Snapshots can be nested, but must be released, i.e. rolled back or
+    /// committed, in the reverse order they were taken in.
+    pub fn snapshot(&mut self) -> DatabaseSnapshot {
+        self.open_snapshots += 1;
+
+        DatabaseSnapshot {
+            packages: self.packages.iter().map(StorageLengths::of).collect(),
+            journal_length: self.journal.len(),
+            depth: self.open_snapshots - 1,
+        }
+    }
+
+    /// Restores the state of the database captured by a given snapshot. IDs
+    /// of entities, that existed at that time, stay valid.
+    ///
+    /// # Panics
+    /// Panics if a snapshot taken after the given one is not released yet.
+    pub fn rollback(&mut self, snapshot: DatabaseSnapshot) {
+        self.release(&snapshot);
+
+        while self.journal.len() > snapshot.journal_length {
+            let entry = self.journal.pop().unwrap();
+            self.undo(entry);
+        }
+
+        self.packages.truncate(snapshot.packages.len());
+
+        for (package, lengths) in self.packages.iter_mut().zip(snapshot.packages) {
+            lengths.truncate(package);
+        }
+    }
+
+    /// Keeps all changes made after a given snapshot was taken. If the
+    /// snapshot is nested, the changes can still be undone by rolling back
+    /// an outer one.
+    ///
+    /// # Panics
+    /// Panics if a snapshot taken after the given one is not released yet.
+    pub fn commit(&mut self, snapshot: DatabaseSnapshot) {
+        self.release(&snapshot);
+
+        if self.open_snapshots == 0 {
+            self.journal.clear();
+        }
+    }
+
+    /// Returns `true` if there are snapshots, that are not released yet.
+    #[inline]
+    #[must_use]
+    pub fn in_snapshot(&self) -> bool {
+        self.open_snapshots > 0
+    }
+
+    fn release(&mut self, snapshot: &DatabaseSnapshot) {
+        assert_eq!(
+            snapshot.depth + 1,
+            self.open_snapshots,
+            "snapshots must be released in the reverse order they were taken in"
+        );
+
+        self.open_snapshots -= 1;
+    }
+
+    /// Records a change, so that it can be undone, if there is a snapshot.
+    #[inline]
+    pub(crate) fn record(&mut self, entry: JournalEntry) {
+        if self.in_snapshot() {
+            self.journal.push(entry);
+        }
+    }
+
+    fn undo(&mut self, entry: JournalEntry) {
+        match entry {
+            JournalEntry::ModuleItem {
+                module,
+                name,
+                previous,
+            } => restore(
+                &mut module.get_data_mut(self).module_item_symbols,
+                name,
+                previous,
+            ),
+            JournalEntry::Submodule {
+                module,
+                name,
+                previous,
+            } => restore(&mut module.get_data_mut(self).submodules, name, previous),
+            JournalEntry::Parent { module, previous } => {
+                module.get_data_mut(self).parent = previous;
+            }
+            JournalEntry::ResolvedImport {
+                module,
+                name,
+                previous,
+            } => restore(
+                &mut module.get_data_mut(self).resolved_imports,
+                name,
+                previous,
+            ),
+            JournalEntry::DependencyAdded { module, dependency } => {
+                module.get_data_mut(self).dependencies.remove(&dependency);
+                dependency.get_data_mut(self).dependents.remove(&module);
+            }
+            JournalEntry::DependencyRemoved { module, dependency } => {
+                module.get_data_mut(self).dependencies.insert(dependency);
+                dependency.get_data_mut(self).dependents.insert(module);
+            }
+            JournalEntry::EnumItem {
+                enum_,
+                name,
+                previous,
+                removed_method,
+            } => {
+                let data = enum_.get_data_mut(self);
+
+                if previous.is_none() {
+                    // Items added later are undone first, so the item is the
+                    // last one.
+                    data.item_order.pop();
+                }
+
+                restore(&mut data.items, name, previous);
+
+                if let Some(method) = removed_method {
+                    data.methods.insert(name, method);
+                }
+            }
+            JournalEntry::EnumMethod {
+                enum_,
+                name,
+                previous,
+            } => restore(&mut enum_.get_data_mut(self).methods, name, previous),
+            JournalEntry::GenericParameter {
+                scope,
+                name,
+                previous,
+            } => restore(&mut scope.get_data_mut(self).parameters, name, previous),
+            JournalEntry::PackageDependency {
+                package,
+                name,
+                previous,
+            } => restore(&mut self.package_mut(package).dependencies, name, previous),
+            JournalEntry::RootModule { package, previous } => {
+                self.package_mut(package).root_module = previous;
+            }
+        }
+    }
+}
+
+/// Restores a value, that a map had for a given key before an insertion.
+fn restore<V>(map: &mut FxHashMap<IdentifierId, V>, key: IdentifierId, previous: Option<V>) {
+    match previous {
+        Some(value) => {
+            map.insert(key, value);
+        }
+        None => {
+            map.remove(&key);
+        }
+    }
+}
@@ -0,0 +1,279 @@
+//! Defines [`DatabaseStats`] - statistics about the number of entities stored
+//! in the [`Database`] and an estimate of the memory they use.
+//!
+//! Heap sizes are estimated by summing capacities of vectors and hash maps,
+//! so they don't include allocator overhead.
+
+use std::{
+    fmt::Display,
+    mem::{size_of, size_of_val},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::IdentifierId;
+
+use crate::{
+    ty::{Type, TypeConstructor},
+    Database, EnumData, EnumItemData, FieldData, FunctionData, GenericParameterData,
+    GenericParameterScopeData, InterfaceData, ModuleData, PredicateData, PredicateId,
+    SignatureData, StructData, TupleLikeStructData, TypeAliasData,
+};
+
+/// Statistics of a single storage in the database, e.g. of all enums.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StorageStats {
+    /// Name of the storage, e.g. `enums`.
+    pub name: String,
+
+    /// Number of entities in the storage.
+    pub count: usize,
+
+    /// Estimated heap size of the storage in bytes.
+    pub heap_size: usize,
+}
+
+/// Statistics of all storages in the database.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DatabaseStats {
+    /// Number of packages in the database.
+    pub packages: usize,
+
+    /// Statistics of every storage in the database.
+    pub storages: Vec<StorageStats>,
+}
+
+impl DatabaseStats {
+    /// Returns statistics of a storage with a given name.
+    #[inline]
+    #[must_use]
+    pub fn storage(&self, name: &str) -> Option<&StorageStats> {
+        self.storages.iter().find(|storage| storage.name == name)
+    }
+
+    /// Returns the total number of entities in the database.
+    #[inline]
+    #[must_use]
+    pub fn total_count(&self) -> usize {
+        self.storages.iter().map(|storage| storage.count).sum()
+    }
+
+    /// Returns the total estimated heap size of the database in bytes.
+    #[inline]
+    #[must_use]
+    pub fn total_heap_size(&self) -> usize {
+        self.storages.iter().map(|storage| storage.heap_size).sum()
+    }
+}
+
+impl Display for DatabaseStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name_width = self
+            .storages
+            .iter()
+            .map(|storage| storage.name.len())
+            .chain(["storage".len(), "total".len()])
+            .max()
+            .unwrap_or_default();
+
+        writeln!(
+            f,
+            "{:<name_width$}  {:>10}  {:>14}",
+            "storage", "count", "heap size (B)"
+        )?;
+
+        for storage in &self.storages {
+            writeln!(
+                f,
+                "{:<name_width$}  {:>10}  {:>14}",
+                storage.name, storage.count, storage.heap_size
+            )?;
+        }
+
+        write!(
+            f,
+            "{:<name_width$}  {:>10}  {:>14}",
+            "total",
+            self.total_count(),
+            self.total_heap_size()
+        )
+    }
+}
+
+impl Database {
+    /// Returns statistics about the number of entities stored in
+    /// the database and an estimate of the memory they use.
+    #[must_use]
+    pub fn stats(&self) -> DatabaseStats {
+        let mut storages = Vec::new();
+
+        macro_rules! storage {
+            ($name:literal, $field:ident) => {
+                storages.push(StorageStats {
+                    name: $name.to_owned(),
+                    count: self
+                        .packages
+                        .iter()
+                        .map(|package| package.$field.len())
+                        .sum(),
+                    heap_size: self
+                        .packages
+                        .iter()
+                        .map(|package| vec_heap_size(&package.$field))
+                        .sum(),
+                });
+            };
+        }
+
+        storage!("modules", module_);
+        storage!("enums", enum_);
+        storage!("enum items", enum_item_);
+        storage!("predicates", predicate_);
+        storage!("structs", struct_);
+        storage!("tuple-like structs", tuple_like_struct_);
+        storage!("fields", field_);
+        storage!("functions", function_);
+        storage!("interfaces", interface_);
+        storage!("type aliases", type_alias_);
+        storage!("generic parameter scopes", generic_parameter_scope_);
+        storage!("generic parameters", generic_parameter_);
+        storage!("signatures", signature_);
+
+        DatabaseStats {
+            packages: self.packages.len(),
+            storages,
+        }
+    }
+}
+
+/// Estimates the number of bytes a value owns on the heap.
+trait HeapSize {
+    fn heap_size(&self) -> usize;
+}
+
+fn vec_heap_size<T: HeapSize>(vec: &Vec<T>) -> usize {
+    vec.capacity() * size_of::<T>() + vec.iter().map(HeapSize::heap_size).sum::<usize>()
+}
+
+/// Every bucket of a hash map stores a key-value pair and a control byte.
+fn map_heap_size<K, V>(map: &FxHashMap<K, V>) -> usize {
+    map.capacity() * (size_of::<(K, V)>() + 1)
+}
+
+impl HeapSize for Type {
+    fn heap_size(&self) -> usize {
+        match self {
+            Self::Constructor(constructor) => constructor.heap_size(),
+            Self::Tuple { element_types } => vec_heap_size(element_types),
+            Self::Function {
+                parameter_types,
+                return_type,
+            } => vec_heap_size(parameter_types) + size_of::<Self>() + return_type.heap_size(),
+            Self::InterfaceObject { bounds } => vec_heap_size(bounds),
+            Self::Variable(_)
+            | Self::Unit
+            | Self::Unknown
+            | Self::Never
+            | Self::GenericParameter(_) => 0,
+        }
+    }
+}
+
+impl HeapSize for TypeConstructor {
+    fn heap_size(&self) -> usize {
+        vec_heap_size(&self.arguments) + self.argument_locations.capacity() * size_of::<Location>()
+    }
+}
+
+impl HeapSize for ModuleData {
+    fn heap_size(&self) -> usize {
+        size_of_val(self.path.segments())
+            + map_heap_size(&self.module_item_symbols)
+            + map_heap_size(&self.submodules)
+            + map_heap_size(&self.resolved_imports)
+    }
+}
+
+impl HeapSize for EnumData {
+    fn heap_size(&self) -> usize {
+        map_heap_size(&self.items)
+            + self.item_order.capacity() * size_of::<IdentifierId>()
+            + map_heap_size(&self.methods)
+    }
+}
+
+impl HeapSize for StructData {
+    fn heap_size(&self) -> usize {
+        map_heap_size(&self.fields) + map_heap_size(&self.methods)
+    }
+}
+
+impl HeapSize for TupleLikeStructData {
+    fn heap_size(&self) -> usize {
+        self.fields.capacity() * size_of::<(Visibility, Type)>()
+            + self
+                .fields
+                .iter()
+                .map(|(_, ty)| ty.heap_size())
+                .sum::<usize>()
+    }
+}
+
+impl HeapSize for FieldData {
+    fn heap_size(&self) -> usize {
+        self.ty.heap_size()
+    }
+}
+
+impl HeapSize for PredicateData {
+    fn heap_size(&self) -> usize {
+        self.ty.heap_size() + vec_heap_size(&self.bounds)
+    }
+}
+
+impl HeapSize for GenericParameterScopeData {
+    fn heap_size(&self) -> usize {
+        map_heap_size(&self.parameters)
+    }
+}
+
+impl HeapSize for GenericParameterData {
+    fn heap_size(&self) -> usize {
+        self.default_value.as_ref().map_or(0, HeapSize::heap_size)
+    }
+}
+
+impl HeapSize for EnumItemData {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl HeapSize for SignatureData {
+    fn heap_size(&self) -> usize {
+        self.predicates.capacity() * size_of::<PredicateId>() + vec_heap_size(&self.implements)
+    }
+}
+
+impl HeapSize for FunctionData {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl HeapSize for InterfaceData {
+    fn heap_size(&self) -> usize {
+        map_heap_size(&self.methods)
+    }
+}
+
+impl HeapSize for TypeAliasData {
+    fn heap_size(&self) -> usize {
+        self.ty.heap_size() + self.aliased_path.capacity() * size_of::<IdentifierAST>()
+    }
+}
@@ -1,4 +1,8 @@
-//! Defines [`Symbol`] and [`BuiltinSymbolId`].
+//! Defines [`Symbol`], [`SymbolKind`] and [`BuiltinSymbolId`].
+
+use derive_more::Display;
+use stellar_ast::NumericLiteralSuffix;
+use stellar_filesystem::location::DUMMY_LOCATION;
 
 use super::*;
 use crate::Path;
@@ -28,6 +32,27 @@ macro_rules! builtin_symbols {
                 }
             }
 
+            impl BuiltinSymbolId {
+                /// All builtin symbols in the order they are declared.
+                pub const ALL: &'static [Self] = &[
+                    $(
+                        Self::[<$name:camel>],
+                    )*
+                ];
+
+                /// Returns a builtin symbol with a given name, e.g. `int32`, if it exists.
+                #[inline]
+                #[must_use]
+                pub const fn from_identifier_or_none(id: IdentifierId) -> Option<Self> {
+                    match id {
+                        $(
+                            stellar_interner::builtin_identifiers::[<$name:upper>] => Some(Self::[<$name:camel>]),
+                        )*
+                        _ => None
+                    }
+                }
+            }
+
             impl From<BuiltinSymbolId> for IdentifierId {
                 #[inline]
                 fn from(id: BuiltinSymbolId) -> Self {
@@ -115,6 +140,25 @@ impl From<BuiltinSymbolId> for Path {
     }
 }
 
+impl From<NumericLiteralSuffix> for BuiltinSymbolId {
+    /// Returns the type a numeric literal with a given suffix has, e.g.
+    /// `uint8` for `255u8`.
+    fn from(suffix: NumericLiteralSuffix) -> Self {
+        match suffix {
+            NumericLiteralSuffix::I8 => Self::Int8,
+            NumericLiteralSuffix::I16 => Self::Int16,
+            NumericLiteralSuffix::I32 => Self::Int32,
+            NumericLiteralSuffix::I64 => Self::Int64,
+            NumericLiteralSuffix::U8 => Self::Uint8,
+            NumericLiteralSuffix::U16 => Self::Uint16,
+            NumericLiteralSuffix::U32 => Self::Uint32,
+            NumericLiteralSuffix::U64 => Self::Uint64,
+            NumericLiteralSuffix::F32 => Self::Float32,
+            NumericLiteralSuffix::F64 => Self::Float64,
+        }
+    }
+}
+
 symbols! {
     module,
     enum,
@@ -127,7 +171,92 @@ symbols! {
     builtin_symbol
 }
 
+/// A kind of symbol, used to phrase diagnostics, e.g. `expected a type, found a function`.
+///
+/// The last few kinds describe names, that are not module items and don't
+/// have a [`Symbol`], e.g. fields and local variables. They are used by
+/// tooling, e.g. by code completion.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Display)]
+pub enum SymbolKind {
+    #[display(fmt = "module")]
+    Module,
+
+    #[display(fmt = "enum")]
+    Enum,
+
+    #[display(fmt = "enum item")]
+    EnumItem,
+
+    #[display(fmt = "struct")]
+    Struct,
+
+    #[display(fmt = "tuple-like struct")]
+    TupleLikeStruct,
+
+    #[display(fmt = "function")]
+    Function,
+
+    #[display(fmt = "interface")]
+    Interface,
+
+    #[display(fmt = "type alias")]
+    TypeAlias,
+
+    #[display(fmt = "builtin type")]
+    BuiltinType,
+
+    #[display(fmt = "field")]
+    Field,
+
+    #[display(fmt = "method")]
+    Method,
+
+    #[display(fmt = "generic parameter")]
+    GenericParameter,
+
+    #[display(fmt = "local variable")]
+    LocalVariable,
+
+    #[display(fmt = "parameter")]
+    Parameter,
+}
+
+impl SymbolKind {
+    /// Returns `true` if symbols of the kind can be used as types.
+    #[inline]
+    #[must_use]
+    pub const fn is_type(self) -> bool {
+        matches!(
+            self,
+            Self::Enum
+                | Self::Struct
+                | Self::TupleLikeStruct
+                | Self::Interface
+                | Self::TypeAlias
+                | Self::BuiltinType
+                | Self::GenericParameter
+        )
+    }
+}
+
 impl Symbol {
+    /// Returns the kind of the symbol.
+    #[inline]
+    #[must_use]
+    pub const fn kind(self) -> SymbolKind {
+        match self {
+            Self::Module(_) => SymbolKind::Module,
+            Self::Enum(_) => SymbolKind::Enum,
+            Self::EnumItem(_) => SymbolKind::EnumItem,
+            Self::Struct(_) => SymbolKind::Struct,
+            Self::TupleLikeStruct(_) => SymbolKind::TupleLikeStruct,
+            Self::Function(_) => SymbolKind::Function,
+            Self::Interface(_) => SymbolKind::Interface,
+            Self::TypeAlias(_) => SymbolKind::TypeAlias,
+            Self::BuiltinSymbol(_) => SymbolKind::BuiltinType,
+        }
+    }
+
     /// Returns the signature of the symbol.
     #[inline]
     #[must_use]
@@ -162,10 +291,7 @@ impl Symbol {
     #[must_use]
     pub fn name(self, db: &Database) -> IdentifierAST {
         match self {
-            Self::Module(module) => IdentifierAST {
-                location: DUMMY_LOCATION,
-                id: module.name(db),
-            },
+            Self::Module(module) => module.name(db),
             Self::Enum(enum_) => enum_.signature(db).name(db),
             Self::Struct(struct_) => struct_.signature(db).name(db),
             Self::Function(function) => function.signature(db).name(db),
@@ -177,6 +303,42 @@ impl Symbol {
         }
     }
 
+    /// Returns the visibility of the symbol. Enum items and builtin symbols
+    /// are always public, modules are public unless they are private inline
+    /// modules.
+    #[inline]
+    #[must_use]
+    pub fn visibility(self, db: &Database) -> Visibility {
+        match self {
+            Self::Enum(enum_) => enum_.visibility(db),
+            Self::Struct(_)
+            | Self::Function(_)
+            | Self::Interface(_)
+            | Self::TupleLikeStruct(_)
+            | Self::TypeAlias(_) => self.signature(db).visibility(db),
+            Self::Module(module) => module.visibility(db),
+            Self::EnumItem(_) | Self::BuiltinSymbol(_) => Visibility::Public(DUMMY_LOCATION),
+        }
+    }
+
+    /// Returns the `#[deprecated]` attribute of the symbol, if any. Enum items
+    /// are deprecated together with their enums.
+    #[inline]
+    #[must_use]
+    pub fn deprecation(self, db: &Database) -> Option<&Deprecation> {
+        match self {
+            Self::Enum(_)
+            | Self::Struct(_)
+            | Self::Function(_)
+            | Self::Interface(_)
+            | Self::TupleLikeStruct(_)
+            | Self::TypeAlias(_) => self.signature(db).attributes(db).deprecation.as_ref(),
+            Self::Module(module) => module.attributes(db).deprecation.as_ref(),
+            Self::EnumItem(item) => Self::Enum(item.enum_(db)).deprecation(db),
+            Self::BuiltinSymbol(_) => None,
+        }
+    }
+
     #[inline]
     #[must_use]
     pub fn module_item_kind_or_none(self) -> Option<ModuleItemKind> {
@@ -200,6 +362,11 @@ impl Symbol {
     #[inline]
     #[must_use]
     pub fn path(self, db: &Database) -> Path {
+        // Builtin symbols are not defined in modules.
+        if let Self::BuiltinSymbol(symbol) = self {
+            return symbol.into();
+        }
+
         let path = self.module(db).path(db).clone();
 
         match self {
@@ -213,7 +380,7 @@ impl Symbol {
             Self::EnumItem(item) => {
                 path + item.enum_(db).signature(db).name(db).id + item.name(db).id
             }
-            Self::BuiltinSymbol(symbol) => symbol.into(),
+            Self::BuiltinSymbol(_) => unreachable!(),
         }
     }
 }
@@ -1,13 +1,18 @@
 //! Defines [`Type`] for working with types and THIR nodes.
 
-use std::fmt::Display;
+use std::{
+    fmt::{self, Display},
+    hash::{Hash, Hasher},
+};
 
 use derive_more::Display;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use stellar_filesystem::location::Location;
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::IdentifierId;
 
-use crate::{symbol::BuiltinSymbolId, GenericParameterId, Symbol};
+use crate::{symbol::BuiltinSymbolId, Database, GenericParameterId, Symbol};
 
 /// A raw representation of types in the Stellar programming language.
 ///
@@ -26,6 +31,11 @@ pub enum Type {
     #[cfg_attr(feature = "serde", serde(rename = "unknown_type"))]
     Unknown,
 
+    /// A type of expressions, that never produce a value, e.g. a block
+    /// ending with `return`. Coerces to any other type.
+    #[cfg_attr(feature = "serde", serde(rename = "never_type"))]
+    Never,
+
     /// A type constructor: `List[uint32]`, `uint32`, `String`.
     ///
     /// Anything that has name and optionally have generic arguments.
@@ -94,6 +104,10 @@ pub enum TypeKind {
     #[display(fmt = "unknown type")]
     Unknown,
 
+    /// A type of expressions, that never produce a value.
+    #[display(fmt = "never type")]
+    Never,
+
     /// A type constructor: `List[uint32]`, `uint32`, `String`.
     ///
     /// Anything that has name and optionally have generic arguments.
@@ -143,6 +157,7 @@ impl Type {
             Self::InterfaceObject { .. } => TypeKind::InterfaceObject,
             Self::Unit => TypeKind::Unit,
             Self::Unknown => TypeKind::Unknown,
+            Self::Never => TypeKind::Never,
         }
     }
 }
@@ -193,18 +208,62 @@ pub struct TypeVariableId(pub usize);
 /// A type constructor: `List[uint32]`, `uint32`, `String`.
 ///
 /// Anything that has name and optionally have generic arguments.
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+///
+/// **Note**: locations of generic arguments are not compared and hashed, so
+/// `Map[String, int32]` written in different places is the same type.
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TypeConstructor {
     pub symbol: Symbol,
     pub arguments: Vec<Type>,
+
+    /// Locations of generic arguments in the source code, e.g. location of
+    /// `BadType` in `Map[String, BadType]`. Empty, if the type is not written
+    /// in the source code, e.g. if it is inferred.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub argument_locations: Vec<Location>,
 }
 
 impl TypeConstructor {
     #[inline]
     #[must_use]
     pub const fn new(symbol: Symbol, arguments: Vec<Type>) -> Self {
-        Self { symbol, arguments }
+        Self {
+            symbol,
+            arguments,
+            argument_locations: Vec::new(),
+        }
+    }
+
+    /// Sets locations of generic arguments, see [`TypeConstructor::argument_location()`].
+    #[inline]
+    #[must_use]
+    pub fn with_argument_locations(mut self, argument_locations: Vec<Location>) -> Self {
+        self.argument_locations = argument_locations;
+        self
+    }
+
+    /// Returns location of the generic argument with a given index, if the
+    /// type is written in the source code.
+    #[inline]
+    #[must_use]
+    pub fn argument_location(&self, idx: usize) -> Option<Location> {
+        self.argument_locations.get(idx).copied()
+    }
+}
+
+impl PartialEq for TypeConstructor {
+    fn eq(&self, other: &Self) -> bool {
+        self.symbol == other.symbol && self.arguments == other.arguments
+    }
+}
+
+impl Eq for TypeConstructor {}
+
+impl Hash for TypeConstructor {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.symbol.hash(state);
+        self.arguments.hash(state);
     }
 }
 
@@ -216,13 +275,155 @@ impl Type {
     }
 }
 
+impl Type {
+    /// Returns an object, that renders the type the way it is written in the
+    /// source code, e.g. `List[String]`, `(int32,)`, `fun(int32): bool` or
+    /// `dyn Iterator[char] + ToString`.
+    ///
+    /// [`Type::Unknown`] is rendered as `_`, [`Type::Never`] as `!` and type
+    /// variables as `?T0`, `?T1`, etc. (see [`TypeDisplay::with_variable_names()`]).
+    ///
+    /// ```ignore
+    /// let message = format!("expected `{}`", ty.display(db));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn display<'a>(&'a self, db: &'a Database) -> TypeDisplay<'a> {
+        TypeDisplay {
+            ty: self,
+            db,
+            variable_names: None,
+        }
+    }
+}
+
+/// Renders a type, see [`Type::display()`].
+///
+/// Types are rendered without redundant parentheses: the only ambiguous
+/// type is a tuple with one element, which is rendered with a trailing
+/// comma, e.g. `(int32,)`.
+#[derive(Debug, Clone, Copy)]
+pub struct TypeDisplay<'a> {
+    ty: &'a Type,
+    db: &'a Database,
+    variable_names: Option<&'a FxHashMap<TypeVariableId, String>>,
+}
+
+impl<'a> TypeDisplay<'a> {
+    /// Renders type variables from a given map with their names instead of
+    /// `?T0`, e.g. as `{integer}`.
+    #[inline]
+    #[must_use]
+    pub const fn with_variable_names(
+        mut self,
+        variable_names: &'a FxHashMap<TypeVariableId, String>,
+    ) -> Self {
+        self.variable_names = Some(variable_names);
+        self
+    }
+
+    const fn nested(self, ty: &'a Type) -> Self {
+        Self { ty, ..self }
+    }
+
+    fn fmt_list(self, f: &mut fmt::Formatter<'_>, types: &[Type]) -> fmt::Result {
+        for (idx, ty) in types.iter().enumerate() {
+            if idx != 0 {
+                f.write_str(", ")?;
+            }
+
+            self.nested(ty).fmt(f)?;
+        }
+
+        Ok(())
+    }
+
+    fn fmt_constructor(
+        self,
+        f: &mut fmt::Formatter<'_>,
+        constructor: &TypeConstructor,
+    ) -> fmt::Result {
+        match constructor.symbol {
+            Symbol::BuiltinSymbol(builtin) => write!(f, "{}", IdentifierId::from(builtin))?,
+            symbol => write!(f, "{}", symbol.name(self.db).id)?,
+        }
+
+        if !constructor.arguments.is_empty() {
+            f.write_str("[")?;
+            self.fmt_list(f, &constructor.arguments)?;
+            f.write_str("]")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for TypeDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.ty {
+            Type::Unit => f.write_str("()"),
+            Type::Unknown => f.write_str("_"),
+            Type::Never => f.write_str("!"),
+            Type::Constructor(constructor) => self.fmt_constructor(f, constructor),
+            Type::Tuple { element_types } => {
+                f.write_str("(")?;
+                self.fmt_list(f, element_types)?;
+
+                if element_types.len() == 1 {
+                    f.write_str(",")?;
+                }
+
+                f.write_str(")")
+            }
+            Type::Function {
+                parameter_types,
+                return_type,
+            } => {
+                f.write_str("fun(")?;
+                self.fmt_list(f, parameter_types)?;
+                f.write_str(")")?;
+
+                if **return_type != Type::Unit {
+                    write!(f, ": {}", self.nested(return_type))?;
+                }
+
+                Ok(())
+            }
+            Type::Variable(variable) => match self
+                .variable_names
+                .and_then(|names| names.get(&variable.id()))
+            {
+                Some(name) => f.write_str(name),
+                None => write!(f, "?T{}", variable.id()),
+            },
+            Type::GenericParameter(parameter) => match parameter.name(self.db) {
+                Some(name) => write!(f, "{name}"),
+                None => f.write_str("_"),
+            },
+            Type::InterfaceObject { bounds } => {
+                f.write_str("dyn ")?;
+
+                for (idx, bound) in bounds.iter().enumerate() {
+                    if idx != 0 {
+                        f.write_str(" + ")?;
+                    }
+
+                    self.fmt_constructor(f, bound)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
 /// Returns a list type with the given element type.
 #[inline]
 #[must_use]
 #[allow(dead_code)]
 pub fn list_of(element_type: Type) -> Type {
-    Type::Constructor(TypeConstructor {
-        symbol: Symbol::BuiltinSymbol(BuiltinSymbolId::List),
-        arguments: vec![element_type],
-    })
+    Type::Constructor(TypeConstructor::new(
+        Symbol::BuiltinSymbol(BuiltinSymbolId::List),
+        vec![element_type],
+    ))
 }
@@ -0,0 +1,88 @@
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_database::{
+    Database, ModuleData, ModuleId, PackageData, Path, SignatureData, StructData, Symbol,
+    DUMMY_STRUCT_ID,
+};
+use stellar_filesystem::location::DUMMY_LOCATION;
+use stellar_interner::{IdentifierId, PathId, DUMMY_IDENTIFIER_ID};
+
+fn identifier(name: &str) -> IdentifierAST {
+    IdentifierAST {
+        location: DUMMY_LOCATION,
+        id: IdentifierId::from(name),
+    }
+}
+
+fn module(db: &mut Database) -> ModuleId {
+    let package = PackageData::alloc(db, DUMMY_IDENTIFIER_ID, PathId::from("geo"));
+
+    ModuleData::alloc(
+        db,
+        package,
+        identifier("shapes"),
+        Path::new(vec![
+            IdentifierId::from("geo"),
+            IdentifierId::from("shapes"),
+        ]),
+        PathId::from("src/shapes.sr"),
+    )
+}
+
+#[test]
+fn struct_() {
+    let mut db = Database::new();
+    let module = module(&mut db);
+    let signature =
+        SignatureData::alloc(&mut db, Visibility::Private, identifier("Point"), 0, module);
+    let struct_ = StructData::alloc(&mut db, signature);
+
+    assert_eq!(
+        Symbol::Struct(struct_).debug(&db).to_string(),
+        "struct `Point` (id 1, module src/shapes.sr)"
+    );
+    assert_eq!(
+        signature.debug(&db).to_string(),
+        "signature of `Point` (id 1, module src/shapes.sr)"
+    );
+}
+
+#[test]
+fn module_() {
+    let mut db = Database::new();
+    let module = module(&mut db);
+
+    assert_eq!(
+        Symbol::Module(module).debug(&db).to_string(),
+        "module `geo.shapes` (id 1, src/shapes.sr)"
+    );
+}
+
+#[test]
+fn unknown() {
+    let mut db = Database::new();
+    let module = module(&mut db);
+
+    // IDs of rolled back entities don't refer to anything.
+    let snapshot = db.snapshot();
+    let signature =
+        SignatureData::alloc(&mut db, Visibility::Private, identifier("Point"), 0, module);
+    let struct_ = StructData::alloc(&mut db, signature);
+    let submodule = ModuleData::alloc(
+        &mut db,
+        module.package(),
+        identifier("circle"),
+        Path::new(vec![IdentifierId::from("circle")]),
+        PathId::from("src/circle.sr"),
+    );
+    db.rollback(snapshot);
+
+    assert_eq!(struct_.debug(&db).to_string(), "struct <unknown> (id 1)");
+    assert_eq!(
+        DUMMY_STRUCT_ID.debug(&db).to_string(),
+        "struct <unknown> (id 0)"
+    );
+    assert_eq!(
+        format!("{:?}", submodule.debug(&db)),
+        "module <unknown> (id 2)"
+    );
+}
@@ -0,0 +1,137 @@
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_database::{
+    Database, DefinitionId, EnumData, EnumItemData, FunctionData, InterfaceData, ModuleData,
+    ModuleId, PackageData, Path, SignatureData, SignatureId, StructData, Symbol,
+    TupleLikeStructData, TypeAliasData,
+};
+use stellar_filesystem::location::DUMMY_LOCATION;
+use stellar_interner::{IdentifierId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+
+fn identifier(name: &str) -> IdentifierAST {
+    IdentifierAST {
+        location: DUMMY_LOCATION,
+        id: IdentifierId::from(name),
+    }
+}
+
+fn module(db: &mut Database, name: &str) -> ModuleId {
+    let package = PackageData::alloc(db, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+
+    ModuleData::alloc(
+        db,
+        package,
+        identifier(name),
+        Path::new(vec![IdentifierId::from(name)]),
+        DUMMY_PATH_ID,
+    )
+}
+
+/// Defines a module item with a given name in the module.
+fn define(
+    db: &mut Database,
+    module: ModuleId,
+    name: &str,
+    alloc: impl FnOnce(&mut Database, SignatureId) -> Symbol,
+) -> Symbol {
+    let name = identifier(name);
+    let node_idx = module.module_item_symbols(db).len();
+    let signature = SignatureData::alloc(db, Visibility::Private, name, node_idx, module);
+    let symbol = alloc(db, signature);
+
+    module.add_module_item(db, name.id, symbol);
+
+    symbol
+}
+
+#[test]
+fn round_trip() {
+    let mut db = Database::new();
+    let module = module(&mut db, "test");
+
+    let symbols = [
+        define(&mut db, module, "E", |db, signature| {
+            EnumData::alloc(db, signature).into()
+        }),
+        define(&mut db, module, "S", |db, signature| {
+            StructData::alloc(db, signature).into()
+        }),
+        define(&mut db, module, "T", |db, signature| {
+            TupleLikeStructData::alloc(db, signature).into()
+        }),
+        define(&mut db, module, "f", |db, signature| {
+            FunctionData::alloc(db, signature).into()
+        }),
+        define(&mut db, module, "I", |db, signature| {
+            InterfaceData::alloc(db, signature).into()
+        }),
+        define(&mut db, module, "A", |db, signature| {
+            TypeAliasData::alloc(db, signature).into()
+        }),
+    ];
+
+    for symbol in symbols {
+        let id = db.definition_id_of(symbol).unwrap();
+
+        assert_eq!(id.module, module);
+        assert_eq!(id.name, symbol.name(&db).id);
+        assert_eq!(db.symbol_by_definition_id(id), Some(symbol));
+    }
+}
+
+#[test]
+fn submodule_round_trip() {
+    let mut db = Database::new();
+    let parent = module(&mut db, "parent");
+    let submodule = ModuleData::alloc(
+        &mut db,
+        parent.package(),
+        identifier("child"),
+        Path::new(vec![
+            IdentifierId::from("parent"),
+            IdentifierId::from("child"),
+        ]),
+        DUMMY_PATH_ID,
+    );
+    parent.add_submodule(&mut db, submodule);
+
+    let id = db.definition_id_of(Symbol::Module(submodule)).unwrap();
+
+    assert_eq!(
+        id,
+        DefinitionId {
+            module: parent,
+            name: IdentifierId::from("child"),
+        }
+    );
+    assert_eq!(
+        db.symbol_by_definition_id(id),
+        Some(Symbol::Module(submodule))
+    );
+    assert_eq!(db.definition_id_of(Symbol::Module(parent)), None);
+}
+
+#[test]
+fn enum_items_have_no_definition_ids() {
+    let mut db = Database::new();
+    let module = module(&mut db, "test");
+    let enum_ = define(&mut db, module, "E", |db, signature| {
+        EnumData::alloc(db, signature).into()
+    });
+    let item = EnumItemData::alloc(&mut db, enum_.to_enum(), identifier("A"), module);
+
+    assert_eq!(db.definition_id_of(Symbol::EnumItem(item)), None);
+}
+
+#[test]
+fn never_defined() {
+    let mut db = Database::new();
+    let module = module(&mut db, "test");
+
+    assert_eq!(
+        db.symbol_by_definition_id(DefinitionId {
+            module,
+            name: IdentifierId::from("missing"),
+        }),
+        None
+    );
+}
@@ -0,0 +1,73 @@
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_database::{
+    Database, EnumData, EnumId, EnumItemData, FunctionData, FunctionId, ModuleData, ModuleId,
+    PackageData, Path, SignatureData,
+};
+use stellar_filesystem::location::DUMMY_LOCATION;
+use stellar_interner::{IdentifierId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+
+fn identifier(name: &str) -> IdentifierAST {
+    IdentifierAST {
+        location: DUMMY_LOCATION,
+        id: IdentifierId::from(name),
+    }
+}
+
+fn enum_(db: &mut Database) -> (ModuleId, EnumId) {
+    let package = PackageData::alloc(db, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let module = ModuleData::alloc(
+        db,
+        package,
+        identifier("test"),
+        Path::new(vec![IdentifierId::from("test")]),
+        DUMMY_PATH_ID,
+    );
+    let signature = SignatureData::alloc(db, Visibility::Private, identifier("E"), 0, module);
+
+    (module, EnumData::alloc(db, signature))
+}
+
+fn method(db: &mut Database, module: ModuleId, name: &str) -> FunctionId {
+    let signature = SignatureData::alloc(db, Visibility::Private, identifier(name), 0, module);
+
+    FunctionData::alloc(db, signature)
+}
+
+#[test]
+fn method_added_after_item() {
+    let mut db = Database::new();
+    let (module, enum_) = enum_(&mut db);
+
+    let item = EnumItemData::alloc(&mut db, enum_, identifier("A"), module);
+    assert_eq!(enum_.add_item(&mut db, IdentifierId::from("A"), item), None);
+
+    let method = method(&mut db, module, "A");
+
+    assert_eq!(
+        enum_.add_method(&mut db, IdentifierId::from("A"), method),
+        Err(item)
+    );
+    assert_eq!(enum_.item(&db, IdentifierId::from("A")), Some(item));
+    assert_eq!(enum_.method(&db, IdentifierId::from("A")), None);
+}
+
+#[test]
+fn item_added_after_method() {
+    let mut db = Database::new();
+    let (module, enum_) = enum_(&mut db);
+
+    let method = method(&mut db, module, "A");
+    assert_eq!(
+        enum_.add_method(&mut db, IdentifierId::from("A"), method),
+        Ok(())
+    );
+
+    let item = EnumItemData::alloc(&mut db, enum_, identifier("A"), module);
+
+    assert_eq!(
+        enum_.add_item(&mut db, IdentifierId::from("A"), item),
+        Some(method)
+    );
+    assert_eq!(enum_.item(&db, IdentifierId::from("A")), Some(item));
+    assert_eq!(enum_.method(&db, IdentifierId::from("A")), None);
+}
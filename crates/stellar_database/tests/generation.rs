@@ -0,0 +1,70 @@
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_database::{Database, ModuleData, PackageData, Path, SignatureData, StructData};
+use stellar_filesystem::location::DUMMY_LOCATION;
+use stellar_interner::{IdentifierId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+
+#[test]
+fn ids_of_same_database() {
+    let mut db = Database::new();
+    let package = PackageData::alloc(&mut db, IdentifierId::from("foo"), DUMMY_PATH_ID);
+
+    assert_eq!(package.name(&db), IdentifierId::from("foo"));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "belongs to another database")]
+fn stale_generation() {
+    let mut first = Database::new();
+    let package = PackageData::alloc(&mut first, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let module = ModuleData::alloc(
+        &mut first,
+        package,
+        IdentifierAST {
+            location: DUMMY_LOCATION,
+            id: IdentifierId::from("foo"),
+        },
+        Path::new(vec![IdentifierId::from("foo")]),
+        DUMMY_PATH_ID,
+    );
+
+    // The second database has a package with the same index, so without the
+    // generation check the lookup would silently succeed.
+    let mut second = Database::new();
+    PackageData::alloc(&mut second, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+
+    let _ = module.filepath(&second);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "belongs to another database")]
+fn stale_generation_of_item() {
+    let mut first = Database::new();
+    let package = PackageData::alloc(&mut first, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let module = ModuleData::alloc(
+        &mut first,
+        package,
+        IdentifierAST {
+            location: DUMMY_LOCATION,
+            id: IdentifierId::from("foo"),
+        },
+        Path::new(vec![IdentifierId::from("foo")]),
+        DUMMY_PATH_ID,
+    );
+    let signature = SignatureData::alloc(
+        &mut first,
+        Visibility::Private,
+        IdentifierAST {
+            location: DUMMY_LOCATION,
+            id: IdentifierId::from("Point"),
+        },
+        0,
+        module,
+    );
+
+    let mut second = Database::new();
+    PackageData::alloc(&mut second, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+
+    let _ = StructData::alloc(&mut second, signature);
+}
@@ -0,0 +1,91 @@
+use std::time::{Duration, Instant};
+
+use stellar_database::{
+    Database, GenericParameterData, GenericParameterId, GenericParameterScopeData, PackageData,
+    PackageId,
+};
+use stellar_filesystem::location::{ByteOffset, Location, DUMMY_LOCATION};
+use stellar_interner::{IdentifierId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+
+#[test]
+fn deep_parent_chain() {
+    let mut db = Database::new();
+    let package = PackageData::alloc(&mut db, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+
+    let root = GenericParameterScopeData::alloc(&mut db, package, None);
+    let parameter = GenericParameterData::alloc(&mut db, package, DUMMY_LOCATION, None);
+    root.add_generic_parameter(&mut db, IdentifierId::from("T"), parameter);
+
+    let mut scope = root;
+
+    for _ in 0..10_000 {
+        scope = scope.child(&mut db);
+    }
+
+    let now = Instant::now();
+
+    assert_eq!(scope.resolve(&db, IdentifierId::from("T")), Some(parameter));
+    assert!(scope.contains(&db, IdentifierId::from("T")));
+    assert!(!scope.contains(&db, IdentifierId::from("M")));
+
+    assert!(now.elapsed() < Duration::from_secs(10));
+}
+
+fn parameter_at(db: &mut Database, package: PackageId, offset: usize) -> GenericParameterId {
+    let location = Location {
+        start: ByteOffset(offset),
+        end: ByteOffset(offset + 1),
+        ..DUMMY_LOCATION
+    };
+
+    GenericParameterData::alloc(db, package, location, None)
+}
+
+#[test]
+fn transitive_iteration_with_shadowing() {
+    let mut db = Database::new();
+    let package = PackageData::alloc(&mut db, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+
+    // interface Foo[T, U] { fun bar[T, M](); }
+    let outer = GenericParameterScopeData::alloc(&mut db, package, None);
+    let outer_t = parameter_at(&mut db, package, 14);
+    let outer_u = parameter_at(&mut db, package, 17);
+    outer.add_generic_parameter(&mut db, IdentifierId::from("U"), outer_u);
+    outer.add_generic_parameter(&mut db, IdentifierId::from("T"), outer_t);
+
+    let inner = outer.child(&mut db);
+    let inner_t = parameter_at(&mut db, package, 30);
+    let inner_m = parameter_at(&mut db, package, 33);
+    inner.add_generic_parameter(&mut db, IdentifierId::from("M"), inner_m);
+    inner.add_generic_parameter(&mut db, IdentifierId::from("T"), inner_t);
+
+    assert_eq!(inner.parent_scope(&db), Some(outer));
+    assert_eq!(inner.package(), package);
+
+    assert_eq!(
+        inner.iter_all(&db).collect::<Vec<_>>(),
+        [
+            (IdentifierId::from("T"), inner_t),
+            (IdentifierId::from("M"), inner_m),
+            (IdentifierId::from("U"), outer_u),
+        ]
+    );
+    assert_eq!(inner.len(&db), 3);
+    assert_eq!(outer.len(&db), 2);
+    assert!(!inner.is_empty(&db));
+
+    assert_eq!(inner.display(&db), "[U, T, M]");
+    assert_eq!(outer.display(&db), "[T, U]");
+}
+
+#[test]
+fn empty_scope() {
+    let mut db = Database::new();
+    let package = PackageData::alloc(&mut db, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+
+    let scope = GenericParameterScopeData::alloc(&mut db, package, None).child(&mut db);
+
+    assert!(scope.is_empty(&db));
+    assert_eq!(scope.len(&db), 0);
+    assert_eq!(scope.display(&db), "[]");
+}
@@ -0,0 +1,98 @@
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_database::{
+    Database, EnumData, ModuleData, ModuleId, PackageData, Path, SignatureData, StructData, Symbol,
+};
+use stellar_filesystem::location::DUMMY_LOCATION;
+use stellar_interner::{IdentifierId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+
+fn identifier(name: &str) -> IdentifierAST {
+    IdentifierAST {
+        location: DUMMY_LOCATION,
+        id: IdentifierId::from(name),
+    }
+}
+
+fn submodule(db: &mut Database, parent: ModuleId, name: &str, visibility: Visibility) -> ModuleId {
+    let module = ModuleData::alloc(
+        db,
+        parent.package(),
+        identifier(name),
+        Path::new(vec![IdentifierId::from("geo"), IdentifierId::from(name)]),
+        DUMMY_PATH_ID,
+    );
+    module.set_visibility(db, visibility);
+    parent.add_submodule(db, module);
+
+    module
+}
+
+/// Defines a module item with a given name in the module.
+fn define(
+    db: &mut Database,
+    module: ModuleId,
+    name: &str,
+    visibility: Visibility,
+    alloc: impl FnOnce(&mut Database, stellar_database::SignatureId) -> Symbol,
+) -> Symbol {
+    let name = identifier(name);
+    let node_idx = module.module_item_symbols(db).len();
+    let signature = SignatureData::alloc(db, visibility, name, node_idx, module);
+    let symbol = alloc(db, signature);
+
+    module.add_module_item(db, name.id, symbol);
+
+    symbol
+}
+
+#[test]
+fn public_symbols() {
+    let mut db = Database::new();
+    let package = PackageData::alloc(&mut db, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let module = ModuleData::alloc(
+        &mut db,
+        package,
+        identifier("geo"),
+        Path::new(vec![IdentifierId::from("geo")]),
+        DUMMY_PATH_ID,
+    );
+
+    let _ = define(
+        &mut db,
+        module,
+        "Secret",
+        Visibility::Private,
+        |db, signature| StructData::alloc(db, signature).into(),
+    );
+    let shape = define(
+        &mut db,
+        module,
+        "Shape",
+        Visibility::Public(DUMMY_LOCATION),
+        |db, signature| EnumData::alloc(db, signature).into(),
+    );
+    let shapes = submodule(
+        &mut db,
+        module,
+        "shapes",
+        Visibility::Public(DUMMY_LOCATION),
+    );
+    let _ = submodule(&mut db, module, "internal", Visibility::Private);
+
+    // Imports can't be public, so they are never re-exported.
+    module.add_resolved_import(&mut db, IdentifierId::from("Imported"), shape);
+
+    assert_eq!(
+        module.public_symbols(&db),
+        [
+            (IdentifierId::from("Shape"), shape),
+            (IdentifierId::from("shapes"), Symbol::Module(shapes)),
+        ]
+    );
+
+    assert!(module.is_public_symbol(&db, IdentifierId::from("Shape")));
+    assert!(module.is_public_symbol(&db, IdentifierId::from("shapes")));
+    assert!(!module.is_public_symbol(&db, IdentifierId::from("Secret")));
+    assert!(!module.is_public_symbol(&db, IdentifierId::from("internal")));
+    assert!(!module.is_public_symbol(&db, IdentifierId::from("Imported")));
+    assert!(!module.is_public_symbol(&db, IdentifierId::from("missing")));
+}
@@ -0,0 +1,126 @@
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_database::{
+    Database, EnumData, EnumItemData, FunctionData, ModuleData, ModuleId, PackageData, PackageId,
+    Path, SignatureData, SignatureId, StructData, Symbol,
+};
+use stellar_filesystem::location::DUMMY_LOCATION;
+use stellar_interner::{IdentifierId, DUMMY_PATH_ID};
+
+fn identifier(name: &str) -> IdentifierAST {
+    IdentifierAST {
+        location: DUMMY_LOCATION,
+        id: IdentifierId::from(name),
+    }
+}
+
+/// Creates a package with the root module `test` and submodules with given
+/// names.
+fn package(db: &mut Database, submodules: &[&str]) -> (PackageId, ModuleId, Vec<ModuleId>) {
+    let package = PackageData::alloc(db, IdentifierId::from("test"), DUMMY_PATH_ID);
+    let root = ModuleData::alloc(
+        db,
+        package,
+        identifier("test"),
+        Path::new(vec![IdentifierId::from("test")]),
+        DUMMY_PATH_ID,
+    );
+    package.set_root_module(db, root);
+
+    let submodules = submodules
+        .iter()
+        .map(|name| {
+            let submodule = ModuleData::alloc(
+                db,
+                package,
+                identifier(name),
+                Path::new(vec![IdentifierId::from("test"), IdentifierId::from(*name)]),
+                DUMMY_PATH_ID,
+            );
+            root.add_submodule(db, submodule);
+
+            submodule
+        })
+        .collect();
+
+    (package, root, submodules)
+}
+
+/// Defines a module item with a given name in the module.
+fn define(
+    db: &mut Database,
+    module: ModuleId,
+    name: &str,
+    alloc: impl FnOnce(&mut Database, SignatureId) -> Symbol,
+) -> Symbol {
+    let name = identifier(name);
+    let node_idx = module.module_item_symbols(db).len();
+    let signature = SignatureData::alloc(db, Visibility::Private, name, node_idx, module);
+    let symbol = alloc(db, signature);
+
+    module.add_module_item(db, name.id, symbol);
+
+    symbol
+}
+
+fn define_struct(db: &mut Database, module: ModuleId, name: &str) -> Symbol {
+    define(db, module, name, |db, signature| {
+        StructData::alloc(db, signature).into()
+    })
+}
+
+#[test]
+fn same_name_in_different_modules() {
+    let mut db = Database::new();
+    let (package, root, submodules) = package(&mut db, &["b", "a"]);
+
+    let in_b = define_struct(&mut db, submodules[0], "Foo");
+    let in_a = define_struct(&mut db, submodules[1], "Foo");
+    let in_root = define_struct(&mut db, root, "Foo");
+    define_struct(&mut db, root, "Bar");
+
+    assert_eq!(
+        db.find_symbols_by_name(package, IdentifierId::from("Foo")),
+        [in_root, in_a, in_b]
+    );
+    assert!(db
+        .find_symbols_by_name(package, IdentifierId::from("Baz"))
+        .is_empty());
+}
+
+#[test]
+fn enum_items() {
+    let mut db = Database::new();
+    let (package, root, _) = package(&mut db, &[]);
+
+    let enum_ = define(&mut db, root, "Option", |db, signature| {
+        EnumData::alloc(db, signature).into()
+    });
+    let item = EnumItemData::alloc(&mut db, enum_.to_enum(), identifier("None"), root);
+    enum_
+        .to_enum()
+        .add_item(&mut db, IdentifierId::from("None"), item);
+    let function = define(&mut db, root, "None", |db, signature| {
+        FunctionData::alloc(db, signature).into()
+    });
+
+    assert_eq!(
+        db.find_symbols_by_name(package, IdentifierId::from("None")),
+        [function, Symbol::EnumItem(item)]
+    );
+}
+
+#[test]
+fn prefix_query() {
+    let mut db = Database::new();
+    let (package, root, submodules) = package(&mut db, &["a"]);
+
+    let foo_bar = define_struct(&mut db, root, "FooBar");
+    let foo = define_struct(&mut db, root, "Foo");
+    define_struct(&mut db, root, "Bar");
+    let nested_foo = define_struct(&mut db, submodules[0], "Fooo");
+
+    assert_eq!(
+        db.find_symbols_matching(package, |name| name.starts_with("Foo")),
+        [foo, foo_bar, nested_foo]
+    );
+}
@@ -0,0 +1,213 @@
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_database::{
+    Database, EnumData, EnumId, EnumItemData, FunctionData, FunctionId, ModuleData, ModuleId,
+    PackageData, Path, SignatureData, StructData, StructId, Symbol,
+};
+use stellar_filesystem::location::DUMMY_LOCATION;
+use stellar_interner::{IdentifierId, DUMMY_PATH_ID};
+
+fn identifier(name: &str) -> IdentifierAST {
+    IdentifierAST {
+        location: DUMMY_LOCATION,
+        id: IdentifierId::from(name),
+    }
+}
+
+/// Creates a package with the root module `test`.
+fn package(db: &mut Database) -> ModuleId {
+    let package = PackageData::alloc(db, IdentifierId::from("test"), DUMMY_PATH_ID);
+    let root = ModuleData::alloc(
+        db,
+        package,
+        identifier("test"),
+        Path::new(vec![IdentifierId::from("test")]),
+        DUMMY_PATH_ID,
+    );
+    package.set_root_module(db, root);
+
+    root
+}
+
+fn define_struct(db: &mut Database, module: ModuleId, name: &str) -> StructId {
+    let name = identifier(name);
+    let signature = SignatureData::alloc(db, Visibility::Private, name, 0, module);
+    let struct_ = StructData::alloc(db, signature);
+
+    module.add_module_item(db, name.id, struct_.into());
+
+    struct_
+}
+
+fn define_function(db: &mut Database, module: ModuleId, name: &str) -> FunctionId {
+    let name = identifier(name);
+    let signature = SignatureData::alloc(db, Visibility::Private, name, 0, module);
+    let function = FunctionData::alloc(db, signature);
+
+    module.add_module_item(db, name.id, function.into());
+
+    function
+}
+
+fn define_enum(db: &mut Database, module: ModuleId, name: &str, items: &[&str]) -> EnumId {
+    let name = identifier(name);
+    let signature = SignatureData::alloc(db, Visibility::Private, name, 0, module);
+    let enum_ = EnumData::alloc(db, signature);
+
+    module.add_module_item(db, name.id, enum_.into());
+
+    for item in items {
+        add_item(db, module, enum_, item);
+    }
+
+    enum_
+}
+
+fn add_item(db: &mut Database, module: ModuleId, enum_: EnumId, name: &str) {
+    let item = EnumItemData::alloc(db, enum_, identifier(name), module);
+    enum_.add_item(db, IdentifierId::from(name), item);
+}
+
+fn names_of_items(db: &Database, enum_: EnumId) -> Vec<String> {
+    enum_
+        .items_ordered(db)
+        .map(|item| item.name(db).id.to_string())
+        .collect()
+}
+
+fn lookup(db: &Database, module: ModuleId, name: &str) -> Option<Symbol> {
+    module.symbol_or_none(db, IdentifierId::from(name))
+}
+
+#[test]
+fn rollback_removes_entities_and_symbols() {
+    let mut db = Database::new();
+    let root = package(&mut db);
+    let foo = define_struct(&mut db, root, "Foo");
+    let bar = define_function(&mut db, root, "bar");
+    let entities_before = db.stats().total_count();
+
+    let snapshot = db.snapshot();
+
+    let baz = define_struct(&mut db, root, "Baz");
+    define_function(&mut db, root, "bar");
+    root.add_resolved_import(&mut db, IdentifierId::from("Qux"), foo.into());
+
+    let submodule = ModuleData::alloc(
+        &mut db,
+        root.package(),
+        identifier("nested"),
+        Path::new(vec![
+            IdentifierId::from("test"),
+            IdentifierId::from("nested"),
+        ]),
+        DUMMY_PATH_ID,
+    );
+    root.add_submodule(&mut db, submodule);
+    submodule.add_dependency(&mut db, root);
+
+    db.rollback(snapshot);
+
+    assert_eq!(lookup(&db, root, "Foo"), Some(foo.into()));
+    assert_eq!(lookup(&db, root, "bar"), Some(bar.into()));
+    assert_eq!(lookup(&db, root, "Baz"), None);
+    assert_eq!(lookup(&db, root, "Qux"), None);
+    assert_eq!(lookup(&db, root, "nested"), None);
+    assert!(root.dependents(&db).is_empty());
+
+    assert!(foo.is_valid(&db));
+    assert!(bar.is_valid(&db));
+    assert!(!baz.is_valid(&db));
+    assert!(!submodule.is_valid(&db));
+    assert_eq!(db.stats().total_count(), entities_before);
+    assert!(!db.in_snapshot());
+}
+
+#[test]
+fn rollback_restores_enum_items() {
+    let mut db = Database::new();
+    let root = package(&mut db);
+    let enum_ = define_enum(&mut db, root, "Shape", &["Circle", "Square"]);
+
+    let snapshot = db.snapshot();
+
+    add_item(&mut db, root, enum_, "Triangle");
+    add_item(&mut db, root, enum_, "Circle");
+
+    assert_eq!(names_of_items(&db, enum_), ["Circle", "Square", "Triangle"]);
+
+    db.rollback(snapshot);
+
+    assert_eq!(names_of_items(&db, enum_), ["Circle", "Square"]);
+    assert_eq!(enum_.items_len(&db), 2);
+}
+
+#[test]
+fn rollback_removes_packages() {
+    let mut db = Database::new();
+    package(&mut db);
+
+    let snapshot = db.snapshot();
+    let root = package(&mut db);
+    db.rollback(snapshot);
+
+    assert!(root.package().root_module_or_none(&db).is_none());
+}
+
+#[test]
+fn nested_snapshots() {
+    let mut db = Database::new();
+    let root = package(&mut db);
+
+    let outer = db.snapshot();
+    let a = define_struct(&mut db, root, "A");
+
+    let inner = db.snapshot();
+    define_struct(&mut db, root, "B");
+    db.rollback(inner);
+
+    assert_eq!(lookup(&db, root, "A"), Some(a.into()));
+    assert_eq!(lookup(&db, root, "B"), None);
+
+    let inner = db.snapshot();
+    define_struct(&mut db, root, "C");
+    db.commit(inner);
+
+    assert!(lookup(&db, root, "C").is_some());
+
+    db.rollback(outer);
+
+    assert_eq!(lookup(&db, root, "A"), None);
+    assert_eq!(lookup(&db, root, "C"), None);
+}
+
+#[test]
+fn commit_keeps_changes() {
+    let mut db = Database::new();
+    let root = package(&mut db);
+
+    let snapshot = db.snapshot();
+    let foo = define_struct(&mut db, root, "Foo");
+    db.commit(snapshot);
+
+    assert_eq!(lookup(&db, root, "Foo"), Some(foo.into()));
+    assert!(!db.in_snapshot());
+
+    // Changes made after all snapshots are released cannot be undone by
+    // later ones.
+    let snapshot = db.snapshot();
+    db.rollback(snapshot);
+
+    assert_eq!(lookup(&db, root, "Foo"), Some(foo.into()));
+}
+
+#[test]
+#[should_panic(expected = "snapshots must be released in the reverse order")]
+fn outer_snapshot_released_first() {
+    let mut db = Database::new();
+    package(&mut db);
+
+    let outer = db.snapshot();
+    let _inner = db.snapshot();
+
+    db.rollback(outer);
+}
@@ -0,0 +1,86 @@
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_database::{
+    Database, EnumData, FunctionData, ModuleData, PackageData, Path, SignatureData,
+};
+use stellar_filesystem::location::DUMMY_LOCATION;
+use stellar_interner::{IdentifierId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+
+fn identifier(name: &str) -> IdentifierAST {
+    IdentifierAST {
+        location: DUMMY_LOCATION,
+        id: IdentifierId::from(name),
+    }
+}
+
+/// Creates a database with one module, `functions` functions and `enums` enums.
+fn populate(functions: usize, enums: usize) -> Database {
+    let mut db = Database::new();
+    let package = PackageData::alloc(&mut db, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let module = ModuleData::alloc(
+        &mut db,
+        package,
+        identifier("test"),
+        Path::new(vec![IdentifierId::from("test")]),
+        DUMMY_PATH_ID,
+    );
+
+    for idx in 0..functions {
+        let name = identifier(&format!("f{idx}"));
+        let signature = SignatureData::alloc(&mut db, Visibility::Private, name, idx, module);
+        let function = FunctionData::alloc(&mut db, signature);
+
+        module.add_module_item(&mut db, name.id, function.into());
+    }
+
+    for idx in 0..enums {
+        let name = identifier(&format!("E{idx}"));
+        let signature = SignatureData::alloc(&mut db, Visibility::Private, name, idx, module);
+        let enum_ = EnumData::alloc(&mut db, signature);
+
+        module.add_module_item(&mut db, name.id, enum_.into());
+    }
+
+    db
+}
+
+#[test]
+fn counts() {
+    let stats = populate(3, 2).stats();
+
+    assert_eq!(stats.packages, 1);
+    assert_eq!(stats.storage("modules").unwrap().count, 1);
+    assert_eq!(stats.storage("functions").unwrap().count, 3);
+    assert_eq!(stats.storage("enums").unwrap().count, 2);
+    assert_eq!(stats.storage("signatures").unwrap().count, 5);
+    // Every signature has its own generic parameter scope.
+    assert_eq!(stats.storage("generic parameter scopes").unwrap().count, 5);
+    assert_eq!(stats.storage("structs").unwrap().count, 0);
+    assert_eq!(stats.total_count(), 16);
+}
+
+#[test]
+fn heap_sizes() {
+    let small = populate(3, 2).stats();
+    let large = populate(300, 200).stats();
+
+    for name in ["modules", "functions", "enums", "signatures"] {
+        let small_size = small.storage(name).unwrap().heap_size;
+        let large_size = large.storage(name).unwrap().heap_size;
+
+        assert!(small_size > 0, "{name}");
+        assert!(large_size >= small_size, "{name}");
+    }
+
+    assert!(large.total_heap_size() > small.total_heap_size());
+    assert_eq!(Database::new().stats().total_heap_size(), 0);
+}
+
+#[test]
+fn display() {
+    let table = populate(3, 2).stats().to_string();
+    let lines = table.lines().collect::<Vec<_>>();
+
+    assert!(lines[0].starts_with("storage"));
+    assert!(lines.last().unwrap().starts_with("total"));
+    assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+}
@@ -0,0 +1,187 @@
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_database::{
+    symbol::BuiltinSymbolId,
+    ty::{Type, TypeConstructor, TypeVariable, TypeVariableId},
+    Database, GenericParameterData, GenericParameterScopeData, InterfaceData, ModuleData, ModuleId,
+    PackageData, Path, SignatureData, StructData, Symbol,
+};
+use stellar_filesystem::location::DUMMY_LOCATION;
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::{IdentifierId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+
+fn identifier(name: &str) -> IdentifierAST {
+    IdentifierAST {
+        location: DUMMY_LOCATION,
+        id: IdentifierId::from(name),
+    }
+}
+
+fn setup() -> (Database, ModuleId) {
+    let mut db = Database::new();
+    let package = PackageData::alloc(&mut db, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let module = ModuleData::alloc(
+        &mut db,
+        package,
+        identifier("test"),
+        Path::new(vec![IdentifierId::from("test")]),
+        DUMMY_PATH_ID,
+    );
+
+    (db, module)
+}
+
+fn builtin(builtin: BuiltinSymbolId, arguments: Vec<Type>) -> Type {
+    Type::Constructor(TypeConstructor::new(
+        Symbol::BuiltinSymbol(builtin),
+        arguments,
+    ))
+}
+
+fn variable(id: usize) -> Type {
+    Type::Variable(TypeVariable::Expression {
+        location: DUMMY_LOCATION,
+        id: TypeVariableId(id),
+    })
+}
+
+#[test]
+fn primitives() {
+    let (db, _) = setup();
+
+    assert_eq!(Type::Unit.display(&db).to_string(), "()");
+    assert_eq!(Type::Unknown.display(&db).to_string(), "_");
+    assert_eq!(Type::Never.display(&db).to_string(), "!");
+    assert_eq!(
+        builtin(BuiltinSymbolId::Int32, vec![])
+            .display(&db)
+            .to_string(),
+        "int32"
+    );
+}
+
+#[test]
+fn constructors_and_tuples() {
+    let (mut db, module) = setup();
+
+    let signature =
+        SignatureData::alloc(&mut db, Visibility::Private, identifier("Pair"), 0, module);
+    let pair = StructData::alloc(&mut db, signature);
+
+    let ty = Type::Constructor(TypeConstructor::new(
+        pair.into(),
+        vec![
+            builtin(
+                BuiltinSymbolId::List,
+                vec![builtin(BuiltinSymbolId::String, vec![])],
+            ),
+            Type::Tuple {
+                element_types: vec![Type::Unknown],
+            },
+        ],
+    ));
+
+    assert_eq!(ty.display(&db).to_string(), "Pair[List[String], (_,)]");
+
+    let tuple = Type::Tuple {
+        element_types: vec![builtin(BuiltinSymbolId::Bool, vec![]), Type::Unit],
+    };
+
+    assert_eq!(tuple.display(&db).to_string(), "(bool, ())");
+}
+
+#[test]
+fn function_returning_tuple_of_constructors() {
+    let (db, _) = setup();
+
+    let ty = Type::Function {
+        parameter_types: vec![
+            builtin(BuiltinSymbolId::Int32, vec![]),
+            builtin(BuiltinSymbolId::String, vec![]),
+        ],
+        return_type: Box::new(Type::Tuple {
+            element_types: vec![
+                builtin(
+                    BuiltinSymbolId::List,
+                    vec![builtin(BuiltinSymbolId::Int32, vec![])],
+                ),
+                builtin(BuiltinSymbolId::Bool, vec![]),
+            ],
+        }),
+    };
+
+    assert_eq!(
+        ty.display(&db).to_string(),
+        "fun(int32, String): (List[int32], bool)"
+    );
+
+    let ty = Type::Function {
+        parameter_types: vec![Type::Function {
+            parameter_types: vec![],
+            return_type: Box::new(builtin(BuiltinSymbolId::Char, vec![])),
+        }],
+        return_type: Box::new(Type::Unit),
+    };
+
+    assert_eq!(ty.display(&db).to_string(), "fun(fun(): char)");
+}
+
+#[test]
+fn interface_objects() {
+    let (mut db, module) = setup();
+
+    let iterator = SignatureData::alloc(
+        &mut db,
+        Visibility::Private,
+        identifier("Iterator"),
+        0,
+        module,
+    );
+    let iterator = InterfaceData::alloc(&mut db, iterator);
+    let to_string = SignatureData::alloc(
+        &mut db,
+        Visibility::Private,
+        identifier("ToString"),
+        1,
+        module,
+    );
+    let to_string = InterfaceData::alloc(&mut db, to_string);
+
+    let ty = Type::InterfaceObject {
+        bounds: vec![
+            TypeConstructor::new(
+                iterator.into(),
+                vec![builtin(BuiltinSymbolId::Char, vec![])],
+            ),
+            TypeConstructor::new(to_string.into(), vec![]),
+        ],
+    };
+
+    assert_eq!(ty.display(&db).to_string(), "dyn Iterator[char] + ToString");
+}
+
+#[test]
+fn variables_and_generic_parameters() {
+    let (mut db, module) = setup();
+    let package = module.package();
+
+    let scope = GenericParameterScopeData::alloc(&mut db, package, None);
+    let parameter = GenericParameterData::alloc(&mut db, package, DUMMY_LOCATION, None);
+    scope.add_generic_parameter(&mut db, IdentifierId::from("T"), parameter);
+
+    let ty = builtin(
+        BuiltinSymbolId::List,
+        vec![Type::Tuple {
+            element_types: vec![variable(0), variable(1), Type::GenericParameter(parameter)],
+        }],
+    );
+
+    assert_eq!(ty.display(&db).to_string(), "List[(?T0, ?T1, T)]");
+
+    let mut names = FxHashMap::default();
+    names.insert(TypeVariableId(1), "{integer}".to_owned());
+
+    assert_eq!(
+        ty.display(&db).with_variable_names(&names).to_string(),
+        "List[(?T0, {integer}, T)]"
+    );
+}
@@ -1,7 +1,197 @@
-#![allow(warnings)]
+//! Dependency graph of packages.
+//!
+//! Packages must be compiled after all of their dependencies, so
+//! [`DependencyGraph::topological_sort()`] orders them accordingly and
+//! reports dependency cycles as [`DependencyCycleError`].
 
-use stellar_manifest::{TomlManifest, TomlPackage};
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png",
+    html_favicon_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png"
+)]
+#![cfg_attr(not(test), forbid(clippy::unwrap_used))]
+#![warn(missing_docs, clippy::dbg_macro)]
+#![warn(
+    // rustc lint groups https://doc.rust-lang.org/rustc/lints/groups.html
+    future_incompatible,
+    let_underscore,
+    nonstandard_style,
+    rust_2018_compatibility,
+    rust_2018_idioms,
+    rust_2021_compatibility,
+    unused,
+    // rustc allowed-by-default lints https://doc.rust-lang.org/rustc/lints/listing/allowed-by-default.html
+    macro_use_extern_crate,
+    meta_variable_misuse,
+    missing_abi,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    non_ascii_idents,
+    noop_method_call,
+    single_use_lifetimes,
+    trivial_casts,
+    trivial_numeric_casts,
+    unreachable_pub,
+    unsafe_op_in_unsafe_fn,
+    unused_crate_dependencies,
+    unused_import_braces,
+    unused_lifetimes,
+    unused_qualifications,
+    variant_size_differences,
+    // rustdoc lints https://doc.rust-lang.org/rustdoc/lints.html
+    rustdoc::broken_intra_doc_links,
+    rustdoc::private_intra_doc_links,
+    rustdoc::missing_crate_level_docs,
+    rustdoc::private_doc_tests,
+    rustdoc::invalid_codeblock_attributes,
+    rustdoc::invalid_rust_codeblocks,
+    rustdoc::bare_urls,
+    // clippy categories https://doc.rust-lang.org/clippy/
+    clippy::all,
+    clippy::correctness,
+    clippy::suspicious,
+    clippy::style,
+    clippy::complexity,
+    clippy::perf,
+    clippy::pedantic,
+    clippy::nursery,
+)]
+#![allow(
+    clippy::module_name_repetitions,
+    clippy::too_many_lines,
+    clippy::option_if_let_else
+)]
 
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display},
+};
+
+/// A graph of packages and their dependencies. Packages are identified by
+/// their names.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DependencyGraph {
+    dependencies: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// An error, that occurs when packages depend on each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DependencyCycleError {
-    traceback: Vec<TomlPackage>,
+    /// Packages forming the cycle, the first one is repeated at the end,
+    /// e.g. `["a", "b", "a"]`.
+    traceback: Vec<String>,
+}
+
+impl DependencyCycleError {
+    /// Returns packages forming the cycle, the first one is repeated at
+    /// the end, e.g. `["a", "b", "a"]`.
+    #[inline]
+    #[must_use]
+    pub fn traceback(&self) -> &[String] {
+        &self.traceback
+    }
+}
+
+impl Display for DependencyCycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "dependency cycle: {}", self.traceback.join(" -> "))
+    }
+}
+
+/// State of a package during depth-first traversal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    InProgress,
+    Done,
+}
+
+impl DependencyGraph {
+    /// Creates a new empty dependency graph.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a package without dependencies into the graph, if it is not
+    /// there yet.
+    pub fn add_package(&mut self, package: impl Into<String>) {
+        self.dependencies.entry(package.into()).or_default();
+    }
+
+    /// Adds a dependency of a package. Both packages are added into the
+    /// graph, if they are not there yet.
+    pub fn add_dependency(&mut self, package: impl Into<String>, dependency: impl Into<String>) {
+        let dependency = dependency.into();
+
+        self.add_package(dependency.clone());
+        self.dependencies
+            .entry(package.into())
+            .or_default()
+            .insert(dependency);
+    }
+
+    /// Returns names of direct dependencies of a package in alphabetical order.
+    pub fn dependencies(&self, package: &str) -> impl Iterator<Item = &str> {
+        self.dependencies
+            .get(package)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// Returns names of all packages, so that every package goes after all
+    /// of its dependencies. Independent packages are ordered alphabetically.
+    ///
+    /// # Errors
+    /// Returns an error if packages depend on each other.
+    pub fn topological_sort(&self) -> Result<Vec<String>, DependencyCycleError> {
+        let mut visits = BTreeMap::new();
+        let mut order = vec![];
+
+        for package in self.dependencies.keys() {
+            self.visit(package, &mut visits, &mut vec![], &mut order)?;
+        }
+
+        Ok(order)
+    }
+
+    fn visit<'g>(
+        &'g self,
+        package: &'g str,
+        visits: &mut BTreeMap<&'g str, Visit>,
+        stack: &mut Vec<&'g str>,
+        order: &mut Vec<String>,
+    ) -> Result<(), DependencyCycleError> {
+        match visits.get(package) {
+            Some(Visit::Done) => return Ok(()),
+            Some(Visit::InProgress) => {
+                let start = stack
+                    .iter()
+                    .position(|visited| *visited == package)
+                    .unwrap_or_default();
+
+                return Err(DependencyCycleError {
+                    traceback: stack[start..]
+                        .iter()
+                        .chain([&package])
+                        .map(|package| (*package).to_owned())
+                        .collect(),
+                });
+            }
+            None => {}
+        }
+
+        visits.insert(package, Visit::InProgress);
+        stack.push(package);
+
+        for dependency in self.dependencies(package) {
+            self.visit(dependency, visits, stack, order)?;
+        }
+
+        stack.pop();
+        visits.insert(package, Visit::Done);
+        order.push(package.to_owned());
+
+        Ok(())
+    }
 }
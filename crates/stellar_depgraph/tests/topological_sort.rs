@@ -0,0 +1,31 @@
+use stellar_depgraph::DependencyGraph;
+
+#[test]
+fn dependencies_go_first() {
+    let mut graph = DependencyGraph::new();
+
+    graph.add_dependency("app", "http");
+    graph.add_dependency("app", "json");
+    graph.add_dependency("http", "io");
+    graph.add_dependency("json", "io");
+    graph.add_package("tools");
+
+    assert_eq!(
+        graph.topological_sort().unwrap(),
+        ["io", "http", "json", "app", "tools"]
+    );
+}
+
+#[test]
+fn dependency_cycle() {
+    let mut graph = DependencyGraph::new();
+
+    graph.add_dependency("a", "b");
+    graph.add_dependency("b", "c");
+    graph.add_dependency("c", "b");
+
+    let error = graph.topological_sort().unwrap_err();
+
+    assert_eq!(error.traceback(), ["b", "c", "b"]);
+    assert_eq!(error.to_string(), "dependency cycle: b -> c -> b");
+}
@@ -95,6 +95,32 @@ impl Label {
     }
 }
 
+/// A suggested change of the source code, that fixes the cause of a diagnostic,
+/// e.g. inserting `mut` before a binding name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct Suggestion {
+    /// The location of the code to replace. Empty locations are used for insertions.
+    pub location: Location,
+    /// The code to replace the code at the location with.
+    pub replacement: String,
+    /// A message describing the change, e.g. `consider making the binding mutable`.
+    pub message: String,
+}
+
+impl Suggestion {
+    /// Create a new suggestion.
+    #[inline]
+    #[must_use]
+    pub fn new(location: Location, replacement: impl ToString, message: impl ToString) -> Self {
+        Self {
+            location,
+            replacement: replacement.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
 /// Represents a diagnostic message that can provide information like errors and
 /// warnings to the user.
 ///
@@ -119,6 +145,8 @@ pub struct Diagnostic {
     /// Notes that are associated with the primary cause of the diagnostic.
     /// These can include line breaks for improved formatting.
     pub notes: Vec<String>,
+    /// Suggested changes of the source code, that fix the diagnostic.
+    pub suggestions: Vec<Suggestion>,
 }
 
 impl Diagnostic {
@@ -132,6 +160,7 @@ impl Diagnostic {
             message: String::new(),
             labels: Vec::new(),
             notes: Vec::new(),
+            suggestions: Vec::new(),
         }
     }
 
@@ -221,6 +250,14 @@ impl Diagnostic {
         self
     }
 
+    /// Add a suggestion to the diagnostic.
+    #[inline]
+    #[must_use]
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestions.push(suggestion);
+        self
+    }
+
     /// Returns the files involved in the diagnostic.
     #[inline]
     #[must_use]
@@ -28,16 +28,50 @@
 /// }
 /// ```
 ///
+/// Notes and suggested fixes can be added after labels:
+///
+/// ```ignore
+/// notes { "note: bindings are immutable by default".to_owned() }
+/// suggestions {
+///     self.insertion_location => "mut ", "consider making the binding mutable"
+/// }
+/// ```
+///
+/// Diagnostics, that implement `BuildDiagnostic` by hand, are listed before
+/// the definitions and must have an `INFO` constant with their code:
+///
+/// ```ignore
+/// implemented_manually { UnexpectedToken, MissingInterfaceMethod }
+/// ```
+///
 /// This macro invokations does few things:
 ///
 /// * Creates a `FailedToResolveModule` struct with given fields.
 /// * Automatically creates a constructor (`new` method) for it.
 /// * Automatically implements `BuildDiagnostic` trait for a given struct.
+/// * Creates an `INFO` constant with the code and the doc comment of the
+///   struct and a `DIAGNOSTICS` constant, that lists all diagnostics of the
+///   module (see [`DiagnosticInfo`]).
+///
+/// [`DiagnosticInfo`]: crate::registry::DiagnosticInfo
 #[macro_export]
 macro_rules! define_diagnostics {
     {
+        implemented_manually { $($manual:ident),* $(,)? }
+        $($rest:tt)*
+    } => {
+        $crate::define_diagnostics!(@define [$($manual)*] $($rest)*);
+    };
+    {
+        $(#[doc = $doc:literal])*
+        diagnostic $($rest:tt)*
+    } => {
+        $crate::define_diagnostics!(@define [] $(#[doc = $doc])* diagnostic $($rest)*);
+    };
+    {
+        @define [$($manual:ident)*]
         $(
-            $(#[$attr:meta])*
+            $(#[doc = $doc:literal])*
             diagnostic($severity:ident) $name:ident (
                 $self:ident
                 $(,
@@ -48,12 +82,18 @@ macro_rules! define_diagnostics {
                 code { $code:expr }
                 message { $message:expr }
                 labels { $($labels:tt)* }
-                $($note:tt)*
+                $($rest:tt)*
             }
         )*
     } => {
+        /// Descriptions of all diagnostics defined in the module.
+        pub const DIAGNOSTICS: &[$crate::registry::DiagnosticInfo] = &[
+            $($name::INFO,)*
+            $($manual::INFO,)*
+        ];
+
         $(
-            $(#[$attr])*
+            $(#[doc = $doc])*
             #[derive(Debug)]
             pub struct $name {
                 $(
@@ -63,6 +103,15 @@ macro_rules! define_diagnostics {
             }
 
             impl $name {
+                #[doc = concat!("Description of `", stringify!($name), "` generated by")]
+                #[doc = concat!("`stellar_diagnostics::define_diagnostics` macro")]
+                pub const INFO: $crate::registry::DiagnosticInfo = $crate::registry::DiagnosticInfo {
+                    name: stringify!($name),
+                    code: $code,
+                    severity: $crate::define_diagnostics!(@severity $severity),
+                    explanation: concat!($($doc, "\n"),*),
+                };
+
                 #[doc = concat!("A constructor for `", stringify!($name), "` generated by")]
                 #[doc = concat!("`stellar_diagnostics::define_diagnostics` macro")]
                 #[inline]
@@ -77,22 +126,35 @@ macro_rules! define_diagnostics {
                 #[inline]
                 fn build($self) -> $crate::diagnostic::Diagnostic {
                     let diagnostic = $crate::diagnostic::Diagnostic::$severity()
-                        .with_code($code.to_string())
+                        .with_code(Self::INFO.code)
                         .with_message($message);
 
-                    define_diagnostics!(@labels diagnostic, $($labels)*);
-                    define_diagnostics!(@notes diagnostic, $($note)*);
+                    $crate::define_diagnostics!(@labels diagnostic, $($labels)*);
+                    $crate::define_diagnostics!(@rest diagnostic, $($rest)*);
 
                     diagnostic
                 }
             }
         )*
     };
-    (@notes $diagnostic:ident,) => {};
-    (@notes $diagnostic:ident, notes { $($note:expr)* }) => {
+    (@severity error) => { $crate::diagnostic::Severity::Error };
+    (@severity warning) => { $crate::diagnostic::Severity::Warning };
+    (@rest $diagnostic:ident,) => {};
+    (@rest $diagnostic:ident, notes { $($note:expr)* } $($rest:tt)*) => {
         let $diagnostic = $diagnostic.with_notes(vec![
             $($note),*
         ]);
+        $crate::define_diagnostics!(@rest $diagnostic, $($rest)*);
+    };
+    (@rest $diagnostic:ident,
+        suggestions { $($location:expr => $replacement:expr, $message:expr);* $(;)? }
+        $($rest:tt)*) => {
+        $(
+            let $diagnostic = $diagnostic.with_suggestion(
+                $crate::diagnostic::Suggestion::new($location, $replacement, $message),
+            );
+        )*
+        $crate::define_diagnostics!(@rest $diagnostic, $($rest)*);
     };
     (@primary_label $diagnostic:ident,) => {};
     (@primary_label $diagnostic:ident, $location:expr) => {
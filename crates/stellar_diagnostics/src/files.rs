@@ -10,6 +10,7 @@
 use stellar_filesystem::in_memory_file::LineTooLargeError;
 use stellar_filesystem::in_memory_file_storage::InMemoryFileStorage;
 use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_filesystem::source_map::SourceMap;
 use stellar_interner::PathId;
 
 /// An enum representing an error that happened while looking up a file or a piece of content in that file.
@@ -98,7 +99,7 @@ impl std::error::Error for Error {
 /// This is to workaround the lack of higher kinded lifetime parameters.
 /// This can be ignored if this is not needed, however.
 #[allow(clippy::missing_errors_doc)]
-pub(crate) trait DiagnosticsRenderHelper<'a> {
+pub trait DiagnosticsRenderHelper<'a> {
     /// The user-facing name of a file.
     fn name(&'a self, filepath: PathId) -> Result<String, Error>;
 
@@ -180,11 +181,11 @@ pub(crate) trait DiagnosticsRenderHelper<'a> {
 ///
 /// [`Files::location`]: Files::location
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub(crate) struct ResolvedLocation {
+pub struct ResolvedLocation {
     /// The user-facing line number.
-    pub(crate) line_number: usize,
+    pub line_number: usize,
     /// The user-facing column number.
-    pub(crate) column_number: usize,
+    pub column_number: usize,
 }
 
 /// The column index at the given byte index in the source file.
@@ -250,3 +251,50 @@ impl<'a> DiagnosticsRenderHelper<'a> for InMemoryFileStorage {
             })
     }
 }
+
+/// Renders files, that are already loaded into the source map. Files are
+/// never read here, see [`DiagnosticsEmitter::emit_global_diagnostics_with_source_map()`].
+///
+/// [`DiagnosticsEmitter::emit_global_diagnostics_with_source_map()`]: crate::DiagnosticsEmitter::emit_global_diagnostics_with_source_map
+impl<'a> DiagnosticsRenderHelper<'a> for SourceMap {
+    #[inline]
+    fn name(&'a self, filepath: PathId) -> Result<String, Error> {
+        self.file(filepath)
+            .map(|file| file.path.as_path().display().to_string())
+            .ok_or(Error::FileMissing)
+    }
+
+    #[inline]
+    fn source(&'a self, filepath: PathId) -> Result<&'a str, Error> {
+        self.file(filepath)
+            .map(|file| &*file.source)
+            .ok_or(Error::FileMissing)
+    }
+
+    #[inline]
+    fn line_index(&'a self, filepath: PathId, byte_offset: ByteOffset) -> Result<usize, Error> {
+        self.file(filepath)
+            .ok_or(Error::FileMissing)
+            .map(|file| file.line_index.line_index(byte_offset))
+    }
+
+    fn line_location(&'a self, filepath: PathId, line_index: usize) -> Result<Location, Error> {
+        let file = self.file(filepath).ok_or(Error::FileMissing)?;
+        let line_too_large = || Error::LineTooLarge {
+            given: line_index,
+            max: file.line_index.line_count() - 1,
+        };
+
+        Ok(Location {
+            filepath,
+            start: file
+                .line_index
+                .line_start(line_index)
+                .ok_or_else(line_too_large)?,
+            end: file
+                .line_index
+                .line_start(line_index + 1)
+                .ok_or_else(line_too_large)?,
+        })
+    }
+}
@@ -65,19 +65,22 @@ pub mod diagnostic;
 pub mod files;
 #[macro_use]
 mod diagnostic_macro;
+pub mod registry;
+mod snapshot;
 pub mod term;
 
 use core::fmt;
 use std::fmt::Display;
 
-use stellar_filesystem::in_memory_file_storage::InMemoryFileStorage;
-use stellar_fx_hash::FxHashSet;
-use stellar_interner::PathId;
+use stellar_filesystem::{in_memory_file_storage::InMemoryFileStorage, source_map::SourceMap};
+use stellar_fx_hash::{FxHashMap, FxHashSet};
+use stellar_interner::{PathId, DUMMY_PATH_ID};
 
+pub use crate::snapshot::DiagnosticsSnapshot;
 use crate::{
     diagnostic::{Diagnostic, Severity},
     term::{
-        termcolor::{ColorChoice, StandardStream},
+        termcolor::{ColorChoice, StandardStream, WriteColor},
         Config,
     },
 };
@@ -103,13 +106,39 @@ impl Default for DiagnosticsEmitter {
 }
 
 /// Global diagnostics.
+///
+/// Diagnostics are stored by files, that their primary labels are located
+/// in, so that diagnostics of a single file can be queried and replaced,
+/// when the file is checked again. Diagnostics without primary labels are
+/// context-free and are stored under [`DUMMY_PATH_ID`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Diagnostics {
     /// Files that are involved in the diagnostics.
     pub files_involved: FxHashSet<PathId>,
 
-    /// Diagnostics.
-    pub diagnostics: Vec<Diagnostic>,
+    /// Diagnostics by files, that their primary labels are located in.
+    by_file: FxHashMap<PathId, Vec<Diagnostic>>,
+
+    /// Keys of [`Diagnostics::by_file`] in the order, in which first
+    /// diagnostics of the files were reported.
+    file_order: Vec<PathId>,
+
+    /// Codes of non-fatal diagnostics, that are not reported.
+    allowed_codes: FxHashSet<String>,
+
+    /// Codes of non-fatal diagnostics, that are reported as errors.
+    denied_codes: FxHashSet<String>,
+}
+
+/// An override of the severity of diagnostics with a given code, e.g. set
+/// with `--allow W004` or `--deny W004`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SeverityOverride {
+    /// Diagnostics are not reported, see [`Diagnostics::allow()`].
+    Allow,
+
+    /// Diagnostics are reported as errors, see [`Diagnostics::deny()`].
+    Deny,
 }
 
 impl Default for Diagnostics {
@@ -125,17 +154,191 @@ impl Diagnostics {
     pub fn new() -> Self {
         Self {
             files_involved: FxHashSet::default(),
-            diagnostics: vec![],
+            by_file: FxHashMap::default(),
+            file_order: vec![],
+            allowed_codes: FxHashSet::default(),
+            denied_codes: FxHashSet::default(),
         }
     }
 
+    /// Stops reporting non-fatal diagnostics with a given code, e.g. `W005`.
+    /// Overrides a previous [`Diagnostics::deny()`] of the code.
+    ///
+    /// Errors cannot be allowed.
+    #[inline]
+    pub fn allow(&mut self, code: impl Into<String>) {
+        let code = code.into();
+
+        self.denied_codes.remove(&code);
+        self.allowed_codes.insert(code);
+    }
+
+    /// Reports non-fatal diagnostics with a given code, e.g. `W005`, as
+    /// errors. Overrides a previous [`Diagnostics::allow()`] of the code.
+    #[inline]
+    pub fn deny(&mut self, code: impl Into<String>) {
+        let code = code.into();
+
+        self.allowed_codes.remove(&code);
+        self.denied_codes.insert(code);
+    }
+
+    /// Applies a severity override to diagnostics with a given code.
+    #[inline]
+    pub fn override_severity(&mut self, code: impl Into<String>, severity: SeverityOverride) {
+        match severity {
+            SeverityOverride::Allow => self.allow(code),
+            SeverityOverride::Deny => self.deny(code),
+        }
+    }
+
+    /// Returns `true` if non-fatal diagnostics with a given code are reported
+    /// as errors.
+    #[inline]
+    #[must_use]
+    pub fn is_denied(&self, code: &str) -> bool {
+        self.denied_codes.contains(code)
+    }
+
+    /// Returns `true` if non-fatal diagnostics with a given code are not reported.
+    #[inline]
+    #[must_use]
+    pub fn is_allowed(&self, code: &str) -> bool {
+        self.allowed_codes.contains(code)
+    }
+
     /// Adds a diagnostic associated with some files.
+    ///
+    /// Non-fatal diagnostics with allowed codes are ignored, see [`Diagnostics::allow()`],
+    /// and ones with denied codes are reported as errors, see [`Diagnostics::deny()`].
+    ///
+    /// # Panics
+    /// With the `strict-locations` feature enabled, panics if a primary label
+    /// of the diagnostic has a dummy location.
     #[inline]
     pub fn add_diagnostic(&mut self, diagnostic: impl BuildDiagnostic) {
-        let diagnostic = diagnostic.build();
+        let mut diagnostic = diagnostic.build();
+
+        if !is_fatal_severity(diagnostic.severity) {
+            if let Some(code) = diagnostic.code.as_deref() {
+                if self.is_allowed(code) {
+                    return;
+                }
+
+                if self.is_denied(code) {
+                    let note = format!("note: `{code}` is denied, so it is reported as an error");
+
+                    diagnostic.severity = Severity::Error;
+                    diagnostic.notes.push(note);
+                }
+            }
+        }
+
+        #[cfg(feature = "strict-locations")]
+        if let Some(label) = diagnostic.labels.iter().find(|label| {
+            label.style == diagnostic::LabelStyle::Primary && label.location.is_dummy()
+        }) {
+            panic!(
+                "primary label of diagnostic `{}` has a dummy location: {}",
+                diagnostic.message, label.location
+            );
+        }
 
         self.files_involved.extend(diagnostic.files_involved());
-        self.diagnostics.push(diagnostic);
+
+        let filepath = primary_file_of(&diagnostic);
+
+        self.by_file
+            .entry(filepath)
+            .or_insert_with(|| {
+                self.file_order.push(filepath);
+                vec![]
+            })
+            .push(diagnostic);
+    }
+
+    /// Returns diagnostics, primary labels of which are located in a given
+    /// file, in the order they were reported in. Context-free diagnostics
+    /// are returned for [`DUMMY_PATH_ID`].
+    #[inline]
+    #[must_use]
+    pub fn for_file(&self, filepath: PathId) -> &[Diagnostic] {
+        self.by_file.get(&filepath).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns files, that have diagnostics located in them, in the order,
+    /// in which their first diagnostics were reported. The key of
+    /// context-free diagnostics is not included.
+    #[inline]
+    pub fn files_with_diagnostics(&self) -> impl Iterator<Item = PathId> + '_ {
+        self.file_order
+            .iter()
+            .copied()
+            .filter(|filepath| *filepath != DUMMY_PATH_ID)
+    }
+
+    /// Removes diagnostics, primary labels of which are located in a given
+    /// file, e.g. before the file is checked again.
+    pub fn clear_file(&mut self, filepath: PathId) {
+        if self.by_file.remove(&filepath).is_none() {
+            return;
+        }
+
+        self.file_order.retain(|file| *file != filepath);
+        self.files_involved = self.iter().flat_map(Diagnostic::files_involved).collect();
+    }
+
+    /// Returns an iterator over all diagnostics. Diagnostics are grouped by
+    /// files in the order of [`Diagnostics::files_with_diagnostics()`],
+    /// context-free diagnostics are ordered in the same way.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> + '_ {
+        self.file_order
+            .iter()
+            .flat_map(|filepath| &self.by_file[filepath])
+    }
+
+    /// Returns a copy of all diagnostics in the order of
+    /// [`Diagnostics::iter()`], e.g. to emit them without borrowing the
+    /// diagnostics.
+    #[must_use]
+    pub fn snapshot(&self) -> DiagnosticsSnapshot {
+        DiagnosticsSnapshot::new(self.files_involved.clone(), self.iter().cloned().collect())
+    }
+
+    /// Returns all diagnostics, see [`Diagnostics::iter()`].
+    #[inline]
+    #[must_use]
+    pub fn all(&self) -> Vec<&Diagnostic> {
+        self.iter().collect()
+    }
+
+    /// Returns the number of diagnostics.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.by_file.values().map(Vec::len).sum()
+    }
+
+    /// Returns `true` if there are no diagnostics.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.by_file.is_empty()
+    }
+
+    /// Returns the number of labels of all diagnostics, that have dummy
+    /// locations (see [`Location::is_dummy()`]). Such labels cannot be
+    /// rendered, so the number is expected to be zero.
+    ///
+    /// [`Location::is_dummy()`]: stellar_filesystem::location::Location::is_dummy
+    #[inline]
+    #[must_use]
+    pub fn count_dummy_labels(&self) -> usize {
+        self.iter()
+            .flat_map(|diagnostic| &diagnostic.labels)
+            .filter(|label| label.location.is_dummy())
+            .count()
     }
 
     /// Returns `true` if diagnostics are fatal.
@@ -149,9 +352,7 @@ impl Diagnostics {
     #[inline]
     #[must_use]
     pub fn is_ok(&self) -> bool {
-        self.diagnostics
-            .iter()
-            .all(|d| !is_fatal_severity(d.severity))
+        self.iter().all(|d| !is_fatal_severity(d.severity))
     }
 }
 
@@ -249,12 +450,102 @@ impl DiagnosticsEmitter {
 
     /// Emit global diagnostics.
     #[inline]
-    pub fn emit_global_diagnostics(&mut self, global_diagnostics: &Diagnostics) {
-        self.initialize_file_storage(&global_diagnostics.files_involved);
-        self.emit_diagnostics(&global_diagnostics.diagnostics);
+    pub fn emit_global_diagnostics(&mut self, global_diagnostics: &DiagnosticsSnapshot) {
+        self.initialize_file_storage(global_diagnostics.files_involved());
+        self.emit_diagnostics(global_diagnostics.iter());
+    }
+
+    /// Emit global diagnostics, taking sources of files involved in them
+    /// from a given source map. Files, that are not loaded into the source
+    /// map yet, are loaded once and stay cached there.
+    ///
+    /// Diagnostics in files, that cannot be read, e.g. because they were
+    /// removed after checking, are emitted without source snippets.
+    pub fn emit_global_diagnostics_with_source_map(
+        &self,
+        global_diagnostics: &DiagnosticsSnapshot,
+        source_map: &mut SourceMap,
+    ) {
+        self.write_global_diagnostics_with_source_map(
+            &mut self.writer.lock(),
+            global_diagnostics,
+            source_map,
+        );
+    }
+
+    /// Same as [`DiagnosticsEmitter::emit_global_diagnostics_with_source_map()`],
+    /// but writes diagnostics into a given writer instead of the stream of
+    /// the emitter.
+    ///
+    /// # Panics
+    /// * If diagnostics cannot be written into the writer.
+    pub fn write_global_diagnostics_with_source_map(
+        &self,
+        writer: &mut dyn WriteColor,
+        global_diagnostics: &DiagnosticsSnapshot,
+        source_map: &mut SourceMap,
+    ) {
+        let unreadable_files = global_diagnostics
+            .files_involved()
+            .iter()
+            .filter(|filepath| source_map.file_or_load(**filepath).is_err())
+            .copied()
+            .collect::<FxHashSet<_>>();
+
+        for diagnostic in global_diagnostics.iter() {
+            if unreadable_files.is_empty() {
+                term::emit(writer, &self.config, source_map, diagnostic).unwrap();
+            } else {
+                term::emit(
+                    writer,
+                    &self.config,
+                    source_map,
+                    &without_sources_of(diagnostic, &unreadable_files),
+                )
+                .unwrap();
+            }
+        }
     }
 }
 
+/// Removes labels and suggestions located in given files from a diagnostic
+/// and mentions the files in notes instead.
+fn without_sources_of(diagnostic: &Diagnostic, filepaths: &FxHashSet<PathId>) -> Diagnostic {
+    let mut diagnostic = diagnostic.clone();
+
+    let mut unreadable = vec![];
+
+    for filepath in diagnostic
+        .labels
+        .iter()
+        .map(|label| label.location.filepath)
+        .chain(
+            diagnostic
+                .suggestions
+                .iter()
+                .map(|suggestion| suggestion.location.filepath),
+        )
+    {
+        if filepaths.contains(&filepath) && !unreadable.contains(&filepath) {
+            unreadable.push(filepath);
+        }
+    }
+
+    diagnostic
+        .labels
+        .retain(|label| !filepaths.contains(&label.location.filepath));
+    diagnostic
+        .suggestions
+        .retain(|suggestion| !filepaths.contains(&suggestion.location.filepath));
+    diagnostic.notes.extend(
+        unreadable
+            .into_iter()
+            .map(|filepath| format!("the source of `{filepath}` cannot be read")),
+    );
+
+    diagnostic
+}
+
 /// General status of diagnostics.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DiagnosticsStatus {
@@ -278,3 +569,20 @@ pub trait BuildDiagnostic {
     #[must_use]
     fn build(self) -> Diagnostic;
 }
+
+impl BuildDiagnostic for Diagnostic {
+    #[inline]
+    fn build(self) -> Diagnostic {
+        self
+    }
+}
+
+/// Returns the file, that the first primary label of a diagnostic is
+/// located in, or [`DUMMY_PATH_ID`] for context-free diagnostics.
+fn primary_file_of(diagnostic: &Diagnostic) -> PathId {
+    diagnostic
+        .labels
+        .iter()
+        .find(|label| label.style == diagnostic::LabelStyle::Primary)
+        .map_or(DUMMY_PATH_ID, |label| label.location.filepath)
+}
@@ -0,0 +1,106 @@
+//! Defines [`DiagnosticInfo`] - a description of a diagnostic type, that is
+//! generated by [`define_diagnostics!`], and [`Phase`] - a part of the
+//! compiler, that owns a range of error codes.
+//!
+//! Codes are namespaced by phases:
+//!
+//! | Codes  | Phase                          |
+//! |--------|--------------------------------|
+//! | `E0xx` | lexing, parsing, loading files |
+//! | `E1xx` | name resolution                |
+//! | `E2xx` | type checking                  |
+//! | `W0xx` | lints                          |
+//!
+//! Every crate, that defines diagnostics, lists them in a `DIAGNOSTICS`
+//! constant, so that the codes can be checked for collisions and explained.
+//!
+//! [`define_diagnostics!`]: crate::define_diagnostics
+
+use std::fmt::Display;
+
+use crate::diagnostic::Severity;
+
+/// A description of a diagnostic type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DiagnosticInfo {
+    /// The name of the type, e.g. `"UnexpectedToken"`.
+    pub name: &'static str,
+
+    /// The code of the diagnostic, e.g. `"E001"`.
+    pub code: &'static str,
+
+    /// Severity of the diagnostic.
+    pub severity: Severity,
+
+    /// Documentation of the type, one line per line of the doc comment.
+    pub explanation: &'static str,
+}
+
+impl DiagnosticInfo {
+    /// Returns the phase, that owns the code of the diagnostic, or `None`,
+    /// if the code doesn't belong to any range.
+    #[inline]
+    #[must_use]
+    pub fn phase(&self) -> Option<Phase> {
+        Phase::of(self.code)
+    }
+
+    /// Returns lines of the explanation without the leading space of doc
+    /// comments.
+    pub fn explanation_lines(&self) -> impl Iterator<Item = &'static str> {
+        self.explanation
+            .lines()
+            .map(|line| line.strip_prefix(' ').unwrap_or(line))
+    }
+}
+
+/// A part of the compiler, that owns a range of diagnostic codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Lexing, parsing and loading source files, `E0xx`.
+    Parsing,
+
+    /// Name resolution, `E1xx`.
+    NameResolution,
+
+    /// Type checking, `E2xx`.
+    TypeChecking,
+
+    /// Lints, `W0xx`.
+    Lints,
+}
+
+impl Phase {
+    /// Returns the phase, that owns a given code, e.g. [`Phase::TypeChecking`]
+    /// for `"E201"`, or `None`, if the code is malformed or doesn't belong to
+    /// any range.
+    #[must_use]
+    pub fn of(code: &str) -> Option<Self> {
+        let &[kind, hundreds, tens, ones] = code.as_bytes() else {
+            return None;
+        };
+
+        if ![hundreds, tens, ones].iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+
+        match (kind, hundreds) {
+            (b'E', b'0') => Some(Self::Parsing),
+            (b'E', b'1') => Some(Self::NameResolution),
+            (b'E', b'2') => Some(Self::TypeChecking),
+            (b'W', b'0') => Some(Self::Lints),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Parsing => "parsing",
+            Self::NameResolution => "name resolution",
+            Self::TypeChecking => "type checking",
+            Self::Lints => "lints",
+        })
+    }
+}
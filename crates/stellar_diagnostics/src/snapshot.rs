@@ -0,0 +1,76 @@
+//! Defines [`DiagnosticsSnapshot`] - an immutable copy of diagnostics, that
+//! is emitted.
+//!
+//! [`DiagnosticsEmitter`] only accepts snapshots, so diagnostics, that are
+//! shared between threads, are never borrowed while doing IO: a lock around
+//! them is held only to take a snapshot, and is released before the snapshot
+//! is emitted. Diagnostics are small, so copying them into a snapshot is cheap
+//! compared to rendering them.
+//!
+//! [`DiagnosticsEmitter`]: crate::DiagnosticsEmitter
+
+use stellar_fx_hash::FxHashSet;
+use stellar_interner::PathId;
+
+use crate::{diagnostic::Diagnostic, is_fatal_severity};
+
+/// An immutable copy of diagnostics, see [`Diagnostics::snapshot()`].
+///
+/// [`Diagnostics::snapshot()`]: crate::Diagnostics::snapshot
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DiagnosticsSnapshot {
+    /// Files that are involved in the diagnostics.
+    files_involved: FxHashSet<PathId>,
+
+    /// Diagnostics in the order of [`Diagnostics::iter()`].
+    ///
+    /// [`Diagnostics::iter()`]: crate::Diagnostics::iter
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl DiagnosticsSnapshot {
+    /// Creates a snapshot of given diagnostics, that involve given files.
+    #[inline]
+    #[must_use]
+    pub const fn new(files_involved: FxHashSet<PathId>, diagnostics: Vec<Diagnostic>) -> Self {
+        Self {
+            files_involved,
+            diagnostics,
+        }
+    }
+
+    /// Returns files, that are involved in the diagnostics.
+    #[inline]
+    #[must_use]
+    pub const fn files_involved(&self) -> &FxHashSet<PathId> {
+        &self.files_involved
+    }
+
+    /// Returns an iterator over all diagnostics.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> + '_ {
+        self.diagnostics.iter()
+    }
+
+    /// Returns the number of diagnostics.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.diagnostics.len()
+    }
+
+    /// Returns `true` if there are no diagnostics.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Returns `true` if some of the diagnostics are fatal.
+    #[inline]
+    #[must_use]
+    pub fn is_fatal(&self) -> bool {
+        self.iter()
+            .any(|diagnostic| is_fatal_severity(diagnostic.severity))
+    }
+}
@@ -44,6 +44,12 @@ pub struct Config {
     ///
     /// Defaults to: `3`.
     pub after_label_lines: usize,
+
+    /// Whether source text of labels is included into diagnostics rendered
+    /// with [`DisplayStyle::Json`].
+    ///
+    /// Defaults to: `false`.
+    pub include_snippets: bool,
 }
 
 impl Default for Config {
@@ -57,6 +63,7 @@ impl Default for Config {
             end_context_lines: 3,
             before_label_lines: 3,
             after_label_lines: 3,
+            include_snippets: false,
         }
     }
 }
@@ -97,6 +104,25 @@ pub enum DisplayStyle {
     /// error[E0002]: bad config found
     /// ```
     Short,
+    /// Output a [GitHub Actions workflow command] per diagnostic, so that
+    /// diagnostics are shown as annotations of pull requests. Notes are
+    /// folded into the message, secondary labels are not shown.
+    ///
+    /// ```text
+    /// ::error file=test,line=2,col=9,endLine=2,endColumn=10::[E0001] mismatched types | expected type `int`
+    /// ::error::[E0002] bad config found
+    /// ```
+    ///
+    /// [GitHub Actions workflow command]: https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions
+    GitHub,
+    /// Output a JSON object per line for tools, that consume diagnostics.
+    /// Labels contain byte offsets and 1-based line and column numbers, and
+    /// the source text they point to, if [`Config::include_snippets`] is set.
+    ///
+    /// ```text
+    /// {"severity":"error","code":"E0001","message":"mismatched types","labels":[{"style":"primary","file":"test","start":8,"end":9,"line":2,"column":9,"end_line":2,"end_column":10,"message":"expected `int` but found `String`","snippet":"a"}],"notes":[],"suggestions":[]}
+    /// ```
+    Json,
 }
 
 /// Styles to use when rendering the diagnostic.
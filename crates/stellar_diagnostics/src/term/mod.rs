@@ -2,10 +2,10 @@
 
 use std::str::FromStr;
 
-use stellar_filesystem::in_memory_file_storage::InMemoryFileStorage;
 use termcolor::{ColorChoice, WriteColor};
 
 use crate::diagnostic::Diagnostic;
+use crate::files::DiagnosticsRenderHelper;
 
 mod config;
 mod renderer;
@@ -62,25 +62,26 @@ impl From<ColorArg> for ColorChoice {
 /// * a file was removed from the file database.
 /// * a file was changed so that it is too small to have an index
 /// * IO fails
-pub fn emit(
+pub fn emit<'f>(
     writer: &mut dyn WriteColor,
     config: &Config,
-    in_memory_file_storage: &InMemoryFileStorage,
+    files: &'f impl DiagnosticsRenderHelper<'f>,
     diagnostic: &Diagnostic,
 ) -> Result<(), super::files::Error> {
     use self::renderer::Renderer;
-    use self::views::{RichDiagnostic, ShortDiagnostic};
+    use self::views::{GitHubDiagnostic, JsonDiagnostic, RichDiagnostic, ShortDiagnostic};
 
-    let mut renderer = Renderer::new(writer, config);
     match config.display_style {
-        DisplayStyle::Rich => {
-            RichDiagnostic::new(diagnostic, config).render(in_memory_file_storage, &mut renderer)
-        }
+        DisplayStyle::Rich => RichDiagnostic::new(diagnostic, config)
+            .render(files, &mut Renderer::new(writer, config)),
         DisplayStyle::Medium => {
-            ShortDiagnostic::new(diagnostic, true).render(in_memory_file_storage, &mut renderer)
+            ShortDiagnostic::new(diagnostic, true).render(files, &mut Renderer::new(writer, config))
         }
-        DisplayStyle::Short => {
-            ShortDiagnostic::new(diagnostic, false).render(in_memory_file_storage, &mut renderer)
+        DisplayStyle::Short => ShortDiagnostic::new(diagnostic, false)
+            .render(files, &mut Renderer::new(writer, config)),
+        DisplayStyle::GitHub => GitHubDiagnostic::new(diagnostic).render(files, writer),
+        DisplayStyle::Json => {
+            JsonDiagnostic::new(diagnostic, config.include_snippets).render(files, writer)
         }
     }
 }
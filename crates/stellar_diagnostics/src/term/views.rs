@@ -1,10 +1,12 @@
 use std::cmp;
+use std::fmt::Write as _;
 
-use stellar_filesystem::in_memory_file_storage::InMemoryFileStorage;
 use stellar_filesystem::location::{ByteOffset, Location};
 use stellar_interner::PathId;
 
-use crate::diagnostic::{Diagnostic, LabelStyle};
+use termcolor::WriteColor;
+
+use crate::diagnostic::{Diagnostic, LabelStyle, Severity, Suggestion};
 use crate::files::{DiagnosticsRenderHelper, Error, ResolvedLocation};
 use crate::term::renderer::{Locus, MultiLabel, Renderer, SingleLabel};
 use crate::term::Config;
@@ -28,9 +30,9 @@ impl<'d, 'c> RichDiagnostic<'d, 'c> {
         RichDiagnostic { diagnostic, config }
     }
 
-    pub(crate) fn render(
+    pub(crate) fn render<'f>(
         &self,
-        in_memory_file_storage: &InMemoryFileStorage,
+        files: &'f impl DiagnosticsRenderHelper<'f>,
         renderer: &mut Renderer<'_, '_>,
     ) -> Result<(), Error> {
         use std::collections::BTreeMap;
@@ -79,15 +81,13 @@ impl<'d, 'c> RichDiagnostic<'d, 'c> {
         // Group labels by file
         for label in &self.diagnostic.labels {
             let start_line_index =
-                in_memory_file_storage.line_index(label.location.filepath, label.location.start)?;
+                files.line_index(label.location.filepath, label.location.start)?;
             let start_line_number = start_line_index + 1;
             let start_line_location =
-                in_memory_file_storage.line_location(label.location.filepath, start_line_index)?;
-            let end_line_index =
-                in_memory_file_storage.line_index(label.location.filepath, label.location.end)?;
+                files.line_location(label.location.filepath, start_line_index)?;
+            let end_line_index = files.line_index(label.location.filepath, label.location.end)?;
             let end_line_number = end_line_index + 1;
-            let end_line_location =
-                in_memory_file_storage.line_location(label.location.filepath, end_line_index)?;
+            let end_line_location = files.line_location(label.location.filepath, end_line_index)?;
 
             outer_padding = cmp::max(
                 outer_padding,
@@ -108,8 +108,8 @@ impl<'d, 'c> RichDiagnostic<'d, 'c> {
                 {
                     // this label has a higher style or has the same style but starts earlier
                     labeled_file.start = label.location.start;
-                    labeled_file.location = in_memory_file_storage
-                        .location(label.location.filepath, label.location.start)?;
+                    labeled_file.location =
+                        files.location(label.location.filepath, label.location.start)?;
                     labeled_file.max_label_style = label.style;
                 }
                 labeled_file
@@ -118,11 +118,8 @@ impl<'d, 'c> RichDiagnostic<'d, 'c> {
                 labeled_files.push(LabeledFile {
                     filepath: label.location.filepath,
                     start: label.location.start,
-                    name: in_memory_file_storage
-                        .name(label.location.filepath)?
-                        .to_string(),
-                    location: in_memory_file_storage
-                        .location(label.location.filepath, label.location.start)?,
+                    name: files.name(label.location.filepath)?,
+                    location: files.location(label.location.filepath, label.location.start)?,
                     num_multi_labels: 0,
                     lines: BTreeMap::new(),
                     max_label_style: label.style,
@@ -143,9 +140,7 @@ impl<'d, 'c> RichDiagnostic<'d, 'c> {
                     break;
                 };
 
-                if let Ok(location) =
-                    in_memory_file_storage.line_location(label.location.filepath, index)
-                {
+                if let Ok(location) = files.line_location(label.location.filepath, index) {
                     let line = labeled_file.get_or_insert_line(
                         index,
                         location,
@@ -164,9 +159,7 @@ impl<'d, 'c> RichDiagnostic<'d, 'c> {
                     .checked_add(offset)
                     .expect("line index too big");
 
-                if let Ok(location) =
-                    in_memory_file_storage.line_location(label.location.filepath, index)
-                {
+                if let Ok(location) = files.line_location(label.location.filepath, index) {
                     let line =
                         labeled_file.get_or_insert_line(index, location, end_line_number + offset);
                     line.must_render = true;
@@ -261,8 +254,7 @@ impl<'d, 'c> RichDiagnostic<'d, 'c> {
                 // 7 │ │     _ 0 => "Buzz"
                 // ```
                 for line_index in (start_line_index + 1)..end_line_index {
-                    let line_location = in_memory_file_storage
-                        .line_location(label.location.filepath, line_index)?;
+                    let line_location = files.line_location(label.location.filepath, line_index)?;
                     let line_number = line_index + 1;
 
                     outer_padding = std::cmp::max(
@@ -333,7 +325,7 @@ impl<'d, 'c> RichDiagnostic<'d, 'c> {
         // ```
         let mut labeled_files = labeled_files.into_iter().peekable();
         while let Some(labeled_file) = labeled_files.next() {
-            let source = in_memory_file_storage.source(labeled_file.filepath)?;
+            let source = files.source(labeled_file.filepath)?;
 
             // Top left border and locus.
             //
@@ -394,8 +386,7 @@ impl<'d, 'c> RichDiagnostic<'d, 'c> {
                             renderer.render_snippet_source(
                                 outer_padding,
                                 line_index + 2,
-                                &source[in_memory_file_storage
-                                    .line_location(file_id, line_index + 1)?],
+                                &source[files.line_location(file_id, line_index + 1)?],
                                 self.diagnostic.severity,
                                 &[],
                                 labeled_file.num_multi_labels,
@@ -422,7 +413,10 @@ impl<'d, 'c> RichDiagnostic<'d, 'c> {
 
             // Check to see if we should render a trailing border after the
             // final line of the snippet.
-            if labeled_files.peek().is_none() && self.diagnostic.notes.is_empty() {
+            if labeled_files.peek().is_none()
+                && self.diagnostic.notes.is_empty()
+                && self.diagnostic.suggestions.is_empty()
+            {
                 // We don't render a border if we are at the final newline
                 // without trailing notes, because it would end up looking too
                 // spaced-out in combination with the final new line.
@@ -446,6 +440,16 @@ impl<'d, 'c> RichDiagnostic<'d, 'c> {
         for note in &self.diagnostic.notes {
             renderer.render_snippet_note(outer_padding, note)?;
         }
+
+        // Suggested fixes
+        //
+        // ```text
+        // = help: consider making the binding mutable: insert `mut ` at main.sr:2:9
+        // ```
+        for suggestion in &self.diagnostic.suggestions {
+            renderer
+                .render_snippet_note(outer_padding, &suggestion_to_string(files, suggestion)?)?;
+        }
         renderer.render_empty()
     }
 }
@@ -464,9 +468,9 @@ impl<'d> ShortDiagnostic<'d> {
         }
     }
 
-    pub(crate) fn render(
+    pub(crate) fn render<'f>(
         &self,
-        in_memory_file_storage: &InMemoryFileStorage,
+        files: &'f impl DiagnosticsRenderHelper<'f>,
         renderer: &mut Renderer<'_, '_>,
     ) -> Result<(), Error> {
         // Located headers
@@ -481,11 +485,8 @@ impl<'d> ShortDiagnostic<'d> {
 
             renderer.render_header(
                 Some(&Locus {
-                    name: in_memory_file_storage
-                        .name(label.location.filepath)?
-                        .to_string(),
-                    location: in_memory_file_storage
-                        .location(label.location.filepath, label.location.start)?,
+                    name: files.name(label.location.filepath)?,
+                    location: files.location(label.location.filepath, label.location.start)?,
                 }),
                 self.diagnostic.severity,
                 self.diagnostic.code.as_deref(),
@@ -517,8 +518,279 @@ impl<'d> ShortDiagnostic<'d> {
             for note in &self.diagnostic.notes {
                 renderer.render_snippet_note(0, note)?;
             }
+
+            for suggestion in &self.diagnostic.suggestions {
+                renderer.render_snippet_note(0, &suggestion_to_string(files, suggestion)?)?;
+            }
         }
 
         Ok(())
     }
 }
+
+/// Output a diagnostic as a GitHub Actions workflow command, e.g.
+/// `::error file=test,line=2,col=9,endLine=2,endColumn=10::[E0001] mismatched types`.
+pub(crate) struct GitHubDiagnostic<'d> {
+    diagnostic: &'d Diagnostic,
+}
+
+impl<'d> GitHubDiagnostic<'d> {
+    pub(crate) const fn new(diagnostic: &'d Diagnostic) -> GitHubDiagnostic<'d> {
+        GitHubDiagnostic { diagnostic }
+    }
+
+    pub(crate) fn render<'f>(
+        &self,
+        files: &'f impl DiagnosticsRenderHelper<'f>,
+        writer: &mut dyn WriteColor,
+    ) -> Result<(), Error> {
+        let command = match self.diagnostic.severity {
+            Severity::Bug | Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note | Severity::Help => "notice",
+        };
+
+        let mut properties = vec![];
+
+        if let Some(label) = self
+            .diagnostic
+            .labels
+            .iter()
+            .find(|label| label.style == LabelStyle::Primary)
+        {
+            let filepath = label.location.filepath;
+            let start = files.location(filepath, label.location.start)?;
+            let end = files.location(filepath, label.location.end)?;
+
+            properties.push(format!(
+                "file={}",
+                escape_workflow_command_property(&files.name(filepath)?)
+            ));
+            properties.push(format!("line={}", start.line_number));
+            properties.push(format!("col={}", start.column_number));
+            properties.push(format!("endLine={}", end.line_number));
+            properties.push(format!("endColumn={}", end.column_number));
+        }
+
+        let mut message = match &self.diagnostic.code {
+            Some(code) => format!("[{code}] {}", self.diagnostic.message),
+            None => self.diagnostic.message.clone(),
+        };
+
+        for note in &self.diagnostic.notes {
+            message.push_str(" | ");
+            message.push_str(note);
+        }
+
+        for suggestion in &self.diagnostic.suggestions {
+            message.push_str(" | ");
+            message.push_str(&suggestion_to_string(files, suggestion)?);
+        }
+
+        if properties.is_empty() {
+            write!(writer, "::{command}")?;
+        } else {
+            write!(writer, "::{command} {}", properties.join(","))?;
+        }
+
+        writeln!(writer, "::{}", escape_workflow_command_data(&message))?;
+
+        Ok(())
+    }
+}
+
+/// Output a diagnostic as a JSON object on one line, see [`DisplayStyle::Json`].
+///
+/// [`DisplayStyle::Json`]: crate::term::DisplayStyle::Json
+pub(crate) struct JsonDiagnostic<'d> {
+    diagnostic: &'d Diagnostic,
+    include_snippets: bool,
+}
+
+impl<'d> JsonDiagnostic<'d> {
+    pub(crate) const fn new(
+        diagnostic: &'d Diagnostic,
+        include_snippets: bool,
+    ) -> JsonDiagnostic<'d> {
+        JsonDiagnostic {
+            diagnostic,
+            include_snippets,
+        }
+    }
+
+    pub(crate) fn render<'f>(
+        &self,
+        files: &'f impl DiagnosticsRenderHelper<'f>,
+        writer: &mut dyn WriteColor,
+    ) -> Result<(), Error> {
+        let severity = match self.diagnostic.severity {
+            Severity::Bug => "bug",
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+            Severity::Help => "help",
+        };
+        let code = self
+            .diagnostic
+            .code
+            .as_deref()
+            .map_or_else(|| "null".to_owned(), json_string);
+
+        let mut labels = vec![];
+
+        for label in &self.diagnostic.labels {
+            let style = match label.style {
+                LabelStyle::Primary => "primary",
+                LabelStyle::Secondary => "secondary",
+            };
+
+            labels.push(format!(
+                "{{\"style\":\"{style}\",{},\"message\":{}{}}}",
+                json_location(files, label.location)?,
+                json_string(&label.message),
+                self.json_snippet(files, label.location)?
+            ));
+        }
+
+        let notes = self
+            .diagnostic
+            .notes
+            .iter()
+            .map(|note| json_string(note))
+            .collect::<Vec<_>>();
+
+        let mut suggestions = vec![];
+
+        for suggestion in &self.diagnostic.suggestions {
+            suggestions.push(format!(
+                "{{{},\"replacement\":{},\"message\":{}}}",
+                json_location(files, suggestion.location)?,
+                json_string(&suggestion.replacement),
+                json_string(&suggestion.message)
+            ));
+        }
+
+        writeln!(
+            writer,
+            "{{\"severity\":\"{severity}\",\"code\":{code},\"message\":{},\"labels\":[{}],\"notes\":[{}],\"suggestions\":[{}]}}",
+            json_string(&self.diagnostic.message),
+            labels.join(","),
+            notes.join(","),
+            suggestions.join(",")
+        )?;
+
+        Ok(())
+    }
+
+    /// Returns the `"snippet"` field of a label, if snippets are included.
+    /// Offsets past the end of the file are clamped, and the field is `null`,
+    /// if the location is not on UTF-8 character boundaries.
+    fn json_snippet<'f>(
+        &self,
+        files: &'f impl DiagnosticsRenderHelper<'f>,
+        location: Location,
+    ) -> Result<String, Error> {
+        if !self.include_snippets {
+            return Ok(String::new());
+        }
+
+        let snippet = location
+            .slice_clamped(files.source(location.filepath)?)
+            .map_or_else(|| "null".to_owned(), json_string);
+
+        Ok(format!(",\"snippet\":{snippet}"))
+    }
+}
+
+/// Returns fields of a JSON object, that describe a location.
+fn json_location<'f>(
+    files: &'f impl DiagnosticsRenderHelper<'f>,
+    location: Location,
+) -> Result<String, Error> {
+    let filepath = location.filepath;
+    let start = files.location(filepath, location.start)?;
+    let end = files.location(filepath, location.end)?;
+
+    Ok(format!(
+        "\"file\":{},\"start\":{},\"end\":{},\"line\":{},\"column\":{},\"end_line\":{},\"end_column\":{}",
+        json_string(&files.name(filepath)?),
+        location.start.0,
+        location.end.0,
+        start.line_number,
+        start.column_number,
+        end.line_number,
+        end.column_number
+    ))
+}
+
+/// Quotes and escapes a JSON string.
+fn json_string(value: &str) -> String {
+    let mut string = String::with_capacity(value.len() + 2);
+    string.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => string.push_str("\\\""),
+            '\\' => string.push_str("\\\\"),
+            '\n' => string.push_str("\\n"),
+            '\r' => string.push_str("\\r"),
+            '\t' => string.push_str("\\t"),
+            c if c.is_control() => {
+                write!(string, "\\u{:04x}", u32::from(c)).unwrap();
+            }
+            c => string.push(c),
+        }
+    }
+
+    string.push('"');
+    string
+}
+
+/// Escapes a message of a workflow command, so that it fits into one line.
+fn escape_workflow_command_data(data: &str) -> String {
+    data.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a value of a workflow command property, e.g. a file name.
+fn escape_workflow_command_property(property: &str) -> String {
+    escape_workflow_command_data(property)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// Renders a suggested fix together with its location, e.g.
+/// ``"help: add `;`: insert `;` at main.sr:2:14"``.
+fn suggestion_to_string<'f>(
+    files: &'f impl DiagnosticsRenderHelper<'f>,
+    suggestion: &Suggestion,
+) -> Result<String, Error> {
+    let Location {
+        filepath,
+        start,
+        end,
+    } = suggestion.location;
+    let location = files.location(filepath, start)?;
+    let at = format!(
+        "{}:{}:{}",
+        files.name(filepath)?,
+        location.line_number,
+        location.column_number
+    );
+
+    let message = &suggestion.message;
+    let replacement = &suggestion.replacement;
+
+    if start == end {
+        return Ok(format!("help: {message}: insert `{replacement}` at {at}"));
+    }
+
+    let code = &files.source(filepath)?[start.0..end.0];
+
+    Ok(if replacement.is_empty() {
+        format!("help: {message}: remove `{code}` at {at}")
+    } else {
+        format!("help: {message}: replace `{code}` with `{replacement}` at {at}")
+    })
+}
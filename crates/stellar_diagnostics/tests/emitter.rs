@@ -0,0 +1,65 @@
+use stellar_diagnostics::{
+    diagnostic::{Diagnostic, Label},
+    term::termcolor::NoColor,
+    Diagnostics, DiagnosticsEmitter,
+};
+use stellar_filesystem::{
+    location::{ByteOffset, Location},
+    source_map::SourceMap,
+};
+use stellar_interner::PathId;
+
+fn diagnostic(filepath: PathId, idx: usize) -> Diagnostic {
+    Diagnostic::error()
+        .with_code("E001")
+        .with_message(format!("diagnostic #{idx}"))
+        .with_labels([Label::primary(Location {
+            filepath,
+            start: ByteOffset(4),
+            end: ByteOffset(8),
+        })
+        .with_message(format!("label #{idx}"))])
+        .with_notes([format!("note #{idx}")])
+}
+
+#[test]
+fn unreadable_files_are_emitted_without_sources() {
+    let readable = PathId::from("src/emitter.sr");
+    let unreadable = PathId::from("src/removed_after_checking.sr");
+    let mut source_map = SourceMap::new();
+    source_map.set_override(readable, "fun main() {}\n");
+
+    let mut diagnostics = Diagnostics::new();
+    diagnostics.add_diagnostic(diagnostic(readable, 0));
+    diagnostics.add_diagnostic(diagnostic(unreadable, 1));
+
+    let mut writer = NoColor::new(vec![]);
+    DiagnosticsEmitter::new().write_global_diagnostics_with_source_map(
+        &mut writer,
+        &diagnostics.snapshot(),
+        &mut source_map,
+    );
+
+    // The label in the unreadable file is removed and the file is
+    // mentioned in a note instead.
+    assert_eq!(
+        String::from_utf8(writer.into_inner()).unwrap(),
+        "\
+error[E001]: diagnostic #0
+  ┌─ src/emitter.sr:1:5
+  │
+1 │ fun main() {}
+  │     ^^^^ label #0
+2 │ \n\
+\x20 │
+  = note #0
+
+error[E001]: diagnostic #1
+ = note #1
+ = the source of `src/removed_after_checking.sr` cannot be read
+
+"
+    );
+    assert!(source_map.file_or_load(readable).is_ok());
+    assert!(source_map.file_or_load(unreadable).is_err());
+}
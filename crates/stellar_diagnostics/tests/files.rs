@@ -0,0 +1,94 @@
+use stellar_diagnostics::{
+    diagnostic::{Diagnostic, Label},
+    Diagnostics,
+};
+use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_interner::{PathId, DUMMY_PATH_ID};
+
+fn error_in(filepath: PathId, message: &str) -> Diagnostic {
+    Diagnostic::error()
+        .with_message(message)
+        .with_labels([Label::primary(Location {
+            filepath,
+            start: ByteOffset(0),
+            end: ByteOffset(1),
+        })])
+}
+
+fn messages(diagnostics: &[Diagnostic]) -> Vec<&str> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect()
+}
+
+#[test]
+fn clear_file() {
+    let main = PathId::from("src/main.sr");
+    let utils = PathId::from("src/utils.sr");
+
+    let mut diagnostics = Diagnostics::new();
+    diagnostics.add_diagnostic(error_in(main, "first"));
+    diagnostics.add_diagnostic(error_in(utils, "second"));
+    diagnostics.add_diagnostic(error_in(main, "third"));
+
+    assert_eq!(messages(diagnostics.for_file(main)), ["first", "third"]);
+    assert_eq!(
+        diagnostics.files_with_diagnostics().collect::<Vec<_>>(),
+        [main, utils]
+    );
+
+    diagnostics.clear_file(main);
+
+    assert!(diagnostics.for_file(main).is_empty());
+    assert_eq!(messages(diagnostics.for_file(utils)), ["second"]);
+    assert_eq!(
+        diagnostics.files_with_diagnostics().collect::<Vec<_>>(),
+        [utils]
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert!(!diagnostics.files_involved.contains(&main));
+}
+
+#[test]
+fn context_free_diagnostics() {
+    let main = PathId::from("src/main.sr");
+
+    let mut diagnostics = Diagnostics::new();
+    diagnostics.add_diagnostic(Diagnostic::error().with_message("no package manifest"));
+    diagnostics.add_diagnostic(error_in(main, "located"));
+
+    assert_eq!(
+        messages(diagnostics.for_file(DUMMY_PATH_ID)),
+        ["no package manifest"]
+    );
+    assert_eq!(
+        diagnostics.files_with_diagnostics().collect::<Vec<_>>(),
+        [main]
+    );
+    assert_eq!(diagnostics.len(), 2);
+
+    diagnostics.clear_file(main);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics.is_fatal());
+}
+
+#[test]
+fn iteration_order() {
+    let main = PathId::from("src/main.sr");
+    let utils = PathId::from("src/utils.sr");
+
+    let mut diagnostics = Diagnostics::new();
+    diagnostics.add_diagnostic(error_in(utils, "first"));
+    diagnostics.add_diagnostic(error_in(main, "second"));
+    diagnostics.add_diagnostic(error_in(utils, "third"));
+
+    assert_eq!(
+        diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.message.as_str())
+            .collect::<Vec<_>>(),
+        ["first", "third", "second"]
+    );
+}
@@ -0,0 +1,64 @@
+use stellar_diagnostics::{
+    diagnostic::{Diagnostic, Label},
+    term::{self, termcolor::NoColor, Config, DisplayStyle},
+};
+use stellar_filesystem::{
+    location::{ByteOffset, Location},
+    source_map::SourceMap,
+};
+use stellar_interner::PathId;
+
+fn render(source_map: &SourceMap, diagnostic: &Diagnostic) -> String {
+    let config = Config {
+        display_style: DisplayStyle::GitHub,
+        ..Config::default()
+    };
+    let mut writer = NoColor::new(vec![]);
+
+    term::emit(&mut writer, &config, source_map, diagnostic).unwrap();
+
+    String::from_utf8(writer.into_inner()).unwrap()
+}
+
+#[test]
+fn located_error() {
+    let filepath = PathId::from("src/main.sr");
+    let mut source_map = SourceMap::new();
+    source_map.set_override(filepath, "fun main() {\n    let x = foo;\n}\n");
+
+    // `foo` on the second line
+    let diagnostic = Diagnostic::error()
+        .with_code("E100")
+        .with_message("failed to resolve `foo`")
+        .with_labels([
+            Label::primary(Location {
+                filepath,
+                start: ByteOffset(25),
+                end: ByteOffset(28),
+            }),
+            Label::secondary(Location {
+                filepath,
+                start: ByteOffset(0),
+                end: ByteOffset(3),
+            }),
+        ])
+        .with_notes(["note: 50% of names are typos", "help: check the spelling"]);
+
+    assert_eq!(
+        render(&source_map, &diagnostic),
+        "::error file=src/main.sr,line=2,col=13,endLine=2,endColumn=16::[E100] failed to resolve \
+         `foo` | note: 50%25 of names are typos | help: check the spelling\n"
+    );
+}
+
+#[test]
+fn warning_without_location() {
+    let diagnostic = Diagnostic::warning()
+        .with_code("W005")
+        .with_message("unused import");
+
+    assert_eq!(
+        render(&SourceMap::new(), &diagnostic),
+        "::warning::[W005] unused import\n"
+    );
+}
@@ -0,0 +1,72 @@
+use stellar_diagnostics::{
+    diagnostic::{Diagnostic, Label},
+    term::{self, termcolor::NoColor, Config, DisplayStyle},
+};
+use stellar_filesystem::{
+    location::{ByteOffset, Location},
+    source_map::SourceMap,
+};
+use stellar_interner::PathId;
+
+fn render(source_map: &SourceMap, diagnostic: &Diagnostic, include_snippets: bool) -> String {
+    let config = Config {
+        display_style: DisplayStyle::Json,
+        include_snippets,
+        ..Config::default()
+    };
+    let mut writer = NoColor::new(vec![]);
+
+    term::emit(&mut writer, &config, source_map, diagnostic).unwrap();
+
+    String::from_utf8(writer.into_inner()).unwrap()
+}
+
+fn diagnostic(filepath: PathId) -> Diagnostic {
+    // `"é"` on the second line
+    Diagnostic::error()
+        .with_code("E256")
+        .with_message("cannot apply `+` to `int32` and `String`")
+        .with_labels([Label::primary(Location {
+            filepath,
+            start: ByteOffset(21),
+            end: ByteOffset(25),
+        })
+        .with_message("this is `String`")])
+        .with_notes(["note: \"strings\"\tare not numbers"])
+}
+
+#[test]
+fn error_with_snippet() {
+    let filepath = PathId::from("src/json.sr");
+    let mut source_map = SourceMap::new();
+    source_map.set_override(filepath, "fun main() {\n    1 + \"é\";\n}\n");
+
+    assert_eq!(
+        render(&source_map, &diagnostic(filepath), true),
+        "{\"severity\":\"error\",\"code\":\"E256\",\"message\":\"cannot apply `+` to `int32` and \
+         `String`\",\"labels\":[{\"style\":\"primary\",\"file\":\"src/json.sr\",\"start\":21,\
+         \"end\":25,\"line\":2,\"column\":9,\"end_line\":2,\"end_column\":12,\"message\":\"this \
+         is `String`\",\"snippet\":\"\\\"é\\\"\"}],\"notes\":[\"note: \\\"strings\\\"\\tare not \
+         numbers\"],\"suggestions\":[]}\n"
+    );
+}
+
+#[test]
+fn snippets_are_optional() {
+    let filepath = PathId::from("src/plain.sr");
+    let mut source_map = SourceMap::new();
+    source_map.set_override(filepath, "fun main() {\n    1 + \"é\";\n}\n");
+
+    assert!(!render(&source_map, &diagnostic(filepath), false).contains("\"snippet\""));
+}
+
+#[test]
+fn warning_without_location() {
+    let diagnostic = Diagnostic::warning().with_message("unused import");
+
+    assert_eq!(
+        render(&SourceMap::new(), &diagnostic, true),
+        "{\"severity\":\"warning\",\"code\":null,\"message\":\"unused import\",\"labels\":[],\
+         \"notes\":[],\"suggestions\":[]}\n"
+    );
+}
@@ -0,0 +1,84 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use stellar_diagnostics::{
+    diagnostic::{Diagnostic, Label},
+    term::{self, termcolor::NoColor, Config},
+    Diagnostics, DiagnosticsSnapshot,
+};
+use stellar_filesystem::{
+    location::{ByteOffset, Location},
+    source_map::SourceMap,
+};
+use stellar_interner::PathId;
+
+const DIAGNOSTICS: usize = 200;
+
+fn diagnostic(filepath: PathId, idx: usize) -> Diagnostic {
+    Diagnostic::error()
+        .with_code("E001")
+        .with_message(format!("diagnostic #{idx}"))
+        .with_labels([Label::primary(Location {
+            filepath,
+            start: ByteOffset(4),
+            end: ByteOffset(8),
+        })
+        .with_message(format!("label #{idx}"))])
+        .with_notes([format!("note #{idx}")])
+}
+
+fn render(source_map: &SourceMap, snapshot: &DiagnosticsSnapshot) -> String {
+    let mut writer = NoColor::new(vec![]);
+
+    for diagnostic in snapshot.iter() {
+        term::emit(&mut writer, &Config::default(), source_map, diagnostic).unwrap();
+    }
+
+    String::from_utf8(writer.into_inner()).unwrap()
+}
+
+#[test]
+fn snapshots_while_writing() {
+    let filepath = PathId::from("src/snapshot.sr");
+    let mut source_map = SourceMap::new();
+    source_map.set_override(filepath, "fun main() {}\n");
+
+    let diagnostics = Arc::new(Mutex::new(Diagnostics::new()));
+    let writer = {
+        let diagnostics = Arc::clone(&diagnostics);
+
+        thread::spawn(move || {
+            for idx in 0..DIAGNOSTICS {
+                diagnostics
+                    .lock()
+                    .unwrap()
+                    .add_diagnostic(diagnostic(filepath, idx));
+            }
+        })
+    };
+
+    loop {
+        let done = writer.is_finished();
+        // The lock is released before the snapshot is rendered.
+        let snapshot = diagnostics.lock().unwrap().snapshot();
+        let output = render(&source_map, &snapshot);
+
+        // Diagnostics of the snapshot are the first ones written and every
+        // one of them is rendered completely.
+        for (idx, diagnostic) in snapshot.iter().enumerate() {
+            assert_eq!(diagnostic.message, format!("diagnostic #{idx}"));
+            assert!(output.contains(&format!("label #{idx}\n")));
+            assert!(output.contains(&format!("= note #{idx}\n")));
+        }
+        assert_eq!(output.matches("error[E001]").count(), snapshot.len());
+
+        if done {
+            assert_eq!(snapshot.len(), DIAGNOSTICS);
+            break;
+        }
+    }
+
+    writer.join().unwrap();
+}
@@ -0,0 +1,136 @@
+use stellar_diagnostics::{
+    define_diagnostics,
+    diagnostic::{Diagnostic, Label, Suggestion},
+    term::{self, termcolor::NoColor, Config, DisplayStyle},
+    BuildDiagnostic,
+};
+use stellar_filesystem::{
+    location::{ByteOffset, Location},
+    source_map::SourceMap,
+};
+use stellar_interner::PathId;
+
+const SOURCE: &str = "fun main() {\n    let x = 1\n    x = 2;\n}\n";
+
+define_diagnostics! {
+    /// Diagnostic, that occurs when an immutable binding is assigned.
+    diagnostic(error) AssignmentToImmutableBinding(
+        self,
+        name_location: Location,
+        assignment_location: Location
+    ) {
+        code { "E999" }
+        message { "cannot assign twice to an immutable binding" }
+        labels {
+            primary { self.assignment_location => "cannot assign twice" }
+            secondary { self.name_location => "first assignment" }
+        }
+        suggestions {
+            Location {
+                end: self.name_location.start,
+                ..self.name_location
+            } => "mut ", "consider making the binding mutable";
+            self.assignment_location => "", "remove the assignment"
+        }
+    }
+}
+
+fn location(filepath: PathId, start: usize, end: usize) -> Location {
+    Location {
+        filepath,
+        start: ByteOffset(start),
+        end: ByteOffset(end),
+    }
+}
+
+/// `x` on the second line and `x = 2` on the third line.
+fn assignment_to_immutable_binding(filepath: PathId) -> Diagnostic {
+    AssignmentToImmutableBinding::new(location(filepath, 21, 22), location(filepath, 31, 36))
+        .build()
+}
+
+fn render(diagnostic: &Diagnostic, display_style: DisplayStyle) -> String {
+    let mut source_map = SourceMap::new();
+    source_map.set_override(PathId::from("src/main.sr"), SOURCE);
+
+    let config = Config {
+        display_style,
+        ..Config::default()
+    };
+    let mut writer = NoColor::new(vec![]);
+
+    term::emit(&mut writer, &config, &source_map, diagnostic).unwrap();
+
+    String::from_utf8(writer.into_inner()).unwrap()
+}
+
+#[test]
+fn suggestions_are_added_by_the_macro() {
+    let filepath = PathId::from("src/main.sr");
+
+    assert_eq!(
+        assignment_to_immutable_binding(filepath).suggestions,
+        [
+            Suggestion::new(
+                location(filepath, 21, 21),
+                "mut ",
+                "consider making the binding mutable"
+            ),
+            Suggestion::new(location(filepath, 31, 36), "", "remove the assignment"),
+        ]
+    );
+}
+
+#[test]
+fn insertions_and_deletions() {
+    let diagnostic = assignment_to_immutable_binding(PathId::from("src/main.sr"));
+
+    assert_eq!(
+        render(&diagnostic, DisplayStyle::Rich),
+        "\
+error[E999]: cannot assign twice to an immutable binding
+  ┌─ src/main.sr:3:5
+  │
+1 │ fun main() {
+2 │     let x = 1
+  │         - first assignment
+3 │     x = 2;
+  │     ^^^^^ cannot assign twice
+4 │ }
+5 │ \n\
+\x20 │
+  = help: consider making the binding mutable: insert `mut ` at src/main.sr:2:9
+  = help: remove the assignment: remove `x = 2` at src/main.sr:3:5
+
+"
+    );
+}
+
+#[test]
+fn replacement() {
+    let filepath = PathId::from("src/main.sr");
+
+    // `1` on the second line
+    let diagnostic = Diagnostic::warning()
+        .with_code("W999")
+        .with_message("literal can be simplified")
+        .with_labels([Label::primary(location(filepath, 25, 26))])
+        .with_suggestion(Suggestion::new(
+            location(filepath, 25, 26),
+            "one",
+            "use a constant",
+        ));
+
+    assert_eq!(
+        render(&diagnostic, DisplayStyle::Medium),
+        "\
+src/main.sr:2:13: warning[W999]: literal can be simplified
+ = help: use a constant: replace `1` with `one` at src/main.sr:2:13
+"
+    );
+    assert_eq!(
+        render(&diagnostic, DisplayStyle::GitHub),
+        "::warning file=src/main.sr,line=2,col=13,endLine=2,endColumn=14::[W999] literal can be \
+         simplified | help: use a constant: replace `1` with `one` at src/main.sr:2:13\n"
+    );
+}
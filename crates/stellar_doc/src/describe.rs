@@ -0,0 +1,78 @@
+//! Describes symbols in a short form, e.g. for hovers in editors.
+
+use stellar_database::{State, Symbol, SymbolKind};
+use stellar_filesystem::location::{Location, DUMMY_LOCATION};
+use stellar_hir::{EnumItem, ModuleItem};
+
+use crate::{docstring, docstring_to_markdown, render::render_declaration_head};
+
+/// A short description of a symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolDescription {
+    /// The rendered head of the declaration, e.g. `pub struct Foo[T: Clone]`
+    /// (see [`render_declaration_head()`]).
+    pub signature: String,
+
+    /// The docstring in Markdown, if the symbol has one.
+    pub docstring: Option<String>,
+
+    /// Location of the name of the symbol in its definition.
+    pub definition: Location,
+
+    /// The kind of the symbol.
+    pub kind: SymbolKind,
+}
+
+/// Describes a symbol: renders the head of its declaration and finds its
+/// docstring.
+///
+/// **Note**: docstrings are taken from the HIR stored in the state (see
+/// [`State::hir()`]), so docstrings of modules and builtin symbols are not
+/// available.
+#[must_use]
+pub fn describe_symbol(symbol: Symbol, state: &State) -> SymbolDescription {
+    let db = state.db();
+
+    let docstring = match symbol {
+        Symbol::EnumItem(item) => {
+            let name = item.name(db).id;
+
+            match state
+                .hir()
+                .get(Symbol::Enum(item.enum_(db)))
+                .map(AsRef::as_ref)
+            {
+                Some(ModuleItem::Enum(enum_)) => enum_
+                    .items
+                    .iter()
+                    .find(|item| item.name_id() == name)
+                    .and_then(enum_item_docstring),
+                _ => None,
+            }
+        }
+        _ => state
+            .hir()
+            .get(symbol)
+            .and_then(|item| docstring(item.as_ref())),
+    };
+
+    SymbolDescription {
+        signature: render_declaration_head(symbol, state),
+        docstring: docstring
+            .map(docstring_to_markdown)
+            .filter(|docstring| !docstring.is_empty()),
+        definition: match symbol {
+            Symbol::BuiltinSymbol(_) => DUMMY_LOCATION,
+            _ => symbol.name(db).location,
+        },
+        kind: symbol.kind(),
+    }
+}
+
+fn enum_item_docstring(item: &EnumItem) -> Option<&str> {
+    match item {
+        EnumItem::Just { docstring, .. }
+        | EnumItem::TupleLike { docstring, .. }
+        | EnumItem::Struct { docstring, .. } => docstring.as_deref(),
+    }
+}
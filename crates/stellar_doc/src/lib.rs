@@ -0,0 +1,408 @@
+//! Documentation generator for the Stellar programming language.
+//!
+//! Walks modules of a package, whose definitions are already collected,
+//! and renders one Markdown page per module with signatures of module items
+//! (see [`render::render_signature()`]) and their docstrings. Items, that are
+//! referenced in signatures, are linked by their full paths.
+//!
+//! Short descriptions of single symbols, e.g. for hovers in editors, are
+//! provided by [`describe::describe_symbol()`].
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png",
+    html_favicon_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png"
+)]
+#![cfg_attr(not(test), forbid(clippy::unwrap_used))]
+#![warn(missing_docs, clippy::dbg_macro)]
+#![warn(
+    // rustc lint groups https://doc.rust-lang.org/rustc/lints/groups.html
+    future_incompatible,
+    let_underscore,
+    nonstandard_style,
+    rust_2018_compatibility,
+    rust_2018_idioms,
+    rust_2021_compatibility,
+    unused,
+    // rustc allowed-by-default lints https://doc.rust-lang.org/rustc/lints/listing/allowed-by-default.html
+    macro_use_extern_crate,
+    meta_variable_misuse,
+    missing_abi,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    non_ascii_idents,
+    noop_method_call,
+    single_use_lifetimes,
+    trivial_casts,
+    trivial_numeric_casts,
+    unreachable_pub,
+    unsafe_op_in_unsafe_fn,
+    unused_crate_dependencies,
+    unused_import_braces,
+    unused_lifetimes,
+    unused_qualifications,
+    variant_size_differences,
+    // rustdoc lints https://doc.rust-lang.org/rustdoc/lints.html
+    rustdoc::broken_intra_doc_links,
+    rustdoc::private_intra_doc_links,
+    rustdoc::missing_crate_level_docs,
+    rustdoc::private_doc_tests,
+    rustdoc::invalid_codeblock_attributes,
+    rustdoc::invalid_rust_codeblocks,
+    rustdoc::bare_urls,
+    // clippy categories https://doc.rust-lang.org/clippy/
+    clippy::all,
+    clippy::correctness,
+    clippy::suspicious,
+    clippy::style,
+    clippy::complexity,
+    clippy::perf,
+    clippy::pedantic,
+    clippy::nursery,
+)]
+#![allow(
+    clippy::module_name_repetitions,
+    clippy::too_many_lines,
+    clippy::option_if_let_else
+)]
+
+pub mod describe;
+pub mod render;
+
+use stellar_ast::Visibility;
+use stellar_database::{Database, ModuleId, PackageId, State, Symbol, SymbolKind};
+use stellar_fx_hash::{FxHashMap, FxHashSet};
+use stellar_hir::{
+    visit::{walk_function_signature, walk_generic_parameter, walk_type_constructor, Visitor},
+    Function, GenericParameter, ModuleItem,
+};
+use stellar_interner::IdentifierId;
+
+use crate::render::{render_function_signature, render_signature};
+
+/// Options of the documentation generator.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Whether to document private module items and methods.
+    pub document_private_items: bool,
+}
+
+/// A generated documentation page of a module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    /// The documented module.
+    pub module: ModuleId,
+
+    /// The name of the page file, e.g. `a.b.md` for the module `a.b`.
+    pub filename: String,
+
+    /// Markdown source of the page.
+    pub content: String,
+}
+
+/// Sections of a module page in the order they appear in.
+const SECTIONS: [(SymbolKind, &str); 6] = [
+    (SymbolKind::Interface, "Interfaces"),
+    (SymbolKind::Struct, "Structs"),
+    (SymbolKind::TupleLikeStruct, "Tuple-like structs"),
+    (SymbolKind::Enum, "Enums"),
+    (SymbolKind::TypeAlias, "Type aliases"),
+    (SymbolKind::Function, "Functions"),
+];
+
+/// Generates documentation pages for the root module of a package and
+/// all of its submodules.
+///
+/// `modules` are HIR of modules, which are used to get docstrings of modules
+/// themselves. Module items are taken from the state (see [`State::hir()`]),
+/// so definitions must be collected before generating documentation.
+#[must_use]
+#[allow(clippy::implicit_hasher)]
+pub fn generate_documentation(
+    state: &State,
+    modules: &FxHashMap<ModuleId, stellar_hir::Module>,
+    package: PackageId,
+    options: Options,
+) -> Vec<Page> {
+    let mut pages = vec![];
+    let mut queue = vec![package.root_module(state.db())];
+
+    while let Some(module) = queue.pop() {
+        pages.push(Page {
+            module,
+            filename: page_filename(state.db(), module),
+            content: ModulePage::new(state, options, module).render(
+                modules
+                    .get(&module)
+                    .and_then(|hir| hir.docstring.as_deref()),
+            ),
+        });
+
+        queue.extend(submodules(state.db(), module).into_iter().rev());
+    }
+
+    pages
+}
+
+/// Returns submodules of a module sorted by their names.
+fn submodules(db: &Database, module: ModuleId) -> Vec<ModuleId> {
+    let mut submodules = module.submodules(db).values().copied().collect::<Vec<_>>();
+    submodules.sort_by_key(|submodule| submodule.name(db).id.to_string());
+    submodules
+}
+
+fn page_filename(db: &Database, module: ModuleId) -> String {
+    format!("{}.md", module.path(db))
+}
+
+struct ModulePage<'s> {
+    state: &'s State,
+    options: Options,
+    module: ModuleId,
+    content: String,
+}
+
+impl<'s> ModulePage<'s> {
+    const fn new(state: &'s State, options: Options, module: ModuleId) -> Self {
+        Self {
+            state,
+            options,
+            module,
+            content: String::new(),
+        }
+    }
+
+    fn render(mut self, docstring: Option<&str>) -> String {
+        let db = self.state.db();
+
+        self.line(format!("# Module `{}`", self.module.path(db)));
+        self.docstring(docstring);
+
+        let submodules = submodules(db, self.module);
+
+        if !submodules.is_empty() {
+            self.line("## Modules");
+            self.line(
+                submodules
+                    .into_iter()
+                    .map(|submodule| {
+                        format!(
+                            "- [`{}`]({})",
+                            submodule.path(db),
+                            page_filename(db, submodule)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+        }
+
+        let mut items = self
+            .module
+            .module_item_symbols(db)
+            .values()
+            .copied()
+            .filter(|symbol| self.is_documented(*symbol))
+            .collect::<Vec<_>>();
+        items.sort_by_key(|symbol| symbol.name(db).location.start);
+
+        for (kind, title) in SECTIONS {
+            let mut items = items
+                .iter()
+                .copied()
+                .filter(|symbol| symbol.kind() == kind)
+                .peekable();
+
+            if items.peek().is_none() {
+                continue;
+            }
+
+            self.line(format!("## {title}"));
+
+            for symbol in items {
+                self.item(symbol);
+            }
+        }
+
+        let mut content = self.content;
+        content.truncate(content.trim_end().len());
+        content.push('\n');
+        content
+    }
+
+    fn item(&mut self, symbol: Symbol) {
+        let db = self.state.db();
+        let hir = self.state.hir().get(symbol).map(AsRef::as_ref);
+
+        let name = symbol.name(db).id;
+
+        self.line(format!("### <a id=\"{name}\"></a>`{name}`"));
+        self.code(&render_signature(symbol, self.state));
+        self.docstring(hir.and_then(docstring));
+
+        let Some(hir) = hir else {
+            return;
+        };
+
+        self.references(symbol, hir);
+
+        let methods = match hir {
+            ModuleItem::Enum(enum_) => &enum_.methods,
+            ModuleItem::Struct(struct_) => &struct_.methods,
+            ModuleItem::TupleLikeStruct(struct_) => &struct_.methods,
+            _ => return,
+        };
+        let document_private_items = self.options.document_private_items;
+        let mut methods = methods
+            .iter()
+            .filter(|method| {
+                document_private_items
+                    || matches!(method.signature.visibility, Visibility::Public(_))
+            })
+            .peekable();
+
+        if methods.peek().is_none() {
+            return;
+        }
+
+        self.line("#### Methods");
+
+        for method in methods {
+            self.code(&render_function_signature(&method.signature));
+            self.docstring(method.signature.docstring.as_deref());
+        }
+    }
+
+    /// Links documented module items, that are referenced in the signature
+    /// of a given module item.
+    fn references(&mut self, symbol: Symbol, item: &ModuleItem) {
+        let db = self.state.db();
+
+        let mut collector = ReferenceCollector::default();
+        collector.visit_module_item(item);
+
+        let mut references = vec![];
+
+        for path in collector.paths {
+            // Generic parameters shadow module items.
+            if let [name] = path.identifiers.as_slice() {
+                if collector.generic_parameters.contains(&name.id) {
+                    continue;
+                }
+            }
+
+            let Ok(reference) = db.resolve_path(self.module, &path.identifiers) else {
+                continue;
+            };
+
+            if reference != symbol
+                && reference.module_item_kind_or_none().is_some()
+                && self.is_documented(reference)
+                && !references.contains(&reference)
+            {
+                references.push(reference);
+            }
+        }
+
+        if references.is_empty() {
+            return;
+        }
+
+        let references = references
+            .into_iter()
+            .map(|symbol| {
+                format!(
+                    "[`{}`]({}#{})",
+                    symbol.path(db),
+                    page_filename(db, symbol.module(db)),
+                    symbol.name(db).id
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        self.line(format!("See also: {references}"));
+    }
+
+    fn is_documented(&self, symbol: Symbol) -> bool {
+        // The same rule as in `ModuleId::public_symbols()`, so that private
+        // items are not documented, unless asked to.
+        self.options.document_private_items
+            || symbol.visibility(self.state.db()) != Visibility::Private
+    }
+
+    fn code(&mut self, code: &str) {
+        self.line(format!("```stellar\n{code}\n```"));
+    }
+
+    fn docstring(&mut self, docstring: Option<&str>) {
+        let Some(docstring) = docstring.map(docstring_to_markdown) else {
+            return;
+        };
+
+        if !docstring.is_empty() {
+            self.line(docstring);
+        }
+    }
+
+    /// Adds a paragraph to the page.
+    fn line(&mut self, line: impl AsRef<str>) {
+        self.content.push_str(line.as_ref());
+        self.content.push_str("\n\n");
+    }
+}
+
+/// Removes comment markers from a docstring. Docstrings are stored as
+/// doc comments concatenated together, e.g. `/// Foo./// Bar.`, so every
+/// marker starts a new line.
+pub(crate) fn docstring_to_markdown(docstring: &str) -> String {
+    docstring
+        .replace("//!", "///")
+        .split("///")
+        .skip(1)
+        .map(|line| line.strip_prefix(' ').unwrap_or(line).trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_owned()
+}
+
+pub(crate) fn docstring(item: &ModuleItem) -> Option<&str> {
+    match item {
+        ModuleItem::Enum(enum_) => enum_.docstring.as_deref(),
+        ModuleItem::Function(function) => function.signature.docstring.as_deref(),
+        ModuleItem::Interface(interface) => interface.docstring.as_deref(),
+        ModuleItem::Struct(struct_) => struct_.docstring.as_deref(),
+        ModuleItem::TupleLikeStruct(struct_) => struct_.docstring.as_deref(),
+        ModuleItem::TypeAlias(alias) => alias.docstring.as_deref(),
+        ModuleItem::Module(module) => module.docstring.as_deref(),
+        ModuleItem::Import { .. } => None,
+    }
+}
+
+/// Collects paths of types used in a signature of a module item. Bodies of
+/// functions and methods are skipped.
+#[derive(Default)]
+struct ReferenceCollector {
+    paths: Vec<stellar_hir::Path>,
+    generic_parameters: FxHashSet<IdentifierId>,
+}
+
+impl Visitor for ReferenceCollector {
+    fn visit_function(&mut self, function: &Function) {
+        walk_function_signature(self, &function.signature);
+    }
+
+    fn visit_method(&mut self, method: &Function) {
+        walk_function_signature(self, &method.signature);
+    }
+
+    fn visit_generic_parameter(&mut self, generic_parameter: &GenericParameter) {
+        self.generic_parameters.insert(generic_parameter.name.id);
+        walk_generic_parameter(self, generic_parameter);
+    }
+
+    fn visit_type_constructor(&mut self, constructor: &stellar_hir::TypeConstructor) {
+        self.paths.push(constructor.path.clone());
+        walk_type_constructor(self, constructor);
+    }
+}
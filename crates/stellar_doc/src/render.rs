@@ -0,0 +1,375 @@
+//! Renders signatures of definitions the way they are written in the source
+//! code, e.g.:
+//!
+//! ```stellar
+//! pub struct Point[T] implements ToString {
+//!     pub x: T,
+//!     pub y: T,
+//! }
+//! ```
+//!
+//! Bodies of functions are omitted. See [`render_signature()`] for details.
+
+use stellar_ast::Visibility;
+use stellar_database::{State, Symbol};
+use stellar_hir::{
+    EnumItem, FunctionParameter, FunctionSignature, GenericParameter, ModuleItem, Pattern,
+    StructField, TupleField, TypeConstructor, WherePredicate,
+};
+use stellar_typechecker::signature_analysis::check_interface_conformance::{
+    type_constructor_to_string, type_to_string,
+};
+
+/// Indentation of fields, enum items and interface methods.
+const INDENT: &str = "    ";
+
+/// Renders the signature of a definition, e.g. `pub fun foo[T](x: T): T` for
+/// a function or a struct with all of its fields.
+///
+/// Definitions, whose HIR is not stored in the state (see [`State::hir()`]),
+/// are rendered as their kind and path, e.g. `struct a.Foo`.
+#[must_use]
+pub fn render_signature(symbol: Symbol, state: &State) -> String {
+    let db = state.db();
+
+    match symbol {
+        Symbol::Module(module) => format!("module {}", module.path(db)),
+        Symbol::BuiltinSymbol(_) => symbol.path(db).to_string(),
+        Symbol::EnumItem(item) => {
+            let enum_ = item.enum_(db);
+            let name = item.name(db).id;
+
+            let rendered = match state.hir().get(Symbol::Enum(enum_)).map(AsRef::as_ref) {
+                Some(ModuleItem::Enum(enum_)) => enum_
+                    .items
+                    .iter()
+                    .find(|item| item.name().id == name)
+                    .map(render_enum_item),
+                _ => None,
+            };
+
+            format!(
+                "{}.{}",
+                enum_.signature(db).name(db).id,
+                rendered.unwrap_or_else(|| name.to_string())
+            )
+        }
+        _ => state.hir().get(symbol).map_or_else(
+            || format!("{} {}", symbol.kind(), symbol.path(db)),
+            |item| render_module_item(item),
+        ),
+    }
+}
+
+/// Renders the head of a declaration, e.g. `pub struct Foo[T: Clone]`.
+///
+/// Fields, enum items and interface methods are elided. Functions, type aliases,
+/// tuple-like structs and enum items are rendered completely, the same way as
+/// in [`render_signature()`].
+#[must_use]
+pub fn render_declaration_head(symbol: Symbol, state: &State) -> String {
+    match state.hir().get(symbol).map(AsRef::as_ref) {
+        Some(item @ (ModuleItem::Enum(_) | ModuleItem::Interface(_) | ModuleItem::Struct(_))) => {
+            render_block_header(item)
+        }
+        _ => render_signature(symbol, state),
+    }
+}
+
+/// Renders the signature of a function or a method, e.g. `fun foo[T](x: T): T`.
+#[must_use]
+pub fn render_function_signature(signature: &FunctionSignature) -> String {
+    let parameters = signature
+        .parameters
+        .iter()
+        .map(render_function_parameter)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut rendered = format!(
+        "{}fun {}{}({parameters})",
+        render_visibility(signature.visibility),
+        signature.name.id,
+        render_generic_parameters(&signature.generic_parameters),
+    );
+
+    if let Some(return_type) = &signature.return_type {
+        rendered.push_str(": ");
+        rendered.push_str(&type_to_string(return_type));
+    }
+
+    rendered.push_str(&render_where_predicates(&signature.where_predicates));
+    rendered
+}
+
+fn render_module_item(item: &ModuleItem) -> String {
+    match item {
+        ModuleItem::Enum(enum_) => render_block(
+            &render_block_header(item),
+            enum_
+                .items
+                .iter()
+                .map(|item| format!("{},", render_enum_item(item))),
+        ),
+        ModuleItem::Function(function) => render_function_signature(&function.signature),
+        ModuleItem::Import { .. } => String::new(),
+        ModuleItem::Interface(interface) => render_block(
+            &render_block_header(item),
+            interface
+                .methods
+                .iter()
+                .map(|method| format!("{};", render_function_signature(&method.signature))),
+        ),
+        ModuleItem::Struct(struct_) => render_block(
+            &render_block_header(item),
+            struct_
+                .fields
+                .iter()
+                .map(|field| format!("{},", render_struct_field(field))),
+        ),
+        ModuleItem::TupleLikeStruct(struct_) => render_header(
+            struct_.visibility,
+            "struct",
+            &format!(
+                "{}{}({})",
+                struct_.name.id,
+                render_generic_parameters(&struct_.generic_parameters),
+                render_tuple_fields(&struct_.fields)
+            ),
+            &[],
+            struct_.implements.as_deref(),
+            &struct_.where_predicates,
+        ),
+        ModuleItem::TypeAlias(alias) => format!(
+            "{}type {}{} = {}",
+            render_visibility(alias.visibility),
+            alias.name.id,
+            render_generic_parameters(&alias.generic_parameters),
+            type_to_string(&alias.value)
+        ),
+        ModuleItem::Module(module) => format!(
+            "{}module {}",
+            render_visibility(module.visibility),
+            module.name.id
+        ),
+    }
+}
+
+/// Renders the header of an enum, an interface or a struct, whose body is
+/// a block.
+fn render_block_header(item: &ModuleItem) -> String {
+    match item {
+        ModuleItem::Enum(enum_) => render_header(
+            enum_.visibility,
+            "enum",
+            &enum_.name.id.to_string(),
+            &enum_.generic_parameters,
+            enum_.implements.as_deref(),
+            &enum_.where_predicates,
+        ),
+        ModuleItem::Interface(interface) => render_header(
+            interface.visibility,
+            "interface",
+            &interface.name.id.to_string(),
+            &interface.generic_parameters,
+            None,
+            &interface.where_predicates,
+        ),
+        ModuleItem::Struct(struct_) => render_header(
+            struct_.visibility,
+            "struct",
+            &struct_.name.id.to_string(),
+            &struct_.generic_parameters,
+            struct_.implements.as_deref(),
+            &struct_.where_predicates,
+        ),
+        _ => unreachable!(),
+    }
+}
+
+/// Renders everything before the body of a definition, e.g.
+/// `pub struct Foo[T] implements Bar where T: Baz`.
+fn render_header(
+    visibility: Visibility,
+    keyword: &str,
+    name: &str,
+    generic_parameters: &[GenericParameter],
+    implements: Option<&[TypeConstructor]>,
+    where_predicates: &[WherePredicate],
+) -> String {
+    let mut header = format!(
+        "{}{keyword} {name}{}",
+        render_visibility(visibility),
+        render_generic_parameters(generic_parameters)
+    );
+
+    if let Some(implements) = implements.filter(|implements| !implements.is_empty()) {
+        header.push_str(" implements ");
+        header.push_str(&render_type_constructors(implements, ", "));
+    }
+
+    header.push_str(&render_where_predicates(where_predicates));
+    header
+}
+
+/// Renders a header followed by a block of lines, e.g. fields of a struct.
+fn render_block(header: &str, lines: impl Iterator<Item = String>) -> String {
+    let lines = lines
+        .map(|line| format!("{INDENT}{line}"))
+        .collect::<Vec<_>>();
+
+    if lines.is_empty() {
+        format!("{header} {{}}")
+    } else {
+        format!("{header} {{\n{}\n}}", lines.join("\n"))
+    }
+}
+
+fn render_enum_item(item: &EnumItem) -> String {
+    match item {
+        EnumItem::Just { name, .. } => name.id.to_string(),
+        EnumItem::TupleLike { name, fields, .. } => {
+            format!("{}({})", name.id, render_tuple_fields(fields))
+        }
+        EnumItem::Struct { name, fields, .. } => format!(
+            "{} {{ {} }}",
+            name.id,
+            fields
+                .iter()
+                .map(render_struct_field)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn render_struct_field(field: &StructField) -> String {
+    format!(
+        "{}{}: {}",
+        render_visibility(field.visibility),
+        field.name.id,
+        type_to_string(&field.ty)
+    )
+}
+
+fn render_tuple_fields(fields: &[TupleField]) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            format!(
+                "{}{}",
+                render_visibility(field.visibility),
+                type_to_string(&field.ty)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn render_function_parameter(parameter: &FunctionParameter) -> String {
+    match parameter {
+        FunctionParameter::SelfParameter(parameter) => parameter.ty.as_ref().map_or_else(
+            || "self".to_owned(),
+            |ty| format!("self: {}", type_to_string(ty)),
+        ),
+        FunctionParameter::NotSelfParameter(parameter) => format!(
+            "{}: {}",
+            render_pattern(&parameter.pattern),
+            type_to_string(&parameter.ty)
+        ),
+    }
+}
+
+/// Renders a pattern of a function parameter. Only patterns, that bind
+/// names, are rendered completely, others are rendered as `_`.
+fn render_pattern(pattern: &Pattern) -> String {
+    match pattern {
+        Pattern::Identifier {
+            identifier,
+            mutable,
+            ..
+        } => {
+            if *mutable {
+                format!("mut {}", identifier.id)
+            } else {
+                identifier.id.to_string()
+            }
+        }
+        Pattern::Tuple { elements, .. } => format!(
+            "({})",
+            elements
+                .iter()
+                .map(render_pattern)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        _ => "_".to_owned(),
+    }
+}
+
+fn render_generic_parameters(generic_parameters: &[GenericParameter]) -> String {
+    if generic_parameters.is_empty() {
+        return String::new();
+    }
+
+    let generic_parameters = generic_parameters
+        .iter()
+        .map(|parameter| {
+            let mut rendered = parameter.name.id.to_string();
+
+            if let Some(bounds) = parameter
+                .bounds
+                .as_deref()
+                .filter(|bounds| !bounds.is_empty())
+            {
+                rendered.push_str(": ");
+                rendered.push_str(&render_type_constructors(bounds, " + "));
+            }
+
+            if let Some(default_value) = &parameter.default_value {
+                rendered.push_str(" = ");
+                rendered.push_str(&type_to_string(default_value));
+            }
+
+            rendered
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("[{generic_parameters}]")
+}
+
+fn render_where_predicates(where_predicates: &[WherePredicate]) -> String {
+    if where_predicates.is_empty() {
+        return String::new();
+    }
+
+    let where_predicates = where_predicates
+        .iter()
+        .map(|predicate| {
+            format!(
+                "{}: {}",
+                type_to_string(&predicate.ty),
+                render_type_constructors(&predicate.bounds, " + ")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(" where {where_predicates}")
+}
+
+fn render_type_constructors(constructors: &[TypeConstructor], separator: &str) -> String {
+    constructors
+        .iter()
+        .map(type_constructor_to_string)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+const fn render_visibility(visibility: Visibility) -> &'static str {
+    match visibility {
+        Visibility::Private => "",
+        Visibility::Public(_) => "pub ",
+    }
+}
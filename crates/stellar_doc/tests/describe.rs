@@ -0,0 +1,85 @@
+use std::path::Path;
+
+use stellar_ast::IdentifierAST;
+use stellar_database::{PackageData, SymbolKind};
+use stellar_doc::describe::{describe_symbol, SymbolDescription};
+use stellar_driver::Session;
+use stellar_filesystem::location::DUMMY_LOCATION;
+use stellar_interner::{IdentifierId, PathId, DUMMY_PATH_ID};
+
+const SOURCE: &str = include_str!("fixtures/describe.sr");
+
+fn describe(path: &[&str]) -> SymbolDescription {
+    let mut session = Session::new();
+
+    let package = PackageData::alloc(
+        session.state_mut().db_mut(),
+        IdentifierId::from("test"),
+        DUMMY_PATH_ID,
+    );
+    let filepath = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/describe.sr");
+    let root = session
+        .parse_file(
+            package,
+            IdentifierId::from("test").into(),
+            PathId::from(filepath),
+        )
+        .unwrap();
+    package.set_root_module(session.state_mut().db_mut(), root);
+
+    session.lower();
+    session.collect_definitions();
+
+    let state = session.state();
+    assert!(state.diagnostics().is_ok());
+
+    let identifiers = path
+        .iter()
+        .map(|segment| IdentifierAST {
+            location: DUMMY_LOCATION,
+            id: IdentifierId::from(*segment),
+        })
+        .collect::<Vec<_>>();
+    let symbol = state.db().resolve_path(root, &identifiers).unwrap();
+
+    describe_symbol(symbol, state)
+}
+
+#[test]
+fn generic_struct() {
+    let description = describe(&["Pair"]);
+
+    assert_eq!(description.signature, "pub struct Pair[T: Clone]");
+    assert_eq!(description.docstring.as_deref(), Some("A pair of values."));
+    assert_eq!(description.kind, SymbolKind::Struct);
+    assert_eq!(
+        &SOURCE[description.definition.start.0..description.definition.end.0],
+        "Pair"
+    );
+}
+
+#[test]
+fn function_with_parameters() {
+    let description = describe(&["add"]);
+
+    assert_eq!(
+        description.signature,
+        "fun add(x: int32, mut y: int32): String"
+    );
+    assert_eq!(description.docstring.as_deref(), Some("Formats a sum."));
+    assert_eq!(description.kind, SymbolKind::Function);
+}
+
+#[test]
+fn enum_item() {
+    let description = describe(&["Option", "Some"]);
+
+    assert_eq!(description.signature, "Option.Some(T)");
+    assert_eq!(description.docstring.as_deref(), Some("Contains a value."));
+    assert_eq!(description.kind, SymbolKind::EnumItem);
+
+    let description = describe(&["Option"]);
+
+    assert_eq!(description.signature, "pub enum Option[T]");
+    assert_eq!(description.docstring, None);
+}
@@ -0,0 +1,107 @@
+use std::path::{Path as StdPath, PathBuf};
+
+use stellar_database::{PackageData, Path, Symbol};
+use stellar_doc::{generate_documentation, render::render_signature, Options, Page};
+use stellar_driver::Session;
+use stellar_interner::{IdentifierId, PathId, DUMMY_PATH_ID};
+
+fn fixture(name: &str) -> PathBuf {
+    StdPath::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/src")
+        .join(name)
+}
+
+fn generate(options: Options) -> (Session, Vec<Page>) {
+    let mut session = Session::new();
+
+    let package = PackageData::alloc(
+        session.state_mut().db_mut(),
+        IdentifierId::from("geometry"),
+        DUMMY_PATH_ID,
+    );
+    let root_module = session
+        .parse_file(
+            package,
+            IdentifierId::from("geometry").into(),
+            PathId::from(fixture("package.sr")),
+        )
+        .unwrap();
+    let shapes = session
+        .parse_file(
+            package,
+            Path::new(vec![
+                IdentifierId::from("geometry"),
+                IdentifierId::from("shapes"),
+            ]),
+            PathId::from(fixture("shapes.sr")),
+        )
+        .unwrap();
+
+    let db = session.state_mut().db_mut();
+    package.set_root_module(db, root_module);
+    root_module.add_submodule(db, shapes);
+
+    session.lower();
+    session.collect_definitions();
+    session.resolve_imports();
+
+    assert!(session.state().diagnostics().is_ok());
+
+    let pages = generate_documentation(session.state(), session.hir(), package, options);
+
+    (session, pages)
+}
+
+#[test]
+fn public_items() {
+    let (_, pages) = generate(Options::default());
+
+    assert_eq!(
+        pages
+            .iter()
+            .map(|page| page.filename.as_str())
+            .collect::<Vec<_>>(),
+        ["geometry.md", "geometry.shapes.md"]
+    );
+    assert_eq!(pages[0].content, include_str!("snapshots/geometry.md"));
+    assert_eq!(
+        pages[1].content,
+        include_str!("snapshots/geometry.shapes.md")
+    );
+}
+
+#[test]
+fn private_items() {
+    let (_, pages) = generate(Options {
+        document_private_items: true,
+    });
+
+    assert!(pages[0]
+        .content
+        .contains("### <a id=\"helper\"></a>`helper`"));
+    assert!(pages[0]
+        .content
+        .contains("fun squared(x: float64): float64"));
+}
+
+#[test]
+fn render_enum_item_and_module() {
+    let (session, pages) = generate(Options::default());
+    let state = session.state();
+    let db = state.db();
+
+    let direction = pages[0]
+        .module
+        .symbol(db, IdentifierId::from("Direction"))
+        .to_enum();
+    let custom = direction.item(db, IdentifierId::from("Custom")).unwrap();
+
+    assert_eq!(
+        render_signature(Symbol::EnumItem(custom), state),
+        "Direction.Custom(float64)"
+    );
+    assert_eq!(
+        render_signature(Symbol::Module(pages[1].module), state),
+        "module geometry.shapes"
+    );
+}
@@ -0,0 +1,70 @@
+//! Defines diagnostics for discovering members of workspaces and for
+//! thresholds of function metrics.
+
+use stellar_diagnostics::define_diagnostics;
+use stellar_hir::{metrics::Metric, IdentifierAST};
+
+define_diagnostics! {
+    /// Diagnostic, that occurs when a directory listed in `members` of a
+    /// workspace doesn't contain a package manifest.
+    diagnostic(error) WorkspaceMemberNotFound(
+        self,
+        member: String,
+        manifest_path: String
+    ) {
+        code { "E017" }
+        message { format!("cannot find workspace member `{}`", self.member) }
+        labels {}
+        notes {
+            format!(
+                "note: the member is listed in `{}`, but its directory doesn't contain `package.toml`",
+                self.manifest_path
+            )
+        }
+    }
+
+    /// Diagnostic, that occurs when two members of a workspace have packages
+    /// with the same name.
+    diagnostic(error) DuplicateWorkspaceMember(
+        self,
+        package_name: String,
+        first_member: String,
+        second_member: String
+    ) {
+        code { "E018" }
+        message {
+            format!("package `{}` is defined by multiple workspace members", self.package_name)
+        }
+        labels {}
+        notes {
+            format!(
+                "note: both `{}` and `{}` define the package, so `{}` is skipped",
+                self.first_member, self.second_member, self.second_member
+            )
+        }
+    }
+
+    /// Diagnostic, that occurs when a metric of a function exceeds the maximum
+    /// passed to `stellar metrics`, e.g. `--max-depth 3`.
+    diagnostic(warning) MetricThresholdExceeded(
+        self,
+        function: IdentifierAST,
+        metric: Metric,
+        value: usize,
+        max: usize
+    ) {
+        code { "W015" }
+        message {
+            format!(
+                "function `{}` has a {} of {}, which exceeds the maximum of {}",
+                self.function.id, self.metric, self.value, self.max
+            )
+        }
+        labels {
+            primary { self.function.location => format!("{} of {}", self.metric, self.value) }
+        }
+        notes {
+            "help: consider splitting the function into smaller ones"
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! The registry of all diagnostics of the compiler, that `stellar explain`
+//! looks codes up in.
+//!
+//! The registry is built from `DIAGNOSTICS` constants, that are generated by
+//! [`define_diagnostics!`] together with the diagnostics themselves, so an
+//! explanation cannot refer to a code, that is not reported, and vice versa.
+//!
+//! [`define_diagnostics!`]: stellar_diagnostics::define_diagnostics
+
+use stellar_diagnostics::registry::DiagnosticInfo;
+
+/// Diagnostics of every crate, that defines them, in the order of the
+/// compiler phases.
+pub const REGISTRY: &[&[DiagnosticInfo]] = &[
+    stellar_parser::diagnostics::DIAGNOSTICS,
+    stellar_ast_lowering::DIAGNOSTICS,
+    stellar_typechecker::DIAGNOSTICS,
+    crate::diagnostics::DIAGNOSTICS,
+];
+
+/// Returns an iterator over all diagnostics of the compiler.
+pub fn all_diagnostics() -> impl Iterator<Item = &'static DiagnosticInfo> {
+    REGISTRY.iter().flat_map(|diagnostics| diagnostics.iter())
+}
+
+/// Returns the diagnostic with a given code, e.g. `"E001"`.
+#[must_use]
+pub fn explain(code: &str) -> Option<&'static DiagnosticInfo> {
+    all_diagnostics().find(|info| info.code.eq_ignore_ascii_case(code))
+}
@@ -0,0 +1,868 @@
+//! Drives compilation of Stellar packages.
+//!
+//! [`Session`] owns the compiler [`State`], modules parsed so far, their HIR
+//! and the diagnostics emitter, and runs compiler passes on them, so that
+//! commands of the CLI don't need to wire the passes together by hand:
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! use stellar_driver::Session;
+//!
+//! let mut session = Session::new();
+//!
+//! session.parse_package(Path::new("my_package")).unwrap();
+//! session.lower();
+//! session.collect_definitions();
+//! session.resolve_imports();
+//! session.emit_diagnostics();
+//! ```
+//!
+//! [`Pipeline`] runs the same passes in a fixed order and reports time each
+//! of them takes on every file, e.g. for embedders.
+//!
+//! Several packages can be checked together as a workspace, see
+//! [`Session::parse_workspace()`] and [`Pipeline::parse_workspace()`].
+//!
+//! Sizes and complexity of functions are reported by
+//! [`Session::function_metrics()`].
+
+//! Short descriptions of single symbols, e.g. for hovers in editors, are
+//! provided by [`describe::describe_symbol()`].
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png",
+    html_favicon_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png"
+)]
+#![cfg_attr(not(test), forbid(clippy::unwrap_used))]
+#![warn(missing_docs, clippy::dbg_macro)]
+#![warn(
+    // rustc lint groups https://doc.rust-lang.org/rustc/lints/groups.html
+    future_incompatible,
+    let_underscore,
+    nonstandard_style,
+    rust_2018_compatibility,
+    rust_2018_idioms,
+    rust_2021_compatibility,
+    unused,
+    // rustc allowed-by-default lints https://doc.rust-lang.org/rustc/lints/listing/allowed-by-default.html
+    macro_use_extern_crate,
+    meta_variable_misuse,
+    missing_abi,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    non_ascii_idents,
+    noop_method_call,
+    single_use_lifetimes,
+    trivial_casts,
+    trivial_numeric_casts,
+    unreachable_pub,
+    unsafe_op_in_unsafe_fn,
+    unused_crate_dependencies,
+    unused_import_braces,
+    unused_lifetimes,
+    unused_qualifications,
+    variant_size_differences,
+    // rustdoc lints https://doc.rust-lang.org/rustdoc/lints.html
+    rustdoc::broken_intra_doc_links,
+    rustdoc::private_intra_doc_links,
+    rustdoc::missing_crate_level_docs,
+    rustdoc::private_doc_tests,
+    rustdoc::invalid_codeblock_attributes,
+    rustdoc::invalid_rust_codeblocks,
+    rustdoc::bare_urls,
+    // clippy categories https://doc.rust-lang.org/clippy/
+    clippy::all,
+    clippy::correctness,
+    clippy::suspicious,
+    clippy::style,
+    clippy::complexity,
+    clippy::perf,
+    clippy::pedantic,
+    clippy::nursery,
+)]
+#![allow(
+    clippy::module_name_repetitions,
+    clippy::too_many_lines,
+    clippy::option_if_let_else
+)]
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Display},
+    fs, io,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use stellar_ast_lowering::{ConstantFolding, LowerToHir};
+use stellar_database::{Config, FunctionId, ModuleId, PackageData, PackageId, Path, State};
+use stellar_depgraph::{DependencyCycleError, DependencyGraph};
+use stellar_diagnostics::{
+    is_fatal_severity, DiagnosticsEmitter, DiagnosticsSnapshot, DiagnosticsStatus,
+};
+use stellar_filesystem::{
+    discover::{discover_package, DiscoverError, ModuleLayout},
+    path_resolver::PackagePathResolver,
+    source_map::InvalidUtf8Error,
+};
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::Module;
+use stellar_interner::{IdentifierId, PathId, DUMMY_PATH_ID};
+use stellar_manifest::{parse_manifest, PackageKind, TomlManifest};
+use stellar_parser::{
+    diagnostics::{FileTooLarge, InvalidUtf8File, ModuleFailedToLoad, TooManyTokens},
+    parse_module, read_and_parse_module, FileLimitExceeded, ParseResult,
+};
+use stellar_thir::body::Body;
+use stellar_typechecker::{
+    expression_analysis::{capture_analysis::CaptureAnalysis, lower_body::lower_all_bodies},
+    resolution::{collect_definitions::CollectDefinitions, resolve_imports::ResolveImports},
+    signature_analysis::{
+        check_entry_point::CheckEntryPoint, collect_signatures::CollectSignatures,
+    },
+};
+
+pub mod diagnostics;
+pub mod explain;
+pub mod metrics;
+pub mod pipeline;
+pub mod workspace;
+
+pub use pipeline::{Phase, PhaseEvent, Pipeline};
+pub use workspace::{MemberReport, WorkspaceMember};
+
+/// An error, that stops compilation.
+///
+/// Errors in the source code itself are reported as diagnostics, and
+/// [`CompileError::ParseFailed`] or [`CompileError::CheckFailed`] only tells,
+/// how many of them are fatal.
+#[derive(Debug)]
+pub enum CompileError {
+    /// A file or a directory cannot be read or written.
+    Io {
+        /// The path of the file or the directory.
+        path: PathBuf,
+
+        /// The underlying error.
+        source: io::Error,
+    },
+
+    /// The manifest file of a package cannot be parsed.
+    InvalidManifest {
+        /// The path of the manifest file.
+        path: PathBuf,
+
+        /// The error message of the manifest parser.
+        message: String,
+    },
+
+    /// The root manifest of a workspace doesn't have a `[workspace]` section.
+    MissingWorkspace(PathBuf),
+
+    /// The source directory of a package doesn't contain `package.sr`.
+    MissingRootModule(PathBuf),
+
+    /// The name of a source file or a directory is not a valid module name.
+    InvalidModuleName(PathBuf),
+
+    /// Symbolic links in the source directory of a package form a cycle.
+    SymlinkCycle(PathBuf),
+
+    /// Packages depend on each other.
+    DependencyCycle(DependencyCycleError),
+
+    /// Source code contains syntax errors.
+    ParseFailed {
+        /// The number of fatal diagnostics.
+        error_count: usize,
+    },
+
+    /// Source code is parsed, but contains semantic errors.
+    CheckFailed {
+        /// The number of fatal diagnostics.
+        error_count: usize,
+    },
+}
+
+impl CompileError {
+    /// Returns the exit code of the compiler for the error: `1` for errors in
+    /// the source code and `2` for errors in the environment, e.g. missing
+    /// files.
+    #[inline]
+    #[must_use]
+    pub const fn exit_code(&self) -> i32 {
+        match self {
+            Self::ParseFailed { .. } | Self::CheckFailed { .. } => 1,
+            Self::Io { .. }
+            | Self::InvalidManifest { .. }
+            | Self::MissingWorkspace(_)
+            | Self::MissingRootModule(_)
+            | Self::InvalidModuleName(_)
+            | Self::SymlinkCycle(_)
+            | Self::DependencyCycle(_) => 2,
+        }
+    }
+}
+
+impl Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "cannot access {}: {source}", path.display()),
+            Self::InvalidManifest { path, message } => write!(
+                f,
+                "cannot parse the manifest file {}: {message}",
+                path.display()
+            ),
+            Self::MissingWorkspace(path) => write!(
+                f,
+                "the manifest file {} doesn't have a `[workspace]` section",
+                path.display()
+            ),
+            Self::MissingRootModule(directory) => {
+                write!(f, "cannot find `package.sr` in {}", directory.display())
+            }
+            Self::InvalidModuleName(path) => {
+                write!(f, "name of {} is not a valid module name", path.display())
+            }
+            Self::SymlinkCycle(path) => {
+                write!(f, "symbolic link cycle detected at {}", path.display())
+            }
+            Self::DependencyCycle(error) => Display::fmt(error, f),
+            Self::ParseFailed { error_count } => write!(
+                f,
+                "cannot parse the source code due to {}",
+                errors(*error_count)
+            ),
+            Self::CheckFailed { error_count } => write!(
+                f,
+                "cannot check the source code due to {}",
+                errors(*error_count)
+            ),
+        }
+    }
+}
+
+impl Error for CompileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<DiscoverError> for CompileError {
+    fn from(error: DiscoverError) -> Self {
+        match error {
+            DiscoverError::Io { path, source } => Self::Io { path, source },
+            DiscoverError::MissingRootModule(directory) => Self::MissingRootModule(directory),
+            DiscoverError::InvalidModuleName(path) => Self::InvalidModuleName(path),
+            DiscoverError::SymlinkCycle(path) => Self::SymlinkCycle(path),
+        }
+    }
+}
+
+fn errors(count: usize) -> String {
+    if count == 1 {
+        "1 previous error".to_owned()
+    } else {
+        format!("{count} previous errors")
+    }
+}
+
+/// A package, that is read, but not parsed yet.
+struct PackageSources {
+    directory: PathBuf,
+    manifest: TomlManifest,
+
+    /// Names of dependencies in the package and names of their packages.
+    dependencies: Vec<(String, String)>,
+}
+
+fn read_manifest(directory: &std::path::Path) -> Result<TomlManifest, CompileError> {
+    let manifest_path = PackagePathResolver::new(directory).manifest();
+
+    let manifest = fs::read_to_string(&manifest_path).map_err(|source| CompileError::Io {
+        path: manifest_path.clone(),
+        source,
+    })?;
+
+    parse_manifest(manifest).map_err(|message| CompileError::InvalidManifest {
+        path: manifest_path,
+        message,
+    })
+}
+
+/// Reads manifests of a package and of all of its local dependencies and
+/// returns the name of the package.
+fn load_package_sources(
+    directory: &std::path::Path,
+    additional_dependencies: &[(String, PathBuf)],
+    graph: &mut DependencyGraph,
+    sources: &mut BTreeMap<String, PackageSources>,
+) -> Result<String, CompileError> {
+    let manifest = read_manifest(directory)?;
+    let name = manifest.package.name.clone();
+
+    if sources.contains_key(&name) {
+        return Ok(name);
+    }
+
+    graph.add_package(name.clone());
+
+    let local_dependencies = manifest
+        .dependencies
+        .iter()
+        .flatten()
+        .filter_map(|(dependency_name, dependency)| {
+            Some((
+                dependency_name.clone(),
+                directory.join(dependency.path.as_ref()?),
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    sources.insert(
+        name.clone(),
+        PackageSources {
+            directory: directory.to_owned(),
+            manifest,
+            dependencies: vec![],
+        },
+    );
+
+    for (dependency_name, dependency_directory) in
+        local_dependencies.iter().chain(additional_dependencies)
+    {
+        let dependency = load_package_sources(dependency_directory, &[], graph, sources)?;
+
+        graph.add_dependency(name.clone(), dependency.clone());
+        sources
+            .get_mut(&name)
+            .expect("the package is added above")
+            .dependencies
+            .push((dependency_name.clone(), dependency));
+    }
+
+    Ok(name)
+}
+
+/// A compilation session.
+///
+/// Modules are parsed into the session with [`Session::parse_file()`] or
+/// [`Session::parse_package()`] and are kept there until they are lowered
+/// with [`Session::lower()`]. Other passes work on HIR of all modules lowered
+/// so far.
+#[allow(missing_debug_implementations)] // `State` doesn't implement `Debug`
+pub struct Session {
+    state: State,
+
+    /// Modules, that are parsed, but not lowered yet.
+    parsed_modules: Vec<ParseResult>,
+
+    /// HIR of lowered modules.
+    hir: FxHashMap<ModuleId, Module>,
+
+    /// Typed bodies of functions.
+    thir: FxHashMap<FunctionId, Body>,
+
+    /// Kinds of packages, that are parsed with their manifests.
+    package_kinds: FxHashMap<PackageId, PackageKind>,
+
+    diagnostics_emitter: DiagnosticsEmitter,
+
+    /// The number of diagnostics of every file, that are already emitted.
+    emitted_diagnostics: FxHashMap<PathId, usize>,
+
+    /// Time it took to read and parse every file, that is not reported by
+    /// a [`Pipeline`] yet.
+    parse_durations: Vec<(PathId, Duration)>,
+}
+
+impl Default for Session {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Session {
+    /// Creates a new empty session.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::from_state(State::new())
+    }
+
+    /// Creates a new session with a given compiler state.
+    #[inline]
+    #[must_use]
+    pub fn from_state(state: State) -> Self {
+        Self {
+            state,
+            parsed_modules: vec![],
+            hir: FxHashMap::default(),
+            thir: FxHashMap::default(),
+            package_kinds: FxHashMap::default(),
+            diagnostics_emitter: DiagnosticsEmitter::new(),
+            emitted_diagnostics: FxHashMap::default(),
+            parse_durations: vec![],
+        }
+    }
+
+    /// Sets the compiler configuration.
+    #[inline]
+    #[must_use]
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.state = std::mem::take(&mut self.state).with_config(config);
+        self
+    }
+
+    /// Sets the emitter used by [`Session::emit_diagnostics()`].
+    #[inline]
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // false-positive clippy lint
+    pub fn with_diagnostics_emitter(mut self, diagnostics_emitter: DiagnosticsEmitter) -> Self {
+        self.diagnostics_emitter = diagnostics_emitter;
+        self
+    }
+
+    /// Returns the compiler state.
+    #[inline]
+    #[must_use]
+    pub const fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Returns the compiler state.
+    #[inline]
+    #[must_use]
+    pub const fn state_mut(&mut self) -> &mut State {
+        &mut self.state
+    }
+
+    /// Returns modules, that are parsed, but not lowered yet.
+    #[inline]
+    #[must_use]
+    pub fn parsed_modules(&self) -> &[ParseResult] {
+        &self.parsed_modules
+    }
+
+    /// Returns HIR of all modules lowered so far.
+    #[inline]
+    #[must_use]
+    pub const fn hir(&self) -> &FxHashMap<ModuleId, Module> {
+        &self.hir
+    }
+
+    /// Returns typed bodies of functions lowered so far.
+    #[inline]
+    #[must_use]
+    pub const fn thir(&self) -> &FxHashMap<FunctionId, Body> {
+        &self.thir
+    }
+
+    /// Returns `true` if an error is reported and [`Config::fail_fast()`] is
+    /// set, so no more files or phases should be processed.
+    #[inline]
+    #[must_use]
+    pub fn should_stop_early(&self) -> bool {
+        self.state.config().fail_fast() && self.state.diagnostics().is_fatal()
+    }
+
+    /// Reads and parses a module of a given package.
+    ///
+    /// A file, that is not valid UTF-8 or exceeds limits of the config, is
+    /// reported as a diagnostic and is replaced with an empty module.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read.
+    pub fn parse_file(
+        &mut self,
+        package: PackageId,
+        path: Path,
+        filepath: PathId,
+    ) -> Result<ModuleId, CompileError> {
+        let now = Instant::now();
+
+        let parsed = match read_and_parse_module(&mut self.state, package, path.clone(), filepath) {
+            Ok(parsed) => parsed,
+            Err(error)
+                if InvalidUtf8Error::from_io_error(&error).is_some()
+                    || FileLimitExceeded::from_io_error(&error).is_some() =>
+            {
+                let module = self.add_unloaded_module(package, path, filepath, &error);
+                self.parse_durations.push((filepath, now.elapsed()));
+
+                return Ok(module);
+            }
+            Err(source) => {
+                return Err(CompileError::Io {
+                    path: filepath.as_path().to_owned(),
+                    source,
+                });
+            }
+        };
+        let module = parsed.module();
+
+        self.parsed_modules.push(parsed);
+        self.parse_durations.push((filepath, now.elapsed()));
+
+        Ok(module)
+    }
+
+    /// Reads the manifest of a package in a given directory and parses its
+    /// source files: `src/package.sr` becomes the root module of the package
+    /// and other `.sr` files in `src` become its submodules.
+    ///
+    /// Dependencies of the package are not parsed, see
+    /// [`Session::parse_package_with_dependencies()`].
+    ///
+    /// # Errors
+    /// Returns an error if the manifest or the root module cannot be read,
+    /// or if there is no `src/package.sr`. Submodules, that cannot be read,
+    /// are reported as diagnostics and replaced with empty placeholders.
+    pub fn parse_package(
+        &mut self,
+        directory: &std::path::Path,
+    ) -> Result<PackageId, CompileError> {
+        let manifest = read_manifest(directory)?;
+
+        self.parse_package_source_files(directory, &manifest)
+    }
+
+    /// Parses a package in a given directory (see [`Session::parse_package()`])
+    /// together with its local dependencies, so that the package can import
+    /// public items of them.
+    ///
+    /// Dependencies are taken from `[dependencies]` sections of manifests
+    /// (only ones with `path`, which is relative to the package directory)
+    /// and from `dependencies` - pairs of names and directories, that are
+    /// added to dependencies of the package. Every package is parsed after
+    /// all of its dependencies.
+    ///
+    /// # Errors
+    /// Returns an error if some of the packages cannot be read (see
+    /// [`Session::parse_package()`]) or if packages depend on each other.
+    pub fn parse_package_with_dependencies(
+        &mut self,
+        directory: &std::path::Path,
+        dependencies: &[(String, PathBuf)],
+    ) -> Result<PackageId, CompileError> {
+        let mut graph = DependencyGraph::new();
+        let mut sources = BTreeMap::new();
+
+        let primary_package =
+            load_package_sources(directory, dependencies, &mut graph, &mut sources)?;
+
+        let order = graph
+            .topological_sort()
+            .map_err(CompileError::DependencyCycle)?;
+        let packages = self.parse_packages_in_order(&order, &sources)?;
+
+        Ok(packages[&primary_package])
+    }
+
+    /// Parses packages, so that every package is parsed after all of its
+    /// dependencies, and returns them by their names.
+    fn parse_packages_in_order(
+        &mut self,
+        order: &[String],
+        sources: &BTreeMap<String, PackageSources>,
+    ) -> Result<BTreeMap<String, PackageId>, CompileError> {
+        let mut packages = BTreeMap::new();
+
+        for name in order {
+            let source = &sources[name];
+            let package = self.parse_package_source_files(&source.directory, &source.manifest)?;
+
+            for (dependency_name, dependency) in &source.dependencies {
+                package.add_dependency(
+                    self.state.db_mut(),
+                    IdentifierId::from(dependency_name.as_str()),
+                    packages[dependency],
+                );
+            }
+
+            packages.insert(name.clone(), package);
+        }
+
+        Ok(packages)
+    }
+
+    fn parse_package_source_files(
+        &mut self,
+        directory: &std::path::Path,
+        manifest: &TomlManifest,
+    ) -> Result<PackageId, CompileError> {
+        let layout = discover_package(directory)?;
+
+        let package_name = IdentifierId::from(manifest.package.name.as_str());
+        let package =
+            PackageData::alloc(self.state.db_mut(), package_name, PathId::from(directory));
+        self.package_kinds.insert(package, manifest.package.kind());
+
+        let root_module = self.parse_file(package, package_name.into(), layout.root_module)?;
+        package.set_root_module(self.state.db_mut(), root_module);
+
+        self.parse_submodules(
+            package,
+            root_module,
+            &Path::from(package_name),
+            &layout.submodules,
+        )?;
+
+        Ok(package)
+    }
+
+    /// Parses discovered submodules of a given module. Directories without
+    /// source files are parsed as empty modules.
+    fn parse_submodules(
+        &mut self,
+        package: PackageId,
+        parent: ModuleId,
+        parent_path: &Path,
+        submodules: &[ModuleLayout],
+    ) -> Result<(), CompileError> {
+        for submodule in submodules {
+            if self.should_stop_early() {
+                return Ok(());
+            }
+
+            let path = parent_path.clone() + IdentifierId::from(submodule.name.as_str());
+            let now = Instant::now();
+
+            let module = match (submodule.filepath, submodule.directory) {
+                (Some(filepath), _) => {
+                    match read_and_parse_module(&mut self.state, package, path.clone(), filepath) {
+                        Ok(parsed) => {
+                            let module = parsed.module();
+
+                            self.parsed_modules.push(parsed);
+                            module
+                        }
+                        Err(error) => {
+                            self.add_unloaded_module(package, path.clone(), filepath, &error)
+                        }
+                    }
+                }
+                (None, Some(directory)) => self.add_empty_module(package, path.clone(), directory),
+                (None, None) => unreachable!("discovered modules have a file or a directory"),
+            };
+
+            self.parse_durations
+                .push((module.filepath(self.state.db()), now.elapsed()));
+
+            parent.add_submodule(self.state.db_mut(), module);
+
+            self.parse_submodules(package, module, &path, &submodule.submodules)?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds a module without source code, e.g. a directory without a source
+    /// file.
+    fn add_empty_module(&mut self, package: PackageId, path: Path, filepath: PathId) -> ModuleId {
+        let result = parse_module(&mut self.state, package, path, filepath, "");
+        let module = result.module();
+
+        self.parsed_modules.push(result);
+        module
+    }
+
+    /// Reports a module, source file of which cannot be read, is not valid
+    /// UTF-8 or exceeds limits of the config, and adds an empty placeholder
+    /// instead of it, so that the rest of the package can still be checked.
+    fn add_unloaded_module(
+        &mut self,
+        package: PackageId,
+        path: Path,
+        filepath: PathId,
+        error: &io::Error,
+    ) -> ModuleId {
+        if let Some(error) = InvalidUtf8Error::from_io_error(error) {
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(InvalidUtf8File::new(error.path, error.offset));
+        } else if let Some(error) = FileLimitExceeded::from_io_error(error) {
+            match *error {
+                FileLimitExceeded::FileSize {
+                    filepath,
+                    size,
+                    max_file_size,
+                } => self
+                    .state
+                    .diagnostics_mut()
+                    .add_diagnostic(FileTooLarge::new(filepath, size, max_file_size)),
+                FileLimitExceeded::TokenCount {
+                    filepath,
+                    max_token_count,
+                } => self
+                    .state
+                    .diagnostics_mut()
+                    .add_diagnostic(TooManyTokens::new(filepath, max_token_count)),
+            }
+        } else {
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(ModuleFailedToLoad::new(
+                    path.to_string(),
+                    filepath,
+                    error.to_string(),
+                ));
+        }
+
+        let module = self.add_empty_module(package, path, filepath);
+        module.mark_load_failed(self.state.db_mut());
+        module
+    }
+
+    /// Lowers all parsed modules into HIR, fills captured variables of
+    /// lambdas and returns HIR of them.
+    pub fn lower(&mut self) -> &FxHashMap<ModuleId, Module> {
+        let mut hir =
+            LowerToHir::run_all(&mut self.state, std::mem::take(&mut self.parsed_modules));
+        CaptureAnalysis::run_all(&mut hir);
+        self.hir.extend(hir);
+        &self.hir
+    }
+
+    /// Folds constants in HIR of all lowered modules.
+    pub fn fold_constants(&mut self) {
+        ConstantFolding::run_all(&mut self.state, &mut self.hir);
+    }
+
+    /// Collects definitions of all lowered modules and generic parameters of
+    /// their signatures.
+    pub fn collect_definitions(&mut self) {
+        CollectDefinitions::run_all(&mut self.state, &self.hir);
+        CollectSignatures::run_all(&mut self.state, &self.hir);
+    }
+
+    /// Resolves imports of all lowered modules. Definitions must be collected
+    /// before (see [`Session::collect_definitions()`]).
+    pub fn resolve_imports(&mut self) {
+        ResolveImports::run_all(&mut self.state, &self.hir);
+    }
+
+    /// Returns the kind of a given package, if it is parsed with its manifest
+    /// (see [`Session::parse_package()`]).
+    #[must_use]
+    pub fn package_kind(&self, package: PackageId) -> Option<PackageKind> {
+        self.package_kinds.get(&package).copied()
+    }
+
+    /// Checks the `main` function of a given package, if it is a binary
+    /// package. Definitions must be collected before (see
+    /// [`Session::collect_definitions()`]).
+    pub fn check_entry_point(&mut self, package: PackageId) {
+        if self.package_kind(package) == Some(PackageKind::Binary) {
+            CheckEntryPoint::run(&mut self.state, package);
+        }
+    }
+
+    /// Infers types in bodies of all functions and lowers them into THIR.
+    /// Imports must be resolved before (see [`Session::resolve_imports()`]).
+    pub fn lower_bodies(&mut self) -> &FxHashMap<FunctionId, Body> {
+        let thir = lower_all_bodies(&mut self.state, &self.hir);
+        self.thir.extend(thir);
+        &self.thir
+    }
+
+    /// Emits diagnostics, that are not emitted yet, and returns the status of
+    /// all diagnostics reported in the session.
+    pub fn emit_diagnostics(&mut self) -> DiagnosticsStatus {
+        let filepaths = self
+            .state
+            .diagnostics()
+            .files_with_diagnostics()
+            .chain([DUMMY_PATH_ID])
+            .collect::<Vec<_>>();
+
+        self.emit_diagnostics_in(&filepaths);
+
+        if self.state.diagnostics().is_fatal() {
+            DiagnosticsStatus::Fatal
+        } else {
+            DiagnosticsStatus::Ok
+        }
+    }
+
+    /// Emits diagnostics in given files, that are not emitted yet. Diagnostics
+    /// without locations are emitted for [`DUMMY_PATH_ID`].
+    pub fn emit_diagnostics_in(&mut self, filepaths: &[PathId]) {
+        let limits = filepaths
+            .iter()
+            .map(|&filepath| (filepath, usize::MAX))
+            .collect::<Vec<_>>();
+
+        self.emit_diagnostics_up_to(&limits);
+    }
+
+    /// Emits diagnostics in given files, that are not emitted yet, up to
+    /// given indices in [`Diagnostics::for_file()`] of every file.
+    ///
+    /// [`Diagnostics::for_file()`]: stellar_diagnostics::Diagnostics::for_file
+    pub(crate) fn emit_diagnostics_up_to(&mut self, limits: &[(PathId, usize)]) {
+        let diagnostics = self.state.diagnostics();
+        let mut new_diagnostics = vec![];
+
+        for &(filepath, limit) in limits {
+            let file_diagnostics = diagnostics.for_file(filepath);
+            let end = limit.min(file_diagnostics.len());
+            let emitted = self.emitted_diagnostics.entry(filepath).or_default();
+
+            new_diagnostics
+                .extend_from_slice(file_diagnostics.get(*emitted..end).unwrap_or_default());
+
+            *emitted = end.max(*emitted);
+        }
+
+        let snapshot =
+            DiagnosticsSnapshot::new(diagnostics.files_involved.clone(), new_diagnostics);
+
+        self.diagnostics_emitter
+            .emit_global_diagnostics_with_source_map(&snapshot, self.state.source_map_mut());
+    }
+
+    /// Returns the number of fatal diagnostics reported in the session.
+    #[must_use]
+    pub fn error_count(&self) -> usize {
+        self.state
+            .diagnostics()
+            .iter()
+            .filter(|diagnostic| is_fatal_severity(diagnostic.severity))
+            .count()
+    }
+
+    /// Emits diagnostics reported while parsing and returns
+    /// [`CompileError::ParseFailed`] if some of them are fatal.
+    ///
+    /// # Errors
+    /// Returns an error if there are fatal diagnostics.
+    pub fn finish_parsing(&mut self) -> Result<(), CompileError> {
+        match self.emit_diagnostics() {
+            DiagnosticsStatus::Ok => Ok(()),
+            DiagnosticsStatus::Fatal => Err(CompileError::ParseFailed {
+                error_count: self.error_count(),
+            }),
+        }
+    }
+
+    /// Emits diagnostics reported while checking lowered modules and returns
+    /// [`CompileError::CheckFailed`] if some of them are fatal.
+    ///
+    /// # Errors
+    /// Returns an error if there are fatal diagnostics.
+    pub fn finish_checking(&mut self) -> Result<(), CompileError> {
+        match self.emit_diagnostics() {
+            DiagnosticsStatus::Ok => Ok(()),
+            DiagnosticsStatus::Fatal => Err(CompileError::CheckFailed {
+                error_count: self.error_count(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Computing sizes and complexity of functions of a package, e.g. for CI
+//! gates like "no function with more than 50 statements".
+//!
+//! Metrics are computed over HIR of functions (see [`FunctionMetrics`]), so
+//! modules must be lowered and definitions collected before. Methods are not
+//! stored in [`HirStorage`], so only functions, that are module items, are
+//! reported.
+//!
+//! [`HirStorage`]: stellar_database::HirStorage
+
+use stellar_database::{FunctionId, PackageId, Symbol};
+use stellar_hir::{
+    metrics::{FunctionMetrics, Metric},
+    IdentifierAST,
+};
+
+use crate::{diagnostics::MetricThresholdExceeded, Session};
+
+/// Metrics of a function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionReport {
+    /// The function.
+    pub function: FunctionId,
+
+    /// The full path of the function, e.g. `shapes.circle.area`.
+    pub path: String,
+
+    /// The name of the function.
+    pub name: IdentifierAST,
+
+    /// Metrics of the function.
+    pub metrics: FunctionMetrics,
+}
+
+/// The maximum allowed value of a metric, e.g. `--max-depth 3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MetricThreshold {
+    /// The metric.
+    pub metric: Metric,
+
+    /// The maximum allowed value of the metric.
+    pub max: usize,
+}
+
+impl Session {
+    /// Returns metrics of all functions of a given package sorted by their
+    /// paths. Definitions must be collected before (see
+    /// [`Session::collect_definitions()`]).
+    #[must_use]
+    pub fn function_metrics(&self, package: PackageId) -> Vec<FunctionReport> {
+        let db = self.state.db();
+        let hir = self.state.hir();
+
+        let mut reports = hir
+            .iter()
+            .filter_map(|(symbol, _)| match symbol {
+                Symbol::Function(function) if function.package() == package => Some(function),
+                _ => None,
+            })
+            .filter_map(|function| {
+                let symbol = Symbol::Function(function);
+
+                Some(FunctionReport {
+                    function,
+                    path: symbol.path(db).to_string(),
+                    name: symbol.name(db),
+                    metrics: hir.metrics(function)?,
+                })
+            })
+            .collect::<Vec<_>>();
+        reports.sort_by(|a, b| a.path.cmp(&b.path));
+
+        reports
+    }
+
+    /// Reports a warning for every metric of a function, that exceeds its
+    /// threshold, and returns the number of reported warnings.
+    pub fn check_metric_thresholds(
+        &mut self,
+        reports: &[FunctionReport],
+        thresholds: &[MetricThreshold],
+    ) -> usize {
+        let mut violations = 0;
+
+        for report in reports {
+            for threshold in thresholds {
+                let value = report.metrics.get(threshold.metric);
+
+                if value > threshold.max {
+                    self.state
+                        .diagnostics_mut()
+                        .add_diagnostic(MetricThresholdExceeded::new(
+                            report.name,
+                            threshold.metric,
+                            value,
+                            threshold.max,
+                        ));
+                    violations += 1;
+                }
+            }
+        }
+
+        violations
+    }
+}
@@ -0,0 +1,854 @@
+//! Defines [`Pipeline`] - a typed sequence of compiler phases.
+//!
+//! Pipelines are built on top of [`Session`] and report every phase run on
+//! every file to observers, e.g. to show timings in the CLI or progress in
+//! editors:
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! use stellar_database::State;
+//! use stellar_driver::Pipeline;
+//!
+//! let mut checked = Pipeline::new(State::new())
+//!     .on_phase(|event| {
+//!         println!("{} `{}` in {:?}", event.phase, event.filepath, event.duration);
+//!     })
+//!     .parse_package(Path::new("my_package"), &[])?
+//!     .lower()
+//!     .collect_definitions()
+//!     .check();
+//!
+//! checked.finish()?;
+//!
+//! println!("checked {} modules", checked.hir().len());
+//! # Ok::<(), stellar_driver::CompileError>(())
+//! ```
+//!
+//! Every phase returns a handle of the next stage, so phases cannot be run
+//! out of order, e.g. imports cannot be resolved before definitions are
+//! collected. All handles dereference to [`Artifacts`], so artifacts of
+//! earlier phases stay accessible.
+//!
+//! With [`Config::fail_fast()`] set, phases are not run on the remaining
+//! files once an error is reported, see [`Session::should_stop_early()`].
+//!
+//! Workspaces are parsed with [`Pipeline::parse_workspace()`]. Phases are
+//! run on every file of all members once, and diagnostics are emitted per
+//! member with [`Checked::finish_member()`].
+//!
+//! [`Config::fail_fast()`]: stellar_database::Config::fail_fast
+
+use std::{
+    fmt::{self, Display},
+    ops::{Deref, DerefMut, Range},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use stellar_ast_lowering::{ConstantFolding, LowerToHir};
+use stellar_database::{
+    FunctionId, ModuleId, PackageData, PackageId, State, Symbol, DUMMY_MODULE_ID,
+};
+use stellar_diagnostics::{is_fatal_severity, DiagnosticsEmitter};
+use stellar_fx_hash::{FxHashMap, FxHashSet};
+use stellar_hir::Module;
+use stellar_interner::{PathId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_manifest::PackageKind;
+use stellar_thir::body::Body;
+use stellar_typechecker::{
+    expression_analysis::{
+        capture_analysis::CaptureAnalysis, check_assignments::CheckAssignments,
+        check_break_and_continue::CheckBreakAndContinue, check_let_patterns::CheckLetPatterns,
+        check_match_exhaustiveness::CheckMatchExhaustiveness, lower_body::lower_body,
+        resolve_enum_items::ResolveEnumItems,
+    },
+    resolution::{
+        check_dead_code::CheckDeadCode, check_naming_conventions::CheckNamingConventions,
+        check_type_paths::CheckTypePaths, collect_definitions::CollectDefinitions,
+        resolve_imports::ResolveImports,
+    },
+    signature_analysis::{
+        check_generic_arguments::CheckGenericArguments,
+        check_generic_parameters::CheckGenericParameters,
+        check_interface_conformance::CheckInterfaceConformance,
+        check_object_safety::CheckObjectSafety, check_private_in_public::CheckPrivateInPublic,
+        collect_signatures::CollectSignatures,
+    },
+};
+
+use crate::{CompileError, MemberReport, Session, WorkspaceMember};
+
+/// A compiler phase run by a [`Pipeline`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Reading and parsing source files, see [`Pipeline::parse()`].
+    Parse,
+
+    /// Lowering ASTs into HIR and collecting variables captured by lambdas,
+    /// see [`Parsed::lower()`].
+    Lower,
+
+    /// Folding constant expressions in HIR, see [`Lowered::fold_constants()`].
+    FoldConstants,
+
+    /// Collecting definitions and generic parameters of their signatures, see
+    /// [`Lowered::collect_definitions()`].
+    CollectDefinitions,
+
+    /// Resolving imports, see [`DefinitionsCollected::check()`].
+    ResolveImports,
+
+    /// Checking types in signatures of module items, see
+    /// [`DefinitionsCollected::check()`].
+    CheckSignatures,
+
+    /// Checking the `main` function of a binary package, see
+    /// [`DefinitionsCollected::check()`]. Reported only for the root module.
+    CheckEntryPoint,
+
+    /// Inferring types in function bodies and lowering them into THIR, see
+    /// [`DefinitionsCollected::check()`].
+    LowerBodies,
+
+    /// Checking statements and expressions in function bodies, that don't
+    /// depend on types, e.g. targets of assignments, see
+    /// [`DefinitionsCollected::check()`].
+    CheckBodies,
+
+    /// Reporting names, that don't follow naming conventions, see
+    /// [`DefinitionsCollected::check()`].
+    Lint,
+
+    /// Reporting unused module items of a package, see
+    /// [`DefinitionsCollected::check()`]. Reported only for the root module,
+    /// because items are used across modules of the package.
+    CheckDeadCode,
+}
+
+impl Phase {
+    /// Returns the name of the phase, e.g. `collect definitions`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Parse => "parse",
+            Self::Lower => "lower",
+            Self::FoldConstants => "fold constants",
+            Self::CollectDefinitions => "collect definitions",
+            Self::ResolveImports => "resolve imports",
+            Self::CheckSignatures => "check signatures",
+            Self::CheckEntryPoint => "check entry point",
+            Self::LowerBodies => "lower bodies",
+            Self::CheckBodies => "check bodies",
+            Self::Lint => "lint",
+            Self::CheckDeadCode => "check dead code",
+        }
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Result of running a phase on a single file, that is passed to observers
+/// registered with [`Pipeline::on_phase()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseEvent {
+    /// The phase.
+    pub phase: Phase,
+
+    /// The file, that the phase was run on. For modules without source
+    /// files this is the path of their directory.
+    pub filepath: PathId,
+
+    /// Time it took to run the phase on the file.
+    pub duration: Duration,
+
+    /// The number of diagnostics reported in the file by the phase,
+    /// including ones without locations, e.g. a missing entry point.
+    pub diagnostics: usize,
+}
+
+type Observer = Box<dyn FnMut(PhaseEvent)>;
+
+/// A builder of a compilation, that hasn't parsed anything yet. See the
+/// [module level documentation](self) for an example.
+#[allow(missing_debug_implementations)] // `Session` doesn't implement `Debug`
+pub struct Pipeline {
+    session: Session,
+    observers: Vec<Observer>,
+    library: bool,
+}
+
+impl Pipeline {
+    /// Creates a new pipeline with a given compiler state.
+    #[inline]
+    #[must_use]
+    pub fn new(state: State) -> Self {
+        Self {
+            session: Session::from_state(state),
+            observers: vec![],
+            library: false,
+        }
+    }
+
+    /// Sets the emitter used by `finish()` methods of stages.
+    #[inline]
+    #[must_use]
+    pub fn with_diagnostics_emitter(mut self, diagnostics_emitter: DiagnosticsEmitter) -> Self {
+        self.session = self.session.with_diagnostics_emitter(diagnostics_emitter);
+        self
+    }
+
+    /// Treats the parsed package as a library, so that
+    /// [`DefinitionsCollected::check()`] doesn't require the `main` function.
+    #[inline]
+    #[must_use]
+    pub const fn library(mut self, library: bool) -> Self {
+        self.library = library;
+        self
+    }
+
+    /// Registers an observer, that is called every time a phase is run on
+    /// a file.
+    #[inline]
+    #[must_use]
+    pub fn on_phase(mut self, observer: impl FnMut(PhaseEvent) + 'static) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// Parses standalone source files as modules of an anonymous package.
+    ///
+    /// # Errors
+    /// Returns an error if some of the files cannot be read or if there are
+    /// fatal parse diagnostics (they are emitted before).
+    pub fn parse(mut self, filepaths: &[PathId]) -> Result<Parsed, CompileError> {
+        let package = PackageData::alloc(
+            self.session.state_mut().db_mut(),
+            DUMMY_IDENTIFIER_ID,
+            DUMMY_PATH_ID,
+        );
+
+        for &filepath in filepaths {
+            if self.session.should_stop_early() {
+                break;
+            }
+
+            self.session
+                .parse_file(package, DUMMY_IDENTIFIER_ID.into(), filepath)?;
+        }
+
+        self.finish_parsing(package)
+    }
+
+    /// Parses a package in a given directory together with its local
+    /// dependencies, see [`Session::parse_package_with_dependencies()`].
+    ///
+    /// # Errors
+    /// Returns an error if some of the packages cannot be read, if they
+    /// depend on each other or if there are fatal parse diagnostics (they
+    /// are emitted before).
+    pub fn parse_package(
+        mut self,
+        directory: &std::path::Path,
+        dependencies: &[(String, PathBuf)],
+    ) -> Result<Parsed, CompileError> {
+        let package = self
+            .session
+            .parse_package_with_dependencies(directory, dependencies)?;
+
+        self.finish_parsing(package)
+    }
+
+    /// Parses all members of a workspace in a given directory, see
+    /// [`Session::parse_workspace()`].
+    ///
+    /// Unlike [`Pipeline::parse_package()`], fatal parse diagnostics are not
+    /// emitted here and don't stop the compilation, so that other members
+    /// can still be checked. They are emitted per member with
+    /// [`Checked::finish_member()`]. Only diagnostics without locations, e.g.
+    /// members, that cannot be found, are emitted here.
+    ///
+    /// # Errors
+    /// Returns an error if the workspace or some of the packages cannot be
+    /// read or if packages depend on each other.
+    pub fn parse_workspace(mut self, root: &std::path::Path) -> Result<Parsed, CompileError> {
+        let members = self.session.parse_workspace(root)?;
+
+        let package = match members.first() {
+            Some(member) => member.package,
+            None => PackageData::alloc(
+                self.session.state_mut().db_mut(),
+                DUMMY_IDENTIFIER_ID,
+                DUMMY_PATH_ID,
+            ),
+        };
+
+        let mut artifacts = self.into_artifacts(package);
+        artifacts.members = members;
+        artifacts.session.emit_diagnostics_in(&[DUMMY_PATH_ID]);
+
+        Ok(Parsed(artifacts))
+    }
+
+    fn finish_parsing(self, package: PackageId) -> Result<Parsed, CompileError> {
+        let mut artifacts = self.into_artifacts(package);
+
+        artifacts.session.finish_parsing()?;
+
+        Ok(Parsed(artifacts))
+    }
+
+    /// Reports parsed files to observers and returns artifacts of parsing.
+    fn into_artifacts(mut self, package: PackageId) -> Artifacts {
+        let parse_durations = std::mem::take(&mut self.session.parse_durations);
+
+        let mut artifacts = Artifacts {
+            session: self.session,
+            observers: self.observers,
+            library: self.library,
+            package,
+            members: vec![],
+            reported_packages: FxHashSet::default(),
+            context_free_diagnostics: FxHashMap::default(),
+            files: parse_durations
+                .iter()
+                .map(|(filepath, _)| *filepath)
+                .collect(),
+        };
+
+        for (filepath, duration) in parse_durations {
+            let diagnostics = artifacts.diagnostics_in(filepath);
+
+            artifacts.report(PhaseEvent {
+                phase: Phase::Parse,
+                filepath,
+                duration,
+                diagnostics,
+            });
+        }
+
+        artifacts
+    }
+}
+
+/// Artifacts of phases run so far, that are accessible from handles of all
+/// stages.
+#[allow(missing_debug_implementations)] // `Session` doesn't implement `Debug`
+pub struct Artifacts {
+    session: Session,
+    observers: Vec<Observer>,
+    library: bool,
+
+    /// The package, that is parsed. For workspaces this is the first member.
+    package: PackageId,
+
+    /// Members of the workspace, if a workspace is parsed, see
+    /// [`Pipeline::parse_workspace()`].
+    members: Vec<WorkspaceMember>,
+
+    /// Packages, diagnostics of which are already emitted by
+    /// [`Checked::finish_member()`].
+    reported_packages: FxHashSet<PackageId>,
+
+    /// Diagnostics without locations, e.g. a missing entry point, reported
+    /// for packages, as ranges in diagnostics of [`DUMMY_PATH_ID`].
+    context_free_diagnostics: FxHashMap<PackageId, Range<usize>>,
+
+    /// Parsed files, including files of dependencies, in the order they
+    /// were parsed in.
+    files: Vec<PathId>,
+}
+
+impl Artifacts {
+    /// Returns the underlying session.
+    #[inline]
+    #[must_use]
+    pub const fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Returns the underlying session.
+    #[inline]
+    #[must_use]
+    pub const fn session_mut(&mut self) -> &mut Session {
+        &mut self.session
+    }
+
+    /// Returns the compiler state.
+    #[inline]
+    #[must_use]
+    pub const fn state(&self) -> &State {
+        self.session.state()
+    }
+
+    /// Returns the parsed package. Standalone files parsed with
+    /// [`Pipeline::parse()`] belong to an anonymous package.
+    #[inline]
+    #[must_use]
+    pub const fn package(&self) -> PackageId {
+        self.package
+    }
+
+    /// Returns members of the parsed workspace in the order they are parsed
+    /// in. Empty, if a single package is parsed.
+    #[inline]
+    #[must_use]
+    pub fn members(&self) -> &[WorkspaceMember] {
+        &self.members
+    }
+
+    /// Returns packages, that are checked: members of the workspace or the
+    /// parsed package.
+    fn checked_packages(&self) -> Vec<PackageId> {
+        if self.members.is_empty() {
+            vec![self.package]
+        } else {
+            self.members.iter().map(|member| member.package).collect()
+        }
+    }
+
+    /// Returns parsed files in the order they were parsed in.
+    #[inline]
+    #[must_use]
+    pub fn files(&self) -> &[PathId] {
+        &self.files
+    }
+
+    /// Returns HIR of lowered modules.
+    #[inline]
+    #[must_use]
+    pub const fn hir(&self) -> &FxHashMap<ModuleId, Module> {
+        self.session.hir()
+    }
+
+    /// Returns typed bodies of functions.
+    #[inline]
+    #[must_use]
+    pub const fn thir(&self) -> &FxHashMap<FunctionId, Body> {
+        self.session.thir()
+    }
+
+    /// Returns the number of diagnostics reported in a given file so far.
+    fn diagnostics_in(&self, filepath: PathId) -> usize {
+        self.state().diagnostics().for_file(filepath).len()
+    }
+
+    fn report(&mut self, event: PhaseEvent) {
+        for observer in &mut self.observers {
+            observer(event);
+        }
+    }
+
+    /// Runs a phase on a single file and reports it to observers.
+    fn run_phase<T>(
+        &mut self,
+        phase: Phase,
+        filepath: PathId,
+        run: impl FnOnce(&mut Session) -> T,
+    ) -> T {
+        let diagnostics_before = self.diagnostics_in(filepath) + self.diagnostics_in(DUMMY_PATH_ID);
+        let now = Instant::now();
+
+        let result = run(&mut self.session);
+
+        let duration = now.elapsed();
+        let diagnostics = (self.diagnostics_in(filepath) + self.diagnostics_in(DUMMY_PATH_ID))
+            .saturating_sub(diagnostics_before);
+
+        self.report(PhaseEvent {
+            phase,
+            filepath,
+            duration,
+            diagnostics,
+        });
+
+        result
+    }
+
+    /// Runs a pass on HIR of modules of every file separately.
+    fn run_hir_phase(
+        &mut self,
+        phase: Phase,
+        mut run: impl FnMut(&mut State, &mut FxHashMap<ModuleId, Module>),
+    ) {
+        for filepath in self.files.clone() {
+            if self.session.should_stop_early() {
+                return;
+            }
+
+            self.run_phase(phase, filepath, |session| {
+                // Inline modules share the file with their parents.
+                let mut modules = session
+                    .hir
+                    .keys()
+                    .copied()
+                    .filter(|module| module.filepath(session.state.db()) == filepath)
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .filter_map(|module| session.hir.remove_entry(&module))
+                    .collect();
+
+                run(&mut session.state, &mut modules);
+
+                session.hir.extend(modules);
+            });
+        }
+    }
+
+    /// Runs a pass on every module of every file separately. Unlike
+    /// [`Artifacts::run_hir_phase()`], HIR of all modules is available to the
+    /// pass, e.g. to look up interfaces defined in other files.
+    fn run_module_phase(
+        &mut self,
+        phase: Phase,
+        mut run: impl FnMut(&mut State, &FxHashMap<ModuleId, Module>, ModuleId),
+    ) {
+        for filepath in self.files.clone() {
+            if self.session.should_stop_early() {
+                return;
+            }
+
+            self.run_phase(phase, filepath, |session| {
+                let modules = session
+                    .hir
+                    .keys()
+                    .copied()
+                    .filter(|module| module.filepath(session.state.db()) == filepath)
+                    .collect::<Vec<_>>();
+
+                for module in modules {
+                    run(&mut session.state, &session.hir, module);
+                }
+            });
+        }
+    }
+}
+
+/// Generates a handle of a pipeline stage, that dereferences to
+/// [`Artifacts`].
+macro_rules! stage {
+    { $(#[$meta:meta])* $name:ident } => {
+        $(#[$meta])*
+        #[allow(missing_debug_implementations)] // `Session` doesn't implement `Debug`
+        pub struct $name(Artifacts);
+
+        impl Deref for $name {
+            type Target = Artifacts;
+
+            #[inline]
+            fn deref(&self) -> &Artifacts {
+                &self.0
+            }
+        }
+
+        impl DerefMut for $name {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut Artifacts {
+                &mut self.0
+            }
+        }
+    };
+}
+
+stage! {
+    /// A stage, at which all files are parsed. ASTs are available in
+    /// [`Session::parsed_modules()`].
+    Parsed
+}
+
+stage! {
+    /// A stage, at which all modules are lowered into HIR.
+    Lowered
+}
+
+stage! {
+    /// A stage, at which definitions of all modules are collected.
+    DefinitionsCollected
+}
+
+stage! {
+    /// A stage, at which imports are resolved, the entry point is checked and
+    /// function bodies are type checked.
+    Checked
+}
+
+impl Parsed {
+    /// Lowers every parsed file into HIR and fills captured variables of
+    /// lambdas.
+    #[must_use]
+    pub fn lower(mut self) -> Lowered {
+        for parsed in std::mem::take(&mut self.0.session.parsed_modules) {
+            if self.session.should_stop_early() {
+                break;
+            }
+
+            let filepath = parsed.module().filepath(self.state().db());
+
+            self.0.run_phase(Phase::Lower, filepath, |session| {
+                let mut hir = LowerToHir::run_all(&mut session.state, vec![parsed]);
+                CaptureAnalysis::run_all(&mut hir);
+                session.hir.extend(hir);
+            });
+        }
+
+        Lowered(self.0)
+    }
+}
+
+impl Lowered {
+    /// Folds constant expressions in HIR of every file.
+    #[must_use]
+    pub fn fold_constants(mut self) -> Self {
+        self.0
+            .run_hir_phase(Phase::FoldConstants, |state, modules| {
+                ConstantFolding::run_all(state, modules);
+            });
+        self
+    }
+
+    /// Collects definitions of every file and generic parameters of their
+    /// signatures.
+    #[must_use]
+    pub fn collect_definitions(mut self) -> DefinitionsCollected {
+        self.0
+            .run_hir_phase(Phase::CollectDefinitions, |state, modules| {
+                CollectDefinitions::run_all(state, modules);
+                CollectSignatures::run_all(state, modules);
+            });
+
+        DefinitionsCollected(self.0)
+    }
+
+    /// Emits diagnostics reported so far.
+    ///
+    /// # Errors
+    /// Returns an error if there are fatal diagnostics.
+    pub fn finish(&mut self) -> Result<(), CompileError> {
+        self.session.finish_checking()
+    }
+}
+
+impl DefinitionsCollected {
+    /// Resolves imports of every file, checks signatures of module items,
+    /// checks entry points of the package or of all members of the workspace,
+    /// unless they are libraries (see [`Pipeline::library()`]), type checks
+    /// bodies of all functions, that are available in [`Artifacts::thir()`]
+    /// afterwards, and reports lints.
+    #[must_use]
+    pub fn check(mut self) -> Checked {
+        self.0
+            .run_hir_phase(Phase::ResolveImports, |state, modules| {
+                ResolveImports::run_all(state, modules);
+            });
+
+        self.0
+            .run_module_phase(Phase::CheckSignatures, |state, modules, module| {
+                let hir = &modules[&module];
+
+                CheckTypePaths::run(state, module, hir);
+                CheckGenericArguments::run(state, modules, module);
+                CheckGenericParameters::run(state, module, hir);
+                CheckInterfaceConformance::run(state, modules, module);
+                CheckObjectSafety::run(state, modules, module);
+                CheckPrivateInPublic::run(state, module, hir);
+            });
+
+        for package in self.checked_packages() {
+            if self.library
+                || self.session.should_stop_early()
+                || self.session.package_kind(package) != Some(PackageKind::Binary)
+            {
+                continue;
+            }
+
+            let filepath = package
+                .root_module(self.state().db())
+                .filepath(self.state().db());
+            let start = self.diagnostics_in(DUMMY_PATH_ID);
+
+            self.0
+                .run_phase(Phase::CheckEntryPoint, filepath, |session| {
+                    session.check_entry_point(package);
+                });
+
+            let end = self.diagnostics_in(DUMMY_PATH_ID);
+            self.0.context_free_diagnostics.insert(package, start..end);
+        }
+
+        self.lower_bodies();
+
+        self.0
+            .run_module_phase(Phase::CheckBodies, |state, modules, module| {
+                let hir = &modules[&module];
+
+                CheckAssignments::run(state, module, hir);
+                CheckLetPatterns::run(state, module, hir);
+                CheckMatchExhaustiveness::run(state, module, hir);
+                CheckBreakAndContinue::run(state, module, hir);
+                ResolveEnumItems::run(state, modules, module);
+            });
+
+        self.0
+            .run_module_phase(Phase::Lint, |state, modules, module| {
+                CheckNamingConventions::run(state, module, &modules[&module]);
+            });
+
+        for package in self.checked_packages() {
+            // Standalone files don't have a root module, so there is no
+            // `main` function to find used items from.
+            let root_module = package.root_module(self.state().db());
+
+            if self.session.should_stop_early() || root_module == DUMMY_MODULE_ID {
+                continue;
+            }
+
+            let filepath = root_module.filepath(self.state().db());
+
+            self.0.run_phase(Phase::CheckDeadCode, filepath, |session| {
+                CheckDeadCode::run(&mut session.state, &session.hir, package);
+            });
+        }
+
+        Checked(self.0)
+    }
+
+    /// Infers types in bodies of functions of every file and lowers them
+    /// into THIR.
+    fn lower_bodies(&mut self) {
+        let db = self.state().db();
+        let functions = self
+            .state()
+            .hir()
+            .iter()
+            .filter_map(|(symbol, _)| match symbol {
+                Symbol::Function(function) => Some(function),
+                _ => None,
+            })
+            .map(|function| {
+                let module = function.signature(db).module(db);
+
+                (module.filepath(db), function)
+            })
+            .collect::<Vec<_>>();
+
+        for filepath in self.files.clone() {
+            if self.session.should_stop_early() {
+                break;
+            }
+
+            self.0.run_phase(Phase::LowerBodies, filepath, |session| {
+                for &(_, function) in functions.iter().filter(|(file, _)| *file == filepath) {
+                    if let Some(body) = lower_body(&mut session.state, &session.hir, function) {
+                        session.thir.insert(function, body);
+                    }
+                }
+            });
+        }
+    }
+
+    /// Emits diagnostics reported so far.
+    ///
+    /// # Errors
+    /// Returns an error if there are fatal diagnostics.
+    pub fn finish(&mut self) -> Result<(), CompileError> {
+        self.session.finish_checking()
+    }
+}
+
+impl Checked {
+    /// Emits diagnostics of a given member of the workspace together with
+    /// diagnostics of its dependencies, that are not members and are not
+    /// reported with another member yet, and returns the result of checking
+    /// the member.
+    ///
+    /// Members are expected to be finished in the order of [`Artifacts::members()`],
+    /// otherwise diagnostics without locations, e.g. a missing entry point,
+    /// may be emitted with a wrong member.
+    ///
+    /// # Panics
+    /// Panics if the package is not a member of the workspace.
+    pub fn finish_member(&mut self, package: PackageId) -> MemberReport {
+        let name = self
+            .members
+            .iter()
+            .find(|member| member.package == package)
+            .map(|member| member.name.clone())
+            .expect("the package is a member of the workspace");
+
+        let db = self.state().db();
+        let members = self
+            .members
+            .iter()
+            .map(|member| member.package)
+            .collect::<FxHashSet<_>>();
+
+        let mut packages = FxHashSet::default();
+        let mut stack = vec![package];
+
+        while let Some(package) = stack.pop() {
+            if self.0.reported_packages.contains(&package) || !packages.insert(package) {
+                continue;
+            }
+
+            stack.extend(
+                package
+                    .dependencies(db)
+                    .values()
+                    .filter(|dependency| !members.contains(dependency)),
+            );
+        }
+
+        let filepaths = self
+            .files
+            .iter()
+            .copied()
+            .filter(|&filepath| {
+                self.hir()
+                    .keys()
+                    .find(|module| module.filepath(db) == filepath)
+                    .is_some_and(|module| packages.contains(&module.package()))
+            })
+            .collect::<Vec<_>>();
+
+        let diagnostics = self.state().diagnostics();
+        let context_free = self
+            .context_free_diagnostics
+            .get(&package)
+            .cloned()
+            .unwrap_or_default();
+
+        let error_count = filepaths
+            .iter()
+            .flat_map(|&filepath| diagnostics.for_file(filepath))
+            .chain(&diagnostics.for_file(DUMMY_PATH_ID)[context_free.clone()])
+            .filter(|diagnostic| is_fatal_severity(diagnostic.severity))
+            .count();
+
+        let limits = filepaths
+            .into_iter()
+            .map(|filepath| (filepath, usize::MAX))
+            .chain([(DUMMY_PATH_ID, context_free.end)])
+            .collect::<Vec<_>>();
+
+        self.0.reported_packages.extend(packages);
+        self.0.session.emit_diagnostics_up_to(&limits);
+
+        MemberReport {
+            name,
+            package,
+            error_count,
+        }
+    }
+
+    /// Emits diagnostics reported so far.
+    ///
+    /// # Errors
+    /// Returns an error if there are fatal diagnostics.
+    pub fn finish(&mut self) -> Result<(), CompileError> {
+        self.session.finish_checking()
+    }
+}
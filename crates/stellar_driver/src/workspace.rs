@@ -0,0 +1,153 @@
+//! Checking several packages from one invocation as a workspace.
+//!
+//! The root manifest of a workspace lists directories of its members:
+//!
+//! ```toml
+//! [workspace]
+//! members = ["json", "http", "server"]
+//! ```
+//!
+//! Members and their local dependencies are parsed into the same [`Session`]
+//! in the order of the dependency graph, so a package, that several members
+//! depend on, is parsed and checked only once. Diagnostics are then reported
+//! per member, see [`Checked::finish_member()`].
+//!
+//! [`Checked::finish_member()`]: crate::pipeline::Checked::finish_member
+
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use stellar_database::PackageId;
+use stellar_depgraph::DependencyGraph;
+use stellar_filesystem::path_resolver::PackagePathResolver;
+use stellar_fx_hash::FxHashMap;
+use stellar_manifest::parse_workspace_manifest;
+
+use crate::{
+    diagnostics::{DuplicateWorkspaceMember, WorkspaceMemberNotFound},
+    load_package_sources, CompileError, Session,
+};
+
+/// A member of a workspace, that is parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    /// The name of the package.
+    pub name: String,
+
+    /// The directory of the package, as it is listed in the root manifest.
+    pub directory: PathBuf,
+
+    /// The package.
+    pub package: PackageId,
+}
+
+/// Result of checking a member of a workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberReport {
+    /// The name of the package.
+    pub name: String,
+
+    /// The package.
+    pub package: PackageId,
+
+    /// The number of fatal diagnostics in the package and in its
+    /// dependencies, that are not members and are reported with it.
+    pub error_count: usize,
+}
+
+impl MemberReport {
+    /// Returns `true` if the member doesn't have fatal diagnostics.
+    #[inline]
+    #[must_use]
+    pub const fn is_ok(&self) -> bool {
+        self.error_count == 0
+    }
+}
+
+impl Session {
+    /// Reads the root manifest of a workspace in a given directory and parses
+    /// all of its members together with their local dependencies (see
+    /// [`Session::parse_package_with_dependencies()`]). Every package is
+    /// parsed once, even if several members depend on it.
+    ///
+    /// Members, that don't exist, and members with the same package names
+    /// are reported as diagnostics and are skipped.
+    ///
+    /// Returns members in the order they are parsed in, i.e. every member
+    /// goes after members it depends on.
+    ///
+    /// # Errors
+    /// Returns an error if the root manifest cannot be read or doesn't have
+    /// a `[workspace]` section, if some of the packages cannot be read or if
+    /// packages depend on each other.
+    pub fn parse_workspace(
+        &mut self,
+        root: &std::path::Path,
+    ) -> Result<Vec<WorkspaceMember>, CompileError> {
+        let manifest_path = PackagePathResolver::new(root).manifest();
+
+        let manifest = fs::read_to_string(&manifest_path).map_err(|source| CompileError::Io {
+            path: manifest_path.clone(),
+            source,
+        })?;
+        let workspace = parse_workspace_manifest(manifest)
+            .map_err(|message| CompileError::InvalidManifest {
+                path: manifest_path.clone(),
+                message,
+            })?
+            .ok_or_else(|| CompileError::MissingWorkspace(manifest_path.clone()))?;
+
+        let mut graph = DependencyGraph::new();
+        let mut sources = BTreeMap::new();
+
+        // Members as they are listed in the root manifest and their
+        // directories by names of their packages.
+        let mut members = FxHashMap::<String, (&String, PathBuf)>::default();
+
+        for member in &workspace.members {
+            let directory = root.join(member);
+
+            if !PackagePathResolver::new(&directory).manifest().is_file() {
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(WorkspaceMemberNotFound::new(
+                        member.clone(),
+                        manifest_path.display().to_string(),
+                    ));
+                continue;
+            }
+
+            let name = load_package_sources(&directory, &[], &mut graph, &mut sources)?;
+
+            if let Some((first_member, _)) = members.get(&name) {
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(DuplicateWorkspaceMember::new(
+                        name,
+                        String::clone(first_member),
+                        member.clone(),
+                    ));
+                continue;
+            }
+
+            members.insert(name, (member, directory));
+        }
+
+        let order = graph
+            .topological_sort()
+            .map_err(CompileError::DependencyCycle)?;
+        let packages = self.parse_packages_in_order(&order, &sources)?;
+
+        Ok(order
+            .into_iter()
+            .filter_map(|name| {
+                let (_, directory) = members.remove(&name)?;
+
+                Some(WorkspaceMember {
+                    package: packages[&name],
+                    name,
+                    directory,
+                })
+            })
+            .collect())
+    }
+}
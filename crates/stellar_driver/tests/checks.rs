@@ -0,0 +1,154 @@
+//! Tests, that checks of the typechecker are run by [`DefinitionsCollected::check()`].
+//!
+//! [`DefinitionsCollected::check()`]: stellar_driver::pipeline::DefinitionsCollected::check
+
+use std::path::Path;
+
+use stellar_database::{ty::Type, State};
+use stellar_driver::{pipeline::Checked, Pipeline};
+use stellar_interner::{IdentifierId, PathId};
+use stellar_thir::body::{ExpressionKind, Statement};
+
+/// Checks a standalone file in `tests/fixtures/checks`.
+fn check_fixture(name: &str) -> Checked {
+    let filepath = PathId::from(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/checks")
+            .join(name),
+    );
+
+    Pipeline::new(State::new())
+        .parse(&[filepath])
+        .unwrap()
+        .lower()
+        .collect_definitions()
+        .check()
+}
+
+/// Checks a standalone file in `tests/fixtures/checks` and returns codes of
+/// reported diagnostics in the order they are reported in.
+fn check(name: &str) -> Vec<String> {
+    check_fixture(name)
+        .state()
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.code.clone().unwrap())
+        .collect()
+}
+
+#[test]
+fn type_paths() {
+    // `foo` is a function, and `Strin` is not defined.
+    assert_eq!(check("type_paths.sr"), ["E112", "E110"]);
+}
+
+#[test]
+fn generic_arguments() {
+    // `Map[Point]` misses an argument, and `Point` doesn't implement
+    // `ToString`.
+    assert_eq!(check("generic_arguments.sr"), ["E254", "E255"]);
+}
+
+#[test]
+fn generic_parameters() {
+    // `T` is not used.
+    assert_eq!(check("generic_parameters.sr"), ["W008"]);
+}
+
+#[test]
+fn interface_conformance() {
+    // `area` returns a wrong type, and `name` is missing.
+    assert_eq!(check("interface_conformance.sr"), ["E202", "E201"]);
+}
+
+#[test]
+fn object_safety() {
+    // `clone` returns `Self`.
+    assert_eq!(check("object_safety.sr"), ["E237"]);
+}
+
+#[test]
+fn private_in_public() {
+    // `Secret` is private.
+    assert_eq!(check("private_in_public.sr"), ["W012"]);
+}
+
+#[test]
+fn assignment_targets() {
+    // `5` is not a place.
+    assert_eq!(check("assignment_targets.sr"), ["E214"]);
+}
+
+#[test]
+fn immutable_bindings() {
+    // `x` and `y` are not declared with `mut`.
+    assert_eq!(check("immutable_bindings.sr"), ["E216", "E216"]);
+}
+
+#[test]
+fn let_patterns() {
+    // `Option.Some(x)` is refutable, and the `else` block doesn't diverge.
+    assert_eq!(check("let_patterns.sr"), ["E206", "E207"]);
+}
+
+#[test]
+fn match_exhaustiveness() {
+    // `Color.Green` and `Color.Blue` are not covered.
+    assert_eq!(check("match_exhaustiveness.sr"), ["E218"]);
+}
+
+#[test]
+fn enum_items() {
+    // `Color.Red` takes no arguments, and `Shape.Rectangle` takes two.
+    assert_eq!(check("enum_items.sr"), ["E203", "E205"]);
+}
+
+#[test]
+fn break_and_continue() {
+    // `break` is outside of a loop, and `@outr` is not declared.
+    assert_eq!(check("break_and_continue.sr"), ["E220", "E115"]);
+}
+
+#[test]
+fn tuple_indices() {
+    // `pair` has only 2 elements, and `x` is not a tuple.
+    assert_eq!(check("tuple_indices.sr"), ["E209", "E210"]);
+}
+
+#[test]
+fn lambdas() {
+    let checked = check_fixture("lambdas.sr");
+    let codes = checked
+        .state()
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.code.clone().unwrap())
+        .collect::<Vec<_>>();
+
+    // `|x: String| 1` is not `fun(int32): int32`.
+    assert_eq!(codes, ["E208"]);
+
+    let body = checked
+        .thir()
+        .values()
+        .find(|body| body.statements.len() == 2)
+        .unwrap();
+    let Statement::Expression { expression, .. } = &body.statements[0] else {
+        panic!("expected an expression statement");
+    };
+    let ExpressionKind::Call { arguments, .. } = &expression.kind else {
+        panic!("expected a call");
+    };
+    let ExpressionKind::Lambda {
+        parameters,
+        captures,
+        ..
+    } = &arguments[0].value.kind
+    else {
+        panic!("expected a lambda");
+    };
+
+    // `x` is inferred from the signature of `apply`, and `y` is captured.
+    assert!(matches!(parameters[0].ty, Type::Constructor(_)));
+    assert_eq!(captures, &[IdentifierId::from("y")]);
+}
@@ -0,0 +1,147 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use stellar_database::{PackageId, Symbol};
+use stellar_driver::{CompileError, Session};
+use stellar_interner::IdentifierId;
+
+/// Creates a temporary directory for a test.
+fn temporary_directory(test: &str) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("stellar_driver_{test}_{}", std::process::id()));
+
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+
+    directory
+}
+
+/// Writes a package with a given manifest `[dependencies]` section and
+/// the root module.
+fn write_package(directory: &Path, name: &str, dependencies: &str, source: &str) {
+    fs::create_dir_all(directory.join("src")).unwrap();
+    fs::write(
+        directory.join("package.toml"),
+        format!(
+            "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n\n[dependencies]\n{dependencies}"
+        ),
+    )
+    .unwrap();
+    fs::write(directory.join("src/package.sr"), source).unwrap();
+}
+
+fn check(
+    directory: &Path,
+    dependencies: &[(String, PathBuf)],
+) -> (Session, Result<PackageId, CompileError>) {
+    let mut session = Session::new();
+
+    let result = session
+        .parse_package_with_dependencies(directory, dependencies)
+        .and_then(|package| {
+            session.finish_parsing()?;
+
+            session.lower();
+            session.collect_definitions();
+            session.resolve_imports();
+            session.finish_checking()?;
+
+            Ok(package)
+        });
+
+    (session, result)
+}
+
+#[test]
+fn import_public_struct_of_dependency() {
+    let root = temporary_directory("import_public_struct_of_dependency");
+
+    write_package(&root.join("a"), "a", "", "pub struct Point {}");
+    write_package(
+        &root.join("b"),
+        "b",
+        "",
+        "import a.Point;\n\npub fun origin(): Point {}",
+    );
+
+    let (session, result) = check(&root.join("b"), &[("a".to_owned(), root.join("a"))]);
+
+    let db = session.state().db();
+    let b = result.unwrap();
+
+    assert_eq!(b.name(db), IdentifierId::from("b"));
+    assert!(matches!(
+        b.root_module(db)
+            .resolved_imports(db)
+            .get(&IdentifierId::from("Point")),
+        Some(Symbol::Struct(_))
+    ));
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn import_private_struct_of_dependency() {
+    let root = temporary_directory("import_private_struct_of_dependency");
+
+    write_package(&root.join("a"), "a", "", "struct Point {}");
+    write_package(&root.join("b"), "b", "", "import a.Point;");
+
+    let (session, result) = check(&root.join("b"), &[("a".to_owned(), root.join("a"))]);
+
+    assert!(matches!(
+        result,
+        Err(CompileError::CheckFailed { error_count: 1 })
+    ));
+    assert_eq!(
+        session.state().diagnostics().all()[0].code.as_deref(),
+        Some("E106")
+    );
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn dependencies_from_manifest() {
+    let root = temporary_directory("dependencies_from_manifest");
+
+    write_package(&root.join("io"), "io", "", "pub fun println() {}");
+    write_package(
+        &root.join("fmt"),
+        "fmt",
+        "io = { path = \"../io\" }\n",
+        "import io.println;\n\npub struct Formatter {}",
+    );
+    write_package(
+        &root.join("app"),
+        "app",
+        "fmt = { path = \"../fmt\" }\n",
+        "import fmt.Formatter;",
+    );
+
+    let (_, result) = check(&root.join("app"), &[]);
+
+    assert!(result.is_ok());
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn dependency_cycle() {
+    let root = temporary_directory("dependency_cycle");
+
+    write_package(&root.join("a"), "a", "b = { path = \"../b\" }\n", "");
+    write_package(&root.join("b"), "b", "a = { path = \"../a\" }\n", "");
+
+    let (_, result) = check(&root.join("a"), &[]);
+
+    let Err(CompileError::DependencyCycle(error)) = result else {
+        panic!("expected a dependency cycle, got {result:?}");
+    };
+
+    assert_eq!(error.traceback(), ["a", "b", "a"]);
+
+    fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,132 @@
+use std::path::{Path, PathBuf};
+
+use stellar_database::PackageData;
+use stellar_diagnostics::{
+    diagnostic::Severity,
+    registry::{DiagnosticInfo, Phase},
+};
+use stellar_driver::{
+    explain::{all_diagnostics, explain, REGISTRY},
+    Session,
+};
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::{PathId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+/// Parses a fixture file, runs all passes and returns reported codes.
+fn reported_codes(name: &str) -> Vec<Option<String>> {
+    let mut session = Session::new();
+    let package = PackageData::alloc(
+        session.state_mut().db_mut(),
+        DUMMY_IDENTIFIER_ID,
+        DUMMY_PATH_ID,
+    );
+
+    session
+        .parse_file(
+            package,
+            DUMMY_IDENTIFIER_ID.into(),
+            PathId::from(fixture(name)),
+        )
+        .unwrap();
+
+    session.lower();
+    session.collect_definitions();
+    session.resolve_imports();
+
+    session
+        .state()
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.code.clone())
+        .collect()
+}
+
+#[test]
+fn no_two_diagnostics_share_a_code() {
+    let mut codes = FxHashMap::<&str, &str>::default();
+
+    for info in all_diagnostics() {
+        if let Some(other) = codes.insert(info.code, info.name) {
+            panic!("`{}` and `{other}` share the code {}", info.name, info.code);
+        }
+    }
+}
+
+#[test]
+fn codes_belong_to_phases() {
+    let [parser, lowering, typechecker, driver] = REGISTRY else {
+        panic!("unexpected number of crates in the registry");
+    };
+
+    let check = |diagnostics: &[DiagnosticInfo], error_phases: &[Phase]| {
+        for info in diagnostics {
+            let phase = info.phase();
+
+            match info.severity {
+                Severity::Warning => assert_eq!(phase, Some(Phase::Lints), "{}", info.name),
+                _ => assert!(
+                    phase.is_some_and(|phase| error_phases.contains(&phase)),
+                    "{} has the code {}",
+                    info.name,
+                    info.code
+                ),
+            }
+        }
+    };
+
+    check(parser, &[Phase::Parsing]);
+    check(lowering, &[Phase::Parsing]);
+    check(driver, &[Phase::Parsing]);
+    check(typechecker, &[Phase::NameResolution, Phase::TypeChecking]);
+}
+
+#[test]
+fn every_diagnostic_is_explained() {
+    for info in all_diagnostics() {
+        assert!(
+            info.explanation_lines().any(|line| !line.is_empty()),
+            "{} is not explained",
+            info.name
+        );
+        assert_eq!(explain(info.code), Some(info));
+    }
+
+    assert_eq!(
+        explain("e001").map(|info| info.name),
+        Some("UnexpectedToken")
+    );
+    assert_eq!(explain("E999"), None);
+}
+
+#[test]
+fn reported_diagnostics_are_registered() {
+    for name in [
+        "syntax_error.sr",
+        "duplicate_definition.sr",
+        "unclosed_block.sr",
+    ] {
+        let codes = reported_codes(name);
+
+        assert!(!codes.is_empty(), "{name}");
+
+        for code in codes {
+            let code = code.unwrap_or_else(|| panic!("a diagnostic of {name} has no code"));
+
+            assert!(
+                explain(&code).is_some(),
+                "{code} of {name} is not registered"
+            );
+        }
+    }
+
+    assert_eq!(
+        reported_codes("unclosed_block.sr"),
+        [Some("E005".to_owned())]
+    );
+}
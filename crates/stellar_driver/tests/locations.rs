@@ -0,0 +1,86 @@
+use std::path::{Path, PathBuf};
+
+use stellar_database::PackageData;
+use stellar_diagnostics::{
+    diagnostic::{Diagnostic, Label},
+    Diagnostics,
+};
+use stellar_driver::Session;
+use stellar_filesystem::location::DUMMY_LOCATION;
+use stellar_interner::{PathId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+/// Runs all passes on a session and returns the number of reported diagnostics.
+fn run_passes(session: &mut Session) -> usize {
+    session.lower();
+    session.collect_definitions();
+    session.resolve_imports();
+
+    session.state().diagnostics().len()
+}
+
+fn check_file(name: &str) -> Session {
+    let mut session = Session::new();
+    let package = PackageData::alloc(
+        session.state_mut().db_mut(),
+        DUMMY_IDENTIFIER_ID,
+        DUMMY_PATH_ID,
+    );
+
+    session
+        .parse_file(
+            package,
+            DUMMY_IDENTIFIER_ID.into(),
+            PathId::from(fixture(name)),
+        )
+        .unwrap();
+
+    session
+}
+
+#[test]
+fn no_dummy_labels_in_fixtures() {
+    for name in ["syntax_error.sr", "duplicate_definition.sr"] {
+        let mut session = check_file(name);
+
+        assert_ne!(run_passes(&mut session), 0, "{name}");
+        assert_eq!(
+            session.state().diagnostics().count_dummy_labels(),
+            0,
+            "{name}"
+        );
+    }
+
+    let mut session = Session::new();
+    session.parse_package(&fixture("shapes")).unwrap();
+
+    run_passes(&mut session);
+    assert_eq!(session.state().diagnostics().count_dummy_labels(), 0);
+}
+
+#[test]
+fn no_dummy_labels_in_private_import() {
+    let mut session = Session::new();
+    session
+        .parse_package_with_dependencies(&fixture("private_import/app"), &[])
+        .unwrap();
+
+    assert_ne!(run_passes(&mut session), 0);
+    assert_eq!(session.state().diagnostics().count_dummy_labels(), 0);
+}
+
+#[test]
+fn count_dummy_labels() {
+    let mut diagnostics = Diagnostics::new();
+    diagnostics.add_diagnostic(Diagnostic::error().with_message("test").with_labels([
+        Label::primary(DUMMY_LOCATION),
+        Label::secondary(DUMMY_LOCATION),
+    ]));
+
+    assert_eq!(diagnostics.count_dummy_labels(), 2);
+}
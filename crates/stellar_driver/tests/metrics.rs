@@ -0,0 +1,79 @@
+use std::path::{Path, PathBuf};
+
+use stellar_driver::{metrics::MetricThreshold, Session};
+use stellar_hir::metrics::{FunctionMetrics, Metric};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+#[test]
+fn function_metrics() {
+    let mut session = Session::new();
+    let package = session.parse_package(&fixture("metrics")).unwrap();
+
+    session.lower();
+    session.collect_definitions();
+
+    let reports = session.function_metrics(package);
+
+    assert_eq!(
+        reports
+            .iter()
+            .map(|report| (report.path.as_str(), report.metrics))
+            .collect::<Vec<_>>(),
+        [
+            (
+                "metrics.classify",
+                FunctionMetrics {
+                    statements: 3,
+                    expressions: 22,
+                    max_depth: 3,
+                    branches: 5,
+                    parameters: 2,
+                }
+            ),
+            (
+                "metrics.identity",
+                FunctionMetrics {
+                    statements: 0,
+                    expressions: 1,
+                    max_depth: 1,
+                    branches: 0,
+                    parameters: 1,
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn metric_threshold_exceeded() {
+    let mut session = Session::new();
+    let package = session.parse_package(&fixture("metrics")).unwrap();
+
+    session.lower();
+    session.collect_definitions();
+
+    let reports = session.function_metrics(package);
+    let violations = session.check_metric_thresholds(
+        &reports,
+        &[MetricThreshold {
+            metric: Metric::Depth,
+            max: 2,
+        }],
+    );
+
+    assert_eq!(violations, 1);
+
+    let diagnostics = session.state().diagnostics().all();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code.as_deref(), Some("W015"));
+    assert_eq!(
+        diagnostics[0].message,
+        "function `classify` has a nesting depth of 3, which exceeds the maximum of 2"
+    );
+}
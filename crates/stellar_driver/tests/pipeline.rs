@@ -0,0 +1,320 @@
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::Duration,
+};
+
+use stellar_database::{Config, State, Symbol};
+use stellar_diagnostics::SeverityOverride;
+use stellar_driver::{CompileError, Phase, PhaseEvent, Pipeline};
+use stellar_interner::{IdentifierId, PathId};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+/// Creates a pipeline, that records all phase events.
+fn recording_pipeline() -> (Pipeline, Rc<RefCell<Vec<PhaseEvent>>>) {
+    let events = Rc::new(RefCell::new(vec![]));
+    let recorded = Rc::clone(&events);
+
+    let pipeline = Pipeline::new(State::new())
+        .on_phase(move |event| recorded.borrow_mut().push(event))
+        .library(true);
+
+    (pipeline, events)
+}
+
+fn files_of(events: &[PhaseEvent], phase: Phase) -> Vec<PathId> {
+    events
+        .iter()
+        .filter(|event| event.phase == phase)
+        .map(|event| event.filepath)
+        .collect()
+}
+
+#[test]
+fn phase_fires_once_per_file() {
+    let (pipeline, events) = recording_pipeline();
+
+    let checked = pipeline
+        .parse_package(&fixture("shapes"), &[])
+        .unwrap()
+        .lower()
+        .collect_definitions()
+        .check();
+
+    let events = events.borrow();
+    let files = checked.files();
+
+    assert_eq!(files.len(), 2);
+
+    for phase in [
+        Phase::Parse,
+        Phase::Lower,
+        Phase::CollectDefinitions,
+        Phase::ResolveImports,
+        Phase::CheckSignatures,
+        Phase::LowerBodies,
+        Phase::CheckBodies,
+        Phase::Lint,
+    ] {
+        assert_eq!(files_of(&events, phase), files, "{phase}");
+    }
+
+    let root_filepath = {
+        let db = checked.state().db();
+        checked.package().root_module(db).filepath(db)
+    };
+
+    // Libraries don't have entry points, and unused items are found once
+    // per package.
+    assert!(files_of(&events, Phase::CheckEntryPoint).is_empty());
+    assert_eq!(files_of(&events, Phase::CheckDeadCode), [root_filepath]);
+    assert_eq!(events.len(), 8 * files.len() + 1);
+
+    let total = events.iter().map(|event| event.duration).sum::<Duration>();
+
+    assert!(total > Duration::ZERO);
+    assert!(total < Duration::from_secs(60));
+    assert!(events.iter().all(|event| event.diagnostics == 0));
+}
+
+#[test]
+fn earlier_artifacts_are_accessible() {
+    let (pipeline, _) = recording_pipeline();
+
+    let parsed = pipeline.parse_package(&fixture("shapes"), &[]).unwrap();
+    let package = parsed.package();
+
+    assert_eq!(parsed.session().parsed_modules().len(), 2);
+
+    let lowered = parsed.lower();
+
+    assert_eq!(lowered.hir().len(), 2);
+    assert!(lowered.session().parsed_modules().is_empty());
+
+    let mut checked = lowered.collect_definitions().check();
+
+    assert_eq!(checked.package(), package);
+    assert_eq!(checked.hir().len(), 2);
+    assert!(checked.finish().is_ok());
+
+    let db = checked.state().db();
+
+    assert_eq!(package.name(db), IdentifierId::from("shapes"));
+    assert!(matches!(
+        package
+            .root_module(db)
+            .module_item_symbols(db)
+            .get(&IdentifierId::from("unit_circle")),
+        Some(Symbol::Function(_))
+    ));
+}
+
+#[test]
+fn diagnostics_are_reported_per_phase() {
+    let events = Rc::new(RefCell::new(vec![]));
+    let recorded = Rc::clone(&events);
+
+    let mut checked = Pipeline::new(State::new())
+        .on_phase(move |event| recorded.borrow_mut().push(event))
+        .parse_package(&fixture("shapes"), &[])
+        .unwrap()
+        .lower()
+        .collect_definitions()
+        .check();
+
+    let root_filepath = {
+        let db = checked.state().db();
+        checked.package().root_module(db).filepath(db)
+    };
+    let entry_point = events
+        .borrow()
+        .iter()
+        .filter(|event| event.phase == Phase::CheckEntryPoint)
+        .copied()
+        .collect::<Vec<_>>();
+
+    // `shapes` is a binary package without `main`.
+    assert_eq!(entry_point.len(), 1);
+    assert_eq!(entry_point[0].filepath, root_filepath);
+    assert_eq!(entry_point[0].diagnostics, 1);
+    assert!(matches!(
+        checked.finish(),
+        Err(CompileError::CheckFailed { error_count: 1 })
+    ));
+}
+
+#[test]
+fn lints_are_reported() {
+    let (pipeline, events) = recording_pipeline();
+
+    let checked = pipeline
+        .parse_package(&fixture("lints"), &[])
+        .unwrap()
+        .lower()
+        .collect_definitions()
+        .check();
+
+    let codes = checked
+        .state()
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.code.clone().unwrap())
+        .collect::<Vec<_>>();
+
+    // `point` is not in `UpperCamelCase`, and `helper` is never used.
+    assert_eq!(codes, ["W005", "W014"]);
+
+    let events = events.borrow();
+    let diagnostics_of = |phase| {
+        events
+            .iter()
+            .filter(|event| event.phase == phase)
+            .map(|event| event.diagnostics)
+            .sum::<usize>()
+    };
+
+    assert_eq!(diagnostics_of(Phase::Lint), 1);
+    assert_eq!(diagnostics_of(Phase::CheckDeadCode), 1);
+}
+
+#[test]
+fn standalone_files() {
+    let (pipeline, events) = recording_pipeline();
+    let filepath = PathId::from(fixture("syntax_error.sr"));
+
+    let result = pipeline.parse(&[filepath]);
+
+    assert!(matches!(result, Err(CompileError::ParseFailed { .. })));
+
+    let events = events.borrow();
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].phase, Phase::Parse);
+    assert_eq!(events[0].filepath, filepath);
+    assert!(events[0].diagnostics > 0);
+}
+
+#[test]
+fn function_bodies_are_checked() {
+    let (pipeline, events) = recording_pipeline();
+    let filepath = PathId::from(fixture("type_errors.sr"));
+
+    let mut checked = pipeline
+        .parse(&[filepath])
+        .unwrap()
+        .lower()
+        .collect_definitions()
+        .check();
+
+    let mut codes = checked
+        .state()
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.code.clone().unwrap())
+        .collect::<Vec<_>>();
+    codes.sort();
+
+    // `let x: uint8 = 300;` and `1 + "x"`.
+    assert_eq!(codes, ["E236", "E256"]);
+    assert_eq!(checked.thir().len(), 1);
+
+    let events = events.borrow();
+    let lower_bodies = events
+        .iter()
+        .find(|event| event.phase == Phase::LowerBodies)
+        .unwrap();
+
+    assert_eq!(lower_bodies.filepath, filepath);
+    assert_eq!(lower_bodies.diagnostics, 2);
+    assert!(matches!(
+        checked.finish(),
+        Err(CompileError::CheckFailed { error_count: 2 })
+    ));
+}
+
+#[test]
+fn fail_fast_stops_after_first_file() {
+    let check = |config: Config| {
+        let events = Rc::new(RefCell::new(vec![]));
+        let recorded = Rc::clone(&events);
+
+        let checked = Pipeline::new(State::new().with_config(config))
+            .on_phase(move |event| recorded.borrow_mut().push(event))
+            .library(true)
+            .parse_package(&fixture("duplicates"), &[])
+            .unwrap()
+            .lower()
+            .collect_definitions()
+            .check();
+
+        let files_with_errors = checked
+            .state()
+            .diagnostics()
+            .files_with_diagnostics()
+            .collect::<Vec<_>>();
+        let events = events.borrow().clone();
+
+        (checked.files().to_vec(), files_with_errors, events)
+    };
+
+    let (files, files_with_errors, _) = check(Config::new());
+
+    assert_eq!(files_with_errors, files);
+
+    let (files, files_with_errors, events) = check(Config::new().with_fail_fast(true));
+
+    assert_eq!(files_with_errors, [files[0]]);
+    assert_eq!(files_of(&events, Phase::CollectDefinitions), [files[0]]);
+    assert!(files_of(&events, Phase::ResolveImports).is_empty());
+}
+
+#[test]
+fn severity_overrides() {
+    let check = |config: Config| {
+        let mut checked = Pipeline::new(State::new().with_config(config))
+            .library(true)
+            .parse_package(&fixture("shadowed_import"), &[])
+            .unwrap()
+            .lower()
+            .collect_definitions()
+            .check();
+        let result = checked.finish();
+        let codes = checked
+            .state()
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| diagnostic.code.clone().unwrap())
+            .collect::<Vec<_>>();
+
+        (result, checked.state().diagnostics().is_ok(), codes)
+    };
+
+    let (result, is_ok, codes) = check(Config::new());
+
+    assert!(result.is_ok());
+    assert!(is_ok);
+    assert_eq!(codes, ["W004"]);
+
+    let (result, is_ok, codes) =
+        check(Config::new().with_severity_override("W004", SeverityOverride::Deny));
+
+    assert!(matches!(
+        result,
+        Err(CompileError::CheckFailed { error_count: 1 })
+    ));
+    assert!(!is_ok);
+    assert_eq!(codes, ["W004"]);
+
+    let (result, is_ok, codes) =
+        check(Config::new().with_severity_override("W004", SeverityOverride::Allow));
+
+    assert!(result.is_ok());
+    assert!(is_ok);
+    assert!(codes.is_empty());
+}
@@ -0,0 +1,320 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use stellar_database::{Config, PackageData, Symbol};
+use stellar_diagnostics::DiagnosticsStatus;
+use stellar_driver::{CompileError, Session};
+use stellar_filesystem::source_map::{FileLoader, SourceMap};
+use stellar_interner::{IdentifierId, PathId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_manifest::PackageKind;
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+#[test]
+fn parse_package() {
+    let mut session = Session::new();
+    let package = session.parse_package(&fixture("shapes")).unwrap();
+
+    assert_eq!(session.parsed_modules().len(), 2);
+
+    let db = session.state().db();
+    let root_module = package.root_module(db);
+
+    assert_eq!(package.name(db), IdentifierId::from("shapes"));
+    assert_eq!(root_module.path(db).to_string(), "shapes");
+    assert_eq!(
+        root_module
+            .submodules(db)
+            .values()
+            .map(|submodule| submodule.path(db).to_string())
+            .collect::<Vec<_>>(),
+        ["shapes.circle"]
+    );
+}
+
+#[test]
+fn run_passes() {
+    let mut session = Session::new();
+    let package = session.parse_package(&fixture("shapes")).unwrap();
+
+    assert_eq!(session.lower().len(), 2);
+    assert!(session.parsed_modules().is_empty());
+
+    session.collect_definitions();
+    session.resolve_imports();
+
+    assert_eq!(session.emit_diagnostics(), DiagnosticsStatus::Ok);
+
+    let db = session.state().db();
+    let root_module = package.root_module(db);
+
+    assert!(matches!(
+        root_module
+            .module_item_symbols(db)
+            .get(&IdentifierId::from("unit_circle")),
+        Some(Symbol::Function(_))
+    ));
+    assert!(matches!(
+        root_module
+            .resolved_imports(db)
+            .get(&IdentifierId::from("Circle")),
+        Some(Symbol::Struct(_))
+    ));
+}
+
+#[test]
+fn binary_package_without_entry_point() {
+    let mut session = Session::new();
+    let package = session.parse_package(&fixture("shapes")).unwrap();
+
+    session.lower();
+    session.collect_definitions();
+    session.check_entry_point(package);
+
+    assert_eq!(session.package_kind(package), Some(PackageKind::Binary));
+    assert_eq!(
+        session.state().diagnostics().all()[0].code.as_deref(),
+        Some("E224")
+    );
+}
+
+#[test]
+fn library_package_without_entry_point() {
+    let mut session = Session::new();
+    let package = session
+        .parse_package(&fixture("private_import/geometry"))
+        .unwrap();
+
+    session.lower();
+    session.collect_definitions();
+    session.check_entry_point(package);
+
+    assert_eq!(session.package_kind(package), Some(PackageKind::Library));
+    assert!(session.state().diagnostics().is_empty());
+}
+
+#[test]
+fn parse_nested_package() {
+    let mut session = Session::new();
+    let package = session.parse_package(&fixture("nested")).unwrap();
+
+    assert_eq!(session.parsed_modules().len(), 3);
+
+    session.lower();
+    session.collect_definitions();
+    session.resolve_imports();
+
+    assert_eq!(session.emit_diagnostics(), DiagnosticsStatus::Ok);
+
+    let db = session.state().db();
+    let geometry = package
+        .root_module(db)
+        .submodule(db, IdentifierId::from("geometry"))
+        .unwrap();
+
+    assert_eq!(geometry.path(db).to_string(), "nested.geometry");
+    assert_eq!(
+        geometry
+            .submodule(db, IdentifierId::from("point"))
+            .unwrap()
+            .path(db)
+            .to_string(),
+        "nested.geometry.point"
+    );
+}
+
+#[test]
+fn missing_root_module() {
+    let mut session = Session::new();
+
+    let error = session.parse_package(&fixture("no_root")).unwrap_err();
+
+    assert!(matches!(
+        &error,
+        CompileError::MissingRootModule(directory) if *directory == fixture("no_root").join("src")
+    ));
+    assert_eq!(error.exit_code(), 2);
+}
+
+#[test]
+fn missing_manifest() {
+    let mut session = Session::new();
+
+    assert!(matches!(
+        session.parse_package(&fixture("missing")),
+        Err(CompileError::Io { path, .. }) if path == fixture("missing").join("package.toml")
+    ));
+}
+
+fn parse_fixture_file(session: &mut Session, filepath: &Path) -> Result<(), CompileError> {
+    let package = PackageData::alloc(
+        session.state_mut().db_mut(),
+        DUMMY_IDENTIFIER_ID,
+        DUMMY_PATH_ID,
+    );
+
+    session
+        .parse_file(package, DUMMY_IDENTIFIER_ID.into(), PathId::from(filepath))
+        .map(|_| ())
+}
+
+#[test]
+fn parse_missing_file() {
+    let mut session = Session::new();
+    let filepath = fixture("missing.sr");
+
+    let error = parse_fixture_file(&mut session, &filepath).unwrap_err();
+
+    let CompileError::Io { path, source } = &error else {
+        panic!("expected an IO error, got {error:?}");
+    };
+
+    assert_eq!(*path, filepath);
+    assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+    assert!(error.to_string().ends_with(&source.to_string()));
+    assert_eq!(error.exit_code(), 2);
+}
+
+#[test]
+fn syntax_error() {
+    let mut session = Session::new();
+
+    parse_fixture_file(&mut session, &fixture("syntax_error.sr")).unwrap();
+
+    let error = session.finish_parsing().unwrap_err();
+
+    assert!(matches!(
+        error,
+        CompileError::ParseFailed { error_count: 1 }
+    ));
+    assert_eq!(error.exit_code(), 1);
+    assert_eq!(
+        error.to_string(),
+        "cannot parse the source code due to 1 previous error"
+    );
+}
+
+#[test]
+fn check_error() {
+    let mut session = Session::new();
+
+    parse_fixture_file(&mut session, &fixture("duplicate_definition.sr")).unwrap();
+    session.finish_parsing().unwrap();
+
+    session.lower();
+    session.collect_definitions();
+
+    assert!(matches!(
+        session.finish_checking(),
+        Err(CompileError::CheckFailed { error_count: 1 })
+    ));
+}
+
+/// Parses an in-memory file with a given config and returns codes of
+/// reported diagnostics.
+fn parse_in_memory_file(config: Config, source: &str) -> Vec<String> {
+    let mut session = Session::new().with_config(config);
+    let filepath = PathId::from("session/in_memory.sr");
+    session
+        .state_mut()
+        .source_map_mut()
+        .set_override(filepath, source);
+
+    let package = PackageData::alloc(
+        session.state_mut().db_mut(),
+        DUMMY_IDENTIFIER_ID,
+        DUMMY_PATH_ID,
+    );
+    let module = session
+        .parse_file(package, DUMMY_IDENTIFIER_ID.into(), filepath)
+        .unwrap();
+
+    session.lower();
+    session.collect_definitions();
+
+    let codes = session
+        .state()
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.code.clone().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(module.load_failed(session.state().db()), !codes.is_empty());
+    assert_eq!(
+        module.module_item_symbols(session.state().db()).is_empty(),
+        !codes.is_empty()
+    );
+
+    codes
+}
+
+#[test]
+fn max_file_size() {
+    let source = "fun f() {}";
+
+    assert!(parse_in_memory_file(Config::new().with_max_file_size(10), source).is_empty());
+    assert_eq!(
+        parse_in_memory_file(Config::new().with_max_file_size(9), source),
+        ["E010"]
+    );
+}
+
+/// Pretends, that every file is 500 MB large, and fails the test if a file
+/// is loaded.
+struct HugeFileLoader;
+
+impl FileLoader for HugeFileLoader {
+    fn load(&self, path: PathId) -> io::Result<Vec<u8>> {
+        panic!("{} must not be loaded", path.as_path().display());
+    }
+
+    fn size(&self, _: PathId) -> io::Result<usize> {
+        Ok(500 * 1024 * 1024)
+    }
+}
+
+#[test]
+fn oversized_file_is_not_loaded() {
+    let mut session = Session::new();
+    *session.state_mut().source_map_mut() = SourceMap::with_loader(HugeFileLoader);
+
+    let filepath = PathId::from("session/huge.sr");
+    let package = PackageData::alloc(
+        session.state_mut().db_mut(),
+        DUMMY_IDENTIFIER_ID,
+        DUMMY_PATH_ID,
+    );
+    let module = session
+        .parse_file(package, DUMMY_IDENTIFIER_ID.into(), filepath)
+        .unwrap();
+
+    assert!(module.load_failed(session.state().db()));
+    assert!(!session.state().source_map().is_loaded(filepath));
+    assert_eq!(
+        session
+            .state()
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| diagnostic.code.as_deref().unwrap())
+            .collect::<Vec<_>>(),
+        ["E010"]
+    );
+}
+
+#[test]
+fn max_token_count() {
+    // `fun`, `f`, `(`, `)`, `{`, `}`, comments are not counted.
+    let source = "fun f() {} // comment";
+
+    assert!(parse_in_memory_file(Config::new().with_max_token_count(6), source).is_empty());
+    assert_eq!(
+        parse_in_memory_file(Config::new().with_max_token_count(5), source),
+        ["E011"]
+    );
+}
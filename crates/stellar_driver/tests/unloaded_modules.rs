@@ -0,0 +1,127 @@
+#![cfg(unix)]
+
+use std::{
+    fs,
+    os::unix::fs::symlink,
+    path::{Path, PathBuf},
+};
+
+use stellar_database::Config;
+use stellar_driver::Session;
+use stellar_interner::IdentifierId;
+
+/// Creates a temporary directory for a test.
+fn temporary_directory(test: &str) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("stellar_driver_{test}_{}", std::process::id()));
+
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+
+    directory
+}
+
+/// Writes a package, that has a submodule with a Latin-1 encoded string
+/// literal.
+fn write_package_with_latin1_module(root: &Path) {
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("package.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(root.join("src/package.sr"), "").unwrap();
+    fs::write(root.join("src/latin1.sr"), b"fun f() {\n\t\"caf\xe9\";\n}").unwrap();
+}
+
+#[test]
+fn missing_module_file() {
+    let root = temporary_directory("missing_module_file");
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("package.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(root.join("src/package.sr"), "import app.broken.Foo;").unwrap();
+    fs::write(root.join("src/other.sr"), "fun f() {}\nfun f() {}").unwrap();
+
+    // The module is discovered, but its file cannot be read.
+    symlink(root.join("src/deleted.sr"), root.join("src/broken.sr")).unwrap();
+
+    let mut session = Session::new();
+    let package = session.parse_package(&root).unwrap();
+
+    session.lower();
+    session.collect_definitions();
+    session.resolve_imports();
+
+    let mut codes = session
+        .state()
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.code.clone().unwrap())
+        .collect::<Vec<_>>();
+    codes.sort();
+
+    // The duplicate definition in `other.sr` is still reported, and the
+    // import from the broken module isn't reported as an unresolved name.
+    assert_eq!(codes, ["E007", "E100", "E114"]);
+
+    let db = session.state().db();
+    let broken = package.root_module(db).submodules(db)[&IdentifierId::from("broken")];
+
+    assert!(broken.load_failed(db));
+    assert!(broken.module_item_symbols(db).is_empty());
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn invalid_utf8_module_file() {
+    let root = temporary_directory("invalid_utf8_module_file");
+    write_package_with_latin1_module(&root);
+
+    let mut session = Session::new();
+    let package = session.parse_package(&root).unwrap();
+
+    let diagnostics = session.state().diagnostics().all();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code.as_deref(), Some("E009"));
+    assert!(diagnostics[0]
+        .notes
+        .iter()
+        .any(|note| note.contains("byte offset 15")));
+
+    let db = session.state().db();
+    let module = package.root_module(db).submodules(db)[&IdentifierId::from("latin1")];
+
+    assert!(module.load_failed(db));
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn lenient_utf8() {
+    let root = temporary_directory("lenient_utf8");
+    write_package_with_latin1_module(&root);
+
+    let mut session = Session::new().with_config(Config::new().with_lenient_utf8(true));
+    let package = session.parse_package(&root).unwrap();
+
+    session.lower();
+    session.collect_definitions();
+
+    assert!(session.state().diagnostics().is_ok());
+
+    let db = session.state().db();
+    let module = package.root_module(db).submodules(db)[&IdentifierId::from("latin1")];
+
+    assert!(!module.load_failed(db));
+    assert!(module
+        .module_item_symbols(db)
+        .contains_key(&IdentifierId::from("f")));
+
+    fs::remove_dir_all(root).unwrap();
+}
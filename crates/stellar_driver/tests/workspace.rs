@@ -0,0 +1,172 @@
+use std::{
+    cell::RefCell,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use stellar_database::State;
+use stellar_driver::{CompileError, Phase, PhaseEvent, Pipeline, Session};
+
+/// Creates a temporary directory for a test.
+fn temporary_directory(test: &str) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("stellar_driver_{test}_{}", std::process::id()));
+
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+
+    directory
+}
+
+/// Writes a library package with a given manifest `[dependencies]` section
+/// and the root module.
+fn write_package(directory: &Path, name: &str, dependencies: &str, source: &str) {
+    fs::create_dir_all(directory.join("src")).unwrap();
+    fs::write(
+        directory.join("package.toml"),
+        format!(
+            "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nkind = \"library\"\n\n[dependencies]\n{dependencies}"
+        ),
+    )
+    .unwrap();
+    fs::write(directory.join("src/package.sr"), source).unwrap();
+}
+
+fn write_workspace(root: &Path, members: &[&str]) {
+    let members = members
+        .iter()
+        .map(|member| format!("\"{member}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    fs::write(
+        root.join("package.toml"),
+        format!("[workspace]\nmembers = [{members}]\n"),
+    )
+    .unwrap();
+}
+
+#[test]
+fn members_are_checked_separately() {
+    let root = temporary_directory("members_are_checked_separately");
+
+    write_package(
+        &root.join("shared"),
+        "shared",
+        "",
+        "pub struct Point { pub x: int32 }",
+    );
+    write_package(
+        &root.join("geometry"),
+        "geometry",
+        "shared = { path = \"../shared\" }",
+        "import shared.Point;\n\npub fun origin(): Point {}",
+    );
+    write_package(
+        &root.join("render"),
+        "render",
+        "shared = { path = \"../shared\" }",
+        "import shared.Point;\n\npub fun draw(point: Point) {}",
+    );
+    write_package(
+        &root.join("broken"),
+        "broken",
+        "",
+        "pub fun foo() {}\n\npub fun foo() {}",
+    );
+    write_workspace(&root, &["render", "broken", "geometry"]);
+
+    let events = Rc::new(RefCell::new(Vec::<PhaseEvent>::new()));
+    let recorded = Rc::clone(&events);
+
+    let mut checked = Pipeline::new(State::new())
+        .on_phase(move |event| recorded.borrow_mut().push(event))
+        .parse_workspace(&root)
+        .unwrap()
+        .lower()
+        .collect_definitions()
+        .check();
+
+    let names = checked
+        .members()
+        .iter()
+        .map(|member| member.name.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(names, ["broken", "geometry", "render"]);
+
+    let reports = checked
+        .members()
+        .iter()
+        .map(|member| member.package)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|package| checked.finish_member(package))
+        .collect::<Vec<_>>();
+    let results = reports
+        .iter()
+        .map(|report| (report.name.as_str(), report.is_ok()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        results,
+        [("broken", false), ("geometry", true), ("render", true)]
+    );
+    assert_eq!(reports[0].error_count, 1);
+
+    // The shared dependency is parsed and collected once, even though two
+    // members depend on it.
+    let events = events.borrow();
+
+    for phase in [Phase::Parse, Phase::CollectDefinitions] {
+        assert_eq!(
+            events
+                .iter()
+                .filter(|event| event.phase == phase
+                    && event.filepath.as_path().ends_with("shared/src/package.sr"))
+                .count(),
+            1
+        );
+    }
+
+    assert!(matches!(
+        checked.finish(),
+        Err(CompileError::CheckFailed { error_count: 1 })
+    ));
+}
+
+#[test]
+fn member_discovery_errors() {
+    let root = temporary_directory("member_discovery_errors");
+
+    write_package(&root.join("json"), "json", "", "");
+    write_package(&root.join("json_copy"), "json", "", "");
+    write_workspace(&root, &["json", "http", "json_copy"]);
+
+    let mut session = Session::new();
+    let members = session.parse_workspace(&root).unwrap();
+
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0].directory, root.join("json"));
+
+    let codes = session
+        .state()
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.code.as_deref().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(codes, ["E017", "E018"]);
+}
+
+#[test]
+fn missing_workspace_section() {
+    let root = temporary_directory("missing_workspace_section");
+
+    write_package(&root, "json", "", "");
+
+    let result = Session::new().parse_workspace(&root);
+
+    assert!(matches!(result, Err(CompileError::MissingWorkspace(_))));
+}
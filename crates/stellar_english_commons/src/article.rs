@@ -0,0 +1,38 @@
+//! Allows to choose indefinite articles for words.
+
+/// Returns the indefinite article for a given word, e.g. `a` for `function`
+/// and `an` for `enum item`.
+///
+/// # Example
+///
+/// ```
+/// use stellar_english_commons::article::a_or_an;
+///
+/// assert_eq!(a_or_an("function"), "a");
+/// assert_eq!(a_or_an("enum item"), "an");
+/// assert_eq!(a_or_an("Interface"), "an");
+/// ```
+#[must_use]
+pub fn a_or_an(word: impl AsRef<str>) -> &'static str {
+    match word.as_ref().chars().next() {
+        Some('a' | 'e' | 'i' | 'o' | 'u' | 'A' | 'E' | 'I' | 'O' | 'U') => "an",
+        _ => "a",
+    }
+}
+
+/// Prepends the indefinite article to a given word.
+///
+/// # Example
+///
+/// ```
+/// use stellar_english_commons::article::with_article;
+///
+/// assert_eq!(with_article("struct"), "a struct");
+/// assert_eq!(with_article("enum"), "an enum");
+/// ```
+#[must_use]
+pub fn with_article(word: impl AsRef<str>) -> String {
+    let word = word.as_ref();
+
+    format!("{} {word}", a_or_an(word))
+}
@@ -0,0 +1,197 @@
+//! Allows to check and convert cases of identifiers, e.g. `UpperCamelCase`
+//! and `lower_snake_case`.
+//!
+//! Words of an identifier are separated by underscores and by case boundaries:
+//!
+//! * a lowercase letter or a digit followed by an uppercase letter starts
+//!   a new word: `fooBar` consists of `foo` and `Bar`;
+//! * an uppercase letter followed by an uppercase letter and then
+//!   a lowercase one starts a new word, so acronyms are kept as a single
+//!   word: `HTTPServer` consists of `HTTP` and `Server`.
+//!
+//! Leading underscores are kept as they are, as they are commonly used
+//! to mark unused names.
+
+/// Returns `true` if a given name is in `UpperCamelCase`: it starts with
+/// an uppercase letter and contains no underscores.
+///
+/// Consecutive uppercase letters are allowed, so acronyms like `HTTPServer`
+/// are accepted.
+///
+/// # Example
+///
+/// ```
+/// use stellar_english_commons::case::is_upper_camel_case;
+///
+/// assert!(is_upper_camel_case("HTTPServer"));
+/// assert!(is_upper_camel_case("List2"));
+/// assert!(!is_upper_camel_case("my_struct"));
+/// assert!(!is_upper_camel_case("My_Struct"));
+/// ```
+#[must_use]
+pub fn is_upper_camel_case(name: impl AsRef<str>) -> bool {
+    let name = name.as_ref().trim_start_matches('_');
+
+    name.chars().next().is_some_and(char::is_uppercase) && !name.contains('_')
+}
+
+/// Returns `true` if a given name is in `lower_snake_case`: it contains
+/// no uppercase letters.
+///
+/// # Example
+///
+/// ```
+/// use stellar_english_commons::case::is_snake_case;
+///
+/// assert!(is_snake_case("read_to_string"));
+/// assert!(is_snake_case("_unused"));
+/// assert!(!is_snake_case("readToString"));
+/// ```
+#[must_use]
+pub fn is_snake_case(name: impl AsRef<str>) -> bool {
+    !name.as_ref().chars().any(char::is_uppercase)
+}
+
+/// Returns `true` if a given name is in `SCREAMING_SNAKE_CASE`: it contains
+/// no lowercase letters.
+///
+/// # Example
+///
+/// ```
+/// use stellar_english_commons::case::is_screaming_snake_case;
+///
+/// assert!(is_screaming_snake_case("MAX_SIZE"));
+/// assert!(!is_screaming_snake_case("MaxSize"));
+/// ```
+#[must_use]
+pub fn is_screaming_snake_case(name: impl AsRef<str>) -> bool {
+    !name.as_ref().chars().any(char::is_lowercase)
+}
+
+/// Converts a given name into `UpperCamelCase`. Every word is capitalized
+/// and the rest of its letters are lowercased.
+///
+/// # Example
+///
+/// ```
+/// use stellar_english_commons::case::to_upper_camel_case;
+///
+/// assert_eq!(to_upper_camel_case("my_struct"), "MyStruct");
+/// assert_eq!(to_upper_camel_case("HTTP_server"), "HttpServer");
+/// assert_eq!(to_upper_camel_case("myStruct"), "MyStruct");
+/// ```
+#[must_use]
+pub fn to_upper_camel_case(name: impl AsRef<str>) -> String {
+    let (underscores, words) = split_words(name.as_ref());
+
+    let mut result = underscores.to_owned();
+
+    for word in words {
+        let mut chars = word.chars();
+
+        if let Some(first) = chars.next() {
+            result.extend(first.to_uppercase());
+            result.extend(chars.flat_map(char::to_lowercase));
+        }
+    }
+
+    result
+}
+
+/// Converts a given name into `lower_snake_case`.
+///
+/// # Example
+///
+/// ```
+/// use stellar_english_commons::case::to_snake_case;
+///
+/// assert_eq!(to_snake_case("readToString"), "read_to_string");
+/// assert_eq!(to_snake_case("HTTPServer"), "http_server");
+/// assert_eq!(to_snake_case("_Unused"), "_unused");
+/// ```
+#[must_use]
+pub fn to_snake_case(name: impl AsRef<str>) -> String {
+    join_words(name.as_ref(), str::to_lowercase)
+}
+
+/// Converts a given name into `SCREAMING_SNAKE_CASE`.
+///
+/// # Example
+///
+/// ```
+/// use stellar_english_commons::case::to_screaming_snake_case;
+///
+/// assert_eq!(to_screaming_snake_case("maxSize"), "MAX_SIZE");
+/// assert_eq!(to_screaming_snake_case("HTTPServer"), "HTTP_SERVER");
+/// ```
+#[must_use]
+pub fn to_screaming_snake_case(name: impl AsRef<str>) -> String {
+    join_words(name.as_ref(), str::to_uppercase)
+}
+
+fn join_words(name: &str, convert: impl Fn(&str) -> String) -> String {
+    let (underscores, words) = split_words(name);
+
+    let words = words.iter().map(|word| convert(word)).collect::<Vec<_>>();
+
+    format!("{underscores}{}", words.join("_"))
+}
+
+/// Splits a name into leading underscores and words.
+fn split_words(name: &str) -> (&str, Vec<&str>) {
+    let trimmed = name.trim_start_matches('_');
+    let underscores = &name[..name.len() - trimmed.len()];
+
+    let mut words = vec![];
+
+    for part in trimmed.split('_').filter(|part| !part.is_empty()) {
+        let chars = part.char_indices().collect::<Vec<_>>();
+        let mut start = 0;
+
+        for window in chars.windows(2) {
+            let [(_, current), (index, next)] = [window[0], window[1]];
+
+            let lower_to_upper =
+                (current.is_lowercase() || current.is_ascii_digit()) && next.is_uppercase();
+
+            // `HTTPServer`: the word `Server` starts at `S`.
+            let acronym_end = current.is_uppercase()
+                && next.is_uppercase()
+                && part[index..].chars().nth(1).is_some_and(char::is_lowercase);
+
+            if lower_to_upper || acronym_end {
+                words.push(&part[start..index]);
+                start = index;
+            }
+        }
+
+        words.push(&part[start..]);
+    }
+
+    (underscores, words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_upper_camel_case, split_words, to_snake_case, to_upper_camel_case};
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(split_words("HTTPServer").1, ["HTTP", "Server"]);
+        assert_eq!(
+            split_words("parseHTTP2Request").1,
+            ["parse", "HTTP2", "Request"]
+        );
+        assert_eq!(split_words("__foo__bar").1, ["foo", "bar"]);
+        assert_eq!(split_words("__foo__bar").0, "__");
+        assert_eq!(split_words("X").1, ["X"]);
+    }
+
+    #[test]
+    fn test_acronyms() {
+        assert!(is_upper_camel_case("HTTPServer"));
+        assert!(is_upper_camel_case("IO"));
+        assert_eq!(to_snake_case("IOError"), "io_error");
+        assert_eq!(to_upper_camel_case("io_error"), "IoError");
+    }
+}
@@ -58,6 +58,8 @@
     clippy::option_if_let_else
 )]
 
+pub mod article;
+pub mod case;
 pub mod enumeration;
 pub mod ordinal;
 pub mod pluralize;
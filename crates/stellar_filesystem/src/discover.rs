@@ -0,0 +1,236 @@
+//! Discovers modules of a package in its source directory.
+//!
+//! Modules are laid out in the following way:
+//!
+//! ```txt
+//! src
+//! |_ package.sr (the root module)
+//! |_ io.sr (the module `io`)
+//! |_ io (submodules of `io`)
+//!    |_ file.sr (the module `io.file`)
+//! ```
+//!
+//! A directory without a file with the same name is a module without
+//! source code. Hidden files and directories, as well as build directories
+//! (see [`IGNORED_DIRECTORIES`]), are skipped.
+//!
+//! See [`discover_package()`] for more details.
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use stellar_interner::PathId;
+
+use crate::path_resolver::PackagePathResolver;
+
+/// The extension of source files.
+pub const SOURCE_FILE_EXTENSION: &str = "sr";
+
+/// The name of the root module file without the extension.
+pub const ROOT_MODULE_NAME: &str = "package";
+
+/// Directories, that are never treated as modules.
+pub const IGNORED_DIRECTORIES: &[&str] = &["build", "target"];
+
+/// Modules of a package discovered by [`discover_package()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageLayout {
+    /// The path of the root module file.
+    pub root_module: PathId,
+
+    /// Submodules of the root module sorted by their names.
+    pub submodules: Vec<ModuleLayout>,
+}
+
+/// A module discovered by [`discover_package()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleLayout {
+    /// The name of the module.
+    pub name: String,
+
+    /// The path of the module's source file, or `None` if the module is
+    /// a directory without a source file.
+    pub filepath: Option<PathId>,
+
+    /// The path of the directory with submodules of the module, if it exists.
+    pub directory: Option<PathId>,
+
+    /// Submodules of the module sorted by their names.
+    pub submodules: Vec<Self>,
+}
+
+/// An error, that occurred when discovering modules of a package.
+#[derive(Debug)]
+pub enum DiscoverError {
+    /// A file or a directory cannot be read.
+    Io {
+        /// The path of the file or the directory.
+        path: PathBuf,
+
+        /// The underlying error.
+        source: io::Error,
+    },
+
+    /// The source directory doesn't contain the root module file.
+    MissingRootModule(PathBuf),
+
+    /// The name of a file or a directory is not a valid identifier, e.g.
+    /// `my-module.sr`.
+    InvalidModuleName(PathBuf),
+
+    /// A symbolic link points to one of the directories containing it.
+    SymlinkCycle(PathBuf),
+}
+
+impl Display for DiscoverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => {
+                write!(f, "cannot access {}: {source}", path.display())
+            }
+            Self::MissingRootModule(directory) => write!(
+                f,
+                "cannot find the root module `{ROOT_MODULE_NAME}.{SOURCE_FILE_EXTENSION}` in {}",
+                directory.display()
+            ),
+            Self::InvalidModuleName(path) => {
+                write!(f, "name of {} is not a valid module name", path.display())
+            }
+            Self::SymlinkCycle(path) => {
+                write!(f, "symbolic link cycle detected at {}", path.display())
+            }
+        }
+    }
+}
+
+impl Error for DiscoverError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Discovers modules in the source directory of a package with a given
+/// root directory (see [`PackagePathResolver::source_directory()`]).
+///
+/// # Errors
+/// Returns an error if some of the directories cannot be read, if the root
+/// module file is missing, if some of the module names are not valid
+/// identifiers or if symbolic links form a cycle.
+pub fn discover_package(root: &Path) -> Result<PackageLayout, DiscoverError> {
+    let source_directory = PackagePathResolver::new(root).source_directory();
+    let root_module = source_directory.join(format!("{ROOT_MODULE_NAME}.{SOURCE_FILE_EXTENSION}"));
+
+    if !root_module.is_file() {
+        return Err(DiscoverError::MissingRootModule(source_directory));
+    }
+
+    let mut ancestors = vec![];
+    let mut submodules = discover_directory(&source_directory, &mut ancestors)?;
+    submodules.retain(|module| module.name != ROOT_MODULE_NAME);
+
+    Ok(PackageLayout {
+        root_module: PathId::from(root_module),
+        submodules,
+    })
+}
+
+/// Discovers modules in a given directory. `ancestors` are canonical paths
+/// of directories, that are being discovered, and are used to detect
+/// symbolic link cycles.
+fn discover_directory(
+    directory: &Path,
+    ancestors: &mut Vec<PathBuf>,
+) -> Result<Vec<ModuleLayout>, DiscoverError> {
+    let io_error = |source| DiscoverError::Io {
+        path: directory.to_owned(),
+        source,
+    };
+
+    let canonical_path = fs::canonicalize(directory).map_err(io_error)?;
+
+    if ancestors.contains(&canonical_path) {
+        return Err(DiscoverError::SymlinkCycle(directory.to_owned()));
+    }
+
+    let mut entries = fs::read_dir(directory)
+        .map_err(io_error)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io_error)?;
+    entries.sort();
+
+    ancestors.push(canonical_path);
+
+    let mut modules = BTreeMap::<String, ModuleLayout>::new();
+
+    for path in entries {
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            return Err(DiscoverError::InvalidModuleName(path));
+        };
+
+        if name.starts_with('.') {
+            continue;
+        }
+
+        if path.is_dir() {
+            if IGNORED_DIRECTORIES.contains(&name) {
+                continue;
+            }
+
+            let name = module_name(&path, name)?;
+            let submodules = discover_directory(&path, ancestors)?;
+
+            let module = module_entry(&mut modules, name);
+            module.directory = Some(PathId::from(&path));
+            module.submodules = submodules;
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == SOURCE_FILE_EXTENSION)
+        {
+            let stem = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or_default();
+            let name = module_name(&path, stem)?;
+
+            module_entry(&mut modules, name).filepath = Some(PathId::from(&path));
+        }
+    }
+
+    ancestors.pop();
+
+    Ok(modules.into_values().collect())
+}
+
+fn module_entry(modules: &mut BTreeMap<String, ModuleLayout>, name: String) -> &mut ModuleLayout {
+    modules.entry(name.clone()).or_insert_with(|| ModuleLayout {
+        name,
+        filepath: None,
+        directory: None,
+        submodules: vec![],
+    })
+}
+
+/// Returns a module name if it is a valid identifier.
+fn module_name(path: &Path, name: &str) -> Result<String, DiscoverError> {
+    let mut chars = name.chars();
+
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c == '_' || unicode_xid::UnicodeXID::is_xid_start(c))
+        && chars.all(unicode_xid::UnicodeXID::is_xid_continue);
+
+    if is_identifier {
+        Ok(name.to_owned())
+    } else {
+        Err(DiscoverError::InvalidModuleName(path.to_owned()))
+    }
+}
@@ -60,8 +60,10 @@
     clippy::option_if_let_else
 )]
 
+pub mod discover;
 pub mod file_utils;
 pub mod in_memory_file;
 pub mod in_memory_file_storage;
 pub mod location;
 pub mod path_resolver;
+pub mod source_map;
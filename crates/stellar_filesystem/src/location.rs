@@ -8,7 +8,7 @@ use std::{
 use derive_more::Display;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use stellar_interner::PathId;
+use stellar_interner::{PathId, DUMMY_PATH_ID};
 
 /// Represents location in the source text.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -24,6 +24,19 @@ pub struct Location {
     pub end: ByteOffset,
 }
 
+/// Implemented by every syntax tree node, that has a location in the source text.
+pub trait HasLocation {
+    /// Returns the location of the node.
+    fn location(&self) -> Location;
+}
+
+impl HasLocation for Location {
+    #[inline]
+    fn location(&self) -> Location {
+        *self
+    }
+}
+
 impl Index<Location> for str {
     type Output = Self;
 
@@ -139,7 +152,7 @@ impl ByteOffset {
 /// because this can result in undefined behavior with diagnostics and
 /// debug information!
 pub const DUMMY_LOCATION: Location = Location {
-    filepath: PathId(0),
+    filepath: DUMMY_PATH_ID,
     start: ByteOffset(0),
     end: ByteOffset(0),
 };
@@ -204,6 +217,165 @@ impl Location {
     pub const fn end_byte_location(self) -> Self {
         self.end.previous_byte_location_at(self.filepath)
     }
+
+    /// Returns the smallest location, that contains both locations, e.g.
+    /// the location of a binary expression is the merge of locations of its
+    /// operands.
+    ///
+    /// Both locations must be in the same file.
+    ///
+    /// ```
+    /// # use stellar_filesystem::location::{Location, ByteOffset};
+    /// # use stellar_interner::DUMMY_PATH_ID;
+    /// let left = Location { filepath: DUMMY_PATH_ID, start: ByteOffset(0), end: ByteOffset(1) };
+    /// let right = Location { filepath: DUMMY_PATH_ID, start: ByteOffset(4), end: ByteOffset(5) };
+    ///
+    /// assert_eq!(
+    ///     left.merge(right),
+    ///     Location { filepath: DUMMY_PATH_ID, start: ByteOffset(0), end: ByteOffset(5) }
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn merge(self, other: Self) -> Self {
+        debug_assert_eq!(
+            self.filepath, other.filepath,
+            "cannot merge locations in different files"
+        );
+
+        Self {
+            filepath: self.filepath,
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
+    /// Returns the location of the gap between two locations, e.g. whitespace
+    /// between two tokens. `left` must end before `right` starts.
+    ///
+    /// ```
+    /// # use stellar_filesystem::location::{Location, ByteOffset};
+    /// # use stellar_interner::DUMMY_PATH_ID;
+    /// let left = Location { filepath: DUMMY_PATH_ID, start: ByteOffset(0), end: ByteOffset(1) };
+    /// let right = Location { filepath: DUMMY_PATH_ID, start: ByteOffset(4), end: ByteOffset(5) };
+    ///
+    /// assert_eq!(
+    ///     Location::between(left, right),
+    ///     Location { filepath: DUMMY_PATH_ID, start: ByteOffset(1), end: ByteOffset(4) }
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn between(left: Self, right: Self) -> Self {
+        debug_assert_eq!(
+            left.filepath, right.filepath,
+            "cannot get a gap between locations in different files"
+        );
+        debug_assert!(left.end <= right.start, "locations overlap");
+
+        Self {
+            filepath: left.filepath,
+            start: left.end,
+            end: right.start,
+        }
+    }
+
+    /// Returns the empty location at the start of the location.
+    #[inline]
+    #[must_use]
+    pub const fn shrink_to_start(self) -> Self {
+        Self {
+            filepath: self.filepath,
+            start: self.start,
+            end: self.start,
+        }
+    }
+
+    /// Returns the empty location at the end of the location.
+    #[inline]
+    #[must_use]
+    pub const fn shrink_to_end(self) -> Self {
+        Self {
+            filepath: self.filepath,
+            start: self.end,
+            end: self.end,
+        }
+    }
+
+    /// Returns `true` if a byte with a given offset is in the location.
+    #[inline]
+    #[must_use]
+    pub const fn contains(self, offset: ByteOffset) -> bool {
+        self.start.0 <= offset.0 && offset.0 < self.end.0
+    }
+
+    /// Returns `true` if the locations are in the same file and have at least
+    /// one byte in common.
+    #[inline]
+    #[must_use]
+    pub fn overlaps(self, other: Self) -> bool {
+        self.filepath == other.filepath && self.start.max(other.start) < self.end.min(other.end)
+    }
+
+    /// Returns `true` if the location doesn't point into any real source file,
+    /// e.g. if it is [`DUMMY_LOCATION`].
+    ///
+    /// ```
+    /// # use stellar_filesystem::location::{Location, ByteOffset, DUMMY_LOCATION};
+    /// # use stellar_interner::PathId;
+    /// assert!(DUMMY_LOCATION.is_dummy());
+    /// assert!(!Location {
+    ///     filepath: PathId::from("test.sr"),
+    ///     start: ByteOffset(0),
+    ///     end: ByteOffset(3)
+    /// }
+    /// .is_dummy());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_dummy(self) -> bool {
+        self.filepath == DUMMY_PATH_ID
+    }
+
+    /// Returns the text of a source, that the location points to, or `None`
+    /// if the location is out of the source's bounds, or if its start or end
+    /// is not on a UTF-8 character boundary.
+    ///
+    /// ```
+    /// # use stellar_filesystem::location::{Location, ByteOffset};
+    /// # use stellar_interner::DUMMY_PATH_ID;
+    /// let location = Location { filepath: DUMMY_PATH_ID, start: ByteOffset(4), end: ByteOffset(8) };
+    ///
+    /// assert_eq!(location.slice("fun main() {}"), Some("main"));
+    /// assert_eq!(location.slice("fun"), None);
+    /// assert_eq!(location.slice("fun abcé"), None); // `é` takes 2 bytes
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn slice(self, source: &str) -> Option<&str> {
+        source.get(self.start.0..self.end.0)
+    }
+
+    /// Same as [`Location::slice()`], but offsets past the end of the source
+    /// are clamped to the end, so only offsets, that are not on UTF-8
+    /// character boundaries, result in `None`.
+    ///
+    /// ```
+    /// # use stellar_filesystem::location::{Location, ByteOffset};
+    /// # use stellar_interner::DUMMY_PATH_ID;
+    /// let location = Location { filepath: DUMMY_PATH_ID, start: ByteOffset(4), end: ByteOffset(8) };
+    ///
+    /// assert_eq!(location.slice_clamped("fun ma"), Some("ma"));
+    /// assert_eq!(location.slice_clamped("fun"), Some(""));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn slice_clamped(self, source: &str) -> Option<&str> {
+        let end = self.end.0.min(source.len());
+        let start = self.start.0.min(end);
+
+        source.get(start..end)
+    }
 }
 
 impl From<Location> for Range<usize> {
@@ -0,0 +1,344 @@
+//! Defines [`SourceMap`] - a cache of source files, so that every file is read
+//! only once, no matter how many passes need its content.
+//!
+//! The parser, the diagnostics emitter and line/column conversion all get
+//! source text from the same [`SourceMap`], so they always see the same
+//! content of a file, even if it changes on disk in the middle of
+//! compilation.
+//!
+//! Source files must be valid UTF-8. A file, that is not, fails to load with
+//! [`InvalidUtf8Error`], unless lossy decoding is enabled with
+//! [`SourceMap::set_lenient_utf8()`].
+
+use std::{error::Error, fmt, fs, io, sync::Arc};
+
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::PathId;
+
+use crate::location::{ByteOffset, Location};
+
+/// Loads content of source files for [`SourceMap`].
+pub trait FileLoader: Send + Sync {
+    /// Returns raw bytes of a file with a given path. The bytes are decoded
+    /// as UTF-8 by [`SourceMap`].
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read.
+    fn load(&self, path: PathId) -> io::Result<Vec<u8>>;
+
+    /// Returns the size of a file in bytes, so that e.g. a file, that is too
+    /// large, can be rejected without loading it. By default, the file is
+    /// loaded to get its size.
+    ///
+    /// # Errors
+    /// Returns an error if the size of the file cannot be determined.
+    fn size(&self, path: PathId) -> io::Result<usize> {
+        self.load(path).map(|bytes| bytes.len())
+    }
+}
+
+/// Reads source files from disk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiskFileLoader;
+
+impl FileLoader for DiskFileLoader {
+    #[inline]
+    fn load(&self, path: PathId) -> io::Result<Vec<u8>> {
+        fs::read(path.as_path())
+    }
+
+    #[inline]
+    fn size(&self, path: PathId) -> io::Result<usize> {
+        fs::metadata(path.as_path())
+            .map(|metadata| usize::try_from(metadata.len()).unwrap_or(usize::MAX))
+    }
+}
+
+/// An error, that occurs when a source file is not valid UTF-8.
+///
+/// It is returned by [`SourceMap::file_or_load()`] wrapped into an
+/// [`io::Error`] of kind [`io::ErrorKind::InvalidData`], see
+/// [`InvalidUtf8Error::from_io_error()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidUtf8Error {
+    /// The path of the file.
+    pub path: PathId,
+
+    /// The byte offset of the first invalid byte sequence.
+    pub offset: ByteOffset,
+}
+
+impl InvalidUtf8Error {
+    /// Returns the encoding error, that caused a given IO error, if the
+    /// error was caused by a file, that is not valid UTF-8.
+    #[inline]
+    #[must_use]
+    pub fn from_io_error(error: &io::Error) -> Option<&Self> {
+        error.get_ref()?.downcast_ref()
+    }
+}
+
+impl fmt::Display for InvalidUtf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid UTF-8 sequence at byte offset {}", self.offset.0)
+    }
+}
+
+impl Error for InvalidUtf8Error {}
+
+/// Byte offsets of line starts in a source text, used to convert byte
+/// offsets into line numbers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+    source_len: usize,
+}
+
+impl LineIndex {
+    /// Computes line starts of a given source text.
+    #[must_use]
+    pub fn new(source: &str) -> Self {
+        Self {
+            line_starts: std::iter::once(0)
+                .chain(source.match_indices('\n').map(|(idx, _)| idx + 1))
+                .collect(),
+            source_len: source.len(),
+        }
+    }
+
+    /// Returns the number of lines.
+    #[inline]
+    #[must_use]
+    pub const fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the index of the line, that contains a given byte offset.
+    /// Offsets past the end of the text belong to the last line.
+    #[must_use]
+    pub fn line_index(&self, byte_offset: ByteOffset) -> usize {
+        self.line_starts
+            .binary_search(&byte_offset.0)
+            .unwrap_or_else(|next_line| next_line - 1)
+    }
+
+    /// Returns the byte offset of the start of a given line. The line right
+    /// after the last one starts at the end of the text.
+    #[must_use]
+    pub fn line_start(&self, line_index: usize) -> Option<ByteOffset> {
+        if line_index == self.line_starts.len() {
+            return Some(ByteOffset(self.source_len));
+        }
+
+        self.line_starts.get(line_index).copied().map(ByteOffset)
+    }
+}
+
+/// A source file cached in [`SourceMap`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceFile {
+    /// The path of the file.
+    pub path: PathId,
+
+    /// The content of the file.
+    pub source: Arc<str>,
+
+    /// Line starts of the content.
+    pub line_index: LineIndex,
+}
+
+impl SourceFile {
+    /// Creates a new source file with a given content.
+    #[must_use]
+    pub fn new(path: PathId, source: Arc<str>) -> Self {
+        Self {
+            path,
+            line_index: LineIndex::new(&source),
+            source,
+        }
+    }
+}
+
+/// Content of source files, each of which is loaded only once.
+///
+/// Content of a file can be overridden with an in-memory buffer, e.g. for
+/// a file, that is edited, but not saved yet, see [`SourceMap::set_override()`].
+pub struct SourceMap {
+    loader: Box<dyn FileLoader>,
+    files: FxHashMap<PathId, SourceFile>,
+    overrides: FxHashMap<PathId, Arc<str>>,
+    lenient_utf8: bool,
+}
+
+impl Default for SourceMap {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for SourceMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SourceMap")
+            .field("files", &self.files.keys().collect::<Vec<_>>())
+            .field("overrides", &self.overrides.keys().collect::<Vec<_>>())
+            .field("lenient_utf8", &self.lenient_utf8)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SourceMap {
+    /// Creates a new empty source map, that reads files from disk.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_loader(DiskFileLoader)
+    }
+
+    /// Creates a new empty source map, that loads files with a given loader.
+    #[inline]
+    #[must_use]
+    pub fn with_loader(loader: impl FileLoader + 'static) -> Self {
+        Self {
+            loader: Box::new(loader),
+            files: FxHashMap::default(),
+            overrides: FxHashMap::default(),
+            lenient_utf8: false,
+        }
+    }
+
+    /// Enables or disables lossy decoding of files, that are not valid UTF-8.
+    /// When enabled, invalid byte sequences are replaced with `U+FFFD`
+    /// instead of failing to load the file.
+    #[inline]
+    pub fn set_lenient_utf8(&mut self, lenient_utf8: bool) {
+        self.lenient_utf8 = lenient_utf8;
+    }
+
+    /// Returns `true` if files, that are not valid UTF-8, are decoded lossily.
+    #[inline]
+    #[must_use]
+    pub const fn is_lenient_utf8(&self) -> bool {
+        self.lenient_utf8
+    }
+
+    /// Returns the content of a file, loading it if it is not loaded yet.
+    ///
+    /// # Errors
+    /// Returns an error if the file is not loaded yet and cannot be read.
+    #[inline]
+    pub fn get_or_load(&mut self, path: PathId) -> io::Result<Arc<str>> {
+        self.file_or_load(path).map(|file| file.source.clone())
+    }
+
+    /// Returns a cached file together with its line index, loading it if it
+    /// is not loaded yet.
+    ///
+    /// # Errors
+    /// Returns an error if the file is not loaded yet and cannot be read, or
+    /// if it is not valid UTF-8 and lossy decoding is disabled, see
+    /// [`InvalidUtf8Error`].
+    pub fn file_or_load(&mut self, path: PathId) -> io::Result<&SourceFile> {
+        if !self.files.contains_key(&path) {
+            let source = match self.overrides.get(&path) {
+                Some(source) => source.clone(),
+                None => Arc::from(self.decode(path, self.loader.load(path)?)?),
+            };
+
+            self.files.insert(path, SourceFile::new(path, source));
+        }
+
+        Ok(&self.files[&path])
+    }
+
+    /// Decodes raw bytes of a file as UTF-8.
+    fn decode(&self, path: PathId, bytes: Vec<u8>) -> io::Result<String> {
+        String::from_utf8(bytes).or_else(|error| {
+            if self.lenient_utf8 {
+                return Ok(String::from_utf8_lossy(error.as_bytes()).into_owned());
+            }
+
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                InvalidUtf8Error {
+                    path,
+                    offset: ByteOffset(error.utf8_error().valid_up_to()),
+                },
+            ))
+        })
+    }
+
+    /// Returns the size of a file in bytes. A file, that is not loaded yet, is
+    /// not loaded, unless the loader cannot get its size otherwise, see
+    /// [`FileLoader::size()`].
+    ///
+    /// # Errors
+    /// Returns an error if the file is not loaded yet and its size cannot be
+    /// determined.
+    pub fn file_size(&self, path: PathId) -> io::Result<usize> {
+        if let Some(file) = self.files.get(&path) {
+            return Ok(file.source.len());
+        }
+
+        match self.overrides.get(&path) {
+            Some(source) => Ok(source.len()),
+            None => self.loader.size(path),
+        }
+    }
+
+    /// Returns a file, if it is already loaded.
+    #[inline]
+    #[must_use]
+    pub fn file(&self, path: PathId) -> Option<&SourceFile> {
+        self.files.get(&path)
+    }
+
+    /// Returns the source text, that a location points to, if its file is
+    /// already loaded. See [`Location::slice()`] for when `None` is returned.
+    #[inline]
+    #[must_use]
+    pub fn snippet(&self, location: Location) -> Option<&str> {
+        location.slice(&self.file(location.filepath)?.source)
+    }
+
+    /// Same as [`SourceMap::snippet()`], but offsets past the end of the file
+    /// are clamped to the end, see [`Location::slice_clamped()`].
+    #[inline]
+    #[must_use]
+    pub fn snippet_clamped(&self, location: Location) -> Option<&str> {
+        location.slice_clamped(&self.file(location.filepath)?.source)
+    }
+
+    /// Returns `true` if a file is already loaded.
+    #[inline]
+    #[must_use]
+    pub fn is_loaded(&self, path: PathId) -> bool {
+        self.files.contains_key(&path)
+    }
+
+    /// Replaces the content of a file with a given in-memory buffer. The
+    /// buffer is used instead of the file on disk until the override is
+    /// removed with [`SourceMap::remove_override()`].
+    pub fn set_override(&mut self, path: PathId, source: impl Into<Arc<str>>) {
+        let source = source.into();
+
+        self.files
+            .insert(path, SourceFile::new(path, source.clone()));
+        self.overrides.insert(path, source);
+    }
+
+    /// Removes an in-memory override of a file, so that its content is
+    /// loaded again the next time it is needed.
+    pub fn remove_override(&mut self, path: PathId) {
+        if self.overrides.remove(&path).is_some() {
+            self.files.remove(&path);
+        }
+    }
+
+    /// Evicts a cached file, e.g. after it has changed on disk, so that it is
+    /// loaded again the next time it is needed. Overrides are not removed.
+    #[inline]
+    pub fn forget(&mut self, path: PathId) {
+        self.files.remove(&path);
+    }
+}
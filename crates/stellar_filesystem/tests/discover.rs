@@ -0,0 +1,152 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use stellar_filesystem::discover::{discover_package, DiscoverError, ModuleLayout};
+use stellar_interner::PathId;
+
+/// Creates a package with a given test name, that contains given files
+/// and directories (ones ending with `/`) in its source directory.
+fn package(test: &str, entries: &[&str]) -> PathBuf {
+    let root =
+        std::env::temp_dir().join(format!("stellar_filesystem_{test}_{}", std::process::id()));
+
+    let _ = fs::remove_dir_all(&root);
+
+    let source_directory = root.join("src");
+    fs::create_dir_all(&source_directory).unwrap();
+    fs::write(source_directory.join("package.sr"), "").unwrap();
+
+    for entry in entries {
+        let path = source_directory.join(entry);
+
+        if entry.ends_with('/') {
+            fs::create_dir_all(path).unwrap();
+        } else {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+    }
+
+    root
+}
+
+fn module(name: &str, filepath: Option<&Path>, submodules: Vec<ModuleLayout>) -> ModuleLayout {
+    ModuleLayout {
+        name: name.to_owned(),
+        filepath: filepath.map(PathId::from),
+        directory: None,
+        submodules,
+    }
+}
+
+/// Returns names of modules in the tree, e.g. `io.file`.
+fn module_paths(prefix: &str, modules: &[ModuleLayout], paths: &mut Vec<String>) {
+    for module in modules {
+        let path = format!("{prefix}{}", module.name);
+
+        module_paths(&format!("{path}."), &module.submodules, paths);
+        paths.push(path);
+    }
+}
+
+#[test]
+fn nested_directories() {
+    let root = package(
+        "nested_directories",
+        &[
+            "io.sr",
+            "io/file.sr",
+            "io/file/buffered.sr",
+            "fmt/display.sr",
+            "README.md",
+            ".git/config.sr",
+            "target/cache.sr",
+        ],
+    );
+    let source_directory = root.join("src");
+
+    let layout = discover_package(&root).unwrap();
+
+    assert_eq!(
+        layout.root_module,
+        PathId::from(source_directory.join("package.sr"))
+    );
+
+    let mut paths = vec![];
+    module_paths("", &layout.submodules, &mut paths);
+    paths.sort();
+
+    assert_eq!(
+        paths,
+        ["fmt", "fmt.display", "io", "io.file", "io.file.buffered"]
+    );
+
+    let fmt = &layout.submodules[0];
+
+    assert_eq!(fmt.filepath, None);
+    assert_eq!(
+        fmt.directory,
+        Some(PathId::from(source_directory.join("fmt")))
+    );
+
+    let io = &layout.submodules[1];
+
+    assert_eq!(
+        io.filepath,
+        Some(PathId::from(source_directory.join("io.sr")))
+    );
+    assert_eq!(
+        io.submodules[0].filepath,
+        Some(PathId::from(source_directory.join("io/file.sr")))
+    );
+}
+
+#[test]
+fn empty_directory() {
+    let root = package("empty_directory", &["utils/"]);
+
+    let layout = discover_package(&root).unwrap();
+
+    assert_eq!(
+        layout.submodules,
+        [ModuleLayout {
+            directory: Some(PathId::from(root.join("src/utils"))),
+            ..module("utils", None, vec![])
+        }]
+    );
+}
+
+#[test]
+fn invalid_module_name() {
+    let root = package("invalid_module_name", &["io/my-file.sr"]);
+
+    assert!(matches!(
+        discover_package(&root),
+        Err(DiscoverError::InvalidModuleName(path)) if path == root.join("src/io/my-file.sr")
+    ));
+}
+
+#[test]
+fn missing_root_module() {
+    let root = package("missing_root_module", &[]);
+    fs::remove_file(root.join("src/package.sr")).unwrap();
+
+    assert!(matches!(
+        discover_package(&root),
+        Err(DiscoverError::MissingRootModule(directory)) if directory == root.join("src")
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn symlink_cycle() {
+    let root = package("symlink_cycle", &["a/b/"]);
+    std::os::unix::fs::symlink(root.join("src/a"), root.join("src/a/b/c")).unwrap();
+
+    assert!(matches!(
+        discover_package(&root),
+        Err(DiscoverError::SymlinkCycle(path)) if path == root.join("src/a/b/c")
+    ));
+}
@@ -0,0 +1,68 @@
+use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_interner::{PathId, DUMMY_PATH_ID};
+
+fn location(start: usize, end: usize) -> Location {
+    Location {
+        filepath: DUMMY_PATH_ID,
+        start: ByteOffset(start),
+        end: ByteOffset(end),
+    }
+}
+
+#[test]
+fn merge() {
+    assert_eq!(location(0, 3).merge(location(5, 8)), location(0, 8));
+    assert_eq!(location(5, 8).merge(location(0, 3)), location(0, 8));
+    assert_eq!(location(0, 8).merge(location(2, 4)), location(0, 8));
+    assert_eq!(location(2, 2).merge(location(2, 2)), location(2, 2));
+}
+
+#[test]
+#[should_panic = "cannot merge locations in different files"]
+#[cfg(debug_assertions)]
+fn merge_locations_in_different_files() {
+    let _ = location(0, 3).merge(Location {
+        filepath: PathId::from("other.sr"),
+        ..location(5, 8)
+    });
+}
+
+#[test]
+fn between() {
+    assert_eq!(
+        Location::between(location(0, 3), location(5, 8)),
+        location(3, 5)
+    );
+    assert_eq!(
+        Location::between(location(0, 3), location(3, 8)),
+        location(3, 3)
+    );
+}
+
+#[test]
+fn shrink() {
+    assert_eq!(location(2, 6).shrink_to_start(), location(2, 2));
+    assert_eq!(location(2, 6).shrink_to_end(), location(6, 6));
+}
+
+#[test]
+fn contains() {
+    let location = location(2, 4);
+
+    assert!(!location.contains(ByteOffset(1)));
+    assert!(location.contains(ByteOffset(2)));
+    assert!(location.contains(ByteOffset(3)));
+    assert!(!location.contains(ByteOffset(4)));
+}
+
+#[test]
+fn overlaps() {
+    assert!(location(0, 3).overlaps(location(2, 5)));
+    assert!(location(2, 5).overlaps(location(0, 3)));
+    assert!(location(0, 8).overlaps(location(2, 3)));
+    assert!(!location(0, 3).overlaps(location(3, 5)));
+    assert!(!location(0, 3).overlaps(Location {
+        filepath: PathId::from("other.sr"),
+        ..location(0, 3)
+    }));
+}
@@ -0,0 +1,199 @@
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use stellar_filesystem::{
+    location::ByteOffset,
+    source_map::{FileLoader, InvalidUtf8Error, SourceMap},
+};
+use stellar_interner::PathId;
+
+/// Returns `"loaded"` for every file and counts how many times files are
+/// loaded.
+#[derive(Debug, Default, Clone)]
+struct CountingLoader {
+    loads: Arc<AtomicUsize>,
+}
+
+impl FileLoader for CountingLoader {
+    fn load(&self, path: PathId) -> io::Result<Vec<u8>> {
+        self.loads.fetch_add(1, Ordering::SeqCst);
+
+        if path.as_path().ends_with("missing.sr") {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "not found"));
+        }
+
+        if path.as_path().ends_with("latin1.sr") {
+            return Ok(b"let s = \"caf\xe9\";".to_vec());
+        }
+
+        Ok(b"loaded".to_vec())
+    }
+}
+
+fn setup() -> (SourceMap, Arc<AtomicUsize>) {
+    let loader = CountingLoader::default();
+    let loads = loader.loads.clone();
+
+    (SourceMap::with_loader(loader), loads)
+}
+
+#[test]
+fn files_are_loaded_once() {
+    let (mut source_map, loads) = setup();
+    let path = PathId::from("source_map/a.sr");
+
+    assert_eq!(&*source_map.get_or_load(path).unwrap(), "loaded");
+    assert_eq!(&*source_map.get_or_load(path).unwrap(), "loaded");
+    assert_eq!(source_map.file_or_load(path).unwrap().source.len(), 6);
+
+    assert_eq!(loads.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn failed_loads_are_not_cached() {
+    let (mut source_map, loads) = setup();
+    let path = PathId::from("source_map/missing.sr");
+
+    assert!(source_map.get_or_load(path).is_err());
+    assert!(source_map.get_or_load(path).is_err());
+    assert!(!source_map.is_loaded(path));
+
+    assert_eq!(loads.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn overrides_win_over_disk_content() {
+    let (mut source_map, loads) = setup();
+    let path = PathId::from("source_map/b.sr");
+
+    assert_eq!(&*source_map.get_or_load(path).unwrap(), "loaded");
+
+    source_map.set_override(path, "unsaved");
+    assert_eq!(&*source_map.get_or_load(path).unwrap(), "unsaved");
+
+    source_map.forget(path);
+    assert_eq!(&*source_map.get_or_load(path).unwrap(), "unsaved");
+    assert_eq!(loads.load(Ordering::SeqCst), 1);
+
+    source_map.remove_override(path);
+    assert_eq!(&*source_map.get_or_load(path).unwrap(), "loaded");
+    assert_eq!(loads.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn forget_reloads_file() {
+    let (mut source_map, loads) = setup();
+    let path = PathId::from("source_map/c.sr");
+
+    let _ = source_map.get_or_load(path).unwrap();
+    source_map.forget(path);
+    assert!(!source_map.is_loaded(path));
+
+    let _ = source_map.get_or_load(path).unwrap();
+    assert_eq!(loads.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn line_index() {
+    let (mut source_map, _) = setup();
+    let path = PathId::from("source_map/d.sr");
+    source_map.set_override(path, "foo\nbar\r\n\nbaz");
+
+    let line_index = &source_map.file(path).unwrap().line_index;
+
+    assert_eq!(line_index.line_count(), 4);
+    assert_eq!(line_index.line_index(ByteOffset(0)), 0);
+    assert_eq!(line_index.line_index(ByteOffset(5)), 1);
+    assert_eq!(line_index.line_index(ByteOffset(100)), 3);
+    assert_eq!(line_index.line_start(2), Some(ByteOffset(9)));
+    assert_eq!(line_index.line_start(4), Some(ByteOffset(13)));
+    assert_eq!(line_index.line_start(5), None);
+}
+
+#[test]
+fn invalid_utf8() {
+    let (mut source_map, _) = setup();
+    let path = PathId::from("source_map/latin1.sr");
+
+    let error = source_map.get_or_load(path).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(
+        InvalidUtf8Error::from_io_error(&error),
+        Some(&InvalidUtf8Error {
+            path,
+            offset: ByteOffset(12),
+        })
+    );
+    assert!(!source_map.is_loaded(path));
+}
+
+#[test]
+fn lenient_utf8() {
+    let (mut source_map, _) = setup();
+    let path = PathId::from("source_map/latin1.sr");
+    source_map.set_lenient_utf8(true);
+
+    assert_eq!(
+        &*source_map.get_or_load(path).unwrap(),
+        "let s = \"caf\u{fffd}\";"
+    );
+}
+
+#[test]
+fn snippets() {
+    use stellar_filesystem::location::Location;
+
+    let mut source_map = SourceMap::new();
+    let path = PathId::from("source_map/snippets.sr");
+    source_map.set_override(path, "let é = 1;");
+
+    let location = |start, end| Location {
+        filepath: path,
+        start: ByteOffset(start),
+        end: ByteOffset(end),
+    };
+
+    assert_eq!(source_map.snippet(location(4, 6)), Some("é"));
+
+    // Empty span at the end of the file.
+    assert_eq!(source_map.snippet(location(11, 11)), Some(""));
+    assert_eq!(source_map.snippet(location(9, 11)), Some("1;"));
+
+    // Span past the end of the file.
+    assert_eq!(source_map.snippet(location(9, 20)), None);
+    assert_eq!(source_map.snippet_clamped(location(9, 20)), Some("1;"));
+    assert_eq!(source_map.snippet_clamped(location(15, 20)), Some(""));
+
+    // Offset in the middle of `é`.
+    assert_eq!(source_map.snippet(location(5, 6)), None);
+    assert_eq!(source_map.snippet_clamped(location(4, 5)), None);
+
+    // Files, that are not loaded.
+    assert_eq!(
+        source_map.snippet(Location {
+            filepath: PathId::from("source_map/not_loaded.sr"),
+            ..location(0, 1)
+        }),
+        None
+    );
+}
+
+#[test]
+fn file_size_does_not_cache_files() {
+    let (mut source_map, loads) = setup();
+    let path = PathId::from("source_map/size.sr");
+    let overridden = PathId::from("source_map/size_override.sr");
+
+    source_map.set_override(overridden, "fun main() {}");
+
+    assert_eq!(source_map.file_size(path).unwrap(), 6);
+    assert!(!source_map.is_loaded(path));
+    assert_eq!(source_map.file_size(overridden).unwrap(), 13);
+    assert_eq!(loads.load(Ordering::SeqCst), 1);
+}
@@ -37,27 +37,26 @@
 //!                 "parameters": [],
 //!                 "where_predicates": []
 //!             },
-//!             "body": [
-//!                 {
-//!                     "kind": "expression_statement",
-//!                     "expression": {
-//!                         "kind": "while_expression",
+//!             "body": {
+//!                 "tail_expression": {
+//!                     "kind": "while_expression",
+//!                     "location": {
+//!                         "filepath": "test.sr",
+//!                         "start": 17,
+//!                         "end": 21
+//!                     },
+//!                     "condition": {
+//!                         "kind": "literal_expression",
+//!                         "literal_kind": "boolean",
+//!                         "value": true,
 //!                         "location": {
 //!                             "filepath": "test.sr",
 //!                             "start": 17,
 //!                             "end": 21
-//!                         },
-//!                         "condition": {
-//!                             "kind": "literal_expression",
-//!                             "literal_kind": "boolean",
-//!                             "value": true,
-//!                             "location": {
-//!                                 "filepath": "test.sr",
-//!                                 "start": 17,
-//!                                 "end": 21
-//!                             }
-//!                         },
-//!                         "statements_block": [
+//!                         }
+//!                     },
+//!                     "statements_block": {
+//!                         "statements": [
 //!                             {
 //!                                 "kind": "expression_statement",
 //!                                 "expression": {
@@ -92,10 +91,9 @@
 //!                                 "has_semicolon": true
 //!                             }
 //!                         ]
-//!                     },
-//!                     "has_semicolon": false
+//!                     }
 //!                 }
-//!             ]
+//!             }
 //!         }
 //!     ]
 //! }
@@ -104,22 +102,22 @@
 //! In this case, we can see that `loop` was converted into `while true`:
 //!
 //! ```json
-//!                         "kind": "while_expression",
+//!                     "kind": "while_expression",
+//!                     "location": {
+//!                         "filepath": "test.sr",
+//!                         "start": 17,
+//!                         "end": 21
+//!                     },
+//!                     "condition": {
+//!                         "kind": "literal_expression",
+//!                         "literal_kind": "boolean",
+//!                         "value": true,
 //!                         "location": {
 //!                             "filepath": "test.sr",
 //!                             "start": 17,
 //!                             "end": 21
-//!                         },
-//!                         "condition": {
-//!                             "kind": "literal_expression",
-//!                             "literal_kind": "boolean",
-//!                             "value": true,
-//!                             "location": {
-//!                                 "filepath": "test.sr",
-//!                                 "start": 17,
-//!                                 "end": 21
-//!                             }
-//!                         },
+//!                         }
+//!                     },
 //! ```
 
 #![doc(
@@ -181,11 +179,16 @@
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-pub use stellar_ast::{IdentifierAST, ImportPath, Literal, Path, Visibility};
+pub use stellar_ast::{
+    Attribute, AttributeArgument, IdentifierAST, ImportPath, Literal, Path, Visibility,
+};
 use stellar_ast::{ModuleItemKind, NegativeNumericLiteral};
-use stellar_filesystem::location::Location;
+use stellar_filesystem::location::{HasLocation, Location};
 use stellar_interner::{IdentifierId, PathId};
 
+pub mod metrics;
+pub mod visit;
+
 /// A type constructor, e.g. `Option[T]`.
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -208,12 +211,15 @@ pub enum Pattern {
     #[cfg_attr(feature = "serde", serde(rename = "negative_numeric_literal_pattern"))]
     NegativeNumericLiteral(NegativeNumericLiteral),
 
-    /// An identifier pattern, e.g. `f`, `list @ [3, ..]`.
+    /// An identifier pattern, e.g. `f`, `mut x`, `list @ [3, ..]`.
     #[cfg_attr(feature = "serde", serde(rename = "identifier_pattern"))]
     Identifier {
         location: Location,
         identifier: IdentifierAST,
 
+        /// Whether the binding is declared with `mut`.
+        mutable: bool,
+
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pattern: Option<Box<Self>>,
     },
@@ -295,6 +301,20 @@ impl Pattern {
             Self::Literal(literal) => literal.location(),
         }
     }
+
+    /// Returns `true` if the pattern is a rest pattern, either bare (`..`) or
+    /// bound to a name (`tail @ ..`).
+    #[must_use]
+    pub fn is_rest(&self) -> bool {
+        match self {
+            Self::Rest { .. } => true,
+            Self::Identifier {
+                pattern: Some(pattern),
+                ..
+            } => pattern.is_rest(),
+            _ => false,
+        }
+    }
 }
 
 /// A pattern used to match a struct field, e.g. `citizenship: "USA"`, `name` and `..` in
@@ -319,6 +339,17 @@ pub enum StructFieldPattern {
     Rest { location: Location },
 }
 
+impl StructFieldPattern {
+    /// Returns the location of the struct field pattern.
+    #[inline]
+    #[must_use]
+    pub const fn location(&self) -> Location {
+        match self {
+            Self::NotRest { location, .. } | Self::Rest { location } => *location,
+        }
+    }
+}
+
 /// A type, e.g. `int32`, `(char): bool`, `(char, char)`.
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -394,6 +425,12 @@ pub struct TypeAlias {
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub docstring: Option<String>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attributes: Vec<Attribute>,
 }
 
 /// A where clause item, e.g. `T: ToString`.
@@ -438,7 +475,7 @@ pub enum Expression {
     #[cfg_attr(feature = "serde", serde(rename = "block_expression"))]
     StatementsBlock {
         location: Location,
-        block: Vec<Statement>,
+        block: StatementsBlock,
     },
 
     /// Literal expression, e.g. `true`, `\"hello\"`, `1.2`.
@@ -457,8 +494,8 @@ pub enum Expression {
     #[cfg_attr(feature = "serde", serde(rename = "if_expression"))]
     If {
         location: Location,
-        if_blocks: Vec<(Self, Vec<Statement>)>,
-        r#else: Option<Vec<Statement>>,
+        if_blocks: Vec<(Self, StatementsBlock)>,
+        r#else: Option<StatementsBlock>,
     },
 
     /// Field access expression, e.g. `x.y`.
@@ -469,6 +506,33 @@ pub enum Expression {
         right: IdentifierAST,
     },
 
+    /// Tuple index expression, e.g. `x.0`.
+    #[cfg_attr(feature = "serde", serde(rename = "tuple_index_expression"))]
+    TupleIndex {
+        location: Location,
+        left: Box<Self>,
+        index: u32,
+    },
+
+    /// Assignment expression, e.g. `x = 1`.
+    ///
+    /// Compound assignments are desugared into assignments, e.g. `x += 1`
+    /// is lowered into `x = x + 1`.
+    #[cfg_attr(feature = "serde", serde(rename = "assign_expression"))]
+    Assign {
+        location: Location,
+        left: Box<Self>,
+        right: Box<Self>,
+    },
+
+    /// Index expression, e.g. `xs[0]`.
+    #[cfg_attr(feature = "serde", serde(rename = "index_expression"))]
+    Index {
+        location: Location,
+        left: Box<Self>,
+        index: Box<Self>,
+    },
+
     /// Prefix expression, e.g. `!false`, `++a`.
     #[cfg_attr(feature = "serde", serde(rename = "prefix_expression"))]
     Prefix {
@@ -485,20 +549,29 @@ pub enum Expression {
         operator: stellar_ast::PostfixOperator,
     },
 
-    /// While expression, e.g. `while x != 0 {}`.
+    /// While expression, e.g. `while x != 0 {}`. Loops, e.g. `loop {}`,
+    /// are lowered into `while true {}`.
     #[cfg_attr(feature = "serde", serde(rename = "while_expression"))]
     While {
         location: Location,
+
+        /// Whether the expression is lowered from `loop` or `while`.
+        #[cfg_attr(feature = "serde", serde(rename = "loop_kind"))]
+        kind: LoopKind,
+
+        /// Label of the loop, e.g. `outer` in `@outer: while x != 0 { ... }`.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        label: Option<IdentifierAST>,
         condition: Box<Self>,
-        statements_block: Vec<Statement>,
+        statements_block: StatementsBlock,
     },
 
-    /// Call expression, e.g. `s.to_string()`.
+    /// Call expression, e.g. `s.to_string()` or `draw(x: 10, y: 20)`.
     #[cfg_attr(feature = "serde", serde(rename = "call_expression"))]
     Call {
         location: Location,
         callee: Box<Self>,
-        arguments: Vec<Self>,
+        arguments: Vec<CallArgument>,
     },
 
     /// Type expression, e.g. `A[int32]`.
@@ -519,6 +592,9 @@ pub enum Expression {
         location: Location,
         left: Box<Self>,
         fields: Vec<StructExpressionItem>,
+        /// Base of the struct update syntax, e.g. `other` in `Point { x: 1, ..other }`.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        base: Option<Box<Self>>,
     },
 
     /// Match expression (`match fs.read_file(...) { ... }`).
@@ -534,9 +610,34 @@ pub enum Expression {
         parameters: Vec<LambdaFunctionParameter>,
         return_type: Option<Type>,
         value: Box<Self>,
+
+        /// Names of local variables of enclosing scopes, that are used in the lambda.
+        ///
+        /// **Note**: the list is empty after lowering, it is filled by capture analysis.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        captures: Vec<IdentifierId>,
     },
 }
 
+/// A kind of loop, that [`Expression::While`] is lowered from.
+///
+/// Unlike `while` expressions, `loop` expressions can have a value, e.g.
+/// `loop { break 42; }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LoopKind {
+    /// `loop { ... }`.
+    #[cfg_attr(feature = "serde", serde(rename = "loop"))]
+    Loop,
+
+    /// `while condition { ... }`.
+    #[cfg_attr(feature = "serde", serde(rename = "while"))]
+    While,
+}
+
 /// A lambda function parameter, e.g. `x` in `|x| { x + 1 }`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -561,6 +662,9 @@ impl Expression {
             | Self::Identifier(IdentifierAST { location, .. })
             | Self::If { location, .. }
             | Self::FieldAccess { location, .. }
+            | Self::TupleIndex { location, .. }
+            | Self::Index { location, .. }
+            | Self::Assign { location, .. }
             | Self::Prefix { location, .. }
             | Self::Postfix { location, .. }
             | Self::While { location, .. }
@@ -576,14 +680,37 @@ impl Expression {
     }
 }
 
-/// A match expression item - `pattern` `=>` `expression`.
+/// A match expression item - `pattern` (`if` `guard`)? `->` `expression`.
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MatchExpressionItem {
     pub left: Pattern,
+
+    /// A condition, that must be true for the item to be chosen. Guarded
+    /// items don't make a match exhaustive.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub guard: Option<Expression>,
+
     pub right: Expression,
 }
 
+/// An argument of a call expression (optionally `identifier` `:` or `..`, and
+/// `expression`), e.g. `10` and `y: 20` in `draw(10, y: 20)`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CallArgument {
+    /// Name of the parameter, that the argument is passed to.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub label: Option<IdentifierAST>,
+
+    /// Location of `..`, if elements of the tuple are passed as separate
+    /// arguments, e.g. `..args` in `f(..args)`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub spread: Option<Location>,
+
+    pub value: Expression,
+}
+
 /// A field item in a struct expression (`identifier` and optionally `:` `expression`),
 /// e.g. `name: "John"` and `age` in `Person { name: "John", age }`.
 #[derive(Debug, PartialEq, Clone)]
@@ -609,14 +736,31 @@ impl Expression {
     }
 }
 
+/// A block of statements, e.g. `{ let b = 1; b }`.
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StatementsBlock {
+    pub statements: Vec<Statement>,
+
+    /// The last expression of the block, that is not followed by `;`, e.g. `b`
+    /// in `{ let b = 1; b }`. The block evaluates to it or to `()` if there is
+    /// no such expression.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub tail_expression: Option<Box<Expression>>,
+}
+
 /// A statement, e.g. `defer file.close()`, `return Some("hello");`, `break;`.
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "kind"))]
+#[allow(clippy::large_enum_variant)] // boxing would allocate for every `let` statement
 pub enum Statement {
     /// Defer statement - `defer <expr>;`, e.g. `defer file.close()`.
     #[cfg_attr(feature = "serde", serde(rename = "defer_statement"))]
-    Defer { call: Expression },
+    Defer {
+        location: Location,
+        call: Expression,
+    },
 
     /// Expression statement, e.g. `call();`.
     #[cfg_attr(feature = "serde", serde(rename = "expression_statement"))]
@@ -625,30 +769,71 @@ pub enum Statement {
         has_semicolon: bool,
     },
 
-    /// Break statement - `break;`.
+    /// Break statement - `break;`, `break @outer;` or `break 42;`.
     #[cfg_attr(feature = "serde", serde(rename = "break_statement"))]
-    Break { location: Location },
+    Break {
+        location: Location,
+
+        /// Label of the loop to break out of, e.g. `outer` in `break @outer;`.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        label: Option<IdentifierAST>,
+
+        /// Value of the loop expression, e.g. `42` in `break 42;`.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        value: Option<Expression>,
+    },
 
-    /// Continue statement - `continue`;
+    /// Continue statement - `continue;` or `continue @outer;`.
     #[cfg_attr(feature = "serde", serde(rename = "continue_statement"))]
-    Continue { location: Location },
+    Continue {
+        location: Location,
+
+        /// Label of the loop to continue, e.g. `outer` in `continue @outer;`.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        label: Option<IdentifierAST>,
+    },
 
     /// Return statement - `return <expr>;`, e.g. `return 42;`.
     #[cfg_attr(feature = "serde", serde(rename = "return_statement"))]
-    Return { expression: Expression },
+    Return {
+        location: Location,
+        expression: Expression,
+    },
 
-    /// Let statement - `let <pattern> = <expr>;`, e.g. `let x = 1`.
+    /// Let statement - `let <pattern> = <expr>;`, e.g. `let x = 1`, or
+    /// `let <pattern> = <expr> else { ... };`, e.g. `let Some(x) = y else { return; };`.
     #[cfg_attr(feature = "serde", serde(rename = "let_statement"))]
     Let {
+        location: Location,
         pattern: Pattern,
         value: Expression,
 
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         #[cfg_attr(feature = "serde", serde(rename = "type"))]
         ty: Option<Type>,
+
+        /// Block, that is executed when the pattern doesn't match. Must diverge.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        r#else: Option<StatementsBlock>,
     },
 }
 
+impl Statement {
+    /// Returns the location of the statement.
+    #[inline]
+    #[must_use]
+    pub const fn location(&self) -> Location {
+        match self {
+            Self::Defer { location, .. }
+            | Self::Break { location, .. }
+            | Self::Continue { location, .. }
+            | Self::Return { location, .. }
+            | Self::Let { location, .. } => *location,
+            Self::Expression { expression, .. } => expression.location(),
+        }
+    }
+}
+
 /// A function.
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -656,7 +841,7 @@ pub struct Function {
     pub signature: FunctionSignature,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub body: Option<Vec<Statement>>,
+    pub body: Option<StatementsBlock>,
 }
 
 /// A function signature - information about function except a block.
@@ -675,6 +860,12 @@ pub struct FunctionSignature {
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub docstring: Option<String>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attributes: Vec<Attribute>,
 }
 
 /// An enum module item.
@@ -693,6 +884,12 @@ pub struct Enum {
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub docstring: Option<String>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attributes: Vec<Attribute>,
 }
 
 /// An interface module item.
@@ -707,6 +904,12 @@ pub struct Interface {
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub docstring: Option<String>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attributes: Vec<Attribute>,
 }
 
 /// A struct module item.
@@ -725,6 +928,12 @@ pub struct Struct {
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub docstring: Option<String>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attributes: Vec<Attribute>,
 }
 
 /// A tuple-like struct module item.
@@ -743,6 +952,32 @@ pub struct TupleLikeStruct {
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub docstring: Option<String>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attributes: Vec<Attribute>,
+}
+
+/// An inline module item, e.g. `module foo { ... }`.
+///
+/// Items of inline modules are lowered into separate [`Module`]s, so only
+/// the declaration stays in the parent module.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InlineModule {
+    pub visibility: Visibility,
+    pub name: IdentifierAST,
+
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub docstring: Option<String>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attributes: Vec<Attribute>,
 }
 
 /// A module item.
@@ -781,6 +1016,10 @@ pub enum ModuleItem {
     /// Type alias item.
     #[cfg_attr(feature = "serde", serde(rename = "type_alias_module_item"))]
     TypeAlias(TypeAlias),
+
+    /// Inline module item.
+    #[cfg_attr(feature = "serde", serde(rename = "inline_module_item"))]
+    Module(InlineModule),
 }
 
 impl ModuleItem {
@@ -817,6 +1056,10 @@ impl ModuleItem {
             | Self::TypeAlias(TypeAlias {
                 name: IdentifierAST { location, .. },
                 ..
+            })
+            | Self::Module(InlineModule {
+                name: IdentifierAST { location, .. },
+                ..
             }) => *location,
         }
     }
@@ -853,6 +1096,10 @@ impl ModuleItem {
             | Self::TypeAlias(TypeAlias {
                 name: IdentifierAST { id, .. },
                 ..
+            })
+            | Self::Module(InlineModule {
+                name: IdentifierAST { id, .. },
+                ..
             }) => Some(*id),
             Self::Import { .. } => None,
         }
@@ -881,6 +1128,7 @@ impl ModuleItem {
             Self::Struct { .. } => ModuleItemKind::Struct,
             Self::TupleLikeStruct { .. } => ModuleItemKind::TupleLikeStruct,
             Self::TypeAlias(..) => ModuleItemKind::TypeAlias,
+            Self::Module(..) => ModuleItemKind::Module,
         }
     }
 
@@ -894,6 +1142,7 @@ impl ModuleItem {
             | Self::TupleLikeStruct(TupleLikeStruct { visibility, .. })
             | Self::Interface(Interface { visibility, .. })
             | Self::TypeAlias(TypeAlias { visibility, .. })
+            | Self::Module(InlineModule { visibility, .. })
             | Self::Function(Function {
                 signature: FunctionSignature { visibility, .. },
                 ..
@@ -902,6 +1151,25 @@ impl ModuleItem {
         }
     }
 
+    /// Returns attributes of the item. Imports don't have attributes.
+    #[inline]
+    #[must_use]
+    pub fn attributes(&self) -> &[Attribute] {
+        match self {
+            Self::Enum(Enum { attributes, .. })
+            | Self::Struct(Struct { attributes, .. })
+            | Self::TupleLikeStruct(TupleLikeStruct { attributes, .. })
+            | Self::Interface(Interface { attributes, .. })
+            | Self::TypeAlias(TypeAlias { attributes, .. })
+            | Self::Module(InlineModule { attributes, .. })
+            | Self::Function(Function {
+                signature: FunctionSignature { attributes, .. },
+                ..
+            }) => attributes,
+            Self::Import { .. } => &[],
+        }
+    }
+
     /// Returns the type alias variant of the time.
     #[inline]
     #[must_use]
@@ -925,15 +1193,19 @@ impl ModuleItem {
 }
 
 /// An enum item, e.g. `None`, `Ok(T)`, `A { b: T }`.
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "kind"))]
 pub enum EnumItem {
-    /// Just an identifier, e.g. `None` in `enum Option[T] { Some(T), None }`.
+    /// Just an identifier, e.g. `None` in `enum Option[T] { Some(T), None }`,
+    /// optionally with an explicit discriminant, e.g. `A = 1`.
     #[cfg_attr(feature = "serde", serde(rename = "identifier"))]
     Just {
         name: IdentifierAST,
 
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        discriminant: Option<Expression>,
+
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         docstring: Option<String>,
     },
@@ -973,6 +1245,16 @@ impl EnumItem {
     pub const fn name_id(&self) -> IdentifierId {
         self.name().id
     }
+
+    /// Returns the explicit discriminant of the item, e.g. `1` in `A = 1`.
+    #[inline]
+    #[must_use]
+    pub const fn discriminant(&self) -> Option<&Expression> {
+        match self {
+            Self::Just { discriminant, .. } => discriminant.as_ref(),
+            Self::TupleLike { .. } | Self::Struct { .. } => None,
+        }
+    }
 }
 
 /// A tuple field, e.g. `pub String` in `pub struct Wrapper(pub String);`.
@@ -1023,7 +1305,8 @@ pub struct SelfFunctionParameter {
     pub ty: Option<Type>,
 }
 
-/// A function parameter that is not `self`, e.g. `a: uint32`.
+/// A function parameter that is not `self`, e.g. `a: uint32` or
+/// `punctuation: String = "!"`.
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NotSelfFunctionParameter {
@@ -1031,6 +1314,10 @@ pub struct NotSelfFunctionParameter {
 
     #[cfg_attr(feature = "serde", serde(rename = "type"))]
     pub ty: Type,
+
+    /// The value used when the argument is omitted in a call.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub default_value: Option<Expression>,
 }
 
 /// A Stellar module.
@@ -1044,3 +1331,133 @@ pub struct Module {
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub docstring: Option<String>,
 }
+
+/// Implements [`HasLocation`] for nodes, that already have an inherent `location()` method.
+macro_rules! delegate_has_location {
+    ($($ty:ty),*) => {
+        $(
+            impl HasLocation for $ty {
+                #[inline]
+                fn location(&self) -> Location {
+                    <$ty>::location(self)
+                }
+            }
+        )*
+    };
+}
+
+delegate_has_location!(
+    Pattern,
+    StructFieldPattern,
+    Type,
+    Expression,
+    Statement,
+    ModuleItem
+);
+
+impl HasLocation for TypeConstructor {
+    #[inline]
+    fn location(&self) -> Location {
+        self.location
+    }
+}
+
+impl HasLocation for GenericParameter {
+    #[inline]
+    fn location(&self) -> Location {
+        self.name.location
+    }
+}
+
+impl HasLocation for WherePredicate {
+    #[inline]
+    fn location(&self) -> Location {
+        self.ty.location()
+    }
+}
+
+impl HasLocation for LambdaFunctionParameter {
+    #[inline]
+    fn location(&self) -> Location {
+        self.name.location
+    }
+}
+
+impl HasLocation for MatchExpressionItem {
+    #[inline]
+    fn location(&self) -> Location {
+        Location {
+            end: self.right.location().end,
+            ..self.left.location()
+        }
+    }
+}
+
+impl HasLocation for CallArgument {
+    #[inline]
+    fn location(&self) -> Location {
+        let start = self
+            .label
+            .map(|label| label.location)
+            .or(self.spread)
+            .unwrap_or_else(|| self.value.location());
+
+        Location {
+            end: self.value.location().end,
+            ..start
+        }
+    }
+}
+
+impl HasLocation for StructExpressionItem {
+    #[inline]
+    fn location(&self) -> Location {
+        self.value
+            .as_ref()
+            .map_or(self.name.location, |value| Location {
+                end: value.location().end,
+                ..self.name.location
+            })
+    }
+}
+
+impl HasLocation for EnumItem {
+    #[inline]
+    fn location(&self) -> Location {
+        self.name().location
+    }
+}
+
+impl HasLocation for TupleField {
+    #[inline]
+    fn location(&self) -> Location {
+        self.ty.location()
+    }
+}
+
+impl HasLocation for StructField {
+    #[inline]
+    fn location(&self) -> Location {
+        self.name.location
+    }
+}
+
+impl HasLocation for Function {
+    #[inline]
+    fn location(&self) -> Location {
+        self.signature.name.location
+    }
+}
+
+impl HasLocation for FunctionParameter {
+    #[inline]
+    fn location(&self) -> Location {
+        match self {
+            Self::NotSelfParameter(parameter) => Location {
+                end: parameter.ty.location().end,
+                ..parameter.pattern.location()
+            },
+            Self::SelfParameter(parameter) => parameter.self_location,
+        }
+    }
+}
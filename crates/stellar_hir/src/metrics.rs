@@ -0,0 +1,167 @@
+//! Defines [`FunctionMetrics`] - sizes and complexity of a function, e.g. for
+//! CI gates like "no function with more than 50 statements":
+//!
+//! ```stellar
+//! fun sign(x: int32): int32 {
+//!     if x > 0 {
+//!         1
+//!     } else if x < 0 {
+//!         -1
+//!     } else {
+//!         0
+//!     }
+//! }
+//! ```
+//!
+//! The function above has 1 parameter, 3 branches, a maximum nesting depth of
+//! 2 and no statements, as the `if` expression is the tail expression of the
+//! body.
+
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    visit::{walk_expression, walk_statement, walk_statements_block, Visitor},
+    Expression, Function, Statement, StatementsBlock,
+};
+
+/// A metric of [`FunctionMetrics`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Metric {
+    /// See [`FunctionMetrics::statements`].
+    Statements,
+
+    /// See [`FunctionMetrics::expressions`].
+    Expressions,
+
+    /// See [`FunctionMetrics::max_depth`].
+    Depth,
+
+    /// See [`FunctionMetrics::branches`].
+    Branches,
+
+    /// See [`FunctionMetrics::parameters`].
+    Parameters,
+}
+
+impl Metric {
+    /// All metrics in the order they are printed in.
+    pub const ALL: &'static [Self] = &[
+        Self::Statements,
+        Self::Expressions,
+        Self::Depth,
+        Self::Branches,
+        Self::Parameters,
+    ];
+}
+
+impl Display for Metric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Statements => "statement count",
+            Self::Expressions => "expression count",
+            Self::Depth => "nesting depth",
+            Self::Branches => "branch count",
+            Self::Parameters => "parameter count",
+        })
+    }
+}
+
+/// Sizes and complexity of a function. Nodes of lambdas are counted as nodes
+/// of the function, that they are defined in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FunctionMetrics {
+    /// The number of statements in the body, including statements of nested
+    /// blocks. Tail expressions of blocks are not statements.
+    pub statements: usize,
+
+    /// The number of expressions in the body and in default values of
+    /// parameters, including subexpressions, e.g. `3` for `a + b`.
+    pub expressions: usize,
+
+    /// The maximum nesting depth of statements blocks. The body itself has a
+    /// depth of `1`, functions without bodies have a depth of `0`.
+    pub max_depth: usize,
+
+    /// The number of branches: blocks of `if` and `else if`, `else` blocks and
+    /// match expression items.
+    pub branches: usize,
+
+    /// The number of parameters, including `self`.
+    pub parameters: usize,
+}
+
+impl FunctionMetrics {
+    /// Computes metrics of a given function.
+    #[must_use]
+    pub fn of(function: &Function) -> Self {
+        let mut collector = MetricsCollector {
+            metrics: Self {
+                parameters: function.signature.parameters.len(),
+                ..Self::default()
+            },
+            depth: 0,
+        };
+
+        collector.visit_function(function);
+        collector.metrics
+    }
+
+    /// Returns the value of a given metric.
+    #[inline]
+    #[must_use]
+    pub const fn get(&self, metric: Metric) -> usize {
+        match metric {
+            Metric::Statements => self.statements,
+            Metric::Expressions => self.expressions,
+            Metric::Depth => self.max_depth,
+            Metric::Branches => self.branches,
+            Metric::Parameters => self.parameters,
+        }
+    }
+}
+
+struct MetricsCollector {
+    metrics: FunctionMetrics,
+
+    /// Depth of the statements block, that is currently being visited.
+    depth: usize,
+}
+
+impl Visitor for MetricsCollector {
+    fn visit_statements_block(&mut self, block: &StatementsBlock) {
+        self.depth += 1;
+        self.metrics.max_depth = self.metrics.max_depth.max(self.depth);
+
+        walk_statements_block(self, block);
+
+        self.depth -= 1;
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        self.metrics.statements += 1;
+
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        self.metrics.expressions += 1;
+
+        match expression {
+            Expression::If {
+                if_blocks, r#else, ..
+            } => {
+                self.metrics.branches += if_blocks.len() + usize::from(r#else.is_some());
+            }
+            Expression::Match { block, .. } => self.metrics.branches += block.len(),
+            _ => {}
+        }
+
+        walk_expression(self, expression);
+    }
+}
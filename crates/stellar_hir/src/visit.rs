@@ -0,0 +1,1529 @@
+//! Provides [`Visitor`] and [`MutVisitor`] traits to traverse HIR.
+//!
+//! In the [`Visitor`] trait, every method starts with the `visit_` and then
+//! the HIR node name. By default, every method calls the corresponding
+//! `walk_` function, which visits all children of the node, so if you
+//! override a method and still want to traverse the node's children,
+//! call the `walk_` function manually:
+//!
+//! ```
+//! use stellar_hir::{Expression, visit::{Visitor, walk_expression}};
+//!
+//! pub struct AllExpressionsPrinter;
+//!
+//! impl Visitor for AllExpressionsPrinter {
+//!     fn visit_expression(&mut self, expression: &Expression) {
+//!         println!("expression found: {:?}", expression);
+//!
+//!         walk_expression(self, expression);
+//!     }
+//! }
+//! ```
+//!
+//! [`MutVisitor`] works the same way, but allows to rewrite HIR nodes in place.
+//! Its methods end with the `_mut` suffix:
+//!
+//! ```
+//! use stellar_hir::{IdentifierAST, visit::MutVisitor};
+//! use stellar_interner::IdentifierId;
+//!
+//! pub struct Renamer {
+//!     from: IdentifierId,
+//!     to: IdentifierId,
+//! }
+//!
+//! impl MutVisitor for Renamer {
+//!     fn visit_identifier_mut(&mut self, identifier: &mut IdentifierAST) {
+//!         if identifier.id == self.from {
+//!             identifier.id = self.to;
+//!         }
+//!     }
+//! }
+//! ```
+
+use stellar_filesystem::location::Location;
+
+use crate::{
+    Enum, EnumItem, Expression, Function, FunctionParameter, FunctionSignature, GenericParameter,
+    IdentifierAST, ImportPath, InlineModule, Interface, LambdaFunctionParameter, Literal,
+    MatchExpressionItem, Module, ModuleItem, Path, Pattern, Statement, StatementsBlock, Struct,
+    StructExpressionItem, StructField, StructFieldPattern, TupleField, TupleLikeStruct, Type,
+    TypeAlias, TypeConstructor, WherePredicate,
+};
+
+/// Allows to traverse HIR.
+///
+/// See [module level docs](crate::visit) for more details.
+#[allow(unused_variables)]
+pub trait Visitor {
+    /// Visits a module.
+    fn visit_module(&mut self, module: &Module) {
+        walk_module(self, module);
+    }
+
+    /// Visits a module item.
+    fn visit_module_item(&mut self, item: &ModuleItem) {
+        walk_module_item(self, item);
+    }
+
+    /// Visits an import.
+    fn visit_import(&mut self, location: Location, path: &ImportPath) {
+        walk_import(self, path);
+    }
+
+    /// Visits an enum module item.
+    fn visit_enum(&mut self, enum_: &Enum) {
+        walk_enum(self, enum_);
+    }
+
+    /// Visits an enum item.
+    fn visit_enum_item(&mut self, item: &EnumItem) {
+        walk_enum_item(self, item);
+    }
+
+    /// Visits an interface module item.
+    fn visit_interface(&mut self, interface: &Interface) {
+        walk_interface(self, interface);
+    }
+
+    /// Visits a struct module item.
+    fn visit_struct(&mut self, struct_: &Struct) {
+        walk_struct(self, struct_);
+    }
+
+    /// Visits a tuple-like struct module item.
+    fn visit_tuple_like_struct(&mut self, struct_: &TupleLikeStruct) {
+        walk_tuple_like_struct(self, struct_);
+    }
+
+    /// Visits an inline module item. Items of the module are lowered into a
+    /// separate module, so they are not visited.
+    fn visit_inline_module(&mut self, module: &InlineModule) {
+        self.visit_identifier(module.name);
+    }
+
+    /// Visits a type alias module item.
+    fn visit_type_alias(&mut self, alias: &TypeAlias) {
+        walk_type_alias(self, alias);
+    }
+
+    /// Visits a function module item.
+    fn visit_function(&mut self, function: &Function) {
+        walk_function(self, function);
+    }
+
+    /// Visits a method.
+    fn visit_method(&mut self, method: &Function) {
+        self.visit_function(method);
+    }
+
+    /// Visits a function signature.
+    fn visit_function_signature(&mut self, signature: &FunctionSignature) {
+        walk_function_signature(self, signature);
+    }
+
+    /// Visits a function parameter.
+    fn visit_function_parameter(&mut self, parameter: &FunctionParameter) {
+        walk_function_parameter(self, parameter);
+    }
+
+    /// Visits a struct field.
+    fn visit_struct_field(&mut self, field: &StructField) {
+        walk_struct_field(self, field);
+    }
+
+    /// Visits a tuple field.
+    fn visit_tuple_field(&mut self, field: &TupleField) {
+        walk_tuple_field(self, field);
+    }
+
+    /// Visits a generic parameter.
+    fn visit_generic_parameter(&mut self, generic_parameter: &GenericParameter) {
+        walk_generic_parameter(self, generic_parameter);
+    }
+
+    /// Visits a where predicate.
+    fn visit_where_predicate(&mut self, predicate: &WherePredicate) {
+        walk_where_predicate(self, predicate);
+    }
+
+    /// Visits a statements block.
+    fn visit_statements_block(&mut self, block: &StatementsBlock) {
+        walk_statements_block(self, block);
+    }
+
+    /// Visits a statement.
+    fn visit_statement(&mut self, statement: &Statement) {
+        walk_statement(self, statement);
+    }
+
+    /// Visits an expression.
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression);
+    }
+
+    /// Visits a match expression item.
+    fn visit_match_expression_item(&mut self, item: &MatchExpressionItem) {
+        walk_match_expression_item(self, item);
+    }
+
+    /// Visits a struct expression item.
+    fn visit_struct_expression_item(&mut self, item: &StructExpressionItem) {
+        walk_struct_expression_item(self, item);
+    }
+
+    /// Visits a lambda function parameter.
+    fn visit_lambda_function_parameter(&mut self, parameter: &LambdaFunctionParameter) {
+        walk_lambda_function_parameter(self, parameter);
+    }
+
+    /// Visits a pattern.
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        walk_pattern(self, pattern);
+    }
+
+    /// Visits a struct field pattern.
+    fn visit_struct_field_pattern(&mut self, pattern: &StructFieldPattern) {
+        walk_struct_field_pattern(self, pattern);
+    }
+
+    /// Visits a type.
+    fn visit_type(&mut self, ty: &Type) {
+        walk_type(self, ty);
+    }
+
+    /// Visits a type constructor.
+    fn visit_type_constructor(&mut self, constructor: &TypeConstructor) {
+        walk_type_constructor(self, constructor);
+    }
+
+    /// Visits a path.
+    fn visit_path(&mut self, path: &Path) {
+        walk_path(self, path);
+    }
+
+    /// Visits an identifier.
+    fn visit_identifier(&mut self, identifier: IdentifierAST) {}
+
+    /// Visits a literal.
+    fn visit_literal(&mut self, literal: &Literal) {}
+}
+
+/// Visits all items of a module.
+pub fn walk_module<V: Visitor + ?Sized>(visitor: &mut V, module: &Module) {
+    for item in &module.items {
+        visitor.visit_module_item(item);
+    }
+}
+
+/// Dispatches a module item to the corresponding [`Visitor`] method.
+pub fn walk_module_item<V: Visitor + ?Sized>(visitor: &mut V, item: &ModuleItem) {
+    match item {
+        ModuleItem::Enum(enum_) => visitor.visit_enum(enum_),
+        ModuleItem::Function(function) => visitor.visit_function(function),
+        ModuleItem::Import { location, path } => visitor.visit_import(*location, path),
+        ModuleItem::Interface(interface) => visitor.visit_interface(interface),
+        ModuleItem::Struct(struct_) => visitor.visit_struct(struct_),
+        ModuleItem::TupleLikeStruct(struct_) => visitor.visit_tuple_like_struct(struct_),
+        ModuleItem::TypeAlias(alias) => visitor.visit_type_alias(alias),
+        ModuleItem::Module(module) => visitor.visit_inline_module(module),
+    }
+}
+
+/// Visits an import path and its alias.
+pub fn walk_import<V: Visitor + ?Sized>(visitor: &mut V, path: &ImportPath) {
+    visitor.visit_path(&path.path);
+
+    if let Some(as_) = path.as_ {
+        visitor.visit_identifier(as_);
+    }
+}
+
+/// Visits an enum name, generic parameters, where predicates, items, methods
+/// and implemented interfaces.
+pub fn walk_enum<V: Visitor + ?Sized>(visitor: &mut V, enum_: &Enum) {
+    visitor.visit_identifier(enum_.name);
+
+    for generic_parameter in &enum_.generic_parameters {
+        visitor.visit_generic_parameter(generic_parameter);
+    }
+
+    for predicate in &enum_.where_predicates {
+        visitor.visit_where_predicate(predicate);
+    }
+
+    for item in &enum_.items {
+        visitor.visit_enum_item(item);
+    }
+
+    for method in &enum_.methods {
+        visitor.visit_method(method);
+    }
+
+    if let Some(implements) = &enum_.implements {
+        for interface in implements {
+            visitor.visit_type_constructor(interface);
+        }
+    }
+}
+
+/// Visits an enum item name and its fields.
+///
+/// Explicit discriminants are constant expressions, that are not a part of
+/// any function body, so they are not visited.
+pub fn walk_enum_item<V: Visitor + ?Sized>(visitor: &mut V, item: &EnumItem) {
+    visitor.visit_identifier(item.name());
+
+    match item {
+        EnumItem::Just { .. } => {}
+        EnumItem::TupleLike { fields, .. } => {
+            for field in fields {
+                visitor.visit_tuple_field(field);
+            }
+        }
+        EnumItem::Struct { fields, .. } => {
+            for field in fields {
+                visitor.visit_struct_field(field);
+            }
+        }
+    }
+}
+
+/// Visits an interface name, generic parameters, where predicates and methods.
+pub fn walk_interface<V: Visitor + ?Sized>(visitor: &mut V, interface: &Interface) {
+    visitor.visit_identifier(interface.name);
+
+    for generic_parameter in &interface.generic_parameters {
+        visitor.visit_generic_parameter(generic_parameter);
+    }
+
+    for predicate in &interface.where_predicates {
+        visitor.visit_where_predicate(predicate);
+    }
+
+    for method in &interface.methods {
+        visitor.visit_method(method);
+    }
+}
+
+/// Visits a struct name, generic parameters, where predicates, fields, methods
+/// and implemented interfaces.
+pub fn walk_struct<V: Visitor + ?Sized>(visitor: &mut V, struct_: &Struct) {
+    visitor.visit_identifier(struct_.name);
+
+    for generic_parameter in &struct_.generic_parameters {
+        visitor.visit_generic_parameter(generic_parameter);
+    }
+
+    for predicate in &struct_.where_predicates {
+        visitor.visit_where_predicate(predicate);
+    }
+
+    for field in &struct_.fields {
+        visitor.visit_struct_field(field);
+    }
+
+    for method in &struct_.methods {
+        visitor.visit_method(method);
+    }
+
+    if let Some(implements) = &struct_.implements {
+        for interface in implements {
+            visitor.visit_type_constructor(interface);
+        }
+    }
+}
+
+/// Visits a tuple-like struct name, generic parameters, where predicates, fields,
+/// methods and implemented interfaces.
+pub fn walk_tuple_like_struct<V: Visitor + ?Sized>(visitor: &mut V, struct_: &TupleLikeStruct) {
+    visitor.visit_identifier(struct_.name);
+
+    for generic_parameter in &struct_.generic_parameters {
+        visitor.visit_generic_parameter(generic_parameter);
+    }
+
+    for predicate in &struct_.where_predicates {
+        visitor.visit_where_predicate(predicate);
+    }
+
+    for field in &struct_.fields {
+        visitor.visit_tuple_field(field);
+    }
+
+    for method in &struct_.methods {
+        visitor.visit_method(method);
+    }
+
+    if let Some(implements) = &struct_.implements {
+        for interface in implements {
+            visitor.visit_type_constructor(interface);
+        }
+    }
+}
+
+/// Visits a type alias name, generic parameters and value.
+pub fn walk_type_alias<V: Visitor + ?Sized>(visitor: &mut V, alias: &TypeAlias) {
+    visitor.visit_identifier(alias.name);
+
+    for generic_parameter in &alias.generic_parameters {
+        visitor.visit_generic_parameter(generic_parameter);
+    }
+
+    visitor.visit_type(&alias.value);
+}
+
+/// Visits a function signature and body.
+pub fn walk_function<V: Visitor + ?Sized>(visitor: &mut V, function: &Function) {
+    visitor.visit_function_signature(&function.signature);
+
+    if let Some(body) = &function.body {
+        visitor.visit_statements_block(body);
+    }
+}
+
+/// Visits a function name, generic parameters, parameters, return type and
+/// where predicates.
+pub fn walk_function_signature<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    signature: &FunctionSignature,
+) {
+    visitor.visit_identifier(signature.name);
+
+    for generic_parameter in &signature.generic_parameters {
+        visitor.visit_generic_parameter(generic_parameter);
+    }
+
+    for parameter in &signature.parameters {
+        visitor.visit_function_parameter(parameter);
+    }
+
+    if let Some(return_type) = &signature.return_type {
+        visitor.visit_type(return_type);
+    }
+
+    for predicate in &signature.where_predicates {
+        visitor.visit_where_predicate(predicate);
+    }
+}
+
+/// Visits a function parameter pattern, type and default value.
+pub fn walk_function_parameter<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    parameter: &FunctionParameter,
+) {
+    match parameter {
+        FunctionParameter::NotSelfParameter(parameter) => {
+            visitor.visit_pattern(&parameter.pattern);
+            visitor.visit_type(&parameter.ty);
+
+            if let Some(default_value) = &parameter.default_value {
+                visitor.visit_expression(default_value);
+            }
+        }
+        FunctionParameter::SelfParameter(parameter) => {
+            if let Some(ty) = &parameter.ty {
+                visitor.visit_type(ty);
+            }
+        }
+    }
+}
+
+/// Visits a struct field name and type.
+pub fn walk_struct_field<V: Visitor + ?Sized>(visitor: &mut V, field: &StructField) {
+    visitor.visit_identifier(field.name);
+    visitor.visit_type(&field.ty);
+}
+
+/// Visits a tuple field type.
+pub fn walk_tuple_field<V: Visitor + ?Sized>(visitor: &mut V, field: &TupleField) {
+    visitor.visit_type(&field.ty);
+}
+
+/// Visits a generic parameter name, bounds and default value.
+pub fn walk_generic_parameter<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    generic_parameter: &GenericParameter,
+) {
+    visitor.visit_identifier(generic_parameter.name);
+
+    if let Some(bounds) = &generic_parameter.bounds {
+        for bound in bounds {
+            visitor.visit_type_constructor(bound);
+        }
+    }
+
+    if let Some(default_value) = &generic_parameter.default_value {
+        visitor.visit_type(default_value);
+    }
+}
+
+/// Visits a where predicate type and bounds.
+pub fn walk_where_predicate<V: Visitor + ?Sized>(visitor: &mut V, predicate: &WherePredicate) {
+    visitor.visit_type(&predicate.ty);
+
+    for bound in &predicate.bounds {
+        visitor.visit_type_constructor(bound);
+    }
+}
+
+/// Visits all statements in a block and its tail expression.
+pub fn walk_statements_block<V: Visitor + ?Sized>(visitor: &mut V, block: &StatementsBlock) {
+    for statement in &block.statements {
+        visitor.visit_statement(statement);
+    }
+
+    if let Some(tail_expression) = &block.tail_expression {
+        visitor.visit_expression(tail_expression);
+    }
+}
+
+/// Visits children of a statement.
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &Statement) {
+    match statement {
+        Statement::Break { value, .. } => {
+            if let Some(value) = value {
+                visitor.visit_expression(value);
+            }
+        }
+        Statement::Continue { .. } => {}
+        Statement::Defer { call, .. } => visitor.visit_expression(call),
+        Statement::Expression { expression, .. } | Statement::Return { expression, .. } => {
+            visitor.visit_expression(expression);
+        }
+        Statement::Let {
+            pattern,
+            value,
+            ty,
+            r#else,
+            ..
+        } => {
+            visitor.visit_pattern(pattern);
+
+            if let Some(ty) = ty {
+                visitor.visit_type(ty);
+            }
+
+            visitor.visit_expression(value);
+
+            if let Some(r#else) = r#else {
+                visitor.visit_statements_block(r#else);
+            }
+        }
+    }
+}
+
+/// Visits children of an expression.
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &Expression) {
+    match expression {
+        Expression::List { elements, .. } | Expression::Tuple { elements, .. } => {
+            for element in elements {
+                visitor.visit_expression(element);
+            }
+        }
+        Expression::As { left, right, .. } => {
+            visitor.visit_expression(left);
+            visitor.visit_type(right);
+        }
+        Expression::Binary { left, right, .. }
+        | Expression::Assign { left, right, .. }
+        | Expression::Index {
+            left, index: right, ..
+        } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+        Expression::StatementsBlock { block, .. } => visitor.visit_statements_block(block),
+        Expression::Literal(literal) => visitor.visit_literal(literal),
+        Expression::Identifier(identifier) => visitor.visit_identifier(*identifier),
+        Expression::Underscore { .. } => {}
+        Expression::If {
+            if_blocks, r#else, ..
+        } => {
+            for (condition, block) in if_blocks {
+                visitor.visit_expression(condition);
+                visitor.visit_statements_block(block);
+            }
+
+            if let Some(r#else) = r#else {
+                visitor.visit_statements_block(r#else);
+            }
+        }
+        Expression::FieldAccess { left, right, .. } => {
+            visitor.visit_expression(left);
+            visitor.visit_identifier(*right);
+        }
+        Expression::Prefix { inner, .. }
+        | Expression::Postfix { inner, .. }
+        | Expression::TupleIndex { left: inner, .. } => {
+            visitor.visit_expression(inner);
+        }
+        Expression::While {
+            condition,
+            statements_block,
+            ..
+        } => {
+            visitor.visit_expression(condition);
+            visitor.visit_statements_block(statements_block);
+        }
+        Expression::Call {
+            callee, arguments, ..
+        } => {
+            visitor.visit_expression(callee);
+
+            for argument in arguments {
+                visitor.visit_expression(&argument.value);
+            }
+        }
+        Expression::TypeArguments {
+            left,
+            type_arguments,
+            ..
+        } => {
+            visitor.visit_expression(left);
+
+            for argument in type_arguments {
+                visitor.visit_type(argument);
+            }
+        }
+        Expression::Struct {
+            left, fields, base, ..
+        } => {
+            visitor.visit_expression(left);
+
+            for field in fields {
+                visitor.visit_struct_expression_item(field);
+            }
+
+            if let Some(base) = base {
+                visitor.visit_expression(base);
+            }
+        }
+        Expression::Match {
+            expression, block, ..
+        } => {
+            visitor.visit_expression(expression);
+
+            for item in block {
+                visitor.visit_match_expression_item(item);
+            }
+        }
+        Expression::Lambda {
+            parameters,
+            return_type,
+            value,
+            ..
+        } => {
+            for parameter in parameters {
+                visitor.visit_lambda_function_parameter(parameter);
+            }
+
+            if let Some(return_type) = return_type {
+                visitor.visit_type(return_type);
+            }
+
+            visitor.visit_expression(value);
+        }
+    }
+}
+
+/// Visits a match expression item pattern and expression.
+pub fn walk_match_expression_item<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    item: &MatchExpressionItem,
+) {
+    visitor.visit_pattern(&item.left);
+
+    if let Some(guard) = &item.guard {
+        visitor.visit_expression(guard);
+    }
+
+    visitor.visit_expression(&item.right);
+}
+
+/// Visits a struct expression item name and value.
+pub fn walk_struct_expression_item<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    item: &StructExpressionItem,
+) {
+    visitor.visit_identifier(item.name);
+
+    if let Some(value) = &item.value {
+        visitor.visit_expression(value);
+    }
+}
+
+/// Visits a lambda function parameter name and type.
+pub fn walk_lambda_function_parameter<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    parameter: &LambdaFunctionParameter,
+) {
+    visitor.visit_identifier(parameter.name);
+
+    if let Some(ty) = &parameter.ty {
+        visitor.visit_type(ty);
+    }
+}
+
+/// Visits children of a pattern.
+pub fn walk_pattern<V: Visitor + ?Sized>(visitor: &mut V, pattern: &Pattern) {
+    match pattern {
+        Pattern::Literal(literal) => visitor.visit_literal(literal),
+        Pattern::NegativeNumericLiteral(..) | Pattern::Wildcard { .. } | Pattern::Rest { .. } => {}
+        Pattern::Identifier {
+            identifier,
+            pattern,
+            ..
+        } => {
+            visitor.visit_identifier(*identifier);
+
+            if let Some(pattern) = pattern {
+                visitor.visit_pattern(pattern);
+            }
+        }
+        Pattern::Struct { path, fields, .. } => {
+            visitor.visit_path(path);
+
+            for field in fields {
+                visitor.visit_struct_field_pattern(field);
+            }
+        }
+        Pattern::TupleLike {
+            path,
+            inner_patterns,
+            ..
+        } => {
+            visitor.visit_path(path);
+
+            for pattern in inner_patterns {
+                visitor.visit_pattern(pattern);
+            }
+        }
+        Pattern::Tuple {
+            elements: inner_patterns,
+            ..
+        }
+        | Pattern::List { inner_patterns, .. } => {
+            for pattern in inner_patterns {
+                visitor.visit_pattern(pattern);
+            }
+        }
+        Pattern::Path { path } => visitor.visit_path(path),
+        Pattern::Or { left, right, .. } => {
+            visitor.visit_pattern(left);
+            visitor.visit_pattern(right);
+        }
+    }
+}
+
+/// Visits a struct field pattern name and value pattern.
+pub fn walk_struct_field_pattern<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    pattern: &StructFieldPattern,
+) {
+    match pattern {
+        StructFieldPattern::NotRest {
+            field_name,
+            value_pattern,
+            ..
+        } => {
+            visitor.visit_identifier(*field_name);
+
+            if let Some(value_pattern) = value_pattern {
+                visitor.visit_pattern(value_pattern);
+            }
+        }
+        StructFieldPattern::Rest { .. } => {}
+    }
+}
+
+/// Visits children of a type.
+pub fn walk_type<V: Visitor + ?Sized>(visitor: &mut V, ty: &Type) {
+    match ty {
+        Type::Constructor(constructor) => visitor.visit_type_constructor(constructor),
+        Type::Tuple { element_types, .. } => {
+            for element_type in element_types {
+                visitor.visit_type(element_type);
+            }
+        }
+        Type::Function {
+            parameter_types,
+            return_type,
+            ..
+        } => {
+            for parameter_type in parameter_types {
+                visitor.visit_type(parameter_type);
+            }
+
+            if let Some(return_type) = return_type {
+                visitor.visit_type(return_type);
+            }
+        }
+        Type::Underscore { .. } => {}
+        Type::InterfaceObject { bounds, .. } => {
+            for bound in bounds {
+                visitor.visit_type_constructor(bound);
+            }
+        }
+    }
+}
+
+/// Visits a type constructor path and type arguments.
+pub fn walk_type_constructor<V: Visitor + ?Sized>(visitor: &mut V, constructor: &TypeConstructor) {
+    visitor.visit_path(&constructor.path);
+
+    for argument in &constructor.arguments {
+        visitor.visit_type(argument);
+    }
+}
+
+/// Visits all identifiers in a path.
+pub fn walk_path<V: Visitor + ?Sized>(visitor: &mut V, path: &Path) {
+    for identifier in &path.identifiers {
+        visitor.visit_identifier(*identifier);
+    }
+}
+
+/// Allows to traverse HIR and rewrite its nodes in place.
+///
+/// See [module level docs](crate::visit) for more details.
+#[allow(unused_variables)]
+pub trait MutVisitor {
+    /// Visits a module.
+    fn visit_module_mut(&mut self, module: &mut Module) {
+        walk_module_mut(self, module);
+    }
+
+    /// Visits a module item.
+    fn visit_module_item_mut(&mut self, item: &mut ModuleItem) {
+        walk_module_item_mut(self, item);
+    }
+
+    /// Visits an import.
+    fn visit_import_mut(&mut self, location: Location, path: &mut ImportPath) {
+        walk_import_mut(self, path);
+    }
+
+    /// Visits an enum module item.
+    fn visit_enum_mut(&mut self, enum_: &mut Enum) {
+        walk_enum_mut(self, enum_);
+    }
+
+    /// Visits an enum item.
+    fn visit_enum_item_mut(&mut self, item: &mut EnumItem) {
+        walk_enum_item_mut(self, item);
+    }
+
+    /// Visits an interface module item.
+    fn visit_interface_mut(&mut self, interface: &mut Interface) {
+        walk_interface_mut(self, interface);
+    }
+
+    /// Visits a struct module item.
+    fn visit_struct_mut(&mut self, struct_: &mut Struct) {
+        walk_struct_mut(self, struct_);
+    }
+
+    /// Visits a tuple-like struct module item.
+    fn visit_tuple_like_struct_mut(&mut self, struct_: &mut TupleLikeStruct) {
+        walk_tuple_like_struct_mut(self, struct_);
+    }
+
+    /// Visits an inline module item. Items of the module are lowered into a
+    /// separate module, so they are not visited.
+    fn visit_inline_module_mut(&mut self, module: &mut InlineModule) {
+        self.visit_identifier_mut(&mut module.name);
+    }
+
+    /// Visits a type alias module item.
+    fn visit_type_alias_mut(&mut self, alias: &mut TypeAlias) {
+        walk_type_alias_mut(self, alias);
+    }
+
+    /// Visits a function module item.
+    fn visit_function_mut(&mut self, function: &mut Function) {
+        walk_function_mut(self, function);
+    }
+
+    /// Visits a method.
+    fn visit_method_mut(&mut self, method: &mut Function) {
+        self.visit_function_mut(method);
+    }
+
+    /// Visits a function signature.
+    fn visit_function_signature_mut(&mut self, signature: &mut FunctionSignature) {
+        walk_function_signature_mut(self, signature);
+    }
+
+    /// Visits a function parameter.
+    fn visit_function_parameter_mut(&mut self, parameter: &mut FunctionParameter) {
+        walk_function_parameter_mut(self, parameter);
+    }
+
+    /// Visits a struct field.
+    fn visit_struct_field_mut(&mut self, field: &mut StructField) {
+        walk_struct_field_mut(self, field);
+    }
+
+    /// Visits a tuple field.
+    fn visit_tuple_field_mut(&mut self, field: &mut TupleField) {
+        walk_tuple_field_mut(self, field);
+    }
+
+    /// Visits a generic parameter.
+    fn visit_generic_parameter_mut(&mut self, generic_parameter: &mut GenericParameter) {
+        walk_generic_parameter_mut(self, generic_parameter);
+    }
+
+    /// Visits a where predicate.
+    fn visit_where_predicate_mut(&mut self, predicate: &mut WherePredicate) {
+        walk_where_predicate_mut(self, predicate);
+    }
+
+    /// Visits a statements block.
+    fn visit_statements_block_mut(&mut self, block: &mut StatementsBlock) {
+        walk_statements_block_mut(self, block);
+    }
+
+    /// Visits a statement.
+    fn visit_statement_mut(&mut self, statement: &mut Statement) {
+        walk_statement_mut(self, statement);
+    }
+
+    /// Visits an expression.
+    fn visit_expression_mut(&mut self, expression: &mut Expression) {
+        walk_expression_mut(self, expression);
+    }
+
+    /// Visits a match expression item.
+    fn visit_match_expression_item_mut(&mut self, item: &mut MatchExpressionItem) {
+        walk_match_expression_item_mut(self, item);
+    }
+
+    /// Visits a struct expression item.
+    fn visit_struct_expression_item_mut(&mut self, item: &mut StructExpressionItem) {
+        walk_struct_expression_item_mut(self, item);
+    }
+
+    /// Visits a lambda function parameter.
+    fn visit_lambda_function_parameter_mut(&mut self, parameter: &mut LambdaFunctionParameter) {
+        walk_lambda_function_parameter_mut(self, parameter);
+    }
+
+    /// Visits a pattern.
+    fn visit_pattern_mut(&mut self, pattern: &mut Pattern) {
+        walk_pattern_mut(self, pattern);
+    }
+
+    /// Visits a struct field pattern.
+    fn visit_struct_field_pattern_mut(&mut self, pattern: &mut StructFieldPattern) {
+        walk_struct_field_pattern_mut(self, pattern);
+    }
+
+    /// Visits a type.
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        walk_type_mut(self, ty);
+    }
+
+    /// Visits a type constructor.
+    fn visit_type_constructor_mut(&mut self, constructor: &mut TypeConstructor) {
+        walk_type_constructor_mut(self, constructor);
+    }
+
+    /// Visits a path.
+    fn visit_path_mut(&mut self, path: &mut Path) {
+        walk_path_mut(self, path);
+    }
+
+    /// Visits an identifier.
+    fn visit_identifier_mut(&mut self, identifier: &mut IdentifierAST) {}
+
+    /// Visits a literal.
+    fn visit_literal_mut(&mut self, literal: &mut Literal) {}
+}
+
+/// Visits all items of a module.
+pub fn walk_module_mut<V: MutVisitor + ?Sized>(visitor: &mut V, module: &mut Module) {
+    for item in &mut module.items {
+        visitor.visit_module_item_mut(item);
+    }
+}
+
+/// Dispatches a module item to the corresponding [`MutVisitor`] method.
+pub fn walk_module_item_mut<V: MutVisitor + ?Sized>(visitor: &mut V, item: &mut ModuleItem) {
+    match item {
+        ModuleItem::Enum(enum_) => visitor.visit_enum_mut(enum_),
+        ModuleItem::Function(function) => visitor.visit_function_mut(function),
+        ModuleItem::Import { location, path } => visitor.visit_import_mut(*location, path),
+        ModuleItem::Interface(interface) => visitor.visit_interface_mut(interface),
+        ModuleItem::Struct(struct_) => visitor.visit_struct_mut(struct_),
+        ModuleItem::TupleLikeStruct(struct_) => visitor.visit_tuple_like_struct_mut(struct_),
+        ModuleItem::TypeAlias(alias) => visitor.visit_type_alias_mut(alias),
+        ModuleItem::Module(module) => visitor.visit_inline_module_mut(module),
+    }
+}
+
+/// Visits an import path and its alias.
+pub fn walk_import_mut<V: MutVisitor + ?Sized>(visitor: &mut V, path: &mut ImportPath) {
+    visitor.visit_path_mut(&mut path.path);
+
+    if let Some(as_) = &mut path.as_ {
+        visitor.visit_identifier_mut(as_);
+    }
+}
+
+/// Visits an enum name, generic parameters, where predicates, items, methods
+/// and implemented interfaces.
+pub fn walk_enum_mut<V: MutVisitor + ?Sized>(visitor: &mut V, enum_: &mut Enum) {
+    visitor.visit_identifier_mut(&mut enum_.name);
+
+    for generic_parameter in &mut enum_.generic_parameters {
+        visitor.visit_generic_parameter_mut(generic_parameter);
+    }
+
+    for predicate in &mut enum_.where_predicates {
+        visitor.visit_where_predicate_mut(predicate);
+    }
+
+    for item in &mut enum_.items {
+        visitor.visit_enum_item_mut(item);
+    }
+
+    for method in &mut enum_.methods {
+        visitor.visit_method_mut(method);
+    }
+
+    if let Some(implements) = &mut enum_.implements {
+        for interface in implements {
+            visitor.visit_type_constructor_mut(interface);
+        }
+    }
+}
+
+/// Visits an enum item name and its fields, see [`walk_enum_item()`].
+pub fn walk_enum_item_mut<V: MutVisitor + ?Sized>(visitor: &mut V, item: &mut EnumItem) {
+    match item {
+        EnumItem::Just { name, .. } => visitor.visit_identifier_mut(name),
+        EnumItem::TupleLike { name, fields, .. } => {
+            visitor.visit_identifier_mut(name);
+
+            for field in fields {
+                visitor.visit_tuple_field_mut(field);
+            }
+        }
+        EnumItem::Struct { name, fields, .. } => {
+            visitor.visit_identifier_mut(name);
+
+            for field in fields {
+                visitor.visit_struct_field_mut(field);
+            }
+        }
+    }
+}
+
+/// Visits an interface name, generic parameters, where predicates and methods.
+pub fn walk_interface_mut<V: MutVisitor + ?Sized>(visitor: &mut V, interface: &mut Interface) {
+    visitor.visit_identifier_mut(&mut interface.name);
+
+    for generic_parameter in &mut interface.generic_parameters {
+        visitor.visit_generic_parameter_mut(generic_parameter);
+    }
+
+    for predicate in &mut interface.where_predicates {
+        visitor.visit_where_predicate_mut(predicate);
+    }
+
+    for method in &mut interface.methods {
+        visitor.visit_method_mut(method);
+    }
+}
+
+/// Visits a struct name, generic parameters, where predicates, fields, methods
+/// and implemented interfaces.
+pub fn walk_struct_mut<V: MutVisitor + ?Sized>(visitor: &mut V, struct_: &mut Struct) {
+    visitor.visit_identifier_mut(&mut struct_.name);
+
+    for generic_parameter in &mut struct_.generic_parameters {
+        visitor.visit_generic_parameter_mut(generic_parameter);
+    }
+
+    for predicate in &mut struct_.where_predicates {
+        visitor.visit_where_predicate_mut(predicate);
+    }
+
+    for field in &mut struct_.fields {
+        visitor.visit_struct_field_mut(field);
+    }
+
+    for method in &mut struct_.methods {
+        visitor.visit_method_mut(method);
+    }
+
+    if let Some(implements) = &mut struct_.implements {
+        for interface in implements {
+            visitor.visit_type_constructor_mut(interface);
+        }
+    }
+}
+
+/// Visits a tuple-like struct name, generic parameters, where predicates, fields,
+/// methods and implemented interfaces.
+pub fn walk_tuple_like_struct_mut<V: MutVisitor + ?Sized>(
+    visitor: &mut V,
+    struct_: &mut TupleLikeStruct,
+) {
+    visitor.visit_identifier_mut(&mut struct_.name);
+
+    for generic_parameter in &mut struct_.generic_parameters {
+        visitor.visit_generic_parameter_mut(generic_parameter);
+    }
+
+    for predicate in &mut struct_.where_predicates {
+        visitor.visit_where_predicate_mut(predicate);
+    }
+
+    for field in &mut struct_.fields {
+        visitor.visit_tuple_field_mut(field);
+    }
+
+    for method in &mut struct_.methods {
+        visitor.visit_method_mut(method);
+    }
+
+    if let Some(implements) = &mut struct_.implements {
+        for interface in implements {
+            visitor.visit_type_constructor_mut(interface);
+        }
+    }
+}
+
+/// Visits a type alias name, generic parameters and value.
+pub fn walk_type_alias_mut<V: MutVisitor + ?Sized>(visitor: &mut V, alias: &mut TypeAlias) {
+    visitor.visit_identifier_mut(&mut alias.name);
+
+    for generic_parameter in &mut alias.generic_parameters {
+        visitor.visit_generic_parameter_mut(generic_parameter);
+    }
+
+    visitor.visit_type_mut(&mut alias.value);
+}
+
+/// Visits a function signature and body.
+pub fn walk_function_mut<V: MutVisitor + ?Sized>(visitor: &mut V, function: &mut Function) {
+    visitor.visit_function_signature_mut(&mut function.signature);
+
+    if let Some(body) = &mut function.body {
+        visitor.visit_statements_block_mut(body);
+    }
+}
+
+/// Visits a function name, generic parameters, parameters, return type and
+/// where predicates.
+pub fn walk_function_signature_mut<V: MutVisitor + ?Sized>(
+    visitor: &mut V,
+    signature: &mut FunctionSignature,
+) {
+    visitor.visit_identifier_mut(&mut signature.name);
+
+    for generic_parameter in &mut signature.generic_parameters {
+        visitor.visit_generic_parameter_mut(generic_parameter);
+    }
+
+    for parameter in &mut signature.parameters {
+        visitor.visit_function_parameter_mut(parameter);
+    }
+
+    if let Some(return_type) = &mut signature.return_type {
+        visitor.visit_type_mut(return_type);
+    }
+
+    for predicate in &mut signature.where_predicates {
+        visitor.visit_where_predicate_mut(predicate);
+    }
+}
+
+/// Visits a function parameter pattern, type and default value.
+pub fn walk_function_parameter_mut<V: MutVisitor + ?Sized>(
+    visitor: &mut V,
+    parameter: &mut FunctionParameter,
+) {
+    match parameter {
+        FunctionParameter::NotSelfParameter(parameter) => {
+            visitor.visit_pattern_mut(&mut parameter.pattern);
+            visitor.visit_type_mut(&mut parameter.ty);
+
+            if let Some(default_value) = &mut parameter.default_value {
+                visitor.visit_expression_mut(default_value);
+            }
+        }
+        FunctionParameter::SelfParameter(parameter) => {
+            if let Some(ty) = &mut parameter.ty {
+                visitor.visit_type_mut(ty);
+            }
+        }
+    }
+}
+
+/// Visits a struct field name and type.
+pub fn walk_struct_field_mut<V: MutVisitor + ?Sized>(visitor: &mut V, field: &mut StructField) {
+    visitor.visit_identifier_mut(&mut field.name);
+    visitor.visit_type_mut(&mut field.ty);
+}
+
+/// Visits a tuple field type.
+pub fn walk_tuple_field_mut<V: MutVisitor + ?Sized>(visitor: &mut V, field: &mut TupleField) {
+    visitor.visit_type_mut(&mut field.ty);
+}
+
+/// Visits a generic parameter name, bounds and default value.
+pub fn walk_generic_parameter_mut<V: MutVisitor + ?Sized>(
+    visitor: &mut V,
+    generic_parameter: &mut GenericParameter,
+) {
+    visitor.visit_identifier_mut(&mut generic_parameter.name);
+
+    if let Some(bounds) = &mut generic_parameter.bounds {
+        for bound in bounds {
+            visitor.visit_type_constructor_mut(bound);
+        }
+    }
+
+    if let Some(default_value) = &mut generic_parameter.default_value {
+        visitor.visit_type_mut(default_value);
+    }
+}
+
+/// Visits a where predicate type and bounds.
+pub fn walk_where_predicate_mut<V: MutVisitor + ?Sized>(
+    visitor: &mut V,
+    predicate: &mut WherePredicate,
+) {
+    visitor.visit_type_mut(&mut predicate.ty);
+
+    for bound in &mut predicate.bounds {
+        visitor.visit_type_constructor_mut(bound);
+    }
+}
+
+/// Visits all statements in a block and its tail expression.
+pub fn walk_statements_block_mut<V: MutVisitor + ?Sized>(
+    visitor: &mut V,
+    block: &mut StatementsBlock,
+) {
+    for statement in &mut block.statements {
+        visitor.visit_statement_mut(statement);
+    }
+
+    if let Some(tail_expression) = &mut block.tail_expression {
+        visitor.visit_expression_mut(tail_expression);
+    }
+}
+
+/// Visits children of a statement.
+pub fn walk_statement_mut<V: MutVisitor + ?Sized>(visitor: &mut V, statement: &mut Statement) {
+    match statement {
+        Statement::Break { value, .. } => {
+            if let Some(value) = value {
+                visitor.visit_expression_mut(value);
+            }
+        }
+        Statement::Continue { .. } => {}
+        Statement::Defer { call, .. } => visitor.visit_expression_mut(call),
+        Statement::Expression { expression, .. } | Statement::Return { expression, .. } => {
+            visitor.visit_expression_mut(expression);
+        }
+        Statement::Let {
+            pattern,
+            value,
+            ty,
+            r#else,
+            ..
+        } => {
+            visitor.visit_pattern_mut(pattern);
+
+            if let Some(ty) = ty {
+                visitor.visit_type_mut(ty);
+            }
+
+            visitor.visit_expression_mut(value);
+
+            if let Some(r#else) = r#else {
+                visitor.visit_statements_block_mut(r#else);
+            }
+        }
+    }
+}
+
+/// Visits children of an expression.
+pub fn walk_expression_mut<V: MutVisitor + ?Sized>(visitor: &mut V, expression: &mut Expression) {
+    match expression {
+        Expression::List { elements, .. } | Expression::Tuple { elements, .. } => {
+            for element in elements {
+                visitor.visit_expression_mut(element);
+            }
+        }
+        Expression::As { left, right, .. } => {
+            visitor.visit_expression_mut(left);
+            visitor.visit_type_mut(right);
+        }
+        Expression::Binary { left, right, .. }
+        | Expression::Assign { left, right, .. }
+        | Expression::Index {
+            left, index: right, ..
+        } => {
+            visitor.visit_expression_mut(left);
+            visitor.visit_expression_mut(right);
+        }
+        Expression::StatementsBlock { block, .. } => visitor.visit_statements_block_mut(block),
+        Expression::Literal(literal) => visitor.visit_literal_mut(literal),
+        Expression::Identifier(identifier) => visitor.visit_identifier_mut(identifier),
+        Expression::Underscore { .. } => {}
+        Expression::If {
+            if_blocks, r#else, ..
+        } => {
+            for (condition, block) in if_blocks {
+                visitor.visit_expression_mut(condition);
+                visitor.visit_statements_block_mut(block);
+            }
+
+            if let Some(r#else) = r#else {
+                visitor.visit_statements_block_mut(r#else);
+            }
+        }
+        Expression::FieldAccess { left, right, .. } => {
+            visitor.visit_expression_mut(left);
+            visitor.visit_identifier_mut(right);
+        }
+        Expression::Prefix { inner, .. }
+        | Expression::Postfix { inner, .. }
+        | Expression::TupleIndex { left: inner, .. } => {
+            visitor.visit_expression_mut(inner);
+        }
+        Expression::While {
+            condition,
+            statements_block,
+            ..
+        } => {
+            visitor.visit_expression_mut(condition);
+            visitor.visit_statements_block_mut(statements_block);
+        }
+        Expression::Call {
+            callee, arguments, ..
+        } => {
+            visitor.visit_expression_mut(callee);
+
+            for argument in arguments {
+                visitor.visit_expression_mut(&mut argument.value);
+            }
+        }
+        Expression::TypeArguments {
+            left,
+            type_arguments,
+            ..
+        } => {
+            visitor.visit_expression_mut(left);
+
+            for argument in type_arguments {
+                visitor.visit_type_mut(argument);
+            }
+        }
+        Expression::Struct {
+            left, fields, base, ..
+        } => {
+            visitor.visit_expression_mut(left);
+
+            for field in fields {
+                visitor.visit_struct_expression_item_mut(field);
+            }
+
+            if let Some(base) = base {
+                visitor.visit_expression_mut(base);
+            }
+        }
+        Expression::Match {
+            expression, block, ..
+        } => {
+            visitor.visit_expression_mut(expression);
+
+            for item in block {
+                visitor.visit_match_expression_item_mut(item);
+            }
+        }
+        Expression::Lambda {
+            parameters,
+            return_type,
+            value,
+            ..
+        } => {
+            for parameter in parameters {
+                visitor.visit_lambda_function_parameter_mut(parameter);
+            }
+
+            if let Some(return_type) = return_type {
+                visitor.visit_type_mut(return_type);
+            }
+
+            visitor.visit_expression_mut(value);
+        }
+    }
+}
+
+/// Visits a match expression item pattern and expression.
+pub fn walk_match_expression_item_mut<V: MutVisitor + ?Sized>(
+    visitor: &mut V,
+    item: &mut MatchExpressionItem,
+) {
+    visitor.visit_pattern_mut(&mut item.left);
+
+    if let Some(guard) = &mut item.guard {
+        visitor.visit_expression_mut(guard);
+    }
+
+    visitor.visit_expression_mut(&mut item.right);
+}
+
+/// Visits a struct expression item name and value.
+pub fn walk_struct_expression_item_mut<V: MutVisitor + ?Sized>(
+    visitor: &mut V,
+    item: &mut StructExpressionItem,
+) {
+    visitor.visit_identifier_mut(&mut item.name);
+
+    if let Some(value) = &mut item.value {
+        visitor.visit_expression_mut(value);
+    }
+}
+
+/// Visits a lambda function parameter name and type.
+pub fn walk_lambda_function_parameter_mut<V: MutVisitor + ?Sized>(
+    visitor: &mut V,
+    parameter: &mut LambdaFunctionParameter,
+) {
+    visitor.visit_identifier_mut(&mut parameter.name);
+
+    if let Some(ty) = &mut parameter.ty {
+        visitor.visit_type_mut(ty);
+    }
+}
+
+/// Visits children of a pattern.
+pub fn walk_pattern_mut<V: MutVisitor + ?Sized>(visitor: &mut V, pattern: &mut Pattern) {
+    match pattern {
+        Pattern::Literal(literal) => visitor.visit_literal_mut(literal),
+        Pattern::NegativeNumericLiteral(..) | Pattern::Wildcard { .. } | Pattern::Rest { .. } => {}
+        Pattern::Identifier {
+            identifier,
+            pattern,
+            ..
+        } => {
+            visitor.visit_identifier_mut(identifier);
+
+            if let Some(pattern) = pattern {
+                visitor.visit_pattern_mut(pattern);
+            }
+        }
+        Pattern::Struct { path, fields, .. } => {
+            visitor.visit_path_mut(path);
+
+            for field in fields {
+                visitor.visit_struct_field_pattern_mut(field);
+            }
+        }
+        Pattern::TupleLike {
+            path,
+            inner_patterns,
+            ..
+        } => {
+            visitor.visit_path_mut(path);
+
+            for pattern in inner_patterns {
+                visitor.visit_pattern_mut(pattern);
+            }
+        }
+        Pattern::Tuple {
+            elements: inner_patterns,
+            ..
+        }
+        | Pattern::List { inner_patterns, .. } => {
+            for pattern in inner_patterns {
+                visitor.visit_pattern_mut(pattern);
+            }
+        }
+        Pattern::Path { path } => visitor.visit_path_mut(path),
+        Pattern::Or { left, right, .. } => {
+            visitor.visit_pattern_mut(left);
+            visitor.visit_pattern_mut(right);
+        }
+    }
+}
+
+/// Visits a struct field pattern name and value pattern.
+pub fn walk_struct_field_pattern_mut<V: MutVisitor + ?Sized>(
+    visitor: &mut V,
+    pattern: &mut StructFieldPattern,
+) {
+    match pattern {
+        StructFieldPattern::NotRest {
+            field_name,
+            value_pattern,
+            ..
+        } => {
+            visitor.visit_identifier_mut(field_name);
+
+            if let Some(value_pattern) = value_pattern {
+                visitor.visit_pattern_mut(value_pattern);
+            }
+        }
+        StructFieldPattern::Rest { .. } => {}
+    }
+}
+
+/// Visits children of a type.
+pub fn walk_type_mut<V: MutVisitor + ?Sized>(visitor: &mut V, ty: &mut Type) {
+    match ty {
+        Type::Constructor(constructor) => visitor.visit_type_constructor_mut(constructor),
+        Type::Tuple { element_types, .. } => {
+            for element_type in element_types {
+                visitor.visit_type_mut(element_type);
+            }
+        }
+        Type::Function {
+            parameter_types,
+            return_type,
+            ..
+        } => {
+            for parameter_type in parameter_types {
+                visitor.visit_type_mut(parameter_type);
+            }
+
+            if let Some(return_type) = return_type {
+                visitor.visit_type_mut(return_type);
+            }
+        }
+        Type::Underscore { .. } => {}
+        Type::InterfaceObject { bounds, .. } => {
+            for bound in bounds {
+                visitor.visit_type_constructor_mut(bound);
+            }
+        }
+    }
+}
+
+/// Visits a type constructor path and type arguments.
+pub fn walk_type_constructor_mut<V: MutVisitor + ?Sized>(
+    visitor: &mut V,
+    constructor: &mut TypeConstructor,
+) {
+    visitor.visit_path_mut(&mut constructor.path);
+
+    for argument in &mut constructor.arguments {
+        visitor.visit_type_mut(argument);
+    }
+}
+
+/// Visits all identifiers in a path.
+pub fn walk_path_mut<V: MutVisitor + ?Sized>(visitor: &mut V, path: &mut Path) {
+    for identifier in &mut path.identifiers {
+        visitor.visit_identifier_mut(identifier);
+    }
+}
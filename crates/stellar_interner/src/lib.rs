@@ -99,12 +99,12 @@ use alloc::{string::String, vec::Vec};
 
 use hashbrown::{hash_map::RawEntryMut, HashMap};
 use lazy_static::lazy_static;
-use parking_lot::RwLock;
+use parking_lot::{RwLock, RwLockReadGuard};
 use stellar_fx_hash::FxHasher;
 
 /// Represents unique symbol corresponding to some interned identifier.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct IdentifierId(pub usize);
+pub struct IdentifierId(usize);
 
 /// ID of an identifier, that will never exist in the [`IdentifierInterner`].
 pub const DUMMY_IDENTIFIER_ID: IdentifierId = IdentifierId(0);
@@ -114,9 +114,17 @@ where
     S: AsRef<str>,
 {
     /// Interns a string.
+    ///
+    /// Already interned strings are looked up under a read lock, so that
+    /// threads don't block each other, when they intern the same identifiers.
     #[inline]
     fn from(s: S) -> Self {
-        IDENTIFIER_INTERNER.write().get_or_intern(s)
+        let s = s.as_ref();
+
+        // The read lock is released before the write lock is taken.
+        let id = IDENTIFIER_INTERNER.read().get(s);
+
+        id.unwrap_or_else(|| IDENTIFIER_INTERNER.write().get_or_intern(s))
     }
 }
 
@@ -132,6 +140,26 @@ impl IdentifierId {
 
         unsafe { std::mem::transmute(interner_rlock.resolve(self)) }
     }
+
+    /// Returns `true` if the identifier is a keyword, e.g. `fun`. Boolean
+    /// literals `true` and `false` are not keywords.
+    ///
+    /// Keywords are interned by default with consecutive IDs (see
+    /// [`builtin_identifiers`]), so the check doesn't lock the interner.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stellar_interner::IdentifierId;
+    ///
+    /// assert!(IdentifierId::from("fun").is_keyword());
+    /// assert!(!IdentifierId::from("function").is_keyword());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn is_keyword(self) -> bool {
+        builtin_identifiers::AS.0 <= self.0 && self.0 <= builtin_identifiers::MODULE.0
+    }
 }
 
 impl From<IdentifierId> for String {
@@ -414,27 +442,39 @@ where
         self.dedup.len()
     }
 
-    /// Interns the given string and returns a corresponding symbol.
-    fn get_or_intern_using<T>(
-        &mut self,
-        string: T,
-        intern_fn: fn(&mut InternerStorage<S>, T) -> S,
-    ) -> S
-    where
-        T: AsRef<str> + Copy + Hash + for<'a> PartialEq<&'a str>,
-    {
-        let string_ref = string.as_ref();
+    /// Returns the number of bytes used to store interned strings.
+    #[inline]
+    #[must_use]
+    const fn bytes_used(&self) -> usize {
+        self.backend.storage.len()
+    }
+
+    /// Returns a symbol of the given string, if it is already interned.
+    fn get(&self, string: &str) -> Option<S> {
+        let hash = hash_value(&self.hasher, string);
 
+        self.dedup
+            .raw_entry()
+            .from_hash(hash, |symbol_id| {
+                string == unsafe { self.backend.unchecked_resolve(*symbol_id) }
+            })
+            .map(|(&symbol, &())| symbol)
+    }
+
+    /// Interns the given string and returns a corresponding symbol.
+    ///
+    /// The string is only copied into the storage, if it is not interned yet.
+    fn get_or_intern(&mut self, string: &str) -> S {
         let hasher = &self.hasher;
-        let hash = hash_value(hasher, string_ref);
+        let hash = hash_value(hasher, string);
 
         let entry = self.dedup.raw_entry_mut().from_hash(hash, |symbol_id| {
-            string_ref == unsafe { self.backend.unchecked_resolve(*symbol_id) }
+            string == unsafe { self.backend.unchecked_resolve(*symbol_id) }
         });
 
         let (&mut symbol, &mut ()) = match entry {
             RawEntryMut::Vacant(vacant) => {
-                let symbol = intern_fn(&mut self.backend, string);
+                let symbol = self.backend.intern(string);
                 vacant.insert_with_hasher(hash, symbol, (), |symbol_id| {
                     hash_value(hasher, unsafe {
                         self.backend.unchecked_resolve(*symbol_id)
@@ -447,12 +487,6 @@ where
         symbol
     }
 
-    /// Interns the given string and returns a corresponding symbol.
-    #[inline]
-    fn get_or_intern(&mut self, string: impl AsRef<str>) -> S {
-        self.get_or_intern_using(string.as_ref(), InternerStorage::intern)
-    }
-
     /// Shrink backend capacity to fit the interned strings exactly.
     fn shrink_to_fit(&mut self) {
         self.backend.shrink_to_fit();
@@ -527,20 +561,56 @@ define_builtin_identifiers! {
     ISIZE = 11 => "isize", USIZE = 12 => "usize",
     BOOL = 13 => "bool", STRING = 14 => "String", LIST = 15 => "List",
     CHAR = 16 => "char", SMALL_SELF = 17 => "self", BIG_SELF = 18 => "Self",
-    SIZE_OF = 19 => "sizeof", STD = 20 => "std"
+    SIZE_OF = 19 => "sizeof", STD = 20 => "std",
+    AS = 21 => "as", DEFER = 22 => "defer", ELSE = 23 => "else", ENUM = 24 => "enum",
+    FOR = 25 => "for", FUN = 26 => "fun", IF = 27 => "if", PUB = 28 => "pub",
+    RETURN = 29 => "return", STRUCT = 30 => "struct", TYPE = 31 => "type", LET = 32 => "let",
+    WHERE = 33 => "where", WHILE = 34 => "while", MATCH = 35 => "match",
+    IMPORT = 36 => "import", BREAK = 37 => "break", CONTINUE = 38 => "continue",
+    DYN = 39 => "dyn", LOOP = 40 => "loop", INTERFACE = 41 => "interface",
+    IMPLEMENTS = 42 => "implements", MUT = 43 => "mut", MODULE = 44 => "module",
+    TRUE = 45 => "true", FALSE = 46 => "false"
 }
 
 impl IdentifierInterner {
-    /// Returns the number of identifiers interned by the interner.
-    #[allow(dead_code)]
+    /// Returns a read lock of the global interner, that is used by
+    /// [`IdentifierId::from()`] and [`IdentifierId::as_str()`], e.g. to
+    /// print its statistics.
+    ///
+    /// Identifiers must not be interned or resolved, while the lock is
+    /// held, because it would deadlock.
+    #[inline]
+    pub fn global() -> RwLockReadGuard<'static, Self> {
+        IDENTIFIER_INTERNER.read()
+    }
+
+    /// Returns the number of identifiers interned by the interner, including
+    /// builtin ones.
     #[allow(clippy::len_without_is_empty)] // interner is never empty
+    #[inline]
     #[must_use]
-    fn len(&self) -> usize {
+    pub fn len(&self) -> usize {
         self.0.len()
     }
 
-    /// Interns the given identifier (if it doesn't exist) and returns a corresponding symbol.
-    fn get_or_intern(&mut self, identifier: impl AsRef<str>) -> IdentifierId {
+    /// Returns the number of bytes used to store interned identifiers.
+    #[inline]
+    #[must_use]
+    pub fn bytes_used(&self) -> usize {
+        self.0.bytes_used()
+    }
+
+    /// Returns the ID of the given identifier, if it is already interned.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, identifier: &str) -> Option<IdentifierId> {
+        self.0.get(identifier)
+    }
+
+    /// Interns the given identifier (if it doesn't exist) and returns a
+    /// corresponding symbol. The identifier is only copied, if it is not
+    /// interned yet.
+    pub fn get_or_intern(&mut self, identifier: &str) -> IdentifierId {
         self.0.get_or_intern(identifier)
     }
 
@@ -555,7 +625,7 @@ impl IdentifierInterner {
     /// # Example
     ///
     /// ```ignore
-    /// use stellar_interner::{IdentifierInterner, builtin_identifiers::UINT8, IdentifierId};
+    /// use stellar_interner::{IdentifierInterner, builtin_identifiers::UINT8, DUMMY_IDENTIFIER_ID};
     ///
     /// let mut identifier_interner = IdentifierInterner::new();
     ///
@@ -563,7 +633,7 @@ impl IdentifierInterner {
     ///
     /// assert_eq!(identifier_interner.resolve_or_none(hello_id), Some("hello"));
     /// assert_eq!(identifier_interner.resolve_or_none(UINT8), Some("uint8")); // interned by default
-    /// assert_eq!(identifier_interner.resolve_or_none(IdentifierId(3123123123)), None);
+    /// assert_eq!(identifier_interner.resolve_or_none(DUMMY_IDENTIFIER_ID), None);
     /// ```
     #[must_use]
     fn resolve_or_none(&self, id: IdentifierId) -> Option<&str> {
@@ -598,7 +668,7 @@ lazy_static! {
 
 /// ID of a path in the [`PathInterner`].
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub struct PathId(pub usize);
+pub struct PathId(usize);
 
 impl<P> From<P> for PathId
 where
@@ -0,0 +1,33 @@
+use stellar_interner::{
+    builtin_identifiers::{AS, FALSE, FUN, INT32, MUT, STRUCT, TRUE},
+    IdentifierId,
+};
+
+#[test]
+fn builtin_identifiers_are_interned_by_default() {
+    // Resolving doesn't intern anything, so the identifiers must be already
+    // present in the interner.
+    assert_eq!(FUN.as_str(), "fun");
+    assert_eq!(STRUCT.as_str(), "struct");
+    assert_eq!(INT32.as_str(), "int32");
+    assert_eq!(TRUE.as_str(), "true");
+}
+
+#[test]
+fn interning_builtin_identifier_returns_its_id() {
+    assert_eq!(IdentifierId::from("fun"), FUN);
+    assert_eq!(IdentifierId::from("struct"), STRUCT);
+    assert_eq!(IdentifierId::from("int32"), INT32);
+}
+
+#[test]
+fn is_keyword() {
+    assert!(AS.is_keyword());
+    assert!(FUN.is_keyword());
+    assert!(MUT.is_keyword());
+
+    assert!(!INT32.is_keyword());
+    assert!(!TRUE.is_keyword());
+    assert!(!FALSE.is_keyword());
+    assert!(!IdentifierId::from("funny").is_keyword());
+}
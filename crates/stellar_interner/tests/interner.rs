@@ -0,0 +1,39 @@
+use stellar_interner::{IdentifierId, IdentifierInterner};
+
+#[test]
+fn repeated_interning_doesnt_grow_storage() {
+    let mut interner = IdentifierInterner::new();
+
+    let len = interner.len();
+    let bytes_used = interner.bytes_used();
+
+    let id = interner.get_or_intern("point");
+
+    assert_eq!(interner.len(), len + 1);
+    assert_eq!(interner.bytes_used(), bytes_used + "point".len());
+
+    assert_eq!(interner.get_or_intern("point"), id);
+    assert_eq!(interner.get("point"), Some(id));
+
+    assert_eq!(interner.len(), len + 1);
+    assert_eq!(interner.bytes_used(), bytes_used + "point".len());
+}
+
+#[test]
+fn lookup_doesnt_intern() {
+    let interner = IdentifierInterner::new();
+
+    assert_eq!(interner.get("never_interned_identifier"), None);
+    assert_eq!(interner.len(), IdentifierInterner::new().len());
+}
+
+#[test]
+fn global_interner() {
+    let id = IdentifierId::from("global_interner_test");
+
+    assert_eq!(IdentifierId::from("global_interner_test"), id);
+    assert_eq!(
+        IdentifierInterner::global().get("global_interner_test"),
+        Some(id)
+    );
+}
@@ -76,9 +76,9 @@
 
 use std::{mem, str::Chars, string::String};
 
-use stellar_ast::token::{resolve_keyword, LexError, Punctuator, RawLexError, RawToken, Token};
+use stellar_ast::token::{Keyword, LexError, Punctuator, RawLexError, RawToken, Token};
 use stellar_filesystem::location::{ByteOffset, Location};
-use stellar_interner::{IdentifierId, PathId};
+use stellar_interner::{builtin_identifiers, IdentifierId, PathId, DUMMY_IDENTIFIER_ID};
 use stellar_stable_likely::unlikely;
 
 mod number;
@@ -128,7 +128,7 @@ mod number;
 ///
 /// [`EndOfFile`]: stellar_ast::token::RawToken::EndOfFile
 /// [`Error`]: stellar_ast::token::RawToken::Error
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Lexer<'s> {
     /// ID of the path of the file being scanned.
     pub filepath: PathId,
@@ -168,6 +168,14 @@ pub struct Lexer<'s> {
 
     /// Buffer for storing scanned strings (after processing escape sequences).
     scanned_string: String,
+
+    /// Previous token (comments are not included). Used to tokenize
+    /// tuple indices, e.g. `0` and `1` in `x.0.1`.
+    previous_token: RawToken,
+
+    /// Maximum length of an identifier in bytes. Longer identifiers are not
+    /// interned and are tokenized as [`RawLexError::IdentifierTooLong`].
+    max_identifier_length: usize,
 }
 
 impl<'s> Lexer<'s> {
@@ -184,12 +192,30 @@ impl<'s> Lexer<'s> {
             next: chars.next(),
             chars,
             offset: ByteOffset(0),
-            scanned_identifier: IdentifierId(0),
+            scanned_identifier: DUMMY_IDENTIFIER_ID,
             scanned_char: '\0',
             scanned_string: String::new(),
+            previous_token: RawToken::EndOfFile,
+            max_identifier_length: usize::MAX,
         }
     }
 
+    /// Sets the maximum length of an identifier in bytes. By default,
+    /// identifiers are not limited.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_identifier_length(mut self, max_identifier_length: usize) -> Self {
+        self.max_identifier_length = max_identifier_length;
+        self
+    }
+
+    /// Returns the maximum length of an identifier in bytes.
+    #[inline]
+    #[must_use]
+    pub const fn max_identifier_length(&self) -> usize {
+        self.max_identifier_length
+    }
+
     /// Returns a string being scanned early on (after processing escape sequences) and
     /// cleans internal lexer string buffer. So it must be used only once!
     #[inline]
@@ -623,6 +649,13 @@ impl<'s> Lexer<'s> {
 
         self.advance();
 
+        if name.len() > self.max_identifier_length {
+            return Token {
+                raw: RawToken::Error(RawLexError::IdentifierTooLong),
+                location: self.location_from(start_location),
+            };
+        }
+
         self.scanned_identifier = IdentifierId::from(name);
 
         Token {
@@ -680,31 +713,33 @@ impl<'s> Lexer<'s> {
             };
         }
 
-        if let Some(reserved) = resolve_keyword(name) {
-            Token {
-                raw: reserved.into(),
+        if name.len() > self.max_identifier_length {
+            return Token {
+                raw: RawToken::Error(RawLexError::IdentifierTooLong),
                 location: self.location_from(start_location),
-            }
+            };
+        }
+
+        let id = IdentifierId::from(name);
+
+        let raw = if let Some(keyword) = Keyword::from_identifier(id) {
+            keyword.into()
         }
         // Both `true` and `false` are considered boolean literals and are not
         // included in the `Keyword` enum.
-        else if name == "true" {
-            Token {
-                raw: RawToken::TrueBoolLiteral,
-                location: self.location_from(start_location),
-            }
-        } else if name == "false" {
-            Token {
-                raw: RawToken::FalseBoolLiteral,
-                location: self.location_from(start_location),
-            }
+        else if id == builtin_identifiers::TRUE {
+            RawToken::TrueBoolLiteral
+        } else if id == builtin_identifiers::FALSE {
+            RawToken::FalseBoolLiteral
         } else {
-            self.scanned_identifier = IdentifierId::from(name);
+            self.scanned_identifier = id;
 
-            Token {
-                raw: RawToken::Identifier,
-                location: self.location_from(start_location),
-            }
+            RawToken::Identifier
+        };
+
+        Token {
+            raw,
+            location: self.location_from(start_location),
         }
     }
 
@@ -720,6 +755,16 @@ impl<'s> Lexer<'s> {
 
     /// Proceeds to the next token and returns it (see [top level documentation](../index.html) for more details).
     pub fn next_token(&mut self) -> Token {
+        let token = self.scan_token();
+
+        if token.raw != RawToken::Comment {
+            self.previous_token = token.raw;
+        }
+
+        token
+    }
+
+    fn scan_token(&mut self) -> Token {
         self.eat_whitespaces();
 
         // EOF will be processed only once throughout the scanning process compared to all other
@@ -775,6 +820,7 @@ impl<'s> Lexer<'s> {
             (Some('|'), _) => self.advance_with(Punctuator::Or),
             (Some('?'), _) => self.advance_with(Punctuator::QuestionMark),
             (Some('&'), Some('&')) => self.advance_twice_with(Punctuator::DoubleAmpersand),
+            (Some('&'), Some('=')) => self.advance_twice_with(Punctuator::AmpersandEq),
             (Some('&'), _) => self.advance_with(Punctuator::Ampersand),
             (Some('^'), Some('=')) => self.advance_twice_with(Punctuator::CaretEq),
             (Some('^'), _) => self.advance_with(Punctuator::Caret),
@@ -787,12 +833,15 @@ impl<'s> Lexer<'s> {
             (Some('}'), _) => self.advance_with(Punctuator::CloseBrace),
             (Some(','), _) => self.advance_with(Punctuator::Comma),
             (Some(';'), _) => self.advance_with(Punctuator::Semicolon),
-            (Some('%'), Some('=')) => self.advance_with(Punctuator::PercentEq),
+            (Some('%'), Some('=')) => self.advance_twice_with(Punctuator::PercentEq),
             (Some('%'), _) => self.advance_with(Punctuator::Percent),
             (Some('.'), Some('.')) => self.advance_twice_with(Punctuator::DoubleDot),
             _ => {
+                // `.0` in `x.0` is a tuple index, not a float literal.
                 if self.current.is_ascii_digit()
-                    || (self.current == Some('.') && self.next.is_ascii_digit())
+                    || (self.current == Some('.')
+                        && self.next.is_ascii_digit()
+                        && !self.previous_token_ends_expression())
                 {
                     return self.tokenize_number();
                 } else if is_id_start(self.current) {
@@ -807,30 +856,50 @@ impl<'s> Lexer<'s> {
     }
 }
 
+impl Lexer<'_> {
+    /// Returns `true` if the previous token can end an expression, so that
+    /// `.` after it is a field access or a tuple index, e.g. `x.0`, `(a, b).1`.
+    const fn previous_token_ends_expression(&self) -> bool {
+        matches!(
+            self.previous_token,
+            RawToken::Identifier
+                | RawToken::IntegerLiteral
+                | RawToken::Punctuator(Punctuator::CloseParent | Punctuator::CloseBracket)
+        )
+    }
+}
+
 /// Returns `true` if `c` is a whitespace.
 const fn is_whitespace(c: Option<char>) -> bool {
+    matches!(c, Some(c) if is_whitespace_char(c))
+}
+
+/// Returns `true` if `c` is a whitespace character, i.e. it separates tokens
+/// and is skipped by the lexer.
+#[must_use]
+pub const fn is_whitespace_char(c: char) -> bool {
     // Note that it is ok to hard-code the values, because
     // the set is stable and doesn't change with different
     // Unicode versions.
     matches!(
         c,
-        Some('\u{0009}')   // \t
-        | Some('\u{000A}') // \n
-        | Some('\u{000B}') // vertical tab
-        | Some('\u{000C}') // form feed
-        | Some('\u{000D}') // \r
-        | Some('\u{0020}') // space
+        '\u{0009}'   // \t
+        | '\u{000A}' // \n
+        | '\u{000B}' // vertical tab
+        | '\u{000C}' // form feed
+        | '\u{000D}' // \r
+        | '\u{0020}' // space
 
         // NEXT LINE from latin1
-        | Some('\u{0085}')
+        | '\u{0085}'
 
         // Bidi markers
-        | Some('\u{200E}') // LEFT-TO-RIGHT MARK
-        | Some('\u{200F}') // RIGHT-TO-LEFT MARK
+        | '\u{200E}' // LEFT-TO-RIGHT MARK
+        | '\u{200F}' // RIGHT-TO-LEFT MARK
 
         // Dedicated whitespace characters from Unicode
-        | Some('\u{2028}') // LINE SEPARATOR
-        | Some('\u{2029}') // PARAGRAPH SEPARATOR
+        | '\u{2028}' // LINE SEPARATOR
+        | '\u{2029}' // PARAGRAPH SEPARATOR
     )
 }
 
@@ -2,16 +2,24 @@
 
 use std::char::from_u32;
 
-use stellar_ast::token::{NumberKind, RawLexError, RawToken, Token};
+use stellar_ast::{
+    token::{NumberKind, Punctuator, RawLexError, RawToken, Token},
+    NumericLiteralSuffix,
+};
 use stellar_filesystem::location::{ByteOffset, Location};
 
-use crate::{is_id_start, IsAsciiExt, Lexer};
+use crate::{is_id_continue, is_id_start, IsAsciiExt, Lexer};
 
 impl Lexer<'_> {
     /// Tokenizes a number literal token.
     pub(crate) fn tokenize_number(&mut self) -> Token {
         let start_offset = self.offset;
 
+        // `0.1` in `x.0.1` is two tuple indices, not a float literal.
+        if self.previous_token == RawToken::Punctuator(Punctuator::Dot) {
+            return self.tokenize_tuple_index();
+        }
+
         // If the number is an integer or a float.
         let mut number_kind = NumberKind::Invalid;
 
@@ -145,6 +153,26 @@ impl Lexer<'_> {
             }
         }
 
+        // 255u8
+        //    ^^ suffix
+        if is_id_start(self.current) {
+            let suffix_start_offset = self.offset;
+            let suffix =
+                self.advance_while(suffix_start_offset, |current, _| is_id_continue(current));
+
+            match NumericLiteralSuffix::from_str_or_none(suffix) {
+                // `1f32` is a float literal.
+                Some(suffix) if suffix.is_float() => number_kind = NumberKind::Float,
+                Some(_) if number_kind == NumberKind::Int => {}
+                _ => {
+                    return Token {
+                        raw: RawToken::Error(RawLexError::InvalidNumberSuffix),
+                        location: self.location_from(suffix_start_offset),
+                    };
+                }
+            }
+        }
+
         match number_kind {
             NumberKind::Int => Token {
                 raw: RawToken::IntegerLiteral,
@@ -160,6 +188,20 @@ impl Lexer<'_> {
         }
     }
 
+    /// Tokenizes a tuple index, e.g. `0` in `x.0`, which consists of decimal digits only.
+    fn tokenize_tuple_index(&mut self) -> Token {
+        let start_offset = self.offset;
+
+        while self.current.is_ascii_digit() {
+            self.advance();
+        }
+
+        Token {
+            raw: RawToken::IntegerLiteral,
+            location: self.location_from(start_offset),
+        }
+    }
+
     /// Checks if the number has an invalid `_` separator in it (if it doesn't separate
     /// successive digits, the function returns the location of the separator).
     fn check_for_invalid_separator(
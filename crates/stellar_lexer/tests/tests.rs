@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use stellar_ast::token::{RawLexError, RawToken::*};
+    use stellar_ast::token::{Keyword, Punctuator, RawLexError, RawToken::*};
     use stellar_interner::DUMMY_PATH_ID;
     use stellar_lexer::Lexer;
 
@@ -24,6 +24,21 @@ mod tests {
     lexer_test!(float3, "3.14e-5", FloatLiteral);
     lexer_test!(float4, "3.14E5", FloatLiteral);
     lexer_test!(float5, "3.14E-5", FloatLiteral);
+    lexer_test!(float6, ".5", FloatLiteral);
+    lexer_test!(integer_with_suffix, "255u8", IntegerLiteral);
+    lexer_test!(hexadecimal_with_suffix, "0xffi64", IntegerLiteral);
+    lexer_test!(float_with_suffix, "2.5f32", FloatLiteral);
+    lexer_test!(integer_with_float_suffix, "1f64", FloatLiteral);
+    lexer_test!(
+        unknown_suffix,
+        "1u7",
+        Error(RawLexError::InvalidNumberSuffix)
+    );
+    lexer_test!(
+        float_with_integer_suffix,
+        "2.5u8",
+        Error(RawLexError::InvalidNumberSuffix)
+    );
     lexer_test!(global_doc_comment, "///test comment", LocalDocComment);
     lexer_test!(local_doc_comment, "//!test comment", GlobalDocComment);
     lexer_test!(unexpected_char, "١", Error(RawLexError::UnexpectedChar));
@@ -49,6 +64,73 @@ mod tests {
         "`test\n",
         Error(RawLexError::UnterminatedWrappedIdentifier)
     );
+    lexer_test!(keyword, "fun", Keyword(Keyword::Fun));
+    lexer_test!(keyword_prefix, "funny", Identifier);
+    lexer_test!(true_literal, "true", TrueBoolLiteral);
+    lexer_test!(false_literal, "false", FalseBoolLiteral);
     lexer_test!(small_u, "'\\u{1E41}'", CharLiteral);
     lexer_test!(big_u, "\"\\U{0010FFFF}\"", StringLiteral);
+
+    #[test]
+    fn tuple_indices() {
+        let mut lexer = Lexer::new(DUMMY_PATH_ID, "t.0.1 (a, b).12");
+        let tokens = std::iter::from_fn(|| Some(lexer.next_token().raw))
+            .take_while(|token| *token != EndOfFile)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens,
+            [
+                Identifier,
+                Punctuator(Punctuator::Dot),
+                IntegerLiteral,
+                Punctuator(Punctuator::Dot),
+                IntegerLiteral,
+                Punctuator(Punctuator::OpenParent),
+                Identifier,
+                Punctuator(Punctuator::Comma),
+                Identifier,
+                Punctuator(Punctuator::CloseParent),
+                Punctuator(Punctuator::Dot),
+                IntegerLiteral,
+            ]
+        );
+    }
+
+    #[test]
+    fn compound_assignment_operators() {
+        let mut lexer = Lexer::new(DUMMY_PATH_ID, "a %= b &= c");
+        let tokens = std::iter::from_fn(|| Some(lexer.next_token().raw))
+            .take_while(|token| *token != EndOfFile)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens,
+            [
+                Identifier,
+                Punctuator(Punctuator::PercentEq),
+                Identifier,
+                Punctuator(Punctuator::AmpersandEq),
+                Identifier,
+            ]
+        );
+    }
+
+    #[test]
+    fn identifier_too_long() {
+        let mut lexer =
+            Lexer::new(DUMMY_PATH_ID, "abcd abcde `abcde`").with_max_identifier_length(4);
+        let tokens = std::iter::from_fn(|| Some(lexer.next_token().raw))
+            .take_while(|token| *token != EndOfFile)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens,
+            [
+                Identifier,
+                Error(RawLexError::IdentifierTooLong),
+                Error(RawLexError::IdentifierTooLong),
+            ]
+        );
+    }
 }
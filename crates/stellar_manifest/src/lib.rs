@@ -23,6 +23,20 @@
 //! The first part is a general information about the package, the second part is optional
 //! and contains information about the dependencies of the current package.
 //!
+//! Packages are binary by default, i.e. they must have an entry point - the `main` function.
+//! Packages, that are only used as dependencies, are marked with `kind = "library"`.
+//!
+//! Several packages can be checked together as a workspace. The root manifest of a
+//! workspace lists directories of its members, relative to the root directory:
+//!
+//! ```toml
+//! [workspace]
+//! members = ["json", "http", "server"]
+//! ```
+//!
+//! The root manifest may contain only the `[workspace]` section, see
+//! [`parse_workspace_manifest()`].
+//!
 //! [TOML]: https://toml.io/en/v1.0.0
 
 #![doc(
@@ -151,6 +165,19 @@ pub struct TomlPackage {
     pub keywords: Option<Vec<String>>,
     /// Categories associated with the package.
     pub categories: Option<Vec<String>>,
+    /// Kind of the package, binary if not specified.
+    pub kind: Option<PackageKind>,
+}
+
+/// Kind of a package, see [`TomlPackage::kind()`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageKind {
+    /// A package with an entry point - the `main` function.
+    #[default]
+    Binary,
+    /// A package, that is only used as a dependency of other packages.
+    Library,
 }
 
 impl TomlPackage {
@@ -168,9 +195,18 @@ impl TomlPackage {
             repository: None,
             keywords: None,
             categories: None,
+            kind: None,
         }
     }
 
+    /// Returns the kind of the package, [`PackageKind::Binary`] if it is not
+    /// specified.
+    #[inline]
+    #[must_use]
+    pub fn kind(&self) -> PackageKind {
+        self.kind.unwrap_or_default()
+    }
+
     /// Builds a new toml package struct with a given description.
     #[inline]
     #[must_use]
@@ -221,6 +257,14 @@ impl TomlPackage {
         self.categories = Some(categories.into_iter().map(Into::into).collect());
         self
     }
+
+    /// Builds a new toml package struct with a given kind.
+    #[inline]
+    #[must_use]
+    pub const fn with_kind(mut self, kind: PackageKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
 }
 
 /// Represents dependency (value part of the key-value pair in the `[dependencies]` section of the manifest).
@@ -269,6 +313,30 @@ impl TomlDependency {
     }
 }
 
+/// Represents data in the `[workspace]` section of the root manifest of a workspace.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub struct TomlWorkspace {
+    /// Paths to directories of member packages, relative to the root directory.
+    pub members: Vec<String>,
+}
+
+impl TomlWorkspace {
+    /// Returns a new toml workspace struct with given members.
+    #[inline]
+    #[must_use]
+    pub fn new(members: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            members: members.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// The root manifest of a workspace, other sections of which are ignored.
+#[derive(Deserialize)]
+struct TomlWorkspaceManifest {
+    workspace: Option<TomlWorkspace>,
+}
+
 /// # Errors
 ///
 /// Error occurs when manifest format is not valid, or the structure itself is also not valid.
@@ -290,6 +358,24 @@ pub fn parse_manifest(source: impl AsRef<str>) -> Result<TomlManifest, String> {
     Ok(manifest)
 }
 
+/// Parses the `[workspace]` section of a manifest and returns `None` if there
+/// is no such section. Other sections, e.g. `[package]`, are not required.
+///
+/// # Errors
+///
+/// Error occurs when manifest format is not valid, or the `[workspace]` section is not valid.
+/// See [crate level documentation] for more information.
+///
+/// [crate level documentation]: crate
+pub fn parse_workspace_manifest(source: impl AsRef<str>) -> Result<Option<TomlWorkspace>, String> {
+    let toml = parse_document(source)?;
+
+    match serde_ignored::deserialize(toml.into_deserializer(), |_| {}) {
+        Ok(TomlWorkspaceManifest { workspace }) => Ok(workspace),
+        Err(err) => Err(format!("{err}")),
+    }
+}
+
 fn parse_document(source: impl AsRef<str>) -> Result<Document, String> {
     match source.as_ref().parse::<Document>() {
         Ok(table) => Ok(table),
@@ -1,5 +1,8 @@
 use stellar_manifest::TomlPackage;
-use stellar_manifest::{parse_manifest, TomlDependency, TomlManifest};
+use stellar_manifest::{
+    parse_manifest, parse_workspace_manifest, PackageKind, TomlDependency, TomlManifest,
+    TomlWorkspace,
+};
 
 #[test]
 fn simple_manifest() {
@@ -45,6 +48,22 @@ keywords = [\"json\", \"config\"]";
     );
 }
 
+#[test]
+fn package_kind() {
+    let manifest = "[package]
+name = \"json\"
+version = \"1.0.0\"
+kind = \"library\"";
+
+    let manifest = parse_manifest(manifest).unwrap();
+
+    assert_eq!(manifest.package.kind(), PackageKind::Library);
+    assert_eq!(
+        TomlPackage::new("json", "1.0.0").kind(),
+        PackageKind::Binary
+    );
+}
+
 #[test]
 fn dependencies() {
     let manifest = "[package]
@@ -75,3 +94,21 @@ foo2 = { path = \"../foo\" }";
         )
     );
 }
+
+#[test]
+fn workspace() {
+    let manifest = "[workspace]
+members = [\"json\", \"http\"]";
+
+    assert_eq!(
+        parse_workspace_manifest(manifest),
+        Ok(Some(TomlWorkspace::new(["json", "http"])))
+    );
+
+    let manifest = "[package]
+name = \"json\"
+version = \"1.0.0\"";
+
+    assert_eq!(parse_workspace_manifest(manifest), Ok(None));
+    assert!(parse_workspace_manifest("[workspace]\nmembers = 1").is_err());
+}
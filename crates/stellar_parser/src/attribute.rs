@@ -0,0 +1,103 @@
+use stellar_ast::{
+    token::{Punctuator, RawToken},
+    Attribute, AttributeArgument,
+};
+
+use crate::{list::ListParser, Parse, ParseState};
+
+/// Parses attributes before a module item, e.g. `#[cfg(test)] #[deprecated]`.
+pub(crate) struct AttributesParser;
+
+impl Parse for AttributesParser {
+    type Output = Option<Vec<Attribute>>;
+
+    fn parse(self, state: &mut ParseState<'_, '_>) -> Self::Output {
+        let mut attributes = vec![];
+
+        while state.next_token.raw == Punctuator::HashTag {
+            attributes.push(AttributeParser.parse(state)?);
+        }
+
+        Some(attributes)
+    }
+}
+
+struct AttributeParser;
+
+impl Parse for AttributeParser {
+    type Output = Option<Attribute>;
+
+    fn parse(self, state: &mut ParseState<'_, '_>) -> Self::Output {
+        let start = state.next_token.location.start;
+
+        state.advance(); // `#`
+
+        state.consume(Punctuator::OpenBracket)?;
+
+        let name = state.consume_identifier()?;
+
+        let arguments = if state.next_token.raw == Punctuator::OpenParent {
+            Some(AttributeArgumentsParser.parse(state)?)
+        } else {
+            None
+        };
+
+        state.consume(Punctuator::CloseBracket)?;
+
+        Some(Attribute {
+            location: state.location_from(start),
+            name,
+            arguments,
+        })
+    }
+}
+
+struct AttributeArgumentsParser;
+
+impl Parse for AttributeArgumentsParser {
+    type Output = Option<Vec<AttributeArgument>>;
+
+    fn parse(self, state: &mut ParseState<'_, '_>) -> Self::Output {
+        state.advance(); // `(`
+
+        let arguments = ListParser::new(&[RawToken::from(Punctuator::CloseParent)], |state| {
+            AttributeArgumentParser.parse(state)
+        })
+        .parse(state)?;
+
+        state.advance(); // `)`
+
+        Some(arguments)
+    }
+}
+
+struct AttributeArgumentParser;
+
+impl Parse for AttributeArgumentParser {
+    type Output = Option<AttributeArgument>;
+
+    fn parse(self, state: &mut ParseState<'_, '_>) -> Self::Output {
+        if state.next_token.raw == RawToken::StringLiteral {
+            state.advance();
+
+            return Some(AttributeArgument::String {
+                location: state.current_token.location,
+                value: state.lexer.scanned_string(),
+            });
+        }
+
+        let name = state.consume_identifier()?;
+
+        if state.next_token.raw == Punctuator::OpenParent {
+            let arguments = AttributeArgumentsParser.parse(state)?;
+
+            Some(AttributeArgument::Call {
+                location: state.location_from(name.location.start),
+                name,
+                arguments,
+            })
+        } else {
+            Some(AttributeArgument::Identifier(name))
+        }
+    }
+}
@@ -1,10 +1,18 @@
 //! Defines diagnostics for parser.
 
-use stellar_ast::token::{LexError, Token};
+use stellar_ast::{
+    token::{LexError, Token},
+    IdentifierAST,
+};
 use stellar_diagnostics::diagnostic::Label;
+use stellar_diagnostics::registry::DiagnosticInfo;
 use stellar_diagnostics::BuildDiagnostic;
-use stellar_diagnostics::{define_diagnostics, diagnostic::Diagnostic};
+use stellar_diagnostics::{
+    define_diagnostics,
+    diagnostic::{Diagnostic, Severity},
+};
 use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_interner::PathId;
 
 /// Context in which the unnecessary visibility qualifier error is found.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -28,6 +36,8 @@ pub enum UnnecessaryVisibilityQualifierContext {
 }
 
 define_diagnostics! {
+    implemented_manually { UnexpectedToken, UnnecessaryVisibilityQualifierDiagnostic }
+
     /// Diagnostic related to an error occured when tokenizing.
     diagnostic(error) LexErrorDiagnostic(self, error: LexError) {
         code { "E000" }
@@ -37,12 +47,36 @@ define_diagnostics! {
         }
     }
 
+    /// Diagnostic, that occurs when an identifier is longer, than
+    /// [`Config::max_identifier_length()`].
+    ///
+    /// [`Config::max_identifier_length()`]: stellar_database::Config::max_identifier_length
+    diagnostic(error) IdentifierTooLong(
+        self,
+        location: Location,
+        max_identifier_length: usize
+    ) {
+        code { "E012" }
+        message { "identifier is too long" }
+        labels {
+            primary { self.location => format!("longer than {} bytes", self.max_identifier_length) }
+        }
+    }
+
     /// Diagnostic related to an integer overflow error.
-    diagnostic(error) IntegerOverflow(self, location: Location) {
+    ///
+    /// `literal` is the original spelling of the literal, if the diagnostic
+    /// is reported for a single literal, e.g. `0x1_0000_0000_0000_0000`.
+    diagnostic(error) IntegerOverflow(self, location: Location, literal: Option<String>) {
         code { "E002" }
         message { "unexpected integer overflow" }
         labels {
-            primary { self.location => "error appeared when parsing this integer" }
+            primary {
+                self.location => match &self.literal {
+                    Some(literal) => format!("`{literal}` doesn't fit into `u64`"),
+                    None => "error appeared when parsing this integer".to_owned(),
+                }
+            }
         }
         notes {
             "note: integer cannot exceed the maximum value of `u64` (u64.max() == 18_446_744_073_709_551_615)"
@@ -51,11 +85,19 @@ define_diagnostics! {
     }
 
     /// Diagnostic related to an float overflow error.
-    diagnostic(error) FloatOverflow(self, location: Location) {
+    ///
+    /// `literal` is the original spelling of the literal, if the diagnostic
+    /// is reported for a single literal.
+    diagnostic(error) FloatOverflow(self, location: Location, literal: Option<String>) {
         code { "E003" }
         message { "unexpected float overflow" }
         labels {
-            primary { self.location => "error appeared when parsing this float literal" }
+            primary {
+                self.location => match &self.literal {
+                    Some(literal) => format!("`{literal}` doesn't fit into `f64`"),
+                    None => "error appeared when parsing this float literal".to_owned(),
+                }
+            }
         }
         notes {
             "note: float cannot exceed the maximum value of `f64` (f64.max() == 1.7976931348623157e+308)"
@@ -63,25 +105,286 @@ define_diagnostics! {
         }
     }
 
-    /// Diagnostic related to an unexpected token error.
-    diagnostic(error) UnexpectedToken(
+    /// Diagnostic, that occurs when a file ends before a statements block or
+    /// an inline module is closed with `}`.
+    diagnostic(error) EOFInsteadOfCloseBrace(
+        self,
+        location: Location,
+        open_brace_location: Location
+    ) {
+        code { "E005" }
+        message { "expected `}`, found end of file" }
+        labels {
+            primary { self.location => "expected `}`" }
+            secondary { self.open_brace_location => "unclosed delimiter" }
+        }
+    }
+
+    /// Diagnostic, that occurs when a type is nested deeper than
+    /// [`Config::max_type_nesting()`] allows.
+    ///
+    /// [`Config::max_type_nesting()`]: stellar_database::Config::max_type_nesting
+    diagnostic(error) TypeNestingLimitExceeded(self, location: Location, limit: usize) {
+        code { "E006" }
+        message { format!("type nesting limit of {} is exceeded", self.limit) }
+        labels {
+            primary { self.location => "type is nested too deeply here" }
+        }
+        notes {
+            "note: consider introducing a type alias for the inner type"
+        }
+    }
+
+    /// Diagnostic, that occurs when a source file of a module cannot be read.
+    /// The module is replaced with an empty placeholder, so that other
+    /// modules can still be checked.
+    diagnostic(error) ModuleFailedToLoad(
+        self,
+        module_path: String,
+        filepath: PathId,
+        reason: String
+    ) {
+        code { "E007" }
+        message { format!("module `{}` failed to load", self.module_path) }
+        labels {}
+        notes {
+            format!("note: tried to read `{}`: {}", self.filepath, self.reason)
+        }
+    }
+
+    /// Diagnostic, that occurs when a source file is not valid UTF-8.
+    diagnostic(error) InvalidUtf8File(
+        self,
+        filepath: PathId,
+        offset: ByteOffset
+    ) {
+        code { "E009" }
+        message { format!("`{}` is not valid UTF-8", self.filepath) }
+        labels {}
+        notes {
+            format!("note: invalid byte sequence at byte offset {}", self.offset.0)
+            "note: source files must be encoded in UTF-8".to_owned()
+            "help: use `--lenient-utf8` to replace invalid byte sequences with `U+FFFD`".to_owned()
+        }
+    }
+
+    /// Diagnostic, that occurs when a source file is larger, than
+    /// [`Config::max_file_size()`].
+    ///
+    /// [`Config::max_file_size()`]: stellar_database::Config::max_file_size
+    diagnostic(error) FileTooLarge(
+        self,
+        filepath: PathId,
+        size: usize,
+        max_file_size: usize
+    ) {
+        code { "E010" }
+        message { format!("`{}` is too large to be compiled", self.filepath) }
+        labels {}
+        notes {
+            format!(
+                "note: the file has {} bytes, but the limit is {} bytes",
+                self.size, self.max_file_size
+            )
+            "help: use `--max-file-size` to raise the limit".to_owned()
+        }
+    }
+
+    /// Diagnostic, that occurs when a source file has more tokens, than
+    /// [`Config::max_token_count()`].
+    ///
+    /// [`Config::max_token_count()`]: stellar_database::Config::max_token_count
+    diagnostic(error) TooManyTokens(
+        self,
+        filepath: PathId,
+        max_token_count: usize
+    ) {
+        code { "E011" }
+        message { format!("`{}` has too many tokens to be compiled", self.filepath) }
+        labels {}
+        notes {
+            format!("note: the limit is {} tokens", self.max_token_count)
+            "help: use `--max-token-count` to raise the limit".to_owned()
+        }
+    }
+
+    /// Diagnostic, that occurs when a tuple, list or tuple-like struct pattern
+    /// contains more than one rest pattern, e.g. `(.., x, ..)`.
+    diagnostic(error) MultipleRestPatterns(
+        self,
+        location: Location,
+        first_rest_location: Location
+    ) {
+        code { "E008" }
+        message { "`..` can only be used once per pattern" }
+        labels {
+            primary { self.location => "can only be used once per pattern" }
+            secondary { self.first_rest_location => "previously used here" }
+        }
+    }
+
+    /// Diagnostic, that occurs when an enum item with a payload has an explicit
+    /// discriminant, e.g. `A(int32) = 1`. Only items without payload, e.g.
+    /// `A = 1`, can have discriminants.
+    diagnostic(error) DiscriminantOnEnumItemWithPayload(
         self,
-        offset: ByteOffset,
-        got: Token,
-        expected: String
+        location: Location,
+        item_name: IdentifierAST,
+        tuple_like: bool
     ) {
-        code { "E001" }
-        message { format!("expected {}, found {}", self.expected, self.got.raw) }
+        code { "E019" }
+        message {
+            format!(
+                "explicit discriminant on the {} enum item `{}`",
+                if self.tuple_like { "tuple-like" } else { "struct" },
+                self.item_name.id
+            )
+        }
+        labels {
+            primary { self.location => "only enum items without payload can have discriminants" }
+            secondary { self.item_name.location => "this item has a payload" }
+        }
+        suggestions {
+            self.location => "", "remove the discriminant"
+        }
+    }
+
+    /// Diagnostic, that occurs when a statement, e.g. `let`, `return` or
+    /// `break`, is not followed by `;`.
+    diagnostic(error) NoSemicolonAfterStatement(self, statement_location: Location) {
+        code { "E013" }
+        message { "expected `;` after statement" }
+        labels {
+            primary {
+                Location {
+                    start: self.statement_location.end,
+                    ..self.statement_location
+                } => "help: add `;` here"
+            }
+            secondary { self.statement_location => "statement" }
+        }
+        suggestions {
+            Location {
+                start: self.statement_location.end,
+                ..self.statement_location
+            } => ";", "add `;`"
+        }
+    }
+
+    /// Diagnostic, that occurs when an expression statement, which is not
+    /// the tail expression of a block, is not followed by `;`, e.g. `a()` in
+    /// `{ a() b() }`.
+    diagnostic(error) NoSemicolonAfterExpression(self, expression_location: Location) {
+        code { "E014" }
+        message { "expected `;` after expression" }
         labels {
             primary {
-                self.offset.next_byte_location_at(self.got.location.filepath) =>
-                    format!("expected {}", self.expected)
+                Location {
+                    start: self.expression_location.end,
+                    ..self.expression_location
+                } => "help: add `;` here"
             }
-            secondary { self.got.location => "unexpected token" }
+            secondary { self.expression_location => "expression" }
+        }
+        suggestions {
+            Location {
+                start: self.expression_location.end,
+                ..self.expression_location
+            } => ";", "add `;`"
+        }
+    }
+
+    /// Diagnostic, that occurs when a block contains `;`, that doesn't end
+    /// any statement, e.g. the second `;` in `{ a();; }`.
+    diagnostic(warning) EmptyStatement(
+        self,
+        location: Location,
+        previous_token_location: Location
+    ) {
+        code { "W011" }
+        message { "found empty statement" }
+        labels {
+            primary { self.location => "help: remove this `;`" }
+            secondary { self.previous_token_location => "empty statement follows this" }
+        }
+        suggestions {
+            self.location => "", "remove `;`"
+        }
+    }
+}
+
+/// Diagnostic related to an unexpected token error.
+///
+/// If the parser consumed a token before the unexpected one, the diagnostic
+/// points at the end of that token, e.g. at the place, where `;` is missing,
+/// and marks the unexpected token with a secondary label:
+///
+/// ```txt
+/// let a = 1
+///          - expected `;`
+/// let b = 2;
+/// --- unexpected token
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnexpectedToken {
+    /// End of the previous token, `None` if no tokens were consumed yet.
+    pub offset: Option<ByteOffset>,
+
+    /// The unexpected token.
+    pub got: Token,
+
+    /// Description of what was expected, e.g. ``"`;`"``.
+    pub expected: String,
+}
+
+impl UnexpectedToken {
+    /// Description of the diagnostic, see [`DiagnosticInfo`].
+    pub const INFO: DiagnosticInfo = DiagnosticInfo {
+        name: "UnexpectedToken",
+        code: "E001",
+        severity: Severity::Error,
+        explanation: " Diagnostic related to an unexpected token error.\n",
+    };
+
+    /// Creates a new unexpected token diagnostic.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        offset: impl Into<Option<ByteOffset>>,
+        got: impl Into<Token>,
+        expected: impl Into<String>,
+    ) -> Self {
+        Self {
+            offset: offset.into(),
+            got: got.into(),
+            expected: expected.into(),
         }
     }
 }
 
+impl BuildDiagnostic for UnexpectedToken {
+    #[inline]
+    fn build(self) -> Diagnostic {
+        let expected_label = format!("expected {}", self.expected);
+        let labels = match self.offset {
+            Some(offset) => vec![
+                Label::primary(offset.next_byte_location_at(self.got.location.filepath))
+                    .with_message(expected_label),
+                Label::secondary(self.got.location).with_message("unexpected token"),
+            ],
+            None => vec![Label::primary(self.got.location).with_message(expected_label)],
+        };
+
+        Diagnostic::error()
+            .with_code(Self::INFO.code)
+            .with_message(format!(
+                "expected {}, found {}",
+                self.expected, self.got.raw
+            ))
+            .with_labels(labels)
+    }
+}
+
 /// Diagnostic related to an unnecessary visibility qualifier error.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct UnnecessaryVisibilityQualifierDiagnostic {
@@ -92,6 +395,16 @@ pub struct UnnecessaryVisibilityQualifierDiagnostic {
     pub context: UnnecessaryVisibilityQualifierContext,
 }
 
+impl UnnecessaryVisibilityQualifierDiagnostic {
+    /// Description of the diagnostic, see [`DiagnosticInfo`].
+    pub const INFO: DiagnosticInfo = DiagnosticInfo {
+        name: "UnnecessaryVisibilityQualifierDiagnostic",
+        code: "E004",
+        severity: Severity::Error,
+        explanation: " Diagnostic related to an unnecessary visibility qualifier error.\n",
+    };
+}
+
 impl BuildDiagnostic for UnnecessaryVisibilityQualifierDiagnostic {
     #[inline]
     fn build(self) -> Diagnostic {
@@ -109,7 +422,7 @@ impl BuildDiagnostic for UnnecessaryVisibilityQualifierDiagnostic {
 
         Diagnostic::error()
             .with_message("unnecessary visibility qualifier".to_owned())
-            .with_code("E004")
+            .with_code(Self::INFO.code)
             .with_labels(labels)
             .with_notes(match self.context {
                 UnnecessaryVisibilityQualifierContext::InterfaceMethod { .. } => {
@@ -0,0 +1,113 @@
+//! Defines FIRST sets of the grammar, i.e. tokens, that can start a
+//! statement, a module item or an expression, and the [`expected!`] macro,
+//! that renders them in messages of [`UnexpectedToken`] diagnostics:
+//!
+//! ```txt
+//! error[E001]: expected `let`, `return`, `defer`, `break`, `continue`, or an expression, found `)`
+//! ```
+//!
+//! The sets are written by hand and must be kept in sync with the
+//! corresponding parsers.
+//!
+//! [`UnexpectedToken`]: crate::diagnostics::UnexpectedToken
+
+use stellar_ast::token::{Keyword, Punctuator, RawToken};
+use stellar_english_commons::enumeration::one_of;
+
+/// The maximum number of tokens listed in an expected list, not counting the
+/// description of the rest of the set, e.g. `or an expression`.
+pub(crate) const MAX_EXPECTED_TOKENS: usize = 5;
+
+/// Keywords, that start statements other than expression statements.
+/// Together with [`EXPRESSION_FIRST`] they form the FIRST set of statements.
+pub(crate) const STATEMENT_KEYWORDS: &[Keyword] = &[
+    Keyword::Let,
+    Keyword::Return,
+    Keyword::Defer,
+    Keyword::Break,
+    Keyword::Continue,
+];
+
+/// Keywords, that start module items after their docstrings, attributes and
+/// visibility, the most common ones first.
+pub(crate) const MODULE_ITEM_KEYWORDS: &[Keyword] = &[
+    Keyword::Fun,
+    Keyword::Struct,
+    Keyword::Enum,
+    Keyword::Interface,
+    Keyword::Import,
+    Keyword::Type,
+    Keyword::Module,
+];
+
+/// Tokens, that start expressions, including prefix operators and opening
+/// parentheses.
+pub(crate) const EXPRESSION_FIRST: &[RawToken] = &[
+    RawToken::Identifier,
+    RawToken::IntegerLiteral,
+    RawToken::FloatLiteral,
+    RawToken::StringLiteral,
+    RawToken::CharLiteral,
+    RawToken::TrueBoolLiteral,
+    RawToken::FalseBoolLiteral,
+    RawToken::Keyword(Keyword::If),
+    RawToken::Keyword(Keyword::Match),
+    RawToken::Keyword(Keyword::While),
+    RawToken::Keyword(Keyword::Loop),
+    RawToken::Punctuator(Punctuator::OpenParent),
+    RawToken::Punctuator(Punctuator::OpenBracket),
+    RawToken::Punctuator(Punctuator::OpenBrace),
+    RawToken::Punctuator(Punctuator::Or),
+    RawToken::Punctuator(Punctuator::DoubleOr),
+    RawToken::Punctuator(Punctuator::At),
+    RawToken::Punctuator(Punctuator::Underscore),
+    RawToken::Punctuator(Punctuator::Bang),
+    RawToken::Punctuator(Punctuator::Tilde),
+    RawToken::Punctuator(Punctuator::DoublePlus),
+    RawToken::Punctuator(Punctuator::DoubleMinus),
+    RawToken::Punctuator(Punctuator::Plus),
+    RawToken::Punctuator(Punctuator::Minus),
+];
+
+/// Returns `true` if a given token can start an expression.
+#[inline]
+#[must_use]
+pub(crate) fn starts_expression(token: RawToken) -> bool {
+    EXPRESSION_FIRST.contains(&token)
+}
+
+/// Renders the first [`MAX_EXPECTED_TOKENS`] keywords of a FIRST set followed
+/// by a description of the rest of the set, e.g.
+/// ``"`fun`, `struct`, or another module item"``.
+#[must_use]
+pub(crate) fn render_expected(keywords: &[Keyword], rest: &str) -> String {
+    let mut expected = Vec::with_capacity(MAX_EXPECTED_TOKENS + 1);
+    expected.extend(
+        keywords
+            .iter()
+            .take(MAX_EXPECTED_TOKENS)
+            .map(ToString::to_string),
+    );
+    expected.push(rest.to_owned());
+
+    one_of(expected)
+}
+
+/// Renders an expected list for [`ParseState::add_unexpected_token_diagnostic()`]:
+///
+/// ```txt
+/// expected!(an expression);
+/// expected!(STATEMENT_KEYWORDS, or an expression);
+/// ```
+///
+/// [`ParseState::add_unexpected_token_diagnostic()`]: crate::ParseState::add_unexpected_token_diagnostic
+macro_rules! expected {
+    ($($rest:ident)+) => {
+        $crate::expected::render_expected(&[], stringify!($($rest)+))
+    };
+    ($keywords:expr, or $($rest:ident)+) => {
+        $crate::expected::render_expected($keywords, stringify!($($rest)+))
+    };
+}
+
+pub(crate) use expected;
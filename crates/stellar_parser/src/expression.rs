@@ -1,13 +1,17 @@
 use stellar_ast::{
-    precedence::Precedence,
+    precedence::{self, Associativity, Precedence},
     token::{Keyword, Punctuator, RawToken},
-    BinaryOperator, Expression, IdentifierAST, LambdaFunctionParameter, MatchExpressionItem,
-    PostfixOperator, PrefixOperator, RawBinaryOperator, RawPostfixOperator, RawPrefixOperator,
-    StructFieldExpression,
+    BinaryOperator, CallArgument, Expression, IdentifierAST, LambdaFunctionParameter,
+    MatchExpressionItem, PostfixOperator, PrefixOperator, RawBinaryOperator, RawPostfixOperator,
+    RawPrefixOperator, StructFieldExpression,
 };
+use stellar_database::symbol::BuiltinSymbolId;
 use stellar_english_commons::enumeration::one_of;
+use stellar_filesystem::location::ByteOffset;
 
 use crate::{
+    diagnostics::IntegerOverflow,
+    expected::expected,
     list::ListParser,
     literal::LiteralParser,
     pattern::PatternParser,
@@ -19,7 +23,6 @@ use crate::{
 /// Parser for Stellar expressions.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct ExpressionParser {
-    in_statements_block: bool,
     precedence: Precedence,
     prohibit_struct_expressions: bool,
 }
@@ -57,15 +60,6 @@ impl ExpressionParser {
         self
     }
 
-    /// Creates a parser for expressions, that prints diagnostics
-    /// specific to statements blocks.
-    #[inline]
-    #[must_use]
-    pub const fn in_statements_block(mut self) -> Self {
-        self.in_statements_block = true;
-        self
-    }
-
     fn parse_call_expression(
         self,
         state: &mut ParseState<'_, '_>,
@@ -74,19 +68,47 @@ impl ExpressionParser {
         state.advance(); // `(`
 
         let arguments = ListParser::new(&[RawToken::from(Punctuator::CloseParent)], |state| {
-            ExpressionParser::default().parse(state)
+            Self::parse_call_argument(state)
         })
         .parse(state)?;
 
-        state.advance();
+        state.advance(); // `)`
 
         Some(Expression::Call {
-            location: state.location_from(left.location().start),
+            location: left.location().merge(state.current_token.location),
             callee: Box::new(left),
             arguments,
         })
     }
 
+    /// Parses a call argument, e.g. `10` or `y: 20`.
+    fn parse_call_argument(state: &mut ParseState<'_, '_>) -> Option<CallArgument> {
+        let label = if state.next_token.raw == RawToken::Identifier
+            && state.peek_after_next().raw == Punctuator::Colon
+        {
+            let label = state.consume_identifier()?;
+            state.advance(); // `:`
+
+            Some(label)
+        } else {
+            None
+        };
+
+        let spread = if label.is_none() && state.next_token.raw == Punctuator::DoubleDot {
+            state.advance();
+
+            Some(state.current_token.location)
+        } else {
+            None
+        };
+
+        Some(CallArgument {
+            label,
+            spread,
+            value: Self::default().parse(state)?,
+        })
+    }
+
     fn parse_field_access_expression(
         self,
         state: &mut ParseState<'_, '_>,
@@ -94,15 +116,122 @@ impl ExpressionParser {
     ) -> Option<Expression> {
         state.advance(); // `.`
 
+        if state.next_token.raw == RawToken::IntegerLiteral {
+            return Self::parse_tuple_index_expression(state, left);
+        }
+
         let right = state.consume_identifier()?;
 
         Some(Expression::FieldAccess {
-            location: state.location_from(left.location().start),
+            location: left.location().merge(right.location),
             left: Box::new(left),
             right,
         })
     }
 
+    fn parse_tuple_index_expression(
+        state: &mut ParseState<'_, '_>,
+        left: Expression,
+    ) -> Option<Expression> {
+        state.advance(); // index
+
+        let Ok(index) = state.resolve_current_token_str().parse::<u32>() else {
+            state
+                .diagnostics
+                .add_diagnostic(IntegerOverflow::new(state.current_token.location, None));
+            return None;
+        };
+
+        Some(Expression::TupleIndex {
+            location: state.location_from(left.location().start),
+            left: Box::new(left),
+            index,
+        })
+    }
+
+    fn parse_index_expression(
+        state: &mut ParseState<'_, '_>,
+        left: Expression,
+    ) -> Option<Expression> {
+        state.advance(); // `[`
+
+        let index = Self::default().parse(state)?;
+
+        state.consume(Punctuator::CloseBracket)?;
+
+        Some(Expression::Index {
+            location: state.location_from(left.location().start),
+            left: Box::new(left),
+            index: Box::new(index),
+        })
+    }
+
+    /// Returns `true` if brackets after an expression contain type arguments,
+    /// e.g. `new[int32]()`, rather than an index, e.g. `xs[0]`.
+    ///
+    /// The brackets are looked ahead without advancing the parse state. They
+    /// contain type arguments if:
+    ///
+    /// * they are empty or contain more than one element, e.g. `new[K, V]`,
+    /// * they are followed by a call or a struct expression, e.g. `Box[T] { ... }`,
+    /// * they are followed by `.` and contain a type, whose name is builtin or
+    ///   capitalized, e.g. `Option[int32].None` (but not `xs[i].len()`).
+    ///
+    /// Otherwise, e.g. in `x[T]` on its own, they contain an index.
+    fn brackets_contain_type_arguments(self, state: &ParseState<'_, '_>) -> bool {
+        let mut lexer = state.lexer.clone();
+        let mut depth = 1_usize;
+
+        let first_token = lexer.next_no_comments();
+        let mut looks_like_type = match first_token.raw {
+            RawToken::Punctuator(Punctuator::CloseBracket) => return true,
+            RawToken::Identifier => {
+                BuiltinSymbolId::from_identifier_or_none(lexer.scanned_identifier).is_some()
+                    || lexer.source[first_token.location.start.0..]
+                        .starts_with(|c: char| c.is_uppercase())
+            }
+            RawToken::Keyword(Keyword::Fun | Keyword::Dyn)
+            | RawToken::Punctuator(Punctuator::OpenParent) => true,
+            _ => false,
+        };
+
+        let mut token = first_token.raw;
+
+        loop {
+            match token {
+                RawToken::Punctuator(
+                    Punctuator::OpenBracket | Punctuator::OpenParent | Punctuator::OpenBrace,
+                ) => depth += 1,
+                RawToken::Punctuator(
+                    Punctuator::CloseBracket | Punctuator::CloseParent | Punctuator::CloseBrace,
+                ) => {
+                    depth -= 1;
+
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                RawToken::Punctuator(Punctuator::Comma) if depth == 1 => return true,
+                RawToken::EndOfFile => return false,
+                RawToken::Identifier
+                | RawToken::Keyword(Keyword::Fun | Keyword::Dyn)
+                | RawToken::Punctuator(
+                    Punctuator::Dot | Punctuator::Comma | Punctuator::Colon | Punctuator::Plus,
+                ) => {}
+                _ => looks_like_type = false,
+            }
+
+            token = lexer.next_no_comments().raw;
+        }
+
+        match lexer.next_no_comments().raw {
+            RawToken::Punctuator(Punctuator::OpenParent) => true,
+            RawToken::Punctuator(Punctuator::OpenBrace) => !self.prohibit_struct_expressions,
+            RawToken::Punctuator(Punctuator::Dot) => looks_like_type,
+            _ => false,
+        }
+    }
+
     fn parse_type_arguments_expression(
         self,
         state: &mut ParseState<'_, '_>,
@@ -156,44 +285,34 @@ impl ExpressionParser {
     ) -> Option<Expression> {
         state.advance(); // `{`
 
-        let fields = ListParser::new(&[RawToken::from(Punctuator::CloseBrace)], |state| {
-            self.parse_struct_field_expression(state)
-        })
-        .parse(state)?;
+        let mut fields = vec![];
+        let mut base = None;
 
-        state.advance(); // `}`
+        while state.next_token.raw != Punctuator::CloseBrace {
+            // `..base` must be the last element.
+            if state.next_token.raw == Punctuator::DoubleDot {
+                state.advance();
 
-        Some(Expression::Struct {
-            location: state.location_from(left.location().start),
-            left: Box::new(left),
-            fields,
-        })
-    }
+                base = Some(Box::new(Self::default().parse(state)?));
+                break;
+            }
 
-    fn parse_binary_expression(
-        self,
-        state: &mut ParseState<'_, '_>,
-        left: Expression,
-    ) -> Option<Expression> {
-        let operator_token = state.next_token;
-        let operator: BinaryOperator = BinaryOperator {
-            location: operator_token.location,
-            raw: RawBinaryOperator::from(operator_token.raw),
-        };
-        let precedence = state.next_token.raw.into();
+            fields.push(self.parse_struct_field_expression(state)?);
 
-        state.advance();
+            if state.next_token.raw != Punctuator::Comma {
+                break;
+            }
 
-        let right = ExpressionParser::new()
-            .with_precedence(precedence)
-            .prohibit_struct_expressions_if(self.prohibit_struct_expressions)
-            .parse(state)?;
+            state.advance(); // `,`
+        }
+
+        state.consume(Punctuator::CloseBrace)?;
 
-        Some(Expression::Binary {
+        Some(Expression::Struct {
             location: state.location_from(left.location().start),
             left: Box::new(left),
-            right: Box::new(right),
-            operator,
+            fields,
+            base,
         })
     }
 
@@ -215,114 +334,259 @@ impl ExpressionParser {
             operator,
         })
     }
-}
-
-impl Parse for ExpressionParser {
-    type Output = Option<Expression>;
-
-    fn parse(self, state: &mut ParseState<'_, '_>) -> Self::Output {
-        let mut left = PrimaryExpressionParser {
-            in_statements_block: self.in_statements_block,
-            prohibit_struct_expressions: self.prohibit_struct_expressions,
-        }
-        .parse(state)?;
-
-        while self.precedence < state.next_token.raw.into() && !left.with_block() {
-            left = match state.next_token.raw {
-                RawToken::Punctuator(Punctuator::OpenParent) => {
-                    self.parse_call_expression(state, left)
-                }
-                RawToken::Punctuator(Punctuator::Dot) => {
-                    self.parse_field_access_expression(state, left)
-                }
-                RawToken::Punctuator(Punctuator::OpenBracket) => {
-                    self.parse_type_arguments_expression(state, left)
-                }
-                RawToken::Keyword(Keyword::As) => self.parse_cast_expression(state, left),
-                RawToken::Punctuator(Punctuator::OpenBrace) => {
-                    if self.prohibit_struct_expressions {
-                        return Some(left);
-                    }
 
-                    self.parse_struct_expression(state, left)
-                }
-                _ => {
-                    if state.next_token.raw.is_binary_operator() {
-                        self.parse_binary_expression(state, left)
-                    } else if state.next_token.raw.is_postfix_operator() {
-                        self.parse_postfix_expression(state, left)
-                    } else {
-                        break;
-                    }
-                }
-            }?;
+    /// Returns the precedence of an operand, that is currently being parsed,
+    /// and whether struct expressions are prohibited in it.
+    const fn operand_context(self, pending: &[PendingExpression]) -> (Precedence, bool) {
+        match pending.last() {
+            None => (self.precedence, self.prohibit_struct_expressions),
+            Some(PendingExpression::Prefix {
+                prohibit_struct_expressions,
+                ..
+            }) => (Precedence::Unastellar, *prohibit_struct_expressions),
+            Some(PendingExpression::Binary {
+                precedence,
+                prohibit_struct_expressions,
+                ..
+            }) => (*precedence, *prohibit_struct_expressions),
+            Some(PendingExpression::Parenthesized { .. }) => (Precedence::Lowest, false),
         }
-
-        Some(left)
     }
-}
 
-struct PrimaryExpressionParser {
-    in_statements_block: bool,
-    prohibit_struct_expressions: bool,
-}
+    /// Returns whether the next token continues an operand, that is parsed
+    /// with a given precedence, e.g. `*` continues `b` in `a + b * c`.
+    ///
+    /// Right associative binary operators also continue operands of the same
+    /// precedence, see [`precedence::precedence()`].
+    fn continues_operand(precedence: Precedence, next: RawToken) -> bool {
+        let next_precedence = Precedence::from(next);
+
+        precedence < next_precedence
+            || (precedence == next_precedence
+                && RawBinaryOperator::from_token(next).is_some_and(|operator| {
+                    precedence::precedence(operator).1 == Associativity::Right
+                }))
+    }
 
-impl PrimaryExpressionParser {
-    fn parse_parenthesized_or_tuple_expression(
+    /// Parses an operand without postfix operators, e.g. `a` in `-(a.b)`.
+    /// Prefix operators and opening parentheses before the operand are pushed
+    /// onto `pending`.
+    fn parse_operand(
         self,
         state: &mut ParseState<'_, '_>,
+        pending: &mut Vec<PendingExpression>,
     ) -> Option<Expression> {
-        let start = state.next_token.location.start;
-        state.advance();
-
-        let elements = ListParser::new(&[RawToken::from(Punctuator::CloseParent)], |state| {
-            ExpressionParser::default().parse(state)
-        })
-        .parse(state)?;
+        loop {
+            let (_, prohibit_struct_expressions) = self.operand_context(pending);
 
-        state.advance(); // `)`
+            if state.next_token.raw == Punctuator::OpenParent {
+                let start = state.next_token.location.start;
+                state.advance(); // `(`
 
-        let location = state.location_from(start);
+                if state.next_token.raw == Punctuator::CloseParent {
+                    state.advance(); // `)`
 
-        let mut elements = elements.into_iter();
-
-        match (elements.next(), elements.next()) {
-            (Some(element), None) => {
-                if state
-                    .resolve_location(state.location_from(element.location().end))
-                    .contains(',')
-                {
-                    Some(Expression::Tuple {
-                        location,
-                        elements: vec![element],
-                    })
-                } else {
-                    Some(Expression::Parenthesized {
-                        location,
-                        inner: Box::from(element),
-                    })
+                    return Some(Expression::Tuple {
+                        location: state.location_from(start),
+                        elements: vec![],
+                    });
                 }
+
+                pending.push(PendingExpression::Parenthesized {
+                    start,
+                    elements: vec![],
+                });
+            } else if let Some(raw) = RawPrefixOperator::from_token(state.next_token.raw) {
+                let operator = PrefixOperator {
+                    location: state.next_token.location,
+                    raw,
+                };
+                state.advance();
+
+                pending.push(PendingExpression::Prefix {
+                    operator,
+                    prohibit_struct_expressions,
+                });
+            } else {
+                return PrimaryExpressionParser.parse(state);
             }
-            (None, None) => Some(Expression::Tuple {
-                location,
-                elements: vec![],
-            }),
-            (Some(previous), Some(next)) => {
-                let mut new_elements = vec![];
-                new_elements.push(previous);
-                new_elements.push(next);
-
-                new_elements.append(&mut elements.collect::<Vec<_>>());
-
-                Some(Expression::Tuple {
+        }
+    }
+
+    /// Builds a parenthesized expression, e.g. `(a)`, or a tuple expression,
+    /// e.g. `(a,)`, `(a, b)`, after its closing parenthesis is consumed.
+    fn finish_parenthesized_or_tuple_expression(
+        state: &ParseState<'_, '_>,
+        start: ByteOffset,
+        mut elements: Vec<Expression>,
+    ) -> Expression {
+        let location = state.location_from(start);
+
+        if let [element] = elements.as_slice() {
+            if !state
+                .resolve_location(state.location_from(element.location().end))
+                .contains(',')
+            {
+                return Expression::Parenthesized {
                     location,
-                    elements: new_elements,
-                })
+                    inner: Box::new(elements.remove(0)),
+                };
             }
-            _ => unreachable!(),
         }
+
+        Expression::Tuple { location, elements }
     }
+}
+
+/// An expression, whose operand is being parsed, see [`ExpressionParser::parse()`].
+enum PendingExpression {
+    /// A prefix operator, e.g. `-` in `-a`.
+    Prefix {
+        operator: PrefixOperator,
+        prohibit_struct_expressions: bool,
+    },
+
+    /// A binary operator with its left operand, e.g. `a +` in `a + b`.
+    Binary {
+        left: Expression,
+        operator: BinaryOperator,
+        precedence: Precedence,
+        prohibit_struct_expressions: bool,
+    },
+
+    /// Elements of a parenthesized or tuple expression, that are already
+    /// parsed, e.g. `(a, ` in `(a, b)`.
+    Parenthesized {
+        start: ByteOffset,
+        elements: Vec<Expression>,
+    },
+}
+
+impl Parse for ExpressionParser {
+    type Output = Option<Expression>;
 
+    /// Parses an expression.
+    ///
+    /// Operators and parentheses, whose operands are being parsed, are kept
+    /// in an explicit stack instead of the call stack, so that long chains
+    /// like `1 + 1 + ... + 1` and deeply nested prefix operators or
+    /// parentheses don't overflow it.
+    fn parse(self, state: &mut ParseState<'_, '_>) -> Self::Output {
+        let mut pending = vec![];
+
+        'operand: loop {
+            let mut left = self.parse_operand(state, &mut pending)?;
+
+            loop {
+                let (precedence, prohibit_struct_expressions) = self.operand_context(&pending);
+                let parser = self.prohibit_struct_expressions_if(prohibit_struct_expressions);
+
+                if Self::continues_operand(precedence, state.next_token.raw) && !left.with_block() {
+                    match state.next_token.raw {
+                        RawToken::Punctuator(Punctuator::OpenParent) => {
+                            left = parser.parse_call_expression(state, left)?;
+                            continue;
+                        }
+                        RawToken::Punctuator(Punctuator::Dot) => {
+                            left = parser.parse_field_access_expression(state, left)?;
+                            continue;
+                        }
+                        RawToken::Punctuator(Punctuator::OpenBracket) => {
+                            left = if parser.brackets_contain_type_arguments(state) {
+                                parser.parse_type_arguments_expression(state, left)?
+                            } else {
+                                Self::parse_index_expression(state, left)?
+                            };
+                            continue;
+                        }
+                        RawToken::Keyword(Keyword::As) => {
+                            left = parser.parse_cast_expression(state, left)?;
+                            continue;
+                        }
+                        RawToken::Punctuator(Punctuator::OpenBrace)
+                            if !prohibit_struct_expressions =>
+                        {
+                            left = parser.parse_struct_expression(state, left)?;
+                            continue;
+                        }
+                        raw if raw.is_binary_operator() => {
+                            let operator = BinaryOperator {
+                                location: state.next_token.location,
+                                raw: RawBinaryOperator::from(raw),
+                            };
+                            state.advance();
+
+                            pending.push(PendingExpression::Binary {
+                                left,
+                                operator,
+                                precedence: precedence::precedence(operator.raw).0,
+                                prohibit_struct_expressions,
+                            });
+
+                            continue 'operand;
+                        }
+                        raw if raw.is_postfix_operator() => {
+                            left = parser.parse_postfix_expression(state, left)?;
+                            continue;
+                        }
+                        _ => {}
+                    }
+                }
+
+                // The operand is parsed, so the innermost pending expression
+                // can be completed.
+                left = match pending.pop() {
+                    None => return Some(left),
+                    Some(PendingExpression::Prefix { operator, .. }) => Expression::Prefix {
+                        location: state.make_location(operator.location.start, left.location().end),
+                        inner: Box::new(left),
+                        operator,
+                    },
+                    Some(PendingExpression::Binary {
+                        left: binary_left,
+                        operator,
+                        ..
+                    }) => Expression::Binary {
+                        location: binary_left.location().merge(left.location()),
+                        left: Box::new(binary_left),
+                        right: Box::new(left),
+                        operator,
+                    },
+                    Some(PendingExpression::Parenthesized {
+                        start,
+                        mut elements,
+                    }) => {
+                        elements.push(left);
+
+                        if state.next_token.raw == Punctuator::Comma {
+                            state.advance(); // `,`
+
+                            if state.next_token.raw != Punctuator::CloseParent {
+                                pending.push(PendingExpression::Parenthesized { start, elements });
+                                continue 'operand;
+                            }
+                        } else if state.next_token.raw != Punctuator::CloseParent {
+                            state.add_unexpected_token_diagnostic(one_of([
+                                RawToken::from(Punctuator::CloseParent).to_string(),
+                                "`,`".to_owned(),
+                            ]));
+
+                            return None;
+                        }
+
+                        state.advance(); // `)`
+
+                        Self::finish_parenthesized_or_tuple_expression(state, start, elements)
+                    }
+                };
+            }
+        }
+    }
+}
+
+struct PrimaryExpressionParser;
+
+impl PrimaryExpressionParser {
     fn parse_list_expression(&self, state: &mut ParseState<'_, '_>) -> Option<Expression> {
         let start = state.next_token.location.start;
 
@@ -442,11 +706,19 @@ impl PrimaryExpressionParser {
     ) -> Option<MatchExpressionItem> {
         let left = PatternParser.parse(state)?;
 
+        let guard = if state.next_token.raw == Keyword::If {
+            state.advance(); // `if`
+
+            Some(ExpressionParser::new().parse(state)?)
+        } else {
+            None
+        };
+
         state.consume(Punctuator::Arrow)?;
 
         let right = ExpressionParser::new().parse(state)?;
 
-        Some(MatchExpressionItem { left, right })
+        Some(MatchExpressionItem { left, guard, right })
     }
 
     fn parse_match_expression_block(
@@ -482,7 +754,10 @@ impl PrimaryExpressionParser {
         })
     }
 
-    fn parse_while_expression(&self, state: &mut ParseState<'_, '_>) -> Option<Expression> {
+    fn parse_while_expression(
+        state: &mut ParseState<'_, '_>,
+        label: Option<IdentifierAST>,
+    ) -> Option<Expression> {
         let start = state.next_token.location.start;
         state.advance(); // `while`
 
@@ -494,40 +769,47 @@ impl PrimaryExpressionParser {
 
         Some(Expression::While {
             location: state.location_from(start),
+            label,
             condition: Box::new(condition),
             statements_block: body,
         })
     }
 
-    fn parse_loop_expression(&self, state: &mut ParseState<'_, '_>) -> Option<Expression> {
-        state.advance(); // `loop`
+    /// Parses a labeled loop, e.g. `@outer: loop { ... }`. Location of the
+    /// loop doesn't include its label.
+    fn parse_labeled_loop_expression(state: &mut ParseState<'_, '_>) -> Option<Expression> {
+        state.advance(); // `@`
 
-        let location = state.current_token.location;
-        let statements_block = StatementsBlockParser.parse(state)?;
+        let label = state.consume_identifier()?;
+        state.consume(Punctuator::Colon)?;
 
-        Some(Expression::Loop {
-            location,
-            statements_block,
-        })
+        match state.next_token.raw {
+            RawToken::Keyword(Keyword::Loop) => Self::parse_loop_expression(state, Some(label)),
+            RawToken::Keyword(Keyword::While) => Self::parse_while_expression(state, Some(label)),
+            _ => {
+                state.add_unexpected_token_diagnostic(one_of([
+                    Keyword::Loop.to_string(),
+                    Keyword::While.to_string(),
+                ]));
+
+                None
+            }
+        }
     }
 
-    fn parse_prefix_expression(&self, state: &mut ParseState<'_, '_>) -> Option<Expression> {
-        let operator_token = state.next_token;
-        let operator: PrefixOperator = PrefixOperator {
-            location: operator_token.location,
-            raw: RawPrefixOperator::from(operator_token.raw),
-        };
-        state.advance();
+    fn parse_loop_expression(
+        state: &mut ParseState<'_, '_>,
+        label: Option<IdentifierAST>,
+    ) -> Option<Expression> {
+        state.advance(); // `loop`
 
-        let inner = ExpressionParser::new()
-            .with_precedence(Precedence::Unastellar)
-            .prohibit_struct_expressions_if(self.prohibit_struct_expressions)
-            .parse(state)?;
+        let start = state.current_token.location.start;
+        let statements_block = StatementsBlockParser.parse(state)?;
 
-        Some(Expression::Prefix {
-            location: state.make_location(operator_token.location.start, inner.location().end),
-            inner: Box::new(inner),
-            operator,
+        Some(Expression::Loop {
+            location: state.location_from(start),
+            label,
+            statements_block,
         })
     }
 }
@@ -552,9 +834,6 @@ impl Parse for PrimaryExpressionParser {
                     id: symbol,
                 }))
             }
-            RawToken::Punctuator(Punctuator::OpenParent) => {
-                self.parse_parenthesized_or_tuple_expression(state)
-            }
             RawToken::Punctuator(Punctuator::OpenBracket) => self.parse_list_expression(state),
             RawToken::Punctuator(Punctuator::OpenBrace) => self.parse_block_expression(state),
             RawToken::Punctuator(Punctuator::Or) | RawToken::Punctuator(Punctuator::DoubleOr) => {
@@ -562,8 +841,9 @@ impl Parse for PrimaryExpressionParser {
             }
             RawToken::Keyword(Keyword::If) => self.parse_if_expression(state),
             RawToken::Keyword(Keyword::Match) => self.parse_match_expression(state),
-            RawToken::Keyword(Keyword::While) => self.parse_while_expression(state),
-            RawToken::Keyword(Keyword::Loop) => self.parse_loop_expression(state),
+            RawToken::Keyword(Keyword::While) => Self::parse_while_expression(state, None),
+            RawToken::Keyword(Keyword::Loop) => Self::parse_loop_expression(state, None),
+            RawToken::Punctuator(Punctuator::At) => Self::parse_labeled_loop_expression(state),
             RawToken::Punctuator(Punctuator::Underscore) => {
                 state.advance();
 
@@ -572,19 +852,7 @@ impl Parse for PrimaryExpressionParser {
                 })
             }
             _ => {
-                if state.next_token.raw.is_prefix_operator() {
-                    return self.parse_prefix_expression(state);
-                }
-
-                if self.in_statements_block {
-                    state.add_unexpected_token_diagnostic(one_of([
-                        "statement".to_owned(),
-                        Punctuator::Semicolon.to_string(),
-                        Punctuator::CloseBrace.to_string(),
-                    ]));
-                } else {
-                    state.add_unexpected_token_diagnostic("expression");
-                }
+                state.add_unexpected_token_diagnostic(expected!(an expression));
 
                 None
             }
@@ -1,16 +1,20 @@
 use stellar_ast::{
     token::{Keyword, Punctuator, RawToken},
-    Enum, EnumItem, Function, FunctionParameter, FunctionSignature, IdentifierAST, Interface,
-    ModuleItem, NotSelfFunctionParameter, SelfFunctionParameter, Struct, StructField, TupleField,
-    TupleLikeStruct, TypeAlias, Visibility,
+    Attribute, Enum, EnumItem, Function, FunctionParameter, FunctionSignature, IdentifierAST,
+    InlineModule, Interface, ModuleItem, NotSelfFunctionParameter, SelfFunctionParameter, Struct,
+    StructField, TupleField, TupleLikeStruct, TypeAlias, Visibility,
 };
 use stellar_english_commons::enumeration::one_of;
 use stellar_interner::builtin_identifiers;
 
 use crate::{
+    attribute::AttributesParser,
     diagnostics::{
+        DiscriminantOnEnumItemWithPayload, EOFInsteadOfCloseBrace,
         UnnecessaryVisibilityQualifierContext, UnnecessaryVisibilityQualifierDiagnostic,
     },
+    expected::{expected, MODULE_ITEM_KEYWORDS},
+    expression::ExpressionParser,
     list::ListParser,
     path::ImportPathParser,
     pattern::PatternParser,
@@ -24,6 +28,7 @@ use crate::{
 
 struct ImportParser {
     visibility: Visibility,
+    attributes: Vec<Attribute>,
 }
 
 impl Parse for ImportParser {
@@ -50,6 +55,7 @@ impl Parse for ImportParser {
         Some(ModuleItem::Import {
             path,
             location: state.location_from(start),
+            attributes: self.attributes,
         })
     }
 }
@@ -104,6 +110,7 @@ impl Parse for StructFieldsParser {
 struct StructParser {
     pub(crate) visibility: Visibility,
     pub(crate) docstring: Option<String>,
+    pub(crate) attributes: Vec<Attribute>,
 }
 
 impl Parse for StructParser {
@@ -176,6 +183,7 @@ impl Parse for StructParser {
                         FunctionParser {
                             visibility: VisibilityParser.parse(state),
                             docstring: state.consume_local_docstring(),
+                            attributes: vec![],
                         }
                         .parse(state)?,
                     );
@@ -195,28 +203,45 @@ impl Parse for StructParser {
                 methods,
                 implements,
                 docstring: self.docstring,
+                attributes: self.attributes,
             }))
         } else if state.next_token.raw == Punctuator::OpenBrace {
             state.advance();
 
-            let fields = ListParser::new(
-                &[
-                    RawToken::from(Punctuator::CloseBrace),
-                    RawToken::from(Keyword::Fun),
-                    RawToken::from(Keyword::Pub),
-                ],
-                |state| {
-                    let docstring = state.consume_local_docstring();
-                    let visibility = VisibilityParser.parse(state);
+            let mut fields = vec![];
+
+            // `pub` starts either a public field or a public method.
+            while state.next_token.raw != Punctuator::CloseBrace
+                && state.next_token.raw != Keyword::Fun
+                && !(state.next_token.raw == Keyword::Pub
+                    && state.peek_after_next().raw == Keyword::Fun)
+            {
+                let docstring = state.consume_local_docstring();
+                let visibility = VisibilityParser.parse(state);
 
+                fields.push(
                     StructFieldParser {
                         visibility,
                         docstring,
                     }
-                    .parse(state)
-                },
-            )
-            .parse(state)?;
+                    .parse(state)?,
+                );
+
+                if state.next_token.raw != Punctuator::Comma {
+                    break;
+                }
+
+                state.advance();
+            }
+
+            if state.next_token.raw != Punctuator::CloseBrace
+                && state.next_token.raw != Keyword::Fun
+                && state.next_token.raw != Keyword::Pub
+            {
+                state.add_unexpected_token_diagnostic("`}`, `fun`, `pub`, or `,`");
+
+                return None;
+            }
 
             let mut methods = vec![];
 
@@ -233,6 +258,7 @@ impl Parse for StructParser {
                         FunctionParser {
                             visibility,
                             docstring,
+                            attributes: vec![],
                         }
                         .parse(state)?,
                     );
@@ -250,6 +276,7 @@ impl Parse for StructParser {
                 methods,
                 implements,
                 docstring: self.docstring,
+                attributes: self.attributes,
             }))
         } else {
             state.add_unexpected_token_diagnostic(one_of([
@@ -275,13 +302,26 @@ impl Parse for NotSelfFunctionParameterParser {
 
         let ty = TypeParser.parse(state)?;
 
-        Some(NotSelfFunctionParameter { pattern, ty })
+        let default_value = if state.next_token.raw == Punctuator::Eq {
+            state.advance();
+
+            Some(ExpressionParser::default().parse(state)?)
+        } else {
+            None
+        };
+
+        Some(NotSelfFunctionParameter {
+            pattern,
+            ty,
+            default_value,
+        })
     }
 }
 
 struct FunctionParser {
     visibility: Visibility,
     docstring: Option<String>,
+    attributes: Vec<Attribute>,
 }
 
 impl Parse for FunctionParser {
@@ -339,6 +379,7 @@ impl Parse for FunctionParser {
                 return_type,
                 where_predicates,
                 docstring: self.docstring,
+                attributes: self.attributes,
             },
             body: match state.next_token.raw {
                 RawToken::Punctuator(Punctuator::Semicolon) => {
@@ -365,6 +406,7 @@ impl Parse for FunctionParser {
 struct TypeAliasParser {
     visibility: Visibility,
     docstring: Option<String>,
+    attributes: Vec<Attribute>,
 }
 
 impl Parse for TypeAliasParser {
@@ -388,6 +430,7 @@ impl Parse for TypeAliasParser {
             generic_parameters,
             value,
             docstring: self.docstring,
+            attributes: self.attributes,
         }))
     }
 }
@@ -395,6 +438,7 @@ impl Parse for TypeAliasParser {
 struct InterfaceParser {
     visibility: Visibility,
     docstring: Option<String>,
+    attributes: Vec<Attribute>,
 }
 
 impl Parse for InterfaceParser {
@@ -428,6 +472,7 @@ impl Parse for InterfaceParser {
 
             let method = FunctionParser {
                 docstring: state.consume_local_docstring(),
+                attributes: vec![],
                 visibility: VisibilityParser.parse(state),
             }
             .parse(state)?;
@@ -456,6 +501,7 @@ impl Parse for InterfaceParser {
             methods,
             inherits,
             docstring: self.docstring,
+            attributes: self.attributes,
         }))
     }
 }
@@ -463,6 +509,7 @@ impl Parse for InterfaceParser {
 struct EnumParser {
     visibility: Visibility,
     docstring: Option<String>,
+    attributes: Vec<Attribute>,
 }
 
 macro_rules! possibly_recover {
@@ -477,8 +524,10 @@ macro_rules! possibly_recover {
                         | Keyword::Import
                         | Keyword::Struct
                         | Keyword::Type
-                        | Keyword::Interface,
+                        | Keyword::Interface
+                        | Keyword::Module,
                     )
+                    | RawToken::Punctuator(Punctuator::HashTag)
                     | RawToken::EndOfFile => break,
                     _ => $state.advance(),
                 }
@@ -544,6 +593,7 @@ impl Parse for EnumParser {
                 FunctionParser {
                     visibility,
                     docstring,
+                    attributes: vec![],
                 }
                 .parse(state)
             ));
@@ -560,6 +610,7 @@ impl Parse for EnumParser {
             methods,
             implements,
             docstring: self.docstring,
+            attributes: self.attributes,
         }))
     }
 }
@@ -574,17 +625,49 @@ impl Parse for EnumItemParser {
 
         let name = state.consume_identifier()?;
 
-        match state.next_token.raw {
+        let item = match state.next_token.raw {
             RawToken::Punctuator(Punctuator::OpenBrace) => {
-                EnumItemStructParser { name, docstring }.parse(state)
+                EnumItemStructParser { name, docstring }.parse(state)?
             }
-            RawToken::Punctuator(Punctuator::OpenParent) => Some(EnumItem::TupleLike {
+            RawToken::Punctuator(Punctuator::OpenParent) => EnumItem::TupleLike {
                 name,
                 fields: TupleFieldsParser.parse(state)?,
                 docstring,
-            }),
-            _ => Some(EnumItem::Just { name, docstring }),
+            },
+            _ => {
+                let discriminant = if state.next_token.raw == Punctuator::Eq {
+                    state.advance();
+
+                    Some(ExpressionParser::default().parse(state)?)
+                } else {
+                    None
+                };
+
+                return Some(EnumItem::Just {
+                    name,
+                    discriminant,
+                    docstring,
+                });
+            }
+        };
+
+        // Discriminants of items with payload are parsed to recover.
+        if state.next_token.raw == Punctuator::Eq {
+            let start = state.next_token.location.start;
+
+            state.advance();
+            ExpressionParser::default().parse(state)?;
+
+            state
+                .diagnostics
+                .add_diagnostic(DiscriminantOnEnumItemWithPayload::new(
+                    state.location_from(start),
+                    name,
+                    matches!(item, EnumItem::TupleLike { .. }),
+                ));
         }
+
+        Some(item)
     }
 }
 
@@ -629,6 +712,54 @@ impl Parse for TupleFieldsParser {
     }
 }
 
+struct InlineModuleParser {
+    pub(crate) visibility: Visibility,
+    pub(crate) docstring: Option<String>,
+    pub(crate) attributes: Vec<Attribute>,
+}
+
+impl Parse for InlineModuleParser {
+    type Output = Option<ModuleItem>;
+
+    fn parse(self, state: &mut ParseState<'_, '_>) -> Self::Output {
+        state.advance(); // `module`
+
+        let name = state.consume_identifier()?;
+
+        state.consume(Punctuator::OpenBrace)?;
+        let open_brace_location = state.current_token.location;
+
+        let mut items = vec![];
+
+        while state.next_token.raw != Punctuator::CloseBrace {
+            if state.next_token.raw == RawToken::EndOfFile {
+                state
+                    .diagnostics
+                    .add_diagnostic(EOFInsteadOfCloseBrace::new(
+                        state.next_token.location,
+                        open_brace_location,
+                    ));
+
+                return None;
+            }
+
+            if let Some(item) = ItemParser.parse(state) {
+                items.push(item);
+            }
+        }
+
+        state.advance(); // `}`
+
+        Some(ModuleItem::Module(InlineModule {
+            visibility: self.visibility,
+            name,
+            items,
+            docstring: self.docstring,
+            attributes: self.attributes,
+        }))
+    }
+}
+
 pub(crate) struct ItemsParser;
 
 impl Parse for ItemsParser {
@@ -656,8 +787,10 @@ impl ItemParser {
                     | Keyword::Import
                     | Keyword::Struct
                     | Keyword::Type
-                    | Keyword::Interface,
+                    | Keyword::Interface
+                    | Keyword::Module,
                 )
+                | RawToken::Punctuator(Punctuator::HashTag)
                 | RawToken::EndOfFile => break,
                 _ => state.advance(),
             }
@@ -672,6 +805,11 @@ impl Parse for ItemParser {
 
     fn parse(self, state: &mut ParseState<'_, '_>) -> Self::Output {
         let docstring = state.consume_local_docstring();
+        let Some(attributes) = AttributesParser.parse(state) else {
+            Self::goto_next_valid_item(state);
+
+            return None;
+        };
         let visibility = VisibilityParser.parse(state);
 
         Some(match state.next_token.raw {
@@ -680,20 +818,29 @@ impl Parse for ItemParser {
                     state,
                     EnumParser {
                         visibility,
-                        docstring
+                        docstring,
+                        attributes
                     }
                     .parse(state)
                 )
             }
             RawToken::Keyword(Keyword::Import) => {
-                possibly_recover!(state, ImportParser { visibility }.parse(state))
+                possibly_recover!(
+                    state,
+                    ImportParser {
+                        visibility,
+                        attributes
+                    }
+                    .parse(state)
+                )
             }
             RawToken::Keyword(Keyword::Struct) => {
                 possibly_recover!(
                     state,
                     StructParser {
                         visibility,
-                        docstring
+                        docstring,
+                        attributes
                     }
                     .parse(state)
                 )
@@ -703,7 +850,8 @@ impl Parse for ItemParser {
                     state,
                     InterfaceParser {
                         visibility,
-                        docstring
+                        docstring,
+                        attributes
                     }
                     .parse(state)
                 )
@@ -712,20 +860,34 @@ impl Parse for ItemParser {
                 state,
                 FunctionParser {
                     visibility,
-                    docstring
+                    docstring,
+                    attributes
                 }
                 .parse(state)
             )),
+            RawToken::Keyword(Keyword::Module) => possibly_recover!(
+                state,
+                InlineModuleParser {
+                    visibility,
+                    docstring,
+                    attributes
+                }
+                .parse(state)
+            ),
             RawToken::Keyword(Keyword::Type) => possibly_recover!(
                 state,
                 TypeAliasParser {
                     visibility,
-                    docstring
+                    docstring,
+                    attributes
                 }
                 .parse(state)
             ),
             _ => {
-                state.add_unexpected_token_diagnostic("module item");
+                state.add_unexpected_token_diagnostic(expected!(
+                    MODULE_ITEM_KEYWORDS,
+                    or another module item
+                ));
 
                 Self::goto_next_valid_item(state);
 
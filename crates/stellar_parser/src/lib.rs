@@ -64,7 +64,9 @@
     clippy::unnested_or_patterns
 )]
 
+mod attribute;
 pub mod diagnostics;
+mod expected;
 mod expression;
 mod items;
 mod list;
@@ -72,11 +74,12 @@ mod literal;
 mod path;
 mod pattern;
 mod statement;
+mod tokens;
 mod r#type;
 
 #[cfg(feature = "debug")]
 use std::time::Instant;
-use std::{fs, io};
+use std::{error::Error, fmt, io};
 
 use diagnostics::LexErrorDiagnostic;
 pub use expression::ExpressionParser;
@@ -85,19 +88,20 @@ use pattern::PatternParser;
 use r#type::TypeParser;
 use statement::StatementParser;
 use stellar_ast::{
-    token::{Keyword, LexError, RawToken, Token},
+    token::{Keyword, LexError, RawLexError, RawToken, Token},
     Expression, IdentifierAST, Module, ModuleItem, Pattern, Statement, Type, Visibility,
 };
-use stellar_database::{ModuleData, ModuleId, PackageId, Path, State};
+use stellar_database::{Config, ModuleData, ModuleId, PackageId, Path, State};
 use stellar_diagnostics::Diagnostics;
 use stellar_filesystem::location::{ByteOffset, Location};
 use stellar_interner::PathId;
 use stellar_lexer::Lexer;
 use stellar_stable_likely::unlikely;
+pub use tokens::{tokenize, TokenWithTrivia, Trivia, TriviaKind};
 #[cfg(feature = "debug")]
 use tracing::trace;
 
-use crate::diagnostics::UnexpectedToken;
+use crate::diagnostics::{IdentifierTooLong, UnexpectedToken};
 
 /// Represents a parse state.
 #[derive(Debug)]
@@ -110,8 +114,20 @@ pub struct ParseState<'s, 'd> {
     /// Next token.
     next_token: Token,
 
+    /// End of the last consumed token, used to point at the place, where
+    /// an expected token is missing. `None` if no tokens were consumed yet.
+    previous_token_end: Option<ByteOffset>,
+
     /// Diagnostics that is emitted during parsing.
     diagnostics: &'d mut Diagnostics,
+
+    /// Nesting depth of the type, that is currently being parsed.
+    type_nesting: usize,
+
+    /// Maximum nesting depth of types, see [`Config::max_type_nesting()`].
+    ///
+    /// [`Config::max_type_nesting()`]: stellar_database::Config::max_type_nesting
+    max_type_nesting: usize,
 }
 
 /// Represents AST node that can be parsed.
@@ -187,10 +203,145 @@ impl ParseResult {
     }
 }
 
+/// Returns the name of a module with a given path.
+///
+/// Module names are not written in the source text, so the name refers
+/// to the beginning of the module's file.
+fn module_name(path: &Path, filepath: PathId) -> IdentifierAST {
+    IdentifierAST {
+        location: Location {
+            filepath,
+            start: ByteOffset(0),
+            end: ByteOffset(0),
+        },
+        id: path.last(),
+    }
+}
+
+/// An error, that occurs when a source file exceeds a limit set in
+/// [`Config`], so that e.g. a huge generated file is rejected before it is
+/// parsed.
+///
+/// It is returned by [`read_and_parse_module()`] wrapped into an
+/// [`io::Error`] of kind [`io::ErrorKind::InvalidData`], see
+/// [`FileLimitExceeded::from_io_error()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileLimitExceeded {
+    /// The file is larger, than [`Config::max_file_size()`].
+    FileSize {
+        /// The path of the file.
+        filepath: PathId,
+
+        /// The size of the file in bytes.
+        size: usize,
+
+        /// The maximum size of a file in bytes.
+        max_file_size: usize,
+    },
+
+    /// The file has more tokens, than [`Config::max_token_count()`].
+    TokenCount {
+        /// The path of the file.
+        filepath: PathId,
+
+        /// The maximum number of tokens in a file.
+        max_token_count: usize,
+    },
+}
+
+impl FileLimitExceeded {
+    /// Returns the limit error, that caused a given IO error, if any.
+    #[inline]
+    #[must_use]
+    pub fn from_io_error(error: &io::Error) -> Option<&Self> {
+        error.get_ref()?.downcast_ref()
+    }
+
+    /// Checks, that a file of a given size in bytes doesn't exceed
+    /// [`Config::max_file_size()`]. The size is checked before the file is
+    /// read, so that a huge file is never loaded into memory.
+    ///
+    /// # Errors
+    /// Returns an error if the file is too large.
+    pub const fn check_file_size(
+        config: &Config,
+        filepath: PathId,
+        size: usize,
+    ) -> Result<(), Self> {
+        let max_file_size = config.max_file_size();
+
+        if size > max_file_size {
+            return Err(Self::FileSize {
+                filepath,
+                size,
+                max_file_size,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Checks, that a source file doesn't have more tokens, than
+    /// [`Config::max_token_count()`].
+    ///
+    /// # Errors
+    /// Returns an error if the file has too many tokens.
+    pub fn check(config: &Config, filepath: PathId, source: &str) -> Result<(), Self> {
+        // Every token takes at least one byte, so small files are not
+        // tokenized twice.
+        let max_token_count = config.max_token_count();
+
+        if source.len() <= max_token_count {
+            return Ok(());
+        }
+
+        let mut lexer = Lexer::new(filepath, source);
+        let mut token_count = 0;
+
+        while lexer.next_no_comments().raw != RawToken::EndOfFile {
+            token_count += 1;
+
+            if token_count > max_token_count {
+                return Err(Self::TokenCount {
+                    filepath,
+                    max_token_count,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for FileLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FileSize {
+                size,
+                max_file_size,
+                ..
+            } => write!(
+                f,
+                "file has {size} bytes, but the limit is {max_file_size} bytes"
+            ),
+            Self::TokenCount {
+                max_token_count, ..
+            } => write!(f, "file has more than {max_token_count} tokens"),
+        }
+    }
+}
+
+impl Error for FileLimitExceeded {}
+
 /// Read and parse a Stellar module.
 ///
+/// The source is taken from the source map of the state (see
+/// [`State::source_map()`]), so the file is read only once, even if it is
+/// needed again later, e.g. to emit diagnostics.
+///
 /// # Errors
-/// Returns an error if the file contents cannot be read.
+/// Returns an error if the file contents cannot be read or if the file
+/// exceeds limits of the config, see [`FileLimitExceeded`].
 ///
 /// # Panics
 /// Panics if the file path cannot be resolved in the path storage.
@@ -201,10 +352,35 @@ pub fn read_and_parse_module(
     path: Path,
     filepath: PathId,
 ) -> Result<ParseResult, io::Error> {
-    let module = ModuleData::alloc(state.db_mut(), package, path, filepath);
-    let source = fs::read_to_string(filepath.as_path())?;
+    let size = state.source_map().file_size(filepath)?;
 
-    let mut parse_state = ParseState::new(filepath, &source, state.diagnostics_mut());
+    if let Err(error) = FileLimitExceeded::check_file_size(state.config(), filepath, size) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+    }
+
+    let source = state.source_map_mut().get_or_load(filepath)?;
+
+    if let Err(error) = FileLimitExceeded::check(state.config(), filepath, &source) {
+        // Diagnostics about the file don't point into it, so there is no need
+        // to keep its content.
+        state.source_map_mut().forget(filepath);
+
+        return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+    }
+
+    let module = ModuleData::alloc(
+        state.db_mut(),
+        package,
+        module_name(&path, filepath),
+        path,
+        filepath,
+    );
+
+    let max_type_nesting = state.config().max_type_nesting();
+    let lexer = Lexer::new(filepath, &source)
+        .with_max_identifier_length(state.config().max_identifier_length());
+    let mut parse_state = ParseState::with_lexer(lexer, state.diagnostics_mut())
+        .with_max_type_nesting(max_type_nesting);
 
     Ok(ParseResult::new(
         module,
@@ -226,8 +402,18 @@ pub fn parse_module(
     filepath: PathId,
     source: &str,
 ) -> ParseResult {
-    let module = ModuleData::alloc(state.db_mut(), package, path, filepath);
-    let mut parse_state = ParseState::new(filepath, source, state.diagnostics_mut());
+    let module = ModuleData::alloc(
+        state.db_mut(),
+        package,
+        module_name(&path, filepath),
+        path,
+        filepath,
+    );
+    let max_type_nesting = state.config().max_type_nesting();
+    let lexer = Lexer::new(filepath, source)
+        .with_max_identifier_length(state.config().max_identifier_length());
+    let mut parse_state = ParseState::with_lexer(lexer, state.diagnostics_mut())
+        .with_max_type_nesting(max_type_nesting);
 
     ParseResult {
         module,
@@ -249,7 +435,13 @@ pub fn parse_module_using(
     mut parse_state: ParseState<'_, '_>,
 ) -> ParseResult {
     ParseResult::new(
-        ModuleData::alloc(state.db_mut(), package, path, parse_state.lexer.filepath),
+        ModuleData::alloc(
+            state.db_mut(),
+            package,
+            module_name(&path, parse_state.lexer.filepath),
+            path,
+            parse_state.lexer.filepath,
+        ),
         Module {
             filepath: parse_state.lexer.filepath,
             docstring: parse_state.consume_module_docstring(),
@@ -276,6 +468,18 @@ pub fn parse_item_using(state: &mut ParseState<'_, '_>) -> Option<ModuleItem> {
     ItemParser.parse(state)
 }
 
+/// Parse items of a module without allocating the module in the database,
+/// e.g. to analyze an edited copy of a file, that was already parsed.
+#[inline]
+#[must_use]
+pub fn parse_items(
+    filepath: PathId,
+    source: impl AsRef<str>,
+    diagnostics: &mut Diagnostics,
+) -> Vec<ModuleItem> {
+    ItemsParser.parse(&mut ParseState::new(filepath, source.as_ref(), diagnostics))
+}
+
 /// Parse an expression.
 #[inline]
 #[must_use]
@@ -413,8 +617,13 @@ impl<'s, 'd> ParseState<'s, 'd> {
     /// Creates an initial parse state from file source.
     #[must_use]
     pub fn new(filepath: PathId, source: &'s str, diagnostics: &'d mut Diagnostics) -> Self {
-        let mut lexer = Lexer::new(filepath, source);
+        Self::with_lexer(Lexer::new(filepath, source), diagnostics)
+    }
 
+    /// Creates an initial parse state, that uses a given lexer, e.g. one
+    /// with a limited length of identifiers.
+    #[must_use]
+    pub fn with_lexer(mut lexer: Lexer<'s>, diagnostics: &'d mut Diagnostics) -> Self {
         let current_token = lexer.next_no_comments();
         let next_token = current_token;
 
@@ -422,17 +631,33 @@ impl<'s, 'd> ParseState<'s, 'd> {
             lexer,
             current_token,
             next_token,
+            previous_token_end: None,
             diagnostics,
+            type_nesting: 0,
+            max_type_nesting: Config::DEFAULT_MAX_TYPE_NESTING,
         };
         state.check_next_token();
 
         state
     }
 
+    /// Sets the maximum nesting depth of types.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_type_nesting(mut self, max_type_nesting: usize) -> Self {
+        self.max_type_nesting = max_type_nesting;
+        self
+    }
+
     /// Adds diagnostic if the next token has lex error in itself.
     #[inline]
     fn check_next_token(&mut self) {
-        if let RawToken::Error(error) = self.next_token.raw {
+        if self.next_token.raw == RawToken::Error(RawLexError::IdentifierTooLong) {
+            self.diagnostics.add_diagnostic(IdentifierTooLong::new(
+                self.next_token.location,
+                self.lexer.max_identifier_length(),
+            ));
+        } else if let RawToken::Error(error) = self.next_token.raw {
             self.diagnostics
                 .add_diagnostic(LexErrorDiagnostic::new(LexError {
                     location: self.next_token.location,
@@ -461,6 +686,12 @@ impl<'s, 'd> ParseState<'s, 'd> {
 
         self.current_token = self.next_token;
         self.next_token = self.lexer.next_no_comments();
+        self.previous_token_end = Some(self.current_token.location.end);
+    }
+
+    /// Returns the token after the next one without advancing the parse state.
+    fn peek_after_next(&self) -> Token {
+        self.lexer.clone().next_no_comments()
     }
 
     /// Checks if the next token is [`expected`].
@@ -560,7 +791,7 @@ impl<'s, 'd> ParseState<'s, 'd> {
     #[inline]
     pub(crate) fn add_unexpected_token_diagnostic(&mut self, expected: impl Into<String>) {
         self.diagnostics.add_diagnostic(UnexpectedToken::new(
-            self.current_token.location.end,
+            self.previous_token_end,
             self.next_token,
             expected,
         ));
@@ -1,4 +1,5 @@
-use stellar_ast::{token::RawToken, Literal};
+use stellar_ast::{token::RawToken, Literal, NumericLiteralSuffix};
+use stellar_interner::IdentifierId;
 
 use crate::{
     diagnostics::{FloatOverflow, IntegerOverflow},
@@ -15,38 +16,42 @@ impl Parse for LiteralParser {
             RawToken::IntegerLiteral => {
                 state.advance();
 
-                if let Ok(value) = state
-                    .resolve_current_token_str()
-                    .replace('_', "")
-                    .parse::<u64>()
-                {
+                let text = state.resolve_current_token_str();
+                let (value, suffix) = NumericLiteralSuffix::split_off(text);
+
+                if let Some(value) = parse_integer(value) {
                     Some(Literal::Integer {
                         value,
+                        suffix,
+                        text: IdentifierId::from(text),
                         location: state.current_token.location,
                     })
                 } else {
-                    state
-                        .diagnostics
-                        .add_diagnostic(IntegerOverflow::new(state.current_token.location));
+                    let diagnostic =
+                        IntegerOverflow::new(state.current_token.location, Some(text.to_owned()));
+                    state.diagnostics.add_diagnostic(diagnostic);
+
                     None
                 }
             }
             RawToken::FloatLiteral => {
                 state.advance();
 
-                if let Ok(value) = state
-                    .resolve_current_token_str()
-                    .replace('_', "")
-                    .parse::<f64>()
-                {
+                let text = state.resolve_current_token_str();
+                let (value, suffix) = NumericLiteralSuffix::split_off(text);
+
+                if let Some(value) = parse_float(value) {
                     Some(Literal::Float {
                         value,
+                        suffix,
+                        text: IdentifierId::from(text),
                         location: state.current_token.location,
                     })
                 } else {
-                    state
-                        .diagnostics
-                        .add_diagnostic(FloatOverflow::new(state.current_token.location));
+                    let diagnostic =
+                        FloatOverflow::new(state.current_token.location, Some(text.to_owned()));
+                    state.diagnostics.add_diagnostic(diagnostic);
+
                     None
                 }
             }
@@ -84,3 +89,22 @@ impl Parse for LiteralParser {
         }
     }
 }
+
+/// Parses a value of an integer literal without a suffix, e.g. `0xFF` or
+/// `1_000`. Returns `None` if the value doesn't fit into `u64`.
+pub(crate) fn parse_integer(literal: &str) -> Option<u64> {
+    let literal = literal.replace('_', "");
+    let (digits, radix) = match literal.get(..2) {
+        Some("0x" | "0X") => (&literal[2..], 16),
+        Some("0o" | "0O") => (&literal[2..], 8),
+        Some("0b" | "0B") => (&literal[2..], 2),
+        _ => (literal.as_str(), 10),
+    };
+
+    u64::from_str_radix(digits, radix).ok()
+}
+
+/// Parses a value of a float literal without a suffix, e.g. `1e3`.
+pub(crate) fn parse_float(literal: &str) -> Option<f64> {
+    literal.replace('_', "").parse().ok()
+}
@@ -1,12 +1,14 @@
 use stellar_ast::{
-    token::{Punctuator, RawToken},
-    NegativeNumericLiteral, Path, Pattern, StructFieldPattern,
+    token::{Keyword, Punctuator, RawToken},
+    IdentifierAST, NegativeNumericLiteral, NumericLiteralSuffix, Path, Pattern, StructFieldPattern,
 };
+use stellar_filesystem::location::ByteOffset;
+use stellar_interner::IdentifierId;
 
 use crate::{
-    diagnostics::{FloatOverflow, IntegerOverflow, UnexpectedToken},
+    diagnostics::{FloatOverflow, IntegerOverflow, MultipleRestPatterns},
     list::ListParser,
-    literal::LiteralParser,
+    literal::{parse_float, parse_integer, LiteralParser},
     path::PathParser,
     Parse, ParseState,
 };
@@ -48,19 +50,20 @@ impl PatternExceptOrParser {
             RawToken::IntegerLiteral => {
                 state.advance();
 
-                if let Ok(value) = state
-                    .resolve_current_token_str()
-                    .replace('_', "")
-                    .parse::<u64>()
-                {
+                let text = state.resolve_current_token_str();
+                let (value, suffix) = NumericLiteralSuffix::split_off(text);
+
+                if let Some(value) = parse_integer(value) {
                     Some(NegativeNumericLiteral::Integer {
                         value,
+                        suffix,
+                        text: IdentifierId::from(text),
                         location: state.current_token.location,
                     })
                 } else {
-                    state
-                        .diagnostics
-                        .add_diagnostic(IntegerOverflow::new(state.current_token.location));
+                    let diagnostic =
+                        IntegerOverflow::new(state.current_token.location, Some(text.to_owned()));
+                    state.diagnostics.add_diagnostic(diagnostic);
 
                     None
                 }
@@ -68,19 +71,20 @@ impl PatternExceptOrParser {
             RawToken::FloatLiteral => {
                 state.advance();
 
-                if let Ok(value) = state
-                    .resolve_current_token_str()
-                    .replace('_', "")
-                    .parse::<f64>()
-                {
+                let text = state.resolve_current_token_str();
+                let (value, suffix) = NumericLiteralSuffix::split_off(text);
+
+                if let Some(value) = parse_float(value) {
                     Some(NegativeNumericLiteral::Float {
                         value,
+                        suffix,
+                        text: IdentifierId::from(text),
                         location: state.current_token.location,
                     })
                 } else {
-                    state
-                        .diagnostics
-                        .add_diagnostic(FloatOverflow::new(state.current_token.location));
+                    let diagnostic =
+                        FloatOverflow::new(state.current_token.location, Some(text.to_owned()));
+                    state.diagnostics.add_diagnostic(diagnostic);
 
                     None
                 }
@@ -117,29 +121,96 @@ impl PatternExceptOrParser {
                 .first()
                 .expect("Cannot get first identifier in path when parsing identifier pattern");
 
-            let pattern = if state.next_token.raw == Punctuator::At {
-                state.advance();
-                Some(Box::new(PatternParser.parse(state)?))
-            } else {
-                None
-            };
-
-            Some(Pattern::Identifier {
-                location: state.make_location(
-                    path.location.start,
-                    match pattern {
-                        Some(ref pattern) => pattern.location().end,
-                        None => path.location.end,
-                    },
-                ),
-                identifier: *identifier,
-                pattern,
-            })
+            Self::parse_identifier_pattern_rest(state, path.location.start, *identifier, false)
         } else {
             Some(Pattern::Path { path })
         }
     }
 
+    /// Parses an identifier pattern, that starts with `mut`, e.g. `mut x`.
+    fn parse_mutable_identifier_pattern(state: &mut ParseState<'_, '_>) -> Option<Pattern> {
+        let start = state.next_token.location.start;
+        state.advance();
+
+        let identifier = state.consume_identifier()?;
+
+        Self::parse_identifier_pattern_rest(state, start, identifier, true)
+    }
+
+    /// Parses an optional `@ pattern` after a binding name.
+    fn parse_identifier_pattern_rest(
+        state: &mut ParseState<'_, '_>,
+        start: ByteOffset,
+        identifier: IdentifierAST,
+        mutable: bool,
+    ) -> Option<Pattern> {
+        let pattern = if state.next_token.raw == Punctuator::At {
+            state.advance();
+            Some(Box::new(PatternParser.parse(state)?))
+        } else {
+            None
+        };
+
+        Some(Pattern::Identifier {
+            location: state.make_location(
+                start,
+                match pattern {
+                    Some(ref pattern) => pattern.location().end,
+                    None => identifier.location.end,
+                },
+            ),
+            identifier,
+            mutable,
+            pattern,
+        })
+    }
+
+    fn parse_rest_pattern(state: &mut ParseState<'_, '_>) -> Option<Pattern> {
+        state.advance(); // `..`
+
+        let rest = Pattern::Rest {
+            location: state.current_token.location,
+        };
+
+        if state.next_token.raw != RawToken::Identifier {
+            return Some(rest);
+        }
+
+        // `..tail` is a shorthand for `tail @ ..`
+        let identifier = state.consume_identifier()?;
+
+        Some(Pattern::Identifier {
+            location: state.make_location(rest.location().start, identifier.location.end),
+            identifier,
+            mutable: false,
+            pattern: Some(Box::new(rest)),
+        })
+    }
+
+    /// Reports all rest patterns except the first one, because a tuple or a
+    /// list pattern can contain only one of them.
+    fn check_rest_patterns(state: &mut ParseState<'_, '_>, patterns: &[Pattern]) {
+        let mut rest_patterns = patterns.iter().filter(|pattern| match pattern {
+            Pattern::Rest { .. } => true,
+            Pattern::Identifier {
+                pattern: Some(pattern),
+                ..
+            } => pattern.is_rest(),
+            _ => false,
+        });
+
+        let Some(first) = rest_patterns.next() else {
+            return;
+        };
+
+        for pattern in rest_patterns {
+            state.diagnostics.add_diagnostic(MultipleRestPatterns::new(
+                pattern.location(),
+                first.location(),
+            ));
+        }
+    }
+
     fn parse_list_pattern(&self, state: &mut ParseState<'_, '_>) -> Option<Pattern> {
         let start = state.next_token.location.start;
         state.advance();
@@ -152,6 +223,8 @@ impl PatternExceptOrParser {
 
         state.advance();
 
+        Self::check_rest_patterns(state, &inner_patterns);
+
         Some(Pattern::List {
             location: state.location_from(start),
             inner_patterns,
@@ -172,6 +245,8 @@ impl PatternExceptOrParser {
 
         state.advance(); // `)`
 
+        Self::check_rest_patterns(state, &inner_patterns);
+
         Some(Pattern::TupleLike {
             location: state.location_from(path.location.start),
             path,
@@ -190,6 +265,8 @@ impl PatternExceptOrParser {
 
         state.advance();
 
+        Self::check_rest_patterns(state, &elements);
+
         let location = state.location_from(start);
 
         let mut elements = elements.into_iter();
@@ -283,13 +360,7 @@ impl Parse for PatternExceptOrParser {
             | RawToken::FloatLiteral
             | RawToken::TrueBoolLiteral
             | RawToken::FalseBoolLiteral => LiteralParser.parse(state).map(Pattern::Literal),
-            RawToken::Punctuator(Punctuator::DoubleDot) => {
-                state.advance();
-
-                Some(Pattern::Rest {
-                    location: state.next_token.location,
-                })
-            }
+            RawToken::Punctuator(Punctuator::DoubleDot) => Self::parse_rest_pattern(state),
             RawToken::Punctuator(Punctuator::Underscore) => {
                 state.advance();
 
@@ -301,16 +372,13 @@ impl Parse for PatternExceptOrParser {
                 self.parse_negative_numeric_literal_pattern(state)
             }
             RawToken::Identifier => self.parse_pattern_beginning_with_identifier(state),
+            RawToken::Keyword(Keyword::Mut) => Self::parse_mutable_identifier_pattern(state),
             RawToken::Punctuator(Punctuator::OpenBracket) => self.parse_list_pattern(state),
             RawToken::Punctuator(Punctuator::OpenParent) => {
                 self.parse_grouped_or_tuple_pattern(state)
             }
             _ => {
-                state.diagnostics.add_diagnostic(UnexpectedToken::new(
-                    state.current_token.location.end,
-                    state.next_token,
-                    "pattern",
-                ));
+                state.add_unexpected_token_diagnostic("pattern");
                 None
             }
         }
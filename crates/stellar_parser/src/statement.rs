@@ -1,10 +1,20 @@
 use stellar_ast::{
     token::{Keyword, Punctuator, RawToken},
-    Statement,
+    IdentifierAST, Statement, StatementsBlock,
 };
 
+use stellar_filesystem::location::ByteOffset;
+
 use crate::{
-    expression::ExpressionParser, pattern::PatternParser, r#type::TypeParser, Parse, ParseState,
+    diagnostics::{
+        EOFInsteadOfCloseBrace, EmptyStatement, NoSemicolonAfterExpression,
+        NoSemicolonAfterStatement,
+    },
+    expected::{expected, starts_expression, STATEMENT_KEYWORDS},
+    expression::ExpressionParser,
+    pattern::PatternParser,
+    r#type::TypeParser,
+    Parse, ParseState,
 };
 
 pub(crate) struct StatementParser;
@@ -15,27 +25,77 @@ pub(crate) struct StatementParserResult {
 }
 
 impl StatementParser {
+    /// Consumes `;`, that ends a statement starting at a given offset.
+    fn consume_semicolon(state: &mut ParseState<'_, '_>, start: ByteOffset) -> Option<()> {
+        if state.next_token.raw == Punctuator::Semicolon {
+            state.advance();
+
+            return Some(());
+        }
+
+        if !state.next_token.raw.is_error() {
+            state
+                .diagnostics
+                .add_diagnostic(NoSemicolonAfterStatement::new(state.location_from(start)));
+        }
+
+        None
+    }
+
+    /// Parses `break` or `continue` keyword followed by an optional loop
+    /// label, e.g. `break @outer`, and returns the start of the statement and
+    /// the label.
+    fn parse_loop_control_keyword(
+        state: &mut ParseState<'_, '_>,
+    ) -> Option<(ByteOffset, Option<IdentifierAST>)> {
+        state.advance(); // `break` or `continue`
+
+        let start = state.current_token.location.start;
+
+        let label = if state.next_token.raw == Punctuator::At {
+            state.advance(); // `@`
+
+            Some(state.consume_identifier()?)
+        } else {
+            None
+        };
+
+        Some((start, label))
+    }
+
     fn parse_return_statement(self, state: &mut ParseState<'_, '_>) -> Option<Statement> {
+        let start = state.next_token.location.start;
+
         state.advance();
 
         let expression = ExpressionParser::default().parse(state)?;
 
-        state.consume(Punctuator::Semicolon)?;
+        Self::consume_semicolon(state, start)?;
 
-        Some(Statement::Return { expression })
+        Some(Statement::Return {
+            location: state.location_from(start),
+            expression,
+        })
     }
 
     fn parse_defer_statement(self, state: &mut ParseState<'_, '_>) -> Option<Statement> {
+        let start = state.next_token.location.start;
+
         state.advance();
 
         let call = ExpressionParser::default().parse(state)?;
 
-        state.consume(Punctuator::Semicolon)?;
+        Self::consume_semicolon(state, start)?;
 
-        Some(Statement::Defer { call })
+        Some(Statement::Defer {
+            location: state.location_from(start),
+            call,
+        })
     }
 
     fn parse_let_statement(self, state: &mut ParseState<'_, '_>) -> Option<Statement> {
+        let start = state.next_token.location.start;
+
         state.advance();
 
         let pattern = PatternParser.parse(state)?;
@@ -52,36 +112,59 @@ impl StatementParser {
 
         let value = ExpressionParser::default().parse(state)?;
 
-        state.consume(Punctuator::Semicolon)?;
+        let r#else = if state.next_token.raw == Keyword::Else {
+            state.advance();
+
+            Some(StatementsBlockParser.parse(state)?)
+        } else {
+            None
+        };
+
+        Self::consume_semicolon(state, start)?;
 
-        Some(Statement::Let { pattern, value, ty })
+        Some(Statement::Let {
+            location: state.location_from(start),
+            pattern,
+            value,
+            ty,
+            r#else,
+        })
     }
 
     fn parse_continue_statement(self, state: &mut ParseState<'_, '_>) -> Option<Statement> {
-        state.advance();
-
-        let location = state.current_token.location;
+        let (start, label) = Self::parse_loop_control_keyword(state)?;
+        let location = state.location_from(start);
 
-        state.consume(Punctuator::Semicolon)?;
+        Self::consume_semicolon(state, start)?;
 
-        Some(Statement::Continue { location })
+        Some(Statement::Continue { location, label })
     }
 
     fn parse_break_statement(self, state: &mut ParseState<'_, '_>) -> Option<Statement> {
-        state.advance();
+        let (start, label) = Self::parse_loop_control_keyword(state)?;
 
-        let location = state.current_token.location;
+        let value = if state.next_token.raw == Punctuator::Semicolon {
+            None
+        } else {
+            Some(ExpressionParser::default().parse(state)?)
+        };
 
-        state.consume(Punctuator::Semicolon)?;
+        let location = state.location_from(start);
 
-        Some(Statement::Break { location })
+        Self::consume_semicolon(state, start)?;
+
+        Some(Statement::Break {
+            location,
+            label,
+            value,
+        })
     }
 
     fn parse_expression_statement(
         self,
         state: &mut ParseState<'_, '_>,
     ) -> Option<ExpressionStatementParseResult> {
-        let expression = ExpressionParser::new().in_statements_block().parse(state)?;
+        let expression = ExpressionParser::new().parse(state)?;
 
         let (last_expression_in_block, has_semicolon) = if expression.with_block() {
             // 1. `ExpressionWithBlocks` are treated as individual statements
@@ -93,8 +176,19 @@ impl StatementParser {
             state.advance();
 
             (false, true)
-        } else {
+        } else if state.next_token.raw == Punctuator::CloseBrace {
+            // Expression in the tail position, e.g. `b` in `{ let b = 1; b }`.
             (true, false)
+        } else {
+            // Expressions, that are not in the tail position, must be
+            // followed by `;`.
+            if !state.next_token.raw.is_error() {
+                state
+                    .diagnostics
+                    .add_diagnostic(NoSemicolonAfterExpression::new(expression.location()));
+            }
+
+            return None;
         };
 
         Some(ExpressionStatementParseResult {
@@ -117,6 +211,14 @@ impl Parse for StatementParser {
             RawToken::Keyword(Keyword::Let) => (self.parse_let_statement(state)?, false),
             RawToken::Keyword(Keyword::Continue) => (self.parse_continue_statement(state)?, false),
             RawToken::Keyword(Keyword::Break) => (self.parse_break_statement(state)?, false),
+            token if !starts_expression(token) => {
+                state.add_unexpected_token_diagnostic(expected!(
+                    STATEMENT_KEYWORDS,
+                    or an expression
+                ));
+
+                return None;
+            }
             _ => {
                 let expression_statement_parser_result = self.parse_expression_statement(state)?;
 
@@ -142,25 +244,45 @@ pub(crate) struct ExpressionStatementParseResult {
 pub(crate) struct StatementsBlockParser;
 
 impl Parse for StatementsBlockParser {
-    type Output = Option<Vec<Statement>>;
+    type Output = Option<StatementsBlock>;
 
     fn parse(self, state: &mut ParseState<'_, '_>) -> Self::Output {
         state.consume(Punctuator::OpenBrace)?;
+        let open_brace_location = state.current_token.location;
+
+        let mut statements = vec![];
 
-        let mut block = vec![];
+        // `;` right after an expression with block, e.g. `if a {};`, ends
+        // the expression statement instead of being an empty statement.
+        let mut after_expression_with_block = false;
 
         loop {
             match state.next_token.raw {
                 RawToken::Punctuator(Punctuator::CloseBrace) => break,
                 RawToken::EndOfFile => {
-                    state.add_unexpected_token_diagnostic(Punctuator::CloseBrace);
+                    state
+                        .diagnostics
+                        .add_diagnostic(EOFInsteadOfCloseBrace::new(
+                            state.next_token.location,
+                            open_brace_location,
+                        ));
 
                     return None;
                 }
                 RawToken::Punctuator(Punctuator::Semicolon) => {
-                    // Skip
+                    let previous_token_location = state.current_token.location;
+
                     state.advance();
 
+                    if !after_expression_with_block {
+                        state.diagnostics.add_diagnostic(EmptyStatement::new(
+                            state.current_token.location,
+                            previous_token_location,
+                        ));
+                    }
+
+                    after_expression_with_block = false;
+
                     continue;
                 }
                 _ => {}
@@ -170,7 +292,15 @@ impl Parse for StatementsBlockParser {
                 statement,
                 last_expression_in_block,
             } = StatementParser.parse(state)?;
-            block.push(statement);
+
+            after_expression_with_block = matches!(
+                statement,
+                Statement::Expression {
+                    has_semicolon: false,
+                    ..
+                }
+            );
+            statements.push(statement);
 
             if last_expression_in_block {
                 break;
@@ -179,6 +309,24 @@ impl Parse for StatementsBlockParser {
 
         state.consume(Punctuator::CloseBrace)?;
 
-        Some(block)
+        // The last expression without `;` (including expressions with blocks,
+        // like `if` or `match`) is the value of the whole block.
+        let tail_expression = match statements.pop() {
+            Some(Statement::Expression {
+                expression,
+                has_semicolon: false,
+            }) => Some(Box::new(expression)),
+            Some(statement) => {
+                statements.push(statement);
+
+                None
+            }
+            None => None,
+        };
+
+        Some(StatementsBlock {
+            statements,
+            tail_expression,
+        })
     }
 }
@@ -0,0 +1,198 @@
+//! Defines [`tokenize()`] - a lossless token stream for tooling, e.g. for
+//! formatters and syntax highlighters.
+//!
+//! Unlike the parser, which skips comments and whitespace, the stream keeps
+//! them as [`Trivia`] attached to the following token, so that concatenating
+//! texts of all trivia and tokens gives exactly the source text:
+//!
+//! ```
+//! use stellar_interner::DUMMY_PATH_ID;
+//! use stellar_parser::tokenize;
+//!
+//! let source = "fun main() { // entry point\n}";
+//! let mut text = String::new();
+//!
+//! for token in tokenize(DUMMY_PATH_ID, source) {
+//!     for trivia in &token.leading_trivia {
+//!         text.push_str(&source[trivia.location]);
+//!     }
+//!
+//!     text.push_str(&source[token.token.location]);
+//! }
+//!
+//! assert_eq!(text, source);
+//! ```
+
+use stellar_ast::token::{RawToken, Token};
+use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_interner::PathId;
+use stellar_lexer::{is_whitespace_char, Lexer};
+
+/// Kind of [`Trivia`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TriviaKind {
+    /// A sequence of whitespace characters, that doesn't contain newlines.
+    Whitespace,
+
+    /// A single newline, either `\n` or `\r\n`.
+    Newline,
+
+    /// A usual comment, e.g. `// TODO`. Doc comments are tokens, because
+    /// they are a part of the syntax.
+    Comment,
+}
+
+/// A piece of source text, that doesn't affect the meaning of a program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Trivia {
+    /// Kind of the trivia.
+    pub kind: TriviaKind,
+
+    /// Location of the trivia in the source text.
+    pub location: Location,
+}
+
+/// A token together with trivia, that precedes it. Trivia at the end of
+/// a file precedes the [`RawToken::EndOfFile`] token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenWithTrivia {
+    /// The token. Lex errors are represented as [`RawToken::Error`] tokens.
+    pub token: Token,
+
+    /// Comments and whitespace before the token.
+    pub leading_trivia: Vec<Trivia>,
+}
+
+/// Splits a source text into tokens with trivia. The last token is always
+/// [`RawToken::EndOfFile`] with an empty location at the end of the file.
+///
+/// Texts of all trivia and tokens concatenated in order reconstruct the
+/// source text exactly, even if it contains lex errors.
+#[must_use]
+pub fn tokenize(filepath: PathId, source: &str) -> Vec<TokenWithTrivia> {
+    let mut lexer = Lexer::new(filepath, source);
+    let mut tokens = Vec::new();
+    let mut leading_trivia = Vec::new();
+
+    // End of the source text, that is already covered by trivia and tokens.
+    let mut position = 0;
+
+    loop {
+        let Token { raw, location } = lexer.next_token();
+        let eof = raw.eof();
+
+        let (start, mut end) = if eof {
+            (source.len(), source.len())
+        } else {
+            (location.start.0, location.end.0.min(source.len()))
+        };
+
+        // Locations of single character tokens are one byte long, even if
+        // the character is not ASCII.
+        while !source.is_char_boundary(end) {
+            end += 1;
+        }
+
+        // After some lex errors, e.g. invalid escape sequences, the lexer
+        // continues inside of the literal, so tokens can overlap.
+        if end <= position && !eof {
+            continue;
+        }
+
+        let mut start = start.max(position);
+
+        if start > position {
+            // The lexer skips parts of erroneous literals, which are not
+            // trivia, so they are attached to the token.
+            start = whitespace_trivia(filepath, source, position, start, &mut leading_trivia);
+        }
+
+        let location = Location {
+            filepath,
+            start: ByteOffset(start),
+            end: ByteOffset(end),
+        };
+        position = end;
+
+        if raw == RawToken::Comment {
+            leading_trivia.push(Trivia {
+                kind: TriviaKind::Comment,
+                location,
+            });
+
+            continue;
+        }
+
+        tokens.push(TokenWithTrivia {
+            token: Token { raw, location },
+            leading_trivia: std::mem::take(&mut leading_trivia),
+        });
+
+        if eof {
+            return tokens;
+        }
+    }
+}
+
+/// Adds whitespace and newlines from the beginning of `source[start..end]`
+/// to a given trivia list. Returns the offset of the first character, that
+/// is not a whitespace, or `end`.
+fn whitespace_trivia(
+    filepath: PathId,
+    source: &str,
+    start: usize,
+    end: usize,
+    trivia: &mut Vec<Trivia>,
+) -> usize {
+    let mut position = start;
+    let mut chars = source[start..end].char_indices().peekable();
+
+    let mut push = |kind, start, end| {
+        trivia.push(Trivia {
+            kind,
+            location: Location {
+                filepath,
+                start: ByteOffset(start),
+                end: ByteOffset(end),
+            },
+        });
+    };
+
+    while let Some(&(offset, c)) = chars.peek() {
+        let offset = start + offset;
+
+        if !is_whitespace_char(c) {
+            break;
+        }
+
+        chars.next();
+
+        let newline_length = match (c, chars.peek()) {
+            ('\n', _) => 1,
+            ('\r', Some((_, '\n'))) => {
+                chars.next();
+                2
+            }
+            _ => 0,
+        };
+
+        if newline_length == 0 {
+            continue;
+        }
+
+        if position < offset {
+            push(TriviaKind::Whitespace, position, offset);
+        }
+
+        position = offset + newline_length;
+        push(TriviaKind::Newline, offset, position);
+    }
+
+    let whitespace_end = chars.peek().map_or(end, |&(offset, _)| start + offset);
+
+    if position < whitespace_end {
+        push(TriviaKind::Whitespace, position, whitespace_end);
+    }
+
+    whitespace_end
+}
@@ -3,7 +3,10 @@ use stellar_ast::{
     GenericParameter, Type, TypeConstructor, WherePredicate,
 };
 
-use crate::{list::ListParser, path::PathParser, OptionallyParse, Parse, ParseState};
+use crate::{
+    diagnostics::TypeNestingLimitExceeded, list::ListParser, path::PathParser, OptionallyParse,
+    Parse, ParseState,
+};
 
 pub(crate) struct BoundsParser;
 
@@ -130,6 +133,28 @@ impl Parse for TypeParser {
     type Output = Option<Type>;
 
     fn parse(self, state: &mut ParseState<'_, '_>) -> Self::Output {
+        // Deeply nested types would otherwise overflow the stack.
+        if state.type_nesting >= state.max_type_nesting {
+            state
+                .diagnostics
+                .add_diagnostic(TypeNestingLimitExceeded::new(
+                    state.next_token.location,
+                    state.max_type_nesting,
+                ));
+
+            return None;
+        }
+
+        state.type_nesting += 1;
+        let ty = self.parse_unlimited(state);
+        state.type_nesting -= 1;
+
+        ty
+    }
+}
+
+impl TypeParser {
+    fn parse_unlimited(self, state: &mut ParseState<'_, '_>) -> Option<Type> {
         match state.next_token.raw {
             RawToken::Punctuator(Punctuator::OpenParent) => {
                 self.parse_parenthesized_or_tuple_type(state)
@@ -0,0 +1,175 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use stellar_ast::{Expression, RawBinaryOperator, RawPrefixOperator};
+use stellar_diagnostics::Diagnostics;
+use stellar_filesystem::location::Location;
+use stellar_interner::DUMMY_PATH_ID;
+use stellar_parser::parse_expression;
+
+const OPERATORS: usize = 100_000;
+
+/// Drops an expression on a thread with a large stack, because dropping
+/// deeply nested expressions is recursive.
+fn drop_deep(expression: Option<Expression>) {
+    thread::Builder::new()
+        .stack_size(1 << 30)
+        .spawn(move || drop(expression))
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+fn parse_deep(source: &str) -> Option<Expression> {
+    let mut diagnostics = Diagnostics::new();
+    let now = Instant::now();
+
+    let expression = parse_expression(DUMMY_PATH_ID, source, &mut diagnostics);
+
+    assert!(now.elapsed() < Duration::from_secs(10));
+    assert!(diagnostics.is_empty());
+
+    expression
+}
+
+#[test]
+fn long_left_associative_chain() {
+    let source = format!("1{}", " + 1".repeat(OPERATORS));
+    let expression = parse_deep(&source);
+
+    let Some(Expression::Binary {
+        location,
+        left,
+        operator,
+        ..
+    }) = &expression
+    else {
+        panic!("expected a binary expression");
+    };
+
+    assert_eq!(operator.raw, RawBinaryOperator::Plus);
+    assert_eq!(location.end.0, source.len());
+    assert!(matches!(**left, Expression::Binary { .. }));
+
+    drop_deep(expression);
+}
+
+#[test]
+fn deeply_right_nested_parentheses() {
+    let source = format!("{}1{}", "1 + (".repeat(OPERATORS), ")".repeat(OPERATORS));
+    let expression = parse_deep(&source);
+
+    let Some(Expression::Binary { right, .. }) = &expression else {
+        panic!("expected a binary expression");
+    };
+
+    assert!(matches!(**right, Expression::Parenthesized { .. }));
+
+    drop_deep(expression);
+}
+
+#[test]
+fn deeply_nested_prefix_operators() {
+    let source = format!("{}a.b()", "- ".repeat(OPERATORS));
+    let expression = parse_deep(&source);
+
+    let Some(Expression::Prefix {
+        operator, inner, ..
+    }) = &expression
+    else {
+        panic!("expected a prefix expression");
+    };
+
+    assert_eq!(operator.raw, RawPrefixOperator::Minus);
+    assert!(matches!(**inner, Expression::Prefix { .. }));
+
+    drop_deep(expression);
+}
+
+/// Renders an expression with explicit grouping, e.g. `(+ 1 (* 2 3))` for
+/// `1 + 2 * 3`.
+fn grouping(source: &str, expression: &Expression) -> String {
+    let text = |location: Location| source[location.start.0..location.end.0].to_owned();
+
+    match expression {
+        Expression::Binary {
+            left,
+            operator,
+            right,
+            ..
+        } => format!(
+            "({} {} {})",
+            text(operator.location),
+            grouping(source, left),
+            grouping(source, right)
+        ),
+        Expression::Prefix {
+            operator, inner, ..
+        } => format!("({} {})", text(operator.location), grouping(source, inner)),
+        Expression::Postfix {
+            operator, inner, ..
+        } => format!("({} {})", grouping(source, inner), text(operator.location)),
+        Expression::Parenthesized { inner, .. } => grouping(source, inner),
+        Expression::As { left, right, .. } => {
+            format!("(as {} {})", grouping(source, left), text(right.location()))
+        }
+        Expression::Call {
+            callee, arguments, ..
+        } => format!(
+            "(call {}{})",
+            grouping(source, callee),
+            arguments
+                .iter()
+                .map(|argument| format!(" {}", grouping(source, &argument.value)))
+                .collect::<String>()
+        ),
+        Expression::FieldAccess { left, right, .. } => {
+            format!("(. {} {})", grouping(source, left), text(right.location))
+        }
+        Expression::Tuple { elements, .. } => format!(
+            "(tuple{})",
+            elements
+                .iter()
+                .map(|element| format!(" {}", grouping(source, element)))
+                .collect::<String>()
+        ),
+        expression => text(expression.location()),
+    }
+}
+
+#[test]
+fn precedence_and_associativity() {
+    for (source, expected) in [
+        ("1 + 2 * 3", "(+ 1 (* 2 3))"),
+        ("1 * 2 + 3", "(+ (* 1 2) 3)"),
+        ("a - b - c", "(- (- a b) c)"),
+        ("a = b = c", "(= (= a b) c)"),
+        ("a || b && c == d + e", "(|| a (&& b (== c (+ d e))))"),
+        ("-a + b", "(+ (- a) b)"),
+        ("-a.b(c)", "(- (call (. a b) c))"),
+        ("!-a?", "((! (- a)) ?)"),
+        ("-a as int32", "(as (- a) int32)"),
+        ("a as int32 + b", "(+ (as a int32) b)"),
+        ("(a + b) * c", "(* (+ a b) c)"),
+        ("f(a + b, -c) * 2", "(* (call f (+ a b) (- c)) 2)"),
+        ("(a, (b + c,), ())", "(tuple a (tuple (+ b c)) (tuple))"),
+        ("x += -y * z", "(+= x (* (- y) z))"),
+    ] {
+        let mut diagnostics = Diagnostics::new();
+        let expression = parse_expression(DUMMY_PATH_ID, source, &mut diagnostics).unwrap();
+
+        assert!(diagnostics.is_empty());
+        assert_eq!(grouping(source, &expression), expected, "{source}");
+    }
+}
+
+#[test]
+fn unclosed_parenthesis() {
+    let mut diagnostics = Diagnostics::new();
+
+    assert!(parse_expression(DUMMY_PATH_ID, "(a + b c", &mut diagnostics).is_none());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics.all()[0].code.as_deref(), Some("E001"));
+}
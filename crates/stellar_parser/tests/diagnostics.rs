@@ -0,0 +1,227 @@
+use stellar_diagnostics::{
+    term::{self, termcolor::NoColor, Config},
+    Diagnostics,
+};
+use stellar_filesystem::source_map::SourceMap;
+use stellar_interner::PathId;
+use stellar_parser::{parse_expression, parse_item, parse_pattern};
+
+/// Parses a source with a given parser and renders the emitted diagnostics.
+fn render(source: &str, parse: fn(PathId, &str, &mut Diagnostics)) -> String {
+    let filepath = PathId::from("test.sr");
+    let mut source_map = SourceMap::new();
+    source_map.set_override(filepath, source);
+
+    let mut diagnostics = Diagnostics::new();
+    parse(filepath, source, &mut diagnostics);
+
+    let mut writer = NoColor::new(vec![]);
+
+    for diagnostic in diagnostics.iter() {
+        term::emit(&mut writer, &Config::default(), &source_map, diagnostic).unwrap();
+    }
+
+    String::from_utf8(writer.into_inner()).unwrap()
+}
+
+#[test]
+fn missing_semicolon() {
+    let output = render(
+        "{\n    let a = 1\n    let b = 2;\n}",
+        |filepath, source, diagnostics| {
+            let _ = parse_expression(filepath, source, diagnostics);
+        },
+    );
+
+    assert_eq!(
+        output,
+        "\
+error[E013]: expected `;` after statement
+  ┌─ test.sr:2:14
+  │
+1 │ {
+2 │     let a = 1
+  │     ---------^ help: add `;` here
+  │     │        \n\
+\x20 │     statement
+3 │     let b = 2;
+4 │ }
+  │
+  = help: add `;`: insert `;` at test.sr:2:14
+
+"
+    );
+}
+
+/// The primary label is empty and doesn't cover the line break, so only
+/// lines after the statement are shown as the context.
+#[test]
+fn missing_semicolon_context() {
+    let output = render(
+        "{\n    a()\n    b();\n    c();\n    d();\n    e();\n}",
+        |filepath, source, diagnostics| {
+            let _ = parse_expression(filepath, source, diagnostics);
+        },
+    );
+
+    assert_eq!(
+        output,
+        "\
+error[E014]: expected `;` after expression
+  ┌─ test.sr:2:8
+  │
+1 │ {
+2 │     a()
+  │     ---^ help: add `;` here
+  │     │  \n\
+\x20 │     expression
+3 │     b();
+4 │     c();
+5 │     d();
+  │
+  = help: add `;`: insert `;` at test.sr:2:8
+
+"
+    );
+}
+
+/// There is no previous token to point after, so the unexpected token
+/// itself gets the primary label.
+#[test]
+fn unexpected_first_token() {
+    let output = render("=", |filepath, source, diagnostics| {
+        let _ = parse_pattern(filepath, source, diagnostics);
+    });
+
+    assert_eq!(
+        output,
+        "\
+error[E001]: expected pattern, found `=`
+  ┌─ test.sr:1:1
+  │
+1 │ =
+  │ ^ expected pattern
+
+"
+    );
+}
+
+#[test]
+fn integer_overflow_shows_original_spelling() {
+    let output = render(
+        "0x1_0000_0000_0000_0000",
+        |filepath, source, diagnostics| {
+            let _ = parse_expression(filepath, source, diagnostics);
+        },
+    );
+
+    assert_eq!(
+        output,
+        "\
+error[E002]: unexpected integer overflow
+  ┌─ test.sr:1:1
+  │
+1 │ 0x1_0000_0000_0000_0000
+  │ ^^^^^^^^^^^^^^^^^^^^^^^ `0x1_0000_0000_0000_0000` doesn't fit into `u64`
+  │
+  = note: integer cannot exceed the maximum value of `u64` (u64.max() == 18_446_744_073_709_551_615)
+  = note: you can use exponent to do so, but be careful!
+
+"
+    );
+}
+
+#[test]
+fn unexpected_token_at_statement_start() {
+    let output = render(
+        "{\n    let a = 1;\n    => a\n}",
+        |filepath, source, diagnostics| {
+            let _ = parse_expression(filepath, source, diagnostics);
+        },
+    );
+
+    assert_eq!(
+        output,
+        "\
+error[E001]: expected `let`, `return`, `defer`, `break`, `continue`, or an expression, found `=`
+  ┌─ test.sr:2:15
+  │
+1 │ {
+2 │     let a = 1;
+  │               ^ expected `let`, `return`, `defer`, `break`, `continue`, or an expression
+3 │     => a
+  │     - unexpected token
+4 │ }
+
+"
+    );
+}
+
+#[test]
+fn unexpected_token_at_item_start() {
+    let output = render("pub let a = 1;", |filepath, source, diagnostics| {
+        let _ = parse_item(filepath, source, diagnostics);
+    });
+
+    assert_eq!(
+        output,
+        "\
+error[E001]: expected `fun`, `struct`, `enum`, `interface`, `import`, or another module item, found `let`
+  ┌─ test.sr:1:4
+  │
+1 │ pub let a = 1;
+  │    ^--- unexpected token
+  │    │ \n\
+\x20 │    expected `fun`, `struct`, `enum`, `interface`, `import`, or another module item
+
+"
+    );
+}
+
+#[test]
+fn end_of_file_instead_of_close_brace() {
+    let output = render("{\n    let a = 1;\n", |filepath, source, diagnostics| {
+        let _ = parse_expression(filepath, source, diagnostics);
+    });
+
+    assert_eq!(
+        output,
+        "\
+error[E005]: expected `}`, found end of file
+  ┌─ test.sr:3:1
+  │
+1 │ {
+  │ - unclosed delimiter
+2 │     let a = 1;
+3 │ \n\
+\x20 │ ^ expected `}`
+
+"
+    );
+}
+
+#[test]
+fn discriminant_on_tuple_like_enum_item() {
+    let output = render(
+        "enum Shape { Circle(float64) = 1, Point }",
+        |filepath, source, diagnostics| {
+            let _ = parse_item(filepath, source, diagnostics);
+        },
+    );
+
+    assert_eq!(
+        output,
+        "\
+error[E019]: explicit discriminant on the tuple-like enum item `Circle`
+  ┌─ test.sr:1:30
+  │
+1 │ enum Shape { Circle(float64) = 1, Point }
+  │              ------          ^^^ only enum items without payload can have discriminants
+  │              │                \n\
+\x20 │              this item has a payload
+  │
+  = help: remove the discriminant: remove `= 1` at test.sr:1:30
+
+"
+    );
+}
@@ -5,6 +5,8 @@ tests_using! {
 
     int_literal -> "1",
     float_literal -> "1.2",
+    suffixed_int_literal -> "255u8",
+    suffixed_float_literal -> "1.2f32",
     string_literal -> "\"string\"",
     bool_literal -> "true",
     identifier -> "foo",
@@ -16,6 +18,8 @@ tests_using! {
     binary1 -> "1 + 2",
     binary2 -> "(f()? + 2) / 3.2 + !a()",
     struct_ -> "Person { name: \"John\", age }",
+    struct_update -> "Point { x: 1, ..origin() }",
+    struct_update_only -> "Point { ..origin }",
     tuple -> "(1, (1, \"hello\"), true)",
     list -> "[1, 2, 3]",
     postfix -> "checked_div(1, 0)?",
@@ -23,9 +27,370 @@ tests_using! {
     double_plus_hell -> "++a++",
     if_else -> "if true { 1 } else if f() { 3 } else { 2 }",
     loop_ -> "loop {}",
+    labeled_loop -> "@outer: loop { while true { break @outer; } }",
+    labeled_while -> "@outer: while true { continue @outer; }",
     while_ -> "while true { }",
     underscore -> "_",
     match_ -> "match true { true -> 1, _ -> 2 }",
+    match_with_guard -> "match x { Some(y) if y > 0 -> y, _ -> 0 }",
     lambda -> "|a, b: usize| a + b",
-    block -> "{ a++; a }"
+    lambda_without_parameters -> "|| 1",
+    tuple_index -> "t.0",
+    chained_tuple_index -> "t.0.1",
+    call_tuple_index -> "f().12",
+    index -> "xs[0]",
+    chained_index -> "xs[i][j + 1]",
+    index_of_call -> "f()[0].len()",
+    type_arguments_call -> "new[List[int32]]()",
+    type_arguments_struct -> "Box[int32] { value: 1 }",
+    type_arguments_field_access -> "Option[List[int32]].None",
+    assignment -> "x = 1",
+    compound_assignment -> "p.x += xs[0] * 2",
+    lambda_with_return_type -> "|x|: int32 { x }",
+    block -> "{ a++; a }",
+    labeled_arguments -> "draw(10, y: 20, color: rgb(r: 1, g: 2, b: 3))"
+}
+
+#[test]
+fn call_location_is_merge_of_callee_and_closing_parenthesis() {
+    use stellar_ast::Expression;
+    use stellar_diagnostics::Diagnostics;
+    use stellar_filesystem::location::{ByteOffset, Location};
+    use stellar_interner::DUMMY_PATH_ID;
+    use stellar_parser::parse_expression;
+
+    let source = "foo.bar(1, 2 + 3)";
+    let mut diagnostics = Diagnostics::new();
+
+    let Some(Expression::Call {
+        location, callee, ..
+    }) = parse_expression(DUMMY_PATH_ID, source, &mut diagnostics)
+    else {
+        panic!("expected a call expression");
+    };
+
+    let closing_parenthesis = Location {
+        filepath: DUMMY_PATH_ID,
+        start: ByteOffset(source.len() - 1),
+        end: ByteOffset(source.len()),
+    };
+
+    assert_eq!(location, callee.location().merge(closing_parenthesis));
+    assert_eq!(callee.location().end, ByteOffset("foo.bar".len()));
+    assert_eq!(location.end, ByteOffset(source.len()));
+}
+
+#[test]
+fn labeled_call_arguments() {
+    use stellar_ast::{CallArgument, Expression};
+    use stellar_diagnostics::Diagnostics;
+    use stellar_interner::{IdentifierId, DUMMY_PATH_ID};
+    use stellar_parser::parse_expression;
+
+    let mut diagnostics = Diagnostics::new();
+
+    let Some(Expression::Call { arguments, .. }) =
+        parse_expression(DUMMY_PATH_ID, "draw(x, y: 20, z)", &mut diagnostics)
+    else {
+        panic!("expected a call expression");
+    };
+
+    let labels = arguments
+        .iter()
+        .map(|CallArgument { label, .. }| label.map(|label| label.id))
+        .collect::<Vec<_>>();
+
+    assert_eq!(labels, [None, Some(IdentifierId::from("y")), None]);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn spread_call_argument() {
+    use stellar_ast::{CallArgument, Expression};
+    use stellar_diagnostics::Diagnostics;
+    use stellar_interner::DUMMY_PATH_ID;
+    use stellar_parser::parse_expression;
+
+    let source = "f(1, ..args)";
+    let mut diagnostics = Diagnostics::new();
+
+    let Some(Expression::Call { arguments, .. }) =
+        parse_expression(DUMMY_PATH_ID, source, &mut diagnostics)
+    else {
+        panic!("expected a call expression");
+    };
+
+    let [first, CallArgument {
+        spread: Some(spread),
+        ..
+    }] = &arguments[..]
+    else {
+        panic!("expected a spread argument");
+    };
+
+    assert_eq!(first.spread, None);
+    assert_eq!(&source[spread.start.0..spread.end.0], "..");
+    assert_eq!(
+        &source[arguments[1].location().start.0..arguments[1].location().end.0],
+        "..args"
+    );
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn match_item_guard() {
+    use stellar_ast::Expression;
+    use stellar_diagnostics::Diagnostics;
+    use stellar_interner::DUMMY_PATH_ID;
+    use stellar_parser::parse_expression;
+
+    let source = "match x { Some(y) if y > 0 -> y, _ -> 0 }";
+    let mut diagnostics = Diagnostics::new();
+
+    let Some(Expression::Match { block, .. }) =
+        parse_expression(DUMMY_PATH_ID, source, &mut diagnostics)
+    else {
+        panic!("expected a match expression");
+    };
+
+    assert!(diagnostics.is_empty());
+    assert!(matches!(block[0].guard, Some(Expression::Binary { .. })));
+    assert!(matches!(block[0].right, Expression::Identifier(_)));
+    assert_eq!(block[1].guard, None);
+}
+
+#[test]
+fn numeric_literals_keep_original_spelling() {
+    use stellar_ast::{Expression, Literal};
+    use stellar_diagnostics::Diagnostics;
+    use stellar_interner::DUMMY_PATH_ID;
+    use stellar_parser::parse_expression;
+
+    let mut diagnostics = Diagnostics::new();
+    let mut parse = |source| parse_expression(DUMMY_PATH_ID, source, &mut diagnostics);
+
+    let Some(Expression::Literal(literal @ Literal::Integer { value: 255, .. })) = parse("0xFF")
+    else {
+        panic!("expected an integer literal");
+    };
+    assert_eq!(literal.to_string(), "0xFF");
+
+    for source in ["1_000u32", "0b1010", "0o17", "1e3", "2.5f32"] {
+        let Some(Expression::Literal(literal)) = parse(source) else {
+            panic!("expected a literal");
+        };
+        assert_eq!(literal.to_string(), source);
+    }
+
+    assert!(matches!(
+        parse("0b1010"),
+        Some(Expression::Literal(Literal::Integer { value: 10, .. }))
+    ));
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn numeric_literal_suffixes() {
+    use stellar_ast::{Expression, Literal, NumericLiteralSuffix};
+    use stellar_diagnostics::Diagnostics;
+    use stellar_interner::DUMMY_PATH_ID;
+    use stellar_parser::parse_expression;
+
+    let mut diagnostics = Diagnostics::new();
+    let mut parse = |source| parse_expression(DUMMY_PATH_ID, source, &mut diagnostics);
+
+    assert!(matches!(
+        parse("255u8"),
+        Some(Expression::Literal(Literal::Integer {
+            value: 255,
+            suffix: Some(NumericLiteralSuffix::U8),
+            ..
+        }))
+    ));
+    assert!(matches!(
+        parse("1_000i64"),
+        Some(Expression::Literal(Literal::Integer {
+            value: 1000,
+            suffix: Some(NumericLiteralSuffix::I64),
+            ..
+        }))
+    ));
+    assert!(matches!(
+        parse("2.5f32"),
+        Some(Expression::Literal(Literal::Float {
+            suffix: Some(NumericLiteralSuffix::F32),
+            ..
+        }))
+    ));
+    assert!(matches!(
+        parse("3f64"),
+        Some(Expression::Literal(Literal::Float {
+            suffix: Some(NumericLiteralSuffix::F64),
+            ..
+        }))
+    ));
+    assert!(matches!(
+        parse("7"),
+        Some(Expression::Literal(Literal::Integer { suffix: None, .. }))
+    ));
+
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn block_tail_expression() {
+    use stellar_ast::{Expression, Statement};
+    use stellar_diagnostics::Diagnostics;
+    use stellar_interner::DUMMY_PATH_ID;
+    use stellar_parser::parse_expression;
+
+    let mut diagnostics = Diagnostics::new();
+    let mut parse = |source| parse_expression(DUMMY_PATH_ID, source, &mut diagnostics);
+
+    let Some(Expression::StatementsBlock { block, .. }) = parse("{ let b = 1; b }") else {
+        panic!("expected block expression");
+    };
+
+    assert!(matches!(block.statements[..], [Statement::Let { .. }]));
+    assert!(matches!(
+        block.tail_expression.as_deref(),
+        Some(Expression::Identifier(_))
+    ));
+
+    let Some(Expression::StatementsBlock { block, .. }) = parse("{ let b = 1; b; }") else {
+        panic!("expected block expression");
+    };
+
+    assert!(matches!(
+        block.statements[..],
+        [
+            Statement::Let { .. },
+            Statement::Expression {
+                has_semicolon: true,
+                ..
+            }
+        ]
+    ));
+    assert_eq!(block.tail_expression, None);
+
+    let Some(Expression::StatementsBlock { block, .. }) = parse("{ if a { b } c; if d { e } }")
+    else {
+        panic!("expected block expression");
+    };
+
+    assert_eq!(block.statements.len(), 2);
+    assert!(matches!(
+        block.tail_expression.as_deref(),
+        Some(Expression::If { .. })
+    ));
+
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn missing_semicolon_in_the_middle_of_block() {
+    use stellar_diagnostics::Diagnostics;
+    use stellar_interner::DUMMY_PATH_ID;
+    use stellar_parser::parse_expression;
+
+    let mut diagnostics = Diagnostics::new();
+
+    assert_eq!(
+        parse_expression(DUMMY_PATH_ID, "{ a() b() }", &mut diagnostics),
+        None
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics.all()[0].code.as_deref(), Some("E014"));
+    assert_eq!(
+        diagnostics.all()[0].message,
+        "expected `;` after expression"
+    );
+}
+
+#[test]
+fn binary_operators_follow_precedence_table() {
+    use stellar_ast::{
+        precedence::{precedence, Associativity},
+        Expression, RawBinaryOperator,
+    };
+    use stellar_diagnostics::Diagnostics;
+    use stellar_interner::DUMMY_PATH_ID;
+    use stellar_parser::parse_expression;
+
+    let operators = [
+        "+", "+=", "-", "-=", "*", "*=", "**", "/", "/=", "%", "%=", "<<", ">>", "==", "!=", "<",
+        "<=", ">", ">=", "=", "&", "&=", "|", "|=", "&&", "||",
+    ]
+    .map(|operator| operator.parse::<RawBinaryOperator>().unwrap());
+
+    for first in operators {
+        for second in operators {
+            let source = format!("a {first} b {second} c");
+            let mut diagnostics = Diagnostics::new();
+
+            let Some(Expression::Binary {
+                left,
+                right,
+                operator,
+                ..
+            }) = parse_expression(DUMMY_PATH_ID, &source, &mut diagnostics)
+            else {
+                panic!("expected a binary expression in `{source}`");
+            };
+
+            assert!(diagnostics.is_empty(), "`{source}`");
+
+            let (first_precedence, associativity) = precedence(first);
+            let (second_precedence, _) = precedence(second);
+
+            let groups_left = first_precedence > second_precedence
+                || (first_precedence == second_precedence && associativity == Associativity::Left);
+
+            if groups_left {
+                assert_eq!(operator.raw, second, "`{source}`");
+                assert!(
+                    matches!(*left, Expression::Binary { operator, .. } if operator.raw == first),
+                    "`{source}`"
+                );
+            } else {
+                assert_eq!(operator.raw, first, "`{source}`");
+                assert!(
+                    matches!(*right, Expression::Binary { operator, .. } if operator.raw == second),
+                    "`{source}`"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn operator_locations_cover_only_operators() {
+    use stellar_ast::Expression;
+    use stellar_diagnostics::Diagnostics;
+    use stellar_interner::DUMMY_PATH_ID;
+    use stellar_parser::parse_expression;
+
+    let source = "-a  +  b?";
+    let mut diagnostics = Diagnostics::new();
+
+    let Some(Expression::Binary {
+        left,
+        right,
+        operator,
+        ..
+    }) = parse_expression(DUMMY_PATH_ID, source, &mut diagnostics)
+    else {
+        panic!("expected a binary expression");
+    };
+    assert_eq!(&source[operator.location], "+");
+
+    let Expression::Prefix { operator, .. } = *left else {
+        panic!("expected a prefix expression");
+    };
+    assert_eq!(&source[operator.location], "-");
+
+    let Expression::Postfix { operator, .. } = *right else {
+        panic!("expected a postfix expression");
+    };
+    assert_eq!(&source[operator.location], "?");
 }
@@ -0,0 +1,74 @@
+use stellar_ast::{EnumItem, Expression, ModuleItem, Visibility};
+use stellar_diagnostics::Diagnostics;
+use stellar_interner::DUMMY_PATH_ID;
+use stellar_parser::parse_item;
+
+mod macros;
+
+tests_using! {
+    parse_item,
+    struct_ -> "struct Point { x: int32, y: int32 }",
+    struct_with_public_fields -> "pub struct Point { pub x: int32, pub y: int32 }",
+    struct_with_methods -> "struct Point { x: int32, fun new() {} pub fun get() {} }",
+    struct_with_public_field_and_method -> "struct Point { pub x: int32, pub fun x() {} }",
+    tuple_like_struct -> "pub struct Meters(pub float64);",
+    function_with_default_parameter -> "fun greet(name: String, punctuation: String = \"!\") {}",
+    enum_with_explicit_discriminants -> "enum Flag { A = 1, B = 2, C = 4 }",
+    enum_with_mixed_discriminants -> "enum Flag { A, B = 2, C, D = B.value() + 1 }"
+}
+
+#[test]
+fn public_fields_are_not_methods() {
+    let mut diagnostics = Diagnostics::new();
+    let Some(ModuleItem::Struct(struct_)) = parse_item(
+        DUMMY_PATH_ID,
+        "struct Point { pub x: int32, y: int32, pub fun x() {} }",
+        &mut diagnostics,
+    ) else {
+        panic!("expected a struct");
+    };
+
+    assert!(diagnostics.is_ok());
+    assert!(matches!(
+        struct_.fields[0].visibility,
+        Visibility::Public(_)
+    ));
+    assert_eq!(struct_.fields[1].visibility, Visibility::Private);
+    assert_eq!(struct_.methods.len(), 1);
+}
+
+#[test]
+fn enum_item_discriminants() {
+    let mut diagnostics = Diagnostics::new();
+    let Some(ModuleItem::Enum(enum_)) = parse_item(
+        DUMMY_PATH_ID,
+        "enum Flag { A = 1, B, C(int32), D = 1 << 3 }",
+        &mut diagnostics,
+    ) else {
+        panic!("expected an enum");
+    };
+
+    assert!(diagnostics.is_ok());
+    assert!(matches!(
+        &enum_.items[0],
+        EnumItem::Just {
+            discriminant: Some(Expression::Literal(_)),
+            ..
+        }
+    ));
+    assert!(matches!(
+        &enum_.items[1],
+        EnumItem::Just {
+            discriminant: None,
+            ..
+        }
+    ));
+    assert!(matches!(&enum_.items[2], EnumItem::TupleLike { .. }));
+    assert!(matches!(
+        &enum_.items[3],
+        EnumItem::Just {
+            discriminant: Some(Expression::Binary { .. }),
+            ..
+        }
+    ));
+}
@@ -0,0 +1,77 @@
+use std::time::{Duration, Instant};
+
+use stellar_database::{Config, PackageData, State};
+use stellar_diagnostics::Diagnostics;
+use stellar_interner::{DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::{parse_module, parse_type, parse_type_using, ParseState};
+
+fn nested_type(depth: usize) -> String {
+    format!("{}int32{}", "List[".repeat(depth), "]".repeat(depth))
+}
+
+fn error_codes(diagnostics: &Diagnostics) -> Vec<&str> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.code.as_deref().unwrap())
+        .collect()
+}
+
+#[test]
+fn deeply_nested_type() {
+    let mut diagnostics = Diagnostics::new();
+    let now = Instant::now();
+
+    let ty = parse_type(DUMMY_PATH_ID, nested_type(10_000), &mut diagnostics);
+
+    assert!(now.elapsed() < Duration::from_secs(10));
+    assert!(ty.is_none());
+    assert_eq!(error_codes(&diagnostics), ["E006"]);
+}
+
+#[test]
+fn type_nesting_within_limit() {
+    let mut diagnostics = Diagnostics::new();
+
+    assert!(parse_type(DUMMY_PATH_ID, nested_type(100), &mut diagnostics).is_some());
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn custom_type_nesting_limit() {
+    let mut diagnostics = Diagnostics::new();
+    let source = nested_type(3);
+
+    let mut state =
+        ParseState::new(DUMMY_PATH_ID, &source, &mut diagnostics).with_max_type_nesting(3);
+
+    assert!(parse_type_using(&mut state).is_none());
+    assert_eq!(error_codes(&diagnostics), ["E006"]);
+}
+
+#[test]
+fn identifier_too_long() {
+    let mut state = State::new().with_config(Config::new().with_max_identifier_length(8));
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let source = "fun short() {}\nfun too_long_name() {}";
+
+    let _ = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source,
+    );
+
+    // The name is not tokenized as an identifier, so it is also reported as
+    // an unexpected token.
+    let diagnostic = state
+        .diagnostics()
+        .iter()
+        .find(|diagnostic| diagnostic.code.as_deref() == Some("E012"))
+        .unwrap();
+
+    assert_eq!(
+        &source[diagnostic.labels[0].location.start.0..diagnostic.labels[0].location.end.0],
+        "too_long_name"
+    );
+}
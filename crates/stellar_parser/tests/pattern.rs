@@ -7,10 +7,15 @@ tests_using! {
     literal -> "3",
     identifier1 -> "foo",
     identifier2 -> "foo @ [1, ..]",
+    mutable_identifier1 -> "mut foo",
+    mutable_identifier2 -> "mut foo @ [1, ..]",
+    mutable_identifier_in_tuple -> "(a, mut b)",
     tuple -> "(1, 2, _)",
     tuple2 -> "(1,)",
     tuple3 -> "(..)",
     list -> "[1, .., 3]",
+    list_with_rest_binding -> "[head, ..tail]",
+    tuple_with_rest_in_the_middle -> "(first, .., last)",
     struct_ -> "Person { name: \"John\", age, .. }",
     or -> "Some(_) | None"
 }
@@ -0,0 +1,60 @@
+mod macros;
+
+tests_using! {
+    parse_statement,
+    let_ -> "let x = 1;",
+    let_with_type -> "let x: int32 = 1;",
+    let_mut -> "let mut x = 1;",
+    let_else -> "let Some(x) = y else { return 0; };",
+    let_else_with_if -> "let Some(x) = if a { b } else { c } else { return 0; };",
+    break_ -> "break;",
+    break_with_value -> "break @outer x + 1;",
+    continue_ -> "continue;",
+    defer -> "defer foo();",
+    return_ -> "return 1;"
+}
+
+/// Parses a source and returns codes of the emitted diagnostics.
+fn error_codes(
+    source: &str,
+    parse: fn(&str, &mut stellar_diagnostics::Diagnostics),
+) -> Vec<String> {
+    let mut diagnostics = stellar_diagnostics::Diagnostics::new();
+    parse(source, &mut diagnostics);
+
+    diagnostics
+        .iter()
+        .filter_map(|diagnostic| diagnostic.code.clone())
+        .collect()
+}
+
+fn statement(source: &str, diagnostics: &mut stellar_diagnostics::Diagnostics) {
+    assert!(
+        stellar_parser::parse_statement(stellar_interner::DUMMY_PATH_ID, source, diagnostics)
+            .is_none()
+    );
+}
+
+fn block(source: &str, diagnostics: &mut stellar_diagnostics::Diagnostics) {
+    let _ = stellar_parser::parse_expression(stellar_interner::DUMMY_PATH_ID, source, diagnostics);
+}
+
+#[test]
+fn no_semicolon_after_let() {
+    assert_eq!(error_codes("let x = 1", statement), ["E013"]);
+    assert_eq!(error_codes("return 1 }", statement), ["E013"]);
+}
+
+#[test]
+fn statements_on_the_same_line() {
+    assert_eq!(error_codes("{ let a = 1 let b = 2; }", block), ["E013"]);
+    assert_eq!(error_codes("{ a() b(); }", block), ["E014"]);
+}
+
+#[test]
+fn empty_statements() {
+    assert_eq!(error_codes("{ a();; }", block), ["W011"]);
+    assert_eq!(error_codes("{ ; a() }", block), ["W011"]);
+    assert!(error_codes("{ if a {}; match b {}; }", block).is_empty());
+    assert_eq!(error_codes("{ if a {};; }", block), ["W011"]);
+}
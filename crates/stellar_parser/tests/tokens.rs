@@ -0,0 +1,208 @@
+use stellar_ast::token::{Keyword, Punctuator, RawLexError, RawToken};
+use stellar_interner::DUMMY_PATH_ID;
+use stellar_parser::{tokenize, TokenWithTrivia, TriviaKind};
+
+const FIXTURE: &str = "//! Geometry primitives.
+
+/// A point on a plane.
+pub struct Point { x: float64, y: float64 } // TODO: add `z`
+
+fun main() {\r
+    let message = \"bad \\q escape\";
+    let c = 'ab';
+    let ١ = 1; // unexpected character
+    println(message);
+}
+// trailing comment
+";
+
+fn reconstruct(source: &str, tokens: &[TokenWithTrivia]) -> String {
+    let mut text = String::new();
+
+    for token in tokens {
+        for trivia in &token.leading_trivia {
+            text.push_str(&source[trivia.location]);
+        }
+
+        text.push_str(&source[token.token.location]);
+    }
+
+    text
+}
+
+fn raw_tokens(tokens: &[TokenWithTrivia]) -> Vec<RawToken> {
+    tokens.iter().map(|token| token.token.raw).collect()
+}
+
+#[test]
+fn lossless_round_trip() {
+    let tokens = tokenize(DUMMY_PATH_ID, FIXTURE);
+
+    assert_eq!(reconstruct(FIXTURE, &tokens), FIXTURE);
+
+    // Tokens follow each other without gaps and overlaps.
+    let mut position = 0;
+
+    for token in &tokens {
+        for trivia in &token.leading_trivia {
+            assert_eq!(trivia.location.start.0, position);
+            position = trivia.location.end.0;
+        }
+
+        assert_eq!(token.token.location.start.0, position);
+        position = token.token.location.end.0;
+    }
+
+    assert_eq!(position, FIXTURE.len());
+}
+
+#[test]
+fn comments_and_whitespace_are_trivia() {
+    let tokens = tokenize(DUMMY_PATH_ID, FIXTURE);
+    let raw = raw_tokens(&tokens);
+
+    assert_eq!(raw[0], RawToken::GlobalDocComment);
+    assert_eq!(raw[1], RawToken::LocalDocComment);
+    assert_eq!(raw[2], RawToken::Keyword(Keyword::Pub));
+    assert!(!raw.contains(&RawToken::Comment));
+
+    let fun = tokens
+        .iter()
+        .find(|token| token.token.raw == RawToken::Keyword(Keyword::Fun))
+        .unwrap();
+    let trivia = fun
+        .leading_trivia
+        .iter()
+        .map(|trivia| (trivia.kind, &FIXTURE[trivia.location]))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        trivia,
+        [
+            (TriviaKind::Whitespace, " "),
+            (TriviaKind::Comment, "// TODO: add `z`"),
+            (TriviaKind::Newline, "\n"),
+            (TriviaKind::Newline, "\n"),
+        ]
+    );
+
+    let open_brace = tokens
+        .iter()
+        .position(|token| token.token.raw == RawToken::Keyword(Keyword::Fun))
+        .unwrap()
+        + 4;
+    let trivia = tokens[open_brace + 1]
+        .leading_trivia
+        .iter()
+        .map(|trivia| trivia.kind)
+        .collect::<Vec<_>>();
+
+    assert_eq!(raw[open_brace], RawToken::Punctuator(Punctuator::OpenBrace));
+    assert_eq!(trivia, [TriviaKind::Newline, TriviaKind::Whitespace]);
+    assert_eq!(
+        &FIXTURE[tokens[open_brace + 1].leading_trivia[0].location],
+        "\r\n"
+    );
+}
+
+#[test]
+fn lex_errors_do_not_stop_tokenization() {
+    let tokens = tokenize(DUMMY_PATH_ID, FIXTURE);
+    let raw = raw_tokens(&tokens);
+    let errors = tokens
+        .iter()
+        .filter_map(|token| match token.token.raw {
+            RawToken::Error(error) => Some((error, &FIXTURE[token.token.location])),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        [
+            (RawLexError::UnknownEscapeSequence, "\"bad \\q"),
+            // The lexer continues after the escape sequence, so the closing
+            // quote starts a new string literal.
+            (RawLexError::UnterminatedStringLiteral, "\";"),
+            (RawLexError::MoreThanOneCharInCharLiteral, "'ab'"),
+            (RawLexError::UnexpectedChar, "١"),
+        ]
+    );
+
+    let eof = tokens.last().unwrap();
+
+    assert_eq!(
+        raw[raw.len() - 2],
+        RawToken::Punctuator(Punctuator::CloseBrace)
+    );
+    assert_eq!(eof.token.raw, RawToken::EndOfFile);
+    assert_eq!(eof.token.location.start.0, FIXTURE.len());
+    assert_eq!(eof.token.location.end.0, FIXTURE.len());
+    assert_eq!(
+        eof.leading_trivia
+            .iter()
+            .map(|trivia| trivia.kind)
+            .collect::<Vec<_>>(),
+        [
+            TriviaKind::Newline,
+            TriviaKind::Comment,
+            TriviaKind::Newline
+        ]
+    );
+}
+
+#[test]
+fn empty_source() {
+    let tokens = tokenize(DUMMY_PATH_ID, "");
+
+    assert_eq!(raw_tokens(&tokens), [RawToken::EndOfFile]);
+    assert!(tokens[0].leading_trivia.is_empty());
+}
+
+#[test]
+fn operators_map_to_tokens_and_back() {
+    use stellar_ast::{RawBinaryOperator, RawPostfixOperator, RawPrefixOperator};
+
+    fn check<T: Copy + std::fmt::Debug + std::fmt::Display + PartialEq>(
+        operators: &[T],
+        to_token: fn(T) -> RawToken,
+        from_token: fn(RawToken) -> Option<T>,
+    ) {
+        let mut tokens = Vec::new();
+
+        for &operator in operators {
+            let source = operator.to_string();
+            let lexed = raw_tokens(&tokenize(DUMMY_PATH_ID, &source));
+
+            assert_eq!(lexed[0], to_token(operator), "`{source}`");
+            assert_eq!(from_token(lexed[0]), Some(operator), "`{source}`");
+            assert!(!tokens.contains(&lexed[0]), "`{source}` shares its token");
+            tokens.push(lexed[0]);
+        }
+    }
+
+    check(
+        RawBinaryOperator::ALL,
+        RawBinaryOperator::to_token,
+        RawBinaryOperator::from_token,
+    );
+    check(
+        RawPrefixOperator::ALL,
+        RawPrefixOperator::to_token,
+        RawPrefixOperator::from_token,
+    );
+    check(
+        RawPostfixOperator::ALL,
+        RawPostfixOperator::to_token,
+        RawPostfixOperator::from_token,
+    );
+
+    assert_eq!(
+        RawBinaryOperator::from_token(RawToken::Punctuator(Punctuator::Dot)),
+        None
+    );
+    assert_eq!(
+        RawPrefixOperator::from_token(RawToken::Keyword(Keyword::As)),
+        None
+    );
+}
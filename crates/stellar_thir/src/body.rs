@@ -0,0 +1,315 @@
+//! Defines [`Body`] - a typed representation of a function body.
+//!
+//! Compared to HIR, every expression stores its type and names of global
+//! definitions are resolved into symbols, e.g. `io.println` in
+//! `io.println("hello")` becomes a single [`ExpressionKind::Symbol`] node.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use stellar_ast::{BinaryOperator, IdentifierAST, PostfixOperator, PrefixOperator};
+use stellar_database::{ty::Type, FunctionId, Symbol};
+use stellar_filesystem::location::Location;
+use stellar_hir::{Literal, LoopKind, Pattern};
+use stellar_interner::IdentifierId;
+
+/// A typed function body.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Body {
+    /// The function, that the body belongs to.
+    pub function: FunctionId,
+
+    /// The return type of the function.
+    pub return_type: Type,
+
+    pub statements: Vec<Statement>,
+}
+
+/// A typed expression.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Expression {
+    pub location: Location,
+
+    /// The type of the expression, or [`Type::Unknown`] if it cannot be inferred.
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub ty: Type,
+
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub kind: ExpressionKind,
+}
+
+/// A kind of typed expression.
+///
+/// See [`stellar_hir::Expression`] for more details about each expression.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+pub enum ExpressionKind {
+    /// List expression, e.g. `[1, 2, 3]`.
+    #[cfg_attr(feature = "serde", serde(rename = "list_expression"))]
+    List { elements: Vec<Expression> },
+
+    /// As expression, e.g. `a as float32`. The target type is the type of
+    /// the expression.
+    #[cfg_attr(feature = "serde", serde(rename = "as_expression"))]
+    As { left: Box<Expression> },
+
+    /// Binary expression, e.g. `1 + 2`.
+    #[cfg_attr(feature = "serde", serde(rename = "binary_expression"))]
+    Binary {
+        left: Box<Expression>,
+        operator: BinaryOperator,
+        right: Box<Expression>,
+    },
+
+    /// Block expression, e.g. `{ let b = 1; b }`.
+    #[cfg_attr(feature = "serde", serde(rename = "block_expression"))]
+    StatementsBlock { block: Vec<Statement> },
+
+    /// Literal expression, e.g. `true`, `\"hello\"`, `1.2`.
+    #[cfg_attr(feature = "serde", serde(rename = "literal_expression"))]
+    Literal { literal: Literal },
+
+    /// Local variable, e.g. `x` in `let x = 1; x`.
+    #[cfg_attr(feature = "serde", serde(rename = "local_expression"))]
+    Local { name: IdentifierAST },
+
+    /// Global definition, e.g. `foo` or `std.io.println`.
+    #[cfg_attr(feature = "serde", serde(rename = "symbol_expression"))]
+    Symbol { symbol: Symbol },
+
+    /// Name, that cannot be resolved. Errors about such names are reported
+    /// by name resolution.
+    #[cfg_attr(feature = "serde", serde(rename = "unresolved_expression"))]
+    Unresolved { name: IdentifierAST },
+
+    /// Underscore expression, e.g. `_`.
+    #[cfg_attr(feature = "serde", serde(rename = "underscore_expression"))]
+    Underscore,
+
+    /// If expression, e.g. `if x { ... } else { ... }`.
+    #[cfg_attr(feature = "serde", serde(rename = "if_expression"))]
+    If {
+        if_blocks: Vec<(Expression, Vec<Statement>)>,
+        r#else: Option<Vec<Statement>>,
+    },
+
+    /// Field access expression, e.g. `x.y`.
+    #[cfg_attr(feature = "serde", serde(rename = "field_access_expression"))]
+    FieldAccess {
+        left: Box<Expression>,
+        right: IdentifierAST,
+    },
+
+    /// Tuple index expression, e.g. `x.0`.
+    #[cfg_attr(feature = "serde", serde(rename = "tuple_index_expression"))]
+    TupleIndex { left: Box<Expression>, index: u32 },
+
+    /// Assignment expression, e.g. `x = 1`.
+    #[cfg_attr(feature = "serde", serde(rename = "assign_expression"))]
+    Assign {
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+
+    /// Index expression, e.g. `xs[0]`.
+    #[cfg_attr(feature = "serde", serde(rename = "index_expression"))]
+    Index {
+        left: Box<Expression>,
+        index: Box<Expression>,
+    },
+
+    /// Prefix expression, e.g. `!false`, `++a`.
+    #[cfg_attr(feature = "serde", serde(rename = "prefix_expression"))]
+    Prefix {
+        inner: Box<Expression>,
+        operator: PrefixOperator,
+    },
+
+    /// Postfix expression, e.g. `safe_div(1, 0)?`, `a++`.
+    #[cfg_attr(feature = "serde", serde(rename = "postfix_expression"))]
+    Postfix {
+        inner: Box<Expression>,
+        operator: PostfixOperator,
+    },
+
+    /// While expression, e.g. `while x != 0 {}`, or loop expression, e.g.
+    /// `loop { break 42; }`.
+    #[cfg_attr(feature = "serde", serde(rename = "while_expression"))]
+    While {
+        #[cfg_attr(feature = "serde", serde(rename = "loop_kind"))]
+        kind: LoopKind,
+
+        /// Label of the loop, e.g. `outer` in `@outer: while x != 0 { ... }`.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        label: Option<IdentifierAST>,
+        condition: Box<Expression>,
+        statements_block: Vec<Statement>,
+    },
+
+    /// Call expression, e.g. `s.to_string()`.
+    #[cfg_attr(feature = "serde", serde(rename = "call_expression"))]
+    Call {
+        callee: Box<Expression>,
+
+        /// Arguments in the order, in which they are written, so that they
+        /// are evaluated in the same order.
+        arguments: Vec<CallArgument>,
+    },
+
+    /// Type arguments expression, e.g. `A[int32]`.
+    #[cfg_attr(feature = "serde", serde(rename = "type_arguments_expression"))]
+    TypeArguments {
+        left: Box<Expression>,
+        type_arguments: Vec<Type>,
+    },
+
+    /// Tuple expression, e.g. `(a, 32, \"hello\")`.
+    #[cfg_attr(feature = "serde", serde(rename = "tuple_expression"))]
+    Tuple { elements: Vec<Expression> },
+
+    /// Struct expression, e.g. `Person { name: \"John\", age: 25 }`.
+    #[cfg_attr(feature = "serde", serde(rename = "struct_expression"))]
+    Struct {
+        left: Box<Expression>,
+        fields: Vec<StructExpressionItem>,
+
+        /// Base of the struct update syntax, e.g. `other` in `Point { x: 1, ..other }`.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        base: Option<Box<Expression>>,
+    },
+
+    /// Match expression (`match fs.read_file(...) { ... }`).
+    #[cfg_attr(feature = "serde", serde(rename = "match_expression"))]
+    Match {
+        expression: Box<Expression>,
+        block: Vec<MatchExpressionItem>,
+    },
+
+    /// Lambda expression (`|x| { x + 1 }`).
+    #[cfg_attr(feature = "serde", serde(rename = "lambda_expression"))]
+    Lambda {
+        parameters: Vec<LambdaFunctionParameter>,
+        value: Box<Expression>,
+
+        /// Names of local variables of enclosing scopes, that are used in
+        /// the lambda.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        captures: Vec<IdentifierId>,
+    },
+}
+
+/// A lambda function parameter, e.g. `x` in `|x| { x + 1 }`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LambdaFunctionParameter {
+    pub name: IdentifierAST,
+
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub ty: Type,
+}
+
+/// A match expression item - `pattern` (`if` `guard`)? `->` `expression`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MatchExpressionItem {
+    pub left: Pattern,
+
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub guard: Option<Expression>,
+
+    pub right: Expression,
+}
+
+/// An argument of a call expression, e.g. `y: 20` in `draw(10, y: 20)`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CallArgument {
+    /// Name of the parameter, that the argument is passed to.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub label: Option<IdentifierAST>,
+
+    /// Whether elements of the tuple are passed as separate arguments,
+    /// e.g. `..args` in `f(..args)`.
+    pub spread: bool,
+
+    pub value: Expression,
+}
+
+/// A field item in a struct expression, e.g. `name: "John"` in
+/// `Person { name: "John", age }`. Shorthand fields, e.g. `age`, are
+/// lowered into `age: age`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StructExpressionItem {
+    pub name: IdentifierAST,
+    pub value: Expression,
+}
+
+/// A typed statement.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+pub enum Statement {
+    /// Defer statement - `defer <expr>;`, e.g. `defer file.close()`.
+    #[cfg_attr(feature = "serde", serde(rename = "defer_statement"))]
+    Defer {
+        location: Location,
+        call: Expression,
+    },
+
+    /// Expression statement, e.g. `call();`.
+    #[cfg_attr(feature = "serde", serde(rename = "expression_statement"))]
+    Expression {
+        expression: Expression,
+        has_semicolon: bool,
+    },
+
+    /// Break statement - `break;`, `break @outer;` or `break 42;`.
+    #[cfg_attr(feature = "serde", serde(rename = "break_statement"))]
+    Break {
+        location: Location,
+
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        label: Option<IdentifierAST>,
+
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        value: Option<Expression>,
+    },
+
+    /// Continue statement - `continue;` or `continue @outer;`.
+    #[cfg_attr(feature = "serde", serde(rename = "continue_statement"))]
+    Continue {
+        location: Location,
+
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        label: Option<IdentifierAST>,
+    },
+
+    /// Return statement - `return <expr>;`, e.g. `return 42;`.
+    #[cfg_attr(feature = "serde", serde(rename = "return_statement"))]
+    Return {
+        location: Location,
+        expression: Expression,
+    },
+
+    /// Let statement - `let <pattern> = <expr>;`, e.g. `let x = 1`.
+    #[cfg_attr(feature = "serde", serde(rename = "let_statement"))]
+    Let {
+        location: Location,
+        pattern: Pattern,
+        value: Expression,
+
+        /// The type of bound values: the annotated type if it exists,
+        /// otherwise the type of `value`.
+        #[cfg_attr(feature = "serde", serde(rename = "type"))]
+        ty: Type,
+
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        r#else: Option<Vec<Self>>,
+    },
+}
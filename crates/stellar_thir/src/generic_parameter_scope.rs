@@ -54,24 +54,23 @@ impl GenericParameterScope {
     /// [`contains()`]: GenericParameterScope::contains
     #[must_use]
     pub fn resolve(&self, parameter_name: IdentifierId) -> Option<&GenericParameterData> {
-        if let Some(data) = self.parameters.get(&parameter_name) {
-            Some(data)
-        } else if let Some(parent_scope) = &self.parent_scope {
-            parent_scope.resolve(parameter_name)
-        } else {
-            None
+        let mut scope = Some(self);
+
+        while let Some(current) = scope {
+            if let Some(data) = current.parameters.get(&parameter_name) {
+                return Some(data);
+            }
+
+            scope = current.parent_scope.as_deref();
         }
+
+        None
     }
 
     /// Checks if the generic parameter exists in the scope.
     #[must_use]
     pub fn contains(&self, parameter_name: IdentifierId) -> bool {
-        self.parameters.contains_key(&parameter_name)
-            || if let Some(parent_scope) = &self.parent_scope {
-                parent_scope.contains(parameter_name)
-            } else {
-                false
-            }
+        self.resolve(parameter_name).is_some()
     }
 }
 
@@ -65,10 +65,10 @@ use serde::{Deserialize, Serialize};
 use stellar_ast::{IdentifierAST, Literal, Visibility};
 use stellar_filesystem::location::Location;
 use stellar_fx_hash::FxHashMap;
-use stellar_hir as _;
 use stellar_interner::IdentifierId;
 use ty::{Type, TypeConstructor};
 
+pub mod body;
 pub mod generic_parameter_scope;
 pub mod ty;
 
@@ -340,10 +340,11 @@ impl Expression {
     }
 }
 
-/// A match expression item - `pattern` `=>` `expression`.
+/// A match expression item - `pattern` (`if` `guard`)? `->` `expression`.
 #[derive(Debug, PartialEq, Clone)]
 pub struct MatchExpressionItem {
     pub left: Pattern,
+    pub guard: Option<Expression>,
     pub right: Expression,
 }
 
@@ -548,8 +549,8 @@ impl From<&stellar_hir::Path> for Path {
 /// use stellar_thir::{path, Path};
 /// use stellar_interner::IdentifierId;
 ///
-/// let a = IdentifierId(2);
-/// let b = IdentifierId(3);
+/// let a = IdentifierId::from("a");
+/// let b = IdentifierId::from("b");
 /// assert_eq!(path!(a, b), Path { identifiers: vec![a, b] });
 /// ```
 #[macro_export]
@@ -1,34 +1,66 @@
 use itertools::Itertools;
-use stellar_ast::{IdentifierAST, ModuleItemKind};
+use stellar_ast::{BinaryOperator, IdentifierAST, ModuleItemKind};
+use stellar_database::SymbolKind;
 use stellar_diagnostics::{
     define_diagnostics,
-    diagnostic::{Diagnostic, Label},
+    diagnostic::{Diagnostic, Label, Severity},
+    registry::DiagnosticInfo,
     BuildDiagnostic,
 };
-use stellar_english_commons::pluralize::PluralizeExt;
+use stellar_english_commons::{
+    article::with_article,
+    enumeration::{all_of, one_of},
+    pluralize::PluralizeExt,
+};
 use stellar_filesystem::location::Location;
-use stellar_interner::PathId;
+use stellar_interner::{IdentifierId, PathId};
 
 define_diagnostics! {
+    implemented_manually {
+        CycleDetectedWhenComputingSignatureOf,
+        MissingInterfaceMethod,
+        FailedToResolveEnumItem,
+        UndeclaredLoopLabel,
+        UnknownArgumentLabel,
+        UnknownField,
+        FailedToResolveType,
+        NonExhaustiveMatch,
+        SpreadElementTypeMismatch,
+        PrivateTypeInPublicInterface,
+    }
+
     /// Diagnostic related to an item defined multiple times error.
     diagnostic(error) ItemDefinedMultipleTimes(
         self,
         name: String,
+        first_definition_kind: SymbolKind,
         first_definition_location: Location,
+        second_definition_kind: SymbolKind,
         second_definition_location: Location
     ) {
-        code { "E005" }
-        message { format!("the name `{}` is defined multiple times", self.name) }
+        code { "E100" }
+        message {
+            format!(
+                "the name `{}` is defined multiple times: previously as {}, now as {}",
+                self.name,
+                with_article(self.first_definition_kind.to_string()),
+                with_article(self.second_definition_kind.to_string())
+            )
+        }
         labels {
             primary {
-                self.first_definition_location => format!("previous definition of `{}` is here", self.name)
+                self.second_definition_location => format!("`{}` redefined here as {}",
+                    self.name, with_article(self.second_definition_kind.to_string()))
             }
             secondary {
-                self.second_definition_location => format!("{} redefined here", self.name)
+                self.first_definition_location => format!("previous definition of the {} `{}` here",
+                    self.first_definition_kind, self.name)
             }
         }
     }
 
+    /// Diagnostic, that occurs when an enum has several items with the same
+    /// name.
     diagnostic(error) EnumItemDefinedMultipleTimes(
         self,
         enum_name: String,
@@ -36,7 +68,7 @@ define_diagnostics! {
         first_definition_location: Location,
         second_definition_location: Location
     ) {
-        code { "E006" }
+        code { "E101" }
         message { format!("duplicate definition of the enum item `{}` in `{}`", self.item_name, self.enum_name) }
         labels {
             primary {
@@ -46,13 +78,415 @@ define_diagnostics! {
         }
     }
 
+    /// Diagnostic, that occurs when an enum has a method and an item with
+    /// the same name, e.g. `enum Shape { Circle, fun Circle() {} }`.
+    diagnostic(error) EnumMethodConflictsWithVariant(
+        self,
+        method_name: IdentifierAST,
+        item_name: IdentifierAST
+    ) {
+        code { "E119" }
+        message { format!("method `{}` conflicts with the enum item of the same name", self.method_name.id) }
+        labels {
+            primary { self.method_name.location => "method is defined here" }
+            secondary { self.item_name.location => format!("`{}` is defined here as an enum item", self.item_name.id) }
+        }
+        notes {
+            "note: `Enum.name` would be ambiguous, so the enum item is used"
+        }
+    }
+
+    /// Diagnostic, that occurs when a generic type alias is used with a wrong
+    /// number of generic arguments, e.g. `Pair[int32, int32]` with
+    /// `type Pair[T] = (T, T);`.
+    diagnostic(error) TypeAliasArityMismatch(
+        self,
+        location: Location,
+        alias_name: IdentifierAST,
+        min_expected: usize,
+        max_expected: usize,
+        found: usize
+    ) {
+        code { "E239" }
+        message {
+            format!(
+                "type alias `{}` takes {} generic argument{} but {} {} supplied",
+                self.alias_name.id,
+                if self.min_expected == self.max_expected {
+                    self.max_expected.to_string()
+                } else {
+                    format!("from {} to {}", self.min_expected, self.max_expected)
+                },
+                if self.max_expected == 1 { "" } else { "s" },
+                self.found,
+                if self.found == 1 { "was" } else { "were" }
+            )
+        }
+        labels {
+            primary {
+                self.location => if self.found > self.max_expected {
+                    "unexpected generic argument".to_owned()
+                } else {
+                    format!("expected {} generic argument{}", self.max_expected, if self.max_expected == 1 { "" } else { "s" })
+                }
+            }
+            secondary { self.alias_name.location => "type alias is defined here" }
+        }
+    }
+
+    /// Diagnostic, that occurs when a generic struct, enum or interface is
+    /// used with a wrong number of generic arguments, e.g. `Box[int32, int32]`
+    /// with `struct Box[T] { value: T }`.
+    diagnostic(error) GenericArgumentArityMismatch(
+        self,
+        location: Location,
+        kind: SymbolKind,
+        name: IdentifierAST,
+        min_expected: usize,
+        max_expected: usize,
+        found: usize
+    ) {
+        code { "E254" }
+        message {
+            format!(
+                "{} `{}` takes {} generic argument{} but {} {} supplied",
+                self.kind,
+                self.name.id,
+                if self.min_expected == self.max_expected {
+                    self.max_expected.to_string()
+                } else {
+                    format!("from {} to {}", self.min_expected, self.max_expected)
+                },
+                if self.max_expected == 1 { "" } else { "s" },
+                self.found,
+                if self.found == 1 { "was" } else { "were" }
+            )
+        }
+        labels {
+            primary {
+                self.location => if self.found > self.max_expected {
+                    "unexpected generic argument".to_owned()
+                } else {
+                    format!("expected {} generic argument{}", self.max_expected, if self.max_expected == 1 { "" } else { "s" })
+                }
+            }
+            secondary { self.name.location => format!("{} is defined here", self.kind) }
+        }
+    }
+
+    /// Diagnostic, that occurs when a generic argument doesn't implement an
+    /// interface, that the corresponding generic parameter is bounded by, e.g.
+    /// `Set[Point]` with `struct Set[T: Hash] {}`, if `Point` doesn't
+    /// implement `Hash`.
+    diagnostic(error) BoundNotSatisfied(
+        self,
+        location: Location,
+        ty: String,
+        interface: String,
+        bound_location: Location
+    ) {
+        code { "E255" }
+        message {
+            format!("the type `{}` doesn't implement interface `{}`", self.ty, self.interface)
+        }
+        labels {
+            primary { self.location => format!("`{}` doesn't implement `{}`", self.ty, self.interface) }
+            secondary { self.bound_location => "required by this bound" }
+        }
+    }
+
+    /// Diagnostic, that occurs when an argument is passed to a parameter,
+    /// that is already passed by a previous argument, e.g. `x: 20` in
+    /// `draw(10, x: 20)` or `draw(x: 10, x: 20)`.
+    diagnostic(error) ArgumentPassedMoreThanOnce(
+        self,
+        parameter: IdentifierId,
+        first_location: Location,
+        second_location: Location
+    ) {
+        code { "E241" }
+        message { format!("argument for parameter `{}` is passed more than once", self.parameter) }
+        labels {
+            primary { self.second_location => "passed more than once" }
+            secondary { self.first_location => format!("`{}` is first passed here", self.parameter) }
+        }
+    }
+
+    /// Diagnostic, that occurs when a positional argument follows a labeled
+    /// one, e.g. `20` in `draw(x: 10, 20)`.
+    diagnostic(error) PositionalArgumentAfterLabeled(
+        self,
+        location: Location,
+        labeled_location: Location
+    ) {
+        code { "E242" }
+        message { "positional argument after labeled arguments" }
+        labels {
+            primary { self.location => "positional argument" }
+            secondary { self.labeled_location => "first labeled argument is here" }
+        }
+        notes {
+            "help: label the argument or move it before labeled arguments"
+        }
+    }
+
+    /// Diagnostic, that occurs when the value of a spread argument is not
+    /// a tuple, e.g. `..1` in `add(..1)`.
+    diagnostic(error) SpreadArgumentNotTuple(
+        self,
+        location: Location,
+        found: String
+    ) {
+        code { "E246" }
+        message { "spread argument must be a tuple" }
+        labels {
+            primary { self.location => format!("expected a tuple, found `{}`", self.found) }
+        }
+    }
+
+    /// Diagnostic, that occurs when a spread tuple has a different number of
+    /// elements, than the number of parameters left, e.g. `..(1, 2, 3)` in
+    /// `add(..(1, 2, 3))`, where `add` has 2 parameters.
+    diagnostic(error) SpreadArgumentArityMismatch(
+        self,
+        location: Location,
+        expected: usize,
+        found: usize
+    ) {
+        code { "E247" }
+        message {
+            format!("spread tuple has {} element{} but {} parameter{} left",
+                self.found, if self.found == 1 { "" } else { "s" },
+                self.expected, if self.expected == 1 { " is" } else { "s are" })
+        }
+        labels {
+            primary {
+                self.location => format!("expected a tuple of {} element{}",
+                    self.expected, if self.expected == 1 { "" } else { "s" })
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when a call argument follows a spread
+    /// argument, e.g. `3` in `add(..pair, 3)` or `..other` in
+    /// `add(..pair, ..other)`.
+    diagnostic(error) ArgumentAfterSpread(
+        self,
+        location: Location,
+        spread_location: Location,
+        is_spread: bool
+    ) {
+        code { "E249" }
+        message {
+            if self.is_spread {
+                "only one spread argument is allowed in a call"
+            } else {
+                "argument after a spread argument"
+            }
+        }
+        labels {
+            primary { self.location => "unexpected argument" }
+            secondary { self.spread_location => "spread argument is here" }
+        }
+        notes {
+            "help: spread argument must be the last argument of a call"
+        }
+    }
+
+    /// Diagnostic, that occurs when a value cannot be cast to a type, e.g.
+    /// `"1" as int32`.
+    diagnostic(error) InvalidCast(
+        self,
+        location: Location,
+        from: String,
+        to: String,
+        to_interface: bool
+    ) {
+        code { "E250" }
+        message { format!("cannot cast `{}` as `{}`", self.from, self.to) }
+        labels {
+            primary { self.location => "invalid cast" }
+        }
+        notes {
+            if self.to_interface {
+                format!("help: cast to the interface object type `dyn {}` instead", self.to)
+            } else {
+                "note: only numeric types, `bool`, `char` and enums without fields can be cast".to_owned()
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when a binary operator is applied to operands,
+    /// that it doesn't support, e.g. `1 + "x"` or `true && 1`.
+    diagnostic(error) InvalidBinaryOperands(
+        self,
+        operator: BinaryOperator,
+        left_type: String,
+        left_location: Location,
+        right_type: String,
+        right_location: Location
+    ) {
+        code { "E256" }
+        message {
+            format!(
+                "cannot apply `{}` to `{}` and `{}`",
+                self.operator.raw, self.left_type, self.right_type
+            )
+        }
+        labels {
+            primary { self.operator.location => format!("`{}` cannot be applied to these types", self.operator.raw) }
+            secondary { self.left_location => format!("this is `{}`", self.left_type) }
+            secondary { self.right_location => format!("this is `{}`", self.right_type) }
+        }
+    }
+
+    /// Diagnostic, that occurs when operands of a binary operator are numbers
+    /// of different types, e.g. `x + y`, where `x` is `int32` and `y` is
+    /// `int64`. Numbers are never converted implicitly.
+    diagnostic(error) MixedNumericOperands(
+        self,
+        operator: BinaryOperator,
+        left_type: String,
+        left_location: Location,
+        right_type: String,
+        right_location: Location
+    ) {
+        code { "E257" }
+        message {
+            format!(
+                "cannot apply `{}` to `{}` and `{}`",
+                self.operator.raw, self.left_type, self.right_type
+            )
+        }
+        labels {
+            primary { self.right_location => format!("expected `{}`, found `{}`", self.left_type, self.right_type) }
+            secondary { self.left_location => format!("this is `{}`", self.left_type) }
+        }
+        notes {
+            format!(
+                "help: convert the right operand with `as {}` or the left one with `as {}`",
+                self.left_type, self.right_type
+            )
+        }
+    }
+
+    /// Diagnostic, that occurs when the default value of a parameter has
+    /// a type, that is different from the type of the parameter, e.g.
+    /// `fun f(x: int32 = "a") {}`.
+    diagnostic(error) DefaultValueTypeMismatch(
+        self,
+        location: Location,
+        expected: String,
+        found: String,
+        type_location: Location
+    ) {
+        code { "E243" }
+        message { "mismatched types" }
+        labels {
+            primary {
+                self.location => format!("expected `{}`, found `{}`", self.expected, self.found)
+            }
+            secondary {
+                self.type_location => "expected because of this parameter type"
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when the default value of a parameter uses
+    /// another parameter, e.g. `a` in `fun f(a: int32, b: int32 = a) {}`.
+    diagnostic(error) DefaultValueReferencesParameter(
+        self,
+        location: Location,
+        parameter: IdentifierId,
+        parameter_location: Location
+    ) {
+        code { "E244" }
+        message { format!("default value cannot use the parameter `{}`", self.parameter) }
+        labels {
+            primary { self.location => "parameter is used here" }
+            secondary { self.parameter_location => format!("`{}` is defined here", self.parameter) }
+        }
+        notes {
+            "note: default values are evaluated independently of other arguments"
+        }
+    }
+
+    /// Diagnostic, that occurs when a call doesn't pass arguments for all
+    /// parameters without default values, e.g. `greet()` with
+    /// `fun greet(name: String, punctuation: String = "!")`.
+    diagnostic(error) MissingArguments(
+        self,
+        location: Location,
+        function_name: IdentifierAST,
+        parameters: Vec<String>
+    ) {
+        code { "E245" }
+        message {
+            format!("missing {} for {} {} in the call to `{}`",
+                if self.parameters.len() == 1 { "argument" } else { "arguments" },
+                if self.parameters.len() == 1 { "parameter" } else { "parameters" },
+                all_of(&self.parameters),
+                self.function_name.id)
+        }
+        labels {
+            primary { self.location => format!("missing {}", all_of(&self.parameters)) }
+            secondary { self.function_name.location => "function is defined here" }
+        }
+    }
+
+    /// Diagnostic, that occurs when a tuple-like struct is constructed with
+    /// a wrong number of arguments, e.g. `Point(1.0)`, where `Point` has two
+    /// fields.
+    diagnostic(error) TupleLikeStructArityMismatch(
+        self,
+        location: Location,
+        struct_name: IdentifierAST,
+        expected: usize,
+        got: usize
+    ) {
+        code { "E252" }
+        message { format!("struct `{}` takes {} {}, but {} {} supplied",
+            self.struct_name.id,
+            self.expected,
+            if self.expected == 1 { "argument" } else { "arguments" },
+            self.got,
+            if self.got == 1 { "was" } else { "were" }) }
+        labels {
+            primary { self.location }
+            secondary {
+                self.struct_name.location => format!("`{}` is defined here", self.struct_name.id)
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when a tuple-like struct with a private field
+    /// is constructed outside of the module, that defines it, e.g.
+    /// `geometry.Meters(1.0)`, where the field of `Meters` is private.
+    diagnostic(error) PrivateTupleLikeStructConstructor(
+        self,
+        location: Location,
+        struct_name: IdentifierId,
+        field_index: usize,
+        field_location: Location
+    ) {
+        code { "E253" }
+        message {
+            format!("cannot construct `{}` here, because its field `{}` is private",
+                self.struct_name, self.field_index)
+        }
+        labels {
+            primary { self.location => "private constructor" }
+            secondary { self.field_location => format!("field `{}` is private", self.field_index) }
+        }
+    }
+
     /// Diagnostic related to trying to import a package error.
     diagnostic(error) PackageImport(
         self,
         location: Location,
         package_name: IdentifierAST
     ) {
-        code { "E007" }
+        code { "E102" }
         message { format!("trying to import package `{}`", self.package_name.id) }
         labels {
             primary { self.location => format!("help: remove this import")
@@ -73,7 +507,7 @@ define_diagnostics! {
         location: Location,
         package_name: String
     ) {
-        code { "E008" }
+        code { "E105" }
         message { format!("failed to resolve the package `{}`", self.package_name) }
         labels {
             primary { self.location }
@@ -91,7 +525,7 @@ define_diagnostics! {
         item_name: String,
         item_name_location: Location
     ) {
-        code { "E008" }
+        code { "E104" }
         message { format!("failed to resolve the module item `{}`", self.item_name) }
         labels {
             primary { self.item_name_location }
@@ -110,7 +544,7 @@ define_diagnostics! {
         item_name: String,
         item_name_location: Location
     ) {
-        code { "E008" }
+        code { "E106" }
         message { format!("failed to resolve private module item `{}`", self.item_name) }
         labels {
             primary { self.item_name_location }
@@ -133,7 +567,7 @@ define_diagnostics! {
         module_item_kind: ModuleItemKind,
         name: IdentifierAST
     ) {
-        code { "E008" }
+        code { "E108" }
         message { format!("failed to resolve the name `{}`", self.name.id) }
         labels {
             primary {
@@ -162,7 +596,7 @@ define_diagnostics! {
         enum_item_name: IdentifierAST,
         name: IdentifierAST
     ) {
-        code { "E008" }
+        code { "E107" }
         message { format!("failed to resolve the name `{}`", self.name.id) }
         labels {
             primary {
@@ -179,39 +613,26 @@ define_diagnostics! {
         }
     }
 
-    diagnostic(error) FailedToResolveEnumItem(
-        self,
-        enum_name: IdentifierAST,
-        enum_item_name: IdentifierAST
-    ) {
-        code { "E008" }
-        message { format!("failed to resolve enum item `{}`", self.enum_item_name.id) }
-        labels {
-            primary {
-                self.enum_item_name.location => format!("cannot find the name `{}` in the definition of enum `{}`",
-                    self.enum_item_name.id, self.enum_name.id)
-            }
-        }
-    }
-
     /// Diagnostic, that occurs when the compiler tries to resolve a name in a module scope.
     diagnostic(error) FailedToResolveName(
         self,
         name: IdentifierAST
     ) {
-        code { "E008" }
+        code { "E103" }
         message { format!("failed to resolve the name `{}`", self.name.id) }
         labels {
             primary { self.name.location }
         }
     }
 
+    /// Diagnostic, that occurs when `_` is used as a type in a signature of
+    /// an item, e.g. `fun foo(): _`.
     diagnostic(error) UnderscoreTypeInSignature(
         self,
         item_name: IdentifierAST,
         location: Location
     ) {
-        code { "E009" }
+        code { "E200" }
         message { format!("found underscore type in signature of `{}`", self.item_name.id) }
         labels {
             primary { self.location }
@@ -222,38 +643,1555 @@ define_diagnostics! {
             "note: types cannot be inferred in signatures, because of explicitness."
         }
     }
-}
 
-pub struct CycleDetectedWhenComputingSignatureOf {
-    pub backtrace: Vec<IdentifierAST>,
-}
+    /// Diagnostic, that occurs when a module is nested deeper than
+    /// [`Config::max_module_depth()`] allows.
+    ///
+    /// [`Config::max_module_depth()`]: stellar_database::Config::max_module_depth
+    diagnostic(error) ModuleDepthLimitExceeded(
+        self,
+        location: Location,
+        depth: usize,
+        limit: usize
+    ) {
+        code { "E111" }
+        message { format!("module depth limit of {} is exceeded", self.limit) }
+        labels {
+            primary { self.location => format!("this module is nested {} levels deep", self.depth) }
+        }
+        notes {
+            "note: definitions of this module are ignored"
+        }
+    }
 
-impl CycleDetectedWhenComputingSignatureOf {
-    pub fn new(backtrace: Vec<IdentifierAST>) -> Self {
-        Self { backtrace }
+    /// Diagnostic, that occurs when an imported name is shadowed by a module item
+    /// or a submodule with the same name, e.g. `import a.Foo; struct Foo {}`.
+    diagnostic(warning) ImportShadowedByLocalDefinition(
+        self,
+        import_location: Location,
+        name: IdentifierId,
+        definition_location: Location
+    ) {
+        code { "W004" }
+        message { "import is shadowed by a local definition of the same name" }
+        labels {
+            primary { self.import_location => format!("`{}` is imported here", self.name) }
+            secondary {
+                self.definition_location => format!("`{}` is defined here and is used instead", self.name)
+            }
+        }
+        notes {
+            "help: remove the import or rename it with `as`"
+        }
     }
-}
 
-impl BuildDiagnostic for CycleDetectedWhenComputingSignatureOf {
-    fn build(self) -> Diagnostic {
-        Diagnostic::error()
-            .with_message(format!(
-                "cycle detected when computing signature of {}",
-                self.backtrace.first().unwrap().id
-            ))
-            .with_code("E009")
-            .with_labels(
-                self.backtrace
-                    .iter()
-                    .tuple_windows()
-                    .enumerate()
-                    .map(|(idx, (a, b))| {
-                        Label::secondary(a.location).with_message(format!(
-                            "computing signature of {} requires also to compute signature of {}",
-                            a.id, b.id
-                        ))
-                    })
-                    .collect::<Vec<_>>(),
-            )
+    /// Diagnostic, that occurs when the same item is imported twice under the
+    /// same name, e.g. `import a.Foo; import a.Foo;`.
+    diagnostic(warning) DuplicateImport(
+        self,
+        import_location: Location,
+        name: IdentifierId,
+        previous_import_location: Location
+    ) {
+        code { "W010" }
+        message { format!("`{}` is imported multiple times", self.name) }
+        labels {
+            primary { self.import_location => "help: remove this import" }
+            secondary { self.previous_import_location => format!("`{}` is first imported here", self.name) }
+        }
+    }
+
+    /// Diagnostic, that occurs when different items are imported under the
+    /// same name, e.g. `import a.Foo; import b.Foo;`.
+    diagnostic(error) ConflictingImports(
+        self,
+        import_location: Location,
+        name: IdentifierId,
+        previous_import_location: Location
+    ) {
+        code { "E118" }
+        message { format!("name `{}` is imported multiple times with different targets", self.name) }
+        labels {
+            primary { self.import_location => format!("`{}` is imported again here", self.name) }
+            secondary { self.previous_import_location => format!("`{}` is first imported here", self.name) }
+        }
+        notes {
+            "help: rename one of the imports with `as`"
+        }
+    }
+
+    /// Diagnostic, that occurs when an enum item, that has no payload, is
+    /// used with arguments, e.g. `Color.Red(1)`.
+    diagnostic(error) EnumItemTakesNoArguments(
+        self,
+        enum_item_name: IdentifierAST,
+        location: Location,
+        definition_location: Location
+    ) {
+        code { "E203" }
+        message { format!("enum item `{}` takes no arguments", self.enum_item_name.id) }
+        labels {
+            primary {
+                self.location => format!("help: remove the arguments of `{}`", self.enum_item_name.id)
+            }
+            secondary {
+                self.definition_location => format!("`{}` is defined here", self.enum_item_name.id)
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when a tuple-like enum item is used without
+    /// a payload, e.g. `Option.Some`.
+    diagnostic(error) MissingEnumItemPayload(
+        self,
+        enum_item_name: IdentifierAST,
+        payload_size: usize,
+        definition_location: Location
+    ) {
+        code { "E204" }
+        message { format!("missing payload of enum item `{}`", self.enum_item_name.id) }
+        labels {
+            primary {
+                self.enum_item_name.location => format!("`{}` expects {} {}",
+                    self.enum_item_name.id,
+                    self.payload_size,
+                    if self.payload_size == 1 { "argument" } else { "arguments" })
+            }
+            secondary {
+                self.definition_location => format!("`{}` is defined here", self.enum_item_name.id)
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when a tuple-like enum item is used with a wrong
+    /// number of arguments, e.g. `Option.Some(1, 2)`.
+    diagnostic(error) EnumItemPayloadArityMismatch(
+        self,
+        enum_item_name: IdentifierAST,
+        location: Location,
+        expected: usize,
+        got: usize,
+        definition_location: Location
+    ) {
+        code { "E205" }
+        message { format!("enum item `{}` takes {} {}, but {} {} supplied",
+            self.enum_item_name.id,
+            self.expected,
+            if self.expected == 1 { "argument" } else { "arguments" },
+            self.got,
+            if self.got == 1 { "was" } else { "were" }) }
+        labels {
+            primary { self.location }
+            secondary {
+                self.definition_location => format!("`{}` is defined here", self.enum_item_name.id)
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when a pattern in a `let` statement can fail to
+    /// match a value, e.g. `let Some(x) = y;`.
+    diagnostic(error) RefutablePatternInLet(
+        self,
+        location: Location
+    ) {
+        code { "E206" }
+        message { "refutable pattern in `let` statement" }
+        labels {
+            primary { self.location => "pattern doesn't match all possible values" }
+        }
+        notes {
+            "help: use `match` or `let ... else { ... }` to handle values, that don't match the pattern"
+        }
+    }
+
+    /// Diagnostic, that occurs when the `else` block of `let ... else` can
+    /// finish normally, e.g. `let Some(x) = y else { foo(); };`.
+    diagnostic(error) LetElseBlockDoesNotDiverge(
+        self,
+        location: Location
+    ) {
+        code { "E207" }
+        message { "`else` block of `let ... else` must diverge" }
+        labels {
+            primary { self.location => "execution can continue past the end of the `else` block" }
+        }
+        notes {
+            "help: end the `else` block with `return`, `break` or `continue`"
+        }
+    }
+
+    /// Diagnostic, that occurs when a lambda is passed to a function, that
+    /// expects a function of another type, e.g. `apply(|x, y| x, 1)`, where
+    /// `apply` expects `fun(int32): int32`.
+    diagnostic(error) LambdaTypeMismatch(
+        self,
+        location: Location,
+        expected: String,
+        found: String,
+        expected_location: Location
+    ) {
+        code { "E208" }
+        message { "mismatched types" }
+        labels {
+            primary {
+                self.location => format!("expected `{}`, found `{}`", self.expected, self.found)
+            }
+            secondary {
+                self.expected_location => "expected because of this parameter type"
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when a tuple is indexed with an index, that is
+    /// greater than or equal to the number of its elements, e.g. `(1, 2).2`.
+    diagnostic(error) TupleIndexOutOfRange(
+        self,
+        location: Location,
+        index: u32,
+        tuple_size: usize
+    ) {
+        code { "E209" }
+        message { format!("tuple index `{}` is out of range", self.index) }
+        labels {
+            primary {
+                self.location => format!("the tuple has only {} {}",
+                    self.tuple_size,
+                    if self.tuple_size == 1 { "element" } else { "elements" })
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when a value, that is not a tuple, is indexed
+    /// like a tuple, e.g. `1.0` or `point.0`, where `point` is a struct.
+    diagnostic(error) TupleIndexOnNonTuple(
+        self,
+        location: Location,
+        index: u32,
+        ty: String
+    ) {
+        code { "E210" }
+        message { format!("cannot access element `{}` of a value, that is not a tuple", self.index) }
+        labels {
+            primary { self.location => format!("this has type `{}`", self.ty) }
+        }
+    }
+
+    /// Diagnostic, that occurs when an element of a list literal has a type,
+    /// that is different from the types of the previous elements, e.g.
+    /// `"a"` in `[1, "a"]`.
+    diagnostic(error) ListElementTypeMismatch(
+        self,
+        location: Location,
+        expected: String,
+        found: String,
+        first_element_location: Location
+    ) {
+        code { "E211" }
+        message { "mismatched types of list elements" }
+        labels {
+            primary {
+                self.location => format!("expected `{}`, found `{}`", self.expected, self.found)
+            }
+            secondary {
+                self.first_element_location => "expected because of this element"
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when a value, that is not a list, is indexed,
+    /// e.g. `1[0]`.
+    diagnostic(error) IndexOnNonList(
+        self,
+        location: Location,
+        ty: String
+    ) {
+        code { "E212" }
+        message { format!("cannot index into a value of type `{}`", self.ty) }
+        labels {
+            primary { self.location => "only lists can be indexed" }
+        }
+    }
+
+    /// Diagnostic, that occurs when a list is indexed with a value, that is
+    /// not an integer, e.g. `xs["a"]`.
+    diagnostic(error) NonIntegerIndex(
+        self,
+        location: Location,
+        ty: String
+    ) {
+        code { "E213" }
+        message { "list indices must be integers" }
+        labels {
+            primary { self.location => format!("expected an integer, found `{}`", self.ty) }
+        }
+    }
+
+    /// Diagnostic, that occurs when the left-hand side of an assignment is
+    /// not a place, that can be assigned to, e.g. `f() = 1`.
+    diagnostic(error) InvalidAssignmentTarget(
+        self,
+        location: Location
+    ) {
+        code { "E214" }
+        message { "invalid left-hand side of assignment" }
+        labels {
+            primary { self.location => "cannot assign to this expression" }
+        }
+        notes {
+            "help: only local variables, fields, tuple elements and list elements can be assigned to"
+        }
+    }
+
+    /// Diagnostic, that occurs when the type of an assigned value doesn't
+    /// match the type of the place it is assigned to, e.g. `x = "a"`, where
+    /// `x` is `int32`.
+    diagnostic(error) AssignmentTypeMismatch(
+        self,
+        location: Location,
+        expected: String,
+        found: String,
+        target_location: Location
+    ) {
+        code { "E215" }
+        message { "mismatched types" }
+        labels {
+            primary {
+                self.location => format!("expected `{}`, found `{}`", self.expected, self.found)
+            }
+            secondary {
+                self.target_location => "expected because of the type of this place"
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when a value is assigned to a binding, that is
+    /// not declared with `mut`, e.g. `let x = 1; x = 2;`.
+    diagnostic(error) AssignmentToImmutableBinding(
+        self,
+        location: Location,
+        name: String,
+        binding_location: Location
+    ) {
+        code { "E216" }
+        message { format!("cannot assign to immutable binding `{}`", self.name) }
+        labels {
+            primary { self.location => "cannot assign to immutable binding" }
+            secondary { self.binding_location => "binding is declared here" }
+        }
+        suggestions {
+            Location {
+                end: self.binding_location.start,
+                ..self.binding_location
+            } => "mut ", "consider making this binding mutable"
+        }
+    }
+
+    /// Diagnostic, that occurs when a name, that is not a type, is used as
+    /// a type, e.g. `let x: foo = 1;`, where `foo` is a function.
+    diagnostic(error) ExpectedType(
+        self,
+        location: Location,
+        found_kind: SymbolKind,
+        found_name: String,
+        definition_location: Location
+    ) {
+        code { "E112" }
+        message {
+            format!("expected a type, found {} `{}`", with_article(self.found_kind.to_string()), self.found_name)
+        }
+        labels {
+            primary { self.location => "not a type" }
+            secondary {
+                self.definition_location => format!("the {} `{}` is defined here", self.found_kind, self.found_name)
+            }
+        }
+        notes {
+            format!("note: {} can't be used as types", self.found_kind.to_string().pluralize())
+        }
+    }
+
+    /// Diagnostic, that occurs when a name of a type is used as a value,
+    /// e.g. `let x = Point;`, where `Point` is a struct.
+    diagnostic(error) ExpectedValueFoundType(
+        self,
+        location: Location,
+        found_kind: SymbolKind,
+        found_name: String,
+        definition_location: Location
+    ) {
+        code { "E117" }
+        message {
+            format!("expected a value, found {} `{}`", with_article(self.found_kind.to_string()), self.found_name)
+        }
+        labels {
+            primary { self.location => "not a value" }
+            secondary {
+                self.definition_location => format!("the {} `{}` is defined here", self.found_kind, self.found_name)
+            }
+        }
+        notes {
+            if self.found_kind == SymbolKind::Struct {
+                format!("help: use a struct expression to construct a value, e.g. `{} {{ ... }}`", self.found_name)
+            } else {
+                format!("note: {} can't be used as values", self.found_kind.to_string().pluralize())
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when a module item has the same name as
+    /// a submodule, e.g. `struct util {}` next to the `util.sr` file.
+    ///
+    /// Submodules are not defined in the source code, so the file of the
+    /// submodule is mentioned in a note instead of a label.
+    diagnostic(error) ItemConflictsWithSubmodule(
+        self,
+        name: String,
+        item_kind: SymbolKind,
+        item_location: Location,
+        submodule_filepath: PathId
+    ) {
+        code { "E113" }
+        message {
+            format!("the name `{}` is defined both as a submodule and as {}", self.name, with_article(self.item_kind.to_string()))
+        }
+        labels {
+            primary { self.item_location => format!("{} `{}` conflicts with a submodule", self.item_kind, self.name) }
+        }
+        notes {
+            format!("note: the submodule `{}` is defined in `{}`", self.name, self.submodule_filepath)
+        }
+    }
+
+    /// Diagnostic, that occurs when a name is looked up in a module, source
+    /// file of which cannot be read.
+    diagnostic(error) NameInModuleFailedToLoad(
+        self,
+        module_name: IdentifierAST,
+        item_name: IdentifierAST
+    ) {
+        code { "E114" }
+        message {
+            format!("cannot resolve `{}`: module `{}` failed to load", self.item_name.id, self.module_name.id)
+        }
+        labels {
+            primary { self.item_name.location }
+            secondary { self.module_name.location => "source file of this module cannot be read" }
+        }
+    }
+
+    /// Diagnostic, that occurs when a guard of a match expression item is
+    /// not a boolean, e.g. `x if 1 -> ...`.
+    diagnostic(error) NonBooleanMatchGuard(
+        self,
+        location: Location,
+        ty: String
+    ) {
+        code { "E217" }
+        message { "match guards must be booleans" }
+        labels {
+            primary { self.location => format!("expected `bool`, found `{}`", self.ty) }
+        }
+    }
+
+    /// Diagnostic, that occurs when an identifier pattern of a match
+    /// expression item differs from a name of an item of the matched enum
+    /// only in case, e.g. `red` in `match color { red -> ... }`, so it binds
+    /// a new variable, that matches everything.
+    diagnostic(warning) EnumItemShadowedByBinding(
+        self,
+        binding: IdentifierAST,
+        enum_name: IdentifierId,
+        item_name: IdentifierId
+    ) {
+        code { "W013" }
+        message {
+            format!("this pattern binds a new variable `{}` instead of matching the variant `{}`",
+                self.binding.id, self.item_name)
+        }
+        labels {
+            primary { self.binding.location => "matches any value" }
+        }
+        suggestions {
+            self.binding.location => format!("{}.{}", self.enum_name, self.item_name), "qualify the variant"
+        }
+    }
+
+    /// Diagnostic, that occurs when a tuple pattern doesn't fit the tuple
+    /// it matches, e.g. `let (a, b, ..) = (1,);`.
+    diagnostic(error) TuplePatternArityMismatch(
+        self,
+        location: Location,
+        expected: usize,
+        found: usize,
+        has_rest: bool
+    ) {
+        code { "E219" }
+        message { "mismatched types" }
+        labels {
+            primary {
+                self.location => format!(
+                    "expected a tuple with {} elements, found one with {}{} elements",
+                    self.expected,
+                    if self.has_rest { "at least " } else { "" },
+                    self.found
+                )
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when `break` or `continue` is used outside
+    /// of a loop.
+    diagnostic(error) LoopControlOutsideOfLoop(
+        self,
+        location: Location,
+        keyword: &'static str
+    ) {
+        code { "E220" }
+        message { format!("`{}` outside of a loop", self.keyword) }
+        labels {
+            primary { self.location => format!("cannot `{}` outside of a loop", self.keyword) }
+        }
+    }
+
+    /// Diagnostic, that occurs when `break` statements exit the same `loop`
+    /// with values of different types, e.g. `loop { break 1; break "a"; }`.
+    diagnostic(error) BreakValueTypeMismatch(
+        self,
+        location: Location,
+        expected: String,
+        found: String,
+        first_break_location: Location
+    ) {
+        code { "E221" }
+        message { "mismatched types of `break` values" }
+        labels {
+            primary {
+                self.location => format!("expected `{}`, found `{}`", self.expected, self.found)
+            }
+            secondary {
+                self.first_break_location => "expected because of this `break`"
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when `break` with a value exits a `while`
+    /// loop, e.g. `while x { break 1; }`.
+    diagnostic(error) BreakWithValueInWhile(
+        self,
+        location: Location
+    ) {
+        code { "E222" }
+        message { "`break` with a value inside of a `while` loop" }
+        labels {
+            primary { self.location => "can only break with a value inside `loop`" }
+        }
+    }
+
+    /// Diagnostic, that occurs when an inline module, e.g. `module foo { ... }`,
+    /// has the same name as another submodule of its parent module.
+    diagnostic(error) InlineModuleConflictsWithSubmodule(
+        self,
+        name: IdentifierAST,
+        submodule_filepath: PathId
+    ) {
+        code { "E116" }
+        message { format!("the module `{}` is defined multiple times", self.name.id) }
+        labels {
+            primary { self.name.location => format!("module `{}` redefined here", self.name.id) }
+        }
+        notes {
+            format!("note: the module `{}` is already defined in `{}`", self.name.id, self.submodule_filepath)
+        }
+    }
+
+    /// Diagnostic, that occurs when a `deprecated` attribute has arguments
+    /// other than a single string, e.g. `#[deprecated(foo)]`.
+    diagnostic(error) MalformedDeprecatedAttribute(
+        self,
+        location: Location
+    ) {
+        code { "E223" }
+        message { "malformed `deprecated` attribute" }
+        labels {
+            primary { self.location => "expected `#[deprecated]` or `#[deprecated(\"note\")]`" }
+        }
+    }
+
+    /// Diagnostic, that occurs when an item with a `#[deprecated]` attribute
+    /// is used outside of its own definition.
+    diagnostic(warning) DeprecatedItemUsage(
+        self,
+        location: Location,
+        kind: SymbolKind,
+        name: IdentifierId,
+        note: Option<String>,
+        definition_location: Location
+    ) {
+        code { "W007" }
+        message {
+            match &self.note {
+                Some(note) => format!("use of deprecated {} `{}`: {}", self.kind, self.name, note),
+                None => format!("use of deprecated {} `{}`", self.kind, self.name),
+            }
+        }
+        labels {
+            primary { self.location }
+            secondary { self.definition_location => format!("`{}` is defined here", self.name) }
+        }
+    }
+
+    /// Diagnostic, that occurs when the root module of a binary package
+    /// doesn't define the `main` function.
+    diagnostic(error) MissingEntryPoint(
+        self,
+        package_name: IdentifierId,
+        root_module_filepath: PathId
+    ) {
+        code { "E224" }
+        message {
+            format!("`main` function not found in the package `{}` (`{}`)",
+                self.package_name, self.root_module_filepath)
+        }
+        labels {}
+        notes {
+            "note: library packages (`kind = \"library\"` in the manifest) and packages checked with `--lib` don't need an entry point"
+        }
+    }
+
+    /// Diagnostic, that occurs when `main` in the root module of a binary
+    /// package is not a function, e.g. `struct main {}`.
+    diagnostic(error) EntryPointIsNotAFunction(
+        self,
+        location: Location,
+        kind: SymbolKind
+    ) {
+        code { "E225" }
+        message { "`main` is not a function" }
+        labels {
+            primary { self.location => format!("expected the entry point function, found {}", with_article(self.kind.to_string())) }
+        }
+    }
+
+    /// Diagnostic, that occurs when the `main` function is not public.
+    diagnostic(error) PrivateEntryPoint(
+        self,
+        location: Location
+    ) {
+        code { "E226" }
+        message { "`main` function must be public" }
+        labels {
+            primary { self.location => "help: consider adding `pub`" }
+        }
+    }
+
+    /// Diagnostic, that occurs when the `main` function has parameters.
+    diagnostic(error) EntryPointWithParameters(
+        self,
+        location: Location
+    ) {
+        code { "E227" }
+        message { "`main` function cannot have parameters" }
+        labels {
+            primary { self.location }
+        }
+    }
+
+    /// Diagnostic, that occurs when the `main` function has generic
+    /// parameters.
+    diagnostic(error) GenericEntryPoint(
+        self,
+        location: Location
+    ) {
+        code { "E228" }
+        message { "`main` function cannot have generic parameters" }
+        labels {
+            primary { self.location }
+        }
+    }
+
+    /// Diagnostic, that occurs when a private field of a struct is accessed
+    /// outside of the module, where the struct is defined.
+    diagnostic(error) PrivateFieldAccess(
+        self,
+        location: Location,
+        field: IdentifierId,
+        struct_name: IdentifierId,
+        field_location: Location
+    ) {
+        code { "E230" }
+        message { format!("field `{}` of struct `{}` is private", self.field, self.struct_name) }
+        labels {
+            primary { self.location => "private field" }
+            secondary { self.field_location => format!("field `{}` is declared here", self.field) }
+        }
+    }
+
+    /// Diagnostic, that occurs when a field is accessed on a value, that is
+    /// not a struct, e.g. `x.len`, where `x` is `int32`.
+    diagnostic(error) FieldAccessOnNonStruct(
+        self,
+        location: Location,
+        field: IdentifierId,
+        ty: String
+    ) {
+        code { "E231" }
+        message { format!("cannot access field `{}` of a value, that is not a struct", self.field) }
+        labels {
+            primary { self.location => format!("this has type `{}`", self.ty) }
+        }
+    }
+
+    /// Diagnostic, that occurs when a field is specified more than once in
+    /// a struct expression, e.g. `Point { x: 1, x: 2 }`.
+    diagnostic(error) DuplicateStructExpressionField(
+        self,
+        field: IdentifierId,
+        first_location: Location,
+        second_location: Location
+    ) {
+        code { "E232" }
+        message { format!("field `{}` is specified more than once", self.field) }
+        labels {
+            primary { self.second_location => "used more than once" }
+            secondary { self.first_location => format!("first use of `{}`", self.field) }
+        }
+    }
+
+    /// Diagnostic, that occurs when a struct expression doesn't specify all
+    /// fields of the struct, e.g. `Point { x: 1 }`.
+    diagnostic(error) MissingStructExpressionFields(
+        self,
+        location: Location,
+        struct_name: IdentifierId,
+        fields: Vec<IdentifierId>
+    ) {
+        code { "E233" }
+        message {
+            format!("missing {} {} in initializer of `{}`",
+                if self.fields.len() == 1 { "field" } else { "fields" },
+                all_of(self.fields.iter().map(|field| format!("`{field}`"))),
+                self.struct_name)
+        }
+        labels {
+            primary {
+                self.location => format!("missing {}",
+                    all_of(self.fields.iter().map(|field| format!("`{field}`"))))
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when a value of a field in a struct expression
+    /// has a type, that is different from the type of the field.
+    diagnostic(error) StructExpressionFieldTypeMismatch(
+        self,
+        location: Location,
+        expected: String,
+        found: String,
+        field_location: Location
+    ) {
+        code { "E234" }
+        message { "mismatched types" }
+        labels {
+            primary {
+                self.location => format!("expected `{}`, found `{}`", self.expected, self.found)
+            }
+            secondary {
+                self.field_location => "expected because of the type of this field"
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when the base of the struct update syntax has
+    /// a type, that is different from the constructed struct, e.g.
+    /// `Point { x: 1, ..size }`, where `size` is `Size`.
+    diagnostic(error) StructUpdateBaseTypeMismatch(
+        self,
+        location: Location,
+        expected: String,
+        found: String
+    ) {
+        code { "E235" }
+        message { "mismatched types of the struct update base" }
+        labels {
+            primary {
+                self.location => format!("expected `{}`, found `{}`", self.expected, self.found)
+            }
+        }
+    }
+
+    /// Diagnostic, that occurs when a value of a numeric literal doesn't fit
+    /// into the type of the literal, e.g. `let x: uint8 = 300;` or `-129i8`.
+    ///
+    /// `literal` is the literal as it is written in the source text, e.g.
+    /// `0x1FF` or `-129i8`.
+    diagnostic(error) NumericLiteralOutOfRange(
+        self,
+        location: Location,
+        literal: String,
+        ty: String,
+        range: String
+    ) {
+        code { "E236" }
+        message { format!("literal out of range for `{}`", self.ty) }
+        labels {
+            primary {
+                self.location => format!("`{}` doesn't fit into `{}`", self.literal, self.ty)
+            }
+        }
+        notes {
+            format!("note: the range of `{}` is `{}`", self.ty, self.range)
+        }
+    }
+
+    /// Diagnostic, that occurs when an interface, that is not object safe, is
+    /// used as a bound of an interface object type, e.g. `dyn Clone`, where
+    /// `Clone` has a method `fun clone(self): Self`.
+    diagnostic(error) InterfaceIsNotObjectSafe(
+        self,
+        location: Location,
+        interface_name: IdentifierAST,
+        method_name: IdentifierAST,
+        reason: &'static str
+    ) {
+        code { "E237" }
+        message { format!("interface `{}` cannot be used as an interface object", self.interface_name.id) }
+        labels {
+            primary {
+                self.location => format!("`{}` cannot be used as an interface object", self.interface_name.id)
+            }
+            secondary {
+                self.method_name.location => format!("...because method `{}` {}", self.method_name.id, self.reason)
+            }
+        }
+        notes {
+            "note: methods of interfaces used as objects can't have generic parameters or return `Self`"
+        }
+    }
+
+    /// Diagnostic, that occurs when a generic parameter of an item is not
+    /// used in the item, e.g. `T` in `struct Foo[T] { x: int32 }`.
+    diagnostic(warning) UnusedGenericParameter(
+        self,
+        name: IdentifierAST
+    ) {
+        code { "W008" }
+        message { format!("generic parameter `{}` is never used", self.name.id) }
+        labels {
+            primary { self.name.location => "unused generic parameter" }
+        }
+        notes {
+            format!("help: remove the parameter or rename it to `_{}`, if it is unused intentionally", self.name.id)
+        }
+    }
+
+    /// Diagnostic, that occurs when a private module item is not used in its
+    /// package and is not reachable from public items or `main`, e.g.
+    /// `struct Foo {}`, if nothing refers to `Foo`.
+    diagnostic(warning) UnusedModuleItem(
+        self,
+        kind: SymbolKind,
+        name: IdentifierAST
+    ) {
+        code { "W014" }
+        message { format!("{} `{}` is never used", self.kind, self.name.id) }
+        labels {
+            primary { self.name.location => "not reachable from public items or `main`" }
+        }
+        notes {
+            "help: remove the item or add `#[allow(dead_code)]` to it, if it is unused intentionally"
+        }
+    }
+
+    /// Diagnostic, that occurs when the type of a where predicate doesn't
+    /// mention any generic parameter of the item, e.g. `where int32: ToString`.
+    diagnostic(error) WherePredicateWithoutGenericParameters(
+        self,
+        location: Location
+    ) {
+        code { "E238" }
+        message { "where predicate doesn't constrain any generic parameter" }
+        labels {
+            primary { self.location => "doesn't mention any generic parameter in scope" }
+        }
+        notes {
+            "note: where predicates can only constrain generic parameters of items, that declare them"
+        }
+    }
+
+    /// Diagnostic, that occurs when a name of a type is not in `UpperCamelCase`,
+    /// e.g. `struct my_struct {}`.
+    diagnostic(warning) NonUpperCamelCaseName(
+        self,
+        kind: &'static str,
+        name: IdentifierAST,
+        suggestion: String
+    ) {
+        code { "W005" }
+        message { format!("{} `{}` should have an upper camel case name", self.kind, self.name.id) }
+        labels {
+            primary { self.name.location }
+        }
+        suggestions {
+            self.name.location => self.suggestion.clone(), "convert the identifier to upper camel case"
+        }
+    }
+
+    /// Diagnostic, that occurs when a name of a function, a method, a field
+    /// or a module is not in `lower_snake_case`, e.g. `fun readFile() {}`.
+    diagnostic(warning) NonSnakeCaseName(
+        self,
+        kind: &'static str,
+        name: IdentifierAST,
+        suggestion: String
+    ) {
+        code { "W006" }
+        message { format!("{} `{}` should have a snake case name", self.kind, self.name.id) }
+        labels {
+            primary { self.name.location }
+        }
+        suggestions {
+            self.name.location => self.suggestion.clone(), "convert the identifier to snake case"
+        }
+    }
+
+    /// Diagnostic, that occurs when a method of a type doesn't match the
+    /// signature of the corresponding interface method.
+    diagnostic(error) InterfaceMethodSignatureMismatch(
+        self,
+        method_name: IdentifierAST,
+        interface_name: IdentifierAST,
+        expected_signature: String,
+        interface_method_location: Location
+    ) {
+        code { "E202" }
+        message { format!("method `{}` has an incompatible signature for interface `{}`",
+            self.method_name.id, self.interface_name.id) }
+        labels {
+            primary {
+                self.method_name.location => format!("expected `{}`", self.expected_signature)
+            }
+            secondary {
+                self.interface_method_location => "interface method is declared here"
+            }
+        }
+    }
+}
+
+/// Diagnostic, that occurs when computing a signature of an item requires
+/// computing the signature itself.
+pub struct CycleDetectedWhenComputingSignatureOf {
+    pub backtrace: Vec<IdentifierAST>,
+}
+
+impl CycleDetectedWhenComputingSignatureOf {
+    pub const INFO: DiagnosticInfo = DiagnosticInfo {
+        name: "CycleDetectedWhenComputingSignatureOf",
+        code: "E251",
+        severity: Severity::Error,
+        explanation: concat!(
+            " Diagnostic, that occurs when computing a signature of an item requires\n",
+            " computing the signature itself.\n"
+        ),
+    };
+
+    pub fn new(backtrace: Vec<IdentifierAST>) -> Self {
+        Self { backtrace }
+    }
+}
+
+impl BuildDiagnostic for CycleDetectedWhenComputingSignatureOf {
+    fn build(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_message(format!(
+                "cycle detected when computing signature of {}",
+                self.backtrace.first().unwrap().id
+            ))
+            .with_code(Self::INFO.code)
+            .with_labels(
+                self.backtrace
+                    .iter()
+                    .tuple_windows()
+                    .enumerate()
+                    .map(|(idx, (a, b))| {
+                        Label::secondary(a.location).with_message(format!(
+                            "computing signature of {} requires also to compute signature of {}",
+                            a.id, b.id
+                        ))
+                    })
+                    .collect::<Vec<_>>(),
+            )
+    }
+}
+
+/// Diagnostic, that occurs when a type doesn't provide some methods of an
+/// interface it implements.
+pub struct MissingInterfaceMethod {
+    pub type_name: IdentifierAST,
+    pub interface_name: IdentifierAST,
+    pub implements_location: Location,
+    pub missing_methods: Vec<IdentifierAST>,
+}
+
+impl MissingInterfaceMethod {
+    pub const INFO: DiagnosticInfo = DiagnosticInfo {
+        name: "MissingInterfaceMethod",
+        code: "E201",
+        severity: Severity::Error,
+        explanation: concat!(
+            " Diagnostic, that occurs when a type doesn't provide some methods of an\n",
+            " interface it implements.\n"
+        ),
+    };
+
+    pub fn new(
+        type_name: IdentifierAST,
+        interface_name: IdentifierAST,
+        implements_location: Location,
+        missing_methods: Vec<IdentifierAST>,
+    ) -> Self {
+        Self {
+            type_name,
+            interface_name,
+            implements_location,
+            missing_methods,
+        }
+    }
+}
+
+impl BuildDiagnostic for MissingInterfaceMethod {
+    fn build(self) -> Diagnostic {
+        let missing_methods = self
+            .missing_methods
+            .iter()
+            .map(|method| format!("`{}`", method.id))
+            .join(", ");
+
+        Diagnostic::error()
+            .with_message(format!(
+                "not all methods of interface `{}` are implemented in `{}`",
+                self.interface_name.id, self.type_name.id
+            ))
+            .with_code(Self::INFO.code)
+            .with_labels(
+                std::iter::once(
+                    Label::primary(self.implements_location)
+                        .with_message(format!("missing {missing_methods} in implementation")),
+                )
+                .chain(self.missing_methods.iter().map(|method| {
+                    Label::secondary(method.location)
+                        .with_message(format!("`{}` is declared here", method.id))
+                }))
+                .collect::<Vec<_>>(),
+            )
+    }
+}
+
+/// Diagnostic, that occurs when the compiler cannot find an enum item
+/// with a given name, e.g. `Option.Nome`.
+pub struct FailedToResolveEnumItem {
+    pub enum_name: IdentifierAST,
+    pub enum_item_name: IdentifierAST,
+    pub suggestion: Option<IdentifierId>,
+
+    /// Type aliases, that were expanded to get the enum, e.g. `Opt` in
+    /// `Opt.Nome` with `type Opt = Option;`. Empty if the enum is referred
+    /// to directly.
+    pub aliases: Vec<IdentifierId>,
+}
+
+impl FailedToResolveEnumItem {
+    pub const INFO: DiagnosticInfo = DiagnosticInfo {
+        name: "FailedToResolveEnumItem",
+        code: "E109",
+        severity: Severity::Error,
+        explanation: concat!(
+            " Diagnostic, that occurs when the compiler cannot find an enum item\n",
+            " with a given name, e.g. `Option.Nome`.\n"
+        ),
+    };
+
+    pub fn new(enum_name: IdentifierAST, enum_item_name: IdentifierAST) -> Self {
+        Self {
+            enum_name,
+            enum_item_name,
+            suggestion: None,
+            aliases: vec![],
+        }
+    }
+
+    /// Adds a name of an existing enum item, that is similar to the unresolved one.
+    pub fn with_suggestion(mut self, suggestion: Option<IdentifierId>) -> Self {
+        self.suggestion = suggestion;
+        self
+    }
+
+    /// Adds names of type aliases, that the enum was referred to through,
+    /// starting with the one used in the path.
+    pub fn with_aliases(mut self, aliases: Vec<IdentifierId>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+}
+
+impl BuildDiagnostic for FailedToResolveEnumItem {
+    fn build(self) -> Diagnostic {
+        let message = match self.aliases.first() {
+            Some(alias) => format!(
+                "failed to resolve enum item `{}` in `{alias}`, an alias of enum `{}`",
+                self.enum_item_name.id, self.enum_name.id
+            ),
+            None => format!("failed to resolve enum item `{}`", self.enum_item_name.id),
+        };
+        let alias_chain = (self.aliases.len() > 1).then(|| {
+            let chain = self
+                .aliases
+                .iter()
+                .map(|alias| format!("`{alias}`"))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+
+            format!("note: alias chain: {chain} -> `{}`", self.enum_name.id)
+        });
+
+        Diagnostic::error()
+            .with_message(message)
+            .with_code(Self::INFO.code)
+            .with_labels(vec![Label::primary(self.enum_item_name.location)
+                .with_message(format!(
+                    "cannot find the name `{}` in the definition of enum `{}`",
+                    self.enum_item_name.id, self.enum_name.id
+                ))])
+            .with_notes(
+                alias_chain.into_iter().chain(
+                    self.suggestion
+                        .map(|suggestion| format!("help: did you mean `{suggestion}`?")),
+                ),
+            )
+    }
+}
+
+/// Diagnostic, that occurs when `break` or `continue` refers to a label,
+/// that doesn't belong to any enclosing loop, e.g. `break @outr;`.
+pub struct UndeclaredLoopLabel {
+    pub label: IdentifierAST,
+    pub suggestion: Option<IdentifierId>,
+}
+
+impl UndeclaredLoopLabel {
+    pub const INFO: DiagnosticInfo = DiagnosticInfo {
+        name: "UndeclaredLoopLabel",
+        code: "E115",
+        severity: Severity::Error,
+        explanation: concat!(
+            " Diagnostic, that occurs when `break` or `continue` refers to a label,\n",
+            " that doesn't belong to any enclosing loop, e.g. `break @outr;`.\n"
+        ),
+    };
+
+    pub fn new(label: IdentifierAST) -> Self {
+        Self {
+            label,
+            suggestion: None,
+        }
+    }
+
+    /// Adds a label of an enclosing loop, that is similar to the undeclared one.
+    pub fn with_suggestion(mut self, suggestion: Option<IdentifierId>) -> Self {
+        self.suggestion = suggestion;
+        self
+    }
+}
+
+impl BuildDiagnostic for UndeclaredLoopLabel {
+    fn build(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_message(format!("use of undeclared label `@{}`", self.label.id))
+            .with_code(Self::INFO.code)
+            .with_labels(vec![
+                Label::primary(self.label.location).with_message("undeclared label")
+            ])
+            .with_notes(
+                self.suggestion
+                    .map(|suggestion| format!("help: did you mean `@{suggestion}`?")),
+            )
+    }
+}
+
+/// Diagnostic, that occurs when a function doesn't have a parameter with
+/// the label of an argument, e.g. `z: 30` in `draw(x: 10, z: 30)`, where
+/// `draw` is `fun draw(x: int32, y: int32)`.
+pub struct UnknownArgumentLabel {
+    pub label: IdentifierAST,
+    pub function_name: IdentifierId,
+    pub suggestion: Option<IdentifierId>,
+}
+
+impl UnknownArgumentLabel {
+    pub const INFO: DiagnosticInfo = DiagnosticInfo {
+        name: "UnknownArgumentLabel",
+        code: "E240",
+        severity: Severity::Error,
+        explanation: concat!(
+            " Diagnostic, that occurs when a function doesn't have a parameter with\n",
+            " the label of an argument, e.g. `z: 30` in `draw(x: 10, z: 30)`, where\n",
+            " `draw` is `fun draw(x: int32, y: int32)`.\n"
+        ),
+    };
+
+    pub fn new(label: IdentifierAST, function_name: IdentifierId) -> Self {
+        Self {
+            label,
+            function_name,
+            suggestion: None,
+        }
+    }
+
+    /// Adds a name of an existing parameter, that is similar to the label.
+    pub fn with_suggestion(mut self, suggestion: Option<IdentifierId>) -> Self {
+        self.suggestion = suggestion;
+        self
+    }
+}
+
+impl BuildDiagnostic for UnknownArgumentLabel {
+    fn build(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_message(format!(
+                "function `{}` has no parameter named `{}`",
+                self.function_name, self.label.id
+            ))
+            .with_code(Self::INFO.code)
+            .with_labels(vec![
+                Label::primary(self.label.location).with_message("unknown parameter")
+            ])
+            .with_notes(
+                self.suggestion
+                    .map(|suggestion| format!("help: did you mean `{suggestion}`?")),
+            )
+    }
+}
+
+/// Diagnostic, that occurs when a struct doesn't have a field with a given
+/// name, e.g. `point.z`, where `point` is `Point { x: int32, y: int32 }`.
+pub struct UnknownField {
+    pub location: Location,
+    pub field: IdentifierId,
+    pub ty: String,
+    pub suggestions: Vec<IdentifierId>,
+}
+
+impl UnknownField {
+    pub const INFO: DiagnosticInfo = DiagnosticInfo {
+        name: "UnknownField",
+        code: "E229",
+        severity: Severity::Error,
+        explanation: concat!(
+            " Diagnostic, that occurs when a struct doesn't have a field with a given\n",
+            " name, e.g. `point.z`, where `point` is `Point { x: int32, y: int32 }`.\n"
+        ),
+    };
+
+    pub fn new(location: Location, field: IdentifierId, ty: String) -> Self {
+        Self {
+            location,
+            field,
+            ty,
+            suggestions: vec![],
+        }
+    }
+
+    /// Adds names of existing fields, that are similar to the unknown one.
+    pub fn with_suggestions(mut self, suggestions: Vec<IdentifierId>) -> Self {
+        self.suggestions = suggestions;
+        self
+    }
+}
+
+impl BuildDiagnostic for UnknownField {
+    fn build(self) -> Diagnostic {
+        let help = (!self.suggestions.is_empty()).then(|| {
+            format!(
+                "help: did you mean {}?",
+                one_of(
+                    self.suggestions
+                        .iter()
+                        .map(|suggestion| format!("`{suggestion}`"))
+                )
+            )
+        });
+
+        Diagnostic::error()
+            .with_message(format!("no field `{}` on type `{}`", self.field, self.ty))
+            .with_code(Self::INFO.code)
+            .with_labels(vec![
+                Label::primary(self.location).with_message("unknown field")
+            ])
+            .with_notes(help)
+    }
+}
+
+/// Diagnostic, that occurs when a path in a type cannot be resolved, e.g.
+/// `Pont` in `fun foo(p: Pont) {}`.
+pub struct FailedToResolveType {
+    pub name: IdentifierAST,
+
+    /// The segment before the failed one, e.g. `geometry` in `geometry.Pont`.
+    pub namespace: Option<IdentifierAST>,
+    pub suggestions: Vec<IdentifierId>,
+}
+
+impl FailedToResolveType {
+    pub const INFO: DiagnosticInfo = DiagnosticInfo {
+        name: "FailedToResolveType",
+        code: "E110",
+        severity: Severity::Error,
+        explanation: concat!(
+            " Diagnostic, that occurs when a path in a type cannot be resolved, e.g.\n",
+            " `Pont` in `fun foo(p: Pont) {}`.\n"
+        ),
+    };
+
+    pub fn new(name: IdentifierAST, namespace: Option<IdentifierAST>) -> Self {
+        Self {
+            name,
+            namespace,
+            suggestions: vec![],
+        }
+    }
+
+    /// Adds names in scope, that are similar to the unresolved one.
+    pub fn with_suggestions(mut self, suggestions: Vec<IdentifierId>) -> Self {
+        self.suggestions = suggestions;
+        self
+    }
+}
+
+impl BuildDiagnostic for FailedToResolveType {
+    fn build(self) -> Diagnostic {
+        let help = (!self.suggestions.is_empty()).then(|| {
+            format!(
+                "help: did you mean {}?",
+                one_of(
+                    self.suggestions
+                        .iter()
+                        .map(|suggestion| format!("`{suggestion}`"))
+                )
+            )
+        });
+
+        Diagnostic::error()
+            .with_message(format!("failed to resolve the name `{}`", self.name.id))
+            .with_code(Self::INFO.code)
+            .with_labels(
+                std::iter::once(Label::primary(self.name.location).with_message(
+                    if self.namespace.is_some() {
+                        "not found"
+                    } else {
+                        "not found in this scope"
+                    },
+                ))
+                .chain(self.namespace.map(|namespace| {
+                    Label::secondary(namespace.location).with_message(format!(
+                        "`{}` doesn't contain `{}`",
+                        namespace.id, self.name.id
+                    ))
+                }))
+                .collect::<Vec<_>>(),
+            )
+            .with_notes(help)
+    }
+}
+
+/// Diagnostic, that occurs when items of a match expression don't match all
+/// possible values, e.g. when an enum item is missing or all items have guards.
+pub struct NonExhaustiveMatch {
+    pub location: Location,
+    pub missing_patterns: Vec<String>,
+}
+
+impl NonExhaustiveMatch {
+    pub const INFO: DiagnosticInfo = DiagnosticInfo {
+        name: "NonExhaustiveMatch",
+        code: "E218",
+        severity: Severity::Error,
+        explanation: concat!(
+            " Diagnostic, that occurs when items of a match expression don't match all\n",
+            " possible values, e.g. when an enum item is missing or all items have guards.\n"
+        ),
+    };
+
+    pub fn new(location: Location, missing_patterns: Vec<String>) -> Self {
+        Self {
+            location,
+            missing_patterns,
+        }
+    }
+}
+
+impl BuildDiagnostic for NonExhaustiveMatch {
+    fn build(self) -> Diagnostic {
+        let missing_patterns = self
+            .missing_patterns
+            .iter()
+            .map(|pattern| format!("`{pattern}`"))
+            .join(", ");
+
+        Diagnostic::error()
+            .with_message("non-exhaustive patterns in `match` expression")
+            .with_code(Self::INFO.code)
+            .with_labels(vec![Label::primary(self.location)
+                .with_message(format!("{missing_patterns} not covered"))])
+            .with_notes(vec![
+                "help: add match items for missing patterns or a wildcard pattern `_`, items with guards don't count".to_owned(),
+            ])
+    }
+}
+
+/// Diagnostic, that occurs when an element of a spread tuple has a type,
+/// that is different from the type of the parameter, that it is passed to,
+/// e.g. `..("a", 2)` in `add(..("a", 2))`, where `add` takes `int32`s.
+pub struct SpreadElementTypeMismatch {
+    pub location: Location,
+    pub element: usize,
+    pub expected: String,
+    pub found: String,
+    pub parameter_location: Location,
+
+    /// Location of the declaration of the tuple, e.g. of the name of a local
+    /// variable, that stores it.
+    pub declaration_location: Option<Location>,
+}
+
+impl SpreadElementTypeMismatch {
+    pub const INFO: DiagnosticInfo = DiagnosticInfo {
+        name: "SpreadElementTypeMismatch",
+        code: "E248",
+        severity: Severity::Error,
+        explanation: concat!(
+            " Diagnostic, that occurs when an element of a spread tuple has a type,\n",
+            " that is different from the type of the parameter, that it is passed to,\n",
+            " e.g. `..(\"a\", 2)` in `add(..(\"a\", 2))`, where `add` takes `int32`s.\n"
+        ),
+    };
+
+    pub fn new(
+        location: Location,
+        element: usize,
+        expected: String,
+        found: String,
+        parameter_location: Location,
+    ) -> Self {
+        Self {
+            location,
+            element,
+            expected,
+            found,
+            parameter_location,
+            declaration_location: None,
+        }
+    }
+
+    pub fn with_declaration_location(mut self, declaration_location: Option<Location>) -> Self {
+        self.declaration_location = declaration_location;
+        self
+    }
+}
+
+impl BuildDiagnostic for SpreadElementTypeMismatch {
+    fn build(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_message("mismatched types")
+            .with_code(Self::INFO.code)
+            .with_labels(
+                [
+                    Label::primary(self.location).with_message(format!(
+                        "expected `{}`, found `{}` in element {} of the tuple",
+                        self.expected, self.found, self.element
+                    )),
+                    Label::secondary(self.parameter_location)
+                        .with_message("expected because of this parameter type"),
+                ]
+                .into_iter()
+                .chain(self.declaration_location.map(|location| {
+                    Label::secondary(location).with_message("tuple is declared here")
+                })),
+            )
+    }
+}
+
+/// Diagnostic, that occurs when a public item mentions a type, that can't be
+/// named outside of its package, e.g. `Secret` in `pub fun reveal(): Secret`,
+/// where `Secret` is private.
+///
+/// It is a warning, unless [`Config::deny_private_in_public()`] is set.
+///
+/// [`Config::deny_private_in_public()`]: stellar_database::Config::deny_private_in_public
+pub struct PrivateTypeInPublicInterface {
+    pub location: Location,
+    pub type_name: IdentifierAST,
+    pub type_kind: SymbolKind,
+    pub item_name: IdentifierId,
+    pub item_kind: SymbolKind,
+    pub deny: bool,
+}
+
+impl PrivateTypeInPublicInterface {
+    pub const INFO: DiagnosticInfo = DiagnosticInfo {
+        name: "PrivateTypeInPublicInterface",
+        code: "W012",
+        severity: Severity::Warning,
+        explanation: concat!(
+            " Diagnostic, that occurs when a public item mentions a type, that can't be\n",
+            " named outside of its package, e.g. `Secret` in `pub fun reveal(): Secret`,\n",
+            " where `Secret` is private.\n",
+            "\n",
+            " It is a warning, unless [`Config::deny_private_in_public()`] is set.\n",
+            "\n",
+            " [`Config::deny_private_in_public()`]: stellar_database::Config::deny_private_in_public\n"
+        ),
+    };
+
+    pub fn new(
+        location: Location,
+        type_name: IdentifierAST,
+        type_kind: SymbolKind,
+        item_name: IdentifierId,
+        item_kind: SymbolKind,
+        deny: bool,
+    ) -> Self {
+        Self {
+            location,
+            type_name,
+            type_kind,
+            item_name,
+            item_kind,
+            deny,
+        }
+    }
+}
+
+impl BuildDiagnostic for PrivateTypeInPublicInterface {
+    fn build(self) -> Diagnostic {
+        let diagnostic = if self.deny {
+            Diagnostic::error()
+        } else {
+            Diagnostic::warning()
+        };
+
+        diagnostic
+            .with_message(format!(
+                "private {} `{}` in public interface",
+                self.type_kind, self.type_name.id
+            ))
+            .with_code(Self::INFO.code)
+            .with_labels(vec![
+                Label::primary(self.location).with_message("can't be named outside of its package"),
+                Label::secondary(self.type_name.location)
+                    .with_message(format!("`{}` is defined here", self.type_name.id)),
+            ])
+            .with_notes(vec![format!(
+                "note: the {} `{}` is public",
+                self.item_kind, self.item_name
+            )])
     }
 }
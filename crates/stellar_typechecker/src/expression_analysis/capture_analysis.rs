@@ -0,0 +1,63 @@
+//! Fills the list of captured variables of every lambda in HIR, e.g. `x` in:
+//!
+//! ```stellar
+//! fun adder(x: int32): (int32): int32 {
+//!     |y| x + y
+//! }
+//! ```
+
+use stellar_database::ModuleId;
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::{
+    visit::{walk_expression_mut, walk_function_mut, MutVisitor},
+    Expression, Function,
+};
+
+use crate::resolution::scope::{BindingKind, ScopeTree};
+
+pub struct CaptureAnalysis {
+    /// Scopes of the function, that is currently being visited.
+    scopes: Option<ScopeTree>,
+}
+
+impl CaptureAnalysis {
+    pub fn run_all(modules: &mut FxHashMap<ModuleId, stellar_hir::Module>) {
+        let mut me = CaptureAnalysis { scopes: None };
+
+        for hir in modules.values_mut() {
+            me.visit_module_mut(hir);
+        }
+    }
+}
+
+impl MutVisitor for CaptureAnalysis {
+    fn visit_function_mut(&mut self, function: &mut Function) {
+        self.scopes = Some(ScopeTree::build(function));
+        walk_function_mut(self, function);
+        self.scopes = None;
+    }
+
+    fn visit_expression_mut(&mut self, expression: &mut Expression) {
+        if let Expression::Lambda {
+            location, captures, ..
+        } = expression
+        {
+            // Lambda scopes cover exactly the source code of lambdas.
+            if let Some(scope) = self.scopes.as_ref().and_then(|scopes| {
+                scopes
+                    .scopes()
+                    .iter()
+                    .find(|scope| scope.location == *location)
+            }) {
+                *captures = scope
+                    .bindings
+                    .iter()
+                    .filter(|binding| binding.kind == BindingKind::Captured)
+                    .map(|binding| binding.name.id)
+                    .collect();
+            }
+        }
+
+        walk_expression_mut(self, expression);
+    }
+}
@@ -0,0 +1,152 @@
+//! Checks, that left-hand sides of assignments are places, that can be
+//! assigned to: local variables, fields, tuple elements and list elements,
+//! e.g. `x = 1`, `p.x += 1`, `t.0 = 1` and `xs[0] = 1`, and that assigned
+//! local variables and parameters are declared with `mut`.
+//!
+//! Shadowing an immutable binding with a new one is still allowed:
+//!
+//! ```stellar
+//! let x = 1;
+//! let x = x + 1;
+//! ```
+
+#[cfg(feature = "debug")]
+use std::time::Instant;
+
+use stellar_ast::IdentifierAST;
+use stellar_database::{ModuleId, State};
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::{
+    visit::{walk_expression, walk_function, Visitor},
+    Expression, Function,
+};
+#[cfg(feature = "debug")]
+use tracing::trace;
+
+use super::expression_path;
+use crate::{
+    diagnostics::{AssignmentToImmutableBinding, InvalidAssignmentTarget},
+    resolution::scope::{Binding, ScopeTree},
+};
+
+pub struct CheckAssignments<'s> {
+    state: &'s mut State,
+
+    /// Scopes of the function, that is currently being visited.
+    scopes: Option<ScopeTree>,
+}
+
+impl<'s> CheckAssignments<'s> {
+    pub fn run_all(state: &'s mut State, modules: &FxHashMap<ModuleId, stellar_hir::Module>) {
+        for (module, hir) in modules {
+            CheckAssignments::run(state, *module, hir);
+        }
+    }
+
+    /// Checks assignments in bodies of functions of a given module.
+    pub fn run(state: &'s mut State, _module: ModuleId, hir: &stellar_hir::Module) {
+        #[cfg(feature = "debug")]
+        let now = Instant::now();
+
+        let mut me = CheckAssignments {
+            state,
+            scopes: None,
+        };
+
+        me.visit_module(hir);
+
+        #[cfg(feature = "debug")]
+        trace!(
+            "check_assignments_in(module = '{}') <{} us>",
+            _module.filepath(me.state.db()),
+            now.elapsed().as_micros()
+        );
+    }
+
+    fn visit_function_body(&mut self, function: &Function) {
+        self.scopes = Some(ScopeTree::build(function));
+        walk_function(self, function);
+        self.scopes = None;
+    }
+
+    /// Resolves a local variable or a parameter.
+    fn resolve_local(&self, name: IdentifierAST) -> Option<Binding> {
+        self.scopes.as_ref().and_then(|scopes| {
+            scopes
+                .bindings_at(name.location.start)
+                .into_iter()
+                .find(|binding| binding.name.id == name.id)
+        })
+    }
+
+    fn is_local(&self, name: IdentifierAST) -> bool {
+        self.resolve_local(name).is_some()
+    }
+
+    /// Returns the variable, that is modified by an assignment to a place,
+    /// e.g. `p` in `p.x.0 = 1`.
+    fn root_variable(expression: &Expression) -> Option<IdentifierAST> {
+        match expression {
+            Expression::Identifier(identifier) => Some(*identifier),
+            Expression::FieldAccess { left, .. }
+            | Expression::TupleIndex { left, .. }
+            | Expression::Index { left, .. } => Self::root_variable(left),
+            _ => None,
+        }
+    }
+
+    fn check_mutability(&mut self, location: Location, left: &Expression) {
+        let Some(binding) = Self::root_variable(left).and_then(|name| self.resolve_local(name))
+        else {
+            return;
+        };
+
+        if !binding.mutable {
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(AssignmentToImmutableBinding::new(
+                    location,
+                    binding.name.id.to_string(),
+                    binding.name.location,
+                ));
+        }
+    }
+
+    /// Returns `true` if a value can be assigned to an expression.
+    fn is_place(&self, expression: &Expression) -> bool {
+        match expression {
+            Expression::Identifier(identifier) => self.is_local(*identifier),
+            // Paths like `module.function` are not places.
+            Expression::FieldAccess { .. } => {
+                expression_path(expression).map_or(true, |path| self.is_local(path[0]))
+            }
+            Expression::TupleIndex { .. } | Expression::Index { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+impl Visitor for CheckAssignments<'_> {
+    fn visit_function(&mut self, function: &Function) {
+        self.visit_function_body(function);
+    }
+
+    fn visit_method(&mut self, method: &Function) {
+        self.visit_function_body(method);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Expression::Assign { location, left, .. } = expression {
+            if self.is_place(left) {
+                self.check_mutability(*location, left);
+            } else {
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(InvalidAssignmentTarget::new(left.location()));
+            }
+        }
+
+        walk_expression(self, expression);
+    }
+}
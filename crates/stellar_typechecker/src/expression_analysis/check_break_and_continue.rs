@@ -0,0 +1,171 @@
+//! Checks, that `break` and `continue` statements are inside loops and that
+//! their labels refer to enclosing loops:
+//!
+//! ```stellar
+//! fun main() {
+//!     @outer: loop {
+//!         while true {
+//!             break @outer;   // ok
+//!             break @outr;    // error: use of undeclared label `@outr`
+//!         }
+//!     }
+//!
+//!     continue;               // error: `continue` outside of a loop
+//! }
+//! ```
+//!
+//! Only `loop` expressions can have values, so `break` with a value, e.g.
+//! `break 42;`, cannot exit a `while` loop.
+//!
+//! Bodies of lambdas are checked separately from enclosing functions, so
+//! `break` inside a lambda cannot refer to a loop around it.
+
+#[cfg(feature = "debug")]
+use std::time::Instant;
+
+use stellar_ast::IdentifierAST;
+use stellar_database::{ModuleId, State};
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::{
+    visit::{walk_expression, walk_statement, Visitor},
+    Expression, LoopKind, Statement,
+};
+#[cfg(feature = "debug")]
+use tracing::trace;
+
+use crate::{
+    diagnostics::{BreakWithValueInWhile, LoopControlOutsideOfLoop, UndeclaredLoopLabel},
+    suggestions::find_similar_name,
+};
+
+pub struct CheckBreakAndContinue<'s> {
+    state: &'s mut State,
+
+    /// Kinds and labels of loops, that enclose the currently visited
+    /// statement, from the outermost one.
+    loops: Vec<(LoopKind, Option<IdentifierAST>)>,
+}
+
+impl<'s> CheckBreakAndContinue<'s> {
+    pub fn run_all(state: &'s mut State, modules: &FxHashMap<ModuleId, stellar_hir::Module>) {
+        for (module, hir) in modules {
+            CheckBreakAndContinue::run(state, *module, hir);
+        }
+    }
+
+    /// Checks `break` and `continue` statements in bodies of functions of a
+    /// given module.
+    pub fn run(state: &'s mut State, _module: ModuleId, hir: &stellar_hir::Module) {
+        #[cfg(feature = "debug")]
+        let now = Instant::now();
+
+        let mut me = CheckBreakAndContinue {
+            state,
+            loops: vec![],
+        };
+
+        me.visit_module(hir);
+
+        #[cfg(feature = "debug")]
+        trace!(
+            "check_break_and_continue_in(module = '{}') <{} us>",
+            _module.filepath(me.state.db()),
+            now.elapsed().as_micros()
+        );
+    }
+
+    /// Checks a `break` or `continue` statement and returns the kind of the
+    /// loop, that it refers to, if any.
+    fn check_loop_control(
+        &mut self,
+        location: Location,
+        keyword: &'static str,
+        label: Option<IdentifierAST>,
+    ) -> Option<LoopKind> {
+        let Some(label) = label else {
+            if self.loops.is_empty() {
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(LoopControlOutsideOfLoop::new(location, keyword));
+            }
+
+            return self.loops.last().map(|(kind, _)| *kind);
+        };
+
+        if let Some((kind, _)) =
+            self.loops.iter().rev().find(|(_, loop_label)| {
+                loop_label.is_some_and(|loop_label| loop_label.id == label.id)
+            })
+        {
+            return Some(*kind);
+        }
+
+        let suggestion = find_similar_name(
+            label.id,
+            self.loops
+                .iter()
+                .filter_map(|(_, label)| *label)
+                .map(|label| label.id),
+        );
+
+        self.state
+            .diagnostics_mut()
+            .add_diagnostic(UndeclaredLoopLabel::new(label).with_suggestion(suggestion));
+
+        None
+    }
+}
+
+impl Visitor for CheckBreakAndContinue<'_> {
+    fn visit_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Break {
+                location,
+                label,
+                value,
+            } => {
+                let kind = self.check_loop_control(*location, "break", *label);
+
+                if let Some(value) = value {
+                    if kind == Some(LoopKind::While) {
+                        self.state
+                            .diagnostics_mut()
+                            .add_diagnostic(BreakWithValueInWhile::new(value.location()));
+                    }
+
+                    self.visit_expression(value);
+                }
+            }
+            Statement::Continue { location, label } => {
+                self.check_loop_control(*location, "continue", *label);
+            }
+            _ => walk_statement(self, statement),
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::While {
+                kind,
+                label,
+                condition,
+                statements_block,
+                ..
+            } => {
+                // `break` in a condition refers to an enclosing loop.
+                self.visit_expression(condition);
+
+                self.loops.push((*kind, *label));
+                self.visit_statements_block(statements_block);
+                self.loops.pop();
+            }
+            Expression::Lambda { .. } => {
+                let loops = std::mem::take(&mut self.loops);
+                walk_expression(self, expression);
+                self.loops = loops;
+            }
+            _ => walk_expression(self, expression),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Checks, that patterns in `let` statements match all possible values, e.g.
+//! `let Some(x) = y;` is rejected, and that `else` blocks of `let ... else`
+//! statements diverge.
+
+#[cfg(feature = "debug")]
+use std::time::Instant;
+
+use stellar_ast::IdentifierAST;
+use stellar_database::{Database, ModuleId, State, Symbol};
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::{
+    visit::{walk_statement, Visitor},
+    Expression, Pattern, Statement, StatementsBlock, StructFieldPattern,
+};
+#[cfg(feature = "debug")]
+use tracing::trace;
+
+use crate::{
+    diagnostics::{LetElseBlockDoesNotDiverge, RefutablePatternInLet},
+    resolution::resolve_path_silently,
+};
+
+pub struct CheckLetPatterns<'s> {
+    state: &'s mut State,
+    module: ModuleId,
+}
+
+impl<'s> CheckLetPatterns<'s> {
+    pub fn run_all(state: &'s mut State, modules: &FxHashMap<ModuleId, stellar_hir::Module>) {
+        for (module, hir) in modules {
+            CheckLetPatterns::run(state, *module, hir);
+        }
+    }
+
+    /// Checks `let` statements in bodies of functions of a given module.
+    pub fn run(state: &'s mut State, module: ModuleId, hir: &stellar_hir::Module) {
+        #[cfg(feature = "debug")]
+        let now = Instant::now();
+
+        let mut me = CheckLetPatterns { state, module };
+
+        me.visit_module(hir);
+
+        #[cfg(feature = "debug")]
+        trace!(
+            "check_let_patterns_in(module = '{}') <{} us>",
+            module.filepath(me.state.db()),
+            now.elapsed().as_micros()
+        );
+    }
+}
+
+impl Visitor for CheckLetPatterns<'_> {
+    fn visit_statement(&mut self, statement: &Statement) {
+        if let Statement::Let {
+            location,
+            pattern,
+            r#else,
+            ..
+        } = statement
+        {
+            match r#else {
+                Some(block) => {
+                    if !block_diverges(block) {
+                        let location = block.tail_expression.as_ref().map_or_else(
+                            || {
+                                block
+                                    .statements
+                                    .last()
+                                    .map_or(*location, Statement::location)
+                            },
+                            |expression| expression.location(),
+                        );
+
+                        self.state
+                            .diagnostics_mut()
+                            .add_diagnostic(LetElseBlockDoesNotDiverge::new(location));
+                    }
+                }
+                None => {
+                    let refutability = Refutability {
+                        db: self.state.db(),
+                        module: self.module,
+                    };
+
+                    if refutability.is_refutable(pattern) {
+                        self.state
+                            .diagnostics_mut()
+                            .add_diagnostic(RefutablePatternInLet::new(pattern.location()));
+                    }
+                }
+            }
+        }
+
+        walk_statement(self, statement);
+    }
+}
+
+/// Decides whether a pattern can fail to match a value.
+///
+/// Types of values are not known at this point, so patterns, that cannot be
+/// resolved, are assumed to be irrefutable, to avoid false positives.
+pub(super) struct Refutability<'d> {
+    pub(super) db: &'d Database,
+    pub(super) module: ModuleId,
+}
+
+impl Refutability<'_> {
+    pub(super) fn is_refutable(&self, pattern: &Pattern) -> bool {
+        match pattern {
+            Pattern::Wildcard { .. } | Pattern::Rest { .. } => false,
+            Pattern::Literal(..) | Pattern::NegativeNumericLiteral(..) => true,
+            Pattern::Identifier {
+                identifier,
+                pattern,
+                ..
+            } => {
+                // `None` is a path to an enum item, not a binding.
+                if pattern.is_none() && self.resolves_to_refutable_enum_item(&[*identifier]) {
+                    return true;
+                }
+
+                pattern
+                    .as_ref()
+                    .is_some_and(|pattern| self.is_refutable(pattern))
+            }
+            Pattern::Tuple { elements, .. } => self.any_refutable(elements),
+            Pattern::List { inner_patterns, .. } => {
+                // Only `[..]` matches lists of any length.
+                !matches!(inner_patterns.as_slice(), [Pattern::Rest { .. }])
+            }
+            Pattern::Or { left, right, .. } => self.is_refutable(left) && self.is_refutable(right),
+            Pattern::Path { path } => self.resolves_to_refutable_enum_item(&path.identifiers),
+            Pattern::TupleLike {
+                path,
+                inner_patterns,
+                ..
+            } => {
+                self.resolves_to_refutable_enum_item(&path.identifiers)
+                    || self.any_refutable(inner_patterns)
+            }
+            Pattern::Struct { path, fields, .. } => {
+                self.resolves_to_refutable_enum_item(&path.identifiers)
+                    || fields.iter().any(|field| match field {
+                        StructFieldPattern::NotRest {
+                            value_pattern: Some(pattern),
+                            ..
+                        } => self.is_refutable(pattern),
+                        _ => false,
+                    })
+            }
+        }
+    }
+
+    fn any_refutable(&self, patterns: &[Pattern]) -> bool {
+        patterns.iter().any(|pattern| self.is_refutable(pattern))
+    }
+
+    /// Returns `true` if a path refers to an item of an enum, that has other items.
+    fn resolves_to_refutable_enum_item(&self, path: &[IdentifierAST]) -> bool {
+        let Some(Symbol::EnumItem(item)) = resolve_path_silently(self.db, self.module, path) else {
+            return false;
+        };
+
+        item.enum_(self.db).items(self.db).len() > 1
+    }
+}
+
+/// Returns `true` if executing a block never continues past its end, i.e.
+/// it always returns, breaks or continues.
+fn block_diverges(block: &StatementsBlock) -> bool {
+    block.statements.iter().any(|statement| match statement {
+        Statement::Return { .. } | Statement::Break { .. } | Statement::Continue { .. } => true,
+        Statement::Expression { expression, .. } => expression_diverges(expression),
+        Statement::Let { .. } | Statement::Defer { .. } => false,
+    }) || block
+        .tail_expression
+        .as_deref()
+        .is_some_and(expression_diverges)
+}
+
+fn expression_diverges(expression: &Expression) -> bool {
+    match expression {
+        Expression::StatementsBlock { block, .. } => block_diverges(block),
+        Expression::If {
+            if_blocks,
+            r#else: Some(r#else),
+            ..
+        } => if_blocks.iter().all(|(_, block)| block_diverges(block)) && block_diverges(r#else),
+        Expression::Match { block, .. } => {
+            !block.is_empty() && block.iter().all(|item| expression_diverges(&item.right))
+        }
+        _ => false,
+    }
+}
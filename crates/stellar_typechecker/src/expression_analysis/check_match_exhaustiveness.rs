@@ -0,0 +1,211 @@
+//! Checks, that items of match expressions match all possible values:
+//!
+//! ```stellar
+//! enum Option[T] { Some(T), None }
+//!
+//! fun foo(x: Option[int32]): int32 {
+//!     match x {                  // error: `Option.None` not covered
+//!         Option.Some(y) -> y,
+//!     }
+//! }
+//! ```
+//!
+//! Guards can be false, so items with guards, e.g. `Option.Some(y) if y > 0 -> y`,
+//! don't cover their patterns.
+//!
+//! Types of matched values are not known at this point, so only matches over
+//! enum items and matches, where every item has a guard, are checked.
+
+#[cfg(feature = "debug")]
+use std::time::Instant;
+
+use stellar_ast::IdentifierAST;
+use stellar_database::{EnumId, EnumItemId, ModuleId, State, Symbol};
+use stellar_fx_hash::{FxHashMap, FxHashSet};
+use stellar_hir::{
+    visit::{walk_expression, Visitor},
+    Expression, MatchExpressionItem, Pattern, StructFieldPattern,
+};
+#[cfg(feature = "debug")]
+use tracing::trace;
+
+use super::check_let_patterns::Refutability;
+use crate::{diagnostics::NonExhaustiveMatch, resolution::resolve_path_silently};
+
+pub struct CheckMatchExhaustiveness<'s> {
+    state: &'s mut State,
+    module: ModuleId,
+}
+
+impl<'s> CheckMatchExhaustiveness<'s> {
+    pub fn run_all(state: &'s mut State, modules: &FxHashMap<ModuleId, stellar_hir::Module>) {
+        for (module, hir) in modules {
+            CheckMatchExhaustiveness::run(state, *module, hir);
+        }
+    }
+
+    /// Checks match expressions in bodies of functions of a given module.
+    pub fn run(state: &'s mut State, module: ModuleId, hir: &stellar_hir::Module) {
+        #[cfg(feature = "debug")]
+        let now = Instant::now();
+
+        let mut me = CheckMatchExhaustiveness { state, module };
+
+        me.visit_module(hir);
+
+        #[cfg(feature = "debug")]
+        trace!(
+            "check_match_exhaustiveness_in(module = '{}') <{} us>",
+            module.filepath(me.state.db()),
+            now.elapsed().as_micros()
+        );
+    }
+
+    /// Returns patterns, that are not covered by items of a match expression.
+    fn missing_patterns(&self, block: &[MatchExpressionItem]) -> Vec<String> {
+        let refutability = Refutability {
+            db: self.state.db(),
+            module: self.module,
+        };
+
+        let unguarded = block
+            .iter()
+            .filter(|item| item.guard.is_none())
+            .map(|item| &item.left)
+            .collect::<Vec<_>>();
+
+        if unguarded
+            .iter()
+            .any(|pattern| !refutability.is_refutable(pattern))
+        {
+            return vec![];
+        }
+
+        let Some(enum_) = block
+            .iter()
+            .find_map(|item| self.enum_items_of(&item.left).first().copied())
+            .map(|item| item.enum_(self.state.db()))
+        else {
+            // Nothing is known about matched values, unless every item has a guard.
+            return if unguarded.is_empty() && !block.is_empty() {
+                vec!["_".to_owned()]
+            } else {
+                vec![]
+            };
+        };
+
+        let covered = unguarded
+            .iter()
+            .flat_map(|pattern| self.covered_enum_items(&refutability, pattern))
+            .collect::<FxHashSet<_>>();
+
+        self.missing_enum_items(enum_, &covered)
+    }
+
+    fn missing_enum_items(&self, enum_: EnumId, covered: &FxHashSet<EnumItemId>) -> Vec<String> {
+        let db = self.state.db();
+        let enum_name = enum_.signature(db).name(db).id;
+
+        enum_
+            .items_ordered(db)
+            .filter(|item| !covered.contains(item))
+            .map(|item| format!("{enum_name}.{}", item.name(db).id))
+            .collect()
+    }
+
+    /// Returns enum items, that a pattern refers to, e.g. `Option.Some` for
+    /// `Option.Some(x)`.
+    fn enum_items_of(&self, pattern: &Pattern) -> Vec<EnumItemId> {
+        match pattern {
+            Pattern::Identifier {
+                identifier,
+                pattern: None,
+                ..
+            } => self.resolve_enum_item(&[*identifier]).into_iter().collect(),
+            Pattern::Identifier {
+                pattern: Some(pattern),
+                ..
+            } => self.enum_items_of(pattern),
+            Pattern::Path { path }
+            | Pattern::TupleLike { path, .. }
+            | Pattern::Struct { path, .. } => self
+                .resolve_enum_item(&path.identifiers)
+                .into_iter()
+                .collect(),
+            Pattern::Or { left, right, .. } => {
+                let mut items = self.enum_items_of(left);
+                items.extend(self.enum_items_of(right));
+                items
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Returns enum items, all values of which are matched by a pattern, e.g.
+    /// `Option.Some` for `Option.Some(_)`, but not for `Option.Some(1)`.
+    fn covered_enum_items(
+        &self,
+        refutability: &Refutability<'_>,
+        pattern: &Pattern,
+    ) -> Vec<EnumItemId> {
+        let covers_payload = match pattern {
+            Pattern::TupleLike { inner_patterns, .. } => inner_patterns
+                .iter()
+                .all(|pattern| !refutability.is_refutable(pattern)),
+            Pattern::Struct { fields, .. } => fields.iter().all(|field| match field {
+                StructFieldPattern::NotRest {
+                    value_pattern: Some(pattern),
+                    ..
+                } => !refutability.is_refutable(pattern),
+                _ => true,
+            }),
+            Pattern::Identifier {
+                pattern: Some(pattern),
+                ..
+            } => return self.covered_enum_items(refutability, pattern),
+            Pattern::Or { left, right, .. } => {
+                let mut items = self.covered_enum_items(refutability, left);
+                items.extend(self.covered_enum_items(refutability, right));
+                return items;
+            }
+            _ => true,
+        };
+
+        if covers_payload {
+            self.enum_items_of(pattern)
+        } else {
+            vec![]
+        }
+    }
+
+    fn resolve_enum_item(&self, path: &[IdentifierAST]) -> Option<EnumItemId> {
+        match resolve_path_silently(self.state.db(), self.module, path) {
+            Some(Symbol::EnumItem(item)) => Some(item),
+            _ => None,
+        }
+    }
+}
+
+impl Visitor for CheckMatchExhaustiveness<'_> {
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Expression::Match {
+            expression: matched,
+            block,
+            ..
+        } = expression
+        {
+            let missing_patterns = self.missing_patterns(block);
+
+            if !missing_patterns.is_empty() {
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(NonExhaustiveMatch::new(
+                        matched.location(),
+                        missing_patterns,
+                    ));
+            }
+        }
+
+        walk_expression(self, expression);
+    }
+}
@@ -0,0 +1,2271 @@
+//! Infers types of expressions in function bodies, as much as they can be
+//! inferred from literals, local variables and signatures of called functions,
+//! and reports type errors:
+//!
+//! * elements of a list literal, e.g. `[1, 2, 3]`, must have the same type `T`,
+//!   and the literal has type `List[T]`,
+//! * only lists can be indexed, e.g. `xs[0]`, and indices must be integers,
+//! * struct expressions, e.g. `Point { x: 1, y: 2 }`, must specify every
+//!   field of the struct exactly once, unless they have a base, e.g.
+//!   `Point { x: 1, ..other }`, which must be a value of the same struct.
+//!   Values must have types of the fields and private fields of structs
+//!   from other modules cannot be specified,
+//! * accessed fields, e.g. `point.x`, must exist in the struct and must be
+//!   public, if the struct is defined in another module. The same applies to
+//!   elements of tuple-like structs, e.g. `wrapper.0`,
+//! * only tuples and tuple-like structs can be indexed with tuple indices,
+//!   e.g. `pair.0`, and the indices must be in range,
+//! * assigned values must have types of places they are assigned to,
+//! * guards of match expression items, e.g. `x if x > 0 -> ...`, must be booleans,
+//! * tuple patterns must have as many elements as matched tuples, unless they
+//!   contain a rest pattern, e.g. `(first, .., last)`, in which case they
+//!   cannot have more elements, than the tuples,
+//! * values of `break` statements, that exit the same `loop`, must have the
+//!   same type `T`, and the loop has type `T`, e.g. `loop { break 42; }`.
+//!   Loops without `break` values have type `()`,
+//! * values of numeric literals must fit into their types, e.g. `300` doesn't
+//!   fit into `uint8`. Suffixes set types of literals, e.g. `255u8` is
+//!   `uint8`, and negated literals are checked together with the sign, so
+//!   `-128i8` is accepted, while `-129i8` is not,
+//! * names of types cannot be used as values, e.g. `let p = Point;`, where
+//!   `Point` is a struct,
+//! * labeled arguments of calls, e.g. `y: 20` in `draw(x: 10, y: 20)`, must
+//!   name parameters of the called function, that are not passed by other
+//!   arguments, and positional arguments cannot follow labeled ones,
+//! * identifier patterns of match expression items, that name items of the
+//!   matched enum, e.g. `Red` in `match color { Red -> ... }`, match the
+//!   items. Ones, that differ from names of the items only in case, e.g.
+//!   `red`, bind new variables matching everything, so they are reported.
+//! * operands of binary operators must have types, that the operators are
+//!   defined for (see [`operator_accepts()`]), and both operands must have
+//!   the same type, e.g. `1 + "x"` and `x + y`, where `x` is `int32` and `y`
+//!   is `int64`, are reported. Comparisons, equality and logical operators
+//!   return `bool`, other operators return the type of their operands.
+//!
+//! Element types of empty list literals are inferred from how the lists are
+//! used later:
+//!
+//! ```stellar
+//! fun main() {
+//!     let xs = [];          // `List[int32]`
+//!     let x: int32 = xs[0];
+//! }
+//! ```
+
+#[cfg(feature = "debug")]
+use std::time::Instant;
+
+use stellar_ast::{
+    BinaryOperator, IdentifierAST, RawBinaryOperator, RawPrefixOperator, Visibility,
+};
+use stellar_database::{
+    symbol::BuiltinSymbolId,
+    ty::{list_of, Type, TypeConstructor},
+    EnumId, ModuleId, State, Symbol, DUMMY_MODULE_ID,
+};
+use stellar_filesystem::location::{HasLocation, Location};
+use stellar_fx_hash::{FxHashMap, FxHashSet};
+use stellar_hir::{
+    visit::{walk_expression, walk_function, walk_module_item, walk_statement, Visitor},
+    CallArgument, EnumItem, Expression, Function, FunctionParameter, GenericParameter,
+    LambdaFunctionParameter, Literal, LoopKind, MatchExpressionItem, ModuleItem,
+    NotSelfFunctionParameter, Pattern, Statement, StatementsBlock, StructExpressionItem,
+};
+use stellar_interner::IdentifierId;
+#[cfg(feature = "debug")]
+use tracing::trace;
+
+use super::{
+    collect_implementations, expand_type_aliases, expression_path,
+    infer_lambda_types::lambda_type_to_string,
+    match_call_arguments, parameter_names, resolve_called_function, resolve_type,
+    tuple_pattern_elements,
+    unification::{Substitution, TypeVariableKind, TypeVariables},
+    ArgumentMismatch,
+};
+use crate::{
+    diagnostics::{
+        ArgumentAfterSpread, ArgumentPassedMoreThanOnce, AssignmentTypeMismatch,
+        BreakValueTypeMismatch, DefaultValueReferencesParameter, DefaultValueTypeMismatch,
+        DuplicateStructExpressionField, EnumItemShadowedByBinding, ExpectedValueFoundType,
+        FieldAccessOnNonStruct, IndexOnNonList, InvalidBinaryOperands, InvalidCast,
+        LambdaTypeMismatch, ListElementTypeMismatch, MissingArguments,
+        MissingStructExpressionFields, MixedNumericOperands, NonBooleanMatchGuard, NonIntegerIndex,
+        NumericLiteralOutOfRange, PositionalArgumentAfterLabeled, PrivateFieldAccess,
+        PrivateTupleLikeStructConstructor, SpreadArgumentArityMismatch, SpreadArgumentNotTuple,
+        SpreadElementTypeMismatch, StructExpressionFieldTypeMismatch, StructUpdateBaseTypeMismatch,
+        TupleIndexOnNonTuple, TupleIndexOutOfRange, TupleLikeStructArityMismatch,
+        TuplePatternArityMismatch, UnknownArgumentLabel, UnknownField,
+    },
+    resolution::{check_deprecated_usage, resolve_path_silently, scope::ScopeTree},
+    signature_analysis::check_interface_conformance::type_to_string as hir_type_to_string,
+    suggestions::{find_similar_name, find_similar_names},
+};
+
+/// Maximum number of similar field names suggested for an unknown field.
+const MAX_FIELD_SUGGESTIONS: usize = 3;
+
+pub struct InferExpressionTypes<'s, 'h> {
+    state: &'s mut State,
+    modules: &'h FxHashMap<ModuleId, stellar_hir::Module>,
+    module: ModuleId,
+
+    /// Symbol of the module item, that is currently being visited.
+    item: Option<Symbol>,
+
+    /// Scopes of the function, that is currently being visited.
+    scopes: Option<ScopeTree>,
+
+    /// Types of local variables by locations of their definitions.
+    bindings: FxHashMap<Location, Type>,
+
+    /// Types of expressions by their locations.
+    types: FxHashMap<Location, Type>,
+    substitution: Substitution,
+    type_variables: TypeVariables,
+
+    /// Loops, that enclose the currently visited expression, from the
+    /// outermost one.
+    loops: Vec<EnclosingLoop>,
+
+    /// Numeric literals, that are checked to fit into their types, once the
+    /// types are inferred.
+    numeric_literals: Vec<NumericLiteral>,
+}
+
+/// A loop, that encloses the currently visited expression.
+struct EnclosingLoop {
+    kind: LoopKind,
+    label: Option<IdentifierAST>,
+
+    /// The type of `break` values, that exit the loop, and the location of
+    /// the first of them.
+    break_type: Option<(Type, Location)>,
+}
+
+/// The type, that a lambda is expected to have, because of the parameter
+/// of a function it is passed to.
+struct ExpectedLambdaType<'a> {
+    ty: &'a Type,
+
+    /// The type as it is written in the function signature.
+    hir: &'a stellar_hir::Type,
+}
+
+/// A numeric literal, whose value is checked to fit into its type.
+struct NumericLiteral {
+    /// Location of the literal, including `-`, if the literal is negated.
+    location: Location,
+    literal: Literal,
+    negative: bool,
+    ty: Type,
+}
+
+impl<'s, 'h> InferExpressionTypes<'s, 'h> {
+    /// Infers types of expressions and returns them by expression locations.
+    /// Expressions, whose types are not known, are not included.
+    ///
+    /// **Note**: type variables, that cannot be inferred, are left in the types.
+    pub fn run_all(
+        state: &'s mut State,
+        modules: &'h FxHashMap<ModuleId, stellar_hir::Module>,
+    ) -> FxHashMap<Location, Type> {
+        let mut me = Self::new(state, modules, DUMMY_MODULE_ID);
+
+        for (module, hir) in modules {
+            #[cfg(feature = "debug")]
+            let now = Instant::now();
+
+            me.module = *module;
+            me.visit_module(hir);
+
+            #[cfg(feature = "debug")]
+            trace!(
+                "infer_expression_types_in(module = '{}') <{} us>",
+                module.filepath(me.state.db()),
+                now.elapsed().as_micros()
+            );
+        }
+
+        me.finish()
+    }
+
+    /// Infers types of expressions in a body of a given function defined in
+    /// a given module.
+    ///
+    /// Unlike [`InferExpressionTypes::run_all()`], types of numeric literals,
+    /// that are not constrained, are inferred to be `int32` and `float64`.
+    pub fn run_function(
+        state: &'s mut State,
+        modules: &'h FxHashMap<ModuleId, stellar_hir::Module>,
+        module: ModuleId,
+        function: &Function,
+    ) -> FxHashMap<Location, Type> {
+        let mut me = Self::new(state, modules, module);
+
+        me.item = module.module_item_symbol_or_none(me.state.db(), function.signature.name.id);
+        me.visit_function_body(function);
+        me.substitution.apply_numeric_defaults();
+
+        me.finish()
+    }
+
+    fn new(
+        state: &'s mut State,
+        modules: &'h FxHashMap<ModuleId, stellar_hir::Module>,
+        module: ModuleId,
+    ) -> Self {
+        let implementations = collect_implementations(state.db(), modules);
+
+        Self {
+            state,
+            modules,
+            module,
+            item: None,
+            scopes: None,
+            bindings: FxHashMap::default(),
+            types: FxHashMap::default(),
+            substitution: Substitution::with_implementations(implementations),
+            type_variables: TypeVariables::new(),
+            loops: vec![],
+            numeric_literals: vec![],
+        }
+    }
+
+    fn finish(mut self) -> FxHashMap<Location, Type> {
+        self.check_numeric_literals();
+
+        self.types
+            .iter()
+            .map(|(location, ty)| (*location, self.substitution.apply(ty)))
+            .collect()
+    }
+
+    fn visit_function_body(&mut self, function: &Function) {
+        self.scopes = Some(ScopeTree::build(function));
+        self.bindings.clear();
+
+        // Generic parameters can be any types, so values of these types are not checked.
+        let generic_parameters = function
+            .signature
+            .generic_parameters
+            .iter()
+            .map(|parameter| (parameter.name.id, Type::Unknown))
+            .collect::<FxHashMap<_, _>>();
+
+        for parameter in &function.signature.parameters {
+            if let FunctionParameter::NotSelfParameter(parameter) = parameter {
+                let ty = self.resolve_type(self.module, &parameter.ty, &generic_parameters);
+                self.record_pattern(&parameter.pattern, &ty);
+
+                if let Some(default_value) = &parameter.default_value {
+                    self.check_default_value(parameter, default_value, &ty);
+                }
+            }
+        }
+
+        walk_function(self, function);
+        self.scopes = None;
+    }
+
+    /// Checks, that the default value of a parameter doesn't use other
+    /// parameters and has the type of the parameter.
+    fn check_default_value(
+        &mut self,
+        parameter: &NotSelfFunctionParameter,
+        default_value: &Expression,
+        ty: &Type,
+    ) {
+        let mut identifiers = UsedIdentifiers::default();
+        identifiers.visit_expression(default_value);
+
+        // Only parameters are defined before the function body, so every
+        // local binding visible in a default value is a parameter.
+        for identifier in identifiers.0 {
+            if let Some(parameter_location) = self.binding_of(identifier) {
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(DefaultValueReferencesParameter::new(
+                        identifier.location,
+                        identifier.id,
+                        parameter_location,
+                    ));
+            }
+        }
+
+        let default_value_type = self.type_of(default_value);
+
+        if !self.substitution.unify(ty, &default_value_type) {
+            let diagnostic = DefaultValueTypeMismatch::new(
+                default_value.location(),
+                self.type_to_string(ty),
+                self.type_to_string(&default_value_type),
+                parameter.ty.location(),
+            );
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+        }
+    }
+
+    /// Converts a HIR type into a type.
+    ///
+    /// Type aliases are expanded, and the ones, that cannot be expanded, are
+    /// converted into [`Type::Unknown`] to avoid false errors.
+    fn resolve_type(
+        &mut self,
+        module: ModuleId,
+        ty: &stellar_hir::Type,
+        generic_parameters: &FxHashMap<IdentifierId, Type>,
+    ) -> Type {
+        let db = self.state.db();
+        let ty = resolve_type(db, module, ty, generic_parameters, &mut self.type_variables);
+
+        erase_type_aliases(expand_type_aliases(
+            db,
+            self.state.hir(),
+            ty,
+            &mut self.type_variables,
+            0,
+        ))
+    }
+
+    /// Records types of names bound by a pattern.
+    fn record_pattern(&mut self, pattern: &Pattern, ty: &Type) {
+        match (pattern, self.substitution.apply(ty)) {
+            (Pattern::Identifier { identifier, .. }, ty) => {
+                self.bindings.insert(identifier.location, ty);
+            }
+            (Pattern::Tuple { location, elements }, Type::Tuple { element_types }) => {
+                let Some(elements) = tuple_pattern_elements(elements, &element_types) else {
+                    let diagnostic = TuplePatternArityMismatch::new(
+                        *location,
+                        element_types.len(),
+                        elements.iter().filter(|element| !element.is_rest()).count(),
+                        elements.iter().any(Pattern::is_rest),
+                    );
+                    self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+                    return;
+                };
+
+                for (element, types) in elements {
+                    // Elements of a rest pattern are not bound to any name.
+                    if let [ty] = types {
+                        if !element.is_rest() {
+                            self.record_pattern(element, ty);
+                        }
+                    }
+                }
+            }
+            (Pattern::List { inner_patterns, .. }, ty) => {
+                let element_type = self.type_variables.fresh(pattern.location());
+
+                if !self.substitution.unify(&ty, &list_of(element_type.clone())) {
+                    return;
+                }
+
+                for inner_pattern in inner_patterns {
+                    match inner_pattern {
+                        // `tail @ ..` is bound to a list of the remaining elements.
+                        Pattern::Identifier {
+                            identifier: rest,
+                            pattern: Some(pattern),
+                            ..
+                        } if pattern.is_rest() => {
+                            let ty = self.substitution.apply(&list_of(element_type.clone()));
+                            self.bindings.insert(rest.location, ty);
+                        }
+                        Pattern::Rest { .. } => {}
+                        inner_pattern => self.record_pattern(inner_pattern, &element_type),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn type_of(&mut self, expression: &Expression) -> Type {
+        let ty = match expression {
+            Expression::Literal(literal) => self.type_of_literal(literal),
+            Expression::List { location, elements } => self.type_of_list(*location, elements),
+            Expression::Index {
+                location,
+                left,
+                index,
+            } => self.type_of_index(*location, left, index),
+            Expression::Tuple { elements, .. } => {
+                if elements.is_empty() {
+                    Type::Unit
+                } else {
+                    Type::Tuple {
+                        element_types: elements
+                            .iter()
+                            .map(|element| self.type_of(element))
+                            .collect(),
+                    }
+                }
+            }
+            Expression::Assign { left, right, .. } => self.type_of_assignment(left, right),
+            Expression::FieldAccess { left, right, .. } if !self.is_global_path(expression) => {
+                self.type_of_field_access(left, *right)
+            }
+            Expression::FieldAccess { location, .. } => {
+                self.type_of_global_path(*location, expression)
+            }
+            Expression::TupleIndex {
+                location,
+                left,
+                index,
+            } => self.type_of_tuple_index(*location, left, *index),
+            Expression::Struct {
+                location,
+                left,
+                fields,
+                base,
+            } => self.type_of_struct_expression(*location, left, fields, base.as_deref()),
+            Expression::Identifier(identifier) => self.type_of_identifier(*identifier),
+            Expression::Prefix {
+                location,
+                inner,
+                operator,
+            } if operator.raw == RawPrefixOperator::Minus => {
+                self.type_of_negation(*location, inner)
+            }
+            Expression::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => self.type_of_binary(left, *operator, right),
+            Expression::Call {
+                location,
+                callee,
+                arguments,
+                ..
+            } => self.type_of_call(*location, callee, arguments),
+            Expression::As {
+                location,
+                left,
+                right,
+            } => self.type_of_cast(*location, left, right),
+            Expression::StatementsBlock { block, .. } => self.type_of_block(block),
+            Expression::If {
+                location,
+                if_blocks,
+                r#else,
+            } => self.type_of_if(*location, if_blocks, r#else.as_ref()),
+            Expression::Match {
+                location,
+                expression,
+                block,
+            } => self.type_of_match(*location, expression, block),
+            Expression::While {
+                kind,
+                label,
+                condition,
+                statements_block,
+                ..
+            } => self.type_of_loop(*kind, *label, condition, statements_block),
+            Expression::Lambda {
+                location,
+                parameters,
+                return_type,
+                value,
+                ..
+            } => self.type_of_lambda(*location, parameters, return_type.as_ref(), value, None),
+            _ => {
+                walk_expression(self, expression);
+                Type::Unknown
+            }
+        };
+
+        if ty != Type::Unknown {
+            self.types.insert(expression.location(), ty.clone());
+        }
+
+        ty
+    }
+
+    /// Infers the type of a lambda, e.g. `fun(int32): int32` for `|x| x + 1`.
+    ///
+    /// Unannotated parameters are inferred from the expected type, if the
+    /// lambda is passed to a function, and unannotated return types from the
+    /// body. Lambdas, that don't have the expected type, are reported.
+    fn type_of_lambda(
+        &mut self,
+        location: Location,
+        parameters: &[LambdaFunctionParameter],
+        return_type: Option<&stellar_hir::Type>,
+        value: &Expression,
+        expected: Option<ExpectedLambdaType<'_>>,
+    ) -> Type {
+        let no_generic_parameters = FxHashMap::default();
+
+        let parameter_types = parameters
+            .iter()
+            .map(|parameter| match &parameter.ty {
+                Some(ty) => self.resolve_type(self.module, ty, &no_generic_parameters),
+                None => self.type_variables.fresh(parameter.name.location),
+            })
+            .collect::<Vec<_>>();
+
+        // Parameters are inferred before the body, so that the body can use
+        // their types. Mismatches are reported, once the body is inferred.
+        if let Some(Type::Function {
+            parameter_types: expected_parameter_types,
+            ..
+        }) = expected
+            .as_ref()
+            .map(|expected| self.substitution.apply(expected.ty))
+        {
+            if expected_parameter_types.len() == parameter_types.len() {
+                for (parameter_type, expected_type) in
+                    parameter_types.iter().zip(&expected_parameter_types)
+                {
+                    self.substitution.unify(parameter_type, expected_type);
+                }
+            }
+        }
+
+        for (parameter, ty) in parameters.iter().zip(&parameter_types) {
+            self.bindings.insert(parameter.name.location, ty.clone());
+        }
+
+        // `break` inside of a lambda cannot exit loops around it.
+        let loops = std::mem::take(&mut self.loops);
+        let value_type = self.type_of(value);
+        self.loops = loops;
+
+        let resolved_return_type = match return_type {
+            Some(ty) => {
+                let annotated_type = self.resolve_type(self.module, ty, &no_generic_parameters);
+
+                // Mismatches between annotations and values are not reported here.
+                self.substitution.unify(&annotated_type, &value_type);
+                annotated_type
+            }
+            None => value_type,
+        };
+
+        let ty = Type::Function {
+            parameter_types,
+            return_type: Box::new(resolved_return_type),
+        };
+
+        if let Some(expected) = expected {
+            if !self.substitution.unify(&ty, expected.ty) {
+                self.report_lambda_type_mismatch(location, parameters, return_type, &ty, &expected);
+            }
+        }
+
+        self.types.insert(location, ty.clone());
+
+        ty
+    }
+
+    /// Reports a lambda, that doesn't have the type of the parameter it is
+    /// passed to.
+    fn report_lambda_type_mismatch(
+        &mut self,
+        location: Location,
+        parameters: &[LambdaFunctionParameter],
+        return_type: Option<&stellar_hir::Type>,
+        ty: &Type,
+        expected: &ExpectedLambdaType<'_>,
+    ) {
+        let found_return_type = match (return_type, ty) {
+            (Some(return_type), _) => hir_type_to_string(return_type),
+            (None, Type::Function { return_type, .. }) => self.type_to_string(return_type),
+            (None, _) => "_".to_owned(),
+        };
+
+        let diagnostic = LambdaTypeMismatch::new(
+            location,
+            hir_type_to_string(expected.hir),
+            lambda_type_to_string(parameters, &found_return_type),
+            expected.hir.location(),
+        );
+        self.state.diagnostics_mut().add_diagnostic(diagnostic);
+    }
+
+    fn type_of_literal(&mut self, literal: &Literal) -> Type {
+        let builtin = match literal {
+            Literal::Integer { .. } | Literal::Float { .. } => {
+                return self.type_of_numeric_literal(literal.location(), literal, false);
+            }
+            Literal::String { .. } => BuiltinSymbolId::String,
+            Literal::Character { .. } => BuiltinSymbolId::Char,
+            Literal::Boolean { .. } => BuiltinSymbolId::Bool,
+        };
+
+        Type::new_primitive(Symbol::BuiltinSymbol(builtin))
+    }
+
+    /// Infers the type of an integer or a float literal: `255u8` has type
+    /// `uint8`, and literals without suffixes get type variables, that are
+    /// `int32` and `float64` by default.
+    fn type_of_numeric_literal(
+        &mut self,
+        location: Location,
+        literal: &Literal,
+        negative: bool,
+    ) -> Type {
+        let (suffix, kind) = match literal {
+            Literal::Integer { suffix, .. } => (*suffix, TypeVariableKind::Integer),
+            Literal::Float { suffix, .. } => (*suffix, TypeVariableKind::Float),
+            _ => unreachable!("only numeric literals have suffixes"),
+        };
+
+        let ty = match suffix {
+            Some(suffix) => Type::new_primitive(Symbol::BuiltinSymbol(suffix.into())),
+            None => self.fresh_variable_of_kind(literal.location(), kind),
+        };
+
+        self.numeric_literals.push(NumericLiteral {
+            location,
+            literal: literal.clone(),
+            negative,
+            ty: ty.clone(),
+        });
+
+        ty
+    }
+
+    /// Infers the type of a cast, e.g. `x as float64`, and checks, that
+    /// values of the left operand's type can be cast to the type, see
+    /// [`is_allowed_cast()`].
+    fn type_of_cast(
+        &mut self,
+        location: Location,
+        left: &Expression,
+        right: &stellar_hir::Type,
+    ) -> Type {
+        let to = self.resolve_type(self.module, right, &FxHashMap::default());
+        let from = self.type_of(left);
+        let from = self.substitution.apply(&from);
+
+        if matches!(from, Type::Unknown | Type::Never) || to == Type::Unknown {
+            return to;
+        }
+
+        let (from_kind, to_kind) = (self.cast_type(&from), self.cast_type(&to));
+
+        // Only `uint32` can be cast to `char`, so `65 as char` is `uint32`.
+        if from_kind == CastType::Integer(None) && to_kind == CastType::Char {
+            let uint32 = Type::new_primitive(Symbol::BuiltinSymbol(BuiltinSymbolId::Uint32));
+            self.substitution.unify(&from, &uint32);
+        }
+
+        // Casts to the same type and to interface objects are coercions.
+        if is_allowed_cast(from_kind, to_kind) || self.substitution.unify(&to, &from) {
+            return to;
+        }
+
+        let diagnostic = InvalidCast::new(
+            location,
+            self.type_to_string(&from),
+            self.type_to_string(&to),
+            matches!(&to, Type::Constructor(constructor) if constructor.symbol.is_interface()),
+        );
+        self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+        to
+    }
+
+    /// Classifies a type for checking casts.
+    fn cast_type(&self, ty: &Type) -> CastType {
+        match ty {
+            Type::Variable(variable) => match self.substitution.kind(variable.id()) {
+                Some(TypeVariableKind::Integer) => CastType::Integer(None),
+                Some(TypeVariableKind::Float) => CastType::Float,
+                None => CastType::Other,
+            },
+            Type::Constructor(TypeConstructor {
+                symbol: Symbol::BuiltinSymbol(builtin),
+                ..
+            }) => match builtin {
+                BuiltinSymbolId::Float32 | BuiltinSymbolId::Float64 => CastType::Float,
+                BuiltinSymbolId::Char => CastType::Char,
+                BuiltinSymbolId::Bool => CastType::Bool,
+                builtin if integer_range(*builtin).is_some() => CastType::Integer(Some(*builtin)),
+                _ => CastType::Other,
+            },
+            Type::Constructor(TypeConstructor {
+                symbol: symbol @ Symbol::Enum(_),
+                ..
+            }) => match self.definition_of(*symbol) {
+                Some((_, ModuleItem::Enum(enum_)))
+                    if enum_
+                        .items
+                        .iter()
+                        .all(|item| matches!(item, EnumItem::Just { .. })) =>
+                {
+                    CastType::FieldlessEnum
+                }
+                _ => CastType::Other,
+            },
+            _ => CastType::Other,
+        }
+    }
+
+    /// Infers the type of a negation, e.g. `-1`. Only negated numeric literals
+    /// are inferred for now.
+    fn type_of_negation(&mut self, location: Location, inner: &Expression) -> Type {
+        let Expression::Literal(literal @ (Literal::Integer { .. } | Literal::Float { .. })) =
+            inner
+        else {
+            self.type_of(inner);
+            return Type::Unknown;
+        };
+
+        let ty = self.type_of_numeric_literal(location, literal, true);
+        self.types.insert(inner.location(), ty.clone());
+
+        ty
+    }
+
+    /// Infers the type of a binary expression, e.g. `a + b`, and checks, that
+    /// the operator is defined for types of the operands, see
+    /// [`operator_accepts()`].
+    fn type_of_binary(
+        &mut self,
+        left: &Expression,
+        operator: BinaryOperator,
+        right: &Expression,
+    ) -> Type {
+        let left_type = self.type_of(left);
+        let right_type = self.type_of(right);
+
+        let returns_bool = matches!(
+            operator.raw,
+            RawBinaryOperator::Less
+                | RawBinaryOperator::LessEq
+                | RawBinaryOperator::Greater
+                | RawBinaryOperator::GreaterEq
+                | RawBinaryOperator::DoubleEq
+                | RawBinaryOperator::BangEq
+                | RawBinaryOperator::DoubleAmpersand
+                | RawBinaryOperator::DoubleOr
+        );
+        let result = if returns_bool {
+            Type::new_primitive(Symbol::BuiltinSymbol(BuiltinSymbolId::Bool))
+        } else {
+            left_type.clone()
+        };
+
+        let (Some(left_kind), Some(right_kind)) = (
+            self.operand_type(&left_type),
+            self.operand_type(&right_type),
+        ) else {
+            return if returns_bool { result } else { Type::Unknown };
+        };
+
+        if operator_accepts(operator.raw, left_kind) && operator_accepts(operator.raw, right_kind) {
+            if self.substitution.unify(&left_type, &right_type) {
+                return result;
+            }
+
+            if left_kind == right_kind
+                && matches!(left_kind, OperandType::Integer | OperandType::Float)
+            {
+                let diagnostic = MixedNumericOperands::new(
+                    operator,
+                    self.type_to_string(&left_type),
+                    left.location(),
+                    self.type_to_string(&right_type),
+                    right.location(),
+                );
+                self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+                return result;
+            }
+        }
+
+        let diagnostic = InvalidBinaryOperands::new(
+            operator,
+            self.type_to_string(&left_type),
+            left.location(),
+            self.type_to_string(&right_type),
+            right.location(),
+        );
+        self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+        if returns_bool {
+            result
+        } else {
+            Type::Unknown
+        }
+    }
+
+    /// Classifies a type of an operand of a binary operator, or returns `None`
+    /// if the type is not known.
+    fn operand_type(&self, ty: &Type) -> Option<OperandType> {
+        Some(match self.substitution.apply(ty) {
+            Type::Unknown | Type::Never => return None,
+            Type::Variable(variable) => match self.substitution.kind(variable.id()) {
+                Some(TypeVariableKind::Integer) => OperandType::Integer,
+                Some(TypeVariableKind::Float) => OperandType::Float,
+                None => OperandType::Unconstrained,
+            },
+            Type::Constructor(TypeConstructor {
+                symbol: Symbol::BuiltinSymbol(builtin),
+                ..
+            }) => match builtin {
+                BuiltinSymbolId::Float32 | BuiltinSymbolId::Float64 => OperandType::Float,
+                BuiltinSymbolId::Char => OperandType::Char,
+                BuiltinSymbolId::Bool => OperandType::Bool,
+                BuiltinSymbolId::String => OperandType::String,
+                builtin if integer_range(builtin).is_some() => OperandType::Integer,
+                _ => OperandType::Other,
+            },
+            _ => OperandType::Other,
+        })
+    }
+
+    /// Reports numeric literals, whose values don't fit into their types.
+    /// Literals, whose types are not inferred, are checked against `int32`
+    /// and `float64`.
+    fn check_numeric_literals(&mut self) {
+        let mut substitution = self.substitution.clone();
+        substitution.apply_numeric_defaults();
+
+        for literal in std::mem::take(&mut self.numeric_literals) {
+            let ty = substitution.apply(&literal.ty);
+
+            let Type::Constructor(TypeConstructor {
+                symbol: Symbol::BuiltinSymbol(builtin),
+                ..
+            }) = ty
+            else {
+                continue;
+            };
+
+            let range = match literal.literal {
+                Literal::Integer { value, .. } => {
+                    let Some((min, max)) = integer_range(builtin) else {
+                        continue;
+                    };
+
+                    let value = if literal.negative {
+                        -i128::from(value)
+                    } else {
+                        i128::from(value)
+                    };
+
+                    if (min..=max).contains(&value) {
+                        continue;
+                    }
+
+                    format!("{min}..={max}")
+                }
+                Literal::Float { value, .. } => {
+                    if builtin != BuiltinSymbolId::Float32 || value.abs() <= f64::from(f32::MAX) {
+                        continue;
+                    }
+
+                    format!("{:e}..={:e}", f32::MIN, f32::MAX)
+                }
+                _ => continue,
+            };
+
+            let diagnostic = NumericLiteralOutOfRange::new(
+                literal.location,
+                if literal.negative {
+                    format!("-{}", literal.literal)
+                } else {
+                    literal.literal.to_string()
+                },
+                ty.display(self.state.db()).to_string(),
+                range,
+            );
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+        }
+    }
+
+    fn type_of_list(&mut self, location: Location, elements: &[Expression]) -> Type {
+        let element_type = self.type_variables.fresh(location);
+
+        for element in elements {
+            let ty = self.type_of(element);
+
+            if !self.substitution.unify(&element_type, &ty) {
+                let diagnostic = ListElementTypeMismatch::new(
+                    element.location(),
+                    self.type_to_string(&element_type),
+                    self.type_to_string(&ty),
+                    elements[0].location(),
+                );
+                self.state.diagnostics_mut().add_diagnostic(diagnostic);
+            }
+        }
+
+        list_of(element_type)
+    }
+
+    fn type_of_index(&mut self, location: Location, left: &Expression, index: &Expression) -> Type {
+        let left_type = self.type_of(left);
+        let index_type = self.type_of(index);
+
+        let element_type = self.type_variables.fresh(location);
+
+        if !self
+            .substitution
+            .unify(&left_type, &list_of(element_type.clone()))
+        {
+            let diagnostic = IndexOnNonList::new(left.location(), self.type_to_string(&left_type));
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+            return Type::Unknown;
+        }
+
+        let integer = self.fresh_variable_of_kind(index.location(), TypeVariableKind::Integer);
+
+        if !self.substitution.unify(&index_type, &integer) {
+            let diagnostic =
+                NonIntegerIndex::new(index.location(), self.type_to_string(&index_type));
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+        }
+
+        element_type
+    }
+
+    fn type_of_assignment(&mut self, left: &Expression, right: &Expression) -> Type {
+        let left_type = self.type_of(left);
+        let right_type = self.type_of(right);
+
+        if !self.substitution.unify(&left_type, &right_type) {
+            let diagnostic = AssignmentTypeMismatch::new(
+                right.location(),
+                self.type_to_string(&left_type),
+                self.type_to_string(&right_type),
+                left.location(),
+            );
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+        }
+
+        Type::Unit
+    }
+
+    fn type_of_loop(
+        &mut self,
+        kind: LoopKind,
+        label: Option<IdentifierAST>,
+        condition: &Expression,
+        statements_block: &StatementsBlock,
+    ) -> Type {
+        self.type_of(condition);
+
+        self.loops.push(EnclosingLoop {
+            kind,
+            label,
+            break_type: None,
+        });
+
+        self.visit_statements_block(statements_block);
+
+        let enclosing_loop = self.loops.pop().expect("loop is pushed above");
+
+        match kind {
+            // `loop` without `break` never finishes.
+            LoopKind::Loop => enclosing_loop.break_type.map_or(Type::Never, |(ty, _)| ty),
+            LoopKind::While => Type::Unit,
+        }
+    }
+
+    /// Unifies the type of a `break` value with the types of other values,
+    /// that exit the same `loop`. `break;` has value `()`.
+    fn check_break(
+        &mut self,
+        location: Location,
+        label: Option<IdentifierAST>,
+        value: Option<&Expression>,
+    ) {
+        let ty = value.map_or(Type::Unit, |value| self.type_of(value));
+        let location = value.map_or(location, Expression::location);
+
+        // Unknown labels and `while` loops are reported by `CheckBreakAndContinue`.
+        let Some(enclosing_loop) = self.loops.iter().rposition(|enclosing_loop| {
+            label.map_or(true, |label| {
+                enclosing_loop
+                    .label
+                    .is_some_and(|loop_label| loop_label.id == label.id)
+            })
+        }) else {
+            return;
+        };
+
+        if self.loops[enclosing_loop].kind != LoopKind::Loop {
+            return;
+        }
+
+        let Some((expected, first_break_location)) = self.loops[enclosing_loop].break_type.clone()
+        else {
+            self.loops[enclosing_loop].break_type = Some((ty, location));
+            return;
+        };
+
+        if !self.substitution.unify(&expected, &ty) {
+            let diagnostic = BreakValueTypeMismatch::new(
+                location,
+                self.type_to_string(&expected),
+                self.type_to_string(&ty),
+                first_break_location,
+            );
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+        }
+    }
+
+    /// Infers the type of a block: [`Type::Never`] if one of its statements
+    /// diverges, otherwise the type of its tail expression or `()`, if the
+    /// block doesn't have one.
+    fn type_of_block(&mut self, block: &StatementsBlock) -> Type {
+        let mut diverges = false;
+
+        for statement in &block.statements {
+            match statement {
+                Statement::Expression { expression, .. } => {
+                    let expression_type = self.type_of(expression);
+
+                    diverges |= self.substitution.apply(&expression_type) == Type::Never;
+                }
+                Statement::Return { .. } | Statement::Break { .. } | Statement::Continue { .. } => {
+                    self.visit_statement(statement);
+                    diverges = true;
+                }
+                Statement::Let { .. } | Statement::Defer { .. } => self.visit_statement(statement),
+            }
+        }
+
+        let ty = block
+            .tail_expression
+            .as_ref()
+            .map_or(Type::Unit, |tail_expression| {
+                let ty = self.type_of(tail_expression);
+                self.substitution.apply(&ty)
+            });
+
+        if diverges {
+            Type::Never
+        } else {
+            ty
+        }
+    }
+
+    /// Infers the type of an `if` expression. `if` without `else` has type
+    /// `()`.
+    fn type_of_if(
+        &mut self,
+        location: Location,
+        if_blocks: &[(Expression, StatementsBlock)],
+        r#else: Option<&StatementsBlock>,
+    ) -> Type {
+        let mut branch_types = vec![];
+
+        for (condition, block) in if_blocks {
+            self.type_of(condition);
+            branch_types.push(self.type_of_block(block));
+        }
+
+        let Some(r#else) = r#else else {
+            return Type::Unit;
+        };
+
+        branch_types.push(self.type_of_block(r#else));
+
+        self.unify_branches(location, &branch_types)
+    }
+
+    /// Unifies types of branches of an `if` or a `match` expression, but
+    /// mismatches are not reported here. Branches, that diverge, don't
+    /// affect the resulting type.
+    fn unify_branches(&mut self, location: Location, branch_types: &[Type]) -> Type {
+        if !branch_types.is_empty() && branch_types.iter().all(|ty| *ty == Type::Never) {
+            return Type::Never;
+        }
+
+        let ty = self.type_variables.fresh(location);
+
+        for branch_type in branch_types {
+            self.substitution.unify(&ty, branch_type);
+        }
+
+        ty
+    }
+
+    /// Records types of names bound by patterns of match items and checks,
+    /// that guards of the items are booleans.
+    fn type_of_match(
+        &mut self,
+        location: Location,
+        expression: &Expression,
+        block: &[MatchExpressionItem],
+    ) -> Type {
+        let ty = self.type_of(expression);
+        let bool = Type::new_primitive(Symbol::BuiltinSymbol(BuiltinSymbolId::Bool));
+        let mut branch_types = vec![];
+
+        for item in block {
+            if !self.check_enum_item_identifiers(&item.left, &ty) {
+                self.record_pattern(&item.left, &ty);
+            }
+
+            if let Some(guard) = &item.guard {
+                let guard_type = self.type_of(guard);
+
+                if !self.substitution.unify(&bool, &guard_type) {
+                    let diagnostic = NonBooleanMatchGuard::new(
+                        guard.location(),
+                        self.type_to_string(&guard_type),
+                    );
+                    self.state.diagnostics_mut().add_diagnostic(diagnostic);
+                }
+            }
+
+            let branch_type = self.type_of(&item.right);
+            branch_types.push(self.substitution.apply(&branch_type));
+        }
+
+        self.unify_branches(location, &branch_types)
+    }
+
+    /// Checks identifier patterns of a match expression item against items
+    /// of the matched enum. Returns `true` if the pattern is an identifier,
+    /// that names an item, e.g. `Red` in `match color { Red -> ... }`, and
+    /// therefore matches the item instead of binding a variable.
+    fn check_enum_item_identifiers(&mut self, pattern: &Pattern, ty: &Type) -> bool {
+        let Type::Constructor(TypeConstructor {
+            symbol: Symbol::Enum(enum_),
+            ..
+        }) = self.substitution.apply(ty)
+        else {
+            return false;
+        };
+
+        match pattern {
+            Pattern::Identifier {
+                identifier,
+                mutable: false,
+                pattern: None,
+                ..
+            } => {
+                if enum_.item(self.state.db(), identifier.id).is_some() {
+                    self.types.insert(identifier.location, ty.clone());
+
+                    return true;
+                }
+
+                self.check_enum_item_shadowing(enum_, *identifier);
+
+                false
+            }
+            Pattern::Or { left, right, .. } => {
+                self.check_enum_item_identifiers(left, ty);
+                self.check_enum_item_identifiers(right, ty);
+
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Reports a binding, that differs from a name of an item of a given
+    /// enum only in case, e.g. `red` for `Color.Red`.
+    fn check_enum_item_shadowing(&mut self, enum_: EnumId, binding: IdentifierAST) {
+        let db = self.state.db();
+        let binding_name = binding.id.to_string();
+
+        let Some(item) = enum_.items_ordered(db).find(|item| {
+            item.name(db)
+                .id
+                .to_string()
+                .eq_ignore_ascii_case(&binding_name)
+        }) else {
+            return;
+        };
+
+        let diagnostic = EnumItemShadowedByBinding::new(
+            binding,
+            enum_.signature(db).name(db).id,
+            item.name(db).id,
+        );
+        self.state.diagnostics_mut().add_diagnostic(diagnostic);
+    }
+
+    /// Returns `true` if an expression like `a.b.c` refers to a global name,
+    /// e.g. an enum item or an item of another module, instead of fields of
+    /// a local variable.
+    fn is_global_path(&self, expression: &Expression) -> bool {
+        expression_path(expression)
+            .and_then(|path| path.first().copied())
+            .is_some_and(|first| self.binding_of(first).is_none())
+    }
+
+    /// Finds the definition of a local variable with a given name, that is
+    /// visible at the name's location.
+    fn binding_of(&self, identifier: IdentifierAST) -> Option<Location> {
+        self.scopes.as_ref().and_then(|scopes| {
+            scopes
+                .bindings_at(identifier.location.start)
+                .into_iter()
+                .find(|binding| binding.name.id == identifier.id)
+                .map(|binding| binding.name.location)
+        })
+    }
+
+    /// Infers the type of a struct field access, e.g. `point.x`, and checks,
+    /// that the field exists and is visible.
+    fn type_of_field_access(&mut self, left: &Expression, right: IdentifierAST) -> Type {
+        let left_type = self.type_of(left);
+        let left_type = self.substitution.apply(&left_type);
+
+        let (struct_module, struct_, arguments) = match &left_type {
+            Type::Constructor(TypeConstructor {
+                symbol: symbol @ Symbol::Struct(_),
+                arguments,
+                ..
+            }) => match self.definition_of(*symbol) {
+                Some((module, ModuleItem::Struct(struct_))) => (module, struct_, arguments),
+                _ => return Type::Unknown,
+            },
+            // Fields of values, whose types are not known, cannot be checked.
+            Type::Unknown | Type::Variable(_) | Type::Never => return Type::Unknown,
+            _ => {
+                let diagnostic = FieldAccessOnNonStruct::new(
+                    right.location,
+                    right.id,
+                    self.type_to_string(&left_type),
+                );
+                self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+                return Type::Unknown;
+            }
+        };
+
+        let Some(field) = struct_
+            .fields
+            .iter()
+            .find(|field| field.name.id == right.id)
+        else {
+            let suggestions = find_similar_names(
+                right.id,
+                struct_.fields.iter().map(|field| field.name.id),
+                MAX_FIELD_SUGGESTIONS,
+            );
+            let diagnostic =
+                UnknownField::new(right.location, right.id, self.type_to_string(&left_type))
+                    .with_suggestions(suggestions);
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+            return Type::Unknown;
+        };
+
+        if field.visibility == Visibility::Private && struct_module != self.module {
+            let diagnostic = PrivateFieldAccess::new(
+                right.location,
+                right.id,
+                struct_.name.id,
+                field.name.location,
+            );
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+        }
+
+        let generic_parameters = generic_arguments(&struct_.generic_parameters, arguments);
+
+        self.resolve_type(struct_module, &field.ty, &generic_parameters)
+    }
+
+    /// Infers the type of a tuple index expression, e.g. `pair.0`, and checks,
+    /// that the indexed value is a tuple or a tuple-like struct, and that the
+    /// index is in range.
+    fn type_of_tuple_index(&mut self, location: Location, left: &Expression, index: u32) -> Type {
+        let left_type = self.type_of(left);
+        let left_type = self.substitution.apply(&left_type);
+
+        let (struct_module, struct_, arguments) = match &left_type {
+            Type::Tuple { element_types } => {
+                if let Some(ty) = element_types.get(index as usize) {
+                    return ty.clone();
+                }
+
+                // `.2` in `pair.2`.
+                let diagnostic = TupleIndexOutOfRange::new(
+                    Location {
+                        start: left.location().end,
+                        ..location
+                    },
+                    index,
+                    element_types.len(),
+                );
+                self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+                return Type::Unknown;
+            }
+            Type::Constructor(TypeConstructor {
+                symbol: symbol @ Symbol::TupleLikeStruct(_),
+                arguments,
+                ..
+            }) => match self.definition_of(*symbol) {
+                Some((module, ModuleItem::TupleLikeStruct(struct_))) => {
+                    (module, struct_, arguments)
+                }
+                _ => return Type::Unknown,
+            },
+            // Values, whose types are not known, cannot be checked. Numeric
+            // literals are never tuples, even if their exact types are not
+            // known yet.
+            Type::Unknown | Type::Never => return Type::Unknown,
+            Type::Variable(variable) if self.substitution.kind(variable.id()).is_none() => {
+                return Type::Unknown;
+            }
+            _ => {
+                let diagnostic = TupleIndexOnNonTuple::new(
+                    left.location(),
+                    index,
+                    self.type_to_string(&left_type),
+                );
+                self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+                return Type::Unknown;
+            }
+        };
+
+        // `.0` in `wrapper.0`.
+        let location = Location {
+            start: left.location().end,
+            ..location
+        };
+
+        let Some(field) = struct_.fields.get(index as usize) else {
+            let diagnostic = UnknownField::new(
+                location,
+                IdentifierId::from(index.to_string()),
+                self.type_to_string(&left_type),
+            );
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+            return Type::Unknown;
+        };
+
+        if field.visibility == Visibility::Private && struct_module != self.module {
+            let diagnostic = PrivateFieldAccess::new(
+                location,
+                IdentifierId::from(index.to_string()),
+                struct_.name.id,
+                field.ty.location(),
+            );
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+        }
+
+        let generic_parameters = generic_arguments(&struct_.generic_parameters, arguments);
+
+        self.resolve_type(struct_module, &field.ty, &generic_parameters)
+    }
+
+    /// Infers the type of a struct expression, e.g. `Point { x: 1, y: 2 }`,
+    /// and checks its fields against the definition of the struct.
+    fn type_of_struct_expression(
+        &mut self,
+        location: Location,
+        left: &Expression,
+        fields: &[StructExpressionItem],
+        base: Option<&Expression>,
+    ) -> Type {
+        let (path, type_arguments) = match left {
+            Expression::TypeArguments {
+                left,
+                type_arguments,
+                ..
+            } => (expression_path(left), Some(type_arguments.as_slice())),
+            _ => (expression_path(left), None),
+        };
+
+        let definition = path
+            .and_then(|path| resolve_path_silently(self.state.db(), self.module, &path))
+            .filter(|symbol| matches!(symbol, Symbol::Struct(_)))
+            .and_then(|symbol| Some((symbol, self.definition_of(symbol)?)));
+
+        // Enum items are checked separately.
+        let Some((symbol, (struct_module, ModuleItem::Struct(struct_)))) = definition else {
+            for field in fields {
+                if let Some(value) = &field.value {
+                    self.type_of(value);
+                }
+            }
+
+            if let Some(base) = base {
+                self.type_of(base);
+            }
+
+            return Type::Unknown;
+        };
+
+        let arguments = self.instantiate(&struct_.generic_parameters, type_arguments);
+        let generic_parameters = generic_arguments(&struct_.generic_parameters, &arguments);
+
+        let mut specified_fields = FxHashMap::<IdentifierId, Location>::default();
+
+        for field in fields {
+            // `Point { x }` is a shorthand for `Point { x: x }`.
+            let (value_type, value_location) = match &field.value {
+                Some(value) => (self.type_of(value), value.location()),
+                None => (self.type_of_identifier(field.name), field.name.location),
+            };
+
+            if let Some(first_location) = specified_fields.get(&field.name.id) {
+                let diagnostic = DuplicateStructExpressionField::new(
+                    field.name.id,
+                    *first_location,
+                    field.name.location,
+                );
+                self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+                continue;
+            }
+
+            specified_fields.insert(field.name.id, field.name.location);
+
+            let Some(definition) = struct_
+                .fields
+                .iter()
+                .find(|definition| definition.name.id == field.name.id)
+            else {
+                let suggestions = find_similar_names(
+                    field.name.id,
+                    struct_.fields.iter().map(|definition| definition.name.id),
+                    MAX_FIELD_SUGGESTIONS,
+                );
+                let diagnostic = UnknownField::new(
+                    field.name.location,
+                    field.name.id,
+                    struct_.name.id.to_string(),
+                )
+                .with_suggestions(suggestions);
+                self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+                continue;
+            };
+
+            if definition.visibility == Visibility::Private && struct_module != self.module {
+                let diagnostic = PrivateFieldAccess::new(
+                    field.name.location,
+                    field.name.id,
+                    struct_.name.id,
+                    definition.name.location,
+                );
+                self.state.diagnostics_mut().add_diagnostic(diagnostic);
+            }
+
+            let field_type = self.resolve_type(struct_module, &definition.ty, &generic_parameters);
+
+            if !self.substitution.unify(&field_type, &value_type) {
+                let diagnostic = StructExpressionFieldTypeMismatch::new(
+                    value_location,
+                    self.type_to_string(&field_type),
+                    self.type_to_string(&value_type),
+                    definition.name.location,
+                );
+                self.state.diagnostics_mut().add_diagnostic(diagnostic);
+            }
+        }
+
+        let ty = Type::Constructor(TypeConstructor::new(symbol, arguments));
+
+        // Fields, that are not specified, are taken from the base.
+        if let Some(base) = base {
+            let base_type = self.type_of(base);
+
+            if !self.substitution.unify(&ty, &base_type) {
+                let diagnostic = StructUpdateBaseTypeMismatch::new(
+                    base.location(),
+                    self.type_to_string(&ty),
+                    self.type_to_string(&base_type),
+                );
+                self.state.diagnostics_mut().add_diagnostic(diagnostic);
+            }
+
+            return ty;
+        }
+
+        let missing_fields = struct_
+            .fields
+            .iter()
+            .map(|definition| definition.name.id)
+            .filter(|name| !specified_fields.contains_key(name))
+            .collect::<Vec<_>>();
+
+        if !missing_fields.is_empty() {
+            // The closing brace of the struct expression.
+            let location = Location {
+                start: location.end - 1,
+                ..location
+            };
+
+            let diagnostic =
+                MissingStructExpressionFields::new(location, struct_.name.id, missing_fields);
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+        }
+
+        ty
+    }
+
+    /// Returns types of generic arguments of an item, e.g. of a struct in
+    /// a struct expression. Arguments, that are not specified explicitly,
+    /// are inferred.
+    fn instantiate(
+        &mut self,
+        generic_parameters: &[GenericParameter],
+        type_arguments: Option<&[stellar_hir::Type]>,
+    ) -> Vec<Type> {
+        generic_parameters
+            .iter()
+            .enumerate()
+            .map(
+                |(idx, parameter)| match type_arguments.and_then(|arguments| arguments.get(idx)) {
+                    Some(argument) => {
+                        self.resolve_type(self.module, argument, &FxHashMap::default())
+                    }
+                    None => self.type_variables.fresh(parameter.name.location),
+                },
+            )
+            .collect()
+    }
+
+    /// Finds the HIR of a module item together with the module it is
+    /// defined in.
+    fn definition_of(&self, symbol: Symbol) -> Option<(ModuleId, &'h ModuleItem)> {
+        let signature = symbol.signature(self.state.db());
+        let module = signature.module(self.state.db());
+
+        self.modules
+            .get(&module)?
+            .items
+            .get(signature.node_idx(self.state.db()))
+            .map(|item| (module, item))
+    }
+
+    fn type_of_identifier(&mut self, identifier: IdentifierAST) -> Type {
+        let Some(binding) = self.binding_of(identifier) else {
+            return self
+                .type_of_global_path(identifier.location, &Expression::Identifier(identifier));
+        };
+
+        self.bindings
+            .get(&binding)
+            .cloned()
+            .unwrap_or(Type::Unknown)
+    }
+
+    /// Infers the type of a global name used as a value, e.g. `Option.None`,
+    /// and reports names of types, e.g. `Point` in `let p = Point;`.
+    fn type_of_global_path(&mut self, location: Location, expression: &Expression) -> Type {
+        let Some(symbol) = expression_path(expression)
+            .and_then(|path| resolve_path_silently(self.state.db(), self.module, &path))
+        else {
+            return Type::Unknown;
+        };
+
+        if let Symbol::Enum(_) | Symbol::Struct(_) | Symbol::Interface(_) | Symbol::TypeAlias(_) =
+            symbol
+        {
+            let name = symbol.name(self.state.db());
+            let diagnostic = ExpectedValueFoundType::new(
+                location,
+                symbol.kind(),
+                name.id.to_string(),
+                name.location,
+            );
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+        }
+
+        Type::Unknown
+    }
+
+    /// Infers the type of a call. Arguments are unified with parameter
+    /// types, so that they can constrain types of empty lists, but mismatches
+    /// are not reported here.
+    fn type_of_call(
+        &mut self,
+        location: Location,
+        callee: &Expression,
+        arguments: &[CallArgument],
+    ) -> Type {
+        if let Some(ty) = self.type_of_tuple_like_struct_call(location, callee, arguments) {
+            return ty;
+        }
+
+        let Some((module, function)) = resolve_called_function(
+            self.state.db(),
+            self.modules,
+            self.module,
+            self.scopes.as_ref(),
+            callee,
+        ) else {
+            let callee_type = match callee {
+                // Methods are not resolved yet, so only receivers are inferred.
+                Expression::FieldAccess { left, .. } if !self.is_global_path(callee) => {
+                    self.type_of(left);
+                    Type::Unknown
+                }
+                _ => self.type_of(callee),
+            };
+            let argument_types = arguments
+                .iter()
+                .map(|argument| self.type_of(&argument.value))
+                .collect::<Vec<_>>();
+
+            let Type::Function {
+                parameter_types,
+                return_type,
+            } = self.substitution.apply(&callee_type)
+            else {
+                return Type::Unknown;
+            };
+
+            for (parameter_type, argument_type) in parameter_types.iter().zip(&argument_types) {
+                self.substitution.unify(parameter_type, argument_type);
+            }
+
+            return *return_type;
+        };
+
+        if let Some(symbol) =
+            module.module_item_symbol_or_none(self.state.db(), function.signature.name.id)
+        {
+            check_deprecated_usage(self.state, symbol, callee.location(), self.item);
+        }
+
+        // Every call can instantiate generic parameters of the function differently.
+        let generic_parameters = function
+            .signature
+            .generic_parameters
+            .iter()
+            .map(|parameter| {
+                (
+                    parameter.name.id,
+                    self.type_variables.fresh(parameter.name.location),
+                )
+            })
+            .collect::<FxHashMap<_, _>>();
+
+        let parameter_types = function
+            .signature
+            .parameters
+            .iter()
+            .filter_map(|parameter| match parameter {
+                FunctionParameter::NotSelfParameter(parameter) => Some(&parameter.ty),
+                FunctionParameter::SelfParameter(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let parameters = match_call_arguments(function, arguments);
+        let mut passed = FxHashSet::default();
+        let mut all_matched = true;
+
+        for (argument, parameter) in arguments.iter().zip(parameters) {
+            match parameter {
+                Ok(parameter) if argument.spread.is_some() => {
+                    let argument_type = self.type_of(&argument.value);
+
+                    self.check_spread_argument(
+                        module,
+                        argument,
+                        &argument_type,
+                        &parameter_types[parameter..],
+                        &generic_parameters,
+                    );
+                    passed.extend(parameter..parameter_types.len());
+                }
+                Ok(parameter) => {
+                    let parameter_type_hir = parameter_types[parameter];
+                    let parameter_type =
+                        self.resolve_type(module, parameter_type_hir, &generic_parameters);
+
+                    // Lambdas are inferred from the parameter type, e.g. `x` in
+                    // `apply(|x| x + 1)` gets the type of the parameter of `apply`.
+                    let argument_type = match &argument.value {
+                        Expression::Lambda {
+                            location,
+                            parameters,
+                            return_type,
+                            value,
+                            ..
+                        } => self.type_of_lambda(
+                            *location,
+                            parameters,
+                            return_type.as_ref(),
+                            value,
+                            Some(ExpectedLambdaType {
+                                ty: &parameter_type,
+                                hir: parameter_type_hir,
+                            }),
+                        ),
+                        value => self.type_of(value),
+                    };
+
+                    self.substitution.unify(&parameter_type, &argument_type);
+                    passed.insert(parameter);
+                }
+                Err(mismatch) => {
+                    // Arguments, that don't correspond to any parameter, can still contain lists.
+                    self.type_of(&argument.value);
+
+                    self.report_argument_mismatch(function, argument, mismatch);
+                    all_matched = false;
+                }
+            }
+        }
+
+        // Arguments, that are reported as mismatched, are likely meant for
+        // the parameters, that are left, so these are not reported again.
+        if all_matched {
+            self.check_missing_arguments(location, function, &passed);
+        }
+
+        match &function.signature.return_type {
+            Some(ty) => self.resolve_type(module, ty, &generic_parameters),
+            None => Type::Unit,
+        }
+    }
+
+    /// Infers the type of a tuple-like struct construction, e.g.
+    /// `Point(1.0, 2.0)`, and checks arguments against fields of the struct.
+    /// Returns `None`, if the callee is not a tuple-like struct.
+    fn type_of_tuple_like_struct_call(
+        &mut self,
+        location: Location,
+        callee: &Expression,
+        arguments: &[CallArgument],
+    ) -> Option<Type> {
+        let (path, type_arguments) = match callee {
+            Expression::TypeArguments {
+                left,
+                type_arguments,
+                ..
+            } => (left.as_ref(), Some(type_arguments.as_slice())),
+            _ => (callee, None),
+        };
+
+        if !self.is_global_path(path) {
+            return None;
+        }
+
+        let symbol = expression_path(path)
+            .and_then(|path| resolve_path_silently(self.state.db(), self.module, &path))
+            .filter(|symbol| matches!(symbol, Symbol::TupleLikeStruct(_)))?;
+        let Some((struct_module, ModuleItem::TupleLikeStruct(struct_))) =
+            self.definition_of(symbol)
+        else {
+            return None;
+        };
+
+        check_deprecated_usage(self.state, symbol, callee.location(), self.item);
+
+        // A single private field makes the constructor unusable outside of
+        // the module.
+        if let Some((idx, field)) = struct_
+            .fields
+            .iter()
+            .enumerate()
+            .find(|(_, field)| field.visibility == Visibility::Private)
+            .filter(|_| struct_module != self.module)
+        {
+            let diagnostic = PrivateTupleLikeStructConstructor::new(
+                callee.location(),
+                struct_.name.id,
+                idx,
+                field.ty.location(),
+            );
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+        }
+
+        let type_arguments = self.instantiate(&struct_.generic_parameters, type_arguments);
+        let generic_parameters = generic_arguments(&struct_.generic_parameters, &type_arguments);
+        let field_types = struct_
+            .fields
+            .iter()
+            .map(|field| &field.ty)
+            .collect::<Vec<_>>();
+        let mut spread = false;
+
+        for (idx, argument) in arguments.iter().enumerate() {
+            let argument_type = self.type_of(&argument.value);
+
+            if spread {
+                continue;
+            }
+
+            if argument.spread.is_some() {
+                // A spread tuple is passed to all fields, that are left.
+                self.check_spread_argument(
+                    struct_module,
+                    argument,
+                    &argument_type,
+                    field_types.get(idx..).unwrap_or_default(),
+                    &generic_parameters,
+                );
+                spread = true;
+
+                continue;
+            }
+
+            // Extra arguments are reported below.
+            let Some(field_type) = field_types.get(idx) else {
+                continue;
+            };
+
+            let expected_type = self.resolve_type(struct_module, field_type, &generic_parameters);
+
+            if !self.substitution.unify(&expected_type, &argument_type) {
+                let diagnostic = StructExpressionFieldTypeMismatch::new(
+                    argument.value.location(),
+                    self.type_to_string(&expected_type),
+                    self.type_to_string(&argument_type),
+                    field_type.location(),
+                );
+                self.state.diagnostics_mut().add_diagnostic(diagnostic);
+            }
+        }
+
+        if !spread && arguments.len() != field_types.len() {
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(TupleLikeStructArityMismatch::new(
+                    location,
+                    struct_.name,
+                    field_types.len(),
+                    arguments.len(),
+                ));
+        }
+
+        Some(Type::Constructor(TypeConstructor::new(
+            symbol,
+            type_arguments,
+        )))
+    }
+
+    /// Checks, that the value of a spread argument is a tuple, whose elements
+    /// match the parameters, that it is passed to.
+    fn check_spread_argument(
+        &mut self,
+        module: ModuleId,
+        argument: &CallArgument,
+        argument_type: &Type,
+        parameter_types: &[&stellar_hir::Type],
+        generic_parameters: &FxHashMap<IdentifierId, Type>,
+    ) {
+        let location = argument.value.location();
+        let expected_types = parameter_types
+            .iter()
+            .map(|ty| self.resolve_type(module, ty, generic_parameters))
+            .collect::<Vec<_>>();
+
+        let element_types = match self.substitution.apply(argument_type) {
+            Type::Tuple { element_types } => element_types,
+            // Types of values, that are not known, are not checked.
+            Type::Unknown | Type::Never => return,
+            ty => {
+                // The tuple type can still be inferred from the parameters,
+                // e.g. for a lambda parameter.
+                if matches!(ty, Type::Variable(_))
+                    && self.substitution.unify(
+                        &ty,
+                        &Type::Tuple {
+                            element_types: expected_types,
+                        },
+                    )
+                {
+                    return;
+                }
+
+                let diagnostic = SpreadArgumentNotTuple::new(location, self.type_to_string(&ty));
+                self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+                return;
+            }
+        };
+
+        if element_types.len() != expected_types.len() {
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(SpreadArgumentArityMismatch::new(
+                    location,
+                    expected_types.len(),
+                    element_types.len(),
+                ));
+
+            return;
+        }
+
+        let declaration_location = match &argument.value {
+            Expression::Identifier(identifier) => self.binding_of(*identifier),
+            _ => None,
+        };
+
+        for (idx, (element_type, (expected_type, parameter_type))) in element_types
+            .iter()
+            .zip(expected_types.iter().zip(parameter_types))
+            .enumerate()
+        {
+            if !self.substitution.unify(expected_type, element_type) {
+                let diagnostic = SpreadElementTypeMismatch::new(
+                    location,
+                    idx,
+                    self.type_to_string(expected_type),
+                    self.type_to_string(element_type),
+                    parameter_type.location(),
+                )
+                .with_declaration_location(declaration_location);
+                self.state.diagnostics_mut().add_diagnostic(diagnostic);
+            }
+        }
+    }
+
+    /// Reports an argument, that cannot be passed to any parameter of
+    /// the called function. Extra positional arguments are not reported.
+    fn report_argument_mismatch(
+        &mut self,
+        function: &Function,
+        argument: &CallArgument,
+        mismatch: ArgumentMismatch,
+    ) {
+        match (mismatch, argument.label) {
+            (ArgumentMismatch::PositionalAfterLabeled { labeled_location }, _) => {
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(PositionalArgumentAfterLabeled::new(
+                        argument.location(),
+                        labeled_location,
+                    ));
+            }
+            (ArgumentMismatch::UnknownLabel, Some(label)) => {
+                let suggestion =
+                    find_similar_name(label.id, parameter_names(function).into_iter().flatten());
+
+                self.state.diagnostics_mut().add_diagnostic(
+                    UnknownArgumentLabel::new(label, function.signature.name.id)
+                        .with_suggestion(suggestion),
+                );
+            }
+            (ArgumentMismatch::AfterSpread { spread_location }, _) => {
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(ArgumentAfterSpread::new(
+                        argument.location(),
+                        spread_location,
+                        argument.spread.is_some(),
+                    ));
+            }
+            (ArgumentMismatch::AlreadyPassed { first_location }, Some(label)) => {
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(ArgumentPassedMoreThanOnce::new(
+                        label.id,
+                        first_location,
+                        argument.location(),
+                    ));
+            }
+            _ => {}
+        }
+    }
+
+    /// Reports parameters without default values, that no argument of
+    /// a call is passed to.
+    fn check_missing_arguments(
+        &mut self,
+        location: Location,
+        function: &Function,
+        passed: &FxHashSet<usize>,
+    ) {
+        let missing = function
+            .signature
+            .parameters
+            .iter()
+            .filter_map(|parameter| match parameter {
+                FunctionParameter::NotSelfParameter(parameter) => Some(parameter),
+                FunctionParameter::SelfParameter(_) => None,
+            })
+            .enumerate()
+            .filter(|(idx, parameter)| parameter.default_value.is_none() && !passed.contains(idx))
+            .map(|(idx, parameter)| match &parameter.pattern {
+                Pattern::Identifier { identifier, .. } => format!("`{}`", identifier.id),
+                // Parameters bound to other patterns are referred to by positions.
+                _ => format!("#{}", idx + 1),
+            })
+            .collect::<Vec<_>>();
+
+        if !missing.is_empty() {
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(MissingArguments::new(
+                    location,
+                    function.signature.name,
+                    missing,
+                ));
+        }
+    }
+
+    fn fresh_variable_of_kind(&mut self, location: Location, kind: TypeVariableKind) -> Type {
+        let ty = self.type_variables.fresh(location);
+
+        if let Type::Variable(variable) = &ty {
+            self.substitution.set_kind(variable.id(), kind);
+        }
+
+        ty
+    }
+
+    /// Renders a type for diagnostics, e.g. `List[{integer}]`.
+    fn type_to_string(&self, ty: &Type) -> String {
+        let variable_names = self
+            .substitution
+            .kinds()
+            .map(|(id, kind)| (id, kind.to_string()))
+            .collect::<FxHashMap<_, _>>();
+
+        self.substitution
+            .apply(ty)
+            .display(self.state.db())
+            .with_variable_names(&variable_names)
+            .to_string()
+    }
+}
+
+impl Visitor for InferExpressionTypes<'_, '_> {
+    fn visit_module_item(&mut self, item: &ModuleItem) {
+        self.item = item.name().and_then(|name| {
+            self.module
+                .module_item_symbol_or_none(self.state.db(), name)
+        });
+        walk_module_item(self, item);
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        self.visit_function_body(function);
+    }
+
+    fn visit_method(&mut self, method: &Function) {
+        self.visit_function_body(method);
+    }
+
+    // Default values of parameters are checked in `visit_function_body()`.
+    fn visit_function_parameter(&mut self, _: &FunctionParameter) {}
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        if let Statement::Break {
+            location,
+            label,
+            value,
+        } = statement
+        {
+            self.check_break(*location, *label, value.as_ref());
+            return;
+        }
+
+        let Statement::Let {
+            pattern,
+            value,
+            ty,
+            r#else,
+            ..
+        } = statement
+        else {
+            walk_statement(self, statement);
+            return;
+        };
+
+        let mut value_type = self.type_of(value);
+
+        if let Some(ty) = ty {
+            let annotated_type = self.resolve_type(self.module, ty, &FxHashMap::default());
+
+            // Mismatches between annotations and values are not reported here.
+            self.substitution.unify(&annotated_type, &value_type);
+            value_type = annotated_type;
+        }
+
+        self.record_pattern(pattern, &value_type);
+
+        if let Some(r#else) = r#else {
+            self.visit_statements_block(r#else);
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        self.type_of(expression);
+    }
+}
+
+/// Identifiers used as expressions, e.g. `a` and `b` in `a + b.c`.
+#[derive(Default)]
+struct UsedIdentifiers(Vec<IdentifierAST>);
+
+impl Visitor for UsedIdentifiers {
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Expression::Identifier(identifier) = expression {
+            self.0.push(*identifier);
+        }
+
+        walk_expression(self, expression);
+    }
+}
+
+/// Maps generic parameters of a struct onto type arguments of its type,
+/// e.g. `T` onto `int32` for `Box[int32]`. Missing arguments are unknown.
+fn generic_arguments(
+    generic_parameters: &[GenericParameter],
+    arguments: &[Type],
+) -> FxHashMap<IdentifierId, Type> {
+    generic_parameters
+        .iter()
+        .enumerate()
+        .map(|(idx, parameter)| {
+            (
+                parameter.name.id,
+                arguments.get(idx).cloned().unwrap_or(Type::Unknown),
+            )
+        })
+        .collect()
+}
+
+/// A group of types, that values can be cast from and to, see
+/// [`is_allowed_cast()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CastType {
+    /// An integer type, or `None` for an integer literal without a suffix.
+    Integer(Option<BuiltinSymbolId>),
+    Float,
+    Char,
+    Bool,
+
+    /// An enum, none of which items have fields.
+    FieldlessEnum,
+    Other,
+}
+
+/// Returns `true` if values of one type can be cast to another type:
+///
+/// | From                    | To             |
+/// | ----------------------- | -------------- |
+/// | Numeric type            | Numeric type   |
+/// | `char`                  | `uint32`       |
+/// | `uint32`                | `char`         |
+/// | `bool`                  | Integer type   |
+/// | Enum without fields     | Integer type   |
+///
+/// Numeric casts truncate, see the language reference for details.
+fn is_allowed_cast(from: CastType, to: CastType) -> bool {
+    matches!(
+        (from, to),
+        (
+            CastType::Integer(_) | CastType::Float,
+            CastType::Integer(_) | CastType::Float
+        ) | (
+            CastType::Char,
+            CastType::Integer(Some(BuiltinSymbolId::Uint32))
+        ) | (
+            CastType::Integer(None | Some(BuiltinSymbolId::Uint32)),
+            CastType::Char
+        ) | (
+            CastType::Bool | CastType::FieldlessEnum,
+            CastType::Integer(_)
+        )
+    )
+}
+
+/// A group of types, that binary operators are defined for, see
+/// [`operator_accepts()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OperandType {
+    /// A type variable, that can still be inferred to be any type.
+    Unconstrained,
+    Integer,
+    Float,
+    Char,
+    Bool,
+    String,
+    Other,
+}
+
+/// Returns `true` if a binary operator is defined for operands of a given
+/// type. Both operands must have the same type:
+///
+/// | Operators                       | Operands                       |
+/// | ------------------------------- | ------------------------------ |
+/// | `+`                             | Numeric types, `String`        |
+/// | `-`, `*`, `/`, `%`, `**`        | Numeric types                  |
+/// | `<`, `<=`, `>`, `>=`            | Numeric types, `char`          |
+/// | `==`, `!=`                      | Any type                       |
+/// | `&&`, `\|\|`                      | `bool`                         |
+/// | `&`, `\|`, `<<`, `>>`            | Integer types                  |
+fn operator_accepts(operator: RawBinaryOperator, operand: OperandType) -> bool {
+    use OperandType::{Bool, Char, Float, Integer, String, Unconstrained};
+
+    match operator {
+        RawBinaryOperator::Plus => matches!(operand, Unconstrained | Integer | Float | String),
+        RawBinaryOperator::Minus
+        | RawBinaryOperator::Asterisk
+        | RawBinaryOperator::Slash
+        | RawBinaryOperator::Percent
+        | RawBinaryOperator::DoubleAsterisk => matches!(operand, Unconstrained | Integer | Float),
+        RawBinaryOperator::Less
+        | RawBinaryOperator::LessEq
+        | RawBinaryOperator::Greater
+        | RawBinaryOperator::GreaterEq => matches!(operand, Unconstrained | Integer | Float | Char),
+        RawBinaryOperator::DoubleAmpersand | RawBinaryOperator::DoubleOr => {
+            matches!(operand, Unconstrained | Bool)
+        }
+        RawBinaryOperator::Ampersand
+        | RawBinaryOperator::Or
+        | RawBinaryOperator::LeftShift
+        | RawBinaryOperator::RightShift => matches!(operand, Unconstrained | Integer),
+        RawBinaryOperator::DoubleEq | RawBinaryOperator::BangEq => true,
+        // Assignments are not binary expressions after lowering.
+        _ => true,
+    }
+}
+
+/// Returns the minimum and the maximum values of an integer type, e.g.
+/// `-128` and `127` for `int8`, or `None` if the type is not an integer type.
+fn integer_range(builtin: BuiltinSymbolId) -> Option<(i128, i128)> {
+    Some(match builtin {
+        BuiltinSymbolId::Int8 => (i8::MIN.into(), i8::MAX.into()),
+        BuiltinSymbolId::Int16 => (i16::MIN.into(), i16::MAX.into()),
+        BuiltinSymbolId::Int32 => (i32::MIN.into(), i32::MAX.into()),
+        BuiltinSymbolId::Int64 => (i64::MIN.into(), i64::MAX.into()),
+        BuiltinSymbolId::Uint8 => (0, u8::MAX.into()),
+        BuiltinSymbolId::Uint16 => (0, u16::MAX.into()),
+        BuiltinSymbolId::Uint32 => (0, u32::MAX.into()),
+        BuiltinSymbolId::Uint64 => (0, u64::MAX.into()),
+        _ => return None,
+    })
+}
+
+/// Replaces type aliases in a type with [`Type::Unknown`].
+pub(super) fn erase_type_aliases(ty: Type) -> Type {
+    match ty {
+        Type::Constructor(constructor) if constructor.symbol.is_type_alias() => Type::Unknown,
+        Type::Constructor(TypeConstructor {
+            symbol,
+            arguments,
+            argument_locations,
+        }) => Type::Constructor(
+            TypeConstructor::new(
+                symbol,
+                arguments.into_iter().map(erase_type_aliases).collect(),
+            )
+            .with_argument_locations(argument_locations),
+        ),
+        Type::Tuple { element_types } => Type::Tuple {
+            element_types: element_types.into_iter().map(erase_type_aliases).collect(),
+        },
+        Type::Function {
+            parameter_types,
+            return_type,
+        } => Type::Function {
+            parameter_types: parameter_types
+                .into_iter()
+                .map(erase_type_aliases)
+                .collect(),
+            return_type: Box::new(erase_type_aliases(*return_type)),
+        },
+        ty => ty,
+    }
+}
@@ -0,0 +1,83 @@
+//! Infers types of lambdas, e.g. `|x| x + 1`.
+//!
+//! Types of unannotated lambda parameters are inferred from the context, when
+//! a lambda is passed to a function, and unannotated return types are inferred
+//! from lambda bodies:
+//!
+//! ```stellar
+//! fun apply(f: fun(int32): int32, x: int32): int32 { f(x) }
+//!
+//! fun main() {
+//!     apply(|x| x + 1, 2); // `x` is `int32`
+//!     apply(|x| "s", 2);   // error: the lambda returns `String`
+//! }
+//! ```
+//!
+//! If the type of a lambda doesn't match the expected one, an error is reported.
+//!
+//! Lambdas are inferred together with other expressions by
+//! [`InferExpressionTypes`], so that their bodies can use types of local
+//! variables. This pass only returns the types of lambdas.
+
+use stellar_database::{ty::Type, ModuleId, State};
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::{FxHashMap, FxHashSet};
+use stellar_hir::{
+    visit::{walk_expression, Visitor},
+    Expression, LambdaFunctionParameter,
+};
+
+use super::infer_expression_types::InferExpressionTypes;
+use crate::signature_analysis::check_interface_conformance::type_to_string;
+
+pub struct InferLambdaTypes;
+
+impl InferLambdaTypes {
+    /// Infers types of all lambdas and returns them by lambda locations.
+    ///
+    /// **Note**: type variables, that cannot be inferred, are left in the types.
+    pub fn run_all(
+        state: &mut State,
+        modules: &FxHashMap<ModuleId, stellar_hir::Module>,
+    ) -> FxHashMap<Location, Type> {
+        let mut lambdas = LambdaLocations::default();
+
+        for hir in modules.values() {
+            lambdas.visit_module(hir);
+        }
+
+        let mut types = InferExpressionTypes::run_all(state, modules);
+        types.retain(|location, _| lambdas.0.contains(location));
+
+        types
+    }
+}
+
+/// Locations of all lambdas in the visited HIR.
+#[derive(Default)]
+struct LambdaLocations(FxHashSet<Location>);
+
+impl Visitor for LambdaLocations {
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Expression::Lambda { location, .. } = expression {
+            self.0.insert(*location);
+        }
+
+        walk_expression(self, expression);
+    }
+}
+
+/// Renders the type of a lambda, e.g. `fun(int32, _): int32` for
+/// `|x: int32, y| x + y`. Unannotated parameters are rendered as `_`.
+pub(super) fn lambda_type_to_string(
+    parameters: &[LambdaFunctionParameter],
+    return_type: &str,
+) -> String {
+    let parameters = parameters
+        .iter()
+        .map(|parameter| parameter.ty.as_ref().map_or("_".to_owned(), type_to_string))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("fun({parameters}): {return_type}")
+}
@@ -0,0 +1,496 @@
+//! Lowers function bodies into THIR (see [`stellar_thir::body`]), where every
+//! expression has a type and names of global definitions are resolved into
+//! symbols:
+//!
+//! ```stellar
+//! fun foo(): int64 { 3 }
+//!
+//! fun main() {
+//!     let a = 1;     // `1` has type `int32`
+//!     let b = foo(); // `foo` is `Symbol::Function(...)`, `foo()` has type `int64`
+//! }
+//! ```
+//!
+//! Types, including types of lambdas, are inferred by [`InferExpressionTypes`].
+//! Expressions, whose types cannot be inferred yet, have type [`Type::Unknown`].
+
+#[cfg(feature = "debug")]
+use std::time::Instant;
+
+use stellar_ast::IdentifierAST;
+use stellar_database::{ty::Type, Database, FunctionId, HirStorage, ModuleId, State, Symbol};
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::{Expression, LoopKind, ModuleItem, Statement, StatementsBlock};
+use stellar_interner::IdentifierId;
+use stellar_thir::body::{
+    self as thir, Body, ExpressionKind, LambdaFunctionParameter, MatchExpressionItem,
+    StructExpressionItem,
+};
+#[cfg(feature = "debug")]
+use tracing::trace;
+
+use super::{
+    expand_type_aliases, expression_path,
+    infer_expression_types::{erase_type_aliases, InferExpressionTypes},
+    resolve_type,
+    unification::TypeVariables,
+};
+use crate::resolution::{resolve_path_silently, scope::ScopeTree};
+
+/// Lowers the body of a given function into THIR.
+///
+/// Returns `None` if the function doesn't have a body, or its HIR is not
+/// stored, i.e. definitions are not collected yet.
+pub fn lower_body(
+    state: &mut State,
+    modules: &FxHashMap<ModuleId, stellar_hir::Module>,
+    function: FunctionId,
+) -> Option<Body> {
+    #[cfg(feature = "debug")]
+    let now = Instant::now();
+
+    let item = state.hir().get(Symbol::Function(function))?.clone();
+    let ModuleItem::Function(hir) = item.as_ref() else {
+        return None;
+    };
+    let statements = hir.body.as_ref()?;
+
+    let module = function.signature(state.db()).module(state.db());
+    let types = InferExpressionTypes::run_function(state, modules, module, hir);
+
+    let mut lower = LowerBody {
+        db: state.db(),
+        hir: state.hir(),
+        module,
+        scopes: ScopeTree::build(hir),
+        types,
+        generic_parameters: hir
+            .signature
+            .generic_parameters
+            .iter()
+            .map(|parameter| (parameter.name.id, Type::Unknown))
+            .collect(),
+        type_variables: TypeVariables::new(),
+    };
+
+    let return_type = hir
+        .signature
+        .return_type
+        .as_ref()
+        .map_or(Type::Unit, |ty| lower.resolve_type(ty));
+    let statements = lower.lower_statements(statements);
+
+    #[cfg(feature = "debug")]
+    trace!(
+        "lower_body(function = '{}') <{} us>",
+        hir.signature.name.id,
+        now.elapsed().as_micros()
+    );
+
+    Some(Body {
+        function,
+        return_type,
+        statements,
+    })
+}
+
+/// Lowers bodies of all functions, whose definitions are collected, into THIR.
+pub fn lower_all_bodies(
+    state: &mut State,
+    modules: &FxHashMap<ModuleId, stellar_hir::Module>,
+) -> FxHashMap<FunctionId, Body> {
+    let functions = state
+        .hir()
+        .iter()
+        .filter_map(|(symbol, _)| match symbol {
+            Symbol::Function(function) => Some(function),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    functions
+        .into_iter()
+        .filter_map(|function| Some((function, lower_body(state, modules, function)?)))
+        .collect()
+}
+
+struct LowerBody<'db> {
+    db: &'db Database,
+    hir: &'db HirStorage,
+    module: ModuleId,
+    scopes: ScopeTree,
+
+    /// Types of expressions by their locations.
+    types: FxHashMap<Location, Type>,
+
+    /// Generic parameters can be any types, so they are lowered into
+    /// [`Type::Unknown`], like in [`InferExpressionTypes`].
+    generic_parameters: FxHashMap<IdentifierId, Type>,
+    type_variables: TypeVariables,
+}
+
+impl LowerBody<'_> {
+    fn resolve_type(&mut self, ty: &stellar_hir::Type) -> Type {
+        let ty = resolve_type(
+            self.db,
+            self.module,
+            ty,
+            &self.generic_parameters,
+            &mut self.type_variables,
+        );
+
+        erase_type_aliases(expand_type_aliases(
+            self.db,
+            self.hir,
+            ty,
+            &mut self.type_variables,
+            0,
+        ))
+    }
+
+    /// Lowers a block. THIR doesn't separate tail expressions, so a tail
+    /// expression becomes the last statement without `;`.
+    fn lower_statements(&mut self, block: &StatementsBlock) -> Vec<thir::Statement> {
+        let mut statements = block
+            .statements
+            .iter()
+            .map(|statement| self.lower_statement(statement))
+            .collect::<Vec<_>>();
+
+        if let Some(tail_expression) = &block.tail_expression {
+            statements.push(thir::Statement::Expression {
+                expression: self.lower_expression(tail_expression),
+                has_semicolon: false,
+            });
+        }
+
+        statements
+    }
+
+    fn lower_statement(&mut self, statement: &Statement) -> thir::Statement {
+        match statement {
+            Statement::Defer { location, call } => thir::Statement::Defer {
+                location: *location,
+                call: self.lower_expression(call),
+            },
+            Statement::Expression {
+                expression,
+                has_semicolon,
+            } => thir::Statement::Expression {
+                expression: self.lower_expression(expression),
+                has_semicolon: *has_semicolon,
+            },
+            Statement::Break {
+                location,
+                label,
+                value,
+            } => thir::Statement::Break {
+                location: *location,
+                label: *label,
+                value: value.as_ref().map(|value| self.lower_expression(value)),
+            },
+            Statement::Continue { location, label } => thir::Statement::Continue {
+                location: *location,
+                label: *label,
+            },
+            Statement::Return {
+                location,
+                expression,
+            } => thir::Statement::Return {
+                location: *location,
+                expression: self.lower_expression(expression),
+            },
+            Statement::Let {
+                location,
+                pattern,
+                value,
+                ty,
+                r#else,
+            } => {
+                let value = self.lower_expression(value);
+                let ty = ty
+                    .as_ref()
+                    .map_or_else(|| value.ty.clone(), |ty| self.resolve_type(ty));
+
+                thir::Statement::Let {
+                    location: *location,
+                    pattern: pattern.clone(),
+                    value,
+                    ty,
+                    r#else: r#else
+                        .as_ref()
+                        .map(|statements| self.lower_statements(statements)),
+                }
+            }
+        }
+    }
+
+    fn lower_expressions(&mut self, expressions: &[Expression]) -> Vec<thir::Expression> {
+        expressions
+            .iter()
+            .map(|expression| self.lower_expression(expression))
+            .collect()
+    }
+
+    fn lower_boxed_expression(&mut self, expression: &Expression) -> Box<thir::Expression> {
+        Box::new(self.lower_expression(expression))
+    }
+
+    fn lower_expression(&mut self, expression: &Expression) -> thir::Expression {
+        let location = expression.location();
+        let mut ty = self.types.get(&location).cloned().unwrap_or(Type::Unknown);
+
+        let kind = match expression {
+            Expression::List { elements, .. } => ExpressionKind::List {
+                elements: self.lower_expressions(elements),
+            },
+            Expression::As { left, right, .. } => {
+                ty = self.resolve_type(right);
+
+                ExpressionKind::As {
+                    left: self.lower_boxed_expression(left),
+                }
+            }
+            Expression::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => ExpressionKind::Binary {
+                left: self.lower_boxed_expression(left),
+                operator: *operator,
+                right: self.lower_boxed_expression(right),
+            },
+            Expression::StatementsBlock { block, .. } => {
+                let block = self.lower_statements(block);
+                ty = block_type(&block);
+
+                ExpressionKind::StatementsBlock { block }
+            }
+            Expression::Literal(literal) => ExpressionKind::Literal {
+                literal: literal.clone(),
+            },
+            Expression::Identifier(identifier) => self.lower_name(*identifier),
+            Expression::Underscore { .. } => ExpressionKind::Underscore,
+            Expression::If {
+                if_blocks, r#else, ..
+            } => ExpressionKind::If {
+                if_blocks: if_blocks
+                    .iter()
+                    .map(|(condition, block)| {
+                        (
+                            self.lower_expression(condition),
+                            self.lower_statements(block),
+                        )
+                    })
+                    .collect(),
+                r#else: r#else.as_ref().map(|block| self.lower_statements(block)),
+            },
+            Expression::FieldAccess { left, right, .. } => {
+                match self.resolve_global_path(expression) {
+                    Some(symbol) => ExpressionKind::Symbol { symbol },
+                    None => ExpressionKind::FieldAccess {
+                        left: self.lower_boxed_expression(left),
+                        right: *right,
+                    },
+                }
+            }
+            Expression::TupleIndex { left, index, .. } => ExpressionKind::TupleIndex {
+                left: self.lower_boxed_expression(left),
+                index: *index,
+            },
+            Expression::Assign { left, right, .. } => ExpressionKind::Assign {
+                left: self.lower_boxed_expression(left),
+                right: self.lower_boxed_expression(right),
+            },
+            Expression::Index { left, index, .. } => ExpressionKind::Index {
+                left: self.lower_boxed_expression(left),
+                index: self.lower_boxed_expression(index),
+            },
+            Expression::Prefix {
+                inner, operator, ..
+            } => ExpressionKind::Prefix {
+                inner: self.lower_boxed_expression(inner),
+                operator: *operator,
+            },
+            Expression::Postfix {
+                inner, operator, ..
+            } => ExpressionKind::Postfix {
+                inner: self.lower_boxed_expression(inner),
+                operator: *operator,
+            },
+            Expression::While {
+                kind,
+                label,
+                condition,
+                statements_block,
+                ..
+            } => {
+                // Types of `loop` expressions come from their `break` values.
+                if *kind == LoopKind::While {
+                    ty = Type::Unit;
+                }
+
+                ExpressionKind::While {
+                    kind: *kind,
+                    label: *label,
+                    condition: self.lower_boxed_expression(condition),
+                    statements_block: self.lower_statements(statements_block),
+                }
+            }
+            Expression::Call {
+                callee, arguments, ..
+            } => ExpressionKind::Call {
+                callee: self.lower_boxed_expression(callee),
+                arguments: arguments
+                    .iter()
+                    .map(|argument| thir::CallArgument {
+                        label: argument.label,
+                        spread: argument.spread.is_some(),
+                        value: self.lower_expression(&argument.value),
+                    })
+                    .collect(),
+            },
+            Expression::TypeArguments {
+                left,
+                type_arguments,
+                ..
+            } => ExpressionKind::TypeArguments {
+                left: self.lower_boxed_expression(left),
+                type_arguments: type_arguments
+                    .iter()
+                    .map(|ty| self.resolve_type(ty))
+                    .collect(),
+            },
+            Expression::Tuple { elements, .. } => ExpressionKind::Tuple {
+                elements: self.lower_expressions(elements),
+            },
+            Expression::Struct {
+                left, fields, base, ..
+            } => ExpressionKind::Struct {
+                left: self.lower_boxed_expression(left),
+                fields: fields
+                    .iter()
+                    .map(|field| StructExpressionItem {
+                        name: field.name,
+                        value: match &field.value {
+                            Some(value) => self.lower_expression(value),
+                            None => self.lower_expression(&Expression::Identifier(field.name)),
+                        },
+                    })
+                    .collect(),
+                base: base
+                    .as_deref()
+                    .map(|base| self.lower_boxed_expression(base)),
+            },
+            Expression::Match {
+                expression, block, ..
+            } => ExpressionKind::Match {
+                expression: self.lower_boxed_expression(expression),
+                block: block
+                    .iter()
+                    .map(|item| MatchExpressionItem {
+                        left: item.left.clone(),
+                        guard: item
+                            .guard
+                            .as_ref()
+                            .map(|guard| self.lower_expression(guard)),
+                        right: self.lower_expression(&item.right),
+                    })
+                    .collect(),
+            },
+            Expression::Lambda {
+                parameters,
+                value,
+                captures,
+                ..
+            } => {
+                // Types of unannotated parameters are inferred from the context.
+                let inferred_parameter_types = match &ty {
+                    Type::Function {
+                        parameter_types, ..
+                    } => parameter_types.clone(),
+                    _ => vec![],
+                };
+
+                ExpressionKind::Lambda {
+                    parameters: parameters
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, parameter)| LambdaFunctionParameter {
+                            name: parameter.name,
+                            ty: match &parameter.ty {
+                                Some(ty) => self.resolve_type(ty),
+                                None => inferred_parameter_types
+                                    .get(idx)
+                                    .cloned()
+                                    .unwrap_or(Type::Unknown),
+                            },
+                        })
+                        .collect(),
+                    value: self.lower_boxed_expression(value),
+                    captures: captures.clone(),
+                }
+            }
+        };
+
+        thir::Expression { location, ty, kind }
+    }
+
+    /// Lowers a name into a local variable or a global definition.
+    fn lower_name(&self, name: IdentifierAST) -> ExpressionKind {
+        if self.is_local(name) {
+            return ExpressionKind::Local { name };
+        }
+
+        match resolve_path_silently(self.db, self.module, &[name]) {
+            Some(symbol) => ExpressionKind::Symbol { symbol },
+            None => ExpressionKind::Unresolved { name },
+        }
+    }
+
+    /// Resolves an expression like `a.b.c` into a global definition, unless
+    /// `a` is a local variable.
+    fn resolve_global_path(&self, expression: &Expression) -> Option<Symbol> {
+        let path = expression_path(expression)?;
+
+        if self.is_local(*path.first()?) {
+            return None;
+        }
+
+        resolve_path_silently(self.db, self.module, &path)
+    }
+
+    fn is_local(&self, name: IdentifierAST) -> bool {
+        self.scopes
+            .bindings_at(name.location.start)
+            .iter()
+            .any(|binding| binding.name.id == name.id)
+    }
+}
+
+/// Returns the type of a block: [`Type::Never`] if one of its statements
+/// diverges, otherwise the type of its last expression, if it is not
+/// followed by `;`, or `()`.
+fn block_type(block: &[thir::Statement]) -> Type {
+    let diverges = block.iter().any(|statement| match statement {
+        thir::Statement::Return { .. }
+        | thir::Statement::Break { .. }
+        | thir::Statement::Continue { .. } => true,
+        thir::Statement::Expression { expression, .. } => expression.ty == Type::Never,
+        thir::Statement::Let { .. } | thir::Statement::Defer { .. } => false,
+    });
+
+    if diverges {
+        return Type::Never;
+    }
+
+    match block.last() {
+        Some(thir::Statement::Expression {
+            expression,
+            has_semicolon: false,
+        }) => expression.ty.clone(),
+        _ => Type::Unit,
+    }
+}
@@ -0,0 +1,455 @@
+pub mod capture_analysis;
+pub mod check_assignments;
+pub mod check_break_and_continue;
+pub mod check_let_patterns;
+pub mod check_match_exhaustiveness;
+pub mod infer_expression_types;
+pub mod infer_lambda_types;
+pub mod lower_body;
+pub mod resolve_enum_items;
+pub mod unification;
+
+use std::cmp::Ordering;
+
+use stellar_ast::IdentifierAST;
+use stellar_database::{
+    symbol::BuiltinSymbolId,
+    ty::{Type, TypeConstructor},
+    Database, HirStorage, ModuleId, Symbol,
+};
+use stellar_filesystem::location::{HasLocation, Location};
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::{CallArgument, Expression, Function, FunctionParameter, ModuleItem, Pattern};
+use stellar_interner::IdentifierId;
+
+use self::unification::{Implementations, TypeVariables};
+use crate::resolution::{resolve_path_silently, scope::ScopeTree};
+
+/// Converts an expression like `a.b.c` into a list of identifiers.
+pub(crate) fn expression_path(expression: &Expression) -> Option<Vec<IdentifierAST>> {
+    match expression {
+        Expression::Identifier(identifier) => Some(vec![*identifier]),
+        Expression::FieldAccess { left, right, .. } => {
+            let mut path = expression_path(left)?;
+            path.push(*right);
+            Some(path)
+        }
+        _ => None,
+    }
+}
+
+/// Resolves a global function, that is called, e.g. `foo` in `foo(1)`, and
+/// returns it together with the module it is defined in.
+///
+/// Calls of local variables, e.g. `f(1)`, where `f` is a parameter, are skipped.
+fn resolve_called_function<'h>(
+    db: &Database,
+    modules: &'h FxHashMap<ModuleId, stellar_hir::Module>,
+    module: ModuleId,
+    scopes: Option<&ScopeTree>,
+    callee: &Expression,
+) -> Option<(ModuleId, &'h Function)> {
+    let path = expression_path(callee)?;
+    let first = path.first()?;
+
+    let is_local = scopes.is_some_and(|scopes| {
+        scopes
+            .bindings_at(first.location.start)
+            .iter()
+            .any(|binding| binding.name.id == first.id)
+    });
+
+    if is_local {
+        return None;
+    }
+
+    let Some(Symbol::Function(function)) = resolve_path_silently(db, module, &path) else {
+        return None;
+    };
+
+    let signature = function.signature(db);
+    let module = signature.module(db);
+
+    match modules.get(&module)?.items.get(signature.node_idx(db))? {
+        ModuleItem::Function(function) => Some((module, function)),
+        _ => None,
+    }
+}
+
+/// A reason, why an argument of a call is not passed to any parameter of
+/// the called function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArgumentMismatch {
+    /// The argument is positional and there are no parameters left.
+    Extra,
+
+    /// The argument is positional and follows a labeled one, e.g. `20` in
+    /// `draw(x: 10, 20)`.
+    PositionalAfterLabeled { labeled_location: Location },
+
+    /// The function doesn't have a parameter with the argument's label.
+    UnknownLabel,
+
+    /// The parameter is already passed by a previous argument, e.g. `x: 20`
+    /// in `draw(10, x: 20)`.
+    AlreadyPassed { first_location: Location },
+
+    /// The argument follows a spread argument, e.g. `3` in `add(..pair, 3)`.
+    AfterSpread { spread_location: Location },
+}
+
+/// Returns names of parameters of a function, except `self`. Parameters,
+/// that are bound to patterns other than identifiers, don't have names.
+fn parameter_names(function: &Function) -> Vec<Option<IdentifierId>> {
+    function
+        .signature
+        .parameters
+        .iter()
+        .filter_map(|parameter| match parameter {
+            FunctionParameter::NotSelfParameter(parameter) => Some(&parameter.pattern),
+            FunctionParameter::SelfParameter(_) => None,
+        })
+        .map(|pattern| match pattern {
+            Pattern::Identifier { identifier, .. } => Some(identifier.id),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Matches arguments of a call onto parameters of the called function,
+/// except `self`, and returns the index of the parameter for every argument.
+///
+/// Arguments before the first labeled one are passed positionally, and
+/// labeled arguments are passed to parameters with the same names, in any
+/// order, e.g. in `draw(10, z: 30, y: 20)`, `10` is passed to the first
+/// parameter, `z: 30` to `z` and `y: 20` to `y`.
+///
+/// A spread argument, e.g. `..args`, is passed to all parameters starting
+/// from the returned one, so it must be the last argument.
+fn match_call_arguments(
+    function: &Function,
+    arguments: &[CallArgument],
+) -> Vec<Result<usize, ArgumentMismatch>> {
+    let names = parameter_names(function);
+    let mut passed = FxHashMap::default();
+    let mut first_labeled = None;
+    let mut spread = None;
+
+    arguments
+        .iter()
+        .enumerate()
+        .map(|(idx, argument)| {
+            if let Some(spread_location) = spread {
+                return Err(ArgumentMismatch::AfterSpread { spread_location });
+            }
+
+            let parameter = match (argument.label, first_labeled) {
+                (None, None) if argument.spread.is_some() => {
+                    spread = Some(argument.location());
+
+                    // Extra positional arguments before the spread leave no
+                    // parameters for it.
+                    return Ok(idx.min(names.len()));
+                }
+                (None, None) if idx < names.len() => idx,
+                (None, None) => return Err(ArgumentMismatch::Extra),
+                (None, Some(labeled_location)) => {
+                    return Err(ArgumentMismatch::PositionalAfterLabeled { labeled_location })
+                }
+                (Some(label), _) => {
+                    first_labeled.get_or_insert(argument.location());
+
+                    names
+                        .iter()
+                        .position(|name| *name == Some(label.id))
+                        .ok_or(ArgumentMismatch::UnknownLabel)?
+                }
+            };
+
+            if let Some(first_location) = passed.get(&parameter) {
+                return Err(ArgumentMismatch::AlreadyPassed {
+                    first_location: *first_location,
+                });
+            }
+
+            passed.insert(parameter, argument.location());
+
+            Ok(parameter)
+        })
+        .collect()
+}
+
+/// Maps elements of a tuple pattern onto element types of a tuple, e.g. for
+/// `(first, .., last)` and `(A, B, C, D)`, `first` gets `[A]`, `..` gets
+/// `[B, C]` and `last` gets `[D]`.
+///
+/// Returns `None` if the pattern doesn't fit the tuple, i.e. if it has more
+/// elements, than the tuple, or has fewer elements and no rest pattern.
+fn tuple_pattern_elements<'p, 't, T>(
+    elements: &'p [Pattern],
+    element_types: &'t [T],
+) -> Option<Vec<(&'p Pattern, &'t [T])>> {
+    let Some(rest) = elements.iter().position(Pattern::is_rest) else {
+        return (elements.len() == element_types.len())
+            .then(|| elements.iter().zip(element_types.chunks(1)).collect());
+    };
+
+    let fixed = elements.len() - 1;
+    if fixed > element_types.len() {
+        return None;
+    }
+
+    let rest_end = rest + element_types.len() - fixed;
+
+    Some(
+        elements
+            .iter()
+            .enumerate()
+            .map(|(idx, element)| {
+                let types = match idx.cmp(&rest) {
+                    Ordering::Less => &element_types[idx..=idx],
+                    Ordering::Equal => &element_types[rest..rest_end],
+                    Ordering::Greater => {
+                        let idx = rest_end + idx - rest - 1;
+                        &element_types[idx..=idx]
+                    }
+                };
+
+                (element, types)
+            })
+            .collect(),
+    )
+}
+
+/// Converts a HIR type into a type, resolving names in a given module.
+///
+/// Names, that cannot be resolved, are converted into [`Type::Unknown`],
+/// as they are reported when resolving signatures. Underscores are converted
+/// into new type variables. Locations of generic arguments are kept in type
+/// constructors (see [`TypeConstructor::argument_location()`]).
+pub(crate) fn resolve_type(
+    db: &Database,
+    module: ModuleId,
+    ty: &stellar_hir::Type,
+    generic_parameters: &FxHashMap<IdentifierId, Type>,
+    type_variables: &mut TypeVariables,
+) -> Type {
+    match ty {
+        stellar_hir::Type::Constructor(constructor) => {
+            let symbol = match constructor.path.identifiers.as_slice() {
+                [name] if generic_parameters.contains_key(&name.id) => {
+                    return generic_parameters[&name.id].clone();
+                }
+                [name] if BuiltinSymbolId::from_identifier_or_none(name.id).is_some() => {
+                    Symbol::BuiltinSymbol(BuiltinSymbolId::from(name.id))
+                }
+                identifiers => match resolve_path_silently(db, module, identifiers) {
+                    Some(
+                        symbol @ (Symbol::Enum(_)
+                        | Symbol::Struct(_)
+                        | Symbol::TupleLikeStruct(_)
+                        | Symbol::Interface(_)
+                        | Symbol::TypeAlias(_)),
+                    ) => symbol,
+                    _ => return Type::Unknown,
+                },
+            };
+
+            let arguments = constructor
+                .arguments
+                .iter()
+                .map(|ty| resolve_type(db, module, ty, generic_parameters, type_variables))
+                .collect();
+
+            Type::Constructor(
+                TypeConstructor::new(symbol, arguments)
+                    .with_argument_locations(argument_locations(&constructor.arguments)),
+            )
+        }
+        stellar_hir::Type::Tuple { element_types, .. } => {
+            if element_types.is_empty() {
+                Type::Unit
+            } else {
+                Type::Tuple {
+                    element_types: element_types
+                        .iter()
+                        .map(|ty| resolve_type(db, module, ty, generic_parameters, type_variables))
+                        .collect(),
+                }
+            }
+        }
+        stellar_hir::Type::Function {
+            parameter_types,
+            return_type,
+            ..
+        } => Type::Function {
+            parameter_types: parameter_types
+                .iter()
+                .map(|ty| resolve_type(db, module, ty, generic_parameters, type_variables))
+                .collect(),
+            return_type: Box::new(return_type.as_ref().map_or(Type::Unit, |ty| {
+                resolve_type(db, module, ty, generic_parameters, type_variables)
+            })),
+        },
+        stellar_hir::Type::Underscore { location } => type_variables.fresh(*location),
+        stellar_hir::Type::InterfaceObject { bounds, .. } => bounds
+            .iter()
+            .map(
+                |bound| match resolve_path_silently(db, module, &bound.path.identifiers)? {
+                    symbol @ Symbol::Interface(_) => Some(
+                        TypeConstructor::new(
+                            symbol,
+                            bound
+                                .arguments
+                                .iter()
+                                .map(|ty| {
+                                    resolve_type(db, module, ty, generic_parameters, type_variables)
+                                })
+                                .collect(),
+                        )
+                        .with_argument_locations(argument_locations(&bound.arguments)),
+                    ),
+                    _ => None,
+                },
+            )
+            .collect::<Option<Vec<_>>>()
+            .map_or(Type::Unknown, |bounds| Type::InterfaceObject { bounds }),
+    }
+}
+
+/// Returns locations of generic arguments of a type constructor.
+fn argument_locations(arguments: &[stellar_hir::Type]) -> Vec<Location> {
+    arguments.iter().map(stellar_hir::Type::location).collect()
+}
+
+/// Maximum number of type aliases followed when resolving a type, so that
+/// recursive aliases like `type A = B; type B = A;` don't hang the compiler.
+const MAX_ALIAS_DEPTH: usize = 32;
+
+/// Expands type aliases in a type, substituting generic arguments into the
+/// aliased type positionally, e.g. `Pair[int32]` becomes `(int32, int32)`
+/// with `type Pair[T] = (T, T);`. Missing arguments are replaced with default
+/// values of generic parameters.
+///
+/// Aliases, that cannot be expanded, i.e. with a wrong number of generic
+/// arguments (reported when checking type paths) or nested too deeply, are
+/// kept as they are.
+pub(crate) fn expand_type_aliases(
+    db: &Database,
+    hir: &HirStorage,
+    ty: Type,
+    type_variables: &mut TypeVariables,
+    depth: usize,
+) -> Type {
+    match ty {
+        Type::Constructor(constructor) if constructor.symbol.is_type_alias() => {
+            let Some(ModuleItem::TypeAlias(alias)) = hir
+                .get(constructor.symbol)
+                .map(AsRef::as_ref)
+                .filter(|_| depth < MAX_ALIAS_DEPTH)
+            else {
+                return Type::Constructor(constructor);
+            };
+
+            if constructor.arguments.len() > alias.generic_parameters.len() {
+                return Type::Constructor(constructor);
+            }
+
+            let module = constructor.symbol.module(db);
+            let mut substitutions = FxHashMap::default();
+
+            for (idx, parameter) in alias.generic_parameters.iter().enumerate() {
+                let argument = match (constructor.arguments.get(idx), &parameter.default_value) {
+                    (Some(argument), _) => argument.clone(),
+                    (None, Some(default_value)) => {
+                        resolve_type(db, module, default_value, &substitutions, type_variables)
+                    }
+                    (None, None) => return Type::Constructor(constructor),
+                };
+
+                substitutions.insert(parameter.name.id, argument);
+            }
+
+            let value = resolve_type(db, module, &alias.value, &substitutions, type_variables);
+            expand_type_aliases(db, hir, value, type_variables, depth + 1)
+        }
+        Type::Constructor(TypeConstructor {
+            symbol,
+            arguments,
+            argument_locations,
+        }) => Type::Constructor(
+            TypeConstructor::new(
+                symbol,
+                arguments
+                    .into_iter()
+                    .map(|ty| expand_type_aliases(db, hir, ty, type_variables, depth))
+                    .collect(),
+            )
+            .with_argument_locations(argument_locations),
+        ),
+        Type::Tuple { element_types } => Type::Tuple {
+            element_types: element_types
+                .into_iter()
+                .map(|ty| expand_type_aliases(db, hir, ty, type_variables, depth))
+                .collect(),
+        },
+        Type::Function {
+            parameter_types,
+            return_type,
+        } => Type::Function {
+            parameter_types: parameter_types
+                .into_iter()
+                .map(|ty| expand_type_aliases(db, hir, ty, type_variables, depth))
+                .collect(),
+            return_type: Box::new(expand_type_aliases(
+                db,
+                hir,
+                *return_type,
+                type_variables,
+                depth,
+            )),
+        },
+        ty => ty,
+    }
+}
+
+/// Collects interfaces, that structs and enums in given modules implement.
+pub(crate) fn collect_implementations(
+    db: &Database,
+    modules: &FxHashMap<ModuleId, stellar_hir::Module>,
+) -> Implementations {
+    let mut implementations = Implementations::default();
+
+    for (module, hir) in modules {
+        for item in &hir.items {
+            let (name, implements) = match item {
+                ModuleItem::Enum(stellar_hir::Enum {
+                    name, implements, ..
+                })
+                | ModuleItem::Struct(stellar_hir::Struct {
+                    name, implements, ..
+                })
+                | ModuleItem::TupleLikeStruct(stellar_hir::TupleLikeStruct {
+                    name,
+                    implements,
+                    ..
+                }) => (name, implements),
+                _ => continue,
+            };
+
+            let Some(symbol) = module.module_item_symbol_or_none(db, name.id) else {
+                continue;
+            };
+
+            for interface in implements.iter().flatten() {
+                if let Some(interface @ Symbol::Interface(_)) =
+                    resolve_path_silently(db, *module, &interface.path.identifiers)
+                {
+                    implementations.entry(symbol).or_default().insert(interface);
+                }
+            }
+        }
+    }
+
+    implementations
+}
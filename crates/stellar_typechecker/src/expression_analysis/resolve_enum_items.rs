@@ -0,0 +1,411 @@
+//! Resolves paths to enum items in expressions and patterns, e.g. `Option.Some(1)`,
+//! `Color.Red` and `Option.Some(x) -> ...` in match arms.
+//!
+//! Paths can refer to enums through type aliases, e.g. `Opt.Some(1)` with
+//! `type Opt = Option;` resolves to `Option.Some`.
+//!
+//! Every resolved path gets the type of the enum it belongs to. Generic arguments
+//! of the enum are not known at this point, so they are represented as type
+//! placeholders, that are later inferred by the type checker.
+
+#[cfg(feature = "debug")]
+use std::time::Instant;
+
+use stellar_ast::IdentifierAST;
+use stellar_database::{
+    ty::{Type, TypeConstructor, TypeVariable, TypeVariableId},
+    EnumId, EnumItemId, ModuleId, State, Symbol,
+};
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::{
+    visit::{walk_expression, walk_function, walk_pattern, Visitor},
+    EnumItem, Expression, Function, ModuleItem, Pattern,
+};
+#[cfg(feature = "debug")]
+use tracing::trace;
+
+use super::expression_path;
+use crate::{
+    diagnostics::{
+        EnumItemPayloadArityMismatch, EnumItemTakesNoArguments, FailedToResolveEnumItem,
+        MissingEnumItemPayload,
+    },
+    resolution::{resolve_path_silently, scope::ScopeTree, type_alias_chain_names},
+    suggestions::find_similar_name,
+};
+
+/// An enum item, that a path in an expression or a pattern resolves to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedEnumItem {
+    pub item: EnumItemId,
+
+    /// Type of the enum, e.g. `Option[?0]` for `Option.Some(1)`.
+    pub ty: Type,
+}
+
+pub struct ResolveEnumItems<'s, 'h> {
+    state: &'s mut State,
+    modules: &'h FxHashMap<ModuleId, stellar_hir::Module>,
+    module: ModuleId,
+
+    /// Scopes of the function, that is currently being visited. Used to
+    /// skip paths, that start with a local variable, e.g. `color.value`.
+    scopes: Option<ScopeTree>,
+
+    /// Resolved enum items by locations of paths referring to them.
+    resolved: FxHashMap<Location, ResolvedEnumItem>,
+    next_type_variable_id: usize,
+}
+
+impl<'s, 'h> ResolveEnumItems<'s, 'h> {
+    /// Resolves enum items in all function bodies and returns them by
+    /// locations of paths referring to them.
+    pub fn run_all(
+        state: &'s mut State,
+        modules: &'h FxHashMap<ModuleId, stellar_hir::Module>,
+    ) -> FxHashMap<Location, ResolvedEnumItem> {
+        let mut resolved = FxHashMap::default();
+
+        for module in modules.keys() {
+            resolved.extend(ResolveEnumItems::run(state, modules, *module));
+        }
+
+        resolved
+    }
+
+    /// Resolves enum items in function bodies of a given module, see
+    /// [`ResolveEnumItems::run_all()`].
+    pub fn run(
+        state: &'s mut State,
+        modules: &'h FxHashMap<ModuleId, stellar_hir::Module>,
+        module: ModuleId,
+    ) -> FxHashMap<Location, ResolvedEnumItem> {
+        #[cfg(feature = "debug")]
+        let now = Instant::now();
+
+        let mut me = ResolveEnumItems {
+            state,
+            modules,
+            module,
+            scopes: None,
+            resolved: FxHashMap::default(),
+            next_type_variable_id: 0,
+        };
+
+        me.visit_module(&modules[&module]);
+
+        #[cfg(feature = "debug")]
+        trace!(
+            "resolve_enum_items_in(module = '{}') <{} us>",
+            module.filepath(me.state.db()),
+            now.elapsed().as_micros()
+        );
+
+        me.resolved
+    }
+
+    fn visit_function_body(&mut self, function: &Function) {
+        self.scopes = Some(ScopeTree::build(function));
+        walk_function(self, function);
+        self.scopes = None;
+    }
+
+    /// Resolves `Enum.Item` and `Enum[T].Item` in expression position.
+    fn resolve_expression(
+        &mut self,
+        expression: &Expression,
+    ) -> Option<(EnumItemId, &'h EnumItem)> {
+        let Expression::FieldAccess { left, right, .. } = expression else {
+            return None;
+        };
+
+        let (left, type_arguments) = match left.as_ref() {
+            Expression::TypeArguments {
+                left,
+                type_arguments,
+                ..
+            } => (left.as_ref(), Some(type_arguments)),
+            left => (left, None),
+        };
+
+        let path = expression_path(left)?;
+
+        let (item, item_hir) = self.resolve_enum_item(&path, *right)?;
+        let type_argument_locations = type_arguments
+            .map(|arguments| {
+                arguments
+                    .iter()
+                    .map(stellar_hir::Type::location)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        self.record(expression.location(), item, &type_argument_locations);
+
+        Some((item, item_hir))
+    }
+
+    /// Resolves `Enum.Item` in pattern position.
+    fn resolve_pattern_path(
+        &mut self,
+        location: Location,
+        path: &stellar_hir::Path,
+    ) -> Option<(EnumItemId, &'h EnumItem)> {
+        let (right, left) = path.identifiers.split_last()?;
+
+        let (item, item_hir) = self.resolve_enum_item(left, *right)?;
+        self.record(location, item, &[]);
+
+        Some((item, item_hir))
+    }
+
+    /// Resolves `right` in the namespace of an enum, that `left` path refers to.
+    fn resolve_enum_item(
+        &mut self,
+        left: &[IdentifierAST],
+        right: IdentifierAST,
+    ) -> Option<(EnumItemId, &'h EnumItem)> {
+        let first = left.first()?;
+
+        if self.is_local(*first) {
+            return None;
+        }
+
+        let db = self.state.db();
+
+        // `Opt.Some(1)` with `type Opt = Option;` constructs `Option.Some`.
+        let (enum_, alias) = match resolve_path_silently(db, self.module, left)? {
+            Symbol::Enum(enum_) => (enum_, None),
+            Symbol::TypeAlias(alias) => match db.expand_type_alias(alias) {
+                (_, Some(Symbol::Enum(enum_))) => (enum_, Some(alias)),
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let enum_hir = self.enum_hir(enum_)?;
+
+        let Some(item) = enum_.item(db, right.id) else {
+            // `Enum.method()` is a static method call, not an enum item.
+            if enum_hir
+                .methods
+                .iter()
+                .all(|method| method.signature.name.id != right.id)
+            {
+                let suggestion = find_similar_name(right.id, enum_.items(db).keys().copied());
+                let aliases = alias
+                    .map(|alias| type_alias_chain_names(db, alias))
+                    .unwrap_or_default();
+
+                self.state.diagnostics_mut().add_diagnostic(
+                    FailedToResolveEnumItem::new(enum_hir.name, right)
+                        .with_suggestion(suggestion)
+                        .with_aliases(aliases),
+                );
+            }
+
+            return None;
+        };
+
+        let item_hir = enum_hir
+            .items
+            .iter()
+            .find(|item_hir| item_hir.name().id == right.id)?;
+
+        Some((item, item_hir))
+    }
+
+    fn enum_hir(&self, enum_: EnumId) -> Option<&'h stellar_hir::Enum> {
+        let db = self.state.db();
+        let signature = enum_.signature(db);
+
+        match self
+            .modules
+            .get(&signature.module(db))?
+            .items
+            .get(signature.node_idx(db))?
+        {
+            ModuleItem::Enum(enum_) => Some(enum_),
+            _ => None,
+        }
+    }
+
+    fn is_local(&self, name: IdentifierAST) -> bool {
+        self.scopes.as_ref().is_some_and(|scopes| {
+            scopes
+                .bindings_at(name.location.start)
+                .iter()
+                .any(|binding| binding.name.id == name.id)
+        })
+    }
+
+    /// Records the type of an enum item usage. Generic arguments of the
+    /// enum are represented as type placeholders.
+    fn record(
+        &mut self,
+        location: Location,
+        item: EnumItemId,
+        type_argument_locations: &[Location],
+    ) {
+        let enum_ = item.enum_(self.state.db());
+
+        let Some(enum_hir) = self.enum_hir(enum_) else {
+            return;
+        };
+
+        let arguments = enum_hir
+            .generic_parameters
+            .iter()
+            .enumerate()
+            .map(|(idx, parameter)| {
+                let id = TypeVariableId(self.next_type_variable_id);
+                self.next_type_variable_id += 1;
+
+                Type::Variable(TypeVariable::TypePlaceholder {
+                    location: type_argument_locations.get(idx).copied(),
+                    origin_location: parameter.name.location,
+                    id,
+                })
+            })
+            .collect();
+
+        self.resolved.insert(
+            location,
+            ResolvedEnumItem {
+                item,
+                ty: Type::Constructor(TypeConstructor::new(Symbol::Enum(enum_), arguments)),
+            },
+        );
+    }
+
+    /// Checks the number of arguments (or inner patterns), that an enum item is used with.
+    fn check_payload(
+        &mut self,
+        item: EnumItemId,
+        item_hir: &EnumItem,
+        location: Location,
+        got: usize,
+    ) {
+        let name = item.name(self.state.db());
+
+        match item_hir {
+            EnumItem::Just { .. } => {
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(EnumItemTakesNoArguments::new(name, location, name.location));
+            }
+            EnumItem::TupleLike { fields, .. } if fields.len() != got => {
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(EnumItemPayloadArityMismatch::new(
+                        name,
+                        location,
+                        fields.len(),
+                        got,
+                        name.location,
+                    ));
+            }
+            _ => {}
+        }
+    }
+
+    /// Reports tuple-like enum items used without a payload, e.g. `Option.Some`.
+    fn check_missing_payload(
+        &mut self,
+        item: EnumItemId,
+        item_hir: &EnumItem,
+        usage: IdentifierAST,
+    ) {
+        if let EnumItem::TupleLike { fields, .. } = item_hir {
+            let definition_location = item.name(self.state.db()).location;
+
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(MissingEnumItemPayload::new(
+                    usage,
+                    fields.len(),
+                    definition_location,
+                ));
+        }
+    }
+}
+
+impl Visitor for ResolveEnumItems<'_, '_> {
+    fn visit_function(&mut self, function: &Function) {
+        self.visit_function_body(function);
+    }
+
+    fn visit_method(&mut self, method: &Function) {
+        self.visit_function_body(method);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Call {
+                callee, arguments, ..
+            } => {
+                if let Some((item, item_hir)) = self.resolve_expression(callee) {
+                    self.check_payload(item, item_hir, callee.location(), arguments.len());
+
+                    for argument in arguments {
+                        self.visit_expression(&argument.value);
+                    }
+
+                    return;
+                }
+            }
+            Expression::Struct { left, fields, .. } => {
+                if self.resolve_expression(left).is_some() {
+                    for field in fields {
+                        self.visit_struct_expression_item(field);
+                    }
+
+                    return;
+                }
+            }
+            Expression::FieldAccess { right, .. } => {
+                if let Some((item, item_hir)) = self.resolve_expression(expression) {
+                    self.check_missing_payload(item, item_hir, *right);
+
+                    return;
+                }
+            }
+            _ => {}
+        }
+
+        walk_expression(self, expression);
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Path { path } if path.identifiers.len() > 1 => {
+                if let Some((item, item_hir)) = self.resolve_pattern_path(path.location, path) {
+                    self.check_missing_payload(item, item_hir, *path.identifiers.last().unwrap());
+                }
+            }
+            Pattern::TupleLike {
+                location,
+                path,
+                inner_patterns,
+            } if path.identifiers.len() > 1 => {
+                if let Some((item, item_hir)) = self.resolve_pattern_path(*location, path) {
+                    // `Some(..)` matches any number of fields.
+                    if !inner_patterns
+                        .iter()
+                        .any(|pattern| matches!(pattern, Pattern::Rest { .. }))
+                        || matches!(item_hir, EnumItem::Just { .. })
+                    {
+                        self.check_payload(item, item_hir, path.location, inner_patterns.len());
+                    }
+                }
+            }
+            Pattern::Struct { location, path, .. } if path.identifiers.len() > 1 => {
+                self.resolve_pattern_path(*location, path);
+            }
+            _ => {}
+        }
+
+        walk_pattern(self, pattern);
+    }
+}
@@ -0,0 +1,316 @@
+//! Defines [`Substitution`] - a mapping from type variables to types they are
+//! inferred to be, which is built by unifying types with each other, and
+//! [`TypeVariables`], that creates type variables.
+//!
+//! Values of types, that implement all interfaces of an interface object type,
+//! coerce to it, e.g. `Meters` unifies with `dyn ToString`, if `Meters`
+//! implements `ToString`.
+
+use std::fmt::{self, Display};
+
+use stellar_database::{
+    symbol::BuiltinSymbolId,
+    ty::{Type, TypeVariable, TypeVariableId},
+    Symbol,
+};
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::{FxHashMap, FxHashSet};
+
+/// Creates type variables with unique IDs.
+#[derive(Debug, Default)]
+pub struct TypeVariables {
+    next_id: usize,
+}
+
+impl TypeVariables {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new type variable for an expression at a given location.
+    pub fn fresh(&mut self, location: Location) -> Type {
+        let id = TypeVariableId(self.next_id);
+        self.next_id += 1;
+
+        Type::Variable(TypeVariable::Expression { location, id })
+    }
+}
+
+/// Restricts types, that a type variable can be inferred to be, e.g. the type
+/// of `1` can only be an integer type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeVariableKind {
+    Integer,
+    Float,
+}
+
+impl TypeVariableKind {
+    /// Returns `true` if a type variable of this kind can be inferred to be
+    /// a given type.
+    #[must_use]
+    pub fn accepts(self, ty: &Type) -> bool {
+        let Type::Constructor(constructor) = ty else {
+            return false;
+        };
+
+        let Symbol::BuiltinSymbol(builtin) = constructor.symbol else {
+            return false;
+        };
+
+        match self {
+            Self::Integer => matches!(
+                builtin,
+                BuiltinSymbolId::Int8
+                    | BuiltinSymbolId::Int16
+                    | BuiltinSymbolId::Int32
+                    | BuiltinSymbolId::Int64
+                    | BuiltinSymbolId::Uint8
+                    | BuiltinSymbolId::Uint16
+                    | BuiltinSymbolId::Uint32
+                    | BuiltinSymbolId::Uint64
+            ),
+            Self::Float => matches!(builtin, BuiltinSymbolId::Float32 | BuiltinSymbolId::Float64),
+        }
+    }
+}
+
+impl Display for TypeVariableKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer => f.write_str("{integer}"),
+            Self::Float => f.write_str("{float}"),
+        }
+    }
+}
+
+/// Interfaces, that types implement, by symbols of the types.
+pub type Implementations = FxHashMap<Symbol, FxHashSet<Symbol>>;
+
+/// A mapping from type variables to types, that they are inferred to be.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Substitution {
+    types: FxHashMap<TypeVariableId, Type>,
+    kinds: FxHashMap<TypeVariableId, TypeVariableKind>,
+
+    /// Used to check, if a type coerces to an interface object type.
+    implementations: Implementations,
+}
+
+impl Substitution {
+    /// Creates a new empty substitution.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new empty substitution, that coerces types into interface
+    /// objects, using given implementations of interfaces.
+    #[inline]
+    #[must_use]
+    pub fn with_implementations(implementations: Implementations) -> Self {
+        Self {
+            implementations,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the type a type variable is inferred to be.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, id: TypeVariableId) -> Option<&Type> {
+        self.types.get(&id)
+    }
+
+    /// Returns the kind of a type variable, if it is restricted.
+    #[inline]
+    #[must_use]
+    pub fn kind(&self, id: TypeVariableId) -> Option<TypeVariableKind> {
+        self.kinds.get(&id).copied()
+    }
+
+    /// Returns an iterator over all restricted type variables and their kinds.
+    #[inline]
+    pub fn kinds(&self) -> impl Iterator<Item = (TypeVariableId, TypeVariableKind)> + '_ {
+        self.kinds.iter().map(|(id, kind)| (*id, *kind))
+    }
+
+    /// Restricts types, that a type variable can be inferred to be.
+    #[inline]
+    pub fn set_kind(&mut self, id: TypeVariableId, kind: TypeVariableKind) {
+        self.kinds.insert(id, kind);
+    }
+
+    /// Infers integer and float type variables, that are not inferred yet,
+    /// to be `int32` and `float64` respectively, e.g. the type of `1` in
+    /// `let x = 1;` becomes `int32`.
+    pub fn apply_numeric_defaults(&mut self) {
+        let defaults = self
+            .kinds
+            .iter()
+            .filter(|(id, _)| !self.types.contains_key(id))
+            .map(|(id, kind)| {
+                let builtin = match kind {
+                    TypeVariableKind::Integer => BuiltinSymbolId::Int32,
+                    TypeVariableKind::Float => BuiltinSymbolId::Float64,
+                };
+
+                (*id, Type::new_primitive(Symbol::BuiltinSymbol(builtin)))
+            })
+            .collect::<Vec<_>>();
+
+        self.types.extend(defaults);
+    }
+
+    /// Replaces all inferred type variables in a type with their types.
+    #[must_use]
+    pub fn apply(&self, ty: &Type) -> Type {
+        match ty {
+            Type::Variable(variable) => self
+                .types
+                .get(&variable.id())
+                .map_or_else(|| ty.clone(), |inferred| self.apply(inferred)),
+            Type::Constructor(constructor) => {
+                let mut constructor = constructor.clone();
+
+                for argument in &mut constructor.arguments {
+                    *argument = self.apply(argument);
+                }
+
+                Type::Constructor(constructor)
+            }
+            Type::Tuple { element_types } => Type::Tuple {
+                element_types: self.apply_all(element_types),
+            },
+            Type::Function {
+                parameter_types,
+                return_type,
+            } => Type::Function {
+                parameter_types: self.apply_all(parameter_types),
+                return_type: Box::new(self.apply(return_type)),
+            },
+            Type::Unit
+            | Type::Unknown
+            | Type::Never
+            | Type::GenericParameter(_)
+            | Type::InterfaceObject { .. } => ty.clone(),
+        }
+    }
+
+    fn apply_all(&self, types: &[Type]) -> Vec<Type> {
+        types.iter().map(|ty| self.apply(ty)).collect()
+    }
+
+    /// Infers type variables in two types, so that they become equal.
+    ///
+    /// Returns `false` if the types cannot be equal. [`Type::Unknown`] is equal
+    /// to any type, to avoid reporting errors caused by other errors, and
+    /// [`Type::Never`] coerces to any type, without inferring type variables.
+    /// Types, that implement all bounds of an interface object type, coerce
+    /// to it (generic arguments of the bounds are not checked).
+    pub fn unify(&mut self, left: &Type, right: &Type) -> bool {
+        match (self.apply(left), self.apply(right)) {
+            (Type::Unknown | Type::Never, _)
+            | (_, Type::Unknown | Type::Never)
+            | (Type::Unit, Type::Unit) => true,
+            (Type::Variable(left), Type::Variable(right)) if left.id() == right.id() => true,
+            (Type::Variable(variable), ty) | (ty, Type::Variable(variable)) => {
+                self.bind(variable.id(), ty)
+            }
+            (Type::Constructor(left), Type::Constructor(right)) => {
+                left.symbol == right.symbol && self.unify_all(&left.arguments, &right.arguments)
+            }
+            (
+                Type::Tuple {
+                    element_types: left,
+                },
+                Type::Tuple {
+                    element_types: right,
+                },
+            ) => self.unify_all(&left, &right),
+            (
+                Type::Function {
+                    parameter_types: left_parameter_types,
+                    return_type: left_return_type,
+                },
+                Type::Function {
+                    parameter_types: right_parameter_types,
+                    return_type: right_return_type,
+                },
+            ) => {
+                self.unify_all(&left_parameter_types, &right_parameter_types)
+                    && self.unify(&left_return_type, &right_return_type)
+            }
+            (Type::GenericParameter(left), Type::GenericParameter(right)) => left == right,
+            (Type::InterfaceObject { bounds: left }, Type::InterfaceObject { bounds: right }) => {
+                left == right
+            }
+            (Type::Constructor(constructor), Type::InterfaceObject { bounds })
+            | (Type::InterfaceObject { bounds }, Type::Constructor(constructor)) => bounds
+                .iter()
+                .all(|bound| self.implements(constructor.symbol, bound.symbol)),
+            _ => false,
+        }
+    }
+
+    fn implements(&self, ty: Symbol, interface: Symbol) -> bool {
+        self.implementations
+            .get(&ty)
+            .is_some_and(|interfaces| interfaces.contains(&interface))
+    }
+
+    fn unify_all(&mut self, left: &[Type], right: &[Type]) -> bool {
+        left.len() == right.len()
+            && left
+                .iter()
+                .zip(right)
+                .all(|(left, right)| self.unify(left, right))
+    }
+
+    /// Infers a type variable to be a given type, unless the type contains
+    /// the variable itself, e.g. `?0 = List[?0]`, or the variable's kind
+    /// doesn't accept the type.
+    fn bind(&mut self, id: TypeVariableId, ty: Type) -> bool {
+        if occurs(id, &ty) {
+            return false;
+        }
+
+        if let Some(kind) = self.kind(id) {
+            match &ty {
+                Type::Variable(variable) => match self.kind(variable.id()) {
+                    Some(other_kind) if other_kind != kind => return false,
+                    Some(_) => {}
+                    None => self.set_kind(variable.id(), kind),
+                },
+                Type::Unknown => {}
+                ty if !kind.accepts(ty) => return false,
+                _ => {}
+            }
+        }
+
+        self.types.insert(id, ty);
+        true
+    }
+}
+
+fn occurs(id: TypeVariableId, ty: &Type) -> bool {
+    match ty {
+        Type::Variable(variable) => variable.id() == id,
+        Type::Constructor(constructor) => constructor
+            .arguments
+            .iter()
+            .any(|argument| occurs(id, argument)),
+        Type::Tuple { element_types } => element_types.iter().any(|ty| occurs(id, ty)),
+        Type::Function {
+            parameter_types,
+            return_type,
+        } => parameter_types.iter().any(|ty| occurs(id, ty)) || occurs(id, return_type),
+        Type::Unit
+        | Type::Unknown
+        | Type::Never
+        | Type::GenericParameter(_)
+        | Type::InterfaceObject { .. } => false,
+    }
+}
@@ -1,5 +1,10 @@
 #![allow(warnings)]
 
 mod diagnostics;
+pub mod expression_analysis;
 pub mod resolution;
 pub mod signature_analysis;
+mod suggestions;
+
+pub use diagnostics::DIAGNOSTICS;
+
@@ -0,0 +1,299 @@
+//! Reports private module items, that are never used, e.g. `Foo` in:
+//!
+//! ```stellar
+//! struct Foo {}
+//!
+//! pub fun main() {}
+//! ```
+//!
+//! An item is used, if it is reachable from a public item or from the `main`
+//! function of a package through references (see [`State::references()`]).
+//! So a private struct, that only appears in a signature of a public function,
+//! is not reported, but a private function, that is only called by another
+//! unused function, is.
+//!
+//! References in types are recorded by [`CheckTypePaths`], so the pass must
+//! run after it. Paths in expressions and patterns, e.g. `foo` in `foo()`,
+//! are resolved and recorded by the pass itself.
+//!
+//! Items with an `#[allow(dead_code)]` attribute are not reported.
+//!
+//! [`CheckTypePaths`]: super::check_type_paths::CheckTypePaths
+
+#[cfg(feature = "debug")]
+use std::time::Instant;
+
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_database::{ModuleId, PackageId, State, Symbol, DUMMY_MODULE_ID};
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::{FxHashMap, FxHashSet};
+use stellar_hir::{
+    visit::{walk_expression, walk_function, walk_module_item, walk_pattern, Visitor},
+    Expression, Function, ModuleItem, Pattern, TypeConstructor,
+};
+use stellar_interner::IdentifierId;
+#[cfg(feature = "debug")]
+use tracing::trace;
+
+use super::{resolve_path_silently, scope::ScopeTree};
+use crate::{diagnostics::UnusedModuleItem, expression_analysis::expression_path};
+
+/// A module item together with locations of names, that it refers to.
+struct ItemReferences {
+    symbol: Symbol,
+    name_locations: Vec<Location>,
+}
+
+pub struct CheckDeadCode<'s> {
+    state: &'s mut State,
+    module: ModuleId,
+
+    /// Scopes of the function, that is currently being visited. Used to
+    /// skip paths, that start with a local variable, e.g. `point.x`.
+    scopes: Option<ScopeTree>,
+
+    /// Module items of all visited modules in the order of their definitions.
+    items: Vec<ItemReferences>,
+}
+
+impl<'s> CheckDeadCode<'s> {
+    pub fn run_all(state: &'s mut State, modules: &FxHashMap<ModuleId, stellar_hir::Module>) {
+        let packages = modules
+            .keys()
+            .map(|module| module.package())
+            .collect::<FxHashSet<_>>();
+
+        for package in packages {
+            CheckDeadCode::run(state, modules, package);
+        }
+    }
+
+    /// Reports unused module items of a given package. Items of other
+    /// packages can only refer to public items, which are always used, so
+    /// only modules of the package are visited.
+    pub fn run(
+        state: &'s mut State,
+        modules: &FxHashMap<ModuleId, stellar_hir::Module>,
+        package: PackageId,
+    ) {
+        let mut me = CheckDeadCode {
+            state,
+            module: DUMMY_MODULE_ID,
+            scopes: None,
+            items: vec![],
+        };
+
+        for (module, hir) in modules {
+            if module.package() != package {
+                continue;
+            }
+
+            #[cfg(feature = "debug")]
+            let now = Instant::now();
+
+            me.module = *module;
+            me.visit_module(hir);
+
+            #[cfg(feature = "debug")]
+            trace!(
+                "check_dead_code_in(module = '{}') <{} us>",
+                module.filepath(me.state.db()),
+                now.elapsed().as_micros()
+            );
+        }
+
+        let reachable = me.reachable_items(
+            modules
+                .keys()
+                .copied()
+                .filter(|module| module.package() == package),
+        );
+        me.report_unreachable_items(&reachable);
+    }
+
+    /// Walks references starting from public items and `main` functions of
+    /// packages, and returns all items, that are reached.
+    fn reachable_items(&self, modules: impl Iterator<Item = ModuleId>) -> FxHashSet<Symbol> {
+        let db = self.state.db();
+
+        let referred_symbols = self
+            .state
+            .references()
+            .iter()
+            .flat_map(|(symbol, locations)| {
+                locations.iter().map(move |location| (*location, symbol))
+            })
+            .collect::<FxHashMap<_, _>>();
+        let references = self
+            .items
+            .iter()
+            .map(|item| (item.symbol, item.name_locations.as_slice()))
+            .collect::<FxHashMap<_, _>>();
+
+        let mut stack = self
+            .items
+            .iter()
+            .map(|item| item.symbol)
+            .filter(|symbol| symbol.visibility(db) != Visibility::Private)
+            .chain(
+                modules
+                    .filter(|module| module.package().root_module(db) == *module)
+                    .filter_map(|module| {
+                        module
+                            .module_item_symbol_or_none(db, IdentifierId::from("main"))
+                            .filter(|main| main.is_function())
+                    }),
+            )
+            .collect::<Vec<_>>();
+        let mut reachable = stack.iter().copied().collect::<FxHashSet<_>>();
+
+        while let Some(symbol) = stack.pop() {
+            let Some(locations) = references.get(&symbol) else {
+                continue;
+            };
+
+            for location in *locations {
+                let Some(referred) = referred_symbols.get(location) else {
+                    continue;
+                };
+
+                // Using an enum item uses its enum.
+                let referred = match *referred {
+                    Symbol::EnumItem(item) => Symbol::Enum(item.enum_(db)),
+                    referred => referred,
+                };
+
+                if reachable.insert(referred) {
+                    stack.push(referred);
+                }
+            }
+        }
+
+        reachable
+    }
+
+    fn report_unreachable_items(&mut self, reachable: &FxHashSet<Symbol>) {
+        let db = self.state.db();
+
+        let diagnostics = self
+            .items
+            .iter()
+            .map(|item| item.symbol)
+            .filter(|symbol| {
+                !reachable.contains(symbol) && !symbol.signature(db).attributes(db).allow_dead_code
+            })
+            .map(|symbol| UnusedModuleItem::new(symbol.kind(), symbol.name(db)))
+            .collect::<Vec<_>>();
+
+        for diagnostic in diagnostics {
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+        }
+    }
+
+    /// Resolves the longest prefix of a path, that refers to a symbol, and
+    /// records the reference, e.g. `Point` in `Point.new()`.
+    fn record_path(&mut self, path: &[IdentifierAST]) {
+        let Some(first) = path.first() else {
+            return;
+        };
+
+        if self.is_local(*first) {
+            return;
+        }
+
+        for length in (1..=path.len()).rev() {
+            let prefix = &path[..length];
+
+            if let Some(symbol) = resolve_path_silently(self.state.db(), self.module, prefix) {
+                let location = prefix[length - 1].location;
+
+                self.state.record_reference(symbol, location);
+                self.push_name_location(location);
+
+                return;
+            }
+        }
+    }
+
+    fn push_name_location(&mut self, location: Location) {
+        if let Some(item) = self.items.last_mut() {
+            item.name_locations.push(location);
+        }
+    }
+
+    fn is_local(&self, name: IdentifierAST) -> bool {
+        self.scopes.as_ref().is_some_and(|scopes| {
+            scopes
+                .bindings_at(name.location.start)
+                .iter()
+                .any(|binding| binding.name.id == name.id)
+        })
+    }
+
+    fn visit_function_body(&mut self, function: &Function) {
+        self.scopes = Some(ScopeTree::build(function));
+        walk_function(self, function);
+        self.scopes = None;
+    }
+}
+
+impl Visitor for CheckDeadCode<'_> {
+    fn visit_module_item(&mut self, item: &ModuleItem) {
+        let db = self.state.db();
+
+        // Inline modules are visited as separate modules, and imports are
+        // not module items.
+        let symbol = item
+            .name()
+            .and_then(|name| self.module.module_item_symbol_or_none(db, name))
+            .filter(|symbol| !symbol.is_module() && symbol.name(db).location == item.location());
+
+        let Some(symbol) = symbol else {
+            return;
+        };
+
+        self.items.push(ItemReferences {
+            symbol,
+            name_locations: vec![],
+        });
+
+        walk_module_item(self, item);
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        self.visit_function_body(function);
+    }
+
+    fn visit_method(&mut self, method: &Function) {
+        self.visit_function_body(method);
+    }
+
+    fn visit_type_constructor(&mut self, constructor: &TypeConstructor) {
+        if let Some(name) = constructor.path.identifiers.last() {
+            self.push_name_location(name.location);
+        }
+
+        for argument in &constructor.arguments {
+            self.visit_type(argument);
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Some(path) = expression_path(expression) {
+            self.record_path(&path);
+        } else {
+            walk_expression(self, expression);
+        }
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        if let Pattern::Path { path }
+        | Pattern::Struct { path, .. }
+        | Pattern::TupleLike { path, .. } = pattern
+        {
+            self.record_path(&path.identifiers);
+        }
+
+        walk_pattern(self, pattern);
+    }
+}
@@ -0,0 +1,119 @@
+//! Checks, that names of definitions follow the naming conventions:
+//!
+//! * enums, structs, interfaces and type aliases are named in `UpperCamelCase`;
+//! * functions, methods, fields and modules are named in `lower_snake_case`.
+//!
+//! Violations are reported as warnings, that can be allowed by their codes
+//! (see [`stellar_diagnostics::Diagnostics::allow()`]). See
+//! [`stellar_english_commons::case`] for the exact rules.
+
+#[cfg(feature = "debug")]
+use std::time::Instant;
+
+use stellar_ast::IdentifierAST;
+use stellar_database::{ModuleId, State};
+use stellar_english_commons::case::{
+    is_snake_case, is_upper_camel_case, to_snake_case, to_upper_camel_case,
+};
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::{
+    visit::{walk_module_item, Visitor},
+    Function, ModuleItem, StructField,
+};
+use stellar_interner::DUMMY_IDENTIFIER_ID;
+#[cfg(feature = "debug")]
+use tracing::trace;
+
+use crate::diagnostics::{NonSnakeCaseName, NonUpperCamelCaseName};
+
+pub struct CheckNamingConventions<'s> {
+    state: &'s mut State,
+}
+
+impl<'s> CheckNamingConventions<'s> {
+    pub fn run_all(state: &'s mut State, modules: &FxHashMap<ModuleId, stellar_hir::Module>) {
+        for (module, hir) in modules {
+            CheckNamingConventions::run(state, *module, hir);
+        }
+    }
+
+    /// Checks names of a given module and of definitions in it.
+    pub fn run(state: &'s mut State, module: ModuleId, hir: &stellar_hir::Module) {
+        #[cfg(feature = "debug")]
+        let now = Instant::now();
+
+        let mut me = CheckNamingConventions { state };
+
+        // Modules of standalone files don't have names.
+        let name = module.name(me.state.db());
+        if name.id != DUMMY_IDENTIFIER_ID {
+            me.check_snake_case("module", name);
+        }
+
+        me.visit_module(hir);
+
+        #[cfg(feature = "debug")]
+        trace!(
+            "check_naming_conventions_in(module = '{}') <{} us>",
+            module.filepath(me.state.db()),
+            now.elapsed().as_micros()
+        );
+    }
+
+    fn check_upper_camel_case(&mut self, kind: &'static str, name: IdentifierAST) {
+        let name_str = name.id.to_string();
+
+        if !is_upper_camel_case(&name_str) {
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(NonUpperCamelCaseName::new(
+                    kind,
+                    name,
+                    to_upper_camel_case(name_str),
+                ));
+        }
+    }
+
+    fn check_snake_case(&mut self, kind: &'static str, name: IdentifierAST) {
+        let name_str = name.id.to_string();
+
+        if !is_snake_case(&name_str) {
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(NonSnakeCaseName::new(kind, name, to_snake_case(name_str)));
+        }
+    }
+}
+
+impl Visitor for CheckNamingConventions<'_> {
+    fn visit_module_item(&mut self, item: &ModuleItem) {
+        match item {
+            ModuleItem::Enum(enum_) => self.check_upper_camel_case("enum", enum_.name),
+            ModuleItem::Function(function) => {
+                self.check_snake_case("function", function.signature.name);
+            }
+            ModuleItem::Interface(interface) => {
+                self.check_upper_camel_case("interface", interface.name);
+            }
+            ModuleItem::Struct(struct_) => self.check_upper_camel_case("struct", struct_.name),
+            ModuleItem::TupleLikeStruct(struct_) => {
+                self.check_upper_camel_case("tuple-like struct", struct_.name);
+            }
+            ModuleItem::TypeAlias(alias) => self.check_upper_camel_case("type alias", alias.name),
+            // Names of inline modules are checked together with other modules.
+            ModuleItem::Import { .. } | ModuleItem::Module(_) => {}
+        }
+
+        walk_module_item(self, item);
+    }
+
+    fn visit_function(&mut self, _: &Function) {}
+
+    fn visit_method(&mut self, method: &Function) {
+        self.check_snake_case("method", method.signature.name);
+    }
+
+    fn visit_struct_field(&mut self, field: &StructField) {
+        self.check_snake_case("field", field.name);
+    }
+}
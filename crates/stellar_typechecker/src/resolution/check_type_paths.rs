@@ -0,0 +1,263 @@
+//! Checks, that names used as types refer to types, e.g. reports `foo` in:
+//!
+//! ```stellar
+//! fun foo() {}
+//!
+//! fun bar(x: foo) {}
+//! ```
+//!
+//! Names, that cannot be resolved, are reported with similar names in scope,
+//! e.g. generic parameters, types defined in or imported into the module and
+//! builtin types, or names of submodules, if the failed segment is not the
+//! last one. Resolved names are recorded as references to their symbols (see
+//! [`State::references()`]) and modules, that define them, are recorded as
+//! dependencies of the current module. Uses of deprecated items and generic
+//! type aliases with a wrong number of generic arguments are reported as
+//! well.
+
+#[cfg(feature = "debug")]
+use std::time::Instant;
+
+use stellar_database::{
+    symbol::BuiltinSymbolId, ModuleId, ResolutionError, ResolutionErrorKind, State, Symbol,
+};
+use stellar_fx_hash::{FxHashMap, FxHashSet};
+use stellar_hir::{
+    visit::{walk_generic_parameter, walk_module_item, walk_type, Visitor},
+    GenericParameter, ModuleItem, Type,
+};
+use stellar_interner::{builtin_identifiers::BIG_SELF, IdentifierId};
+#[cfg(feature = "debug")]
+use tracing::trace;
+
+use super::{check_deprecated_usage, report_resolution_error};
+use crate::{
+    diagnostics::{ExpectedType, FailedToResolveType, TypeAliasArityMismatch},
+    suggestions::find_similar_names,
+};
+
+/// Maximum number of similar names suggested for an unresolved name.
+const MAX_SUGGESTIONS: usize = 3;
+
+pub struct CheckTypePaths<'s> {
+    state: &'s mut State,
+    module: ModuleId,
+
+    /// Names of generic parameters of the module item, that is currently
+    /// being visited, including generic parameters of its methods.
+    generic_parameters: FxHashSet<IdentifierId>,
+
+    /// Symbol of the module item, that is currently being visited, so that
+    /// deprecated items can refer to themselves without warnings.
+    item: Option<Symbol>,
+}
+
+impl<'s> CheckTypePaths<'s> {
+    pub fn run_all(state: &'s mut State, modules: &FxHashMap<ModuleId, stellar_hir::Module>) {
+        for (module, hir) in modules {
+            CheckTypePaths::run(state, *module, hir);
+        }
+    }
+
+    /// Checks type paths in a given module.
+    pub fn run(state: &'s mut State, module: ModuleId, hir: &stellar_hir::Module) {
+        #[cfg(feature = "debug")]
+        let now = Instant::now();
+
+        let mut me = CheckTypePaths {
+            state,
+            module,
+            generic_parameters: FxHashSet::default(),
+            item: None,
+        };
+
+        me.visit_module(hir);
+
+        #[cfg(feature = "debug")]
+        trace!(
+            "check_type_paths_in(module = '{}') <{} us>",
+            module.filepath(me.state.db()),
+            now.elapsed().as_micros()
+        );
+    }
+
+    fn check_type_constructor(&mut self, constructor: &stellar_hir::TypeConstructor) {
+        let identifiers = &constructor.path.identifiers;
+
+        // Generic parameters shadow module items, and `Self` refers to the
+        // type, that is being defined.
+        if identifiers.first().is_some_and(|first| {
+            first.id == BIG_SELF || self.generic_parameters.contains(&first.id)
+        }) {
+            return;
+        }
+
+        if let [name] = identifiers[..] {
+            if BuiltinSymbolId::from_identifier_or_none(name.id).is_some() {
+                return;
+            }
+        }
+
+        let db = self.state.db();
+
+        let symbol = match db.resolve_path(self.module, identifiers) {
+            Ok(symbol) => symbol,
+            Err(error) => {
+                let is_last_segment = identifiers
+                    .last()
+                    .is_some_and(|last| last.location == error.failed_segment.location);
+
+                self.report_unresolved_path(*error, is_last_segment);
+                return;
+            }
+        };
+
+        let kind = symbol.kind();
+
+        if !kind.is_type() {
+            let name = symbol.name(db);
+
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(ExpectedType::new(
+                    constructor.path.location,
+                    kind,
+                    name.id.to_string(),
+                    name.location,
+                ));
+        }
+
+        if symbol.is_type_alias() {
+            self.check_type_alias_arity(symbol, constructor);
+        }
+
+        if let Some(name) = identifiers.last() {
+            self.state.record_reference(symbol, name.location);
+            check_deprecated_usage(self.state, symbol, name.location, self.item);
+        }
+
+        let defining_module = symbol.module(self.state.db());
+        self.module
+            .add_dependency(self.state.db_mut(), defining_module);
+    }
+
+    /// Reports a path, that cannot be resolved, suggesting names, that are
+    /// similar to the failed segment: types in scope, if it is the last
+    /// segment, and submodules otherwise.
+    fn report_unresolved_path(&mut self, error: ResolutionError, is_last_segment: bool) {
+        let db = self.state.db();
+
+        let candidates = match (error.reason, error.searched_in) {
+            (ResolutionErrorKind::NotInScope, _) if is_last_segment => self
+                .generic_parameters
+                .iter()
+                .copied()
+                .chain(types_of(self.module.module_item_symbols(db)))
+                .chain(types_of(self.module.resolved_imports(db)))
+                .chain(BuiltinSymbolId::ALL.iter().map(|&builtin| builtin.into()))
+                .collect::<FxHashSet<_>>(),
+            (ResolutionErrorKind::NotInScope, _) => self
+                .module
+                .submodules(db)
+                .keys()
+                .copied()
+                .chain(
+                    self.module
+                        .resolved_imports(db)
+                        .iter()
+                        .filter(|(_, symbol)| symbol.is_module())
+                        .map(|(name, _)| *name),
+                )
+                .collect(),
+            (ResolutionErrorKind::NotFoundInNamespace, Symbol::Module(namespace))
+                if is_last_segment =>
+            {
+                types_of(namespace.module_item_symbols(db)).collect()
+            }
+            (ResolutionErrorKind::NotFoundInNamespace, Symbol::Module(namespace)) => {
+                namespace.submodules(db).keys().copied().collect()
+            }
+            _ => {
+                report_resolution_error(self.state, error);
+                return;
+            }
+        };
+
+        let suggestions = find_similar_names(error.failed_segment.id, candidates, MAX_SUGGESTIONS);
+        let diagnostic = FailedToResolveType::new(error.failed_segment, error.previous_segment)
+            .with_suggestions(suggestions);
+
+        self.state.diagnostics_mut().add_diagnostic(diagnostic);
+    }
+
+    /// Checks, that a type alias is used with as many generic arguments, as
+    /// it has generic parameters. Parameters with default values may be
+    /// omitted. Generic arguments of other types are checked by
+    /// [`CheckGenericArguments`].
+    ///
+    /// [`CheckGenericArguments`]: crate::signature_analysis::check_generic_arguments::CheckGenericArguments
+    fn check_type_alias_arity(
+        &mut self,
+        symbol: Symbol,
+        constructor: &stellar_hir::TypeConstructor,
+    ) {
+        let Some(ModuleItem::TypeAlias(alias)) = self.state.hir().get(symbol).map(AsRef::as_ref)
+        else {
+            return;
+        };
+
+        let max_expected = alias.generic_parameters.len();
+        let min_expected = alias
+            .generic_parameters
+            .iter()
+            .filter(|parameter| parameter.default_value.is_none())
+            .count();
+        let found = constructor.arguments.len();
+
+        if (min_expected..=max_expected).contains(&found) {
+            return;
+        }
+
+        // Point at the first extra argument, if there are too many of them.
+        let location = constructor
+            .arguments
+            .get(max_expected)
+            .map_or(constructor.location, Type::location);
+
+        let diagnostic =
+            TypeAliasArityMismatch::new(location, alias.name, min_expected, max_expected, found);
+        self.state.diagnostics_mut().add_diagnostic(diagnostic);
+    }
+}
+
+impl Visitor for CheckTypePaths<'_> {
+    fn visit_module_item(&mut self, item: &ModuleItem) {
+        self.generic_parameters.clear();
+        self.item = item.name().and_then(|name| {
+            self.module
+                .module_item_symbol_or_none(self.state.db(), name)
+        });
+        walk_module_item(self, item);
+    }
+
+    fn visit_generic_parameter(&mut self, generic_parameter: &GenericParameter) {
+        self.generic_parameters.insert(generic_parameter.name.id);
+        walk_generic_parameter(self, generic_parameter);
+    }
+
+    fn visit_type(&mut self, ty: &Type) {
+        if let Type::Constructor(constructor) = ty {
+            self.check_type_constructor(constructor);
+        }
+
+        walk_type(self, ty);
+    }
+}
+
+/// Returns names of symbols, that are types.
+fn types_of(symbols: &FxHashMap<IdentifierId, Symbol>) -> impl Iterator<Item = IdentifierId> + '_ {
+    symbols
+        .iter()
+        .filter(|(_, symbol)| symbol.kind().is_type())
+        .map(|(name, _)| *name)
+}
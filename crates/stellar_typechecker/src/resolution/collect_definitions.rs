@@ -1,73 +1,120 @@
-#[cfg(feature = "debug")]
-use std::time::Instant;
+use std::sync::Arc;
 
 use stellar_ast::IdentifierAST;
 use stellar_ast_lowering::LoweredModule;
 use stellar_database::{
-    EnumData, EnumId, EnumItemData, FunctionData, InterfaceData, ModuleId, PackageId,
-    SignatureData, State, StructData, Symbol, TupleLikeStructData, TypeAliasData, TypeAliasId,
+    Deprecation, EnumData, EnumId, EnumItemData, FunctionData, InterfaceData, ItemAttributes,
+    ModuleId, PackageId, SignatureData, State, StructData, Symbol, SymbolKind, TupleLikeStructData,
+    TypeAliasData, TypeAliasId,
 };
+use stellar_filesystem::location::Location;
 use stellar_fx_hash::FxHashMap;
-#[cfg(feature = "debug")]
-use tracing::trace;
+use stellar_hir::{
+    visit::{walk_module_item, Visitor},
+    Attribute, AttributeArgument, FunctionParameter, ModuleItem, NotSelfFunctionParameter,
+};
+use stellar_interner::IdentifierId;
+use tracing::{instrument, trace, trace_span};
 
-use crate::diagnostics::{EnumItemDefinedMultipleTimes, ItemDefinedMultipleTimes};
+use crate::diagnostics::{
+    EnumItemDefinedMultipleTimes, EnumMethodConflictsWithVariant,
+    InlineModuleConflictsWithSubmodule, ItemConflictsWithSubmodule, ItemDefinedMultipleTimes,
+    MalformedDeprecatedAttribute, ModuleDepthLimitExceeded,
+};
 
 pub struct CollectDefinitions<'s> {
     state: &'s mut State,
     module: ModuleId,
     current_node_idx: usize,
+
+    /// Symbol of the module item, that is currently being visited, if it
+    /// is defined (items with duplicate names are not).
+    defined_symbol: Option<Symbol>,
 }
 
 impl<'s> CollectDefinitions<'s> {
     pub fn run_all(state: &'s mut State, modules: &FxHashMap<ModuleId, stellar_hir::Module>) {
+        Self::add_inline_modules(state, modules);
+
         for module in modules {
             CollectDefinitions {
                 state,
                 module: *module.0,
                 current_node_idx: 0,
+                defined_symbol: None,
             }
             .run(module.1);
         }
     }
 
-    fn run(mut self, module: &stellar_hir::Module) {
-        #[cfg(feature = "debug")]
-        let now = Instant::now();
-
-        for item in &module.items {
-            match item {
-                stellar_hir::ModuleItem::Enum(enum_) => self.collect_definition_of_enum(enum_),
-                stellar_hir::ModuleItem::Function(function) => {
-                    self.collect_definition_of_function(function)
-                }
-                stellar_hir::ModuleItem::Struct(struct_) => {
-                    self.collect_definition_of_struct(struct_)
-                }
-                stellar_hir::ModuleItem::Interface(interface) => {
-                    self.collect_definition_of_interface(interface)
-                }
-                stellar_hir::ModuleItem::TupleLikeStruct(struct_) => {
-                    self.collect_definition_of_tuple_like_struct(struct_)
-                }
-                stellar_hir::ModuleItem::TypeAlias(alias) => {
-                    self.collect_definition_of_type_alias(alias)
-                }
-                _ => {}
+    /// Adds inline modules, e.g. `module foo { ... }`, to submodules of their
+    /// parents, so that names in them can be resolved.
+    ///
+    /// Inline modules must be added before definitions are collected, so
+    /// that items conflicting with them are reported regardless of the
+    /// order, in which modules are visited.
+    fn add_inline_modules(state: &mut State, modules: &FxHashMap<ModuleId, stellar_hir::Module>) {
+        let db = state.db();
+
+        let mut inline_modules = modules
+            .keys()
+            .copied()
+            .filter_map(|module| {
+                let parent = module.parent(db)?;
+
+                (!parent.contains_submodule_with_id(db, module)).then_some((module, parent))
+            })
+            .collect::<Vec<_>>();
+
+        // The first definition of a module in a file wins.
+        inline_modules.sort_by_key(|(module, _)| module.name(db).location.start);
+
+        for (module, parent) in inline_modules {
+            let name = module.name(state.db());
+
+            if let Some(submodule) = parent.submodule(state.db(), name.id) {
+                let diagnostic =
+                    InlineModuleConflictsWithSubmodule::new(name, submodule.filepath(state.db()));
+
+                state.diagnostics_mut().add_diagnostic(diagnostic);
+
+                continue;
             }
+
+            parent.add_submodule(state.db_mut(), module);
         }
+    }
 
-        #[cfg(feature = "debug")]
-        trace!(
-            "collect_definitions_in(module = '{}') <{} us>",
-            self.module.filepath(self.state.db()),
-            now.elapsed().as_micros()
-        );
+    #[instrument(
+        name = "collect_definitions_in",
+        level = "debug",
+        skip_all,
+        fields(module = %self.module.debug(self.state.db()))
+    )]
+    fn run(mut self, module: &stellar_hir::Module) {
+        let depth = self.module.path(self.state.db()).segments().len();
+        let limit = self.state.config().max_module_depth();
+
+        // Definitions of too deeply nested modules are not collected, so that
+        // later stages don't have to walk arbitrarily long module chains.
+        if depth > limit {
+            let name = self.module.name(self.state.db());
+
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(ModuleDepthLimitExceeded::new(name.location, depth, limit));
+
+            return;
+        }
+
+        self.visit_module(module);
     }
 
+    #[instrument(level = "trace", skip_all, fields(name = %enum_hir.name.id))]
     fn collect_definition_of_enum(&mut self, enum_hir: &stellar_hir::Enum) {
-        #[cfg(feature = "debug")]
-        let now = Instant::now();
+        if self.check_for_duplicate_definition(enum_hir.name, SymbolKind::Enum) {
+            return;
+        }
 
         let signature = SignatureData::alloc(
             self.state.db_mut(),
@@ -80,41 +127,60 @@ impl<'s> CollectDefinitions<'s> {
 
         for item in &enum_hir.items {
             let name = item.name();
+            let _span = trace_span!("collect_definition_of_enum_item", name = %name.id).entered();
 
-            #[cfg(feature = "debug")]
-            let now = Instant::now();
-
-            self.check_for_duplicate_enum_item(enum_, name);
+            if self.check_for_duplicate_enum_item(enum_, name) {
+                continue;
+            }
 
             let item = EnumItemData::alloc(self.state.db_mut(), enum_, name, self.module);
 
             enum_.add_item(self.state.db_mut(), name.id, item);
+        }
 
-            #[cfg(feature = "debug")]
-            trace!(
-                "collect_definition_of_enum_item(enum_name = '{}', item_name = '{}', module = '{}') <{} us>",
-                enum_.name.id,
-                name.id,
-                self.module.filepath(self.state.db()),
-                now.elapsed().as_micros()
-            );
+        for method in &enum_hir.methods {
+            self.collect_definition_of_enum_method(enum_, method);
         }
 
-        self.check_for_duplicate_definition(enum_hir.name);
+        self.add_module_item(enum_hir.name.id, Symbol::Enum(enum_));
+    }
+
+    /// Adds a method to the enum, unless the enum has an item with the same
+    /// name, e.g. `enum Shape { Circle, fun Circle() {} }`.
+    #[instrument(level = "trace", skip_all, fields(name = %method.signature.name.id))]
+    fn collect_definition_of_enum_method(&mut self, enum_: EnumId, method: &stellar_hir::Function) {
+        let name = method.signature.name;
 
-        self.module
-            .add_module_item(self.state.db_mut(), enum_hir.name.id, Symbol::Enum(enum_));
-
-        #[cfg(feature = "debug")]
-        trace!(
-            "collect_definition_of_enum(name = '{}', module = '{}') <{} us>",
-            enum_.name.id,
-            self.module.filepath(self.state.db()),
-            now.elapsed().as_micros()
-        )
+        if let Some(item) = enum_.item(self.state.db(), name.id) {
+            let item_name = item.name(self.state.db());
+
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(EnumMethodConflictsWithVariant::new(name, item_name));
+
+            return;
+        }
+
+        let signature = SignatureData::alloc(
+            self.state.db_mut(),
+            method.signature.visibility,
+            name,
+            self.current_node_idx,
+            self.module,
+        );
+        let id = FunctionData::alloc(self.state.db_mut(), signature);
+        id.set_default_parameters(self.state.db_mut(), default_parameters(method));
+
+        // The item is checked above.
+        let _ = enum_.add_method(self.state.db_mut(), name.id, id);
     }
 
+    #[instrument(level = "trace", skip_all, fields(name = %function.signature.name.id))]
     fn collect_definition_of_function(&mut self, function: &stellar_hir::Function) {
+        if self.check_for_duplicate_definition(function.signature.name, SymbolKind::Function) {
+            return;
+        }
+
         let signature = SignatureData::alloc(
             self.state.db_mut(),
             function.signature.visibility,
@@ -124,19 +190,16 @@ impl<'s> CollectDefinitions<'s> {
         );
 
         let id = FunctionData::alloc(self.state.db_mut(), signature);
+        id.set_default_parameters(self.state.db_mut(), default_parameters(function));
 
-        self.check_for_duplicate_definition(function.signature.name);
-
-        self.module.add_module_item(
-            self.state.db_mut(),
-            function.signature.name.id,
-            Symbol::Function(id),
-        );
+        self.add_module_item(function.signature.name.id, Symbol::Function(id));
     }
 
+    #[instrument(level = "trace", skip_all, fields(name = %struct_.name.id))]
     fn collect_definition_of_struct(&mut self, struct_: &stellar_hir::Struct) {
-        #[cfg(feature = "debug")]
-        let now = Instant::now();
+        if self.check_for_duplicate_definition(struct_.name, SymbolKind::Struct) {
+            return;
+        }
 
         let signature = SignatureData::alloc(
             self.state.db_mut(),
@@ -148,23 +211,14 @@ impl<'s> CollectDefinitions<'s> {
 
         let id = StructData::alloc(self.state.db_mut(), signature);
 
-        self.check_for_duplicate_definition(struct_.name);
-
-        self.module
-            .add_module_item(self.state.db_mut(), struct_.name.id, Symbol::Struct(id));
-
-        #[cfg(feature = "debug")]
-        trace!(
-            "collect_definition_of_struct(name = '{}', module = '{}') <{} us>",
-            struct_.name.id,
-            self.module.filepath(self.state.db()),
-            now.elapsed().as_micros()
-        )
+        self.add_module_item(struct_.name.id, Symbol::Struct(id));
     }
 
+    #[instrument(level = "trace", skip_all, fields(name = %struct_.name.id))]
     fn collect_definition_of_tuple_like_struct(&mut self, struct_: &stellar_hir::TupleLikeStruct) {
-        #[cfg(feature = "debug")]
-        let now = Instant::now();
+        if self.check_for_duplicate_definition(struct_.name, SymbolKind::TupleLikeStruct) {
+            return;
+        }
 
         let signature = SignatureData::alloc(
             self.state.db_mut(),
@@ -176,26 +230,14 @@ impl<'s> CollectDefinitions<'s> {
 
         let id = TupleLikeStructData::alloc(self.state.db_mut(), signature);
 
-        self.check_for_duplicate_definition(struct_.name);
-
-        self.module.add_module_item(
-            self.state.db_mut(),
-            struct_.name.id,
-            Symbol::TupleLikeStruct(id),
-        );
-
-        #[cfg(feature = "debug")]
-        trace!(
-            "collect_definition_of_tuple_like_struct(name = '{}', module = '{}') <{} us>",
-            struct_.name.id,
-            self.module.filepath(self.state.db()),
-            now.elapsed().as_micros()
-        )
+        self.add_module_item(struct_.name.id, Symbol::TupleLikeStruct(id));
     }
 
+    #[instrument(level = "trace", skip_all, fields(name = %interface.name.id))]
     fn collect_definition_of_interface(&mut self, interface: &stellar_hir::Interface) {
-        #[cfg(feature = "debug")]
-        let now = Instant::now();
+        if self.check_for_duplicate_definition(interface.name, SymbolKind::Interface) {
+            return;
+        }
 
         let signature = SignatureData::alloc(
             self.state.db_mut(),
@@ -207,26 +249,14 @@ impl<'s> CollectDefinitions<'s> {
 
         let id = InterfaceData::alloc(self.state.db_mut(), signature);
 
-        self.check_for_duplicate_definition(interface.name);
-
-        self.module.add_module_item(
-            self.state.db_mut(),
-            interface.name.id,
-            Symbol::Interface(id),
-        );
-
-        #[cfg(feature = "debug")]
-        trace!(
-            "collect_definition_of_interface(name = '{}', module = '{}') <{} us>",
-            interface.name.id,
-            self.module.filepath(self.state.db()),
-            now.elapsed().as_micros()
-        )
+        self.add_module_item(interface.name.id, Symbol::Interface(id));
     }
 
+    #[instrument(level = "trace", skip_all, fields(name = %alias.name.id))]
     fn collect_definition_of_type_alias(&mut self, alias: &stellar_hir::TypeAlias) {
-        #[cfg(feature = "debug")]
-        let now = Instant::now();
+        if self.check_for_duplicate_definition(alias.name, SymbolKind::TypeAlias) {
+            return;
+        }
 
         let signature = SignatureData::alloc(
             self.state.db_mut(),
@@ -238,36 +268,114 @@ impl<'s> CollectDefinitions<'s> {
 
         let id = TypeAliasData::alloc(self.state.db_mut(), signature);
 
-        self.check_for_duplicate_definition(alias.name);
+        // The path is resolved lazily, e.g. when `Opt.Some` is used with
+        // `type Opt = Option;`, because the aliased item may be not collected yet.
+        if let stellar_hir::Type::Constructor(constructor) = &alias.value {
+            id.set_aliased_path(self.state.db_mut(), constructor.path.identifiers.clone());
+        }
 
+        self.add_module_item(alias.name.id, Symbol::TypeAlias(id));
+    }
+
+    fn add_module_item(&mut self, name: IdentifierId, symbol: Symbol) {
         self.module
-            .add_module_item(self.state.db_mut(), alias.name.id, Symbol::TypeAlias(id));
-
-        #[cfg(feature = "debug")]
-        trace!(
-            "collect_definition_of_type_alias(name = '{}', module = '{}') <{} us>",
-            alias.name.id,
-            self.module.filepath(self.state.db()),
-            now.elapsed().as_micros()
-        );
+            .add_module_item(self.state.db_mut(), name, symbol);
+        self.defined_symbol = Some(symbol);
+
+        trace!("defined {}", symbol.debug(self.state.db()));
     }
 
-    fn check_for_duplicate_definition(&mut self, name: IdentifierAST) {
-        if let Some(symbol) = self
+    /// Reports a name, that is already defined in the module as a module item
+    /// or a submodule, and returns `true` if it is.
+    ///
+    /// Duplicates must not be allocated, so that the first definition stays
+    /// reachable and no orphan entities are left in the database.
+    fn check_for_duplicate_definition(&mut self, name: IdentifierAST, kind: SymbolKind) -> bool {
+        if let Some(submodule) = self.module.submodule(self.state.db(), name.id) {
+            let diagnostic = ItemConflictsWithSubmodule::new(
+                name.id,
+                kind,
+                name.location,
+                submodule.filepath(self.state.db()),
+            );
+
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+            true
+        } else if let Some(symbol) = self
             .module
             .module_item_symbol_or_none(self.state.db(), name.id)
         {
             let diagnostic = ItemDefinedMultipleTimes::new(
                 name.id,
+                symbol.kind(),
                 symbol.name(self.state.db()).location,
+                kind,
                 name.location,
             );
 
             self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Collects attributes of a module item, that affect later stages, e.g.
+    /// `#[deprecated("use bar instead")]` and `#[allow(dead_code)]`.
+    fn collect_attributes(&mut self, attributes: &[Attribute]) -> ItemAttributes {
+        let mut item_attributes = ItemAttributes::default();
+
+        for attribute in attributes {
+            if attribute.name.id.as_str() == "allow" {
+                item_attributes.allow_dead_code |= attribute.arguments.iter().flatten().any(
+                    |argument| {
+                        matches!(argument, AttributeArgument::Identifier(name) if name.id.as_str() == "dead_code")
+                    },
+                );
+
+                continue;
+            }
+
+            if attribute.name.id.as_str() != "deprecated" {
+                continue;
+            }
+
+            let note = match attribute.arguments.as_deref() {
+                None => None,
+                Some([AttributeArgument::String { value, .. }]) => Some(value.clone()),
+                _ => {
+                    self.state
+                        .diagnostics_mut()
+                        .add_diagnostic(MalformedDeprecatedAttribute::new(attribute.location));
+
+                    continue;
+                }
+            };
+
+            item_attributes.deprecation = Some(Deprecation {
+                location: attribute.location,
+                note,
+            });
         }
+
+        item_attributes
+    }
+
+    /// Returns the inline module, that was allocated for a given module item
+    /// during lowering.
+    fn inline_module(&self, module: &stellar_hir::InlineModule) -> Option<ModuleId> {
+        let db = self.state.db();
+
+        self.module
+            .submodule(db, module.name.id)
+            .filter(|submodule| submodule.name(db).location == module.name.location)
     }
 
-    fn check_for_duplicate_enum_item(&mut self, enum_: EnumId, item_name: IdentifierAST) {
+    /// Reports an enum item, that is already defined in the enum, and returns
+    /// `true` if it is.
+    fn check_for_duplicate_enum_item(&mut self, enum_: EnumId, item_name: IdentifierAST) -> bool {
         if let Some(enum_item) = enum_.item(self.state.db(), item_name.id) {
             let diagnostic = EnumItemDefinedMultipleTimes::new(
                 enum_.signature(self.state.db()).name(self.state.db()).id,
@@ -277,6 +385,79 @@ impl<'s> CollectDefinitions<'s> {
             );
 
             self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+            true
+        } else {
+            false
         }
     }
 }
+
+impl Visitor for CollectDefinitions<'_> {
+    fn visit_module(&mut self, module: &stellar_hir::Module) {
+        for (idx, item) in module.items.iter().enumerate() {
+            self.current_node_idx = idx;
+            walk_module_item(self, item);
+
+            let attributes = self.collect_attributes(item.attributes());
+
+            if let Some(symbol) = self.defined_symbol.take() {
+                symbol
+                    .signature(self.state.db())
+                    .set_attributes(self.state.db_mut(), attributes);
+                self.state.hir_mut().insert(symbol, Arc::new(item.clone()));
+            } else if let ModuleItem::Module(module) = item {
+                if let Some(module) = self.inline_module(module) {
+                    module.set_attributes(self.state.db_mut(), attributes);
+                }
+            }
+        }
+    }
+
+    fn visit_enum(&mut self, enum_: &stellar_hir::Enum) {
+        self.collect_definition_of_enum(enum_);
+    }
+
+    fn visit_function(&mut self, function: &stellar_hir::Function) {
+        self.collect_definition_of_function(function);
+    }
+
+    fn visit_struct(&mut self, struct_: &stellar_hir::Struct) {
+        self.collect_definition_of_struct(struct_);
+    }
+
+    fn visit_interface(&mut self, interface: &stellar_hir::Interface) {
+        self.collect_definition_of_interface(interface);
+    }
+
+    fn visit_tuple_like_struct(&mut self, struct_: &stellar_hir::TupleLikeStruct) {
+        self.collect_definition_of_tuple_like_struct(struct_);
+    }
+
+    fn visit_type_alias(&mut self, alias: &stellar_hir::TypeAlias) {
+        self.collect_definition_of_type_alias(alias);
+    }
+
+    fn visit_import(&mut self, _: Location, _: &stellar_hir::ImportPath) {}
+}
+
+/// Returns the number of trailing parameters of a function, that have
+/// default values. Defaults before parameters without them are reported
+/// when lowering to HIR and are not counted.
+fn default_parameters(function: &stellar_hir::Function) -> usize {
+    function
+        .signature
+        .parameters
+        .iter()
+        .rev()
+        .take_while(|parameter| {
+            matches!(
+                parameter,
+                FunctionParameter::NotSelfParameter(NotSelfFunctionParameter {
+                    default_value: Some(_),
+                    ..
+                })
+            )
+        })
+        .count()
+}
@@ -0,0 +1,766 @@
+//! Defines [`completions_at()`] - names, that can be typed at a given position
+//! in a file, which is used by the language server to answer code completion
+//! requests.
+//!
+//! The context of the position is found syntactically:
+//!
+//! * after `.` - fields and methods of the receiver, e.g. `point.|`, items of
+//!   a module, e.g. `geometry.|`, or items of an enum, e.g. `Color.|`,
+//! * in a type, e.g. `let p: |` - types and generic parameters in scope,
+//! * in an expression - local variables, parameters, module items, submodules
+//!   and imports,
+//! * in an import, e.g. `import std.io.|` - packages, modules and items along
+//!   the typed path.
+//!
+//! Definitions of the file must be collected before (see [`CollectDefinitions`]),
+//! but the file may be edited since then: the item at the position is parsed
+//! again from the source in the source map. To make incomplete code like
+//! `point.` parseable, a placeholder identifier is inserted at the position.
+//!
+//! [`CollectDefinitions`]: crate::resolution::collect_definitions::CollectDefinitions
+
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{
+    symbol::{BuiltinSymbolId, SymbolKind},
+    ty::{Type, TypeConstructor},
+    Database, HirStorage, ModuleId, State, Symbol,
+};
+use stellar_diagnostics::Diagnostics;
+use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_fx_hash::{FxHashMap, FxHashSet};
+use stellar_hir::{
+    visit::{walk_expression, walk_statement, walk_type_constructor, Visitor},
+    Expression, Function, FunctionParameter, GenericParameter, ModuleItem, Statement,
+};
+use stellar_interner::{builtin_identifiers::SMALL_SELF, IdentifierId, PathId};
+use stellar_parser::{parse_items, ParseResult};
+
+use crate::{
+    expression_analysis::{expression_path, infer_expression_types::InferExpressionTypes},
+    resolution::{
+        resolve_path_silently,
+        scope::{Binding, BindingKind, ScopeTree},
+    },
+    signature_analysis::check_interface_conformance::{signature_to_string, type_to_string},
+};
+
+/// An identifier inserted at the completion position, so that incomplete
+/// code, e.g. `point.`, can be parsed.
+const PLACEHOLDER: &str = "__completion__";
+
+/// A name suggested by code completion.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompletionItem {
+    /// The name itself, e.g. `x`.
+    pub label: String,
+    pub kind: SymbolKind,
+
+    /// A short description shown next to the name, e.g. the type of a field
+    /// or the signature of a function.
+    pub detail: String,
+}
+
+/// Returns names, that can be typed at a given position in a file, sorted
+/// by labels.
+///
+/// Only names starting with the identifier, that is typed right before the
+/// position, are returned, e.g. `x` and `xs` for `let y = x|`.
+///
+/// `modules` is HIR of the analyzed modules, it is used to infer the type of
+/// the receiver after `.`.
+#[must_use]
+pub fn completions_at(
+    state: &mut State,
+    modules: &FxHashMap<ModuleId, stellar_hir::Module>,
+    filepath: PathId,
+    offset: ByteOffset,
+) -> Vec<CompletionItem> {
+    let Ok(source) = state.source_map_mut().get_or_load(filepath) else {
+        return Vec::new();
+    };
+
+    let Some(module) = module_of_file(state.db(), modules, filepath) else {
+        return Vec::new();
+    };
+
+    let Some(before) = source.get(..offset.0) else {
+        return Vec::new();
+    };
+
+    let start = before.trim_end_matches(is_identifier_char).len();
+    let prefix = &before[start..];
+
+    let mut completions = Completions::default();
+
+    if let Some(path) = import_path_before(&before[..start]) {
+        import_completions(state.db(), state.hir(), module, &path, &mut completions);
+    } else if let Some((
+        module,
+        item,
+        FindContext {
+            context: Some(context),
+            pending_patterns,
+            ..
+        },
+    )) = context_at(state, &source, filepath, module, offset, ByteOffset(start))
+    {
+        let mut completion = Completion {
+            state,
+            modules,
+            module,
+            item: &item,
+            start: ByteOffset(start),
+            pending_patterns,
+            completions: &mut completions,
+        };
+
+        completion.complete(context);
+    }
+
+    let mut items = completions.items;
+
+    items.retain(|item| item.label.starts_with(prefix));
+    items.sort_unstable_by(|a, b| a.label.cmp(&b.label));
+    items
+}
+
+/// A syntactic context of the completion position.
+#[derive(Debug)]
+enum CompletionContext {
+    /// A name after `.`, e.g. `point.|`.
+    Member {
+        /// Location of the expression before `.`.
+        receiver: Location,
+
+        /// Identifiers of the expression before `.`, if it is a path,
+        /// e.g. `geometry.shapes` in `geometry.shapes.|`.
+        path: Option<Vec<IdentifierAST>>,
+    },
+
+    /// A type, e.g. `let p: |`, or a type in a module, e.g. `let p: geometry.|`.
+    Type { namespace: Vec<IdentifierAST> },
+
+    /// A name used as a value, e.g. `let y = |`.
+    Expression,
+}
+
+/// Returns the module of a given file.
+///
+/// Inline modules are defined in files of their parents, so the module with
+/// the shortest path is the one of the file.
+fn module_of_file(
+    db: &Database,
+    modules: &FxHashMap<ModuleId, stellar_hir::Module>,
+    filepath: PathId,
+) -> Option<ModuleId> {
+    modules
+        .keys()
+        .copied()
+        .filter(|module| module.filepath(db) == filepath)
+        .min_by_key(|module| module.path(db).segments().len())
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Returns identifiers of an import path, that precede the completion
+/// position, e.g. `std` and `io` for `import std.io.|`, or `None` if the
+/// position is not in an import.
+fn import_path_before(text: &str) -> Option<Vec<IdentifierId>> {
+    let line = text.rsplit('\n').next()?.trim_start();
+    let path = line.strip_prefix("import")?;
+
+    if !path.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let path = path.trim();
+
+    if path.is_empty() {
+        return Some(Vec::new());
+    }
+
+    path.strip_suffix('.')?
+        .split('.')
+        .map(|segment| {
+            let segment = segment.trim();
+
+            (!segment.is_empty() && segment.chars().all(is_identifier_char))
+                .then(|| IdentifierId::from(segment))
+        })
+        .collect()
+}
+
+/// Completes an import path: packages first, then modules and items.
+fn import_completions(
+    db: &Database,
+    hir: &HirStorage,
+    module: ModuleId,
+    path: &[IdentifierId],
+    completions: &mut Completions,
+) {
+    let package = module.package();
+
+    let Some((first, rest)) = path.split_first() else {
+        completions.add_symbol(
+            db,
+            hir,
+            package.name(db),
+            Symbol::Module(package.root_module(db)),
+        );
+
+        for (name, dependency) in package.dependencies(db) {
+            completions.add_symbol(db, hir, *name, Symbol::Module(dependency.root_module(db)));
+        }
+
+        return;
+    };
+
+    let root = if *first == package.name(db) {
+        package
+    } else if let Some(dependency) = package.dependencies(db).get(first) {
+        *dependency
+    } else {
+        return;
+    };
+
+    let mut namespace = Symbol::Module(root.root_module(db));
+
+    for segment in rest {
+        let member = match namespace {
+            Symbol::Module(module) => module.local_symbol_or_none(db, *segment),
+            Symbol::Enum(enum_) => enum_.item(db, *segment).map(Symbol::EnumItem),
+            _ => None,
+        };
+
+        match member {
+            Some(member) if is_accessible(db, module, namespace, member) => namespace = member,
+            _ => return,
+        }
+    }
+
+    completions.add_namespace_members(db, hir, module, namespace, |_| true);
+}
+
+/// Returns `true` if a member of a namespace can be used in a given module:
+/// private items are only accessible within their package.
+fn is_accessible(db: &Database, module: ModuleId, namespace: Symbol, member: Symbol) -> bool {
+    namespace.module(db).package() == module.package()
+        || member.visibility(db) != Visibility::Private
+}
+
+/// Parses and lowers the item at the completion position and finds the
+/// context of the position in it.
+///
+/// The placeholder is inserted at the position first. If the item cannot be
+/// parsed, e.g. `point.|` is followed by another statement, the placeholder is
+/// followed by `;` too.
+fn context_at(
+    state: &mut State,
+    source: &str,
+    filepath: PathId,
+    module: ModuleId,
+    offset: ByteOffset,
+    start: ByteOffset,
+) -> Option<(ModuleId, ModuleItem, FindContext)> {
+    [PLACEHOLDER.to_owned(), format!("{PLACEHOLDER};")]
+        .into_iter()
+        .find_map(|placeholder| {
+            let source = format!(
+                "{}{placeholder}{}",
+                &source[..offset.0],
+                &source[offset.0..]
+            );
+
+            let mut diagnostics = Diagnostics::new();
+            let items = parse_items(filepath, source, &mut diagnostics);
+            let (module, item) = item_at(state.db(), module, items, offset)?;
+
+            let mut lowered = silently(state, |state| {
+                LowerToHir::run_all(
+                    state,
+                    vec![ParseResult::new(
+                        module,
+                        stellar_ast::Module {
+                            filepath,
+                            items: vec![item],
+                            docstring: None,
+                        },
+                    )],
+                )
+            });
+            let item = lowered.remove(&module)?.items.pop()?;
+
+            let mut finder = FindContext {
+                start,
+                context: None,
+                pending_patterns: Vec::new(),
+            };
+            finder.visit_module_item(&item);
+
+            finder.context.is_some().then_some((module, item, finder))
+        })
+}
+
+/// Returns the last item, that starts before a given offset, together with
+/// the module, that the item is defined in. Items of inline modules are
+/// searched recursively.
+fn item_at(
+    db: &Database,
+    module: ModuleId,
+    items: Vec<stellar_ast::ModuleItem>,
+    offset: ByteOffset,
+) -> Option<(ModuleId, stellar_ast::ModuleItem)> {
+    let item = items
+        .into_iter()
+        .take_while(|item| item.location().start <= offset)
+        .last()?;
+
+    match item {
+        stellar_ast::ModuleItem::Module(inline_module) => item_at(
+            db,
+            module.submodule(db, inline_module.name.id)?,
+            inline_module.items,
+            offset,
+        ),
+        item => Some((module, item)),
+    }
+}
+
+/// Runs an analysis, discarding diagnostics, that it reports, e.g. errors
+/// caused by the placeholder.
+fn silently<T>(state: &mut State, f: impl FnOnce(&mut State) -> T) -> T {
+    let diagnostics = std::mem::take(state.diagnostics_mut());
+    let result = f(state);
+    *state.diagnostics_mut() = diagnostics;
+
+    result
+}
+
+/// Finds the node, that contains the placeholder.
+struct FindContext {
+    /// Start of the identifier with the placeholder.
+    start: ByteOffset,
+    context: Option<CompletionContext>,
+
+    /// Locations of patterns of `let` statements, which values contain the
+    /// placeholder, e.g. `x` in `let x = |;`. Names bound by the patterns
+    /// are not defined yet at the placeholder.
+    pending_patterns: Vec<Location>,
+}
+
+impl Visitor for FindContext {
+    fn visit_statement(&mut self, statement: &Statement) {
+        if let Statement::Let {
+            location, value, ..
+        } = statement
+        {
+            let value = value.location();
+
+            if value.start <= self.start && self.start <= value.end {
+                self.pending_patterns.push(Location {
+                    end: value.start,
+                    ..*location
+                });
+            }
+        }
+
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::FieldAccess { left, right, .. } if right.location.start == self.start => {
+                self.context = Some(CompletionContext::Member {
+                    receiver: left.location(),
+                    path: expression_path(left),
+                });
+            }
+            Expression::Identifier(identifier) if identifier.location.start == self.start => {
+                self.context = Some(CompletionContext::Expression);
+            }
+            _ => walk_expression(self, expression),
+        }
+    }
+
+    fn visit_type_constructor(&mut self, constructor: &stellar_hir::TypeConstructor) {
+        if let Some((name, namespace)) = constructor.path.identifiers.split_last() {
+            if name.location.start == self.start {
+                self.context = Some(CompletionContext::Type {
+                    namespace: namespace.to_vec(),
+                });
+
+                return;
+            }
+        }
+
+        walk_type_constructor(self, constructor);
+    }
+}
+
+/// Completion items without duplicate labels: names added first shadow the
+/// ones added later, e.g. local variables shadow module items.
+#[derive(Default)]
+struct Completions {
+    items: Vec<CompletionItem>,
+    labels: FxHashSet<String>,
+}
+
+impl Completions {
+    fn add(&mut self, label: String, kind: SymbolKind, detail: String) {
+        if !label.contains(PLACEHOLDER) && self.labels.insert(label.clone()) {
+            self.items.push(CompletionItem {
+                label,
+                kind,
+                detail,
+            });
+        }
+    }
+
+    fn add_symbol(&mut self, db: &Database, hir: &HirStorage, name: IdentifierId, symbol: Symbol) {
+        let detail = match symbol {
+            Symbol::Function(function) => hir.get_function(function).map_or_else(
+                || format!("function {}", symbol.path(db)),
+                |function| signature_to_string(&function.signature),
+            ),
+            _ => format!("{} {}", symbol.kind(), symbol.path(db)),
+        };
+
+        self.add(name.to_string(), symbol.kind(), detail);
+    }
+
+    /// Adds members of a module or an enum, that satisfy a given predicate
+    /// and can be used in a given module. Imports of the module are not
+    /// included, because modules don't re-export names.
+    fn add_namespace_members(
+        &mut self,
+        db: &Database,
+        hir: &HirStorage,
+        module: ModuleId,
+        namespace: Symbol,
+        predicate: impl Fn(Symbol) -> bool,
+    ) {
+        let members: Vec<(IdentifierId, Symbol)> = match namespace {
+            Symbol::Module(namespace) => namespace
+                .module_item_symbols(db)
+                .iter()
+                .map(|(name, symbol)| (*name, *symbol))
+                .chain(
+                    namespace
+                        .submodules(db)
+                        .iter()
+                        .map(|(name, submodule)| (*name, Symbol::Module(*submodule))),
+                )
+                .collect(),
+            Symbol::Enum(enum_) => enum_
+                .items_ordered(db)
+                .map(|item| (item.name(db).id, Symbol::EnumItem(item)))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        for (name, member) in members {
+            if predicate(member) && is_accessible(db, module, namespace, member) {
+                self.add_symbol(db, hir, name, member);
+            }
+        }
+    }
+
+    fn add_method(&mut self, method: &Function) {
+        // Methods without `self` cannot be called on values.
+        if method
+            .signature
+            .parameters
+            .iter()
+            .any(|parameter| matches!(parameter, FunctionParameter::SelfParameter(_)))
+        {
+            self.add(
+                method.signature.name.id.to_string(),
+                SymbolKind::Method,
+                signature_to_string(&method.signature),
+            );
+        }
+    }
+}
+
+/// Completes names in a context found in a module item.
+struct Completion<'s, 'h, 'c> {
+    state: &'s mut State,
+    modules: &'h FxHashMap<ModuleId, stellar_hir::Module>,
+    module: ModuleId,
+
+    /// HIR of the item with the placeholder.
+    item: &'c ModuleItem,
+
+    /// Start of the identifier with the placeholder.
+    start: ByteOffset,
+
+    /// See [`FindContext::pending_patterns`].
+    pending_patterns: Vec<Location>,
+    completions: &'c mut Completions,
+}
+
+impl<'c> Completion<'_, '_, 'c> {
+    fn complete(&mut self, context: CompletionContext) {
+        let (function, generic_parameters) = self.enclosing_function();
+        let bindings = function.map_or_else(Vec::new, |function| {
+            ScopeTree::build(function)
+                .bindings_at(self.start)
+                .into_iter()
+                .filter(|binding| {
+                    let start = binding.name.location.start;
+
+                    start < self.start
+                        && !self
+                            .pending_patterns
+                            .iter()
+                            .any(|pattern| pattern.start <= start && start < pattern.end)
+                })
+                .collect()
+        });
+
+        match context {
+            CompletionContext::Member { receiver, path } => {
+                self.complete_member(function, &bindings, receiver, path);
+            }
+            CompletionContext::Type { namespace } => {
+                self.complete_type(&generic_parameters, &namespace);
+            }
+            CompletionContext::Expression => self.complete_expression(&bindings),
+        }
+    }
+
+    /// Returns the function or the method, that contains the placeholder,
+    /// and generic parameters in scope.
+    fn enclosing_function(&self) -> (Option<&'c Function>, Vec<&'c GenericParameter>) {
+        let (generic_parameters, methods) = match self.item {
+            ModuleItem::Function(function) => {
+                return (
+                    Some(function),
+                    function.signature.generic_parameters.iter().collect(),
+                )
+            }
+            ModuleItem::Struct(struct_) => (&struct_.generic_parameters, &struct_.methods),
+            ModuleItem::TupleLikeStruct(struct_) => (&struct_.generic_parameters, &struct_.methods),
+            ModuleItem::Enum(enum_) => (&enum_.generic_parameters, &enum_.methods),
+            ModuleItem::Interface(interface) => (&interface.generic_parameters, &interface.methods),
+            ModuleItem::TypeAlias(alias) => {
+                return (None, alias.generic_parameters.iter().collect());
+            }
+            ModuleItem::Import { .. } | ModuleItem::Module(_) => return (None, Vec::new()),
+        };
+
+        let method = methods
+            .iter()
+            .take_while(|method| method.signature.name.location.start <= self.start)
+            .last();
+
+        let generic_parameters = generic_parameters
+            .iter()
+            .chain(
+                method
+                    .into_iter()
+                    .flat_map(|method| &method.signature.generic_parameters),
+            )
+            .collect();
+
+        (method, generic_parameters)
+    }
+
+    /// Returns the symbol of the item with the placeholder, e.g. the struct,
+    /// that `self` refers to in its methods.
+    fn item_symbol(&self) -> Option<Symbol> {
+        let name = match self.item {
+            ModuleItem::Struct(struct_) => struct_.name,
+            ModuleItem::TupleLikeStruct(struct_) => struct_.name,
+            ModuleItem::Enum(enum_) => enum_.name,
+            _ => return None,
+        };
+
+        self.module
+            .module_item_symbol_or_none(self.state.db(), name.id)
+    }
+
+    fn complete_member(
+        &mut self,
+        function: Option<&Function>,
+        bindings: &[Binding],
+        receiver: Location,
+        path: Option<Vec<IdentifierAST>>,
+    ) {
+        let is_local = |path: &[IdentifierAST]| {
+            path.first()
+                .is_some_and(|first| bindings.iter().any(|binding| binding.name.id == first.id))
+        };
+
+        let symbol = match path {
+            // `geometry.|` or `Color.|`.
+            Some(path) if !is_local(&path) => {
+                let db = self.state.db();
+
+                if let Some(namespace) = resolve_path_silently(db, self.module, &path) {
+                    self.completions.add_namespace_members(
+                        db,
+                        self.state.hir(),
+                        self.module,
+                        namespace,
+                        |_| true,
+                    );
+                }
+
+                return;
+            }
+            // `self` is not typed by the type inference, so its type is the
+            // item, that the method belongs to.
+            Some(path) if path.len() == 1 && path[0].id == SMALL_SELF => self.item_symbol(),
+            _ => function.and_then(|function| {
+                let types = silently(self.state, |state| {
+                    InferExpressionTypes::run_function(state, self.modules, self.module, function)
+                });
+
+                match types.get(&receiver) {
+                    Some(Type::Constructor(TypeConstructor { symbol, .. })) => Some(*symbol),
+                    _ => None,
+                }
+            }),
+        };
+
+        if let Some(symbol) = symbol {
+            self.complete_fields_and_methods(symbol);
+        }
+    }
+
+    /// Adds fields and methods of values of a given type. Private ones are
+    /// only added in the module, that defines the type.
+    fn complete_fields_and_methods(&mut self, symbol: Symbol) {
+        let db = self.state.db();
+
+        let Some(item) = self.state.hir().get(symbol) else {
+            return;
+        };
+
+        let is_accessible = |visibility: Visibility| {
+            visibility != Visibility::Private || symbol.module(db) == self.module
+        };
+
+        let methods = match item.as_ref() {
+            ModuleItem::Struct(struct_) => {
+                for field in &struct_.fields {
+                    if is_accessible(field.visibility) {
+                        self.completions.add(
+                            field.name.id.to_string(),
+                            SymbolKind::Field,
+                            type_to_string(&field.ty),
+                        );
+                    }
+                }
+
+                &struct_.methods
+            }
+            ModuleItem::TupleLikeStruct(struct_) => &struct_.methods,
+            ModuleItem::Enum(enum_) => &enum_.methods,
+            _ => return,
+        };
+
+        for method in methods {
+            if is_accessible(method.signature.visibility) {
+                self.completions.add_method(method);
+            }
+        }
+    }
+
+    fn complete_type(
+        &mut self,
+        generic_parameters: &[&GenericParameter],
+        namespace: &[IdentifierAST],
+    ) {
+        let is_type = |symbol: Symbol| symbol.kind().is_type();
+
+        if !namespace.is_empty() {
+            let db = self.state.db();
+
+            if let Some(namespace) = resolve_path_silently(db, self.module, namespace) {
+                self.completions.add_namespace_members(
+                    db,
+                    self.state.hir(),
+                    self.module,
+                    namespace,
+                    |symbol| is_type(symbol) || symbol.is_module(),
+                );
+            }
+
+            return;
+        }
+
+        for parameter in generic_parameters {
+            self.completions.add(
+                parameter.name.id.to_string(),
+                SymbolKind::GenericParameter,
+                SymbolKind::GenericParameter.to_string(),
+            );
+        }
+
+        self.complete_module_symbols(is_type);
+
+        for builtin in BuiltinSymbolId::ALL {
+            self.completions.add_symbol(
+                self.state.db(),
+                self.state.hir(),
+                (*builtin).into(),
+                Symbol::BuiltinSymbol(*builtin),
+            );
+        }
+    }
+
+    fn complete_expression(&mut self, bindings: &[Binding]) {
+        for binding in bindings.iter().rev() {
+            let kind = match binding.kind {
+                BindingKind::Local | BindingKind::Captured => SymbolKind::LocalVariable,
+                BindingKind::Parameter => SymbolKind::Parameter,
+            };
+            let detail = if binding.mutable {
+                format!("mutable {kind}")
+            } else {
+                kind.to_string()
+            };
+
+            self.completions
+                .add(binding.name.id.to_string(), kind, detail);
+        }
+
+        self.complete_module_symbols(|_| true);
+    }
+
+    /// Adds module items, submodules and imports of the module, that
+    /// satisfy a given predicate.
+    fn complete_module_symbols(&mut self, predicate: impl Fn(Symbol) -> bool) {
+        let db = self.state.db();
+        let hir = self.state.hir();
+
+        let symbols = self
+            .module
+            .module_item_symbols(db)
+            .iter()
+            .map(|(name, symbol)| (*name, *symbol))
+            .chain(
+                self.module
+                    .submodules(db)
+                    .iter()
+                    .map(|(name, submodule)| (*name, Symbol::Module(*submodule))),
+            )
+            .chain(
+                self.module
+                    .resolved_imports(db)
+                    .iter()
+                    .map(|(name, symbol)| (*name, *symbol)),
+            );
+
+        for (name, symbol) in symbols {
+            if predicate(symbol) {
+                self.completions.add_symbol(db, hir, name, symbol);
+            }
+        }
+    }
+}
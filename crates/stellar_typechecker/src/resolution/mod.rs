@@ -1,17 +1,176 @@
+pub mod check_naming_conventions;
+pub mod check_dead_code;
+pub mod check_type_paths;
 pub mod collect_definitions;
+pub mod completion;
 pub mod resolve_imports;
+pub mod scope;
 
 use std::iter;
 
 use itertools::Itertools;
-use stellar_ast::IdentifierAST;
-use stellar_database::{EnumId, ModuleId, PackageId, State, Symbol, TypeAliasId};
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_database::{
+    Database, EnumId, ModuleId, PackageId, ResolutionError, ResolutionErrorKind, State, Symbol,
+    TypeAliasId,
+};
+use stellar_filesystem::location::Location;
+use stellar_interner::IdentifierId;
 
-use crate::diagnostics::{
-    EnumItemsDoNotServeAsNamespaces, FailedToResolveEnumItem, FailedToResolveNameInModule,
-    FailedToResolvePackage, ModuleItemsExceptEnumsDoNotServeAsNamespaces,
+use crate::{
+    diagnostics::{
+        DeprecatedItemUsage, EnumItemsDoNotServeAsNamespaces, FailedToResolveEnumItem,
+        FailedToResolveName, FailedToResolveNameInModule, FailedToResolvePackage,
+        FailedToResolvePrivateModuleItem, ModuleItemsExceptEnumsDoNotServeAsNamespaces,
+        NameInModuleFailedToLoad,
+    },
+    suggestions::find_similar_name,
 };
 
+/// Resolves a path in a given module without emitting any diagnostics.
+///
+/// See [`Database::resolve_path()`] for details.
+pub(crate) fn resolve_path_silently(
+    db: &Database,
+    module: ModuleId,
+    identifiers: &[IdentifierAST],
+) -> Option<Symbol> {
+    db.resolve_path(module, identifiers).ok()
+}
+
+/// Resolves a path in a given module, reporting a diagnostic if it cannot
+/// be resolved.
+pub(crate) fn resolve_path(
+    state: &mut State,
+    module: ModuleId,
+    identifiers: &[IdentifierAST],
+) -> Option<Symbol> {
+    match state.db().resolve_path(module, identifiers) {
+        Ok(symbol) => Some(symbol),
+        Err(error) => {
+            report_resolution_error(state, *error);
+
+            None
+        }
+    }
+}
+
+/// Returns names of type aliases, that are expanded to get the aliased
+/// symbol, starting with a given alias.
+pub(crate) fn type_alias_chain_names(db: &Database, alias: TypeAliasId) -> Vec<IdentifierId> {
+    db.expand_type_alias(alias)
+        .0
+        .into_iter()
+        .map(|alias| alias.signature(db).name(db).id)
+        .collect()
+}
+
+/// Reports a use of a symbol at a given location, if the symbol is
+/// deprecated.
+///
+/// Uses inside of the deprecated item itself, i.e. when `enclosing_item` is
+/// the symbol or the enum of an enum item, are not reported.
+pub(crate) fn check_deprecated_usage(
+    state: &mut State,
+    symbol: Symbol,
+    location: Location,
+    enclosing_item: Option<Symbol>,
+) {
+    let db = state.db();
+
+    let Some(deprecation) = symbol.deprecation(db) else {
+        return;
+    };
+
+    let defining_item = match symbol {
+        Symbol::EnumItem(item) => Symbol::Enum(item.enum_(db)),
+        _ => symbol,
+    };
+
+    if enclosing_item == Some(defining_item) {
+        return;
+    }
+
+    let name = symbol.name(db);
+    let diagnostic = DeprecatedItemUsage::new(
+        location,
+        symbol.kind(),
+        name.id,
+        deprecation.note.clone(),
+        name.location,
+    );
+
+    state.diagnostics_mut().add_diagnostic(diagnostic);
+}
+
+/// Reports a diagnostic describing why a path cannot be resolved.
+pub(crate) fn report_resolution_error(state: &mut State, error: ResolutionError) {
+    let member = error.failed_segment;
+
+    let Some(namespace) = error.previous_segment else {
+        state
+            .diagnostics_mut()
+            .add_diagnostic(FailedToResolveName::new(member));
+
+        return;
+    };
+
+    match (error.reason, error.searched_in) {
+        (ResolutionErrorKind::PrivateItem, _) => {
+            state
+                .diagnostics_mut()
+                .add_diagnostic(FailedToResolvePrivateModuleItem::new(
+                    namespace.id.to_string(),
+                    namespace.location,
+                    member.id.to_string(),
+                    member.location,
+                ));
+        }
+        (ResolutionErrorKind::ModuleFailedToLoad, _) => {
+            state
+                .diagnostics_mut()
+                .add_diagnostic(NameInModuleFailedToLoad::new(namespace, member));
+        }
+        (ResolutionErrorKind::NotFoundInNamespace, Symbol::Enum(enum_)) => {
+            let db = state.db();
+            let suggestion = find_similar_name(member.id, enum_.items(db).keys().copied());
+            let diagnostic = match error.alias {
+                Some(alias) => FailedToResolveEnumItem::new(enum_.signature(db).name(db), member)
+                    .with_aliases(type_alias_chain_names(db, alias)),
+                None => FailedToResolveEnumItem::new(namespace, member),
+            };
+
+            state
+                .diagnostics_mut()
+                .add_diagnostic(diagnostic.with_suggestion(suggestion));
+        }
+        (ResolutionErrorKind::NotFoundInNamespace | ResolutionErrorKind::NotInScope, _) => {
+            state
+                .diagnostics_mut()
+                .add_diagnostic(FailedToResolveNameInModule::new(
+                    member.id,
+                    member.location,
+                    namespace.id,
+                    namespace.location,
+                ));
+        }
+        (ResolutionErrorKind::NotANamespace, Symbol::EnumItem(_)) => {
+            state
+                .diagnostics_mut()
+                .add_diagnostic(EnumItemsDoNotServeAsNamespaces::new(namespace, member));
+        }
+        (ResolutionErrorKind::NotANamespace, symbol) => {
+            state.diagnostics_mut().add_diagnostic(
+                ModuleItemsExceptEnumsDoNotServeAsNamespaces::new(
+                    namespace,
+                    symbol.module_item_kind(),
+                    member,
+                ),
+            );
+        }
+    }
+}
+
 pub(crate) fn resolve_global_path_in_module_context(
     state: &mut State,
     path: &stellar_ast::Path,
@@ -31,7 +190,13 @@ pub(crate) fn resolve_global_path_in_module_context(
         return None;
     };
 
-    resolve_global_path_by_first_symbol(state, namespace_symbol, namespace, identifiers)
+    resolve_global_path_by_first_symbol(
+        state,
+        module.package(),
+        namespace_symbol,
+        namespace,
+        identifiers,
+    )
 }
 
 pub(crate) fn resolve_global_path(
@@ -42,7 +207,7 @@ pub(crate) fn resolve_global_path(
     let mut identifiers = path.path.identifiers.iter();
     let namespace = identifiers.next()?;
 
-    let Some(package) = (if namespace.id == package.name(state.db()) {
+    let Some(namespace_package) = (if namespace.id == package.name(state.db()) {
         Some(package)
     } else {
         package.dependencies(state.db()).get(&namespace.id).copied()
@@ -57,13 +222,23 @@ pub(crate) fn resolve_global_path(
         return None;
     };
 
-    let root_module = package.root_module(state.db());
+    let root_module = namespace_package.root_module(state.db());
 
-    resolve_global_path_by_first_symbol(state, Symbol::Module(root_module), namespace, identifiers)
+    resolve_global_path_by_first_symbol(
+        state,
+        package,
+        Symbol::Module(root_module),
+        namespace,
+        identifiers,
+    )
 }
 
+/// Resolves a path starting from a given symbol. `package` is the package,
+/// where the path is written, private items of other packages are not
+/// resolved.
 fn resolve_global_path_by_first_symbol<'a>(
     state: &mut State,
+    package: PackageId,
     symbol: Symbol,
     namespace: &'a IdentifierAST,
     identifiers: impl Iterator<Item = &'a IdentifierAST>,
@@ -72,19 +247,20 @@ fn resolve_global_path_by_first_symbol<'a>(
         .chain(identifiers)
         .tuple_windows()
         .try_fold(symbol, |symbol, (namespace, member)| {
-            resolve_global_path_segment(state, symbol, *namespace, *member)
+            resolve_global_path_segment(state, package, symbol, *namespace, *member)
         })
 }
 
 fn resolve_global_path_segment(
     state: &mut State,
+    package: PackageId,
     symbol: Symbol,
     namespace: IdentifierAST,
     member: IdentifierAST,
 ) -> Option<Symbol> {
     match symbol {
         Symbol::Module(module) => {
-            resolve_symbol_in_module_namespace(state, module, namespace, member)
+            resolve_symbol_in_module_namespace(state, package, module, namespace, member)
         }
         Symbol::Enum(enum_) => resolve_symbol_in_enum_namespace(state, enum_, namespace, member),
         Symbol::EnumItem(_) => {
@@ -110,6 +286,7 @@ fn resolve_global_path_segment(
 
 fn resolve_symbol_in_module_namespace(
     state: &mut State,
+    package: PackageId,
     module: ModuleId,
     namespace: IdentifierAST,
     member: IdentifierAST,
@@ -119,7 +296,26 @@ fn resolve_symbol_in_module_namespace(
         .map(Symbol::Module)
         .or(module.module_item_symbol_or_none(state.db(), member.id))
     {
+        if module.package() != package && symbol.visibility(state.db()) == Visibility::Private {
+            state
+                .diagnostics_mut()
+                .add_diagnostic(FailedToResolvePrivateModuleItem::new(
+                    namespace.id.to_string(),
+                    namespace.location,
+                    member.id.to_string(),
+                    member.location,
+                ));
+
+            return None;
+        }
+
         Some(symbol)
+    } else if module.load_failed(state.db()) {
+        state
+            .diagnostics_mut()
+            .add_diagnostic(NameInModuleFailedToLoad::new(namespace, member));
+
+        None
     } else {
         state
             .diagnostics_mut()
@@ -143,9 +339,11 @@ fn resolve_symbol_in_enum_namespace(
     if let Some(symbol) = enum_.item(state.db(), member.id) {
         Some(Symbol::EnumItem(symbol))
     } else {
-        state
-            .diagnostics_mut()
-            .add_diagnostic(FailedToResolveEnumItem::new(namespace, member));
+        let suggestion = find_similar_name(member.id, enum_.items(state.db()).keys().copied());
+
+        state.diagnostics_mut().add_diagnostic(
+            FailedToResolveEnumItem::new(namespace, member).with_suggestion(suggestion),
+        );
 
         None
     }
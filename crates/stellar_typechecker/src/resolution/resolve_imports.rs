@@ -5,15 +5,22 @@ use stellar_ast_lowering::LoweredModule;
 use stellar_database::{ModuleId, State};
 use stellar_filesystem::location::Location;
 use stellar_fx_hash::FxHashMap;
+use stellar_interner::IdentifierId;
 #[cfg(feature = "debug")]
 use tracing::trace;
 
-use super::resolve_global_path;
-use crate::diagnostics::PackageImport;
+use super::{check_deprecated_usage, resolve_global_path};
+use crate::diagnostics::{
+    ConflictingImports, DuplicateImport, ImportShadowedByLocalDefinition, PackageImport,
+};
 
 pub struct ResolveImports<'s> {
     state: &'s mut State,
     module: ModuleId,
+
+    /// Locations of imports, that bind names in the module, to report
+    /// imports of the same name.
+    import_locations: FxHashMap<IdentifierId, Location>,
 }
 
 impl<'s> ResolveImports<'s> {
@@ -22,6 +29,7 @@ impl<'s> ResolveImports<'s> {
             ResolveImports {
                 state,
                 module: *module.0,
+                import_locations: FxHashMap::default(),
             }
             .run(module.1)
         }
@@ -43,12 +51,21 @@ impl<'s> ResolveImports<'s> {
             return;
         };
 
+        if let Some(name) = path.path.identifiers.last() {
+            self.state.record_reference(symbol, name.location);
+            check_deprecated_usage(self.state, symbol, name.location, None);
+        }
+
+        let defining_module = symbol.module(self.state.db());
+        self.module
+            .add_dependency(self.state.db_mut(), defining_module);
+
         if let Some(module) = symbol.to_module_or_none() {
             if self
                 .module
                 .package()
                 .dependencies(self.state.db())
-                .contains_key(&module.name(self.state.db()))
+                .contains_key(&module.name(self.state.db()).id)
                 || module == self.module.package().root_module(self.state.db())
             {
                 self.state
@@ -67,8 +84,48 @@ impl<'s> ResolveImports<'s> {
             symbol.name(self.state.db()).id
         };
 
+        // Local definitions shadow imports, so the import is never used.
+        if let Some(local_symbol) = self.module.local_symbol_or_none(self.state.db(), name) {
+            if local_symbol != symbol {
+                let definition_location = local_symbol.name(self.state.db()).location;
+
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(ImportShadowedByLocalDefinition::new(
+                        location,
+                        name,
+                        definition_location,
+                    ));
+            }
+
+            return;
+        }
+
+        if let Some(&previous_import_location) = self.import_locations.get(&name) {
+            if self.module.resolved_imports(self.state.db())[&name] == symbol {
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(DuplicateImport::new(
+                        location,
+                        name,
+                        previous_import_location,
+                    ));
+            } else {
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(ConflictingImports::new(
+                        location,
+                        name,
+                        previous_import_location,
+                    ));
+            }
+
+            return;
+        }
+
         self.module
             .add_resolved_import(self.state.db_mut(), name, symbol);
+        self.import_locations.insert(name, location);
 
         #[cfg(feature = "debug")]
         trace!(
@@ -0,0 +1,585 @@
+//! Defines [`ScopeTree`] - lexical scopes of value bindings (locals, parameters and
+//! names captured by lambdas) inside a function body.
+//!
+//! The same structure is used by the type checker to resolve local names and by
+//! the language server to answer "what is in scope at offset X".
+
+use stellar_ast::IdentifierAST;
+use stellar_database::{Database, FunctionId, ModuleId};
+use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::{
+    visit::{walk_expression, walk_match_expression_item, Visitor},
+    Expression, FunctionParameter, MatchExpressionItem, ModuleItem, Pattern, Statement,
+    StructFieldPattern,
+};
+use stellar_interner::{builtin_identifiers::SMALL_SELF, IdentifierId};
+
+/// ID of a scope in a [`ScopeTree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScopeId(usize);
+
+/// Kind of a value binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BindingKind {
+    /// A local variable, introduced by `let` or by a pattern in a match arm.
+    Local,
+
+    /// A function or lambda parameter.
+    Parameter,
+
+    /// A binding of an enclosing scope, used inside of a lambda.
+    Captured,
+}
+
+/// A value binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Binding {
+    /// Name of the binding with the location of its definition.
+    pub name: IdentifierAST,
+    pub kind: BindingKind,
+
+    /// Whether the binding is declared with `mut`, e.g. `let mut x = 1;`.
+    pub mutable: bool,
+}
+
+/// A lexical scope of value bindings.
+///
+/// Scopes are parent-chained, the same way as generic parameter scopes are:
+///
+/// ```stellar
+/// fun foo(a: int32) {  // Scope { parent: None, bindings: [a] }
+///     let b = a;       //
+///     {                //
+///         let c = b;   // Scope { parent: ..., bindings: [c] }
+///     }                //
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scope {
+    /// A parent scope.
+    pub parent: Option<ScopeId>,
+
+    /// Location of the source code covered by the scope.
+    pub location: Location,
+
+    /// Bindings in the order they are defined.
+    pub bindings: Vec<Binding>,
+}
+
+impl Scope {
+    /// Creates a new empty scope.
+    #[inline]
+    #[must_use]
+    pub const fn new(parent: Option<ScopeId>, location: Location) -> Self {
+        Self {
+            parent,
+            location,
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Defines a binding in the scope. A binding with the same name
+    /// shadows the previously defined one.
+    #[inline]
+    pub fn define(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
+
+    /// Resolves a binding defined directly in the scope (not in its parents).
+    #[inline]
+    #[must_use]
+    pub fn lookup_local(&self, name: IdentifierId) -> Option<Binding> {
+        self.bindings
+            .iter()
+            .rev()
+            .find(|binding| binding.name.id == name)
+            .copied()
+    }
+}
+
+/// A tree of scopes of a function body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeTree {
+    scopes: Vec<Scope>,
+}
+
+impl ScopeTree {
+    /// Creates a new scope tree with a root scope covering the given location.
+    #[inline]
+    #[must_use]
+    pub fn new(location: Location) -> Self {
+        Self {
+            scopes: vec![Scope::new(None, location)],
+        }
+    }
+
+    /// Builds a scope tree of a given function.
+    #[must_use]
+    pub fn build(function: &stellar_hir::Function) -> Self {
+        let signature = &function.signature;
+
+        let end = function
+            .body
+            .as_ref()
+            .and_then(|body| {
+                body.tail_expression
+                    .as_deref()
+                    .map(Expression::location)
+                    .or_else(|| body.statements.last().map(Statement::location))
+            })
+            // Default values of parameters can use names, so they are
+            // covered as well, even if the body is empty.
+            .or_else(|| {
+                signature
+                    .parameters
+                    .iter()
+                    .rev()
+                    .find_map(|parameter| match parameter {
+                        FunctionParameter::NotSelfParameter(parameter) => {
+                            parameter.default_value.as_ref().map(Expression::location)
+                        }
+                        FunctionParameter::SelfParameter(_) => None,
+                    })
+            })
+            .map_or(signature.name.location.end, |location| location.end);
+
+        let mut builder = ScopeTreeBuilder {
+            tree: Self::new(Location {
+                end,
+                ..signature.name.location
+            }),
+            current_scope: Self::ROOT,
+            lambda_scopes: Vec::new(),
+        };
+
+        for parameter in &signature.parameters {
+            match parameter {
+                FunctionParameter::NotSelfParameter(parameter) => {
+                    builder.define_pattern(&parameter.pattern, BindingKind::Parameter);
+                }
+                FunctionParameter::SelfParameter(parameter) => builder.define(
+                    IdentifierAST {
+                        location: parameter.self_location,
+                        id: SMALL_SELF,
+                    },
+                    BindingKind::Parameter,
+                    // There is no syntax for `mut self`, so methods can modify `self`.
+                    true,
+                ),
+            }
+        }
+
+        if let Some(body) = &function.body {
+            builder.visit_statements_block(body);
+        }
+
+        builder.tree
+    }
+
+    /// ID of the root scope (the scope with function parameters).
+    pub const ROOT: ScopeId = ScopeId(0);
+
+    /// Returns the scope with a given ID.
+    #[inline]
+    #[must_use]
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id.0]
+    }
+
+    /// Returns all the scopes in the tree.
+    #[inline]
+    #[must_use]
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    /// Adds a new scope into the tree and returns its ID.
+    #[inline]
+    pub fn add_scope(&mut self, parent: ScopeId, location: Location) -> ScopeId {
+        self.scopes.push(Scope::new(Some(parent), location));
+
+        ScopeId(self.scopes.len() - 1)
+    }
+
+    /// Defines a binding in the given scope.
+    #[inline]
+    pub fn define(&mut self, scope: ScopeId, binding: Binding) {
+        self.scopes[scope.0].define(binding);
+    }
+
+    /// Resolves a binding visible from the given scope.
+    #[inline]
+    #[must_use]
+    pub fn lookup(&self, scope: ScopeId, name: IdentifierId) -> Option<Binding> {
+        self.lookup_with_distance(scope, name)
+            .map(|(binding, _)| binding)
+    }
+
+    /// Resolves a binding visible from the given scope together with the
+    /// number of parent scopes, that had to be traversed to find it
+    /// (`0` if the binding is defined directly in the given scope).
+    #[must_use]
+    pub fn lookup_with_distance(
+        &self,
+        scope: ScopeId,
+        name: IdentifierId,
+    ) -> Option<(Binding, usize)> {
+        let mut scope = Some(scope);
+        let mut distance = 0;
+
+        while let Some(id) = scope {
+            let data = self.scope(id);
+
+            if let Some(binding) = data.lookup_local(name) {
+                return Some((binding, distance));
+            }
+
+            scope = data.parent;
+            distance += 1;
+        }
+
+        None
+    }
+
+    /// Returns the innermost scope, that covers a given offset.
+    #[must_use]
+    pub fn innermost_scope_at(&self, offset: ByteOffset) -> Option<ScopeId> {
+        // Child scopes are always added after their parents, so the last
+        // scope, that covers the offset, is the innermost one.
+        self.scopes
+            .iter()
+            .rposition(|scope| scope.location.start <= offset && offset <= scope.location.end)
+            .map(ScopeId)
+    }
+
+    /// Returns bindings visible at a given offset. Shadowed bindings are not
+    /// included, bindings defined after the offset are not included either.
+    #[must_use]
+    pub fn bindings_at(&self, offset: ByteOffset) -> Vec<Binding> {
+        let mut bindings = FxHashMap::default();
+        let mut scope = self.innermost_scope_at(offset);
+
+        while let Some(id) = scope {
+            let data = self.scope(id);
+
+            for binding in data.bindings.iter().rev() {
+                if binding.name.location.start <= offset {
+                    bindings.entry(binding.name.id).or_insert(*binding);
+                }
+            }
+
+            scope = data.parent;
+        }
+
+        let mut bindings = bindings.into_values().collect::<Vec<_>>();
+        bindings.sort_by_key(|binding| binding.name.location.start);
+        bindings
+    }
+}
+
+/// Builds a scope tree of a function with a given ID.
+///
+/// Returns `None` if HIR of the function cannot be found in the given modules.
+#[must_use]
+pub fn scopes_for_function(
+    db: &Database,
+    hir: &FxHashMap<ModuleId, stellar_hir::Module>,
+    function: FunctionId,
+) -> Option<ScopeTree> {
+    let signature = function.signature(db);
+
+    match hir
+        .get(&signature.module(db))?
+        .items
+        .get(signature.node_idx(db))?
+    {
+        ModuleItem::Function(function) => Some(ScopeTree::build(function)),
+        _ => None,
+    }
+}
+
+struct ScopeTreeBuilder {
+    tree: ScopeTree,
+    current_scope: ScopeId,
+
+    /// Scopes of lambdas, that are currently being visited.
+    lambda_scopes: Vec<ScopeId>,
+}
+
+impl ScopeTreeBuilder {
+    fn define(&mut self, name: IdentifierAST, kind: BindingKind, mutable: bool) {
+        self.tree.define(
+            self.current_scope,
+            Binding {
+                name,
+                kind,
+                mutable,
+            },
+        );
+    }
+
+    /// Defines all the names bound by a given pattern.
+    ///
+    /// **Note**: identifier patterns referring to unit enum items (like `None`)
+    /// cannot be distinguished from bindings before names are resolved, so they
+    /// are defined as bindings too.
+    fn define_pattern(&mut self, pattern: &Pattern, kind: BindingKind) {
+        match pattern {
+            Pattern::Identifier {
+                identifier,
+                mutable,
+                pattern,
+                ..
+            } => {
+                if let Some(pattern) = pattern {
+                    self.define_pattern(pattern, kind);
+                }
+
+                self.define(*identifier, kind, *mutable);
+            }
+            Pattern::Struct { fields, .. } => {
+                for field in fields {
+                    if let StructFieldPattern::NotRest {
+                        field_name,
+                        value_pattern,
+                        ..
+                    } = field
+                    {
+                        match value_pattern {
+                            Some(pattern) => self.define_pattern(pattern, kind),
+                            None => self.define(*field_name, kind, false),
+                        }
+                    }
+                }
+            }
+            Pattern::TupleLike {
+                inner_patterns: patterns,
+                ..
+            }
+            | Pattern::Tuple {
+                elements: patterns, ..
+            }
+            | Pattern::List {
+                inner_patterns: patterns,
+                ..
+            } => {
+                for pattern in patterns {
+                    self.define_pattern(pattern, kind);
+                }
+            }
+            // Both alternatives bind the same names.
+            Pattern::Or { left, .. } => self.define_pattern(left, kind),
+            Pattern::Literal(..)
+            | Pattern::NegativeNumericLiteral(..)
+            | Pattern::Wildcard { .. }
+            | Pattern::Path { .. }
+            | Pattern::Rest { .. } => {}
+        }
+    }
+
+    fn with_scope(&mut self, location: Location, f: impl FnOnce(&mut Self)) -> ScopeId {
+        let parent = self.current_scope;
+        let scope = self.tree.add_scope(parent, location);
+
+        self.current_scope = scope;
+        f(self);
+        self.current_scope = parent;
+
+        scope
+    }
+
+    /// Records a name used inside of lambdas as captured by all the lambdas
+    /// between the usage and the definition of the name.
+    fn capture(&mut self, name: IdentifierId) {
+        let mut scope = Some(self.current_scope);
+        let mut lambda_scopes = vec![];
+
+        while let Some(id) = scope {
+            if let Some(binding) = self.tree.scope(id).lookup_local(name) {
+                for lambda_scope in lambda_scopes {
+                    self.tree.define(
+                        lambda_scope,
+                        Binding {
+                            name: binding.name,
+                            kind: BindingKind::Captured,
+                            mutable: binding.mutable,
+                        },
+                    );
+                }
+
+                return;
+            }
+
+            if self.lambda_scopes.contains(&id) {
+                lambda_scopes.push(id);
+            }
+
+            scope = self.tree.scope(id).parent;
+        }
+    }
+}
+
+impl Visitor for ScopeTreeBuilder {
+    fn visit_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Let {
+                location,
+                pattern,
+                value,
+                r#else,
+                ..
+            } => {
+                // The value is visited first, so `let x = x;` refers to the
+                // previously defined `x`.
+                self.visit_expression(value);
+
+                if let Some(block) = r#else {
+                    self.with_scope(
+                        Location {
+                            start: value.location().end,
+                            ..*location
+                        },
+                        |builder| builder.visit_statements_block(block),
+                    );
+
+                    // Names bound by the pattern are not visible in the `else` block,
+                    // so they are defined in a scope, that starts after the statement.
+                    let end = self.tree.scope(self.current_scope).location.end;
+                    self.current_scope = self.tree.add_scope(
+                        self.current_scope,
+                        Location {
+                            start: location.end,
+                            end,
+                            ..*location
+                        },
+                    );
+                }
+
+                self.define_pattern(pattern, BindingKind::Local);
+            }
+            Statement::Defer { call, .. } => self.visit_expression(call),
+            Statement::Expression { expression, .. } | Statement::Return { expression, .. } => {
+                self.visit_expression(expression);
+            }
+            Statement::Break { value, .. } => {
+                if let Some(value) = value {
+                    self.visit_expression(value);
+                }
+            }
+            Statement::Continue { .. } => {}
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Identifier(identifier) => {
+                if !self.lambda_scopes.is_empty() {
+                    self.capture(identifier.id);
+                }
+            }
+            Expression::StatementsBlock { location, block } => {
+                self.with_scope(*location, |builder| {
+                    builder.visit_statements_block(block);
+                });
+            }
+            Expression::If {
+                location,
+                if_blocks,
+                r#else,
+            } => {
+                // HIR doesn't store locations of blocks, so a block is assumed to cover
+                // the source code between its condition and the next condition.
+                let mut block_ends = if_blocks
+                    .iter()
+                    .skip(1)
+                    .map(|(condition, _)| condition.location().start)
+                    .collect::<Vec<_>>();
+                let else_start = r#else
+                    .as_ref()
+                    .and_then(|block| {
+                        block
+                            .statements
+                            .first()
+                            .map(Statement::location)
+                            .or_else(|| block.tail_expression.as_deref().map(Expression::location))
+                    })
+                    .map(|location| location.start);
+
+                block_ends.push(else_start.unwrap_or(location.end));
+
+                for ((condition, block), end) in if_blocks.iter().zip(block_ends) {
+                    self.visit_expression(condition);
+
+                    self.with_scope(
+                        Location {
+                            start: condition.location().end,
+                            end,
+                            ..*location
+                        },
+                        |builder| builder.visit_statements_block(block),
+                    );
+                }
+
+                if let (Some(block), Some(start)) = (r#else, else_start) {
+                    self.with_scope(Location { start, ..*location }, |builder| {
+                        builder.visit_statements_block(block)
+                    });
+                }
+            }
+            Expression::While {
+                location,
+                condition,
+                statements_block,
+                ..
+            } => {
+                self.visit_expression(condition);
+
+                self.with_scope(
+                    Location {
+                        start: condition.location().end,
+                        ..*location
+                    },
+                    |builder| builder.visit_statements_block(statements_block),
+                );
+            }
+            Expression::Lambda {
+                location,
+                parameters,
+                value,
+                ..
+            } => {
+                let scope = self.tree.add_scope(self.current_scope, *location);
+                let parent = self.current_scope;
+
+                self.current_scope = scope;
+                self.lambda_scopes.push(scope);
+
+                for parameter in parameters {
+                    self.define(parameter.name, BindingKind::Parameter, false);
+                }
+
+                self.visit_expression(value);
+
+                self.lambda_scopes.pop();
+                self.current_scope = parent;
+            }
+            _ => walk_expression(self, expression),
+        }
+    }
+
+    fn visit_match_expression_item(&mut self, item: &MatchExpressionItem) {
+        self.with_scope(
+            Location {
+                end: item.right.location().end,
+                ..item.left.location()
+            },
+            |builder| {
+                builder.define_pattern(&item.left, BindingKind::Local);
+                walk_match_expression_item(builder, item);
+            },
+        );
+    }
+
+    fn visit_pattern(&mut self, _: &Pattern) {}
+}
@@ -0,0 +1,81 @@
+//! Checks the entry point of a binary package - the `main` function in its
+//! root module:
+//!
+//! ```stellar
+//! pub fun main() {}
+//! ```
+//!
+//! The entry point must be a public function without parameters and generic
+//! parameters. Library packages don't have entry points, so the check must
+//! only be run for binary ones.
+
+use stellar_ast::Visibility;
+use stellar_database::{PackageId, State, Symbol};
+use stellar_hir::ModuleItem;
+use stellar_interner::IdentifierId;
+
+use crate::diagnostics::{
+    EntryPointIsNotAFunction, EntryPointWithParameters, GenericEntryPoint, MissingEntryPoint,
+    PrivateEntryPoint,
+};
+
+pub struct CheckEntryPoint<'s> {
+    state: &'s mut State,
+}
+
+impl<'s> CheckEntryPoint<'s> {
+    /// Checks the `main` function of a given package. Definitions must be
+    /// collected before.
+    pub fn run(state: &'s mut State, package: PackageId) {
+        CheckEntryPoint { state }.check(package);
+    }
+
+    fn check(&mut self, package: PackageId) {
+        let db = self.state.db();
+        let root_module = package.root_module(db);
+
+        let Some(symbol) = root_module.module_item_symbol_or_none(db, IdentifierId::from("main"))
+        else {
+            let diagnostic = MissingEntryPoint::new(package.name(db), root_module.filepath(db));
+
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+            return;
+        };
+
+        let location = symbol.name(db).location;
+
+        let Symbol::Function(_) = symbol else {
+            let diagnostic = EntryPointIsNotAFunction::new(location, symbol.kind());
+
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+            return;
+        };
+
+        let Some(ModuleItem::Function(function)) = self.state.hir().get(symbol).map(AsRef::as_ref)
+        else {
+            return;
+        };
+
+        let is_private = function.signature.visibility == Visibility::Private;
+        let has_parameters = !function.signature.parameters.is_empty();
+        let is_generic = !function.signature.generic_parameters.is_empty();
+
+        if is_private {
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(PrivateEntryPoint::new(location));
+        }
+
+        if has_parameters {
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(EntryPointWithParameters::new(location));
+        }
+
+        if is_generic {
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(GenericEntryPoint::new(location));
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! Checks generic arguments of structs, enums and interfaces used as types:
+//!
+//! - the number of generic arguments must match the number of generic
+//!   parameters, e.g. `Box[int32, int32]` is reported with
+//!   `struct Box[T] { value: T }`. Parameters with default values may be
+//!   omitted. Type aliases are checked by [`CheckTypePaths`];
+//! - every generic argument must implement interfaces, that bound the
+//!   corresponding generic parameter, e.g. `BadType` is reported in
+//!   `Map[String, BadType]` with `struct Map[K, V: ToString] {}`. Only
+//!   structs and enums are checked, as implementations of builtin types are
+//!   not known.
+//!
+//! Diagnostics point at the offending generic argument, using locations of
+//! arguments stored in resolved types (see [`TypeConstructor::argument_location()`]).
+//!
+//! [`CheckTypePaths`]: crate::resolution::check_type_paths::CheckTypePaths
+
+#[cfg(feature = "debug")]
+use std::time::Instant;
+
+use stellar_database::{
+    ty::{Type, TypeConstructor},
+    ModuleId, State, Symbol,
+};
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::{
+    visit::{walk_generic_parameter, walk_module_item, walk_type, Visitor},
+    GenericParameter, ModuleItem,
+};
+use stellar_interner::IdentifierId;
+#[cfg(feature = "debug")]
+use tracing::trace;
+
+use crate::{
+    diagnostics::{BoundNotSatisfied, GenericArgumentArityMismatch},
+    expression_analysis::{
+        collect_implementations, expand_type_aliases, resolve_type,
+        unification::{Implementations, TypeVariables},
+    },
+    resolution::resolve_path_silently,
+};
+
+pub struct CheckGenericArguments<'s> {
+    state: &'s mut State,
+    module: ModuleId,
+
+    /// Generic parameters of the module item, that is currently being
+    /// visited, including generic parameters of its methods. They are not
+    /// checked, so they are resolved into [`Type::Unknown`].
+    generic_parameters: FxHashMap<IdentifierId, Type>,
+
+    type_variables: TypeVariables,
+    implementations: Implementations,
+}
+
+impl<'s> CheckGenericArguments<'s> {
+    pub fn run_all(state: &'s mut State, modules: &FxHashMap<ModuleId, stellar_hir::Module>) {
+        for module in modules.keys() {
+            CheckGenericArguments::run(state, modules, *module);
+        }
+    }
+
+    /// Checks generic arguments in a given module. Implementations of
+    /// interfaces are collected from all modules.
+    pub fn run(
+        state: &'s mut State,
+        modules: &FxHashMap<ModuleId, stellar_hir::Module>,
+        module: ModuleId,
+    ) {
+        #[cfg(feature = "debug")]
+        let now = Instant::now();
+
+        let implementations = collect_implementations(state.db(), modules);
+        let mut me = CheckGenericArguments {
+            state,
+            module,
+            generic_parameters: FxHashMap::default(),
+            type_variables: TypeVariables::new(),
+            implementations,
+        };
+
+        me.visit_module(&modules[&module]);
+
+        #[cfg(feature = "debug")]
+        trace!(
+            "check_generic_arguments_in(module = '{}') <{} us>",
+            module.filepath(me.state.db()),
+            now.elapsed().as_micros()
+        );
+    }
+
+    fn check_type(&mut self, ty: &stellar_hir::Type) {
+        let Type::Constructor(constructor) = resolve_type(
+            self.state.db(),
+            self.module,
+            ty,
+            &self.generic_parameters,
+            &mut self.type_variables,
+        ) else {
+            return;
+        };
+
+        let Some(generic_parameters) = self
+            .state
+            .hir()
+            .get(constructor.symbol)
+            .and_then(|item| generic_parameters_of(item))
+            .map(<[_]>::to_vec)
+        else {
+            return;
+        };
+
+        if !constructor.symbol.is_type_alias()
+            && !self.check_arity(&constructor, &generic_parameters, ty.location())
+        {
+            return;
+        }
+
+        self.check_bounds(&constructor, &generic_parameters);
+    }
+
+    /// Checks, that a type is used with as many generic arguments, as it has
+    /// generic parameters, and returns `false` otherwise.
+    fn check_arity(
+        &mut self,
+        constructor: &TypeConstructor,
+        generic_parameters: &[GenericParameter],
+        location: Location,
+    ) -> bool {
+        let max_expected = generic_parameters.len();
+        let min_expected = generic_parameters
+            .iter()
+            .filter(|parameter| parameter.default_value.is_none())
+            .count();
+        let found = constructor.arguments.len();
+
+        if (min_expected..=max_expected).contains(&found) {
+            return true;
+        }
+
+        // Point at the first extra argument, if there are too many of them.
+        let location = constructor
+            .argument_location(max_expected)
+            .unwrap_or(location);
+        let symbol = constructor.symbol;
+
+        let diagnostic = GenericArgumentArityMismatch::new(
+            location,
+            symbol.kind(),
+            symbol.name(self.state.db()),
+            min_expected,
+            max_expected,
+            found,
+        );
+        self.state.diagnostics_mut().add_diagnostic(diagnostic);
+
+        false
+    }
+
+    /// Checks, that generic arguments implement interfaces, that bound
+    /// corresponding generic parameters.
+    fn check_bounds(
+        &mut self,
+        constructor: &TypeConstructor,
+        generic_parameters: &[GenericParameter],
+    ) {
+        let db = self.state.db();
+        let defining_module = constructor.symbol.module(db);
+
+        let mut diagnostics = vec![];
+
+        for (idx, (argument, parameter)) in constructor
+            .arguments
+            .iter()
+            .zip(generic_parameters)
+            .enumerate()
+        {
+            let Some(location) = constructor.argument_location(idx) else {
+                continue;
+            };
+
+            let argument = expand_type_aliases(
+                db,
+                self.state.hir(),
+                argument.clone(),
+                &mut self.type_variables,
+                0,
+            );
+
+            let Type::Constructor(TypeConstructor {
+                symbol: symbol @ (Symbol::Enum(_) | Symbol::Struct(_) | Symbol::TupleLikeStruct(_)),
+                ..
+            }) = argument
+            else {
+                continue;
+            };
+
+            for bound in parameter.bounds.iter().flatten() {
+                let Some(interface @ Symbol::Interface(_)) =
+                    resolve_path_silently(db, defining_module, &bound.path.identifiers)
+                else {
+                    continue;
+                };
+
+                let implements = self
+                    .implementations
+                    .get(&symbol)
+                    .is_some_and(|interfaces| interfaces.contains(&interface));
+
+                if !implements {
+                    diagnostics.push(BoundNotSatisfied::new(
+                        location,
+                        argument.display(db).to_string(),
+                        interface.name(db).id.to_string(),
+                        bound.location,
+                    ));
+                }
+            }
+        }
+
+        for diagnostic in diagnostics {
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+        }
+    }
+}
+
+impl Visitor for CheckGenericArguments<'_> {
+    fn visit_module_item(&mut self, item: &ModuleItem) {
+        self.generic_parameters.clear();
+        walk_module_item(self, item);
+    }
+
+    fn visit_generic_parameter(&mut self, generic_parameter: &GenericParameter) {
+        self.generic_parameters
+            .insert(generic_parameter.name.id, Type::Unknown);
+        walk_generic_parameter(self, generic_parameter);
+    }
+
+    fn visit_type(&mut self, ty: &stellar_hir::Type) {
+        if let stellar_hir::Type::Constructor(_) = ty {
+            self.check_type(ty);
+        }
+
+        walk_type(self, ty);
+    }
+}
+
+/// Returns generic parameters of a module item, that is a type.
+fn generic_parameters_of(item: &ModuleItem) -> Option<&[GenericParameter]> {
+    match item {
+        ModuleItem::Enum(stellar_hir::Enum {
+            generic_parameters, ..
+        })
+        | ModuleItem::Struct(stellar_hir::Struct {
+            generic_parameters, ..
+        })
+        | ModuleItem::TupleLikeStruct(stellar_hir::TupleLikeStruct {
+            generic_parameters, ..
+        })
+        | ModuleItem::Interface(stellar_hir::Interface {
+            generic_parameters, ..
+        })
+        | ModuleItem::TypeAlias(stellar_hir::TypeAlias {
+            generic_parameters, ..
+        }) => Some(generic_parameters),
+        _ => None,
+    }
+}
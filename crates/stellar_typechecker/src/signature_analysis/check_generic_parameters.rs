@@ -0,0 +1,266 @@
+//! Checks, that generic parameters of items are used and that where
+//! predicates constrain them:
+//!
+//! - every generic parameter must be mentioned in fields of structs and
+//!   enum items, in signatures of functions and interface methods, in values
+//!   of type aliases or in where predicates, e.g. `T` is reported in
+//!   `struct Foo[T] { x: int32 }`. Parameters, which names start with `_`,
+//!   e.g. `_T`, are intentionally unused and are not reported;
+//! - the type of every where predicate must mention a generic parameter in
+//!   scope, e.g. `where int32: ToString` is an error.
+
+#[cfg(feature = "debug")]
+use std::time::Instant;
+
+use stellar_database::{ModuleId, State};
+use stellar_fx_hash::{FxHashMap, FxHashSet};
+use stellar_hir::{
+    visit::{walk_type_constructor, Visitor},
+    Enum, EnumItem, Function, FunctionParameter, FunctionSignature, GenericParameter, Interface,
+    Struct, TupleLikeStruct, TypeAlias, TypeConstructor, WherePredicate,
+};
+use stellar_interner::{builtin_identifiers::BIG_SELF, IdentifierId};
+#[cfg(feature = "debug")]
+use tracing::trace;
+
+use crate::diagnostics::{UnusedGenericParameter, WherePredicateWithoutGenericParameters};
+
+pub struct CheckGenericParameters<'s> {
+    state: &'s mut State,
+}
+
+impl<'s> CheckGenericParameters<'s> {
+    pub fn run_all(state: &'s mut State, modules: &FxHashMap<ModuleId, stellar_hir::Module>) {
+        for (module, hir) in modules {
+            CheckGenericParameters::run(state, *module, hir);
+        }
+    }
+
+    /// Checks generic parameters of items in a given module.
+    pub fn run(state: &'s mut State, _module: ModuleId, hir: &stellar_hir::Module) {
+        #[cfg(feature = "debug")]
+        let now = Instant::now();
+
+        let mut me = CheckGenericParameters { state };
+
+        me.visit_module(hir);
+
+        #[cfg(feature = "debug")]
+        trace!(
+            "check_generic_parameters_in(module = '{}') <{} us>",
+            _module.filepath(me.state.db()),
+            now.elapsed().as_micros()
+        );
+    }
+
+    /// Checks generic parameters and where predicates of an item, given
+    /// names of types used in the item and generic parameters of the
+    /// enclosing item, e.g. of the struct, that a method belongs to.
+    fn check_item(
+        &mut self,
+        generic_parameters: &[GenericParameter],
+        where_predicates: &[WherePredicate],
+        mut used_names: ReferencedNames,
+        enclosing_generic_parameters: &[GenericParameter],
+    ) {
+        for parameter in generic_parameters {
+            if let Some(bounds) = &parameter.bounds {
+                for bound in bounds {
+                    used_names.visit_type_constructor(bound);
+                }
+            }
+
+            if let Some(default_value) = &parameter.default_value {
+                used_names.visit_type(default_value);
+            }
+        }
+
+        for predicate in where_predicates {
+            used_names.visit_type(&predicate.ty);
+
+            for bound in &predicate.bounds {
+                used_names.visit_type_constructor(bound);
+            }
+        }
+
+        for parameter in generic_parameters {
+            if !used_names.0.contains(&parameter.name.id)
+                && !parameter.name.id.to_string().starts_with('_')
+            {
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(UnusedGenericParameter::new(parameter.name));
+            }
+        }
+
+        // `Self` is constrained by predicates like `where Self: Clone`, so it
+        // is treated as a generic parameter in scope.
+        let in_scope = generic_parameters
+            .iter()
+            .chain(enclosing_generic_parameters)
+            .map(|parameter| parameter.name.id)
+            .chain([BIG_SELF])
+            .collect::<FxHashSet<_>>();
+
+        for predicate in where_predicates {
+            let mut names = ReferencedNames::default();
+            names.visit_type(&predicate.ty);
+
+            if names.0.is_disjoint(&in_scope) {
+                self.state.diagnostics_mut().add_diagnostic(
+                    WherePredicateWithoutGenericParameters::new(predicate.ty.location()),
+                );
+            }
+        }
+    }
+
+    fn check_function(
+        &mut self,
+        function: &Function,
+        enclosing_generic_parameters: &[GenericParameter],
+    ) {
+        let signature = &function.signature;
+
+        self.check_item(
+            &signature.generic_parameters,
+            &signature.where_predicates,
+            signature_names(signature),
+            enclosing_generic_parameters,
+        );
+    }
+
+    fn check_methods(&mut self, methods: &[Function], generic_parameters: &[GenericParameter]) {
+        for method in methods {
+            self.check_function(method, generic_parameters);
+        }
+    }
+}
+
+impl Visitor for CheckGenericParameters<'_> {
+    fn visit_enum(&mut self, enum_: &Enum) {
+        let mut names = ReferencedNames::default();
+
+        for item in &enum_.items {
+            match item {
+                EnumItem::Just { .. } => {}
+                EnumItem::TupleLike { fields, .. } => {
+                    for field in fields {
+                        names.visit_type(&field.ty);
+                    }
+                }
+                EnumItem::Struct { fields, .. } => {
+                    for field in fields {
+                        names.visit_type(&field.ty);
+                    }
+                }
+            }
+        }
+
+        self.check_item(
+            &enum_.generic_parameters,
+            &enum_.where_predicates,
+            names,
+            &[],
+        );
+        self.check_methods(&enum_.methods, &enum_.generic_parameters);
+    }
+
+    fn visit_struct(&mut self, struct_: &Struct) {
+        let mut names = ReferencedNames::default();
+
+        for field in &struct_.fields {
+            names.visit_type(&field.ty);
+        }
+
+        self.check_item(
+            &struct_.generic_parameters,
+            &struct_.where_predicates,
+            names,
+            &[],
+        );
+        self.check_methods(&struct_.methods, &struct_.generic_parameters);
+    }
+
+    fn visit_tuple_like_struct(&mut self, struct_: &TupleLikeStruct) {
+        let mut names = ReferencedNames::default();
+
+        for field in &struct_.fields {
+            names.visit_type(&field.ty);
+        }
+
+        self.check_item(
+            &struct_.generic_parameters,
+            &struct_.where_predicates,
+            names,
+            &[],
+        );
+        self.check_methods(&struct_.methods, &struct_.generic_parameters);
+    }
+
+    fn visit_interface(&mut self, interface: &Interface) {
+        // Interfaces don't have fields, so their generic parameters are used
+        // by signatures of their methods.
+        let mut names = ReferencedNames::default();
+
+        for method in &interface.methods {
+            names.0.extend(signature_names(&method.signature).0);
+        }
+
+        self.check_item(
+            &interface.generic_parameters,
+            &interface.where_predicates,
+            names,
+            &[],
+        );
+        self.check_methods(&interface.methods, &interface.generic_parameters);
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        self.check_function(function, &[]);
+    }
+
+    fn visit_type_alias(&mut self, alias: &TypeAlias) {
+        let mut names = ReferencedNames::default();
+        names.visit_type(&alias.value);
+
+        self.check_item(&alias.generic_parameters, &[], names, &[]);
+    }
+}
+
+/// Names of types, that consist of a single identifier, e.g. `T` and `U` in
+/// `Map[T, List[U]]`.
+#[derive(Default)]
+struct ReferencedNames(FxHashSet<IdentifierId>);
+
+impl Visitor for ReferencedNames {
+    fn visit_type_constructor(&mut self, constructor: &TypeConstructor) {
+        if let [name] = constructor.path.identifiers[..] {
+            self.0.insert(name.id);
+        }
+
+        walk_type_constructor(self, constructor);
+    }
+}
+
+/// Returns names of types used in parameters and the return type of
+/// a function.
+fn signature_names(signature: &FunctionSignature) -> ReferencedNames {
+    let mut names = ReferencedNames::default();
+
+    for parameter in &signature.parameters {
+        match parameter {
+            FunctionParameter::NotSelfParameter(parameter) => names.visit_type(&parameter.ty),
+            FunctionParameter::SelfParameter(parameter) => {
+                if let Some(ty) = &parameter.ty {
+                    names.visit_type(ty);
+                }
+            }
+        }
+    }
+
+    if let Some(return_type) = &signature.return_type {
+        names.visit_type(return_type);
+    }
+
+    names
+}
@@ -0,0 +1,348 @@
+//! Checks, that types provide all the methods of interfaces they implement
+//! with matching signatures.
+
+#[cfg(feature = "debug")]
+use std::time::Instant;
+
+use stellar_ast::IdentifierAST;
+use stellar_database::{ModuleId, State};
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::{FunctionParameter, FunctionSignature, ModuleItem, Type};
+use stellar_interner::{builtin_identifiers::BIG_SELF, IdentifierId};
+#[cfg(feature = "debug")]
+use tracing::trace;
+
+use super::resolve_interface;
+use crate::diagnostics::{InterfaceMethodSignatureMismatch, MissingInterfaceMethod};
+
+pub struct CheckInterfaceConformance<'s, 'h> {
+    state: &'s mut State,
+    modules: &'h FxHashMap<ModuleId, stellar_hir::Module>,
+}
+
+impl<'s, 'h> CheckInterfaceConformance<'s, 'h> {
+    pub fn run_all(state: &'s mut State, modules: &'h FxHashMap<ModuleId, stellar_hir::Module>) {
+        let mut me = CheckInterfaceConformance { state, modules };
+
+        for (module, hir) in modules {
+            me.check_module(*module, hir);
+        }
+    }
+
+    /// Checks implementations of interfaces in a given module. Interfaces
+    /// are looked up in all modules.
+    pub fn run(
+        state: &'s mut State,
+        modules: &'h FxHashMap<ModuleId, stellar_hir::Module>,
+        module: ModuleId,
+    ) {
+        CheckInterfaceConformance { state, modules }.check_module(module, &modules[&module]);
+    }
+
+    fn check_module(&mut self, module: ModuleId, hir: &stellar_hir::Module) {
+        #[cfg(feature = "debug")]
+        let now = Instant::now();
+
+        for item in &hir.items {
+            let (name, implements, methods) = match item {
+                ModuleItem::Enum(stellar_hir::Enum {
+                    name,
+                    implements,
+                    methods,
+                    ..
+                })
+                | ModuleItem::Struct(stellar_hir::Struct {
+                    name,
+                    implements,
+                    methods,
+                    ..
+                })
+                | ModuleItem::TupleLikeStruct(stellar_hir::TupleLikeStruct {
+                    name,
+                    implements,
+                    methods,
+                    ..
+                }) => (*name, implements, methods),
+                _ => continue,
+            };
+
+            for interface in implements.iter().flatten() {
+                self.check_conformance(module, name, interface, methods);
+            }
+        }
+
+        #[cfg(feature = "debug")]
+        trace!(
+            "check_interface_conformance_in(module = '{}') <{} us>",
+            module.filepath(self.state.db()),
+            now.elapsed().as_micros()
+        );
+    }
+
+    fn check_conformance(
+        &mut self,
+        module: ModuleId,
+        type_name: IdentifierAST,
+        interface: &stellar_hir::TypeConstructor,
+        methods: &[stellar_hir::Function],
+    ) {
+        // Unresolved names and non-interface types in the `implements` list
+        // are reported when resolving signatures.
+        let Some(interface_hir) =
+            resolve_interface(self.state.db(), self.modules, module, &interface.path)
+        else {
+            return;
+        };
+
+        let interface_generic_parameters = interface_hir
+            .generic_parameters
+            .iter()
+            .map(|parameter| parameter.name.id)
+            .collect::<Vec<_>>();
+        let mut missing_methods = vec![];
+
+        for interface_method in &interface_hir.methods {
+            let expected = &interface_method.signature;
+
+            let Some(method) = methods
+                .iter()
+                .find(|method| method.signature.name.id == expected.name.id)
+            else {
+                // Methods with default implementation don't have to be provided.
+                if interface_method.body.is_none() {
+                    missing_methods.push(expected.name);
+                }
+
+                continue;
+            };
+
+            let matcher = SignatureMatcher {
+                type_name: type_name.id,
+                interface_generic_parameters: &interface_generic_parameters,
+            };
+
+            if !matcher.signatures_match(expected, &method.signature) {
+                self.state
+                    .diagnostics_mut()
+                    .add_diagnostic(InterfaceMethodSignatureMismatch::new(
+                        method.signature.name,
+                        interface_hir.name,
+                        signature_to_string(expected),
+                        expected.name.location,
+                    ));
+            }
+        }
+
+        if !missing_methods.is_empty() {
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(MissingInterfaceMethod::new(
+                    type_name,
+                    interface_hir.name,
+                    interface.location,
+                    missing_methods,
+                ));
+        }
+    }
+}
+
+/// Compares signatures of interface methods with signatures of their implementations.
+struct SignatureMatcher<'a> {
+    /// Name of the implementor, which can be used instead of `Self`.
+    type_name: IdentifierId,
+
+    /// Generic parameters of the interface, which can be substituted with any type.
+    interface_generic_parameters: &'a [IdentifierId],
+}
+
+impl SignatureMatcher<'_> {
+    fn signatures_match(&self, expected: &FunctionSignature, actual: &FunctionSignature) -> bool {
+        expected.parameters.len() == actual.parameters.len()
+            && expected
+                .parameters
+                .iter()
+                .zip(&actual.parameters)
+                .all(|(expected, actual)| match (expected, actual) {
+                    (
+                        FunctionParameter::SelfParameter(..),
+                        FunctionParameter::SelfParameter(..),
+                    ) => true,
+                    (
+                        FunctionParameter::NotSelfParameter(expected),
+                        FunctionParameter::NotSelfParameter(actual),
+                    ) => self.types_match(&expected.ty, &actual.ty),
+                    _ => false,
+                })
+            && match (&expected.return_type, &actual.return_type) {
+                (Some(expected), Some(actual)) => self.types_match(expected, actual),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+
+    fn types_match(&self, expected: &Type, actual: &Type) -> bool {
+        match (expected, actual) {
+            (Type::Constructor(expected), Type::Constructor(actual)) => {
+                self.type_constructors_match(expected, actual)
+            }
+            (
+                Type::Tuple {
+                    element_types: expected,
+                    ..
+                },
+                Type::Tuple {
+                    element_types: actual,
+                    ..
+                },
+            ) => self.all_types_match(expected, actual),
+            (
+                Type::Function {
+                    parameter_types: expected_parameter_types,
+                    return_type: expected_return_type,
+                    ..
+                },
+                Type::Function {
+                    parameter_types: actual_parameter_types,
+                    return_type: actual_return_type,
+                    ..
+                },
+            ) => {
+                self.all_types_match(expected_parameter_types, actual_parameter_types)
+                    && match (expected_return_type, actual_return_type) {
+                        (Some(expected), Some(actual)) => self.types_match(expected, actual),
+                        (None, None) => true,
+                        _ => false,
+                    }
+            }
+            (
+                Type::InterfaceObject {
+                    bounds: expected, ..
+                },
+                Type::InterfaceObject { bounds: actual, .. },
+            ) => {
+                expected.len() == actual.len()
+                    && expected
+                        .iter()
+                        .zip(actual)
+                        .all(|(expected, actual)| self.type_constructors_match(expected, actual))
+            }
+            (Type::Underscore { .. }, Type::Underscore { .. }) => true,
+            (Type::Constructor(expected), _) => self.is_interface_generic_parameter(expected),
+            _ => false,
+        }
+    }
+
+    fn all_types_match(&self, expected: &[Type], actual: &[Type]) -> bool {
+        expected.len() == actual.len()
+            && expected
+                .iter()
+                .zip(actual)
+                .all(|(expected, actual)| self.types_match(expected, actual))
+    }
+
+    fn type_constructors_match(
+        &self,
+        expected: &stellar_hir::TypeConstructor,
+        actual: &stellar_hir::TypeConstructor,
+    ) -> bool {
+        if self.is_interface_generic_parameter(expected) {
+            return true;
+        }
+
+        let expected_path = path_ids(&expected.path);
+        let actual_path = path_ids(&actual.path);
+
+        let paths_match = expected_path == actual_path
+            || (expected_path == [BIG_SELF]
+                && (actual_path == [self.type_name] || actual_path == [BIG_SELF]));
+
+        paths_match && self.all_types_match(&expected.arguments, &actual.arguments)
+    }
+
+    fn is_interface_generic_parameter(&self, ty: &stellar_hir::TypeConstructor) -> bool {
+        ty.arguments.is_empty()
+            && matches!(path_ids(&ty.path).as_slice(), [name] if self.interface_generic_parameters.contains(name))
+    }
+}
+
+fn path_ids(path: &stellar_hir::Path) -> Vec<IdentifierId> {
+    path.identifiers
+        .iter()
+        .map(|identifier| identifier.id)
+        .collect()
+}
+
+pub(crate) fn signature_to_string(signature: &FunctionSignature) -> String {
+    let parameters = signature
+        .parameters
+        .iter()
+        .map(|parameter| match parameter {
+            FunctionParameter::SelfParameter(..) => "self".to_owned(),
+            FunctionParameter::NotSelfParameter(parameter) => type_to_string(&parameter.ty),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    match &signature.return_type {
+        Some(return_type) => format!(
+            "fun {}({parameters}): {}",
+            signature.name.id,
+            type_to_string(return_type)
+        ),
+        None => format!("fun {}({parameters})", signature.name.id),
+    }
+}
+
+/// Renders a type the way it is written in the source code, e.g. `fun(int32): bool`.
+pub fn type_to_string(ty: &Type) -> String {
+    match ty {
+        Type::Constructor(constructor) => type_constructor_to_string(constructor),
+        Type::Tuple { element_types, .. } => format!("({})", types_to_string(element_types)),
+        Type::Function {
+            parameter_types,
+            return_type,
+            ..
+        } => match return_type {
+            Some(return_type) => format!(
+                "fun({}): {}",
+                types_to_string(parameter_types),
+                type_to_string(return_type)
+            ),
+            None => format!("fun({})", types_to_string(parameter_types)),
+        },
+        Type::Underscore { .. } => "_".to_owned(),
+        Type::InterfaceObject { bounds, .. } => format!(
+            "dyn {}",
+            bounds
+                .iter()
+                .map(type_constructor_to_string)
+                .collect::<Vec<_>>()
+                .join(" + ")
+        ),
+    }
+}
+
+fn types_to_string(types: &[Type]) -> String {
+    types
+        .iter()
+        .map(type_to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Renders a type constructor the way it is written in the source code, e.g. `List[int32]`.
+pub fn type_constructor_to_string(constructor: &stellar_hir::TypeConstructor) -> String {
+    let path = constructor
+        .path
+        .identifiers
+        .iter()
+        .map(|identifier| identifier.id.to_string())
+        .collect::<Vec<_>>()
+        .join(".");
+
+    if constructor.arguments.is_empty() {
+        path
+    } else {
+        format!("{path}[{}]", types_to_string(&constructor.arguments))
+    }
+}
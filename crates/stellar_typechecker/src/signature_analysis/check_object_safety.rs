@@ -0,0 +1,123 @@
+//! Checks, that interfaces used as bounds of interface object types, e.g.
+//! `Display` in `dyn Display`, are object safe.
+//!
+//! An interface is object safe, if none of its methods:
+//! - has generic parameters, e.g. `fun map[U](self, f: fun(T): U): List[U]`,
+//!   because a vtable can't contain all of its instantiations;
+//! - returns `Self`, e.g. `fun clone(self): Self`, because the type behind
+//!   an interface object is not known.
+
+#[cfg(feature = "debug")]
+use std::time::Instant;
+
+use stellar_ast::IdentifierAST;
+use stellar_database::{ModuleId, State};
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::{
+    visit::{walk_type, Visitor},
+    Interface, Type, TypeConstructor,
+};
+use stellar_interner::builtin_identifiers::BIG_SELF;
+#[cfg(feature = "debug")]
+use tracing::trace;
+
+use super::resolve_interface;
+use crate::diagnostics::InterfaceIsNotObjectSafe;
+
+pub struct CheckObjectSafety<'s, 'h> {
+    state: &'s mut State,
+    modules: &'h FxHashMap<ModuleId, stellar_hir::Module>,
+    module: ModuleId,
+}
+
+impl<'s, 'h> CheckObjectSafety<'s, 'h> {
+    pub fn run_all(state: &'s mut State, modules: &'h FxHashMap<ModuleId, stellar_hir::Module>) {
+        for module in modules.keys() {
+            CheckObjectSafety::run(state, modules, *module);
+        }
+    }
+
+    /// Checks interface object types in a given module. Interfaces are
+    /// looked up in all modules.
+    pub fn run(
+        state: &'s mut State,
+        modules: &'h FxHashMap<ModuleId, stellar_hir::Module>,
+        module: ModuleId,
+    ) {
+        #[cfg(feature = "debug")]
+        let now = Instant::now();
+
+        let mut me = CheckObjectSafety {
+            state,
+            modules,
+            module,
+        };
+
+        me.visit_module(&modules[&module]);
+
+        #[cfg(feature = "debug")]
+        trace!(
+            "check_object_safety_in(module = '{}') <{} us>",
+            module.filepath(me.state.db()),
+            now.elapsed().as_micros()
+        );
+    }
+
+    fn check_bound(&mut self, bound: &TypeConstructor) {
+        // Unresolved names and non-interface bounds are reported when
+        // resolving signatures.
+        let Some(interface) =
+            resolve_interface(self.state.db(), self.modules, self.module, &bound.path)
+        else {
+            return;
+        };
+
+        if let Some((method_name, reason)) = object_safety_violation(interface) {
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(InterfaceIsNotObjectSafe::new(
+                    bound.location,
+                    interface.name,
+                    method_name,
+                    reason,
+                ));
+        }
+    }
+}
+
+impl Visitor for CheckObjectSafety<'_, '_> {
+    fn visit_type(&mut self, ty: &Type) {
+        if let Type::InterfaceObject { bounds, .. } = ty {
+            for bound in bounds {
+                self.check_bound(bound);
+            }
+        }
+
+        walk_type(self, ty);
+    }
+}
+
+/// Returns the name of the first method of an interface, that makes the
+/// interface not object safe, and the reason why.
+fn object_safety_violation(interface: &Interface) -> Option<(IdentifierAST, &'static str)> {
+    interface.methods.iter().find_map(|method| {
+        let signature = &method.signature;
+
+        if !signature.generic_parameters.is_empty() {
+            Some((signature.name, "has generic parameters"))
+        } else if signature.return_type.as_ref().is_some_and(is_self_type) {
+            Some((signature.name, "returns `Self`"))
+        } else {
+            None
+        }
+    })
+}
+
+fn is_self_type(ty: &Type) -> bool {
+    match ty {
+        Type::Constructor(TypeConstructor {
+            path, arguments, ..
+        }) => arguments.is_empty() && matches!(path.identifiers[..], [name] if name.id == BIG_SELF),
+        _ => false,
+    }
+}
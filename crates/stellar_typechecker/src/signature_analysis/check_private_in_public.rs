@@ -0,0 +1,302 @@
+//! Checks, that public items don't mention private types in their public
+//! interface:
+//!
+//! - types of parameters and return types of public functions and methods,
+//!   and of methods of public interfaces;
+//! - types of public fields of public structs and of fields of items of
+//!   public enums;
+//! - values of public type aliases.
+//!
+//! Private types can't be named outside of their package, so an item, that
+//! mentions them, can't be fully used there, e.g. `pub fun reveal(): Secret`,
+//! where `Secret` is private. Generic parameters and builtin types are never
+//! reported.
+
+#[cfg(feature = "debug")]
+use std::time::Instant;
+
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_database::{Database, ModuleId, State, Symbol, SymbolKind};
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::{FxHashMap, FxHashSet};
+use stellar_hir::{
+    visit::{walk_type_constructor, Visitor},
+    Enum, EnumItem, Function, FunctionParameter, GenericParameter, Interface, Struct,
+    TupleLikeStruct, Type, TypeAlias, TypeConstructor,
+};
+use stellar_interner::{builtin_identifiers::BIG_SELF, IdentifierId};
+#[cfg(feature = "debug")]
+use tracing::trace;
+
+use crate::{diagnostics::PrivateTypeInPublicInterface, resolution::resolve_path_silently};
+
+pub struct CheckPrivateInPublic<'s> {
+    state: &'s mut State,
+    module: ModuleId,
+}
+
+impl<'s> CheckPrivateInPublic<'s> {
+    pub fn run_all(state: &'s mut State, modules: &FxHashMap<ModuleId, stellar_hir::Module>) {
+        for (module, hir) in modules {
+            CheckPrivateInPublic::run(state, *module, hir);
+        }
+    }
+
+    /// Checks public items of a given module.
+    pub fn run(state: &'s mut State, module: ModuleId, hir: &stellar_hir::Module) {
+        #[cfg(feature = "debug")]
+        let now = Instant::now();
+
+        let mut me = CheckPrivateInPublic { state, module };
+
+        me.visit_module(hir);
+
+        #[cfg(feature = "debug")]
+        trace!(
+            "check_private_in_public_in(module = '{}') <{} us>",
+            module.filepath(me.state.db()),
+            now.elapsed().as_micros()
+        );
+    }
+
+    /// Returns `true` if an item with a given visibility, that is defined in
+    /// the current module, can be named outside of the package.
+    fn is_exported(&self, visibility: Visibility) -> bool {
+        visibility != Visibility::Private && is_module_exported(self.state.db(), self.module)
+    }
+
+    /// Reports private types mentioned in given types of an item.
+    fn check_types<'t>(
+        &mut self,
+        types: impl IntoIterator<Item = &'t Type>,
+        generic_parameters: &FxHashSet<IdentifierId>,
+        item_name: IdentifierAST,
+        item_kind: SymbolKind,
+    ) {
+        let mut constructors = TypeConstructors::default();
+
+        for ty in types {
+            constructors.visit_type(ty);
+        }
+
+        for (location, identifiers) in constructors.0 {
+            if let [name] = identifiers[..] {
+                if generic_parameters.contains(&name.id) {
+                    continue;
+                }
+            }
+
+            let Some(symbol) = resolve_path_silently(self.state.db(), self.module, &identifiers)
+            else {
+                // Unresolved types are reported when resolving signatures.
+                continue;
+            };
+
+            if is_symbol_exported(self.state.db(), symbol) {
+                continue;
+            }
+
+            let diagnostic = PrivateTypeInPublicInterface::new(
+                location,
+                symbol.name(self.state.db()),
+                symbol.kind(),
+                item_name.id,
+                item_kind,
+                self.state.config().deny_private_in_public(),
+            );
+
+            self.state.diagnostics_mut().add_diagnostic(diagnostic);
+        }
+    }
+
+    /// Reports private types in parameters and the return type of
+    /// a function.
+    fn check_function(
+        &mut self,
+        function: &Function,
+        enclosing_generic_parameters: &FxHashSet<IdentifierId>,
+        kind: SymbolKind,
+    ) {
+        let signature = &function.signature;
+
+        let mut generic_parameters = enclosing_generic_parameters.clone();
+        generic_parameters.extend(names_of(&signature.generic_parameters));
+
+        let parameter_types = signature
+            .parameters
+            .iter()
+            .filter_map(|parameter| match parameter {
+                FunctionParameter::NotSelfParameter(parameter) => Some(&parameter.ty),
+                FunctionParameter::SelfParameter(parameter) => parameter.ty.as_ref(),
+            });
+
+        self.check_types(
+            parameter_types.chain(&signature.return_type),
+            &generic_parameters,
+            signature.name,
+            kind,
+        );
+    }
+
+    /// Reports private types in signatures of public methods.
+    fn check_methods(
+        &mut self,
+        methods: &[Function],
+        generic_parameters: &FxHashSet<IdentifierId>,
+    ) {
+        for method in methods {
+            if method.signature.visibility != Visibility::Private {
+                self.check_function(method, generic_parameters, SymbolKind::Method);
+            }
+        }
+    }
+}
+
+impl Visitor for CheckPrivateInPublic<'_> {
+    fn visit_enum(&mut self, enum_: &Enum) {
+        if !self.is_exported(enum_.visibility) {
+            return;
+        }
+
+        let generic_parameters = names_of(&enum_.generic_parameters);
+
+        for item in &enum_.items {
+            let field_types = match item {
+                EnumItem::Just { .. } => Vec::new(),
+                EnumItem::TupleLike { fields, .. } => {
+                    fields.iter().map(|field| &field.ty).collect()
+                }
+                EnumItem::Struct { fields, .. } => fields.iter().map(|field| &field.ty).collect(),
+            };
+
+            self.check_types(
+                field_types,
+                &generic_parameters,
+                enum_.name,
+                SymbolKind::Enum,
+            );
+        }
+
+        self.check_methods(&enum_.methods, &generic_parameters);
+    }
+
+    fn visit_struct(&mut self, struct_: &Struct) {
+        if !self.is_exported(struct_.visibility) {
+            return;
+        }
+
+        let generic_parameters = names_of(&struct_.generic_parameters);
+
+        self.check_types(
+            struct_
+                .fields
+                .iter()
+                .filter(|field| field.visibility != Visibility::Private)
+                .map(|field| &field.ty),
+            &generic_parameters,
+            struct_.name,
+            SymbolKind::Struct,
+        );
+        self.check_methods(&struct_.methods, &generic_parameters);
+    }
+
+    fn visit_tuple_like_struct(&mut self, struct_: &TupleLikeStruct) {
+        if !self.is_exported(struct_.visibility) {
+            return;
+        }
+
+        let generic_parameters = names_of(&struct_.generic_parameters);
+
+        self.check_types(
+            struct_
+                .fields
+                .iter()
+                .filter(|field| field.visibility != Visibility::Private)
+                .map(|field| &field.ty),
+            &generic_parameters,
+            struct_.name,
+            SymbolKind::TupleLikeStruct,
+        );
+        self.check_methods(&struct_.methods, &generic_parameters);
+    }
+
+    fn visit_interface(&mut self, interface: &Interface) {
+        if !self.is_exported(interface.visibility) {
+            return;
+        }
+
+        let generic_parameters = names_of(&interface.generic_parameters);
+
+        // All interface methods are public.
+        for method in &interface.methods {
+            self.check_function(method, &generic_parameters, SymbolKind::Method);
+        }
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        if self.is_exported(function.signature.visibility) {
+            self.check_function(function, &FxHashSet::default(), SymbolKind::Function);
+        }
+    }
+
+    fn visit_type_alias(&mut self, alias: &TypeAlias) {
+        if self.is_exported(alias.visibility) {
+            self.check_types(
+                [&alias.value],
+                &names_of(&alias.generic_parameters),
+                alias.name,
+                SymbolKind::TypeAlias,
+            );
+        }
+    }
+}
+
+/// Locations and paths of type constructors, e.g. of `Map`, `String` and
+/// `Secret` in `Map[String, Secret]`.
+#[derive(Default)]
+struct TypeConstructors(Vec<(Location, Vec<IdentifierAST>)>);
+
+impl Visitor for TypeConstructors {
+    fn visit_type_constructor(&mut self, constructor: &TypeConstructor) {
+        self.0
+            .push((constructor.location, constructor.path.identifiers.clone()));
+
+        walk_type_constructor(self, constructor);
+    }
+}
+
+/// Returns names of generic parameters, that are in scope, including `Self`.
+fn names_of(generic_parameters: &[GenericParameter]) -> FxHashSet<IdentifierId> {
+    generic_parameters
+        .iter()
+        .map(|parameter| parameter.name.id)
+        .chain([BIG_SELF])
+        .collect()
+}
+
+/// Returns `true` if a module and all of its parents are public, so that it
+/// can be named outside of its package.
+fn is_module_exported(db: &Database, mut module: ModuleId) -> bool {
+    while let Some(parent) = module.parent(db) {
+        if module.visibility(db) == Visibility::Private {
+            return false;
+        }
+
+        module = parent;
+    }
+
+    true
+}
+
+/// Returns `true` if a symbol can be named outside of its package. Builtin
+/// symbols are always public.
+fn is_symbol_exported(db: &Database, symbol: Symbol) -> bool {
+    match symbol {
+        Symbol::BuiltinSymbol(_) | Symbol::EnumItem(_) => true,
+        Symbol::Module(module) => is_module_exported(db, module),
+        _ => {
+            symbol.visibility(db) != Visibility::Private
+                && is_module_exported(db, symbol.module(db))
+        }
+    }
+}
@@ -1,344 +1,147 @@
-use std::mem;
 #[cfg(feature = "debug")]
 use std::time::Instant;
 
-use stellar_ast::IdentifierAST;
-use stellar_ast_lowering::LoweredModule;
-use stellar_database::{
-    ty::Type, ty::TypeConstructor, GenericParameterData, GenericParameterScopeData,
-    GenericParameterScopeId, ModuleId, PredicateData, SignatureId, State, Symbol, TypeAliasId,
-};
+use stellar_database::{EnumId, GenericParameterData, ModuleId, SignatureId, State, Symbol};
 use stellar_fx_hash::FxHashMap;
-use stellar_interner::{IdentifierId, SymbolId};
-use stellar_thir::{Path, Predicate};
 #[cfg(feature = "debug")]
 use tracing::trace;
 
-use crate::diagnostics::CycleDetectedWhenComputingSignatureOf;
-
 pub struct CollectSignatures<'s, 'h> {
     pub(crate) state: &'s mut State,
-    pub(crate) currently_analyzed_symbols_trace: Vec<Symbol>,
     pub(crate) modules: &'h FxHashMap<ModuleId, stellar_hir::Module>,
 }
 
 impl<'s, 'h> CollectSignatures<'s, 'h> {
     pub fn run_all(state: &'s mut State, modules: &'h FxHashMap<ModuleId, stellar_hir::Module>) {
-        let mut me = CollectSignatures {
-            state,
-            currently_analyzed_symbols_trace: Vec::new(),
-            modules,
-        };
+        let mut me = CollectSignatures { state, modules };
 
-        for module in modules {
-            // me.run(*module.0, module.1);
+        for (module, hir) in modules {
+            me.run(*module, hir);
         }
     }
 
-    //     fn run(&mut self, module: ModuleId, hir: &stellar_hir::Module) {
-    //         for item in &hir.items {
-    //             self.analyze_signature(module, item);
-    //         }
-    //     }
-
-    //     pub(crate) fn analyze_signature(&mut self, module: ModuleId, item: &stellar_hir::ModuleItem) {
-    //         match item {
-    //             stellar_hir::ModuleItem::Enum(enum_) => {
-    //                 self.analyze_signature_of_enum(module, enum_);
-    //             }
-    //             stellar_hir::ModuleItem::Struct(struct_) => {
-    //                 self.analyze_signature_of_struct(module, struct_);
-    //             }
-    //             stellar_hir::ModuleItem::TupleLikeStruct(struct_) => {
-    //                 self.analyze_signature_of_tuple_like_struct(module, struct_);
-    //             }
-    //             stellar_hir::ModuleItem::TypeAlias(alias) => {
-    //                 self.analyze_type_alias(module, alias);
-    //             }
-    //             _ => todo!(),
-    //         }
-    //     }
-
-    //     fn emit_computation_cycle_diagnostic(&mut self) {
-    //         let diagnostic = CycleDetectedWhenComputingSignatureOf::new(
-    //             mem::take(&mut self.currently_analyzed_symbols_trace)
-    //                 .into_iter()
-    //                 .map(|symbol| symbol.name(self.state.db()))
-    //                 .collect::<Vec<_>>(),
-    //         );
-
-    //         self.state.diagnostics_mut().add_diagnostic(diagnostic);
-    //     }
-
-    //     fn start_analyzing_signature(&mut self, symbol: Symbol) {
-    //         #[cfg(feature = "debug")]
-    //         let module = symbol.module(self.state.db());
-
-    //         if self.currently_analyzed_symbols_trace.contains(&symbol) {
-    //             #[cfg(feature = "debug")]
-    //             trace!(
-    //                 "signature cycle detected when analyzing signature of '{}' in '{}'",
-    //                 symbol.name(self.state.db()).id,
-    //                 module.filepath(self.state.db())
-    //             );
-
-    //             self.emit_computation_cycle_diagnostic();
-
-    //             return;
-    //         }
-
-    //         self.currently_analyzed_symbols_trace.push(symbol);
-
-    //         #[cfg(feature = "debug")]
-    //         trace!(
-    //             "start_analyzing_signature_of(name = '{}', module = '{}')",
-    //             symbol.name(self.state.db()).id,
-    //             module.filepath(self.state.db()),
-    //         );
-    //     }
-
-    //     fn analyze_signature_of_enum(&mut self, module: ModuleId, enum_hir: &stellar_hir::Enum) {
-    //         #[cfg(feature = "debug")]
-    //         let now = Instant::now();
-
-    //         let symbol = module.symbol(self.state.db(), enum_hir.name.id);
-
-    //         self.start_analyzing_signature(symbol);
-
-    //         let enum_ = symbol.to_enum();
-    //         let signature = enum_.signature(self.state.db());
-
-    //         self.analyze_generic_parameters(
-    //             module,
-    //             enum_hir.name,
-    //             signature,
-    //             None,
-    //             &enum_hir.generic_parameters,
-    //         );
-    //         self.analyze_where_predicates(module, enum_hir.name, signature, &enum_hir.where_predicates);
-
-    //         signature.set_analyzed(self.state.db_mut());
-
-    //         #[cfg(feature = "debug")]
-    //         trace!(
-    //             "analyze_signature_of_enum(name = '{}', module = '{}') <{} us>",
-    //             enum_hir.name.id,
-    //             module.filepath(self.state.db()),
-    //             now.elapsed().as_micros()
-    //         )
-    //     }
-
-    //     fn analyze_signature_of_struct(&mut self, module: ModuleId, struct_hir: &stellar_hir::Struct) {
-    //         #[cfg(feature = "debug")]
-    //         let now = Instant::now();
-
-    //         let symbol = module.symbol(self.state.db(), struct_hir.name.id);
-
-    //         self.start_analyzing_signature(symbol);
-
-    //         let struct_ = symbol.to_struct();
-    //         let signature = struct_.signature(self.state.db());
-
-    //         self.analyze_generic_parameters(
-    //             module,
-    //             struct_hir.name,
-    //             signature,
-    //             None,
-    //             &struct_hir.generic_parameters,
-    //         );
-    //         self.analyze_where_predicates(
-    //             module,
-    //             struct_hir.name,
-    //             signature,
-    //             &struct_hir.where_predicates,
-    //         );
-
-    //         signature.set_analyzed(self.state.db_mut());
-
-    //         #[cfg(feature = "debug")]
-    //         trace!(
-    //             "analyze_signature_of_struct(name = '{}', module = '{}') <{} us>",
-    //             struct_hir.name.id,
-    //             module.filepath(self.state.db()),
-    //             now.elapsed().as_micros()
-    //         )
-    //     }
-
-    //     fn analyze_signature_of_tuple_like_struct(
-    //         &mut self,
-    //         module: ModuleId,
-    //         struct_hir: &stellar_hir::TupleLikeStruct,
-    //     ) {
-    //         #[cfg(feature = "debug")]
-    //         let now = Instant::now();
-
-    //         let symbol = module.symbol(self.state.db(), struct_hir.name.id);
-
-    //         self.start_analyzing_signature(symbol);
-
-    //         let struct_ = symbol.to_tuple_like_struct();
-    //         let signature = struct_.signature(self.state.db());
-
-    //         self.analyze_generic_parameters(
-    //             module,
-    //             struct_hir.name,
-    //             signature,
-    //             None,
-    //             &struct_hir.generic_parameters,
-    //         );
-    //         self.analyze_where_predicates(
-    //             module,
-    //             struct_hir.name,
-    //             signature,
-    //             &struct_hir.where_predicates,
-    //         );
-
-    //         signature.set_analyzed(self.state.db_mut());
+    fn run(&mut self, module: ModuleId, hir: &stellar_hir::Module) {
+        #[cfg(feature = "debug")]
+        let now = Instant::now();
 
-    //         #[cfg(feature = "debug")]
-    //         trace!(
-    //             "analyze_signature_of_tuple_like_struct(name = '{}', module = '{}') <{} us>",
-    //             struct_hir.name.id,
-    //             module.filepath(self.state.db()),
-    //             now.elapsed().as_micros()
-    //         )
-    //     }
-
-    //     fn analyze_implemented_interfaces(
-    //         &mut self,
-    //         module: ModuleId,
-    //         signature: SignatureId,
-    //         interfaces_hir: &[stellar_hir::TypeConstructor],
-    //     ) {
-    //         for interface_hir in interfaces_hir {
-    //             let interface = self.resolve_interface(interface_hir);
-
-    //             signature.add_implemented_interface(self.state.db_mut(), interface);
-    //         }
-    //     }
-
-    //     fn resolve_interface(
-    //         &mut self,
-    //         interface_hir: &stellar_hir::TypeConstructor,
-    //     ) -> TypeConstructor {
-    //         todo!()
-    //     }
-
-    //     fn analyze_generic_parameters(
-    //         &mut self,
-    //         module: ModuleId,
-    //         item_name: IdentifierAST,
-    //         signature: SignatureId,
-    //         parent_generic_parameter_scope: Option<GenericParameterScopeId>,
-    //         parameters_hir: &[stellar_hir::GenericParameter],
-    //     ) {
-    //         let generic_parameter_scope =
-    //             GenericParameterScopeData::alloc(self.state.db_mut(), module.package());
-
-    //         for parameter_hir in parameters_hir {
-    //             let default_value = if let Some(default_value) = &parameter_hir.default_value {
-    //                 self.resolve_or_analyze_type_in_signature(module, item_name, default_value)
-    //             } else {
-    //                 None
-    //             };
-
-    //             let generic_parameter = GenericParameterData::alloc(
-    //                 self.state.db_mut(),
-    //                 module.package(),
-    //                 parameter_hir.name.location,
-    //                 default_value,
-    //             );
-
-    //             if let Some(bounds) = &parameter_hir.bounds {
-    //                 let bounds = self.resolve_bounds(bounds);
-
-    //                 let predicate = PredicateData::alloc(
-    //                     self.state.db_mut(),
-    //                     module.package(),
-    //                     Type::GenericParameter(generic_parameter),
-    //                     bounds,
-    //                 );
-
-    //                 signature.add_predicate(self.state.db_mut(), predicate);
-    //             }
-
-    //             generic_parameter_scope.add_generic_parameter(
-    //                 self.state.db_mut(),
-    //                 parameter_hir.name.id,
-    //                 generic_parameter,
-    //             );
-    //         }
-
-    //         signature.set_generic_parameter_scope(self.state.db_mut(), generic_parameter_scope);
-    //     }
-
-    //     fn analyze_where_predicates(
-    //         &mut self,
-    //         module: ModuleId,
-    //         item_name: IdentifierAST,
-    //         signature: SignatureId,
-    //         predicates_hir: &[stellar_hir::WherePredicate],
-    //     ) {
-    //         for predicate_hir in predicates_hir {
-    //             let Some(ty) =
-    //                 self.resolve_or_analyze_type_in_signature(module, item_name, &predicate_hir.ty)
-    //             else {
-    //                 return;
-    //             };
-
-    //             let bounds = self.resolve_bounds(&predicate_hir.bounds);
-
-    //             let predicate = PredicateData::alloc(self.state.db_mut(), module.package(), ty, bounds);
-
-    //             signature.add_predicate(self.state.db_mut(), predicate);
-    //         }
-    //     }
-
-    //     fn resolve_bounds(&mut self, bounds: &[stellar_hir::TypeConstructor]) -> Vec<TypeConstructor> {
-    //         todo!()
-    //     }
-
-    //     fn resolve_bound(&mut self, bound: &stellar_hir::TypeConstructor) -> TypeConstructor {
-    //         todo!()
-    //     }
-
-    //     fn analyze_type_signature(&mut self, path: &stellar_ast::Path) {}
-
-    //     fn analyze_type_alias(&mut self, module: ModuleId, alias_hir: &stellar_hir::TypeAlias) {
-    //         #[cfg(feature = "debug")]
-    //         let now = Instant::now();
-
-    //         let symbol = module.symbol(self.state.db(), alias_hir.name.id);
-
-    //         self.start_analyzing_signature(symbol);
+        for item in &hir.items {
+            self.analyze_signature(module, item);
+        }
 
-    //         let alias = symbol.to_type_alias();
-    //         let signature = alias.signature(self.state.db());
+        #[cfg(feature = "debug")]
+        trace!(
+            "collect_signatures_in(module = '{}') <{} us>",
+            module.filepath(self.state.db()),
+            now.elapsed().as_micros()
+        );
+    }
 
-    //         self.analyze_generic_parameters(
-    //             module,
-    //             alias_hir.name,
-    //             signature,
-    //             None,
-    //             &alias_hir.generic_parameters,
-    //         );
+    /// Collects generic parameters of a module item into the scope of its
+    /// signature. Bounds, default values and where predicates are not
+    /// resolved yet.
+    pub(crate) fn analyze_signature(&mut self, module: ModuleId, item: &stellar_hir::ModuleItem) {
+        let (name, generic_parameters) = match item {
+            stellar_hir::ModuleItem::Enum(enum_) => (enum_.name, &enum_.generic_parameters),
+            stellar_hir::ModuleItem::Function(function) => (
+                function.signature.name,
+                &function.signature.generic_parameters,
+            ),
+            stellar_hir::ModuleItem::Interface(interface) => {
+                (interface.name, &interface.generic_parameters)
+            }
+            stellar_hir::ModuleItem::Struct(struct_) => (struct_.name, &struct_.generic_parameters),
+            stellar_hir::ModuleItem::TupleLikeStruct(struct_) => {
+                (struct_.name, &struct_.generic_parameters)
+            }
+            stellar_hir::ModuleItem::TypeAlias(alias) => (alias.name, &alias.generic_parameters),
+            stellar_hir::ModuleItem::Import { .. } | stellar_hir::ModuleItem::Module(_) => return,
+        };
 
-    //         let Some(value) =
-    //             self.resolve_or_analyze_type_in_signature(module, alias_hir.name, &alias_hir.value)
-    //         else {
-    //             return;
-    //         };
+        // Duplicate definitions are not collected, so the name can refer to
+        // another item.
+        let Some(symbol) = module
+            .module_item_symbol_or_none(self.state.db(), name.id)
+            .filter(|symbol| symbol.name(self.state.db()).location == name.location)
+        else {
+            return;
+        };
 
-    //         alias.set_type(self.state.db_mut(), value);
+        let signature = symbol.signature(self.state.db());
+        self.analyze_generic_parameters(module, signature, generic_parameters);
+
+        match (symbol, item) {
+            (Symbol::Enum(enum_), stellar_hir::ModuleItem::Enum(enum_hir)) => {
+                self.analyze_signatures_of_methods(module, enum_, signature, &enum_hir.methods);
+            }
+            // Alias expansion substitutes generic arguments positionally, so
+            // only generic aliases get the scope. Default values are resolved
+            // lazily during the expansion.
+            (Symbol::TypeAlias(alias), _) if !generic_parameters.is_empty() => {
+                let scope = signature.generic_parameter_scope(self.state.db());
+                alias.set_generic_parameter_scope(self.state.db_mut(), scope);
+            }
+            _ => {}
+        }
+    }
 
-    //         signature.set_analyzed(self.state.db_mut());
+    /// Collects generic parameters of enum methods into child scopes of the
+    /// enum's scope, so that parameters of the enum are visible in them.
+    fn analyze_signatures_of_methods(
+        &mut self,
+        module: ModuleId,
+        enum_: EnumId,
+        enum_signature: SignatureId,
+        methods_hir: &[stellar_hir::Function],
+    ) {
+        for method_hir in methods_hir {
+            let name = method_hir.signature.name;
+
+            // Methods, that conflict with enum items, are not collected.
+            let Some(method) = enum_.method(self.state.db(), name.id).filter(|method| {
+                method
+                    .signature(self.state.db())
+                    .name(self.state.db())
+                    .location
+                    == name.location
+            }) else {
+                continue;
+            };
+
+            let db = self.state.db_mut();
+            let signature = method.signature(db);
+            let scope = enum_signature.generic_parameter_scope(db).child(db);
+            signature.set_generic_parameter_scope(db, scope);
+
+            self.analyze_generic_parameters(
+                module,
+                signature,
+                &method_hir.signature.generic_parameters,
+            );
+        }
+    }
 
-    //         #[cfg(feature = "debug")]
-    //         trace!(
-    //             "analyze_type_alias(name = '{}', module = '{}') <{} us>",
-    //             alias_hir.name.id,
-    //             module.filepath(self.state.db()),
-    //             now.elapsed().as_micros()
-    //         )
-    //     }
+    fn analyze_generic_parameters(
+        &mut self,
+        module: ModuleId,
+        signature: SignatureId,
+        parameters_hir: &[stellar_hir::GenericParameter],
+    ) {
+        let db = self.state.db_mut();
+        let generic_parameter_scope = signature.generic_parameter_scope(db);
+
+        for parameter_hir in parameters_hir {
+            let generic_parameter = GenericParameterData::alloc(
+                db,
+                module.package(),
+                parameter_hir.name.location,
+                None,
+            );
+
+            generic_parameter_scope.add_generic_parameter(
+                db,
+                parameter_hir.name.id,
+                generic_parameter,
+            );
+        }
+    }
 }
@@ -1,3 +1,39 @@
+pub mod check_entry_point;
+pub mod check_generic_arguments;
+pub mod check_generic_parameters;
+pub mod check_interface_conformance;
+pub mod check_object_safety;
+pub mod check_private_in_public;
 pub mod collect_signatures;
 mod resolve;
 mod satisfies;
+
+use stellar_database::{Database, ModuleId, Symbol};
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::ModuleItem;
+
+use crate::resolution::resolve_path_silently;
+
+/// Resolves an interface HIR by its path without emitting any diagnostics.
+pub(crate) fn resolve_interface<'h>(
+    db: &Database,
+    modules: &'h FxHashMap<ModuleId, stellar_hir::Module>,
+    module: ModuleId,
+    path: &stellar_hir::Path,
+) -> Option<&'h stellar_hir::Interface> {
+    let Some(Symbol::Interface(interface)) = resolve_path_silently(db, module, &path.identifiers)
+    else {
+        return None;
+    };
+
+    let signature = interface.signature(db);
+
+    match modules
+        .get(&signature.module(db))?
+        .items
+        .get(signature.node_idx(db))?
+    {
+        ModuleItem::Interface(interface) => Some(interface),
+        _ => None,
+    }
+}
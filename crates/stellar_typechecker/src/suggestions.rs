@@ -0,0 +1,57 @@
+//! Helpers to suggest similar names in diagnostics, e.g. `did you mean `Some`?`.
+
+use itertools::Itertools;
+use stellar_interner::IdentifierId;
+
+/// Finds a name, that is the most similar to a given one, if there is any
+/// close enough to be a possible typo.
+pub(crate) fn find_similar_name(
+    name: IdentifierId,
+    candidates: impl IntoIterator<Item = IdentifierId>,
+) -> Option<IdentifierId> {
+    find_similar_names(name, candidates, 1).into_iter().next()
+}
+
+/// Finds at most `limit` names, that are close enough to a given one to be
+/// possible typos, from the most similar one.
+pub(crate) fn find_similar_names(
+    name: IdentifierId,
+    candidates: impl IntoIterator<Item = IdentifierId>,
+    limit: usize,
+) -> Vec<IdentifierId> {
+    let name = name.to_string();
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (candidate, edit_distance(&name, &candidate.to_string())))
+        .filter(|(_, distance)| *distance <= max_distance)
+        .sorted_by_key(|(candidate, distance)| (*distance, candidate.to_string()))
+        .take(limit)
+        .map(|(candidate, _)| candidate)
+        .collect()
+}
+
+/// Computes the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous_row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current_row = vec![i + 1];
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution_cost = usize::from(a_char != *b_char);
+
+            current_row.push(
+                (previous_row[j] + substitution_cost)
+                    .min(previous_row[j + 1] + 1)
+                    .min(current_row[j] + 1),
+            );
+        }
+
+        previous_row = current_row;
+    }
+
+    previous_row[b.len()]
+}
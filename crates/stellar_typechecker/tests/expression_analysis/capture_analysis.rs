@@ -0,0 +1,90 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_hir::{
+    visit::{walk_expression, Visitor},
+    Expression,
+};
+use stellar_interner::{IdentifierId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::expression_analysis::capture_analysis::CaptureAnalysis;
+
+/// Collects captures of all lambdas in the order they appear in the source code.
+#[derive(Default)]
+struct LambdaCaptures(Vec<Vec<String>>);
+
+impl Visitor for LambdaCaptures {
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Expression::Lambda { captures, .. } = expression {
+            self.0.push(
+                captures
+                    .iter()
+                    .map(IdentifierId::to_string)
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        walk_expression(self, expression);
+    }
+}
+
+fn lambda_captures(source_code: &str) -> Vec<Vec<String>> {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+
+    let mut hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+    CaptureAnalysis::run_all(&mut hir);
+
+    let mut captures = LambdaCaptures::default();
+
+    for module in hir.values() {
+        captures.visit_module(module);
+    }
+
+    captures.0
+}
+
+#[test]
+fn outer_local() {
+    assert_eq!(
+        lambda_captures(
+            "fun main() {
+    let a = 1;
+    let b = 2;
+    let f = |x| x + a;
+}"
+        ),
+        [["a"]]
+    );
+}
+
+#[test]
+fn nested_lambdas() {
+    assert_eq!(
+        lambda_captures(
+            "fun main(a: int32) {
+    let f = |x| |y| x + y + a;
+}"
+        ),
+        [vec!["a"], vec!["x", "a"]]
+    );
+}
+
+#[test]
+fn no_captures() {
+    assert_eq!(
+        lambda_captures(
+            "fun main() {
+    let f = |x| { let y = x; y };
+}"
+        ),
+        [Vec::<String>::new()]
+    );
+}
@@ -0,0 +1,168 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_interner::{DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::{
+    expression_analysis::{
+        check_assignments::CheckAssignments, infer_expression_types::InferExpressionTypes,
+    },
+    resolution::collect_definitions::CollectDefinitions,
+};
+
+use crate::error_codes;
+
+fn check(source_code: &str) -> State {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    CheckAssignments::run_all(&mut state, &hir);
+    InferExpressionTypes::run_all(&mut state, &hir);
+
+    state
+}
+
+#[test]
+fn plain_assignment() {
+    let state = check(
+        "fun main(mut xs: List[int32]) {
+    let mut x: int32 = 1;
+    x = 5;
+    xs[0] = x;
+
+    let mut t = (1, \"a\");
+    t.1 = \"b\";
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn compound_assignment_on_field() {
+    let state = check(
+        "struct Point { x: int32, y: int32 }
+
+fun main(mut p: Point) {
+    p.x += 1;
+    p.y *= 2;
+    p.y %= 3;
+    p.x &= 1;
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn assignment_to_call_result() {
+    let state = check(
+        "fun get(): int32 { 1 }
+
+fun main() {
+    get() = 2;
+    get() += 1;
+    main = get;
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E214", "E214", "E214"]);
+}
+
+#[test]
+fn assignment_type_mismatch() {
+    let state = check(
+        "fun main(mut xs: List[String]) {
+    let mut x: int32 = 1;
+    x = \"a\";
+    xs[0] = 1;
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E215", "E215"]);
+}
+
+#[test]
+fn assignment_to_immutable_local() {
+    let source_code = "fun main() {
+    let x = 1;
+    x = 2;
+    let (a, mut b) = (1, 2);
+    a += b;
+    b = a;
+}";
+    let state = check(source_code);
+
+    assert_eq!(error_codes(&state), ["E216", "E216"]);
+
+    let diagnostic = state.diagnostics().all()[0];
+    let x = source_code.find("x =").unwrap();
+
+    assert_eq!(diagnostic.suggestions.len(), 1);
+    assert_eq!(diagnostic.suggestions[0].replacement, "mut ");
+    assert_eq!(
+        diagnostic.suggestions[0].location,
+        Location {
+            filepath: DUMMY_PATH_ID,
+            start: ByteOffset(x),
+            end: ByteOffset(x)
+        }
+    );
+
+    let a = source_code.find("a,").unwrap();
+
+    assert_eq!(
+        state.diagnostics().all()[1].suggestions[0].location.start,
+        ByteOffset(a)
+    );
+}
+
+#[test]
+fn shadowing_immutable_local() {
+    let state = check(
+        "fun main() {
+    let x = 1;
+    let x = x + 1;
+    let mut x = x;
+    x = 3;
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn assignment_to_immutable_parameter() {
+    let source_code = "struct Point { x: int32 }
+
+fun main(p: Point, mut q: Point) {
+    p.x = 1;
+    q.x = 1;
+}";
+    let state = check(source_code);
+
+    assert_eq!(error_codes(&state), ["E216"]);
+
+    let p = source_code.find("p: Point").unwrap();
+
+    assert_eq!(
+        state.diagnostics().all()[0].suggestions[0].location,
+        Location {
+            filepath: DUMMY_PATH_ID,
+            start: ByteOffset(p),
+            end: ByteOffset(p)
+        }
+    );
+}
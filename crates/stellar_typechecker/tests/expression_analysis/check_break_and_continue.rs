@@ -0,0 +1,112 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_interner::{DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::expression_analysis::check_break_and_continue::CheckBreakAndContinue;
+
+use crate::error_codes;
+
+fn check(source_code: &str) -> State {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CheckBreakAndContinue::run_all(&mut state, &hir);
+
+    state
+}
+
+#[test]
+fn labeled_break_to_outer_loop() {
+    let state = check(
+        "fun main() {
+    @outer: loop {
+        @inner: while true {
+            break @outer;
+            continue @inner;
+        }
+
+        continue @outer;
+    }
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn undeclared_label_with_suggestion() {
+    let state = check(
+        "fun main() {
+    @outer: loop {
+        loop {
+            break @outr;
+        }
+    }
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E115"]);
+    assert_eq!(
+        state.diagnostics().all()[0].notes,
+        ["help: did you mean `@outer`?"]
+    );
+}
+
+#[test]
+fn label_of_loop_outside_of_lambda() {
+    let state = check(
+        "fun main() {
+    @outer: loop {
+        let f = || { break @outer; };
+    }
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E115"]);
+}
+
+#[test]
+fn break_outside_of_loop() {
+    let state = check(
+        "fun main() {
+    break;
+    continue;
+
+    loop {
+        break;
+    }
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E220", "E220"]);
+}
+
+#[test]
+fn break_with_value_in_while() {
+    let state = check(
+        "fun main() {
+    while true {
+        break 1;
+    }
+
+    @outer: loop {
+        while true {
+            break @outer 2;
+        }
+    }
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E222"]);
+}
@@ -0,0 +1,101 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_interner::{DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::{
+    expression_analysis::check_let_patterns::CheckLetPatterns,
+    resolution::collect_definitions::CollectDefinitions,
+};
+
+use crate::error_codes;
+
+fn check(source_code: &str) -> State {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    CheckLetPatterns::run_all(&mut state, &hir);
+
+    state
+}
+
+#[test]
+fn irrefutable_let() {
+    let state = check(
+        "struct Point { x: int32, y: int32 }
+enum Wrapper { Value(int32) }
+
+fun main() {
+    let (a, _, ..) = (1, 2, 3);
+    let Point { x, y: _ } = Point { x: 1, y: 2 };
+    let Wrapper.Value(value) = Wrapper.Value(3);
+    let [..] = [1, 2];
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn refutable_let_without_else() {
+    let state = check(
+        "enum Option[T] { Some(T), None }
+
+fun main() {
+    let Option.Some(x) = Option.Some(1);
+    let (1, y) = (1, 2);
+    let [a, b] = [1, 2];
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E206", "E206", "E206"]);
+}
+
+#[test]
+fn let_else_with_return() {
+    let state = check(
+        "enum Option[T] { Some(T), None }
+
+fun main(): int32 {
+    let Option.Some(x) = Option.Some(1) else {
+        return 0;
+    };
+    let Option.Some(y) = Option.Some(2) else {
+        if true { return 0; } else { return 0; }
+    };
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn let_else_without_divergence() {
+    let state = check(
+        "enum Option[T] { Some(T), None }
+
+fun foo() {}
+
+fun main(): int32 {
+    let Option.Some(x) = Option.Some(1) else {
+        foo();
+    };
+    let Option.Some(y) = Option.Some(2) else {
+        if true { return 0; }
+    };
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E207", "E207"]);
+}
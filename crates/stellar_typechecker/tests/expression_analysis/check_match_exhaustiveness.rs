@@ -0,0 +1,117 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_interner::{DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::{
+    expression_analysis::check_match_exhaustiveness::CheckMatchExhaustiveness,
+    resolution::collect_definitions::CollectDefinitions,
+};
+
+fn check(source_code: &str) -> State {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    CheckMatchExhaustiveness::run_all(&mut state, &hir);
+
+    state
+}
+
+fn labels(state: &State) -> Vec<&str> {
+    state
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| {
+            assert_eq!(diagnostic.code.as_deref(), Some("E218"));
+            diagnostic.labels[0].message.as_str()
+        })
+        .collect()
+}
+
+#[test]
+fn exhaustive_matches() {
+    let state = check(
+        "enum Option[T] { Some(T), None }
+
+fun main(x: Option[int32]) {
+    match x {
+        Option.Some(_) -> 1,
+        Option.None -> 2,
+    };
+    match x {
+        Option.Some(y) if y > 0 -> 1,
+        _ -> 2,
+    };
+    match x {
+        Option.Some(1) | Option.None -> 1,
+        Option.Some(_) -> 2,
+    };
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn missing_enum_items() {
+    let state = check(
+        "enum Color { Red, Green, Blue }
+
+fun main(color: Color) {
+    match color {
+        Color.Red -> 1,
+    };
+}",
+    );
+
+    assert_eq!(labels(&state), ["`Color.Green`, `Color.Blue` not covered"]);
+}
+
+#[test]
+fn guarded_items_dont_cover_patterns() {
+    let state = check(
+        "enum Option[T] { Some(T), None }
+
+fun main(x: Option[int32]) {
+    match x {
+        Option.Some(y) if y > 0 -> 1,
+        Option.None -> 2,
+    };
+    match 1 {
+        n if n > 0 -> 1,
+    };
+}",
+    );
+
+    assert_eq!(
+        labels(&state),
+        ["`Option.Some` not covered", "`_` not covered"]
+    );
+}
+
+#[test]
+fn refutable_payload_doesnt_cover_item() {
+    let state = check(
+        "enum Option[T] { Some(T), None }
+
+fun main(x: Option[int32]) {
+    match x {
+        Option.Some(1) -> 1,
+        Option.None -> 2,
+    };
+}",
+    );
+
+    assert_eq!(labels(&state), ["`Option.Some` not covered"]);
+}
@@ -0,0 +1,1708 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{
+    symbol::BuiltinSymbolId,
+    ty::{list_of, Type, TypeConstructor},
+    PackageData, State, Symbol,
+};
+use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::{DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::{
+    expression_analysis::infer_expression_types::InferExpressionTypes,
+    resolution::collect_definitions::CollectDefinitions,
+};
+
+use crate::error_codes;
+
+fn infer(source_code: &str) -> (State, FxHashMap<Location, Type>) {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    let types = InferExpressionTypes::run_all(&mut state, &hir);
+
+    (state, types)
+}
+
+/// Returns the type of the first expression with a given source text.
+fn type_of<'t>(
+    types: &'t FxHashMap<Location, Type>,
+    source_code: &str,
+    expression: &str,
+) -> &'t Type {
+    let start = source_code.find(expression).unwrap();
+
+    &types[&Location {
+        filepath: DUMMY_PATH_ID,
+        start: ByteOffset(start),
+        end: ByteOffset(start + expression.len()),
+    }]
+}
+
+fn primitive(builtin: BuiltinSymbolId) -> Type {
+    Type::Constructor(TypeConstructor::new(Symbol::BuiltinSymbol(builtin), vec![]))
+}
+
+#[test]
+fn homogeneous_list_literal() {
+    let source_code = "fun main() {
+    let xs = [\"a\", \"b\", \"c\"];
+    let ys = [1, 2, 3];
+    let zs = [[1], [], [2, 3]];
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of(&types, source_code, "[\"a\", \"b\", \"c\"]"),
+        &list_of(primitive(BuiltinSymbolId::String))
+    );
+}
+
+#[test]
+fn heterogeneous_list_literal() {
+    let source_code = "fun main() {
+    let xs = [1, \"a\", 3];
+    let ys = [(1, true), (2, 'c')];
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E211", "E211"]);
+
+    let location = state.diagnostics().all()[0].labels[0].location;
+    assert_eq!(&source_code[location.start.0..location.end.0], "\"a\"");
+}
+
+#[test]
+fn empty_list_literal_constrained_by_use() {
+    let source_code = "fun sum(xs: List[uint8]): uint8 { 0 }
+
+fun main() {
+    let xs = [];
+    let x: int32 = xs[0];
+
+    let ys = [[]];
+    sum(ys[0]);
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of(&types, source_code, "[]"),
+        &list_of(primitive(BuiltinSymbolId::Int32))
+    );
+    assert_eq!(
+        type_of(&types, source_code, "[[]]"),
+        &list_of(list_of(primitive(BuiltinSymbolId::Uint8)))
+    );
+}
+
+#[test]
+fn string_index() {
+    let source_code = "fun main(xs: List[int32]) {
+    let x = xs[\"a\"];
+    let y = xs[0];
+}";
+    let (state, types) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E213"]);
+    assert_eq!(
+        type_of(&types, source_code, "xs[0]"),
+        &primitive(BuiltinSymbolId::Int32)
+    );
+}
+
+#[test]
+fn index_on_non_list() {
+    let source_code = "fun main(s: String) {
+    let x = s[0];
+    let y = (1, 2)[0];
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E212", "E212"]);
+}
+
+#[test]
+fn match_guard_with_pattern_binding() {
+    let source_code = "fun main(xs: List[bool]) {
+    match xs[0] {
+        flag if flag -> 1,
+        _ -> 2,
+    };
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+
+    let guard_start = source_code.find("if flag").unwrap() + "if ".len();
+    let guard = Location {
+        filepath: DUMMY_PATH_ID,
+        start: ByteOffset(guard_start),
+        end: ByteOffset(guard_start + "flag".len()),
+    };
+    assert_eq!(types[&guard], primitive(BuiltinSymbolId::Bool));
+}
+
+#[test]
+fn non_boolean_match_guard() {
+    let source_code = "fun main() {
+    match 1 {
+        n if n -> 1,
+        _ if \"yes\" -> 2,
+        _ -> 3,
+    };
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E217", "E217"]);
+}
+
+#[test]
+fn binding_shadowing_enum_item_in_match() {
+    let source_code = "enum Color { Red, Green }
+
+fun paint(color: Color): int32 {
+    match color {
+        red -> 1,
+    }
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["W013"]);
+
+    let diagnostic = &state.diagnostics().all()[0];
+    let binding_start = source_code.find("red ->").unwrap();
+
+    assert_eq!(
+        diagnostic.message,
+        "this pattern binds a new variable `red` instead of matching the variant `Red`"
+    );
+    assert_eq!(diagnostic.suggestions[0].replacement, "Color.Red");
+    assert_eq!(
+        diagnostic.suggestions[0].location,
+        Location {
+            filepath: DUMMY_PATH_ID,
+            start: ByteOffset(binding_start),
+            end: ByteOffset(binding_start + "red".len()),
+        }
+    );
+}
+
+#[test]
+fn enum_item_name_in_match_pattern() {
+    let source_code = "enum Color { Red, Green }
+
+fun paint(color: Color): int32 {
+    match color {
+        Red -> 1,
+        Green -> 2,
+    }
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+
+    let color = type_of_last(&types, source_code, "color");
+
+    assert!(matches!(
+        color,
+        Type::Constructor(TypeConstructor {
+            symbol: Symbol::Enum(_),
+            ..
+        })
+    ));
+    assert_eq!(type_of_last(&types, source_code, "Red"), color);
+    assert_eq!(type_of_last(&types, source_code, "Green"), color);
+}
+
+#[test]
+fn unrelated_binding_in_match_over_enum() {
+    let source_code = "enum Color { Red, Green }
+
+fun paint(color: Color): int32 {
+    match color {
+        other -> 1,
+    }
+}";
+    let (state, _) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+}
+
+/// Returns the type of the last expression with a given source text.
+fn type_of_last<'t>(
+    types: &'t FxHashMap<Location, Type>,
+    source_code: &str,
+    expression: &str,
+) -> &'t Type {
+    let start = source_code.rfind(expression).unwrap();
+
+    &types[&Location {
+        filepath: DUMMY_PATH_ID,
+        start: ByteOffset(start),
+        end: ByteOffset(start + expression.len()),
+    }]
+}
+
+#[test]
+fn rest_in_the_middle_of_tuple_pattern() {
+    let source_code = "fun main(t: (int32, String, bool, char)) {
+    let (first, .., last) = t;
+    first;
+    last;
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of_last(&types, source_code, "first"),
+        &primitive(BuiltinSymbolId::Int32)
+    );
+    assert_eq!(
+        type_of_last(&types, source_code, "last"),
+        &primitive(BuiltinSymbolId::Char)
+    );
+}
+
+#[test]
+fn rest_with_too_many_fixed_elements() {
+    let source_code = "fun main(t: (int32, bool)) {
+    let (a, b, .., c) = t;
+    let (x, y, z) = t;
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E219", "E219"]);
+}
+
+#[test]
+fn multiple_rests_in_pattern() {
+    let source_code = "fun main(t: (int32, bool, char)) {
+    let (a, .., b, ..) = t;
+    let [.., x, ..y] = [1, 2];
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E008", "E008"]);
+}
+
+#[test]
+fn rest_binding_in_list_pattern() {
+    let source_code = "fun main(xs: List[String]) {
+    let [head, ..tail] = xs;
+    head;
+    tail;
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of_last(&types, source_code, "head"),
+        &primitive(BuiltinSymbolId::String)
+    );
+    assert_eq!(
+        type_of_last(&types, source_code, "tail"),
+        &list_of(primitive(BuiltinSymbolId::String))
+    );
+}
+
+#[test]
+fn loop_value_from_break() {
+    let source_code = "fun main(done: bool) {
+    let x: int64 = loop {
+        if done {
+            break 1;
+        }
+    };
+    let y = loop { break; };
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of(&types, source_code, "1"),
+        &primitive(BuiltinSymbolId::Int64)
+    );
+    assert_eq!(type_of(&types, source_code, "loop { break; }"), &Type::Unit);
+}
+
+#[test]
+fn conflicting_break_values() {
+    let source_code = "fun main(done: bool) {
+    let x = loop {
+        if done {
+            break 1;
+        }
+
+        break \"a\";
+    };
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E221"]);
+
+    let diagnostic = state.diagnostics().all()[0];
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(label(diagnostic.labels[0].location), "\"a\"");
+    assert_eq!(label(diagnostic.labels[1].location), "1");
+}
+
+#[test]
+fn call_of_deprecated_function() {
+    let source_code = "#[deprecated(\"use bar instead\")]
+fun foo() {}
+
+fun bar() {}
+
+fun main() {
+    foo();
+    bar();
+}";
+    let (state, _) = infer(source_code);
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(error_codes(&state), ["W007"]);
+    assert_eq!(
+        diagnostics[0].message,
+        "use of deprecated function `foo`: use bar instead"
+    );
+
+    let call = source_code.rfind("foo").unwrap();
+    let definition = source_code.find("foo").unwrap();
+
+    assert_eq!(
+        diagnostics[0].labels[0].location,
+        Location {
+            filepath: DUMMY_PATH_ID,
+            start: ByteOffset(call),
+            end: ByteOffset(call + 3),
+        }
+    );
+    assert_eq!(
+        diagnostics[0].labels[1].location,
+        Location {
+            filepath: DUMMY_PATH_ID,
+            start: ByteOffset(definition),
+            end: ByteOffset(definition + 3),
+        }
+    );
+}
+
+#[test]
+fn recursive_call_of_deprecated_function() {
+    let (state, _) = infer(
+        "#[deprecated]
+fun foo() { foo(); }",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn if_with_diverging_branch() {
+    let source_code = "fun main(c: bool) {
+    let x = if c { 1 } else { return (); };
+    let y: uint8 = x;
+    let z: uint16 = if c { return (); } else { 2 };
+    let w = if c { return (); } else { loop {} };
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of(&types, source_code, "if c { 1 } else { return (); }"),
+        &primitive(BuiltinSymbolId::Uint8)
+    );
+    assert_eq!(
+        type_of(&types, source_code, "if c { return (); } else { 2 }"),
+        &primitive(BuiltinSymbolId::Uint16)
+    );
+    assert_eq!(
+        type_of(&types, source_code, "if c { return (); } else { loop {} }"),
+        &Type::Never
+    );
+}
+
+#[test]
+fn match_with_diverging_arm() {
+    let source_code = "fun main(xs: List[bool]) {
+    let x = match xs[0] {
+        true -> \"yes\",
+        false -> { return (); },
+    };
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of(
+            &types,
+            source_code,
+            "match xs[0] {
+        true -> \"yes\",
+        false -> { return (); },
+    }"
+        ),
+        &primitive(BuiltinSymbolId::String)
+    );
+}
+
+#[test]
+fn field_access() {
+    let source_code = "struct Box[T] { value: T, size: uint64 }
+struct Wrapper(String);
+
+fun main(b: Box[bool], w: Wrapper) {
+    let a = b.value;
+    let c = w.0;
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of(&types, source_code, "b.value"),
+        &primitive(BuiltinSymbolId::Bool)
+    );
+    assert_eq!(
+        type_of(&types, source_code, "w.0"),
+        &primitive(BuiltinSymbolId::String)
+    );
+}
+
+#[test]
+fn private_field_access() {
+    let source_code = "module geometry {
+    pub struct Point { pub x: int32, y: int32 }
+    pub struct Meters(float64);
+
+    fun y(p: Point): int32 { p.y }
+}
+
+fun main(p: geometry.Point, m: geometry.Meters) {
+    let x = p.x;
+    let y = p.y;
+    let value = m.0;
+}";
+    let (state, _) = infer(source_code);
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(error_codes(&state), ["E230", "E230"]);
+    assert_eq!(
+        diagnostics[0].message,
+        "field `y` of struct `Point` is private"
+    );
+
+    let field_declaration = source_code.find("y: int32").unwrap();
+    assert_eq!(
+        diagnostics[0].labels[1].location,
+        Location {
+            filepath: DUMMY_PATH_ID,
+            start: ByteOffset(field_declaration),
+            end: ByteOffset(field_declaration + 1),
+        }
+    );
+}
+
+#[test]
+fn unknown_field() {
+    let source_code = "struct Point { x: int32, y: int32, xx: int32, name: String }
+struct Wrapper(String);
+
+fun main(p: Point, w: Wrapper) {
+    let a = p.xy;
+    let b = p.nam;
+    let c = p.foo;
+    let d = w.1;
+}";
+    let (state, _) = infer(source_code);
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(error_codes(&state), ["E229", "E229", "E229", "E229"]);
+    assert_eq!(diagnostics[0].message, "no field `xy` on type `Point`");
+    assert_eq!(
+        diagnostics[0].notes,
+        ["help: did you mean `x`, `xx`, or `y`?"]
+    );
+    assert_eq!(diagnostics[1].notes, ["help: did you mean `name`?"]);
+    assert!(diagnostics[2].notes.is_empty());
+    assert_eq!(diagnostics[3].message, "no field `1` on type `Wrapper`");
+}
+
+#[test]
+fn field_access_on_non_struct() {
+    let source_code = "enum Color { Red, Green }
+
+fun main(x: int32, pair: (int32, int32), color: Color) {
+    let a = x.value;
+    let b = pair.first;
+    let c = color.name;
+    let d = x.foo();
+}";
+    let (state, _) = infer(source_code);
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(error_codes(&state), ["E231", "E231", "E231"]);
+    assert_eq!(
+        diagnostics[0].message,
+        "cannot access field `value` of a value, that is not a struct"
+    );
+    assert_eq!(diagnostics[0].labels[0].message, "this has type `int32`");
+}
+
+#[test]
+fn fields_of_unknown_types_are_not_checked() {
+    let source_code = "fun main[T](x: T) {
+    let a = x.value;
+    let b = foo().bar;
+    let c = Option.None;
+}";
+    let (state, _) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn struct_expression() {
+    let source_code = "struct Point { x: int32, y: int32 }
+struct Box[T] { value: T }
+
+fun main(y: int32) {
+    let p = Point { x: 1, y };
+    let b = Box { value: \"a\" };
+    let c = Box[bool] { value: true };
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of(&types, source_code, "Box { value: \"a\" }")
+            .display(state.db())
+            .to_string(),
+        "Box[String]"
+    );
+    assert_eq!(
+        type_of(&types, source_code, "Box[bool] { value: true }")
+            .display(state.db())
+            .to_string(),
+        "Box[bool]"
+    );
+}
+
+#[test]
+fn unknown_and_duplicate_struct_expression_fields() {
+    let source_code = "struct Point { x: int32, y: int32 }
+
+fun main() {
+    let p = Point { x: 1, y: 2, z: 3 };
+    let q = Point { x: 1, yy: 2, y: 3, x: 4 };
+}";
+    let (state, _) = infer(source_code);
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(error_codes(&state), ["E229", "E229", "E232"]);
+    assert_eq!(diagnostics[0].message, "no field `z` on type `Point`");
+    assert_eq!(diagnostics[1].notes, ["help: did you mean `y`?"]);
+    assert_eq!(
+        diagnostics[2].message,
+        "field `x` is specified more than once"
+    );
+
+    let second_x = source_code.find("x: 4").unwrap();
+    assert_eq!(
+        diagnostics[2].labels[0].location,
+        Location {
+            filepath: DUMMY_PATH_ID,
+            start: ByteOffset(second_x),
+            end: ByteOffset(second_x + 1),
+        }
+    );
+}
+
+#[test]
+fn missing_struct_expression_fields() {
+    let source_code = "struct Point3 { x: int32, y: int32, z: int32 }
+
+fun main() {
+    let p = Point3 { y: 1 };
+}";
+    let (state, _) = infer(source_code);
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(error_codes(&state), ["E233"]);
+    assert_eq!(
+        diagnostics[0].message,
+        "missing fields `x` and `z` in initializer of `Point3`"
+    );
+
+    let closing_brace = source_code.find("};").unwrap();
+    assert_eq!(
+        diagnostics[0].labels[0].location,
+        Location {
+            filepath: DUMMY_PATH_ID,
+            start: ByteOffset(closing_brace),
+            end: ByteOffset(closing_brace + 1),
+        }
+    );
+}
+
+#[test]
+fn struct_expression_field_type_mismatch() {
+    let source_code = "struct Person { name: String, age: uint32 }
+
+fun main() {
+    let p = Person { name: 1, age: \"a\" };
+}";
+    let (state, _) = infer(source_code);
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(error_codes(&state), ["E234", "E234"]);
+    assert_eq!(
+        diagnostics[0].labels[0].message,
+        "expected `String`, found `{integer}`"
+    );
+    assert_eq!(
+        diagnostics[1].labels[0].message,
+        "expected `uint32`, found `String`"
+    );
+}
+
+#[test]
+fn private_fields_in_struct_expression() {
+    let source_code = "module geometry {
+    pub struct Point { pub x: int32, y: int32 }
+
+    fun origin(): Point { Point { x: 0, y: 0 } }
+}
+
+fun main() {
+    let p = geometry.Point { x: 0, y: 0 };
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E230"]);
+}
+
+#[test]
+fn tuple_like_struct_construction() {
+    let source_code = "struct Point(float64, float64);
+struct Wrapper[T](T);
+
+fun main() {
+    let p = Point(1.0, 2.0);
+    let x = p.0;
+    let w = Wrapper(\"a\");
+    let value = w.0;
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of(&types, source_code, "p.0"),
+        &primitive(BuiltinSymbolId::Float64)
+    );
+    assert_eq!(
+        type_of(&types, source_code, "w.0"),
+        &primitive(BuiltinSymbolId::String)
+    );
+}
+
+#[test]
+fn tuple_like_struct_construction_arity_mismatch() {
+    let source_code = "struct Point(float64, float64);
+
+fun main() {
+    let p = Point(1.0);
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E252"]);
+    assert_eq!(
+        state.diagnostics().all()[0].message,
+        "struct `Point` takes 2 arguments, but 1 was supplied"
+    );
+}
+
+#[test]
+fn tuple_like_struct_construction_type_mismatch() {
+    let source_code = "struct Point(float64, float64);
+
+fun main() {
+    let p = Point(1.0, \"2\");
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E234"]);
+    assert_eq!(
+        state.diagnostics().all()[0].labels[0].message,
+        "expected `float64`, found `String`"
+    );
+}
+
+#[test]
+fn tuple_like_struct_construction_with_private_field() {
+    let source_code = "module geometry {
+    pub struct Meters(float64);
+
+    fun one(): Meters { Meters(1.0) }
+}
+
+fun main() {
+    let m = geometry.Meters(1.0);
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E253"]);
+    assert_eq!(
+        state.diagnostics().all()[0].message,
+        "cannot construct `Meters` here, because its field `0` is private"
+    );
+}
+
+#[test]
+fn struct_update_syntax() {
+    let source_code = "struct Point { x: int32, y: int32 }
+struct Size { width: int32, height: int32 }
+
+fun main(origin: Point, size: Size) {
+    let a = Point { x: 1, ..origin };
+    let b = Point { ..size };
+}";
+    let (state, _) = infer(source_code);
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(error_codes(&state), ["E235"]);
+    assert_eq!(
+        diagnostics[0].labels[0].message,
+        "expected `Point`, found `Size`"
+    );
+}
+
+#[test]
+fn suffixed_numeric_literals() {
+    let source_code = "fun main() {
+    let a = 255u8;
+    let b = 2.5f32;
+    let c = 1f64;
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of(&types, source_code, "255u8"),
+        &primitive(BuiltinSymbolId::Uint8)
+    );
+    assert_eq!(
+        type_of(&types, source_code, "2.5f32"),
+        &primitive(BuiltinSymbolId::Float32)
+    );
+    assert_eq!(
+        type_of(&types, source_code, "1f64"),
+        &primitive(BuiltinSymbolId::Float64)
+    );
+}
+
+#[test]
+fn numeric_literal_out_of_range() {
+    let source_code = "fun main() {
+    let a: uint8 = 300;
+    let b: uint8 = 255;
+    let c = 3000000000;
+    let d = 256u8;
+}";
+    let (state, _) = infer(source_code);
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(error_codes(&state), ["E236", "E236", "E236"]);
+    assert_eq!(diagnostics[0].message, "literal out of range for `uint8`");
+    assert_eq!(
+        diagnostics[0].notes,
+        ["note: the range of `uint8` is `0..=255`"]
+    );
+
+    // Integer literals without suffixes are `int32` by default.
+    assert_eq!(diagnostics[1].message, "literal out of range for `int32`");
+}
+
+#[test]
+fn out_of_range_literal_keeps_original_spelling() {
+    let source_code = "fun main() {
+    let a: uint8 = 0x1_FF;
+    let b = -0x81i8;
+}";
+    let (state, _) = infer(source_code);
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(error_codes(&state), ["E236", "E236"]);
+    assert_eq!(
+        diagnostics[0].labels[0].message,
+        "`0x1_FF` doesn't fit into `uint8`"
+    );
+
+    let start = source_code.find("0x1_FF").unwrap();
+    assert_eq!(
+        diagnostics[0].labels[0].location,
+        Location {
+            filepath: DUMMY_PATH_ID,
+            start: ByteOffset(start),
+            end: ByteOffset(start + "0x1_FF".len()),
+        }
+    );
+    assert_eq!(
+        diagnostics[1].labels[0].message,
+        "`-0x81i8` doesn't fit into `int8`"
+    );
+}
+
+#[test]
+fn negative_numeric_literal_range() {
+    let source_code = "fun main() {
+    let a = -128i8;
+    let b = -129i8;
+    let c = -1u8;
+}";
+    let (state, types) = infer(source_code);
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(error_codes(&state), ["E236", "E236"]);
+    assert_eq!(
+        type_of(&types, source_code, "-128i8"),
+        &primitive(BuiltinSymbolId::Int8)
+    );
+
+    let start = source_code.find("-129i8").unwrap();
+    assert_eq!(
+        diagnostics[0].labels[0].location,
+        Location {
+            filepath: DUMMY_PATH_ID,
+            start: ByteOffset(start),
+            end: ByteOffset(start + "-129i8".len()),
+        }
+    );
+    assert_eq!(diagnostics[1].message, "literal out of range for `uint8`");
+}
+
+#[test]
+fn block_with_tail_expression() {
+    let source_code = "fun main() {
+    let x = { let a = 1u8; a };
+    let y = { let b = 1u8; b; };
+    let z = { return (); };
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of(&types, source_code, "{ let a = 1u8; a }"),
+        &primitive(BuiltinSymbolId::Uint8)
+    );
+    assert_eq!(
+        type_of(&types, source_code, "{ let b = 1u8; b; }"),
+        &Type::Unit
+    );
+    assert_eq!(type_of(&types, source_code, "{ return (); }"), &Type::Never);
+}
+
+#[test]
+fn missing_semicolon_in_the_middle_of_block() {
+    let source_code = "fun main() {
+    let x = { let a = 1u8; a a };
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E014"]);
+}
+
+#[test]
+fn coercion_into_interface_object() {
+    let source_code = "interface ToString {
+    fun to_string(self): String;
+}
+
+struct Meters implements ToString {
+    fun to_string(self): String {}
+}
+
+struct Seconds {}
+
+fun main() {
+    let mut value: dyn ToString = Meters {};
+    value = Meters {};
+    value = Seconds {};
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E215"]);
+
+    let diagnostic = state.diagnostics().all()[0];
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(label(diagnostic.labels[0].location), "Seconds {}");
+    assert_eq!(
+        diagnostic.labels[0].message,
+        "expected `dyn ToString`, found `Seconds`"
+    );
+}
+
+#[test]
+fn struct_in_value_position() {
+    let source_code = "struct Point { x: int32 }
+
+enum Color { Red }
+
+fun foo(p: Point) {}
+
+fun main() {
+    let p = Point;
+    foo(Point);
+    let c = Color.Red;
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E117", "E117"]);
+
+    let diagnostic = state.diagnostics().all()[0];
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(
+        diagnostic.message,
+        "expected a value, found a struct `Point`"
+    );
+    assert_eq!(label(diagnostic.labels[0].location), "Point");
+    assert_eq!(diagnostic.labels[0].message, "not a value");
+    assert_eq!(diagnostic.labels[1].location.start.0, 7);
+    assert_eq!(
+        diagnostic.labels[1].message,
+        "the struct `Point` is defined here"
+    );
+    assert_eq!(
+        diagnostic.notes,
+        ["help: use a struct expression to construct a value, e.g. `Point { ... }`"]
+    );
+}
+
+#[test]
+fn generic_type_alias_expansion() {
+    let source_code = "type Pair[T] = (T, T);
+
+fun main(pair: Pair[int32]) {
+    let (a, b) = pair;
+    let c = (pair, true);
+}";
+    let (state, types) = infer(source_code);
+    let int32 = primitive(BuiltinSymbolId::Int32);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of(&types, source_code, "(pair, true)"),
+        &Type::Tuple {
+            element_types: vec![
+                Type::Tuple {
+                    element_types: vec![int32.clone(), int32]
+                },
+                primitive(BuiltinSymbolId::Bool)
+            ]
+        }
+    );
+}
+
+#[test]
+fn generic_type_alias_expansion_arity_mismatch() {
+    let source_code = "type Pair[T] = (T, T);
+
+fun main(pair: Pair[int32]) {
+    let (a, b, c) = pair;
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E219"]);
+}
+
+#[test]
+fn nested_generic_type_alias_expansion() {
+    let source_code = "type Pair[T] = (T, T);
+type Nested[U] = Pair[Pair[U]];
+
+fun main(nested: Nested[int32]) {
+    let ((a, b), (c, d)) = nested;
+    let ((e, f), (g, h, i)) = nested;
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E219"]);
+}
+
+#[test]
+fn positional_arguments() {
+    let source_code = "fun f(xs: List[uint8], ys: List[String]) {}
+
+fun main() {
+    f([], [ ]);
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of(&types, source_code, "[]"),
+        &list_of(primitive(BuiltinSymbolId::Uint8))
+    );
+    assert_eq!(
+        type_of(&types, source_code, "[ ]"),
+        &list_of(primitive(BuiltinSymbolId::String))
+    );
+}
+
+#[test]
+fn labeled_arguments_in_any_order() {
+    let source_code = "fun f(xs: List[uint8], ys: List[String]) {}
+
+fun main() {
+    f(ys: [], xs: [ ]);
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of(&types, source_code, "[]"),
+        &list_of(primitive(BuiltinSymbolId::String))
+    );
+    assert_eq!(
+        type_of(&types, source_code, "[ ]"),
+        &list_of(primitive(BuiltinSymbolId::Uint8))
+    );
+}
+
+#[test]
+fn positional_and_labeled_arguments() {
+    let source_code = "fun f(xs: List[uint8], ys: List[String], zs: List[bool]) {}
+
+fun main() {
+    f([], zs: [ ], ys: [  ]);
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of(&types, source_code, "[]"),
+        &list_of(primitive(BuiltinSymbolId::Uint8))
+    );
+    assert_eq!(
+        type_of(&types, source_code, "[ ]"),
+        &list_of(primitive(BuiltinSymbolId::Bool))
+    );
+    assert_eq!(
+        type_of(&types, source_code, "[  ]"),
+        &list_of(primitive(BuiltinSymbolId::String))
+    );
+}
+
+#[test]
+fn unknown_argument_label() {
+    let source_code = "fun draw(x: int32, color: String) {}
+
+fun main() {
+    draw(x: 1, colour: \"red\");
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E240"]);
+
+    let diagnostic = state.diagnostics().all()[0];
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(
+        diagnostic.message,
+        "function `draw` has no parameter named `colour`"
+    );
+    assert_eq!(label(diagnostic.labels[0].location), "colour");
+    assert_eq!(diagnostic.notes, ["help: did you mean `color`?"]);
+}
+
+#[test]
+fn argument_passed_more_than_once() {
+    let source_code = "fun draw(x: int32, y: int32) {}
+
+fun main() {
+    draw(x: 1, x: 2);
+    draw(1, x: 2);
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E241", "E241"]);
+
+    let diagnostics = state.diagnostics().all();
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(
+        diagnostics[0].message,
+        "argument for parameter `x` is passed more than once"
+    );
+    assert_eq!(label(diagnostics[0].labels[0].location), "x: 2");
+    assert_eq!(label(diagnostics[0].labels[1].location), "x: 1");
+    assert_eq!(label(diagnostics[1].labels[1].location), "1");
+}
+
+#[test]
+fn positional_argument_after_labeled() {
+    let source_code = "fun draw(x: int32, y: int32) {}
+
+fun main() {
+    draw(x: 1, 2);
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E242"]);
+
+    let diagnostic = state.diagnostics().all()[0];
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(label(diagnostic.labels[0].location), "2");
+    assert_eq!(label(diagnostic.labels[1].location), "x: 1");
+}
+
+#[test]
+fn call_omitting_default_value() {
+    let source_code = "fun greet(name: String, punctuation: String = \"!\"): String { name }
+
+fun main() {
+    let a = greet(\"Alice\");
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_ok());
+    assert_eq!(
+        type_of(&types, source_code, "greet(\"Alice\")"),
+        &primitive(BuiltinSymbolId::String)
+    );
+}
+
+#[test]
+fn call_providing_default_value() {
+    let (state, _) = infer(
+        "fun greet(name: String, punctuation: String = \"!\") {}
+
+fun main() {
+    greet(\"Alice\", \"?\");
+    greet(\"Bob\", punctuation: \"?\");
+    greet(punctuation: \"?\", name: \"Carol\");
+}",
+    );
+
+    assert!(state.diagnostics().is_ok());
+}
+
+#[test]
+fn missing_arguments() {
+    let source_code = "fun greet(name: String, punctuation: String = \"!\") {}
+
+fun main() {
+    greet(punctuation: \"?\");
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E245"]);
+
+    let diagnostic = state.diagnostics().all()[0];
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(
+        diagnostic.message,
+        "missing argument for parameter `name` in the call to `greet`"
+    );
+    assert_eq!(
+        label(diagnostic.labels[0].location),
+        "greet(punctuation: \"?\")"
+    );
+}
+
+#[test]
+fn non_trailing_default_value() {
+    let source_code = "fun greet(punctuation: String = \"!\", name: String) {}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E016"]);
+
+    let diagnostic = state.diagnostics().all()[0];
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(label(diagnostic.labels[0].location), "\"!\"");
+    assert_eq!(label(diagnostic.labels[1].location), "name");
+}
+
+#[test]
+fn default_value_type_mismatch() {
+    let source_code = "fun greet(name: String, times: int32 = \"twice\") {}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E243"]);
+
+    let diagnostic = state.diagnostics().all()[0];
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(label(diagnostic.labels[0].location), "\"twice\"");
+    assert_eq!(
+        diagnostic.labels[0].message,
+        "expected `int32`, found `String`"
+    );
+    assert_eq!(label(diagnostic.labels[1].location), "int32");
+}
+
+#[test]
+fn default_value_referencing_parameter() {
+    let source_code = "fun greet(name: String, greeting: String = name) {}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E244"]);
+
+    let diagnostic = state.diagnostics().all()[0];
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(label(diagnostic.labels[0].location), "name");
+    assert_eq!(diagnostic.labels[0].location.start.0, 43);
+    assert_eq!(label(diagnostic.labels[1].location), "name");
+    assert_eq!(diagnostic.labels[1].location.start.0, 10);
+}
+
+#[test]
+fn spread_argument() {
+    let (state, _) = infer(
+        "fun draw(color: String, x: int32, y: int32) {}
+
+fun main() {
+    let point = (10, 20);
+    draw(\"red\", ..point);
+    draw(..(\"blue\", 1, 2));
+}",
+    );
+
+    assert!(state.diagnostics().is_ok());
+}
+
+#[test]
+fn spread_argument_arity_mismatch() {
+    let source_code = "fun draw(x: int32, y: int32) {}
+
+fun main() {
+    draw(..(1, 2, 3));
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E247"]);
+
+    let diagnostic = state.diagnostics().all()[0];
+
+    assert_eq!(
+        diagnostic.message,
+        "spread tuple has 3 elements but 2 parameters are left"
+    );
+}
+
+#[test]
+fn spread_element_type_mismatch() {
+    let source_code = "fun draw(x: int32, y: int32) {}
+
+fun main() {
+    let point = (1, \"2\");
+    draw(..point);
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E248"]);
+
+    let diagnostic = state.diagnostics().all()[0];
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(
+        diagnostic.labels[0].message,
+        "expected `int32`, found `String` in element 1 of the tuple"
+    );
+    assert_eq!(label(diagnostic.labels[0].location), "point");
+    assert_eq!(label(diagnostic.labels[1].location), "int32");
+    assert_eq!(label(diagnostic.labels[2].location), "point");
+    assert_eq!(diagnostic.labels[2].location.start.0, 54);
+}
+
+#[test]
+fn spread_argument_not_tuple() {
+    let source_code = "fun draw(x: int32, y: int32) {}
+
+fun main() {
+    draw(..\"point\");
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E246"]);
+    assert_eq!(
+        state.diagnostics().all()[0].labels[0].message,
+        "expected a tuple, found `String`"
+    );
+}
+
+#[test]
+fn argument_after_spread() {
+    let source_code = "fun draw(x: int32, y: int32) {}
+
+fun main() {
+    draw(..(1, 2), 3);
+    draw(..(1, 2), ..(3,));
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E249", "E249"]);
+
+    let diagnostics = state.diagnostics().all();
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(diagnostics[0].message, "argument after a spread argument");
+    assert_eq!(label(diagnostics[0].labels[0].location), "3");
+    assert_eq!(label(diagnostics[0].labels[1].location), "..(1, 2)");
+    assert_eq!(
+        diagnostics[1].message,
+        "only one spread argument is allowed in a call"
+    );
+}
+
+#[test]
+fn allowed_casts() {
+    let source_code = "enum Color { Red, Green }
+
+fun main() {
+    let float = 1.5 as int64;
+    let code = 'a' as uint32;
+    let character = 97u32 as char;
+    let flag = true as uint8;
+    let color = Color.Green as int32;
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_ok());
+    assert_eq!(
+        type_of(&types, source_code, "1.5 as int64"),
+        &primitive(BuiltinSymbolId::Int64)
+    );
+    assert_eq!(
+        type_of(&types, source_code, "'a' as uint32"),
+        &primitive(BuiltinSymbolId::Uint32)
+    );
+    assert_eq!(
+        type_of(&types, source_code, "97u32 as char"),
+        &primitive(BuiltinSymbolId::Char)
+    );
+    assert_eq!(
+        type_of(&types, source_code, "true as uint8"),
+        &primitive(BuiltinSymbolId::Uint8)
+    );
+    assert_eq!(
+        type_of(&types, source_code, "Color.Green as int32"),
+        &primitive(BuiltinSymbolId::Int32)
+    );
+}
+
+#[test]
+fn invalid_cast() {
+    let source_code = "fun main() {
+    let a = \"1\" as int32;
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E250"]);
+
+    let diagnostic = state.diagnostics().all()[0];
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(diagnostic.message, "cannot cast `String` as `int32`");
+    assert_eq!(label(diagnostic.labels[0].location), "\"1\" as int32");
+}
+
+#[test]
+fn cast_of_struct_to_interface() {
+    let source_code = "interface ToString {
+    fun to_string(self): String;
+}
+
+struct Meters implements ToString {
+    fun to_string(self): String {}
+}
+
+fun main() {
+    let a = Meters {} as ToString;
+    let b = Meters {} as dyn ToString;
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E250"]);
+
+    let diagnostic = state.diagnostics().all()[0];
+
+    assert_eq!(diagnostic.message, "cannot cast `Meters` as `ToString`");
+    assert_eq!(
+        diagnostic.notes,
+        ["help: cast to the interface object type `dyn ToString` instead"]
+    );
+}
+
+#[test]
+fn arithmetic_operators() {
+    let source_code = "fun main() {
+    let a = 1u8 * 2u8 + 3;
+    let b = 1.5f32 / 2.0;
+    let c = 1 - true;
+}";
+    let (state, types) = infer(source_code);
+
+    assert_eq!(
+        type_of(&types, source_code, "1u8 * 2u8 + 3"),
+        &primitive(BuiltinSymbolId::Uint8)
+    );
+    assert_eq!(
+        type_of(&types, source_code, "1.5f32 / 2.0"),
+        &primitive(BuiltinSymbolId::Float32)
+    );
+    assert_eq!(error_codes(&state), ["E256"]);
+    assert_eq!(
+        state.diagnostics().iter().next().unwrap().message,
+        "cannot apply `-` to `{integer}` and `bool`"
+    );
+}
+
+#[test]
+fn string_concatenation() {
+    let source_code = "fun main() {
+    let a = \"a\" + \"b\";
+    let b = 1 + \"x\";
+    let c = \"a\" - \"b\";
+}";
+    let (state, types) = infer(source_code);
+
+    assert_eq!(
+        type_of(&types, source_code, "\"a\" + \"b\""),
+        &primitive(BuiltinSymbolId::String)
+    );
+    assert_eq!(error_codes(&state), ["E256", "E256"]);
+}
+
+#[test]
+fn comparison_operators() {
+    let source_code = "fun main() {
+    let a = 1 < 2;
+    let b = 'a' >= 'b';
+    let c = \"a\" < \"b\";
+}";
+    let (state, types) = infer(source_code);
+
+    assert_eq!(
+        type_of(&types, source_code, "1 < 2"),
+        &primitive(BuiltinSymbolId::Bool)
+    );
+    assert_eq!(
+        type_of(&types, source_code, "'a' >= 'b'"),
+        &primitive(BuiltinSymbolId::Bool)
+    );
+    assert_eq!(error_codes(&state), ["E256"]);
+}
+
+#[test]
+fn equality_operators() {
+    let source_code = "struct Point { x: int32, y: int32 }
+
+fun same(a: Point, b: Point): bool {
+    a == b
+}
+
+fun main() {
+    let a = \"a\" != \"b\";
+    let b = 1 == \"1\";
+}";
+    let (state, types) = infer(source_code);
+
+    assert_eq!(
+        type_of(&types, source_code, "\"a\" != \"b\""),
+        &primitive(BuiltinSymbolId::Bool)
+    );
+    assert_eq!(error_codes(&state), ["E256"]);
+}
+
+#[test]
+fn logical_operators() {
+    let source_code = "fun main() {
+    let a = (true && !false) || 1 < 2;
+    let b = 1 || true;
+}";
+    let (state, types) = infer(source_code);
+
+    assert_eq!(
+        type_of(&types, source_code, "(true && !false) || 1 < 2"),
+        &primitive(BuiltinSymbolId::Bool)
+    );
+    assert_eq!(error_codes(&state), ["E256"]);
+}
+
+#[test]
+fn bitwise_operators() {
+    let source_code = "fun main() {
+    let a = 1u32 << 2 | 4;
+    let b = 1.0 & 2.0;
+    let c = true >> 1;
+}";
+    let (state, types) = infer(source_code);
+
+    assert_eq!(
+        type_of(&types, source_code, "1u32 << 2 | 4"),
+        &primitive(BuiltinSymbolId::Uint32)
+    );
+    assert_eq!(error_codes(&state), ["E256", "E256"]);
+}
+
+#[test]
+fn binary_operator_labels_point_at_operands() {
+    let source_code = "fun main() {
+    let a = \"x\" +  false;
+}";
+    let (state, _) = infer(source_code);
+
+    let diagnostic = state.diagnostics().iter().next().unwrap();
+
+    assert_eq!(
+        diagnostic.message,
+        "cannot apply `+` to `String` and `bool`"
+    );
+    assert_eq!(
+        diagnostic
+            .labels
+            .iter()
+            .map(|label| &source_code[label.location])
+            .collect::<Vec<_>>(),
+        ["+", "\"x\"", "false"]
+    );
+}
+
+#[test]
+fn mixed_integer_widths() {
+    let source_code = "fun add(a: int32, b: int64): int64 {
+    a + b
+}
+
+fun widen(a: int32, b: int64): int64 {
+    a as int64 + b
+}";
+    let (state, _) = infer(source_code);
+
+    assert_eq!(error_codes(&state), ["E257"]);
+
+    let diagnostic = state.diagnostics().iter().next().unwrap();
+
+    assert_eq!(
+        diagnostic.message,
+        "cannot apply `+` to `int32` and `int64`"
+    );
+    assert_eq!(
+        diagnostic.notes,
+        ["help: convert the right operand with `as int32` or the left one with `as int64`"]
+    );
+}
+
+#[test]
+fn single_index() {
+    let (state, _) = infer(
+        "fun main(pair: (int32, String)) {
+    let a = pair.0;
+    let b = pair.1;
+    let c = (1, 2, 3).2;
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn chained_index() {
+    let (state, _) = infer(
+        "type Pair = (int32, (String, bool));
+
+fun pair(): Pair { (1, (\"a\", true)) }
+
+fun main() {
+    let t = ((1, 2), 3);
+    let a = t.0.1;
+    let b = pair().1.1;
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn out_of_range_index() {
+    let (state, _) = infer(
+        "fun main(pair: (int32, String)) {
+    let a = pair.2;
+    let t = ((1, 2), 3);
+    let b = t.0.5;
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E209", "E209"]);
+}
+
+#[test]
+fn non_tuple_index() {
+    let (state, _) = infer(
+        "struct Point { x: int32, y: int32 }
+
+fun main(x: int32) {
+    let a = x.0;
+    let p = Point { x: 1, y: 2 };
+    let b = p.1;
+    let c = (1, 2).0.0;
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E210", "E210", "E210"]);
+}
+
+#[test]
+fn unknown_types_are_not_checked() {
+    let (state, _) = infer(
+        "fun main[T](x: T) {
+    let a = x.0;
+    let b = foo().1;
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn lambda_types_are_used_in_calls() {
+    let source_code = "fun main() {
+    let double = |x: int32| x * 2;
+    let y = double(1);
+}";
+    let (state, types) = infer(source_code);
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        type_of(&types, source_code, "|x: int32| x * 2"),
+        &Type::Function {
+            parameter_types: vec![primitive(BuiltinSymbolId::Int32)],
+            return_type: Box::new(primitive(BuiltinSymbolId::Int32)),
+        }
+    );
+    assert_eq!(
+        type_of(&types, source_code, "double(1)"),
+        &primitive(BuiltinSymbolId::Int32)
+    );
+}
@@ -0,0 +1,173 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{
+    symbol::BuiltinSymbolId,
+    ty::{Type, TypeConstructor},
+    PackageData, State, Symbol,
+};
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::{DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::{
+    expression_analysis::infer_lambda_types::InferLambdaTypes,
+    resolution::collect_definitions::CollectDefinitions,
+};
+
+use crate::error_codes;
+
+fn infer(source_code: &str) -> (State, Vec<Type>) {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    let types = InferLambdaTypes::run_all(&mut state, &hir);
+
+    (state, sorted_by_location(types))
+}
+
+fn sorted_by_location(types: FxHashMap<Location, Type>) -> Vec<Type> {
+    let mut types = types.into_iter().collect::<Vec<_>>();
+    types.sort_by_key(|(location, _)| location.start);
+    types.into_iter().map(|(_, ty)| ty).collect()
+}
+
+fn int32() -> Type {
+    Type::Constructor(TypeConstructor::new(
+        Symbol::BuiltinSymbol(BuiltinSymbolId::Int32),
+        vec![],
+    ))
+}
+
+#[test]
+fn infer_from_parameter_type() {
+    let (state, types) = infer(
+        "fun apply(f: fun(int32): int32, x: int32): int32 { f(x) }
+
+fun main() {
+    apply(|x| x + 1, 2);
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        types,
+        [Type::Function {
+            parameter_types: vec![int32()],
+            return_type: Box::new(int32()),
+        }]
+    );
+}
+
+#[test]
+fn infer_through_generic_parameter() {
+    let (state, types) = infer(
+        "fun map[T](x: T, f: fun(T): T): T { f(x) }
+
+fun main() {
+    map(1, |x: int32| x);
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        types,
+        [Type::Function {
+            parameter_types: vec![int32()],
+            return_type: Box::new(int32()),
+        }]
+    );
+}
+
+#[test]
+fn parameter_count_mismatch() {
+    let (state, _) = infer(
+        "fun apply(f: fun(int32): int32, x: int32): int32 { f(x) }
+
+fun main() {
+    apply(|x, y| x, 2);
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E208"]);
+}
+
+#[test]
+fn parameter_type_mismatch() {
+    let (state, _) = infer(
+        "fun apply(f: fun(int32): int32, x: int32): int32 { f(x) }
+
+fun main() {
+    apply(|x: String| 1, 2);
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E208"]);
+}
+
+#[test]
+fn return_type_mismatch() {
+    let (state, _) = infer(
+        "fun apply(f: fun(int32): int32, x: int32): int32 { f(x) }
+
+fun main() {
+    apply(|x| \"s\", 2);
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E208"]);
+    assert_eq!(
+        state.diagnostics().all()[0].labels[0].message,
+        "expected `fun(int32): int32`, found `fun(_): String`"
+    );
+}
+
+#[test]
+fn infer_return_type_from_body() {
+    let (state, types) = infer(
+        "fun apply(f: fun(int32): int32, x: int32): int32 { f(x) }
+
+fun main() {
+    apply(|x| { let y = x; y * 2 }, 2);
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        types,
+        [Type::Function {
+            parameter_types: vec![int32()],
+            return_type: Box::new(int32()),
+        }]
+    );
+}
+
+#[test]
+fn local_callee_shadows_function() {
+    let (state, types) = infer(
+        "fun apply(f: fun(String): String) {}
+
+fun main(apply: fun(fun(int32): int32)) {
+    apply(|x| x);
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        types,
+        [Type::Function {
+            parameter_types: vec![int32()],
+            return_type: Box::new(int32()),
+        }]
+    );
+}
@@ -0,0 +1,178 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{
+    symbol::BuiltinSymbolId,
+    ty::{Type, TypeConstructor},
+    PackageData, State, Symbol,
+};
+use stellar_interner::{IdentifierId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_thir::body::{Body, Expression, ExpressionKind, Statement};
+use stellar_typechecker::{
+    expression_analysis::lower_body::{lower_all_bodies, lower_body},
+    resolution::collect_definitions::CollectDefinitions,
+};
+
+/// Lowers the body of a function with a given name.
+fn lower(source_code: &str, function: &str) -> (State, Option<Body>) {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    let module = parse_result.module();
+    package.set_root_module(state.db_mut(), module);
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+
+    let Symbol::Function(function) =
+        module.module_item_symbol(state.db(), IdentifierId::from(function))
+    else {
+        panic!("`{function}` is not a function");
+    };
+
+    let body = lower_body(&mut state, &hir, function);
+
+    (state, body)
+}
+
+/// Returns the value of the `let` statement with a given index.
+fn let_value(body: &Body, idx: usize) -> &Expression {
+    let Statement::Let { value, .. } = &body.statements[idx] else {
+        panic!("expected a let statement");
+    };
+
+    value
+}
+
+fn primitive(builtin: BuiltinSymbolId) -> Type {
+    Type::Constructor(TypeConstructor::new(Symbol::BuiltinSymbol(builtin), vec![]))
+}
+
+#[test]
+fn integer_literal() {
+    let (_, body) = lower(
+        "fun main() {
+    let a = 1;
+    let b = 2.5;
+    let c: uint8 = 3;
+}",
+        "main",
+    );
+    let body = body.unwrap();
+
+    let a = let_value(&body, 0);
+    assert!(matches!(a.kind, ExpressionKind::Literal { .. }));
+    assert_eq!(a.ty, primitive(BuiltinSymbolId::Int32));
+
+    assert_eq!(let_value(&body, 1).ty, primitive(BuiltinSymbolId::Float64));
+    assert_eq!(let_value(&body, 2).ty, primitive(BuiltinSymbolId::Uint8));
+    assert_eq!(body.return_type, Type::Unit);
+}
+
+#[test]
+fn call_has_return_type_of_callee() {
+    let (state, body) = lower(
+        "fun foo(x: int64): String { \"\" }
+
+fun main() {
+    let s = foo(1);
+}",
+        "main",
+    );
+    let body = body.unwrap();
+
+    let call = let_value(&body, 0);
+    assert_eq!(call.ty, primitive(BuiltinSymbolId::String));
+
+    let ExpressionKind::Call { callee, arguments } = &call.kind else {
+        panic!("expected a call");
+    };
+
+    let ExpressionKind::Symbol {
+        symbol: Symbol::Function(function),
+    } = callee.kind
+    else {
+        panic!("expected a resolved function");
+    };
+    assert_eq!(
+        function.signature(state.db()).name(state.db()).id,
+        IdentifierId::from("foo")
+    );
+
+    // The argument is inferred from the parameter type.
+    assert_eq!(arguments[0].value.ty, primitive(BuiltinSymbolId::Int64));
+}
+
+#[test]
+fn locals() {
+    let (_, body) = lower(
+        "fun main(x: bool) {
+    let y = x;
+    y
+}",
+        "main",
+    );
+    let body = body.unwrap();
+
+    let y = let_value(&body, 0);
+    assert!(matches!(y.kind, ExpressionKind::Local { .. }));
+    assert_eq!(y.ty, primitive(BuiltinSymbolId::Bool));
+
+    let Statement::Expression { expression, .. } = &body.statements[1] else {
+        panic!("expected an expression statement");
+    };
+    assert_eq!(expression.ty, primitive(BuiltinSymbolId::Bool));
+}
+
+#[test]
+fn function_without_body() {
+    let (_, body) = lower("fun foo();", "foo");
+
+    assert_eq!(body, None);
+}
+
+#[test]
+fn all_bodies() {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        "fun a() {} fun b() {} struct C {}",
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+    CollectDefinitions::run_all(&mut state, &hir);
+
+    assert_eq!(lower_all_bodies(&mut state, &hir).len(), 2);
+}
+
+#[test]
+fn block_types() {
+    let (_, body) = lower(
+        "fun main(c: bool) {
+    let a = { let b = 1; b };
+    let c = { 1; };
+    let d = { if c { return (); } 2 };
+    let e = { loop {} };
+}",
+        "main",
+    );
+    let body = body.unwrap();
+
+    assert_eq!(let_value(&body, 0).ty, primitive(BuiltinSymbolId::Int32));
+    assert_eq!(let_value(&body, 1).ty, Type::Unit);
+    assert_eq!(let_value(&body, 2).ty, primitive(BuiltinSymbolId::Int32));
+    assert_eq!(let_value(&body, 3).ty, Type::Never);
+}
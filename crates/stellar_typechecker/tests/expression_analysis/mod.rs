@@ -0,0 +1,9 @@
+mod capture_analysis;
+mod check_assignments;
+mod check_break_and_continue;
+mod check_let_patterns;
+mod check_match_exhaustiveness;
+mod infer_expression_types;
+mod infer_lambda_types;
+mod lower_body;
+mod resolve_enum_items;
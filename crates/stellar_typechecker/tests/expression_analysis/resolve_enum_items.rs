@@ -0,0 +1,250 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{ty::Type, PackageData, State};
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::{IdentifierId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::{
+    expression_analysis::resolve_enum_items::{ResolveEnumItems, ResolvedEnumItem},
+    resolution::collect_definitions::CollectDefinitions,
+};
+
+use crate::error_codes;
+
+fn resolve(source_code: &str) -> (State, FxHashMap<Location, ResolvedEnumItem>) {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    let resolved = ResolveEnumItems::run_all(&mut state, &hir);
+
+    (state, resolved)
+}
+
+fn resolved_item_names(
+    state: &State,
+    resolved: &FxHashMap<Location, ResolvedEnumItem>,
+) -> Vec<String> {
+    let mut resolved = resolved.iter().collect::<Vec<_>>();
+    resolved.sort_by_key(|(location, _)| location.start);
+
+    resolved
+        .into_iter()
+        .map(|(_, resolved)| resolved.item.name(state.db()).id.to_string())
+        .collect()
+}
+
+#[test]
+fn unit_items() {
+    let (state, resolved) = resolve(
+        "enum Color { Red, Green }
+
+fun main() {
+    let color = Color.Red;
+    match color {
+        Color.Green -> {},
+        _ -> {},
+    };
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(resolved_item_names(&state, &resolved), ["Red", "Green"]);
+    assert!(resolved
+        .values()
+        .all(|resolved| matches!(&resolved.ty, Type::Constructor(constructor) if constructor.arguments.is_empty())));
+}
+
+#[test]
+fn unit_item_with_arguments() {
+    let (state, _) = resolve(
+        "enum Color { Red, Green }
+
+fun main() {
+    let color = Color.Red(1);
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E203"]);
+}
+
+#[test]
+fn tuple_like_items() {
+    let (state, resolved) = resolve(
+        "enum Option[T] { Some(T), None }
+
+fun main() {
+    let a = Option.Some(1);
+    let b = Option[int32].None;
+    match a {
+        Option.Some(x) -> {},
+        Option.None -> {},
+    };
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(
+        resolved_item_names(&state, &resolved),
+        ["Some", "None", "Some", "None"]
+    );
+
+    for resolved in resolved.values() {
+        let Type::Constructor(constructor) = &resolved.ty else {
+            panic!("expected type constructor");
+        };
+
+        assert_eq!(constructor.arguments.len(), 1);
+    }
+}
+
+#[test]
+fn tuple_like_item_arity_mismatch() {
+    let (state, _) = resolve(
+        "enum Option[T] { Some(T), None }
+
+fun main() {
+    let a = Option.Some(1, 2);
+    let b = Option.Some;
+    match a {
+        Option.Some(x, y) -> {},
+        Option.Some(..) -> {},
+        _ -> {},
+    };
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E205", "E204", "E205"]);
+}
+
+#[test]
+fn unknown_item() {
+    let (state, resolved) = resolve(
+        "enum Option[T] { Some(T), None }
+
+fun main() {
+    let a = Option.Nome;
+}",
+    );
+
+    assert!(resolved.is_empty());
+    assert_eq!(error_codes(&state), ["E109"]);
+    assert_eq!(
+        state.diagnostics().all()[0].notes,
+        ["help: did you mean `None`?"]
+    );
+}
+
+#[test]
+fn local_variables_are_not_enum_paths() {
+    let (state, resolved) = resolve(
+        "enum Option[T] { Some(T), None }
+
+fun main(Option: int32) {
+    let a = Option.Nome;
+}",
+    );
+
+    assert!(resolved.is_empty());
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn items_through_type_alias() {
+    let (state, resolved) = resolve(
+        "enum Option[T] { Some(T), None }
+
+type Opt[T] = Option[T];
+
+fun main() {
+    let a = Opt.Some(1);
+    match a {
+        Opt.None -> {},
+        _ -> {},
+    };
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(resolved_item_names(&state, &resolved), ["Some", "None"]);
+
+    let db = state.db();
+
+    assert!(resolved.values().all(|resolved| {
+        resolved.item.enum_(db).signature(db).name(db).id == IdentifierId::from("Option")
+    }));
+}
+
+#[test]
+fn items_through_alias_of_alias() {
+    let (state, resolved) = resolve(
+        "enum Option[T] { Some(T), None }
+
+type Opt[T] = Option[T];
+type Maybe[T] = Opt[T];
+
+fun main() {
+    let a = Maybe.Some(1);
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(resolved_item_names(&state, &resolved), ["Some"]);
+}
+
+#[test]
+fn unknown_item_through_alias_of_alias() {
+    let (state, resolved) = resolve(
+        "enum Option[T] { Some(T), None }
+
+type Opt[T] = Option[T];
+type Maybe[T] = Opt[T];
+
+fun main() {
+    let a = Maybe.Nome;
+}",
+    );
+
+    assert!(resolved.is_empty());
+    assert_eq!(error_codes(&state), ["E109"]);
+
+    let diagnostic = &state.diagnostics().all()[0];
+
+    assert_eq!(
+        diagnostic.message,
+        "failed to resolve enum item `Nome` in `Maybe`, an alias of enum `Option`"
+    );
+    assert_eq!(
+        diagnostic.notes,
+        [
+            "note: alias chain: `Maybe` -> `Opt` -> `Option`",
+            "help: did you mean `None`?"
+        ]
+    );
+}
+
+#[test]
+fn cyclic_aliases_are_not_enum_paths() {
+    let (state, resolved) = resolve(
+        "type A = B;
+type B = A;
+
+fun main() {
+    let a = A.Some;
+}",
+    );
+
+    assert!(resolved.is_empty());
+    assert!(state.diagnostics().is_empty());
+}
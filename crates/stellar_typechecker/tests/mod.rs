@@ -1,2 +1,14 @@
+use stellar_database::State;
+
+mod expression_analysis;
 mod resolution;
 mod signature_analysis;
+
+/// Returns codes of all diagnostics reported so far.
+fn error_codes(state: &State) -> Vec<&str> {
+    state
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.code.as_deref().unwrap())
+        .collect()
+}
@@ -0,0 +1,152 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_interner::{IdentifierId, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::resolution::{
+    check_dead_code::CheckDeadCode, check_type_paths::CheckTypePaths,
+    collect_definitions::CollectDefinitions, resolve_imports::ResolveImports,
+};
+
+fn check(source_code: &str) -> State {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("app"), DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("app").into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    ResolveImports::run_all(&mut state, &hir);
+    CheckTypePaths::run_all(&mut state, &hir);
+    CheckDeadCode::run_all(&mut state, &hir);
+
+    state
+}
+
+fn messages(state: &State) -> Vec<&str> {
+    state
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect()
+}
+
+#[test]
+fn orphan_private_items() {
+    let state = check(
+        "struct Orphan {}
+enum Color { Red }
+interface Shape {}
+type Ints = List[int32];
+
+fun helper() {}
+
+fun main() {}",
+    );
+
+    assert_eq!(
+        messages(&state),
+        [
+            "struct `Orphan` is never used",
+            "enum `Color` is never used",
+            "interface `Shape` is never used",
+            "type alias `Ints` is never used",
+            "function `helper` is never used",
+        ]
+    );
+    assert!(state
+        .diagnostics()
+        .iter()
+        .all(|diagnostic| diagnostic.code.as_deref() == Some("W014")));
+}
+
+#[test]
+fn items_reachable_from_public_items() {
+    let state = check(
+        "struct Point { x: int32 }
+struct Origin {}
+
+pub fun distance(a: Point, b: Point): int32 {
+    a.x - b.x
+}
+
+fun origin(): Origin {
+    Origin {}
+}",
+    );
+
+    // `Origin` is used by `origin` only, which is never used itself.
+    assert_eq!(
+        messages(&state),
+        [
+            "struct `Origin` is never used",
+            "function `origin` is never used"
+        ]
+    );
+}
+
+#[test]
+fn items_reachable_from_main() {
+    let state = check(
+        "enum Color { Red, Green }
+struct Counter { value: int32 }
+
+fun increment(counter: Counter): int32 {
+    counter.value + 1
+}
+
+fun is_red(color: Color): bool {
+    match color {
+        Color.Red -> true,
+        _ -> false,
+    }
+}
+
+fun main() {
+    let counter = Counter { value: 1 };
+    increment(counter);
+    is_red(Color.Green);
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn local_variables_shadow_items() {
+    let state = check(
+        "fun helper() {}
+
+fun main() {
+    let helper = 1;
+    helper;
+}",
+    );
+
+    assert_eq!(messages(&state), ["function `helper` is never used"]);
+}
+
+#[test]
+fn allow_dead_code_attribute() {
+    let state = check(
+        "#[allow(dead_code)]
+struct Unused {}
+
+#[allow(unused, dead_code)]
+fun helper() {}
+
+#[allow(unused)]
+fun other_helper() {}
+
+fun main() {}",
+    );
+
+    assert_eq!(messages(&state), ["function `other_helper` is never used"]);
+}
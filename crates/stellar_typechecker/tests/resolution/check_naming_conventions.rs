@@ -0,0 +1,178 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_interner::{IdentifierId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::resolution::{
+    check_naming_conventions::CheckNamingConventions, collect_definitions::CollectDefinitions,
+};
+
+fn check_with_state(mut state: State, module_name: IdentifierId, source_code: &str) -> State {
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        module_name.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    CheckNamingConventions::run_all(&mut state, &hir);
+
+    state
+}
+
+fn check(source_code: &str) -> State {
+    check_with_state(State::new(), IdentifierId::from("test"), source_code)
+}
+
+/// Returns messages and suggested replacements of reported diagnostics.
+fn warnings(state: &State) -> Vec<(&str, &str)> {
+    state
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| {
+            (
+                diagnostic.message.as_str(),
+                diagnostic.suggestions[0].replacement.as_str(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn types() {
+    let state = check(
+        "struct Point {}
+struct my_point {}
+enum Color {}
+enum color_kind {}
+interface ToString {}
+interface to_string {}
+struct Pair(int32, int32);
+struct pair_of_strings(String, String);
+type Points = List[Point];
+type point_list = List[Point];",
+    );
+
+    assert_eq!(
+        warnings(&state),
+        vec![
+            (
+                "struct `my_point` should have an upper camel case name",
+                "MyPoint"
+            ),
+            (
+                "enum `color_kind` should have an upper camel case name",
+                "ColorKind"
+            ),
+            (
+                "interface `to_string` should have an upper camel case name",
+                "ToString"
+            ),
+            (
+                "tuple-like struct `pair_of_strings` should have an upper camel case name",
+                "PairOfStrings"
+            ),
+            (
+                "type alias `point_list` should have an upper camel case name",
+                "PointList"
+            ),
+        ]
+    );
+    assert!(state.diagnostics().is_ok());
+}
+
+#[test]
+fn acronyms() {
+    let state = check(
+        "struct HTTPServer {}
+struct IO {}
+struct Http_Server {}",
+    );
+
+    assert_eq!(
+        warnings(&state),
+        vec![(
+            "struct `Http_Server` should have an upper camel case name",
+            "HttpServer"
+        )]
+    );
+}
+
+#[test]
+fn functions_methods_and_fields() {
+    let state = check(
+        "fun read_file() {}
+fun readFile() {}
+fun _unused() {}
+struct Point {
+    x: int32,
+    yCoordinate: int32,
+
+    fun to_string(self): String {}
+    fun toString(self): String {}
+}
+interface Parser {
+    fun parseHTTPRequest(self);
+}",
+    );
+
+    assert_eq!(
+        warnings(&state),
+        vec![
+            (
+                "function `readFile` should have a snake case name",
+                "read_file"
+            ),
+            (
+                "field `yCoordinate` should have a snake case name",
+                "y_coordinate"
+            ),
+            (
+                "method `toString` should have a snake case name",
+                "to_string"
+            ),
+            (
+                "method `parseHTTPRequest` should have a snake case name",
+                "parse_http_request"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn module_name() {
+    let state = check_with_state(State::new(), IdentifierId::from("MyModule"), "");
+
+    assert_eq!(
+        warnings(&state),
+        vec![(
+            "module `MyModule` should have a snake case name",
+            "my_module"
+        )]
+    );
+}
+
+#[test]
+fn allowed_warnings() {
+    let mut state = State::new();
+    state.diagnostics_mut().allow("W006");
+
+    let state = check_with_state(
+        state,
+        IdentifierId::from("test"),
+        "fun readFile() {}\nstruct my_point {}",
+    );
+
+    assert_eq!(
+        warnings(&state),
+        vec![(
+            "struct `my_point` should have an upper camel case name",
+            "MyPoint"
+        )]
+    );
+}
@@ -0,0 +1,232 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_filesystem::location::Location;
+use stellar_interner::{IdentifierId, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::resolution::{
+    check_type_paths::CheckTypePaths, collect_definitions::CollectDefinitions,
+    resolve_imports::ResolveImports,
+};
+
+fn check(source_code: &str) -> State {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("app"), DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("app").into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    ResolveImports::run_all(&mut state, &hir);
+    CheckTypePaths::run_all(&mut state, &hir);
+
+    state
+}
+
+fn messages(state: &State) -> Vec<&str> {
+    state
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect()
+}
+
+#[test]
+fn types() {
+    let state = check(
+        "struct Point { x: int32 }
+enum Option[T] { Some(T), None }
+type Points = List[Point];
+
+fun foo[T](x: T, y: Option[Points]) {
+    let z: (Point, fun(T): T) = (x, y);
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn function_in_type_position() {
+    let state = check(
+        "fun foo() {}
+
+fun bar(x: foo): List[foo] {}",
+    );
+
+    assert_eq!(
+        messages(&state),
+        [
+            "expected a type, found a function `foo`",
+            "expected a type, found a function `foo`"
+        ]
+    );
+    assert_eq!(
+        state.diagnostics().all()[0].labels[1].message,
+        "the function `foo` is defined here"
+    );
+    assert_eq!(
+        state.diagnostics().all()[0].notes,
+        ["note: functions can't be used as types"]
+    );
+}
+
+#[test]
+fn enum_item_in_type_position() {
+    let state = check(
+        "enum Option[T] { Some(T), None }
+
+fun foo() {
+    let x: Option.None = 1;
+}",
+    );
+
+    assert_eq!(
+        messages(&state),
+        ["expected a type, found an enum item `None`"]
+    );
+}
+
+#[test]
+fn generic_parameter_shadows_function() {
+    let state = check(
+        "fun T() {}
+
+fun foo[T](x: T) {}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn deprecated_types() {
+    let state = check(
+        "#[deprecated]
+struct Node { next: Option[Node] }
+
+enum Option[T] { Some(T), None }
+
+fun foo(node: Node) {}",
+    );
+
+    assert_eq!(messages(&state), ["use of deprecated struct `Node`"]);
+}
+
+#[test]
+fn type_alias_arity_mismatch() {
+    let source_code = "type Pair[T] = (T, T);
+type Map[K, V = int32] = List[(K, V)];
+
+fun foo(a: Pair[int32, int32], b: Pair, c: Map[int32], d: Map[int32, int32]) {}";
+    let state = check(source_code);
+
+    assert_eq!(
+        messages(&state),
+        [
+            "type alias `Pair` takes 1 generic argument but 2 were supplied",
+            "type alias `Pair` takes 1 generic argument but 0 were supplied"
+        ]
+    );
+
+    let diagnostic = state.diagnostics().all()[0];
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(diagnostic.code.as_deref(), Some("E239"));
+    assert_eq!(label(diagnostic.labels[0].location), "int32");
+    assert_eq!(
+        diagnostic.labels[0].location.start.0,
+        source_code.find("int32, int32]").unwrap() + "int32, ".len()
+    );
+
+    let diagnostic = state.diagnostics().all()[1];
+
+    assert_eq!(label(diagnostic.labels[0].location), "Pair");
+    assert_eq!(label(diagnostic.labels[1].location), "Pair");
+}
+
+fn notes(state: &State) -> Vec<&str> {
+    state
+        .diagnostics()
+        .iter()
+        .flat_map(|diagnostic| &diagnostic.notes)
+        .map(String::as_str)
+        .collect()
+}
+
+#[test]
+fn typo_in_generic_parameter() {
+    let state = check("fun first[Element](items: List[Elemnt]): Element {}");
+
+    assert_eq!(messages(&state), ["failed to resolve the name `Elemnt`"]);
+    assert_eq!(notes(&state), ["help: did you mean `Element`?"]);
+}
+
+#[test]
+fn typo_in_imported_struct() {
+    let state = check(
+        "import app.geometry.Point;
+
+module geometry {
+    pub struct Point {}
+}
+
+fun origin(): Pont {}",
+    );
+
+    assert_eq!(messages(&state), ["failed to resolve the name `Pont`"]);
+    assert_eq!(notes(&state), ["help: did you mean `Point`?"]);
+}
+
+#[test]
+fn unrelated_type_name() {
+    let state = check(
+        "struct Point {}
+
+fun origin(): Vector {}",
+    );
+
+    assert_eq!(messages(&state), ["failed to resolve the name `Vector`"]);
+    assert!(notes(&state).is_empty());
+}
+
+#[test]
+fn typo_in_middle_segment() {
+    let state = check(
+        "module shapes {
+    pub module round {
+        pub struct Circle {}
+    }
+
+    pub module square {}
+}
+
+fun unit(): shapes.rund.Circle {}",
+    );
+
+    assert_eq!(messages(&state), ["failed to resolve the name `rund`"]);
+    assert_eq!(notes(&state), ["help: did you mean `round`?"]);
+}
+
+#[test]
+fn suggestions_are_ordered_by_distance_and_name() {
+    let state = check(
+        "struct Sector {}
+struct Vector {}
+struct Vectors {}
+struct Victor {}
+
+fun direction(): Vectorz {}",
+    );
+
+    assert_eq!(
+        notes(&state),
+        ["help: did you mean `Vector`, `Vectors`, or `Sector`?"]
+    );
+}
@@ -1,8 +1,16 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use stellar_ast::Visibility;
 use stellar_ast_lowering::LowerToHir;
-use stellar_database::{PackageData, State};
+use stellar_database::{Config, PackageData, Path, State};
+use stellar_filesystem::location::{ByteOffset, Location};
 use stellar_interner::{IdentifierId, PathId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
 use stellar_parser::parse_module;
 use stellar_typechecker::resolution::collect_definitions::CollectDefinitions;
+use tracing::{span, Event, Metadata, Subscriber};
 
 #[test]
 fn test_enum() {
@@ -14,7 +22,7 @@ fn test_enum() {
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
@@ -40,7 +48,7 @@ fn test_duplicate_definition() {
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
@@ -53,6 +61,301 @@ fn test_duplicate_definition() {
     assert!(state.diagnostics().is_fatal());
 }
 
+#[test]
+fn test_duplicate_definition_of_different_kinds() {
+    let mut state = State::new();
+    let filepath = PathId::from("test.sr");
+    let source_code = "struct A {}\nfun A() {}";
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        filepath,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+
+    let diagnostic = state.diagnostics().all()[0];
+
+    assert_eq!(
+        diagnostic.message,
+        "the name `A` is defined multiple times: previously as a struct, now as a function"
+    );
+    assert_eq!(
+        diagnostic.labels[0].message,
+        "`A` redefined here as a function"
+    );
+    assert_eq!(
+        diagnostic.labels[0].location,
+        Location {
+            filepath,
+            start: ByteOffset(16),
+            end: ByteOffset(17)
+        }
+    );
+    assert_eq!(
+        diagnostic.labels[1].message,
+        "previous definition of the struct `A` here"
+    );
+    assert_eq!(
+        diagnostic.labels[1].location,
+        Location {
+            filepath,
+            start: ByteOffset(7),
+            end: ByteOffset(8)
+        }
+    );
+}
+
+#[test]
+fn test_duplicate_definition_of_enum_and_interface() {
+    let mut state = State::new();
+    let filepath = PathId::from("test.sr");
+    let source_code = "enum Shape {}\ninterface Shape {}";
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        filepath,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+
+    let diagnostic = state.diagnostics().all()[0];
+
+    assert_eq!(
+        diagnostic.message,
+        "the name `Shape` is defined multiple times: previously as an enum, now as an interface"
+    );
+    assert_eq!(
+        diagnostic.labels[1].message,
+        "previous definition of the enum `Shape` here"
+    );
+}
+
+#[test]
+fn test_definition_conflicting_with_submodule() {
+    let mut state = State::new();
+    let filepath = PathId::from("a/package.sr");
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let submodule = parse_module(
+        &mut state,
+        package,
+        Path::new(vec![IdentifierId::from("a"), IdentifierId::from("util")]),
+        PathId::from("a/util.sr"),
+        "",
+    );
+    let root = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("a").into(),
+        filepath,
+        "struct util {}",
+    );
+    let root_module = root.module();
+
+    package.set_root_module(state.db_mut(), root_module);
+    root_module.add_submodule(state.db_mut(), submodule.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "the name `util` is defined both as a submodule and as a struct"
+    );
+    assert_eq!(
+        diagnostics[0].labels[0].location,
+        Location {
+            filepath,
+            start: ByteOffset(7),
+            end: ByteOffset(11)
+        }
+    );
+    assert_eq!(
+        diagnostics[0].notes,
+        ["note: the submodule `util` is defined in `a/util.sr`"]
+    );
+    assert!(root_module
+        .module_item_symbol_or_none(state.db(), IdentifierId::from("util"))
+        .is_none());
+}
+
+#[test]
+fn test_inline_module_conflicting_with_submodule() {
+    let mut state = State::new();
+    let filepath = PathId::from("a/package.sr");
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let submodule = parse_module(
+        &mut state,
+        package,
+        Path::new(vec![IdentifierId::from("a"), IdentifierId::from("util")]),
+        PathId::from("a/util.sr"),
+        "fun foo() {}",
+    );
+    let root = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("a").into(),
+        filepath,
+        "module util { fun bar() {} }",
+    );
+    let (root_module, submodule_module) = (root.module(), submodule.module());
+
+    package.set_root_module(state.db_mut(), root_module);
+    root_module.add_submodule(state.db_mut(), submodule_module);
+
+    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "the module `util` is defined multiple times"
+    );
+    assert_eq!(
+        diagnostics[0].labels[0].location,
+        Location {
+            filepath,
+            start: ByteOffset(7),
+            end: ByteOffset(11)
+        }
+    );
+    assert_eq!(
+        diagnostics[0].notes,
+        ["note: the module `util` is already defined in `a/util.sr`"]
+    );
+    assert_eq!(
+        root_module.submodule(state.db(), IdentifierId::from("util")),
+        Some(submodule_module)
+    );
+}
+
+#[test]
+fn test_duplicate_definition_is_not_allocated() {
+    let mut state = State::new();
+    let source_code = "fun f() {}\nfun f() {}\nenum E { A, A }\nenum E { B }";
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    let module = parse_result.module();
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+
+    let db = state.db();
+    let f = module.symbol(db, IdentifierId::from("f")).to_function();
+
+    assert_eq!(f.signature(db).name(db).location.start.0, 4);
+
+    let enum_ = module.symbol(db, IdentifierId::from("E")).to_enum();
+
+    assert!(enum_.item(db, IdentifierId::from("A")).is_some());
+    assert!(enum_.item(db, IdentifierId::from("B")).is_none());
+
+    let stats = db.stats();
+
+    assert_eq!(stats.storage("functions").unwrap().count, 1);
+    assert_eq!(stats.storage("enums").unwrap().count, 1);
+    assert_eq!(stats.storage("enum items").unwrap().count, 1);
+    assert_eq!(stats.storage("signatures").unwrap().count, 2);
+    assert_eq!(state.diagnostics().len(), 3);
+}
+
+#[test]
+fn test_items_removed_by_cfg_are_not_defined() {
+    let mut state = State::new();
+    let source_code = "#[cfg(windows)] fun f() {}\n#[cfg(not(windows))] fun f() {}";
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    let module = parse_result.module();
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+
+    let db = state.db();
+    let f = module.symbol(db, IdentifierId::from("f")).to_function();
+
+    assert_eq!(f.signature(db).name(db).location.start.0, 52);
+    assert!(state.diagnostics().is_ok());
+}
+
+#[test]
+fn test_deprecated_attribute() {
+    let mut state = State::new();
+    let source_code = "#[deprecated(\"use g\")] fun f() {}\n#[deprecated(soon)] fun g() {}";
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    let module = parse_result.module();
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+
+    let db = state.db();
+    let f = module.symbol(db, IdentifierId::from("f"));
+    let g = module.symbol(db, IdentifierId::from("g"));
+
+    assert_eq!(
+        f.deprecation(db)
+            .and_then(|deprecation| deprecation.note.as_deref()),
+        Some("use g")
+    );
+    assert!(g.deprecation(db).is_none());
+
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code.as_deref(), Some("E223"));
+    assert_eq!(diagnostics[0].labels[0].location.start.0, 34);
+}
+
 #[test]
 fn test_enum_items() {
     let mut state = State::new();
@@ -63,7 +366,7 @@ fn test_enum_items() {
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
@@ -96,7 +399,7 @@ fn duplicate_enum_item_definitions() {
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
@@ -109,6 +412,48 @@ fn duplicate_enum_item_definitions() {
     assert!(state.diagnostics().is_fatal());
 }
 
+#[test]
+fn enum_method_conflicting_with_item() {
+    let mut state = State::new();
+    let filepath = PathId::from("test.sr");
+    let source_code = "enum Shape { Circle, Square\n fun Circle() {}\n fun area() {} }";
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        filepath,
+        source_code,
+    );
+    let module = parse_result.module();
+    package.set_root_module(state.db_mut(), module);
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code.as_deref(), Some("E119"));
+    assert_eq!(
+        diagnostics[0].labels[0].location,
+        Location {
+            filepath,
+            start: ByteOffset(33),
+            end: ByteOffset(39),
+        }
+    );
+
+    let db = state.db();
+    let enum_ = module.symbol(db, IdentifierId::from("Shape")).to_enum();
+
+    assert!(enum_.item(db, IdentifierId::from("Circle")).is_some());
+    assert_eq!(enum_.method(db, IdentifierId::from("Circle")), None);
+    assert!(enum_.method(db, IdentifierId::from("area")).is_some());
+}
+
 #[test]
 fn test_function() {
     let mut state = State::new();
@@ -119,7 +464,7 @@ fn test_function() {
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
@@ -146,7 +491,7 @@ fn test_struct() {
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
@@ -173,7 +518,7 @@ fn test_interface() {
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
@@ -200,7 +545,7 @@ fn test_type_alias() {
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
@@ -216,3 +561,201 @@ fn test_type_alias() {
         .is_type_alias());
     assert!(state.diagnostics().is_ok());
 }
+
+#[test]
+fn test_module_depth_limit() {
+    let mut state = State::new().with_config(Config::new().with_max_module_depth(100));
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let path = Path::new(vec![IdentifierId::from("a"); 10_000]);
+
+    let now = Instant::now();
+
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        path,
+        PathId::from("test.sr"),
+        "enum A {}",
+    );
+    let module = parse_result.module();
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+
+    assert!(now.elapsed() < Duration::from_secs(10));
+    assert!(module
+        .module_item_symbol_or_none(state.db(), IdentifierId::from("A"))
+        .is_none());
+    assert_eq!(state.diagnostics().len(), 1);
+    assert_eq!(state.diagnostics().all()[0].code.as_deref(), Some("E111"));
+}
+
+#[test]
+fn test_hir_storage() {
+    let mut state = State::new();
+    let source_code = "fun f() {\n    let x = 1;\n    let y = x;\n    y\n}\nfun g();\nstruct S {}";
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    let module = parse_result.module();
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+
+    let db = state.db();
+    let f = module.symbol(db, IdentifierId::from("f")).to_function();
+    let g = module.symbol(db, IdentifierId::from("g")).to_function();
+    let s = module.symbol(db, IdentifierId::from("S"));
+
+    let body = state.hir().get_function_body(f).unwrap();
+    assert_eq!(body.statements.len(), 2);
+    assert!(body.tail_expression.is_some());
+    assert!(state.hir().get_function_body(g).is_none());
+    assert!(matches!(
+        state.hir().get(s).map(AsRef::as_ref),
+        Some(stellar_hir::ModuleItem::Struct(_))
+    ));
+    assert_eq!(state.hir().len(), 3);
+}
+
+#[test]
+fn test_enum_items_order() {
+    let mut state = State::new();
+    let source_code = "pub enum E { C, A, C, B }";
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    let module = parse_result.module();
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+
+    let db = state.db();
+    let enum_ = module.symbol(db, IdentifierId::from("E")).to_enum();
+
+    assert_eq!(
+        enum_
+            .items_ordered(db)
+            .map(|item| item.name(db).id.to_string())
+            .collect::<Vec<_>>(),
+        ["C", "A", "B"]
+    );
+    assert_eq!(enum_.items_len(db), 3);
+    assert_eq!(
+        enum_.visibility(db),
+        Visibility::Public(Location {
+            filepath: DUMMY_PATH_ID,
+            start: ByteOffset(0),
+            end: ByteOffset(3)
+        })
+    );
+    assert_eq!(state.diagnostics().len(), 1);
+}
+
+/// A subscriber, that records names of created spans.
+#[derive(Default, Clone)]
+struct SpanRecorder(Arc<Mutex<Vec<&'static str>>>);
+
+impl Subscriber for SpanRecorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        let mut names = self.0.lock().unwrap();
+        names.push(span.metadata().name());
+
+        span::Id::from_u64(names.len() as u64)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+#[test]
+fn spans() {
+    let recorder = SpanRecorder::default();
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let mut state = State::new();
+        let source_code = "enum A { B }\nstruct C {}";
+
+        let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+        let parse_result = parse_module(
+            &mut state,
+            package,
+            DUMMY_IDENTIFIER_ID.into(),
+            DUMMY_PATH_ID,
+            source_code,
+        );
+        package.set_root_module(state.db_mut(), parse_result.module());
+
+        let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+        CollectDefinitions::run_all(&mut state, &hir);
+    });
+
+    let names = recorder.0.lock().unwrap();
+
+    assert!(names.contains(&"collect_definitions_in"));
+    assert!(names.contains(&"collect_definition_of_enum"));
+    assert!(names.contains(&"collect_definition_of_enum_item"));
+    assert!(names.contains(&"collect_definition_of_struct"));
+}
+
+#[test]
+fn default_parameters() {
+    let mut state = State::new();
+    let source_code = "fun greet(name: String, punctuation: String = \"!\") {}
+fun add(a: int32, b: int32) {}";
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        PathId::from("test.sr"),
+        source_code,
+    );
+    let module = parse_result.module();
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+
+    let default_parameters = |name: &str| {
+        module
+            .symbol(state.db(), IdentifierId::from(name))
+            .to_function()
+            .default_parameters(state.db())
+    };
+
+    assert_eq!(default_parameters("greet"), 1);
+    assert_eq!(default_parameters("add"), 0);
+}
@@ -0,0 +1,239 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{symbol::SymbolKind, PackageData, State};
+use stellar_filesystem::location::ByteOffset;
+use stellar_interner::{IdentifierId, PathId, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::resolution::{
+    collect_definitions::CollectDefinitions,
+    completion::{completions_at, CompletionItem},
+    resolve_imports::ResolveImports,
+};
+
+/// Items of the package, that are completed in tests.
+const GEOMETRY: &str = "import app.geometry.Point;
+
+module geometry {
+    pub struct Point {
+        pub x: int32,
+        pub y: int32,
+        secret: int32,
+
+        pub fun length(self): float64 {}
+        pub fun origin(): Point {}
+        fun normalize(self) {}
+    }
+
+    pub enum Shape { Circle(float64), Square(float64) }
+
+    struct Internal {}
+}
+";
+
+/// Marks the completion position in tests.
+const CURSOR: &str = "<|>";
+
+/// Analyzes a package `app`, which root module consists of [`GEOMETRY`] and
+/// `saved`, then edits the module to consist of [`GEOMETRY`] and `edited`,
+/// and completes names at [`CURSOR`] in it.
+fn completions_after_edit(saved: &str, edited: &str) -> Vec<CompletionItem> {
+    let saved = format!("{GEOMETRY}{saved}");
+    let edited = format!("{GEOMETRY}{edited}");
+    let offset = edited.find(CURSOR).unwrap();
+
+    let mut state = State::new();
+    let filepath = PathId::from("app/package.sr");
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("app"), DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("app").into(),
+        filepath,
+        &saved,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    ResolveImports::run_all(&mut state, &hir);
+
+    state
+        .source_map_mut()
+        .set_override(filepath, edited.replace(CURSOR, ""));
+
+    completions_at(&mut state, &hir, filepath, ByteOffset(offset))
+}
+
+/// Completes names at [`CURSOR`] in a source code, that is not edited after
+/// the analysis.
+fn completions(source_code: &str) -> Vec<CompletionItem> {
+    completions_after_edit(&source_code.replace(CURSOR, ""), source_code)
+}
+
+fn labels_of(items: Vec<CompletionItem>) -> Vec<String> {
+    items.into_iter().map(|item| item.label).collect()
+}
+
+fn labels(source_code: &str) -> Vec<String> {
+    labels_of(completions(source_code))
+}
+
+#[test]
+fn fields_and_methods_after_dangling_dot() {
+    assert_eq!(
+        labels(
+            "fun main(p: geometry.Point) {
+    p.<|>
+    let q = 1;
+}"
+        ),
+        ["length", "x", "y"]
+    );
+    assert_eq!(
+        labels(
+            "fun main(p: geometry.Point) {
+    p.le<|>
+}"
+        ),
+        ["length"]
+    );
+}
+
+#[test]
+fn private_members_of_self() {
+    let saved = "struct Counter {
+    value: int32,
+
+    fun increment(self) {}
+    fun reset(self) {}
+}";
+    let edited = "struct Counter {
+    value: int32,
+
+    fun increment(self) { self.<|> }
+    fun reset(self) {}
+}";
+
+    assert_eq!(
+        labels_of(completions_after_edit(saved, edited)),
+        ["increment", "reset", "value"]
+    );
+}
+
+#[test]
+fn details_of_members() {
+    let items = completions(
+        "fun main(p: geometry.Point) {
+    p.<|>
+}",
+    );
+
+    assert_eq!(
+        items[0],
+        CompletionItem {
+            label: "length".to_owned(),
+            kind: SymbolKind::Method,
+            detail: "fun length(self): float64".to_owned(),
+        }
+    );
+    assert_eq!(
+        items[1],
+        CompletionItem {
+            label: "x".to_owned(),
+            kind: SymbolKind::Field,
+            detail: "int32".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn module_and_enum_items() {
+    assert_eq!(
+        labels(
+            "fun main() {
+    let shape = geometry.<|>
+}"
+        ),
+        ["Internal", "Point", "Shape"]
+    );
+    assert_eq!(
+        labels(
+            "fun main() {
+    let shape = geometry.Shape.<|>;
+}"
+        ),
+        ["Circle", "Square"]
+    );
+}
+
+#[test]
+fn locals_parameters_and_module_symbols() {
+    assert_eq!(
+        labels(
+            "fun distance(from: Point, to: Point): float64 {
+    let dx = 1;
+    {
+        let hidden = 2;
+    }
+    let mut dy = 2;
+    <|>
+}"
+        ),
+        ["Point", "distance", "dx", "dy", "from", "geometry", "to"]
+    );
+    assert_eq!(
+        labels(
+            "fun distance(from: Point, to: Point): float64 {
+    let dx = 1;
+    d<|>
+}"
+        ),
+        ["distance", "dx"]
+    );
+}
+
+#[test]
+fn let_statement_value() {
+    let saved = "fun distance(from: Point, to: Point): float64 {
+    let dx = 1;
+}";
+    let edited = "fun distance(from: Point, to: Point): float64 {
+    let dx = 1;
+    let dy = <|>
+}";
+
+    assert_eq!(
+        labels_of(completions_after_edit(saved, edited)),
+        ["Point", "distance", "dx", "from", "geometry", "to"]
+    );
+}
+
+#[test]
+fn types_and_generic_parameters() {
+    assert_eq!(
+        labels("fun scale[Unit](value: <|>) {}"),
+        [
+            "List", "Point", "String", "Unit", "bool", "char", "float32", "float64", "int16",
+            "int32", "int64", "int8", "uint16", "uint32", "uint64", "uint8"
+        ]
+    );
+    assert_eq!(
+        labels(
+            "fun main() {
+    let shape: geometry.<|> = 1;
+}"
+        ),
+        ["Internal", "Point", "Shape"]
+    );
+}
+
+#[test]
+fn import_paths() {
+    assert_eq!(labels("import <|>"), ["app"]);
+    assert_eq!(labels("import app.geo<|>"), ["geometry"]);
+    assert_eq!(
+        labels("import app.geometry.<|>"),
+        ["Internal", "Point", "Shape"]
+    );
+}
@@ -0,0 +1,110 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{ModuleId, PackageData, State};
+use stellar_fx_hash::FxHashSet;
+use stellar_interner::{IdentifierId, PathId, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::resolution::{
+    collect_definitions::CollectDefinitions, resolve_imports::ResolveImports,
+};
+
+/// Modules of the package `p`, where `a` imports from `b` and `b` imports
+/// from `c`.
+struct Modules {
+    a: ModuleId,
+    b: ModuleId,
+    c: ModuleId,
+}
+
+fn check() -> (State, Modules) {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("p"), DUMMY_PATH_ID);
+    let root = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("p").into(),
+        PathId::from("p/package.sr"),
+        "",
+    );
+    package.set_root_module(state.db_mut(), root.module());
+
+    let mut parse_submodule = |name: &str, source: &str| {
+        let result = parse_module(
+            &mut state,
+            package,
+            IdentifierId::from(name).into(),
+            PathId::from(format!("p/{name}.sr").as_str()),
+            source,
+        );
+        root.module().add_submodule(state.db_mut(), result.module());
+
+        result
+    };
+
+    let a = parse_submodule("a", "import p.b.B;");
+    let b = parse_submodule("b", "import p.c.C;\n\npub struct B {}");
+    let c = parse_submodule("c", "pub struct C {}");
+
+    let modules = Modules {
+        a: a.module(),
+        b: b.module(),
+        c: c.module(),
+    };
+
+    let hir = LowerToHir::run_all(&mut state, vec![root, a, b, c]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    ResolveImports::run_all(&mut state, &hir);
+
+    assert!(state.diagnostics().is_ok());
+
+    (state, modules)
+}
+
+fn set(modules: impl IntoIterator<Item = ModuleId>) -> FxHashSet<ModuleId> {
+    modules.into_iter().collect()
+}
+
+#[test]
+fn dependents_of() {
+    let (state, modules) = check();
+    let db = state.db();
+
+    assert_eq!(
+        set(db.dependents_of(modules.c)),
+        set([modules.a, modules.b])
+    );
+    assert_eq!(db.dependents_of(modules.b), [modules.a]);
+    assert!(db.dependents_of(modules.a).is_empty());
+}
+
+#[test]
+fn invalidate_imported_module() {
+    let (mut state, modules) = check();
+
+    assert_eq!(
+        set(state.invalidate_module(modules.c)),
+        set([modules.a, modules.b, modules.c])
+    );
+
+    let db = state.db();
+
+    assert!(modules.c.module_item_symbols(db).is_empty());
+    assert!(modules.b.resolved_imports(db).is_empty());
+    assert!(modules.a.resolved_imports(db).is_empty());
+    assert!(modules.c.dependents(db).is_empty());
+}
+
+#[test]
+fn invalidate_importing_module() {
+    let (mut state, modules) = check();
+
+    assert_eq!(state.invalidate_module(modules.a), [modules.a]);
+
+    let db = state.db();
+
+    assert!(modules.a.resolved_imports(db).is_empty());
+    assert!(!modules.b.resolved_imports(db).is_empty());
+    assert!(modules.b.dependents(db).is_empty());
+    assert_eq!(db.dependents_of(modules.c), [modules.b]);
+}
@@ -1,2 +1,10 @@
+mod check_dead_code;
+mod check_naming_conventions;
+mod check_type_paths;
 mod collect_definitions;
+mod completion;
+mod invalidation;
+mod references;
 mod resolve_imports;
+mod resolve_path;
+mod scope;
@@ -0,0 +1,78 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_interner::{IdentifierId, PathId, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::resolution::{
+    check_type_paths::CheckTypePaths, collect_definitions::CollectDefinitions,
+    resolve_imports::ResolveImports,
+};
+
+const SUBMODULE: &str = "pub struct Point {}
+
+struct Line { start: Point }
+
+fun draw(point: Point) {}";
+
+const ROOT: &str = "import a.b.Point;";
+
+/// Returns the location of the `n`-th occurrence of `Point` in a given
+/// source code.
+fn point_location(filepath: PathId, source: &str, n: usize) -> Location {
+    let (start, _) = source.match_indices("Point").nth(n).unwrap();
+
+    Location {
+        filepath,
+        start: ByteOffset(start),
+        end: ByteOffset(start + "Point".len()),
+    }
+}
+
+#[test]
+fn references_of_struct() {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let submodule_filepath = PathId::from("a/b.sr");
+    let submodule = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("b").into(),
+        submodule_filepath,
+        SUBMODULE,
+    );
+    let root_filepath = PathId::from("a/package.sr");
+    let root = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("a").into(),
+        root_filepath,
+        ROOT,
+    );
+
+    package.set_root_module(state.db_mut(), root.module());
+    root.module()
+        .add_submodule(state.db_mut(), submodule.module());
+
+    let submodule_id = submodule.module();
+    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    ResolveImports::run_all(&mut state, &hir);
+    CheckTypePaths::run_all(&mut state, &hir);
+
+    assert!(state.diagnostics().is_ok());
+
+    let point = submodule_id.module_item_symbol(state.db(), IdentifierId::from("Point"));
+    let mut references = state.references_of(point).to_vec();
+    references.sort_by_key(|location| (location.filepath != root_filepath, location.start));
+
+    assert_eq!(
+        references,
+        [
+            point_location(root_filepath, ROOT, 0),
+            point_location(submodule_filepath, SUBMODULE, 1),
+            point_location(submodule_filepath, SUBMODULE, 2),
+        ]
+    );
+}
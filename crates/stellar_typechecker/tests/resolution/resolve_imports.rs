@@ -1,5 +1,5 @@
 use stellar_ast_lowering::LowerToHir;
-use stellar_database::{PackageData, State};
+use stellar_database::{ModuleId, PackageData, State, Symbol};
 use stellar_interner::{IdentifierId, PathId, DUMMY_PATH_ID};
 use stellar_parser::parse_module;
 use stellar_typechecker::{
@@ -15,14 +15,14 @@ fn resolve_submodule_import_ok() {
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.b;",
     );
@@ -47,14 +47,14 @@ fn resolve_submodule_import_err() {
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.c;",
     );
@@ -78,14 +78,14 @@ fn resolve_module_item_ok() {
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "fun foo() {}",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.b.foo;",
     );
@@ -110,14 +110,14 @@ fn resolve_module_item_err1() {
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "fun foo() {}",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.b.foo;
 import a.b.foo2;",
@@ -143,14 +143,14 @@ fn resolve_module_item_err2() {
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "fun foo() {}",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.c.foo;",
     );
@@ -175,14 +175,14 @@ fn resolve_enum_item_ok() {
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "enum Result[T, E] { Ok(T), Err(E) }",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.b.Result;
 import a.b.Result.Ok;
@@ -209,14 +209,14 @@ fn resolve_enum_item_err1() {
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "enum Result[T, E] { Ok(T), Err(E) }",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.b.Result.Foo;",
     );
@@ -241,14 +241,14 @@ fn resolve_enum_item_err2() {
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "enum Result[T, E] { Ok(T), Err(E) }",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.b.Result.Ok.Foo;",
     );
@@ -273,14 +273,14 @@ fn resolve_name_in_module_items_except_enums() {
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "fun foo() {}",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.b.foo.foo;",
     );
@@ -305,14 +305,14 @@ fn importing_package() {
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "import a;",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "",
     );
@@ -328,3 +328,181 @@ fn importing_package() {
 
     assert!(state.diagnostics().is_fatal());
 }
+
+/// Resolves imports in package `a` with a root module and a submodule `b`,
+/// returns the state and the root module.
+fn resolve_imports_in_root(root_source: &str, submodule_source: &str) -> (State, ModuleId) {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let submodule = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("b").into(),
+        PathId::from("a/b.sr"),
+        submodule_source,
+    );
+    let root = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("a").into(),
+        PathId::from("a/package.sr"),
+        root_source,
+    );
+    let root_module = root.module();
+
+    package.set_root_module(state.db_mut(), root_module);
+    root_module.add_submodule(state.db_mut(), submodule.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    ResolveImports::run_all(&mut state, &hir);
+
+    (state, root_module)
+}
+
+#[test]
+fn import_shadowed_by_local_definition() {
+    let (state, root) = resolve_imports_in_root("import a.b.Foo;\nstruct Foo {}", "struct Foo {}");
+
+    assert!(state.diagnostics().is_ok());
+    assert_eq!(state.diagnostics().len(), 1);
+    assert_eq!(state.diagnostics().all()[0].code.as_deref(), Some("W004"));
+
+    let Symbol::Struct(struct_) = root.symbol(state.db(), IdentifierId::from("Foo")) else {
+        panic!("expected struct");
+    };
+    assert_eq!(struct_.signature(state.db()).module(state.db()), root);
+}
+
+#[test]
+fn aliased_import_is_not_shadowed() {
+    let (state, root) =
+        resolve_imports_in_root("import a.b.Foo as BFoo;\nstruct Foo {}", "struct Foo {}");
+
+    assert!(state.diagnostics().is_empty());
+
+    let Symbol::Struct(local) = root.symbol(state.db(), IdentifierId::from("Foo")) else {
+        panic!("expected struct");
+    };
+    let Symbol::Struct(imported) = root.symbol(state.db(), IdentifierId::from("BFoo")) else {
+        panic!("expected struct");
+    };
+
+    assert_eq!(local.signature(state.db()).module(state.db()), root);
+    assert_ne!(imported.signature(state.db()).module(state.db()), root);
+}
+
+#[test]
+fn import_without_local_definition() {
+    let (state, root) = resolve_imports_in_root("import a.b.Foo;", "struct Foo {}");
+
+    assert!(state.diagnostics().is_empty());
+
+    let Symbol::Struct(imported) = root.symbol(state.db(), IdentifierId::from("Foo")) else {
+        panic!("expected struct");
+    };
+    assert_ne!(imported.signature(state.db()).module(state.db()), root);
+}
+
+#[test]
+fn import_of_deprecated_struct() {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let submodule = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("b").into(),
+        PathId::from("a/b.sr"),
+        "#[deprecated] struct Foo {}",
+    );
+    let root = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("a").into(),
+        PathId::from("a/package.sr"),
+        "import a.b.Foo;",
+    );
+
+    package.set_root_module(state.db_mut(), root.module());
+    root.module()
+        .add_submodule(state.db_mut(), submodule.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    ResolveImports::run_all(&mut state, &hir);
+
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code.as_deref(), Some("W007"));
+    assert_eq!(diagnostics[0].message, "use of deprecated struct `Foo`");
+    assert_eq!(
+        diagnostics[0].labels[1].location.filepath,
+        PathId::from("a/b.sr")
+    );
+}
+
+#[test]
+fn duplicate_import() {
+    let source_code = "import a.b.Foo;\nimport a.b.Foo as Foo;";
+    let (state, root) = resolve_imports_in_root(source_code, "struct Foo {}");
+    let diagnostics = state.diagnostics().all();
+
+    assert!(state.diagnostics().is_ok());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code.as_deref(), Some("W010"));
+    assert_eq!(diagnostics[0].message, "`Foo` is imported multiple times");
+
+    let label = |idx: usize| {
+        let location = diagnostics[0].labels[idx].location;
+        &source_code[location.start.0..location.end.0]
+    };
+
+    assert_eq!(label(0), "import a.b.Foo as Foo;");
+    assert_eq!(label(1), "import a.b.Foo;");
+    assert!(root
+        .resolved_imports(state.db())
+        .contains_key(&IdentifierId::from("Foo")));
+}
+
+#[test]
+fn conflicting_imports() {
+    let source_code = "import a.b.Foo;\nimport a.b.Bar as Foo;";
+    let (state, root) = resolve_imports_in_root(source_code, "struct Foo {}\nstruct Bar {}");
+    let diagnostics = state.diagnostics().all();
+
+    assert!(state.diagnostics().is_fatal());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code.as_deref(), Some("E118"));
+    assert_eq!(
+        diagnostics[0].message,
+        "name `Foo` is imported multiple times with different targets"
+    );
+
+    let label = |idx: usize| {
+        let location = diagnostics[0].labels[idx].location;
+        &source_code[location.start.0..location.end.0]
+    };
+
+    assert_eq!(label(0), "import a.b.Bar as Foo;");
+    assert_eq!(label(1), "import a.b.Foo;");
+
+    // The first import is kept.
+    let imported = root.symbol(state.db(), IdentifierId::from("Foo"));
+    assert_eq!(imported.name(state.db()).id, IdentifierId::from("Foo"));
+}
+
+#[test]
+fn aliased_imports_of_the_same_name() {
+    let (state, root) = resolve_imports_in_root(
+        "import a.b.Foo;\nimport a.b.Foo as BFoo;\nimport a.b.Bar as Baz;",
+        "struct Foo {}\nstruct Bar {}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+    assert_eq!(root.resolved_imports(state.db()).len(), 3);
+}
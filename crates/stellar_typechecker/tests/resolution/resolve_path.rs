@@ -0,0 +1,285 @@
+use stellar_ast::IdentifierAST;
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{
+    ModuleId, PackageData, Path, ResolutionError, ResolutionErrorKind, State, Symbol,
+};
+use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_interner::{IdentifierId, PathId, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::resolution::{
+    collect_definitions::CollectDefinitions, resolve_imports::ResolveImports,
+};
+
+/// Creates a package `a` with a root module and a submodule `a.b`.
+fn package(root_source_code: &str, submodule_source_code: &str) -> (State, ModuleId, ModuleId) {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let submodule = parse_module(
+        &mut state,
+        package,
+        Path::new(vec![IdentifierId::from("a"), IdentifierId::from("b")]),
+        PathId::from("a/b.sr"),
+        submodule_source_code,
+    );
+    let root = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("a").into(),
+        PathId::from("a/package.sr"),
+        root_source_code,
+    );
+    let (root_module, submodule_module) = (root.module(), submodule.module());
+
+    package.set_root_module(state.db_mut(), root_module);
+    root_module.add_submodule(state.db_mut(), submodule_module);
+
+    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    ResolveImports::run_all(&mut state, &hir);
+
+    (state, root_module, submodule_module)
+}
+
+/// Creates a path with segments located one after another.
+fn path(segments: &[&str]) -> Vec<IdentifierAST> {
+    let mut offset = 0;
+
+    segments
+        .iter()
+        .map(|segment| {
+            let identifier = IdentifierAST {
+                location: Location {
+                    filepath: DUMMY_PATH_ID,
+                    start: ByteOffset(offset),
+                    end: ByteOffset(offset + segment.len()),
+                },
+                id: IdentifierId::from(*segment),
+            };
+
+            offset += segment.len() + 1;
+            identifier
+        })
+        .collect()
+}
+
+#[test]
+fn resolve_through_submodule() {
+    let (state, root, submodule) = package("", "fun foo() {}");
+    let db = state.db();
+
+    let symbol = db.resolve_path(root, &path(&["b", "foo"])).unwrap();
+
+    assert_eq!(symbol, submodule.symbol(db, IdentifierId::from("foo")));
+    assert_eq!(
+        db.resolve_path(root, &path(&["a", "b"])),
+        Ok(Symbol::Module(submodule))
+    );
+}
+
+#[test]
+fn resolve_through_inline_module() {
+    let (state, root, _) = package("module inline { pub struct Struct {} }", "");
+    let db = state.db();
+
+    let inline = root.submodule(db, IdentifierId::from("inline")).unwrap();
+
+    assert_eq!(inline.parent(db), Some(root));
+    assert_eq!(inline.filepath(db), root.filepath(db));
+    assert_eq!(
+        db.resolve_path(root, &path(&["a", "inline", "Struct"])),
+        Ok(inline.symbol(db, IdentifierId::from("Struct")))
+    );
+}
+
+#[test]
+fn resolve_through_nested_inline_modules() {
+    let (state, root, _) = package("module x { pub module y { pub struct S {} } }", "");
+    let db = state.db();
+
+    let x = root.submodule(db, IdentifierId::from("x")).unwrap();
+    let y = x.submodule(db, IdentifierId::from("y")).unwrap();
+
+    assert_eq!(y.parent(db), Some(x));
+    assert_eq!(
+        db.resolve_path(root, &path(&["x", "y", "S"])),
+        Ok(y.symbol(db, IdentifierId::from("S")))
+    );
+}
+
+#[test]
+fn resolve_enum_item() {
+    let (state, root, _) = package("enum Color { Red, Green }", "");
+    let db = state.db();
+
+    let color = root.symbol(db, IdentifierId::from("Color")).to_enum();
+    let red = color.item(db, IdentifierId::from("Red")).unwrap();
+
+    assert_eq!(
+        db.resolve_path(root, &path(&["Color", "Red"])),
+        Ok(Symbol::EnumItem(red))
+    );
+}
+
+#[test]
+fn resolve_enum_item_through_type_alias() {
+    let (state, root, submodule) = package(
+        "enum Option[T] { Some(T), None }",
+        "type Opt[T] = a.Option[T];\ntype Maybe[T] = Opt[T];",
+    );
+    let db = state.db();
+
+    let option = root.symbol(db, IdentifierId::from("Option")).to_enum();
+    let some = Ok(Symbol::EnumItem(
+        option.item(db, IdentifierId::from("Some")).unwrap(),
+    ));
+
+    assert_eq!(db.resolve_path(submodule, &path(&["Opt", "Some"])), some);
+    assert_eq!(db.resolve_path(root, &path(&["b", "Maybe", "Some"])), some);
+
+    let Symbol::TypeAlias(maybe) = submodule.symbol(db, IdentifierId::from("Maybe")) else {
+        panic!("expected a type alias");
+    };
+    let (chain, expanded) = db.expand_type_alias(maybe);
+
+    assert_eq!(expanded, Some(Symbol::Enum(option)));
+    assert_eq!(
+        chain
+            .iter()
+            .map(|alias| alias.signature(db).name(db).id.to_string())
+            .collect::<Vec<_>>(),
+        ["Maybe", "Opt"]
+    );
+
+    let nome = db
+        .resolve_path(submodule, &path(&["Maybe", "Nome"]))
+        .unwrap_err();
+
+    assert_eq!(nome.reason, ResolutionErrorKind::NotFoundInNamespace);
+    assert_eq!(nome.searched_in, Symbol::Enum(option));
+    assert_eq!(nome.alias, Some(maybe));
+}
+
+#[test]
+fn resolve_through_reexport() {
+    let (state, root, _) = package("enum Option[T] { Some(T), None }", "import a.Option;");
+    let db = state.db();
+
+    let option = root.symbol(db, IdentifierId::from("Option")).to_enum();
+
+    assert_eq!(
+        db.resolve_path(root, &path(&["b", "Option", "Some"])),
+        Ok(Symbol::EnumItem(
+            option.item(db, IdentifierId::from("Some")).unwrap()
+        ))
+    );
+}
+
+#[test]
+fn cyclic_type_aliases() {
+    let (state, root, _) = package("type A = B;\ntype B = A;", "");
+    let db = state.db();
+
+    let Symbol::TypeAlias(a) = root.symbol(db, IdentifierId::from("A")) else {
+        panic!("expected a type alias");
+    };
+
+    assert_eq!(db.expand_type_alias(a).0.len(), 2);
+    assert_eq!(db.expand_type_alias(a).1, None);
+    assert_eq!(
+        db.resolve_path(root, &path(&["A", "x"]))
+            .unwrap_err()
+            .reason,
+        ResolutionErrorKind::NotANamespace
+    );
+}
+
+#[test]
+fn failure_at_second_segment() {
+    let (state, root, submodule) = package("", "fun foo() {}");
+    let path = path(&["b", "bar", "baz"]);
+
+    assert_eq!(
+        state.db().resolve_path(root, &path),
+        Err(Box::new(ResolutionError {
+            failed_segment: path[1],
+            searched_in: Symbol::Module(submodule),
+            previous_segment: Some(path[0]),
+            alias: None,
+            reason: ResolutionErrorKind::NotFoundInNamespace,
+        }))
+    );
+}
+
+#[test]
+fn failure_in_not_a_namespace() {
+    let (state, root, _) = package("struct Foo {}\nenum Color { Red }", "");
+    let db = state.db();
+
+    let foo_path = path(&["Foo", "bar"]);
+    let foo = db.resolve_path(root, &foo_path).unwrap_err();
+
+    assert_eq!(foo.failed_segment, foo_path[1]);
+    assert_eq!(foo.reason, ResolutionErrorKind::NotANamespace);
+    assert!(foo.searched_in.is_struct());
+
+    let red_path = path(&["Color", "Red", "x"]);
+    let red = db.resolve_path(root, &red_path).unwrap_err();
+
+    assert_eq!(red.failed_segment, red_path[2]);
+    assert_eq!(red.reason, ResolutionErrorKind::NotANamespace);
+
+    let unknown_path = path(&["unknown"]);
+
+    assert_eq!(
+        db.resolve_path(root, &unknown_path).unwrap_err().reason,
+        ResolutionErrorKind::NotInScope
+    );
+}
+
+#[test]
+fn private_item_of_dependency() {
+    let (mut state, _, submodule) = package(
+        "pub fun baz() {}",
+        "import a.baz;\n\npub fun foo() {}\n\nfun bar() {}",
+    );
+    let dependency = submodule.package();
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("c"), DUMMY_PATH_ID);
+    let root = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("c").into(),
+        PathId::from("c/package.sr"),
+        "",
+    );
+    let root_module = root.module();
+
+    package.set_root_module(state.db_mut(), root_module);
+    package.add_dependency(state.db_mut(), IdentifierId::from("a"), dependency);
+
+    let db = state.db();
+
+    assert_eq!(
+        db.resolve_path(root_module, &path(&["a", "b", "foo"])),
+        Ok(submodule.symbol(db, IdentifierId::from("foo")))
+    );
+    assert_eq!(
+        db.resolve_path(root_module, &path(&["a", "b", "bar"]))
+            .map_err(|error| error.reason),
+        Err(ResolutionErrorKind::PrivateItem)
+    );
+
+    // Imports are private, so they are not re-exported to other packages.
+    assert_eq!(
+        db.resolve_path(root_module, &path(&["a", "b", "baz"]))
+            .map_err(|error| error.reason),
+        Err(ResolutionErrorKind::PrivateItem)
+    );
+
+    // Private items are accessible within their package.
+    assert!(db
+        .resolve_path(submodule, &path(&["a", "b", "bar"]))
+        .is_ok());
+}
@@ -0,0 +1,145 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State, Symbol};
+use stellar_filesystem::location::ByteOffset;
+use stellar_interner::{IdentifierId, PathId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::resolution::{
+    collect_definitions::CollectDefinitions,
+    scope::{scopes_for_function, BindingKind, ScopeTree},
+};
+
+fn scopes(source_code: &str) -> ScopeTree {
+    let mut state = State::new();
+    let filepath = PathId::from("test.sr");
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        filepath,
+        source_code,
+    );
+    let module = parse_result.module();
+    package.set_root_module(state.db_mut(), module);
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+
+    assert!(state.diagnostics().is_ok());
+
+    let Symbol::Function(function) = module.symbol(state.db(), IdentifierId::from("main")) else {
+        panic!("expected function");
+    };
+
+    scopes_for_function(state.db(), &hir, function).unwrap()
+}
+
+fn offset_of(source_code: &str, pattern: &str) -> ByteOffset {
+    ByteOffset(source_code.find(pattern).unwrap())
+}
+
+fn names_at(tree: &ScopeTree, offset: ByteOffset) -> Vec<String> {
+    tree.bindings_at(offset)
+        .into_iter()
+        .map(|binding| binding.name.id.to_string())
+        .collect()
+}
+
+#[test]
+fn nested_blocks_shadowing() {
+    let source_code = "fun main(a: int32) {
+    let b = a;
+    {
+        let a = b;
+        inner(a);
+    }
+    outer(a);
+}";
+    let tree = scopes(source_code);
+
+    let inner = tree
+        .innermost_scope_at(offset_of(source_code, "inner"))
+        .unwrap();
+    let outer = tree
+        .innermost_scope_at(offset_of(source_code, "outer"))
+        .unwrap();
+
+    assert_eq!(outer, ScopeTree::ROOT);
+    assert_ne!(inner, outer);
+
+    let (binding, distance) = tree
+        .lookup_with_distance(inner, IdentifierId::from("a"))
+        .unwrap();
+    assert_eq!(binding.kind, BindingKind::Local);
+    assert_eq!(distance, 0);
+
+    let (binding, distance) = tree
+        .lookup_with_distance(inner, IdentifierId::from("b"))
+        .unwrap();
+    assert_eq!(binding.kind, BindingKind::Local);
+    assert_eq!(distance, 1);
+
+    let binding = tree.lookup(outer, IdentifierId::from("a")).unwrap();
+    assert_eq!(binding.kind, BindingKind::Parameter);
+}
+
+#[test]
+fn match_arm_binding() {
+    let source_code = "fun main(x: Option[int32]) {
+    match x {
+        Some(value) -> first(value),
+        _ -> second(),
+    };
+}";
+    let tree = scopes(source_code);
+
+    assert_eq!(
+        names_at(&tree, offset_of(source_code, "first")),
+        ["x", "value"]
+    );
+    assert_eq!(names_at(&tree, offset_of(source_code, "second")), ["x"]);
+}
+
+#[test]
+fn bindings_at_offset() {
+    let source_code = "fun main() {
+    let a = 1;
+    while a < 10 {
+        let b = 2;
+        inside();
+    }
+    outside();
+}";
+    let tree = scopes(source_code);
+
+    assert_eq!(names_at(&tree, offset_of(source_code, "let a")), [""; 0]);
+    assert_eq!(
+        names_at(&tree, offset_of(source_code, "inside")),
+        ["a", "b"]
+    );
+    assert_eq!(names_at(&tree, offset_of(source_code, "outside")), ["a"]);
+    assert_eq!(names_at(&tree, ByteOffset(source_code.len() + 10)), [""; 0]);
+}
+
+#[test]
+fn lambda_captures() {
+    let source_code = "fun main(a: int32) {
+    let f = |b| { a + b };
+}";
+    let tree = scopes(source_code);
+
+    let lambda = tree
+        .innermost_scope_at(offset_of(source_code, "a + b"))
+        .unwrap();
+
+    assert_eq!(
+        tree.lookup(lambda, IdentifierId::from("a")).unwrap().kind,
+        BindingKind::Captured
+    );
+    assert_eq!(
+        tree.lookup(lambda, IdentifierId::from("b")).unwrap().kind,
+        BindingKind::Parameter
+    );
+}
@@ -0,0 +1,93 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_interner::{IdentifierId, PathId, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::{
+    resolution::collect_definitions::CollectDefinitions,
+    signature_analysis::check_entry_point::CheckEntryPoint,
+};
+
+fn check(source_code: &str) -> State {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("app"), DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("app").into(),
+        PathId::from("app/src/package.sr"),
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    CheckEntryPoint::run(&mut state, package);
+
+    state
+}
+
+fn messages(state: &State) -> Vec<&str> {
+    state
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect()
+}
+
+#[test]
+fn valid_main() {
+    let state = check("pub fun main() {}");
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn missing_main() {
+    let state = check("pub fun run() {}");
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(
+        messages(&state),
+        ["`main` function not found in the package `app` (`app/src/package.sr`)"]
+    );
+    assert_eq!(diagnostics[0].code.as_deref(), Some("E224"));
+    assert!(diagnostics[0].notes[0].contains("`--lib`"));
+}
+
+#[test]
+fn main_is_not_a_function() {
+    let state = check("pub struct main {}");
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(messages(&state), ["`main` is not a function"]);
+    assert_eq!(
+        diagnostics[0].labels[0].message,
+        "expected the entry point function, found a struct"
+    );
+    assert_eq!(diagnostics[0].labels[0].location.start.0, 11);
+}
+
+#[test]
+fn generic_main() {
+    let state = check("pub fun main[T]() {}");
+
+    assert_eq!(
+        messages(&state),
+        ["`main` function cannot have generic parameters"]
+    );
+}
+
+#[test]
+fn private_main_with_parameters() {
+    let state = check("fun main(args: List[String]) {}");
+
+    assert_eq!(
+        messages(&state),
+        [
+            "`main` function must be public",
+            "`main` function cannot have parameters"
+        ]
+    );
+}
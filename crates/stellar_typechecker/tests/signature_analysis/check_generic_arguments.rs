@@ -0,0 +1,143 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_filesystem::location::Location;
+use stellar_interner::{DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::{
+    resolution::collect_definitions::CollectDefinitions,
+    signature_analysis::check_generic_arguments::CheckGenericArguments,
+};
+
+fn check(source_code: &str) -> State {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    CheckGenericArguments::run_all(&mut state, &hir);
+
+    state
+}
+
+fn messages(state: &State) -> Vec<&str> {
+    state
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect()
+}
+
+fn label(source_code: &str, location: Location) -> &str {
+    &source_code[location.start.0..location.end.0]
+}
+
+#[test]
+fn correct_generic_arguments() {
+    let state = check(
+        "interface ToString {}
+
+struct Point implements ToString {}
+
+struct Map[K, V: ToString] {}
+struct Set[T = Point] {}
+
+fun foo[T](a: Map[String, Point], b: Set, c: Set[T], d: List[Map[T, Point]]) {}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn bound_not_satisfied() {
+    let source_code = "interface ToString {}
+
+struct BadType {}
+
+struct Map[K, V: ToString] {}
+
+fun foo(map: Map[String, BadType]) {}";
+    let state = check(source_code);
+
+    assert_eq!(
+        messages(&state),
+        ["the type `BadType` doesn't implement interface `ToString`"]
+    );
+
+    let diagnostic = state.diagnostics().all()[0];
+    let start = source_code.find("BadType]").unwrap();
+
+    assert_eq!(diagnostic.code.as_deref(), Some("E255"));
+    assert_eq!(diagnostic.labels[0].location.start.0, start);
+    assert_eq!(diagnostic.labels[0].location.end.0, start + "BadType".len());
+    assert_eq!(
+        label(source_code, diagnostic.labels[1].location),
+        "ToString"
+    );
+}
+
+#[test]
+fn bound_not_satisfied_in_nested_type() {
+    let source_code = "interface Hash {}
+
+enum Color { Red, Green }
+
+struct Set[T: Hash] {}
+
+fun foo(sets: List[Set[Color]]) {}";
+    let state = check(source_code);
+
+    assert_eq!(
+        messages(&state),
+        ["the type `Color` doesn't implement interface `Hash`"]
+    );
+    assert_eq!(
+        label(source_code, state.diagnostics().all()[0].labels[0].location),
+        "Color"
+    );
+}
+
+#[test]
+fn too_many_generic_arguments() {
+    let source_code = "struct Box[T] { value: T }
+
+fun foo(a: Box[int32, String]) {}";
+    let state = check(source_code);
+
+    assert_eq!(
+        messages(&state),
+        ["struct `Box` takes 1 generic argument but 2 were supplied"]
+    );
+
+    let diagnostic = state.diagnostics().all()[0];
+
+    assert_eq!(diagnostic.code.as_deref(), Some("E254"));
+    assert_eq!(label(source_code, diagnostic.labels[0].location), "String");
+    assert_eq!(label(source_code, diagnostic.labels[1].location), "Box");
+}
+
+#[test]
+fn too_few_generic_arguments() {
+    let source_code = "enum Result[T, E = String] { Ok(T), Err(E) }
+
+fun foo(a: Result) {}";
+    let state = check(source_code);
+
+    assert_eq!(
+        messages(&state),
+        ["enum `Result` takes from 1 to 2 generic arguments but 0 were supplied"]
+    );
+    assert_eq!(
+        label(source_code, state.diagnostics().all()[0].labels[0].location),
+        "Result"
+    );
+}
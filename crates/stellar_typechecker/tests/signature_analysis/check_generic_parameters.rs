@@ -0,0 +1,104 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_filesystem::location::Location;
+use stellar_interner::{DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::{
+    resolution::collect_definitions::CollectDefinitions,
+    signature_analysis::check_generic_parameters::CheckGenericParameters,
+};
+
+use crate::error_codes;
+
+fn check(source_code: &str) -> State {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    CheckGenericParameters::run_all(&mut state, &hir);
+
+    state
+}
+
+#[test]
+fn used_generic_parameters() {
+    let state = check(
+        "struct Box[T] { value: T }
+
+enum Option[T] { Some(T), None }
+
+interface Into[T] {
+    fun into(self): T;
+}
+
+type Pair[A, B] = (A, B);
+
+fun print[T](value: T) where T: ToString {}
+
+fun default[T](): T {}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn unused_generic_parameter() {
+    let source_code = "struct Foo[T] { x: int32 }
+
+fun bar[U]() {}";
+    let state = check(source_code);
+
+    assert_eq!(error_codes(&state), ["W008", "W008"]);
+
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+    let diagnostics = state.diagnostics().all();
+
+    assert_eq!(
+        diagnostics[0].message,
+        "generic parameter `T` is never used"
+    );
+    assert_eq!(label(diagnostics[0].labels[0].location), "T");
+    assert_eq!(
+        diagnostics[1].message,
+        "generic parameter `U` is never used"
+    );
+}
+
+#[test]
+fn underscore_generic_parameter() {
+    let state = check("struct Marker[_T] {}");
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn where_predicate_on_foreign_type() {
+    let source_code = "fun foo[T](x: T) where int32: ToString {}
+
+struct Wrapper[T] {
+    value: T,
+
+    fun show(self) where Self: ToString, T: ToString {}
+}";
+    let state = check(source_code);
+
+    assert_eq!(error_codes(&state), ["E238"]);
+
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(
+        label(state.diagnostics().all()[0].labels[0].location),
+        "int32"
+    );
+}
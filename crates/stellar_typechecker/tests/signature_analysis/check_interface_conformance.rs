@@ -0,0 +1,99 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_interner::{DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::{
+    resolution::collect_definitions::CollectDefinitions,
+    signature_analysis::check_interface_conformance::CheckInterfaceConformance,
+};
+
+use crate::error_codes;
+
+fn check(source_code: &str) -> State {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    CheckInterfaceConformance::run_all(&mut state, &hir);
+
+    state
+}
+
+#[test]
+fn conforming_type() {
+    let state = check(
+        "interface Into[T] {
+    fun into(self): T;
+    fun describe(self): String {}
+}
+
+struct Meters(float64) implements Into[float64] {
+    fun into(self): float64 {}
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn missing_method() {
+    let state = check(
+        "interface ToString {
+    fun to_string(self): String;
+    fun len(self): uint64;
+}
+
+struct Name implements ToString {
+    value: String,
+
+    fun len(self): uint64 {}
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E201"]);
+    assert_eq!(state.diagnostics().all()[0].labels.len(), 2);
+}
+
+#[test]
+fn wrong_parameter_count() {
+    let state = check(
+        "interface Add {
+    fun add(self, other: Self): Self;
+}
+
+struct Point implements Add {
+    x: int32,
+
+    fun add(self): Point {}
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E202"]);
+}
+
+#[test]
+fn wrong_return_type() {
+    let state = check(
+        "interface ToString {
+    fun to_string(self): String;
+}
+
+enum Color implements ToString {
+    Red,
+    fun to_string(self): char {}
+}",
+    );
+
+    assert_eq!(error_codes(&state), ["E202"]);
+}
@@ -0,0 +1,82 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_filesystem::location::Location;
+use stellar_interner::{DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::{
+    resolution::collect_definitions::CollectDefinitions,
+    signature_analysis::check_object_safety::CheckObjectSafety,
+};
+
+use crate::error_codes;
+
+fn check(source_code: &str) -> State {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    CheckObjectSafety::run_all(&mut state, &hir);
+
+    state
+}
+
+#[test]
+fn object_safe_interface() {
+    let state = check(
+        "interface ToString {
+    fun to_string(self): String;
+}
+
+fun print(value: dyn ToString) {
+    let values: List[dyn ToString] = [value];
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn interface_with_generic_method() {
+    let source_code = "interface Mapper {
+    fun map[T](self, value: T): T;
+}
+
+fun f(mapper: dyn Mapper) {}";
+    let state = check(source_code);
+
+    assert_eq!(error_codes(&state), ["E237"]);
+
+    let diagnostic = state.diagnostics().all()[0];
+    let label = |location: Location| &source_code[location.start.0..location.end.0];
+
+    assert_eq!(label(diagnostic.labels[0].location), "Mapper");
+    assert_eq!(
+        diagnostic.labels[0].location.start.0,
+        source_code.rfind("Mapper").unwrap()
+    );
+    assert_eq!(label(diagnostic.labels[1].location), "map");
+}
+
+#[test]
+fn interface_with_method_returning_self() {
+    let state = check(
+        "interface Clone {
+    fun clone(self): Self;
+}
+
+fun f(value: dyn Clone) {}",
+    );
+
+    assert_eq!(error_codes(&state), ["E237"]);
+}
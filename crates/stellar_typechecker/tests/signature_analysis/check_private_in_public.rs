@@ -0,0 +1,160 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{Config, PackageData, State};
+use stellar_diagnostics::diagnostic::Severity;
+use stellar_interner::{DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_parser::parse_module;
+use stellar_typechecker::{
+    resolution::{collect_definitions::CollectDefinitions, resolve_imports::ResolveImports},
+    signature_analysis::check_private_in_public::CheckPrivateInPublic,
+};
+
+fn check_with_config(source_code: &str, config: Config) -> State {
+    let mut state = State::new().with_config(config);
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    ResolveImports::run_all(&mut state, &hir);
+    CheckPrivateInPublic::run_all(&mut state, &hir);
+
+    state
+}
+
+fn check(source_code: &str) -> State {
+    check_with_config(source_code, Config::new())
+}
+
+fn messages(state: &State) -> Vec<&str> {
+    state
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect()
+}
+
+#[test]
+fn public_function_returning_private_struct() {
+    let source_code = "struct Secret {}
+
+pub fun reveal(): Secret {}";
+    let state = check(source_code);
+
+    assert_eq!(
+        messages(&state),
+        ["private struct `Secret` in public interface"]
+    );
+
+    let diagnostic = state.diagnostics().all()[0];
+
+    assert_eq!(diagnostic.severity, Severity::Warning);
+    assert_eq!(diagnostic.code.as_deref(), Some("W012"));
+    assert_eq!(&source_code[diagnostic.labels[0].location], "Secret");
+    assert_eq!(diagnostic.labels[0].location.start.0, 36);
+    assert_eq!(diagnostic.labels[1].location.start.0, 7);
+    assert_eq!(diagnostic.notes, ["note: the function `reveal` is public"]);
+}
+
+#[test]
+fn private_function_returning_private_struct() {
+    let state = check(
+        "struct Secret {}
+
+fun reveal(): Secret {}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn private_field_of_private_type() {
+    let state = check(
+        "struct Secret {}
+
+pub struct Vault {
+    secret: Secret,
+    pub label: List[String],
+}",
+    );
+
+    assert!(state.diagnostics().is_empty());
+}
+
+#[test]
+fn public_surface_of_public_items() {
+    let state = check(
+        "interface Hidden {}
+
+struct Secret {}
+
+pub struct Vault[T] {
+    pub secret: List[Secret],
+    pub value: T,
+
+    pub fun open(self, key: Secret): Option[T] {}
+    fun peek(self): Secret {}
+}
+
+pub enum Entry {
+    Sealed(Secret),
+}
+
+pub interface Lock {
+    fun unlock(self, key: dyn Hidden);
+}
+
+pub type Key = Secret;",
+    );
+
+    assert_eq!(
+        messages(&state),
+        [
+            "private struct `Secret` in public interface",
+            "private struct `Secret` in public interface",
+            "private struct `Secret` in public interface",
+            "private interface `Hidden` in public interface",
+            "private struct `Secret` in public interface",
+        ]
+    );
+}
+
+#[test]
+fn items_of_private_modules() {
+    let state = check(
+        "module inner {
+    pub struct Secret {}
+}
+
+module util {
+    pub fun reveal(): inner.Secret {}
+}
+
+pub fun reveal(): inner.Secret {}",
+    );
+
+    assert_eq!(
+        messages(&state),
+        ["private struct `Secret` in public interface"]
+    );
+}
+
+#[test]
+fn denied_private_in_public() {
+    let state = check_with_config(
+        "struct Secret {}
+
+pub fun reveal(): Secret {}",
+        Config::new().with_deny_private_in_public(true),
+    );
+
+    assert!(state.diagnostics().is_fatal());
+}
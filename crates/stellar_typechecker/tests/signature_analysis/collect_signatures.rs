@@ -1,6 +1,6 @@
 use stellar_ast_lowering::LowerToHir;
 use stellar_database::{PackageData, State};
-use stellar_interner::{IdentifierId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_interner::{IdentifierId, PathId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
 use stellar_parser::parse_module;
 use stellar_typechecker::{
     resolution::collect_definitions::CollectDefinitions,
@@ -16,7 +16,7 @@ fn simple_generic_parameter() {
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         DUMMY_PATH_ID,
         source_code,
     );
@@ -35,3 +35,108 @@ fn simple_generic_parameter() {
 
     assert!(state.diagnostics().is_ok());
 }
+
+#[test]
+fn generic_parameters_of_functions_and_interfaces() {
+    let mut state = State::new();
+    let source_code = "fun id[T](value: T): T { value }
+interface Into[T] {}";
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    let module = parse_result.module();
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    CollectSignatures::run_all(&mut state, &hir);
+
+    for name in ["id", "Into"] {
+        let scope = module
+            .symbol(state.db(), IdentifierId::from(name))
+            .signature(state.db())
+            .generic_parameter_scope(state.db());
+
+        assert!(scope.contains(state.db(), IdentifierId::from("T")));
+        assert_eq!(scope.parent_scope(state.db()), None);
+    }
+}
+
+#[test]
+fn enum_method_scope_is_child_of_enum_scope() {
+    let mut state = State::new();
+    let source_code = "enum Option[T] {
+    None,
+    Some(T),
+
+    fun map[M](self, f: fun(T): M): Option[M] {}
+}";
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        DUMMY_PATH_ID,
+        source_code,
+    );
+    let module = parse_result.module();
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    CollectSignatures::run_all(&mut state, &hir);
+
+    let db = state.db();
+    let enum_ = module.symbol(db, IdentifierId::from("Option")).to_enum();
+    let enum_scope = enum_.signature(db).generic_parameter_scope(db);
+    let method_scope = enum_
+        .method(db, IdentifierId::from("map"))
+        .unwrap()
+        .signature(db)
+        .generic_parameter_scope(db);
+
+    assert_eq!(method_scope.parent_scope(db), Some(enum_scope));
+    assert_eq!(method_scope.len(db), 2);
+    assert!(method_scope.contains(db, IdentifierId::from("T")));
+    assert!(!enum_scope.contains(db, IdentifierId::from("M")));
+}
+
+#[test]
+fn generic_type_alias() {
+    let mut state = State::new();
+    let filepath = PathId::from("test.sr");
+    let source_code = "type A = int8;\ntype Pair[T] = (T, T);";
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        filepath,
+        source_code,
+    );
+    let module = parse_result.module();
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    CollectSignatures::run_all(&mut state, &hir);
+
+    let db = state.db();
+    let alias = module.symbol(db, IdentifierId::from("A")).to_type_alias();
+    let pair = module
+        .symbol(db, IdentifierId::from("Pair"))
+        .to_type_alias();
+
+    assert_eq!(alias.generic_parameter_scope(db), None);
+    assert!(pair
+        .generic_parameter_scope(db)
+        .unwrap()
+        .contains(db, IdentifierId::from("T")));
+}
@@ -1 +1,7 @@
+mod check_entry_point;
+mod check_generic_arguments;
+mod check_generic_parameters;
+mod check_interface_conformance;
+mod check_object_safety;
+mod check_private_in_public;
 mod collect_signatures;